@@ -8,7 +8,11 @@
 //! - Health checks and monitoring
 
 use axum::{routing::get, Router};
-use rustassistant::{api::ApiConfig, init_db};
+use rustassistant::{
+    api::{ApiConfig, API_CONFIG_ENV},
+    init_db,
+    server::serve_until_shutdown,
+};
 use sqlx::PgPool;
 use std::net::SocketAddr;
 use tower_http::cors::{Any, CorsLayer};
@@ -69,8 +73,20 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Allow anonymous reads for GET requests
     api_config = api_config.allow_anonymous_read();
 
-    // Build API router
-    let api_router = api_config.build_router(db_pool.clone()).await;
+    // Auth and rate limits from a config file replace the settings above,
+    // and are re-read on SIGHUP
+    let api_config_path = std::env::var_os(API_CONFIG_ENV).map(std::path::PathBuf::from);
+    if let Some(path) = &api_config_path {
+        tracing::info!("📝 Loading API config from {}", path.display());
+        api_config = api_config.with_config_file(path)?;
+    }
+
+    // Build API router, keeping its job queue to drain on shutdown
+    let (api_router, job_queue, reload_handle) =
+        api_config.build_reloadable_router(db_pool.clone()).await;
+    if let Some(path) = api_config_path {
+        reload_handle.spawn_sighup_listener(path);
+    }
 
     // Build CORS layer
     let cors = CorsLayer::new()
@@ -113,9 +129,10 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Print example curl commands
     print_usage_examples(&host, port);
 
-    // Start server
+    // Start server; on Ctrl-C or SIGTERM, finish in-flight requests and
+    // save unfinished indexing jobs for the next start
     let listener = tokio::net::TcpListener::bind(addr).await?;
-    axum::serve(listener, app).await?;
+    serve_until_shutdown(listener, app, Some(job_queue)).await?;
 
     Ok(())
 }
@@ -239,6 +256,10 @@ fn print_usage_examples(host: &str, port: u16) {
     println!("  PORT              - Server port (default: 3000)");
     println!("  API_KEY           - API key for authentication (optional)");
     println!("  REQUIRE_AUTH      - Enable authentication (default: false)");
+    println!(
+        "  {} - API auth/rate-limit TOML, re-read on SIGHUP (optional)",
+        API_CONFIG_ENV
+    );
     println!("  RUST_LOG          - Log level (default: info)");
 
     println!("\n");
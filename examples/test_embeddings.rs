@@ -161,6 +161,7 @@ async fn test_models() -> anyhow::Result<()> {
             batch_size: 32,
             show_download_progress: false,
             cache_dir: None,
+            backend: Default::default(),
         };
 
         let generator = EmbeddingGenerator::new(config)?;
@@ -0,0 +1,23 @@
+-- Migration: 022_queue_dead_letter.sql
+-- Adds a per-item retry ceiling to `queue_items` and moves stage values
+-- to their snake_case spelling.
+-- Items whose retry_count reaches max_retries move to the 'dead_letter'
+-- stage and are no longer picked up by get_retriable_items.
+
+ALTER TABLE queue_items
+    ADD COLUMN IF NOT EXISTS max_retries INTEGER NOT NULL DEFAULT 3;
+
+-- Stages used to be written with their Debug spelling lowercased
+-- ('pendinganalysis'); queries now match the snake_case names, so
+-- convert rows still waiting in the old spelling.
+UPDATE queue_items SET stage = 'pending_analysis' WHERE stage = 'pendinganalysis';
+UPDATE queue_items SET stage = 'pending_tagging'  WHERE stage = 'pendingtagging';
+
+-- Dead-letter listing is a small, frequently-polled subset.
+CREATE INDEX IF NOT EXISTS idx_queue_dead_letter
+    ON queue_items (updated_at)
+    WHERE stage = 'dead_letter';
+
+-- ============================================================================
+-- Migration Complete
+-- ============================================================================
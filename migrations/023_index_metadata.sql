@@ -0,0 +1,15 @@
+-- Migration: 023_index_metadata.sql
+-- Key/value metadata about the document index. Records the embedding model
+-- and dimension the stored vectors were built with so a model switch can be
+-- detected at startup (IndexingConfig::needs_reindex) instead of silently
+-- returning incomparable search results.
+
+CREATE TABLE IF NOT EXISTS index_metadata (
+    key        TEXT   PRIMARY KEY,
+    value      TEXT   NOT NULL,
+    updated_at BIGINT NOT NULL
+);
+
+-- ============================================================================
+-- Migration Complete
+-- ============================================================================
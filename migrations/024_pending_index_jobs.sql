@@ -0,0 +1,15 @@
+-- Migration: 024_pending_index_jobs.sql
+-- Indexing jobs interrupted by a server shutdown. JobQueue::shutdown writes
+-- each unfinished job's remaining document IDs here; JobQueue::resume_pending
+-- re-submits and deletes them on the next startup.
+
+CREATE TABLE IF NOT EXISTS pending_index_jobs (
+    id            TEXT    PRIMARY KEY,
+    document_ids  TEXT    NOT NULL,  -- JSON array of document IDs
+    force_reindex BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at    BIGINT  NOT NULL
+);
+
+-- ============================================================================
+-- Migration Complete
+-- ============================================================================
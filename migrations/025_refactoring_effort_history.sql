@@ -0,0 +1,21 @@
+-- Migration: 025_refactoring_effort_history.sql
+-- Completed refactorings with their estimated and actual effort.
+-- RefactorAssistant::calibrated_estimate scales its heuristic estimate by the
+-- observed actual/estimated ratio for the same code smell type.
+
+CREATE TABLE IF NOT EXISTS refactoring_effort_history (
+    id              BIGSERIAL        PRIMARY KEY,
+    smell_type      TEXT             NOT NULL,  -- CodeSmellType, e.g. 'LongFunction'
+    estimated       TEXT             NOT NULL,  -- EffortEstimate the task was planned with
+    estimated_hours DOUBLE PRECISION NOT NULL,
+    actual_hours    DOUBLE PRECISION NOT NULL,
+    file_path       TEXT,
+    completed_at    BIGINT           NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_refactoring_effort_history_smell
+    ON refactoring_effort_history (smell_type);
+
+-- ============================================================================
+-- Migration Complete
+-- ============================================================================
@@ -0,0 +1,21 @@
+-- Migration: 026_conversations.sql
+-- Multi-turn Grok conversations. GrokClient::continue_conversation sends the
+-- stored history with each new question; the CLI resumes a conversation with
+-- `rustassistant ask --session <id>`.
+
+CREATE TABLE IF NOT EXISTS conversations (
+    id                 TEXT    PRIMARY KEY,
+    model              TEXT    NOT NULL,
+    messages           TEXT    NOT NULL,  -- JSON array of ConversationMessage
+    max_context_tokens BIGINT  NOT NULL,
+    trimmed_messages   BIGINT  NOT NULL DEFAULT 0,
+    created_at         BIGINT  NOT NULL,
+    updated_at         BIGINT  NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_conversations_updated
+    ON conversations (updated_at DESC);
+
+-- ============================================================================
+-- Migration Complete
+-- ============================================================================
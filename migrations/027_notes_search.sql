@@ -0,0 +1,17 @@
+-- Migration: 027_notes_search.sql
+-- Full-text search over notes. PostgreSQL's tsvector/GIN index stands in for
+-- SQLite FTS5: search_notes matches against search_vector and orders results
+-- by ts_rank. The column is generated, so it stays in sync on every insert
+-- and update (and goes with the row on delete) without triggers, and is
+-- filled for existing notes without touching their updated_at.
+
+ALTER TABLE notes ADD COLUMN IF NOT EXISTS search_vector tsvector GENERATED ALWAYS AS (
+    setweight(to_tsvector('english', COALESCE(title, '')), 'A') ||
+    setweight(to_tsvector('english', COALESCE(content, '')), 'B')
+) STORED;
+
+CREATE INDEX IF NOT EXISTS idx_notes_search_vector ON notes USING GIN (search_vector);
+
+-- ============================================================================
+-- Migration Complete
+-- ============================================================================
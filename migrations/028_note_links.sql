@@ -0,0 +1,20 @@
+-- Migration: 028_note_links.sql
+-- Wiki-style links between notes. Every `[[target]]` in a note's content is
+-- stored with the text as written; it resolves at query time to the note
+-- with that id or, failing that, the most recent note with that title.
+-- Links whose target resolves to nothing (e.g. a deleted note) are broken.
+
+CREATE TABLE IF NOT EXISTS note_links (
+    source_id  TEXT   NOT NULL REFERENCES notes(id) ON DELETE CASCADE,
+    target     TEXT   NOT NULL,  -- note id or title between [[ ]]
+    created_at BIGINT NOT NULL DEFAULT EXTRACT(EPOCH FROM NOW())::BIGINT,
+    PRIMARY KEY (source_id, target)
+);
+
+CREATE INDEX IF NOT EXISTS idx_note_links_target       ON note_links (target);
+CREATE INDEX IF NOT EXISTS idx_note_links_target_lower ON note_links (LOWER(target));
+CREATE INDEX IF NOT EXISTS idx_notes_title_lower       ON notes (LOWER(title));
+
+-- ============================================================================
+-- Migration Complete
+-- ============================================================================
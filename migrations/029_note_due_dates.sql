@@ -0,0 +1,11 @@
+-- Migration: 029_note_due_dates.sql
+-- Optional due date on notes, for using notes as a lightweight task list.
+-- list_overdue returns notes past due that aren't archived or deleted.
+
+ALTER TABLE notes ADD COLUMN IF NOT EXISTS due_at BIGINT;
+
+CREATE INDEX IF NOT EXISTS idx_notes_due_at ON notes (due_at) WHERE due_at IS NOT NULL;
+
+-- ============================================================================
+-- Migration Complete
+-- ============================================================================
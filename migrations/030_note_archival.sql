@@ -0,0 +1,21 @@
+-- Migration: 030_note_archival.sql
+-- Automatic archival of processed notes.
+--
+-- status_changed_at records when a note last changed status, so
+-- archive_stale can tell how long a note has been processed; updated_at
+-- can't be used because tag edits and other updates bump it.
+--
+-- The status check from 005 only allowed active/archived/deleted, while
+-- NoteStatus and the CLI also use inbox and processed; widen it.
+
+ALTER TABLE notes ADD COLUMN IF NOT EXISTS status_changed_at BIGINT;
+
+ALTER TABLE notes DROP CONSTRAINT IF EXISTS notes_status_check;
+ALTER TABLE notes ADD CONSTRAINT notes_status_check
+    CHECK (status IN ('inbox', 'active', 'processed', 'archived', 'deleted'));
+
+CREATE INDEX IF NOT EXISTS idx_notes_processed ON notes (status_changed_at) WHERE status = 'processed';
+
+-- ============================================================================
+-- Migration Complete
+-- ============================================================================
@@ -0,0 +1,31 @@
+-- Migration: 031_document_versions.sql
+-- Version history for documents.
+--
+-- documents always holds the current version; documents.version is its
+-- number. Before an update or revert overwrites a document, the row is
+-- copied into document_versions under its old number, so the chain for a
+-- document is document_versions 1..n-1 followed by the current row n.
+-- created_at on a version is when that version was written (the
+-- document's updated_at at the time).
+
+ALTER TABLE documents ADD COLUMN IF NOT EXISTS version INTEGER NOT NULL DEFAULT 1;
+
+CREATE TABLE IF NOT EXISTS document_versions (
+    id           BIGSERIAL PRIMARY KEY,
+    document_id  TEXT    NOT NULL REFERENCES documents(id) ON DELETE CASCADE,
+    version      INTEGER NOT NULL,
+    title        TEXT    NOT NULL,
+    content      TEXT    NOT NULL,
+    content_type TEXT,
+    doc_type     TEXT,
+    tags         TEXT,
+    source_url   TEXT,
+    created_at   BIGINT  NOT NULL,
+    UNIQUE (document_id, version)
+);
+
+CREATE INDEX IF NOT EXISTS idx_document_versions_document ON document_versions (document_id, version);
+
+-- ============================================================================
+-- Migration Complete
+-- ============================================================================
@@ -0,0 +1,32 @@
+-- Migration: 032_generated_tasks.sql
+-- Tasks produced by TaskGenerator, remembered across runs.
+--
+-- A task is identified within a repository by its dedup_key (file,
+-- category and normalized title, see Task::dedup_key), so re-running
+-- generation only surfaces tasks that aren't stored yet. last_seen is
+-- bumped on every run that still produces the task; a task the latest run
+-- no longer produces can be closed, and is reopened if it comes back.
+
+CREATE TABLE IF NOT EXISTS generated_tasks (
+    id          TEXT    PRIMARY KEY NOT NULL,
+    repo_path   TEXT    NOT NULL,
+    dedup_key   TEXT    NOT NULL,
+    title       TEXT    NOT NULL,
+    description TEXT    NOT NULL DEFAULT '',
+    file_path   TEXT    NOT NULL,
+    line_number INTEGER,
+    priority    TEXT    NOT NULL,               -- TaskPriority, e.g. 'high'
+    category    TEXT    NOT NULL,               -- Category, e.g. 'janus'
+    tags        TEXT    NOT NULL DEFAULT '',    -- comma-separated
+    status      TEXT    NOT NULL DEFAULT 'open', -- open | closed
+    first_seen  BIGINT  NOT NULL,
+    last_seen   BIGINT  NOT NULL,
+    closed_at   BIGINT,
+    UNIQUE (repo_path, dedup_key)
+);
+
+CREATE INDEX IF NOT EXISTS idx_generated_tasks_repo_status ON generated_tasks (repo_path, status);
+
+-- ============================================================================
+-- Migration Complete
+-- ============================================================================
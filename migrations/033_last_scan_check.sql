@@ -0,0 +1,13 @@
+-- Migration: 033_last_scan_check.sql
+-- When the auto-scanner last checked a repository for changes.
+--
+-- The scanner compares this against scan_interval_mins to decide whether a
+-- repository is due; NULL means "due now", which is how a forced scan is
+-- requested. Unlike last_scanned_at it is bumped on every check, including
+-- ones that find nothing to analyze.
+
+ALTER TABLE repositories ADD COLUMN IF NOT EXISTS last_scan_check BIGINT;
+
+-- ============================================================================
+-- Migration Complete
+-- ============================================================================
@@ -3,12 +3,14 @@
 //! Provides administrative endpoints for the dashboard.
 //! Includes metrics, analytics, webhook management, API key management, and system health.
 
+use crate::api::auth::{require_admin, ApiKeyScope};
 use crate::api::handlers::ApiState;
 use crate::api::ApiResponse;
 use anyhow::Result;
 use axum::{
     extract::{Path, Query, State},
     http::StatusCode,
+    middleware,
     response::IntoResponse,
     routing::{delete, get, post},
     Json, Router,
@@ -31,6 +33,8 @@ pub fn admin_router() -> Router<Arc<ApiState>> {
         .route("/admin/api-keys/:id", delete(revoke_api_key))
         .route("/admin/jobs", get(list_jobs))
         .route("/admin/jobs/:id/retry", post(retry_job))
+        .route("/admin/reindex", post(reindex_all))
+        .route_layer(middleware::from_fn(require_admin))
 }
 
 // ============================================================================
@@ -61,6 +65,13 @@ pub struct HealthStatus {
 pub struct CreateApiKeyRequest {
     pub name: String,
     pub description: Option<String>,
+    /// Defaults to `read_write`
+    #[serde(default = "default_key_scope")]
+    pub scope: ApiKeyScope,
+}
+
+fn default_key_scope() -> ApiKeyScope {
+    ApiKeyScope::ReadWrite
 }
 
 #[derive(Debug, Serialize)]
@@ -68,6 +79,8 @@ pub struct ApiKeyResponse {
     pub id: String,
     pub name: String,
     pub prefix: String,
+    /// Inferred from the key prefix
+    pub scope: Option<ApiKeyScope>,
     /// Only populated on creation — never returned again after that.
     pub key: Option<String>,
     pub created_at: DateTime<Utc>,
@@ -88,6 +101,16 @@ pub struct JobResponse {
     pub chunks_processed: i64,
 }
 
+#[derive(Debug, Serialize)]
+pub struct ReindexResponse {
+    pub embeddings_cleared: u64,
+    pub documents_queued: usize,
+    /// Indexing job that will rebuild the embeddings (None if there are no documents)
+    pub job_id: Option<String>,
+    pub model: String,
+    pub dimension: usize,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct JobsQuery {
     /// Filter by status: pending | processing | completed | failed
@@ -203,6 +226,7 @@ async fn list_api_keys(
         .map(
             |(id, name, prefix, created_at, last_used, request_count)| ApiKeyResponse {
                 id,
+                scope: ApiKeyScope::from_key(&prefix),
                 name,
                 prefix,
                 key: None,
@@ -221,7 +245,7 @@ async fn create_api_key(
     State(state): State<Arc<ApiState>>,
     Json(req): Json<CreateApiKeyRequest>,
 ) -> Result<impl IntoResponse, StatusCode> {
-    let raw_key = crate::api::generate_api_key();
+    let raw_key = crate::api::generate_api_key(req.scope);
     let key_hash = crate::api::hash_api_key(&raw_key);
     let prefix = raw_key[..8].to_string();
     let id = uuid::Uuid::new_v4().to_string();
@@ -246,6 +270,7 @@ async fn create_api_key(
         id,
         name: req.name,
         prefix,
+        scope: Some(req.scope),
         key: Some(raw_key),
         created_at,
         last_used: None,
@@ -370,6 +395,86 @@ async fn retry_job(
     )))
 }
 
+/// POST /admin/reindex
+///
+/// Drops all chunks and embeddings, marks every document unindexed, and
+/// submits one indexing job for all of them using the active embedding model.
+async fn reindex_all(State(state): State<Arc<ApiState>>) -> Result<impl IntoResponse, StatusCode> {
+    // Don't wipe the index if the job that rebuilds it can't be submitted
+    if state.job_queue.is_shutting_down() {
+        return Err(StatusCode::SERVICE_UNAVAILABLE);
+    }
+
+    let mut tx = state.db_pool.begin().await.map_err(db_err)?;
+
+    let embeddings_cleared = sqlx::query("DELETE FROM document_embeddings")
+        .execute(&mut *tx)
+        .await
+        .map_err(db_err)?
+        .rows_affected();
+
+    sqlx::query("DELETE FROM document_chunks")
+        .execute(&mut *tx)
+        .await
+        .map_err(db_err)?;
+
+    sqlx::query("UPDATE documents SET indexed_at = NULL")
+        .execute(&mut *tx)
+        .await
+        .map_err(db_err)?;
+
+    let document_ids: Vec<String> = sqlx::query_scalar("SELECT id FROM documents ORDER BY id")
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(db_err)?;
+
+    tx.commit().await.map_err(db_err)?;
+
+    // The index is now empty, so it matches the active model from here on
+    let fingerprint = {
+        let mut config = state.indexing_config.write().await;
+        let fingerprint = config.embedding_fingerprint();
+        crate::indexing::record_index_fingerprint(&state.db_pool, &fingerprint)
+            .await
+            .map_err(|e| {
+                tracing::error!(error = %e, "Failed to record index metadata");
+                StatusCode::INTERNAL_SERVER_ERROR
+            })?;
+        config.stored_embedding = Some(fingerprint.clone());
+        fingerprint
+    };
+
+    let documents_queued = document_ids.len();
+    let job_id = if document_ids.is_empty() {
+        None
+    } else {
+        let job_id = state
+            .job_queue
+            .submit_job(document_ids, true)
+            .await
+            .map_err(|e| {
+                tracing::error!(error = %e, "Failed to submit reindex job");
+                StatusCode::SERVICE_UNAVAILABLE
+            })?;
+        Some(job_id)
+    };
+
+    tracing::info!(
+        embeddings_cleared,
+        documents_queued,
+        model = %fingerprint.model,
+        "Full reindex started"
+    );
+
+    Ok(Json(ApiResponse::success(ReindexResponse {
+        embeddings_cleared,
+        documents_queued,
+        job_id,
+        model: fingerprint.model,
+        dimension: fingerprint.dimension,
+    })))
+}
+
 // ============================================================================
 // Tests
 // ============================================================================
@@ -1,25 +1,82 @@
 //! Authentication and authorization middleware for RAG API
 
 use axum::{
-    extract::{Request, State},
-    http::{HeaderMap, StatusCode},
+    async_trait,
+    extract::{FromRequestParts, Request, State},
+    http::{request::Parts, HeaderMap, StatusCode},
     middleware::Next,
     response::{IntoResponse, Response},
 };
 use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
+use std::collections::HashMap;
 use std::sync::Arc;
 
+use super::reload::Reloadable;
+use super::types::ApiError;
+
 // ============================================================================
 // Types
 // ============================================================================
 
+/// What an API key is allowed to do
+///
+/// Scopes are ordered: each one includes everything the previous allows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ApiKeyScope {
+    /// Reads, search, and indexing existing documents — no uploads, updates
+    /// or deletes
+    ReadOnly,
+    /// Everything except admin endpoints
+    ReadWrite,
+    /// Full access, including `/admin/*`
+    Admin,
+}
+
+impl ApiKeyScope {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ApiKeyScope::ReadOnly => "read_only",
+            ApiKeyScope::ReadWrite => "read_write",
+            ApiKeyScope::Admin => "admin",
+        }
+    }
+
+    /// Prefix that `generate_api_key` puts on keys of this scope
+    fn key_prefix(&self) -> &'static str {
+        match self {
+            ApiKeyScope::ReadOnly => "ro_",
+            ApiKeyScope::ReadWrite => "rw_",
+            ApiKeyScope::Admin => "ad_",
+        }
+    }
+
+    /// Infer the scope of a key produced by `generate_api_key`
+    pub fn from_key(key: &str) -> Option<Self> {
+        [Self::ReadOnly, Self::ReadWrite, Self::Admin]
+            .into_iter()
+            .find(|scope| key.starts_with(scope.key_prefix()))
+    }
+
+    /// Whether this scope grants at least `required`
+    pub fn allows(&self, required: ApiKeyScope) -> bool {
+        *self >= required
+    }
+}
+
+impl std::fmt::Display for ApiKeyScope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 /// Authentication configuration
 #[derive(Debug, Clone)]
 pub struct AuthConfig {
-    /// API keys (hashed with SHA256)
-    pub api_keys: Vec<String>,
+    /// API keys (hashed with SHA256) and the scope each one grants
+    pub api_keys: HashMap<String, ApiKeyScope>,
     /// Whether authentication is required
     pub require_auth: bool,
     /// Whether to allow anonymous read-only access
@@ -29,7 +86,7 @@ pub struct AuthConfig {
 impl Default for AuthConfig {
     fn default() -> Self {
         Self {
-            api_keys: Vec::new(),
+            api_keys: HashMap::new(),
             require_auth: false,
             allow_anonymous_read: true,
         }
@@ -38,18 +95,30 @@ impl Default for AuthConfig {
 
 impl AuthConfig {
     /// Create new auth config with API keys
+    ///
+    /// Scopes are inferred from the `generate_api_key` prefix; keys without one
+    /// keep full access, as before scopes existed.
     pub fn new(api_keys: Vec<String>) -> Self {
-        let require_auth = !api_keys.is_empty();
-        Self {
-            api_keys: api_keys.into_iter().map(|key| hash_api_key(&key)).collect(),
-            require_auth,
+        let mut config = Self {
+            api_keys: HashMap::new(),
+            require_auth: false,
             allow_anonymous_read: false,
+        };
+        for key in api_keys {
+            config.add_key(&key);
         }
+        config
     }
 
-    /// Add an API key
+    /// Add an API key, inferring its scope (unprefixed keys get `Admin`)
     pub fn add_key(&mut self, key: &str) {
-        self.api_keys.push(hash_api_key(key));
+        let scope = ApiKeyScope::from_key(key).unwrap_or(ApiKeyScope::Admin);
+        self.add_key_with_scope(key, scope);
+    }
+
+    /// Add an API key with an explicit scope
+    pub fn add_key_with_scope(&mut self, key: &str, scope: ApiKeyScope) {
+        self.api_keys.insert(hash_api_key(key), scope);
         self.require_auth = true;
     }
 
@@ -58,8 +127,18 @@ impl AuthConfig {
         if !self.require_auth {
             return true;
         }
-        let hashed = hash_api_key(key);
-        self.api_keys.contains(&hashed)
+        self.key_scope(key).is_some()
+    }
+
+    /// Hash of `key` when auth is on and it is one of the configured keys
+    pub fn authenticated_key(&self, key: &str) -> Option<String> {
+        let hash = hash_api_key(key);
+        (self.require_auth && self.api_keys.contains_key(&hash)).then_some(hash)
+    }
+
+    /// Scope granted to a key, or `None` if it is not configured
+    pub fn key_scope(&self, key: &str) -> Option<ApiKeyScope> {
+        self.api_keys.get(&hash_api_key(key)).copied()
     }
 
     /// Check if method is read-only
@@ -71,18 +150,20 @@ impl AuthConfig {
     pub fn validate_request(&self, key: Option<&str>, method: &str) -> AuthResult {
         // No auth required
         if !self.require_auth {
-            return AuthResult::Allowed;
+            return AuthResult::Allowed(ApiKeyScope::Admin);
         }
 
         // Check if anonymous read is allowed for GET requests
         if self.allow_anonymous_read && Self::is_read_only_method(method) && key.is_none() {
-            return AuthResult::Allowed;
+            return AuthResult::Allowed(ApiKeyScope::ReadOnly);
         }
 
         // Validate API key
         match key {
-            Some(k) if self.validate_key(k) => AuthResult::Allowed,
-            Some(_) => AuthResult::InvalidKey,
+            Some(k) => match self.key_scope(k) {
+                Some(scope) => AuthResult::Allowed(scope),
+                None => AuthResult::InvalidKey,
+            },
             None => AuthResult::MissingKey,
         }
     }
@@ -91,11 +172,53 @@ impl AuthConfig {
 /// Authentication result
 #[derive(Debug, Clone, PartialEq)]
 pub enum AuthResult {
-    Allowed,
+    Allowed(ApiKeyScope),
     MissingKey,
     InvalidKey,
 }
 
+/// Scope attached to each request by `auth_middleware`
+///
+/// Routes that need more than `ReadOnly` sit behind [`require_read_write`]
+/// or [`require_admin`]; handlers can also take this as an extractor and
+/// call [`Authorized::require`]. A request that didn't pass through the
+/// middleware has no scope and is rejected with a 401.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Authorized(pub ApiKeyScope);
+
+impl Authorized {
+    /// Fail with a 403 unless this request's scope grants `required`
+    pub fn require(&self, required: ApiKeyScope) -> Result<(), ApiError> {
+        if self.0.allows(required) {
+            Ok(())
+        } else {
+            Err(ApiError::forbidden(format!(
+                "API key scope '{}' is insufficient for this action; '{}' is required",
+                self.0, required
+            )))
+        }
+    }
+}
+
+#[async_trait]
+impl<S: Send + Sync> FromRequestParts<S> for Authorized {
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        parts
+            .extensions
+            .get::<Authorized>()
+            .copied()
+            .ok_or_else(|| ApiError::unauthorized("Request was not authenticated"))
+    }
+}
+
+/// Hash of the API key `auth_middleware` matched against the configured
+/// keys; absent for anonymous requests and when auth is off, so it only
+/// ever names a real key
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuthenticatedKey(pub String);
+
 /// Hash an API key using SHA256
 pub fn hash_api_key(key: &str) -> String {
     let mut hasher = Sha256::new();
@@ -108,10 +231,13 @@ pub fn hash_api_key(key: &str) -> String {
 // ============================================================================
 
 /// Authentication middleware
+///
+/// Reads the config through a [`Reloadable`], so keys can be rotated
+/// without a restart; a request is checked against one snapshot.
 pub async fn auth_middleware(
-    State(config): State<Arc<AuthConfig>>,
+    State(config): State<Arc<Reloadable<AuthConfig>>>,
     headers: HeaderMap,
-    request: Request,
+    mut request: Request,
     next: Next,
 ) -> Response {
     // Extract API key from headers
@@ -128,14 +254,37 @@ pub async fn auth_middleware(
     let method = request.method().as_str();
 
     // Validate
+    let config = config.load();
     match config.validate_request(api_key, method) {
-        AuthResult::Allowed => next.run(request).await,
+        AuthResult::Allowed(scope) => {
+            request.extensions_mut().insert(Authorized(scope));
+            if let Some(hash) = api_key.and_then(|key| config.authenticated_key(key)) {
+                request.extensions_mut().insert(AuthenticatedKey(hash));
+            }
+            next.run(request).await
+        }
         AuthResult::MissingKey => (
             StatusCode::UNAUTHORIZED,
             "Missing API key. Provide via X-API-Key header or Authorization: Bearer <key>",
         )
             .into_response(),
-        AuthResult::InvalidKey => (StatusCode::FORBIDDEN, "Invalid API key").into_response(),
+        AuthResult::InvalidKey => (StatusCode::UNAUTHORIZED, "Invalid API key").into_response(),
+    }
+}
+
+/// Route layer rejecting requests whose key lacks the `ReadWrite` scope
+pub async fn require_read_write(scope: Authorized, request: Request, next: Next) -> Response {
+    match scope.require(ApiKeyScope::ReadWrite) {
+        Ok(()) => next.run(request).await,
+        Err(denied) => denied.into_response(),
+    }
+}
+
+/// Route layer rejecting requests whose key lacks the `Admin` scope
+pub async fn require_admin(scope: Authorized, request: Request, next: Next) -> Response {
+    match scope.require(ApiKeyScope::Admin) {
+        Ok(()) => next.run(request).await,
+        Err(denied) => denied.into_response(),
     }
 }
 
@@ -143,19 +292,24 @@ pub async fn auth_middleware(
 // API Key Management
 // ============================================================================
 
-/// Generate a new API key
-pub fn generate_api_key() -> String {
+/// Generate a new API key for the given scope
+///
+/// The key carries a scope prefix (`ro_`, `rw_`, `ad_`) so `AuthConfig::add_key`
+/// can register it with the right scope.
+pub fn generate_api_key(scope: ApiKeyScope) -> String {
     use rand::Rng;
     const CHARSET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789";
     const KEY_LEN: usize = 32;
 
     let mut rng = rand::thread_rng();
-    (0..KEY_LEN)
+    let random: String = (0..KEY_LEN)
         .map(|_| {
             let idx = rng.gen_range(0..CHARSET.len());
             CHARSET[idx] as char
         })
-        .collect()
+        .collect();
+
+    format!("{}{}", scope.key_prefix(), random)
 }
 
 /// API Key metadata
@@ -239,8 +393,11 @@ mod tests {
         config.add_key("valid_key");
         config.allow_anonymous_read = true;
 
-        // Anonymous GET should be allowed
-        assert_eq!(config.validate_request(None, "GET"), AuthResult::Allowed);
+        // Anonymous GET should be allowed, read-only
+        assert_eq!(
+            config.validate_request(None, "GET"),
+            AuthResult::Allowed(ApiKeyScope::ReadOnly)
+        );
 
         // Anonymous POST should be denied
         assert_eq!(
@@ -248,10 +405,10 @@ mod tests {
             AuthResult::MissingKey
         );
 
-        // Valid key should be allowed for any method
+        // Valid unscoped key should be allowed for any method with full access
         assert_eq!(
             config.validate_request(Some("valid_key"), "POST"),
-            AuthResult::Allowed
+            AuthResult::Allowed(ApiKeyScope::Admin)
         );
 
         // Invalid key should be denied
@@ -259,16 +416,81 @@ mod tests {
             config.validate_request(Some("wrong_key"), "POST"),
             AuthResult::InvalidKey
         );
+
+        assert_eq!(
+            config.authenticated_key("valid_key"),
+            Some(hash_api_key("valid_key"))
+        );
+        assert_eq!(config.authenticated_key("wrong_key"), None);
+        // With auth off any key is let through, but none is authenticated
+        assert_eq!(AuthConfig::default().authenticated_key("valid_key"), None);
     }
 
     #[test]
     fn test_generate_api_key() {
-        let key1 = generate_api_key();
-        let key2 = generate_api_key();
+        let key1 = generate_api_key(ApiKeyScope::ReadWrite);
+        let key2 = generate_api_key(ApiKeyScope::ReadWrite);
 
-        assert_eq!(key1.len(), 32);
-        assert_eq!(key2.len(), 32);
+        assert_eq!(key1.len(), 35);
+        assert_eq!(key2.len(), 35);
         assert_ne!(key1, key2);
+        assert_eq!(ApiKeyScope::from_key(&key1), Some(ApiKeyScope::ReadWrite));
+    }
+
+    #[test]
+    fn test_scoped_keys() {
+        let read_only = generate_api_key(ApiKeyScope::ReadOnly);
+        let admin = generate_api_key(ApiKeyScope::Admin);
+        let config = AuthConfig::new(vec![read_only.clone(), admin.clone()]);
+
+        assert_eq!(
+            config.validate_request(Some(&read_only), "POST"),
+            AuthResult::Allowed(ApiKeyScope::ReadOnly)
+        );
+        assert_eq!(
+            config.validate_request(Some(&admin), "DELETE"),
+            AuthResult::Allowed(ApiKeyScope::Admin)
+        );
+
+        // Tampering with the prefix does not escalate — the hash no longer matches
+        let forged = read_only.replacen("ro_", "ad_", 1);
+        assert_eq!(
+            config.validate_request(Some(&forged), "DELETE"),
+            AuthResult::InvalidKey
+        );
+    }
+
+    #[test]
+    fn test_scope_ordering() {
+        assert!(ApiKeyScope::Admin.allows(ApiKeyScope::ReadWrite));
+        assert!(ApiKeyScope::ReadWrite.allows(ApiKeyScope::ReadOnly));
+        assert!(!ApiKeyScope::ReadOnly.allows(ApiKeyScope::ReadWrite));
+        assert!(!ApiKeyScope::ReadWrite.allows(ApiKeyScope::Admin));
+
+        let denied = Authorized(ApiKeyScope::ReadOnly)
+            .require(ApiKeyScope::ReadWrite)
+            .unwrap_err();
+        assert_eq!(denied.into_response().status(), StatusCode::FORBIDDEN);
+        assert!(Authorized(ApiKeyScope::Admin)
+            .require(ApiKeyScope::Admin)
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_missing_scope_is_rejected() {
+        let (mut parts, _) = axum::http::Request::new(()).into_parts();
+        let rejected = Authorized::from_request_parts(&mut parts, &())
+            .await
+            .unwrap_err();
+        assert_eq!(rejected.into_response().status(), StatusCode::UNAUTHORIZED);
+
+        parts.extensions.insert(Authorized(ApiKeyScope::ReadOnly));
+        assert_eq!(
+            Authorized::from_request_parts(&mut parts, &())
+                .await
+                .unwrap(),
+            Authorized(ApiKeyScope::ReadOnly)
+        );
     }
 
     #[test]
@@ -0,0 +1,227 @@
+//! Bulk document upload
+//!
+//! Turns a zip archive or uploaded files into documents for
+//! `POST /documents/bulk`: titles come from file names, content types from
+//! extensions, and unsafe or unreadable entries are reported per file
+//! instead of failing the whole upload.
+
+use std::io::{Cursor, Read};
+use std::path::{Component, Path};
+
+use super::types::BulkUploadFailure;
+
+/// Largest request body accepted by `POST /documents/bulk`
+pub const MAX_BULK_UPLOAD_BYTES: usize = 50 * 1024 * 1024;
+
+/// Most bytes extracted from one archive, against zip bombs
+pub const MAX_EXTRACTED_BYTES: u64 = 200 * 1024 * 1024;
+
+/// Most documents created by one bulk upload
+pub const MAX_BULK_FILES: usize = 1000;
+
+/// A file to be stored as a document
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BulkFile {
+    /// Path inside the archive, or the uploaded file name
+    pub name: String,
+    pub content: String,
+}
+
+impl BulkFile {
+    /// Decode an uploaded file, which must be UTF-8 text
+    pub fn from_bytes(name: &str, bytes: Vec<u8>) -> Result<Self, BulkUploadFailure> {
+        let content = String::from_utf8(bytes)
+            .map_err(|_| BulkUploadFailure::new(name, "File is not UTF-8 text"))?;
+        if content.trim().is_empty() {
+            return Err(BulkUploadFailure::new(name, "File is empty"));
+        }
+        Ok(Self {
+            name: name.to_string(),
+            content,
+        })
+    }
+
+    /// Title derived from the file name: `getting_started.md` becomes
+    /// `getting started`
+    pub fn title(&self) -> String {
+        let path = Path::new(&self.name);
+        let stem = path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or(&self.name);
+        let title = stem.replace(['_', '-'], " ");
+        let title = title.split_whitespace().collect::<Vec<_>>().join(" ");
+        if title.is_empty() {
+            self.name.clone()
+        } else {
+            title
+        }
+    }
+
+    /// Content type from the extension, in the vocabulary `POST /documents`
+    /// uses
+    pub fn content_type(&self) -> &'static str {
+        let extension = Path::new(&self.name)
+            .extension()
+            .and_then(|e| e.to_str())
+            .unwrap_or("")
+            .to_lowercase();
+        match extension.as_str() {
+            "md" | "markdown" => "markdown",
+            "html" | "htm" => "html",
+            "rs" | "py" | "js" | "ts" | "tsx" | "go" | "java" | "kt" | "swift" | "c" | "h"
+            | "cpp" | "hpp" | "cs" | "rb" | "sh" | "toml" | "yaml" | "yml" | "json" | "sql" => {
+                "code"
+            }
+            _ => "text",
+        }
+    }
+}
+
+/// Whether a name looks like a zip archive
+pub fn is_zip_name(name: &str) -> bool {
+    name.to_lowercase().ends_with(".zip")
+}
+
+/// Extract the text files of a zip archive.
+///
+/// Directories and macOS metadata are skipped. Entries that would escape
+/// the archive root (`../`, absolute paths), aren't UTF-8 text, or run past
+/// [`MAX_EXTRACTED_BYTES`] are returned as failures. Only an unreadable
+/// archive is an error.
+pub fn extract_zip(bytes: &[u8]) -> Result<(Vec<BulkFile>, Vec<BulkUploadFailure>), String> {
+    let mut archive = zip::ZipArchive::new(Cursor::new(bytes))
+        .map_err(|e| format!("Invalid zip archive: {}", e))?;
+
+    let mut files = Vec::new();
+    let mut failures = Vec::new();
+    let mut extracted: u64 = 0;
+
+    for i in 0..archive.len() {
+        let mut entry = match archive.by_index(i) {
+            Ok(entry) => entry,
+            Err(e) => {
+                failures.push(BulkUploadFailure::new(
+                    format!("entry {}", i),
+                    format!("Unreadable entry: {}", e),
+                ));
+                continue;
+            }
+        };
+        let name = entry.name().to_string();
+        if entry.is_dir() || is_metadata(&name) {
+            continue;
+        }
+        if !is_safe_entry_name(&name) || entry.enclosed_name().is_none() {
+            failures.push(BulkUploadFailure::new(
+                &name,
+                "Path escapes the archive root",
+            ));
+            continue;
+        }
+
+        // Read at most one byte past the remaining budget, so an entry
+        // lying about its size is still caught
+        let budget = MAX_EXTRACTED_BYTES - extracted;
+        let mut buf = Vec::new();
+        if let Err(e) = (&mut entry).take(budget + 1).read_to_end(&mut buf) {
+            failures.push(BulkUploadFailure::new(
+                &name,
+                format!("Failed to extract: {}", e),
+            ));
+            continue;
+        }
+        if buf.len() as u64 > budget {
+            failures.push(BulkUploadFailure::new(
+                &name,
+                format!(
+                    "Archive expands past {} MB; remaining entries skipped",
+                    MAX_EXTRACTED_BYTES / (1024 * 1024)
+                ),
+            ));
+            break;
+        }
+        extracted += buf.len() as u64;
+
+        match BulkFile::from_bytes(&name, buf) {
+            Ok(file) => files.push(file),
+            Err(failure) => failures.push(failure),
+        }
+    }
+
+    Ok((files, failures))
+}
+
+/// Relative path made only of normal components
+fn is_safe_entry_name(name: &str) -> bool {
+    let path = Path::new(name);
+    !name.contains('\\')
+        && path
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)))
+}
+
+fn is_metadata(name: &str) -> bool {
+    name.starts_with("__MACOSX/")
+        || Path::new(name)
+            .file_name()
+            .and_then(|n| n.to_str())
+            .is_some_and(|n| n == ".DS_Store")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use zip::write::SimpleFileOptions;
+
+    fn zip_of(entries: &[(&str, &[u8])]) -> Vec<u8> {
+        let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        for (name, content) in entries {
+            writer
+                .start_file(*name, SimpleFileOptions::default())
+                .unwrap();
+            writer.write_all(content).unwrap();
+        }
+        writer.finish().unwrap().into_inner()
+    }
+
+    #[test]
+    fn test_extract_zip_rejects_unsafe_entries() {
+        let archive = zip_of(&[
+            ("docs/getting_started.md", b"# Getting started"),
+            ("notes/api-design.txt", b"Keep it small"),
+            ("../escape.md", b"nope"),
+            ("docs/../../escape.md", b"nope"),
+            ("/etc/passwd", b"nope"),
+            ("image.png", &[0xff, 0xd8, 0xff, 0x00]),
+            ("__MACOSX/docs/._getting_started.md", b"meta"),
+        ]);
+
+        let (files, failures) = extract_zip(&archive).unwrap();
+        let names: Vec<_> = files.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec!["docs/getting_started.md", "notes/api-design.txt"]
+        );
+        assert_eq!(files[0].title(), "getting started");
+        assert_eq!(files[0].content_type(), "markdown");
+        assert_eq!(files[1].title(), "api design");
+        assert_eq!(files[1].content_type(), "text");
+
+        let failed: Vec<_> = failures.iter().map(|f| f.file.as_str()).collect();
+        assert_eq!(
+            failed,
+            vec![
+                "../escape.md",
+                "docs/../../escape.md",
+                "/etc/passwd",
+                "image.png"
+            ]
+        );
+        assert!(failures[0].error.contains("escapes"));
+        assert!(failures[3].error.contains("UTF-8"));
+
+        assert!(extract_zip(b"not a zip").is_err());
+    }
+}
@@ -0,0 +1,241 @@
+//! CORS configuration for the RAG API
+//!
+//! The CORS layer wraps the whole API router, outside auth and rate
+//! limiting, so browser preflight (`OPTIONS`) requests are answered without
+//! an API key.
+//!
+//! CORS only stops a page from *reading* responses: a "simple" cross-site
+//! `POST` (no custom headers, form content type) is still sent. Servers
+//! without API keys should add [`reject_cross_site_writes`] as well.
+
+use axum::extract::{Request, State};
+use axum::http::{header, HeaderName, HeaderValue, Method, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use std::sync::Arc;
+use std::time::Duration;
+use tower_http::cors::{AllowOrigin, Any, CorsLayer};
+use tracing::warn;
+
+/// Cross-origin access settings
+#[derive(Debug, Clone)]
+pub struct CorsConfig {
+    /// Origins allowed to call the API, e.g. `https://dashboard.example.com`;
+    /// `*` allows any origin. Empty disables cross-origin access.
+    pub allowed_origins: Vec<String>,
+    /// Allowed methods; `*` allows any
+    pub allowed_methods: Vec<String>,
+    /// Allowed request headers; `*` allows any
+    pub allowed_headers: Vec<String>,
+    /// Response headers readable by browser code
+    pub expose_headers: Vec<String>,
+    /// Allow cookies and `Authorization` to be sent cross-origin. Ignored
+    /// when any origin, method or header is allowed with `*`.
+    pub allow_credentials: bool,
+    /// How long browsers may cache a preflight response
+    pub max_age_seconds: u64,
+}
+
+impl Default for CorsConfig {
+    fn default() -> Self {
+        Self::restrictive()
+    }
+}
+
+impl CorsConfig {
+    /// Any origin, method and header, for local development
+    pub fn permissive() -> Self {
+        Self {
+            allowed_origins: vec!["*".to_string()],
+            allowed_methods: vec!["*".to_string()],
+            allowed_headers: vec!["*".to_string()],
+            ..Self::restrictive()
+        }
+    }
+
+    /// No cross-origin access until origins are added
+    pub fn restrictive() -> Self {
+        Self {
+            allowed_origins: Vec::new(),
+            allowed_methods: ["GET", "POST", "PUT", "DELETE"]
+                .into_iter()
+                .map(String::from)
+                .collect(),
+            allowed_headers: ["content-type", "authorization", "x-api-key"]
+                .into_iter()
+                .map(String::from)
+                .collect(),
+            expose_headers: [
+                "x-ratelimit-limit",
+                "x-ratelimit-remaining",
+                "x-ratelimit-reset",
+                "retry-after",
+            ]
+            .into_iter()
+            .map(String::from)
+            .collect(),
+            allow_credentials: false,
+            max_age_seconds: 3600,
+        }
+    }
+
+    /// Allow another origin
+    pub fn with_origin(mut self, origin: impl Into<String>) -> Self {
+        self.allowed_origins.push(origin.into());
+        self
+    }
+
+    /// Whether browser pages on `origin` may call the API
+    pub fn allows_origin(&self, origin: &str) -> bool {
+        let origin = origin.trim_end_matches('/');
+        self.allowed_origins
+            .iter()
+            .any(|allowed| allowed == "*" || allowed.trim_end_matches('/') == origin)
+    }
+
+    /// Build the tower-http layer. Entries that aren't valid origins,
+    /// methods or header names are skipped with a warning.
+    pub fn layer(&self) -> CorsLayer {
+        let is_any = |values: &[String]| values.iter().any(|v| v == "*");
+
+        let mut layer = CorsLayer::new()
+            .expose_headers(parse_all::<HeaderName>(&self.expose_headers, "header"))
+            .max_age(Duration::from_secs(self.max_age_seconds));
+
+        layer = if is_any(&self.allowed_origins) {
+            layer.allow_origin(Any)
+        } else {
+            layer.allow_origin(AllowOrigin::list(parse_all::<HeaderValue>(
+                &self.allowed_origins,
+                "origin",
+            )))
+        };
+        layer = if is_any(&self.allowed_methods) {
+            layer.allow_methods(Any)
+        } else {
+            layer.allow_methods(parse_all::<Method>(&self.allowed_methods, "method"))
+        };
+        layer = if is_any(&self.allowed_headers) {
+            layer.allow_headers(Any)
+        } else {
+            layer.allow_headers(parse_all::<HeaderName>(&self.allowed_headers, "header"))
+        };
+
+        // Browsers reject credentials combined with wildcards, and
+        // tower-http refuses to build such a layer
+        let wildcard = is_any(&self.allowed_origins)
+            || is_any(&self.allowed_methods)
+            || is_any(&self.allowed_headers);
+        if self.allow_credentials && wildcard {
+            warn!("CORS credentials can't be combined with `*`; credentials disabled");
+        }
+        layer.allow_credentials(self.allow_credentials && !wildcard)
+    }
+}
+
+fn parse_all<T: std::str::FromStr>(values: &[String], kind: &str) -> Vec<T> {
+    values
+        .iter()
+        .filter_map(|value| match value.parse() {
+            Ok(parsed) => Some(parsed),
+            Err(_) => {
+                warn!("Ignoring invalid CORS {}: {}", kind, value);
+                None
+            }
+        })
+        .collect()
+}
+
+/// Middleware rejecting state-changing requests sent by pages on other sites
+///
+/// Browsers send `Origin` on every cross-site `POST`/`PUT`/`DELETE`; it must
+/// be the server's own origin or one of the allowed origins. Requests
+/// without it (curl, the CLI, other servers) pass, unless
+/// `Sec-Fetch-Site` marks them as cross-site.
+pub async fn reject_cross_site_writes(
+    State(config): State<Arc<CorsConfig>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if matches!(
+        *request.method(),
+        Method::GET | Method::HEAD | Method::OPTIONS
+    ) {
+        return next.run(request).await;
+    }
+
+    let headers = request.headers();
+    let text = |name: &HeaderName| headers.get(name).and_then(|v| v.to_str().ok());
+    let allowed = match text(&header::ORIGIN) {
+        Some(origin) => {
+            let same_origin = origin
+                .split_once("://")
+                .zip(text(&header::HOST))
+                .is_some_and(|((_, authority), host)| authority == host);
+            same_origin || config.allows_origin(origin)
+        }
+        None => text(&HeaderName::from_static("sec-fetch-site")) != Some("cross-site"),
+    };
+
+    if allowed {
+        next.run(request).await
+    } else {
+        (
+            StatusCode::FORBIDDEN,
+            "Cross-site request rejected; add the origin to the allowed CORS origins",
+        )
+            .into_response()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use axum::routing::post;
+    use axum::Router;
+    use tower::ServiceExt;
+
+    async fn status(method: Method, headers: &[(&str, &str)]) -> StatusCode {
+        let config = Arc::new(CorsConfig::restrictive().with_origin("https://dash.example.com"));
+        let app = Router::new()
+            .route("/write", post(|| async { "ok" }).get(|| async { "ok" }))
+            .layer(axum::middleware::from_fn_with_state(
+                config,
+                reject_cross_site_writes,
+            ));
+
+        let mut request = Request::builder().method(method).uri("/write");
+        for (name, value) in headers {
+            request = request.header(*name, *value);
+        }
+        app.oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap()
+            .status()
+    }
+
+    #[tokio::test]
+    async fn test_cross_site_writes_are_rejected() {
+        let host = ("host", "localhost:3000");
+
+        // A page on another site posting a form
+        let evil = status(Method::POST, &[host, ("origin", "https://evil.example")]).await;
+        assert_eq!(evil, StatusCode::FORBIDDEN);
+        let fetch_metadata = status(Method::POST, &[host, ("sec-fetch-site", "cross-site")]).await;
+        assert_eq!(fetch_metadata, StatusCode::FORBIDDEN);
+        let sandboxed = status(Method::POST, &[host, ("origin", "null")]).await;
+        assert_eq!(sandboxed, StatusCode::FORBIDDEN);
+
+        // Same origin, allowed origins, non-browser clients and reads pass
+        for headers in [
+            vec![host, ("origin", "http://localhost:3000")],
+            vec![host, ("origin", "https://dash.example.com")],
+            vec![host],
+        ] {
+            assert_eq!(status(Method::POST, &headers).await, StatusCode::OK);
+        }
+        let read = status(Method::GET, &[host, ("origin", "https://evil.example")]).await;
+        assert_eq!(read, StatusCode::OK);
+    }
+}
@@ -1,18 +1,27 @@
 //! API handlers for RAG system endpoints
 
 use axum::{
-    extract::{Path, Query, State},
-    http::StatusCode,
+    body::Bytes,
+    extract::{FromRequest, Multipart, Path, Query, Request, State},
+    http::{header, StatusCode},
     response::IntoResponse,
     Json,
 };
 use std::sync::Arc;
 use std::time::Instant;
 
+use super::bulk::{self, BulkFile};
 use super::types::*;
+use crate::db;
+use crate::db::queue::{QueuePriority, QueueSource, QueueStage};
 use crate::embeddings::EmbeddingGenerator;
 use crate::indexing::IndexingConfig;
-use crate::search::{SearchConfig, SearchFilters, SearchQuery, SemanticSearcher};
+use crate::queue::QueueEventBus;
+use crate::rerank::{RerankConfig, Reranker};
+use crate::search::{
+    extract_snippet, SearchConfig, SearchFilters, SearchQuery, SearchResult, SemanticSearcher,
+    SnippetConfig,
+};
 use sqlx::PgPool;
 
 // ============================================================================
@@ -25,9 +34,47 @@ pub struct ApiState {
     pub embedding_generator: Arc<tokio::sync::Mutex<EmbeddingGenerator>>,
     pub searcher: Arc<SemanticSearcher>,
     pub job_queue: Arc<super::jobs::JobQueue>,
+    /// Active indexing config, including the recorded index fingerprint
+    pub indexing_config: Arc<tokio::sync::RwLock<IndexingConfig>>,
+    pub readiness: Arc<ReadinessConfig>,
+    /// LLM reranker for `rerank: true` searches; `None` when no rerank
+    /// model is configured
+    pub reranker: Option<Arc<Reranker>>,
     pub start_time: std::time::SystemTime,
 }
 
+/// Dependencies checked by `/health/ready`
+///
+/// | Variable                           | Default                      |
+/// |------------------------------------|------------------------------|
+/// | `XAI_API_KEY`                      | (none) — LLM check skipped   |
+/// | `RUSTASSISTANT_READY_LLM_URL`      | `https://api.x.ai/v1/models` |
+/// | `RUSTASSISTANT_READY_REQUIRE_LLM`  | `false`                      |
+#[derive(Debug, Clone, Default)]
+pub struct ReadinessConfig {
+    /// Authenticated endpoint to ping, with its API key; `None` skips the
+    /// LLM check
+    pub llm_probe: Option<(String, String)>,
+    /// Report not ready when the LLM provider is unreachable
+    pub llm_critical: bool,
+}
+
+impl ReadinessConfig {
+    pub fn from_env() -> Self {
+        let llm_url = std::env::var("RUSTASSISTANT_READY_LLM_URL")
+            .unwrap_or_else(|_| "https://api.x.ai/v1/models".to_string());
+        Self {
+            llm_probe: std::env::var("XAI_API_KEY")
+                .ok()
+                .filter(|key| !key.is_empty())
+                .map(|key| (llm_url, key)),
+            llm_critical: std::env::var("RUSTASSISTANT_READY_REQUIRE_LLM")
+                .map(|v| matches!(v.to_lowercase().as_str(), "1" | "true" | "yes"))
+                .unwrap_or(false),
+        }
+    }
+}
+
 impl ApiState {
     pub async fn new(
         db_pool: PgPool,
@@ -36,16 +83,19 @@ impl ApiState {
         job_queue_config: super::jobs::JobQueueConfig,
     ) -> Self {
         let searcher = Arc::new(
-            SemanticSearcher::new(SearchConfig::default())
-                .await
-                .expect("Failed to create semantic searcher"),
+            SemanticSearcher::with_embedding_config(
+                SearchConfig::default(),
+                indexing_config.embedding_config.clone(),
+            )
+            .await
+            .expect("Failed to create semantic searcher"),
         );
 
         let job_queue = Arc::new(super::jobs::JobQueue::new(
             job_queue_config,
             db_pool.clone(),
             embedding_generator.clone(),
-            indexing_config,
+            indexing_config.clone(),
         ));
 
         Self {
@@ -53,6 +103,9 @@ impl ApiState {
             embedding_generator,
             searcher,
             job_queue,
+            indexing_config: Arc::new(tokio::sync::RwLock::new(indexing_config)),
+            readiness: Arc::new(ReadinessConfig::from_env()),
+            reranker: RerankConfig::from_env().map(|config| Arc::new(Reranker::new(config))),
             start_time: std::time::SystemTime::now(),
         }
     }
@@ -93,6 +146,111 @@ pub async fn health_check(State(state): State<Arc<ApiState>>) -> impl IntoRespon
     Json(ApiResponse::success(response))
 }
 
+/// Liveness probe: only confirms the process is serving requests, so a
+/// slow database or LLM provider never gets the process restarted
+pub async fn liveness_check(State(state): State<Arc<ApiState>>) -> impl IntoResponse {
+    Json(ApiResponse::success(LivenessResponse {
+        status: "alive".to_string(),
+        uptime_seconds: state.start_time.elapsed().unwrap_or_default().as_secs(),
+    }))
+}
+
+/// Readiness probe: 200 when every critical dependency is up, 503
+/// otherwise. The database is always critical; the LLM provider is only
+/// checked when an API key is configured, and only gates readiness with
+/// `RUSTASSISTANT_READY_REQUIRE_LLM`.
+pub async fn readiness_check(State(state): State<Arc<ApiState>>) -> impl IntoResponse {
+    let mut checks = Vec::new();
+
+    let started = Instant::now();
+    let db = sqlx::query("SELECT 1").execute(&state.db_pool).await;
+    checks.push(dependency_check(
+        "database",
+        true,
+        started,
+        db.map(|_| ()).map_err(|e| e.to_string()),
+    ));
+
+    let llm_critical = state.readiness.llm_critical;
+    match &state.readiness.llm_probe {
+        Some((url, api_key)) => {
+            let started = Instant::now();
+            let result = ping_llm(url, api_key).await;
+            checks.push(dependency_check("llm", llm_critical, started, result));
+        }
+        None => checks.push(DependencyCheck {
+            name: "llm".to_string(),
+            status: "skipped".to_string(),
+            critical: llm_critical,
+            latency_ms: 0,
+            error: None,
+        }),
+    }
+
+    let (code, response) = readiness_response(checks);
+    let body = if code.is_success() {
+        ApiResponse::success(response)
+    } else {
+        ApiResponse {
+            success: false,
+            data: Some(response),
+            error: Some("A critical dependency is unavailable".to_string()),
+            message: None,
+        }
+    };
+    (code, Json(body))
+}
+
+fn dependency_check(
+    name: &str,
+    critical: bool,
+    started: Instant,
+    result: std::result::Result<(), String>,
+) -> DependencyCheck {
+    DependencyCheck {
+        name: name.to_string(),
+        status: if result.is_ok() { "up" } else { "down" }.to_string(),
+        critical,
+        latency_ms: started.elapsed().as_millis() as u64,
+        error: result.err(),
+    }
+}
+
+/// Status code and body for a set of checks; skipped checks never fail
+fn readiness_response(checks: Vec<DependencyCheck>) -> (StatusCode, ReadinessResponse) {
+    let ready = checks
+        .iter()
+        .all(|check| !check.critical || check.status != "down");
+    let (code, status) = if ready {
+        (StatusCode::OK, "ready")
+    } else {
+        (StatusCode::SERVICE_UNAVAILABLE, "not_ready")
+    };
+    (
+        code,
+        ReadinessResponse {
+            status: status.to_string(),
+            checks,
+        },
+    )
+}
+
+/// Cheap authenticated request against the LLM provider
+async fn ping_llm(url: &str, api_key: &str) -> std::result::Result<(), String> {
+    let response = reqwest::Client::new()
+        .get(url)
+        .bearer_auth(api_key)
+        .timeout(std::time::Duration::from_secs(5))
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+    if response.status().is_success() {
+        Ok(())
+    } else {
+        Err(format!("HTTP {}", response.status()))
+    }
+}
+
 /// Get system statistics
 pub async fn get_stats(State(state): State<Arc<ApiState>>) -> impl IntoResponse {
     // Get document stats
@@ -146,6 +304,8 @@ pub async fn get_stats(State(state): State<Arc<ApiState>>) -> impl IntoResponse
     // Get job stats
     let job_stats = state.job_queue.get_stats().await;
 
+    let stale_index = state.indexing_config.read().await.needs_reindex();
+
     let response = StatsResponse {
         documents: DocumentStats {
             total: total_docs,
@@ -169,6 +329,7 @@ pub async fn get_stats(State(state): State<Arc<ApiState>>) -> impl IntoResponse
             jobs_completed: job_stats.completed as i64,
             jobs_failed: job_stats.failed as i64,
         },
+        stale_index,
     };
 
     Json(ApiResponse::success(response))
@@ -204,7 +365,50 @@ pub async fn upload_document(
             .into_response();
     }
 
-    // Insert document
+    match insert_document(&state.db_pool, &req).await {
+        Ok(doc_id) => {
+            // Queue for indexing; during shutdown the document is stored but left unindexed
+            let (status, message) = match state
+                .job_queue
+                .submit_job(vec![doc_id.clone()], false)
+                .await
+            {
+                Ok(job_id) => (
+                    "queued_for_indexing",
+                    format!("Document uploaded successfully. Indexing job: {}", job_id),
+                ),
+                Err(e) => (
+                    "not_indexed",
+                    format!("Document uploaded but not queued for indexing: {}", e),
+                ),
+            };
+
+            let response = UploadDocumentResponse {
+                id: doc_id,
+                title: req.title,
+                status: status.to_string(),
+                created_at: chrono::Utc::now(),
+                message,
+            };
+
+            (StatusCode::CREATED, Json(ApiResponse::success(response))).into_response()
+        }
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::<()>::error(format!(
+                "Failed to upload document: {}",
+                e
+            ))),
+        )
+            .into_response(),
+    }
+}
+
+/// Insert a document row and return its id
+async fn insert_document(
+    pool: &PgPool,
+    req: &UploadDocumentRequest,
+) -> Result<String, sqlx::Error> {
     let doc_id = uuid::Uuid::new_v4().to_string();
     let tags_json = serde_json::to_string(&req.tags).unwrap_or_else(|_| "[]".to_string());
     // req.doc_type carries content-type values like "markdown"/"text"/"code"/"html".
@@ -212,7 +416,7 @@ pub async fn upload_document(
     // uses a different vocabulary (reference/research/tutorial/…).
     let content_type = req.doc_type.clone();
 
-    let result: Result<sqlx::postgres::PgQueryResult, sqlx::Error> = sqlx::query(
+    sqlx::query(
         r#"
         INSERT INTO documents (
             id, title, content, content_type, tags,
@@ -229,36 +433,185 @@ pub async fn upload_document(
     .bind(req.repo_id)
     .bind(&req.source_type)
     .bind(&req.source_url)
-    .execute(&state.db_pool)
-    .await;
+    .execute(pool)
+    .await?;
 
-    match result {
-        Ok(_result) => {
-            // Queue for indexing
-            let job_id = state
-                .job_queue
-                .submit_job(vec![doc_id.clone()], false)
-                .await;
+    Ok(doc_id)
+}
 
-            let response = UploadDocumentResponse {
-                id: doc_id,
-                title: req.title,
-                status: "queued_for_indexing".to_string(),
-                created_at: chrono::Utc::now(),
-                message: format!("Document uploaded successfully. Indexing job: {}", job_id),
+/// Bulk upload documents from a zip archive (`Content-Type:
+/// application/zip`) or a multipart form.
+///
+/// Form file fields become one document each, and `.zip` files in the form
+/// are extracted. Optional `tags` (comma separated) and `repo_id` fields
+/// apply to every document. All created documents are queued in one
+/// indexing job; files that can't be stored are listed under `failed`.
+pub async fn bulk_upload_documents(
+    State(state): State<Arc<ApiState>>,
+    request: Request,
+) -> impl IntoResponse {
+    let content_type = request
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("")
+        .to_lowercase();
+
+    let mut files = Vec::new();
+    let mut failed = Vec::new();
+    let mut tags = Vec::new();
+    let mut repo_id = None;
+
+    let bad_request = |message: String| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::<()>::error(message)),
+        )
+            .into_response()
+    };
+
+    if content_type.starts_with("multipart/form-data") {
+        let mut form = match Multipart::from_request(request, &state).await {
+            Ok(form) => form,
+            Err(e) => return e.into_response(),
+        };
+        loop {
+            let field = match form.next_field().await {
+                Ok(Some(field)) => field,
+                Ok(None) => break,
+                Err(e) => return bad_request(format!("Invalid multipart form: {}", e)),
+            };
+            let field_name = field.name().unwrap_or("").to_string();
+            let file_name = field.file_name().map(str::to_string);
+            let bytes = match field.bytes().await {
+                Ok(bytes) => bytes,
+                Err(e) => return bad_request(format!("Failed to read form field: {}", e)),
             };
 
-            (StatusCode::CREATED, Json(ApiResponse::success(response))).into_response()
+            match (file_name, field_name.as_str()) {
+                (Some(name), _) if bulk::is_zip_name(&name) => match bulk::extract_zip(&bytes) {
+                    Ok((extracted, failures)) => {
+                        files.extend(extracted);
+                        failed.extend(failures);
+                    }
+                    Err(e) => failed.push(BulkUploadFailure::new(name, e)),
+                },
+                (Some(name), _) => match BulkFile::from_bytes(&name, bytes.to_vec()) {
+                    Ok(file) => files.push(file),
+                    Err(failure) => failed.push(failure),
+                },
+                (None, "tags") => {
+                    tags = String::from_utf8_lossy(&bytes)
+                        .split(',')
+                        .map(|t| t.trim().to_string())
+                        .filter(|t| !t.is_empty())
+                        .collect();
+                }
+                (None, "repo_id") => match String::from_utf8_lossy(&bytes).trim().parse() {
+                    Ok(id) => repo_id = Some(id),
+                    Err(_) => return bad_request("repo_id must be an integer".to_string()),
+                },
+                _ => {}
+            }
         }
-        Err(e) => (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ApiResponse::<()>::error(format!(
-                "Failed to upload document: {}",
-                e
-            ))),
+    } else if content_type.starts_with("application/zip")
+        || content_type.starts_with("application/x-zip-compressed")
+    {
+        let body = match Bytes::from_request(request, &state).await {
+            Ok(body) => body,
+            Err(e) => return e.into_response(),
+        };
+        match bulk::extract_zip(&body) {
+            Ok((extracted, failures)) => {
+                files = extracted;
+                failed = failures;
+            }
+            Err(e) => return bad_request(e),
+        }
+    } else {
+        return (
+            StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            Json(ApiResponse::<()>::error(
+                "Expected multipart/form-data or application/zip".to_string(),
+            )),
         )
-            .into_response(),
+            .into_response();
+    }
+
+    if files.len() > bulk::MAX_BULK_FILES {
+        return bad_request(format!(
+            "Upload contains {} files; the limit is {}",
+            files.len(),
+            bulk::MAX_BULK_FILES
+        ));
+    }
+    if files.is_empty() && failed.is_empty() {
+        return bad_request("No files found in upload".to_string());
     }
+
+    let mut created = Vec::new();
+    for file in files {
+        let req = UploadDocumentRequest {
+            title: file.title(),
+            content: file.content.clone(),
+            doc_type: file.content_type().to_string(),
+            tags: tags.clone(),
+            repo_id,
+            source_type: Some("file".to_string()),
+            source_url: Some(file.name.clone()),
+        };
+        match insert_document(&state.db_pool, &req).await {
+            Ok(id) => created.push(BulkUploadedDocument {
+                id,
+                title: req.title,
+                file: file.name,
+            }),
+            Err(e) => failed.push(BulkUploadFailure::new(
+                file.name,
+                format!("Failed to store document: {}", e),
+            )),
+        }
+    }
+
+    let (job_id, status, message) = if created.is_empty() {
+        (None, "failed", "No documents were created".to_string())
+    } else {
+        let ids = created.iter().map(|doc| doc.id.clone()).collect();
+        match state.job_queue.submit_job(ids, false).await {
+            Ok(job_id) => {
+                let message = format!(
+                    "Uploaded {} documents ({} failed). Indexing job: {}",
+                    created.len(),
+                    failed.len(),
+                    job_id
+                );
+                (Some(job_id), "queued_for_indexing", message)
+            }
+            Err(e) => (
+                None,
+                "not_indexed",
+                format!(
+                    "Uploaded {} documents but not queued for indexing: {}",
+                    created.len(),
+                    e
+                ),
+            ),
+        }
+    };
+
+    let code = if created.is_empty() {
+        StatusCode::UNPROCESSABLE_ENTITY
+    } else {
+        StatusCode::CREATED
+    };
+    let response = BulkUploadResponse {
+        job_id,
+        status: status.to_string(),
+        created,
+        failed,
+        message,
+    };
+    (code, Json(ApiResponse::success(response))).into_response()
 }
 
 /// Get document by ID
@@ -470,59 +823,156 @@ pub async fn list_documents(
     Json(ApiResponse::success(response)).into_response()
 }
 
-/// Update document metadata
+/// Update a document; the previous version is kept in its history
 pub async fn update_document(
     State(state): State<Arc<ApiState>>,
-    Path(id): Path<i64>,
+    Path(id): Path<String>,
     Json(req): Json<UpdateDocumentRequest>,
 ) -> impl IntoResponse {
-    let mut updates = Vec::new();
-
-    if let Some(title) = req.title {
-        updates.push(format!("title = '{}'", title));
-    }
-
-    if let Some(tags) = req.tags {
-        let tags_json = serde_json::to_string(&tags).unwrap_or_else(|_| "[]".to_string());
-        updates.push(format!("tags = '{}'", tags_json));
-    }
-
-    if updates.is_empty() {
+    if req.title.is_none() && req.content.is_none() && req.tags.is_none() {
         return (
             StatusCode::BAD_REQUEST,
             Json(ApiResponse::<()>::error("No fields to update".to_string())),
         )
             .into_response();
     }
+    if req.content.as_deref().is_some_and(|c| c.trim().is_empty()) {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::<()>::error(
+                "Content cannot be empty".to_string(),
+            )),
+        )
+            .into_response();
+    }
 
-    let query = format!(
-        "UPDATE documents SET {}, updated_at = CURRENT_TIMESTAMP WHERE id = $1",
-        updates.join(", ")
-    );
+    let previous = match db::get_document(&state.db_pool, &id).await {
+        Ok(doc) => doc,
+        Err(e) => return document_error_response(e, "update document"),
+    };
+    let tags = req
+        .tags
+        .map(|tags| serde_json::to_string(&tags).unwrap_or_else(|_| "[]".to_string()));
+
+    match db::update_document(&state.db_pool, &id, req.title, req.content, None, tags).await {
+        Ok(doc) => {
+            let message = if doc.content != previous.content {
+                reindex_after_change(&state, &id, "Document updated").await
+            } else {
+                "Document updated successfully".to_string()
+            };
+            Json(ApiResponse::message(message)).into_response()
+        }
+        Err(e) => document_error_response(e, "update document"),
+    }
+}
 
-    let result = sqlx::query(&query).bind(id).execute(&state.db_pool).await;
+/// List a document's versions, oldest first
+pub async fn list_document_versions(
+    State(state): State<Arc<ApiState>>,
+    Path(id): Path<String>,
+) -> impl IntoResponse {
+    match db::list_document_versions(&state.db_pool, &id).await {
+        Ok(versions) => {
+            let summaries: Vec<DocumentVersionSummary> = versions
+                .into_iter()
+                .map(|v| DocumentVersionSummary {
+                    version: v.version,
+                    title: v.title,
+                    doc_type: v.doc_type,
+                    word_count: v.content.split_whitespace().count(),
+                    created_at: timestamp_or_now(v.created_at),
+                    is_current: v.is_current,
+                })
+                .collect();
+            Json(ApiResponse::success(summaries)).into_response()
+        }
+        Err(e) => document_error_response(e, "list document versions"),
+    }
+}
 
-    match result {
-        Ok(result) if result.rows_affected() > 0 => Json(ApiResponse::message(
-            "Document updated successfully".to_string(),
-        ))
-        .into_response(),
-        Ok(_) => (
-            StatusCode::NOT_FOUND,
-            Json(ApiResponse::<()>::error("Document not found".to_string())),
-        )
-            .into_response(),
-        Err(e) => (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ApiResponse::<()>::error(format!(
-                "Failed to update document: {}",
-                e
-            ))),
-        )
-            .into_response(),
+/// Get one version of a document
+pub async fn get_document_version(
+    State(state): State<Arc<ApiState>>,
+    Path((id, version)): Path<(String, i32)>,
+) -> impl IntoResponse {
+    match db::get_document_version(&state.db_pool, &id, version).await {
+        Ok(v) => {
+            let response = DocumentVersionResponse {
+                document_id: v.document_id,
+                version: v.version,
+                title: v.title,
+                content: v.content,
+                content_type: v.content_type,
+                doc_type: v.doc_type,
+                tags: parse_tags(v.tags.as_deref()),
+                source_url: v.source_url,
+                created_at: timestamp_or_now(v.created_at),
+                is_current: v.is_current,
+            };
+            Json(ApiResponse::success(response)).into_response()
+        }
+        Err(e) => document_error_response(e, "fetch document version"),
+    }
+}
+
+/// Restore an old version as the new current version and re-index it
+pub async fn revert_document(
+    State(state): State<Arc<ApiState>>,
+    Path((id, version)): Path<(String, i32)>,
+) -> impl IntoResponse {
+    match db::revert_document(&state.db_pool, &id, version).await {
+        Ok(_) => {
+            let message =
+                reindex_after_change(&state, &id, &format!("Reverted to version {}", version))
+                    .await;
+            Json(ApiResponse::message(message)).into_response()
+        }
+        Err(e) => document_error_response(e, "revert document"),
     }
 }
 
+/// Queue a forced re-index of the latest version
+async fn reindex_after_change(state: &ApiState, id: &str, done: &str) -> String {
+    match state.job_queue.submit_job(vec![id.to_string()], true).await {
+        Ok(job_id) => format!("{}. Indexing job: {}", done, job_id),
+        Err(e) => format!("{} but not queued for indexing: {}", done, e),
+    }
+}
+
+/// Tags are stored as a JSON array by the API and comma-separated by the CLI
+fn parse_tags(tags: Option<&str>) -> Vec<String> {
+    let Some(tags) = tags.filter(|t| !t.trim().is_empty()) else {
+        return Vec::new();
+    };
+    serde_json::from_str(tags).unwrap_or_else(|_| {
+        tags.split(',')
+            .map(|t| t.trim().to_string())
+            .filter(|t| !t.is_empty())
+            .collect()
+    })
+}
+
+fn timestamp_or_now(ts: i64) -> chrono::DateTime<chrono::Utc> {
+    chrono::DateTime::from_timestamp(ts, 0).unwrap_or_else(chrono::Utc::now)
+}
+
+fn document_error_response(e: db::DbError, action: &str) -> axum::response::Response {
+    let status = match e {
+        db::DbError::NotFound(_) => StatusCode::NOT_FOUND,
+        db::DbError::InvalidInput(_) => StatusCode::BAD_REQUEST,
+        db::DbError::Sqlx(_) => StatusCode::INTERNAL_SERVER_ERROR,
+    };
+    (
+        status,
+        Json(ApiResponse::<()>::error(format!(
+            "Failed to {}: {}",
+            action, e
+        ))),
+    )
+        .into_response()
+}
+
 /// Delete document
 pub async fn delete_document(
     State(state): State<Arc<ApiState>>,
@@ -579,10 +1029,24 @@ pub async fn search_documents(
 ) -> impl IntoResponse {
     let start_time = Instant::now();
 
+    if let Some(alpha) = req.alpha {
+        if !(0.0..=1.0).contains(&alpha) {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(ApiResponse::<()>::error(format!(
+                    "alpha must be between 0.0 and 1.0, got {}",
+                    alpha
+                ))),
+            )
+                .into_response();
+        }
+    }
+
     // Build search filters
     let filters = SearchFilters {
         doc_type: req.filters.doc_type.clone(),
         tags: req.filters.tags.clone(),
+        tag_expression: req.filters.tag_expression.clone(),
         repo_id: req.filters.repo_id,
         source_type: req.filters.source_type.clone(),
         indexed_only: req.filters.indexed_only.unwrap_or(false),
@@ -590,37 +1054,51 @@ pub async fn search_documents(
         created_before: req.filters.date_to.map(|dt| dt.timestamp()),
     };
 
+    // Reranking retrieves a larger candidate set and trims it afterwards
+    let reranker = state.reranker.as_ref().filter(|_| req.rerank);
+    let limit = if req.limit == 0 {
+        state.searcher.config().default_top_k
+    } else {
+        req.limit
+    };
+
     // Build search query
     let query = SearchQuery {
         text: req.query.clone(),
-        top_k: req.limit,
+        top_k: reranker.map_or(req.limit, |r| r.config().candidate_count(limit)),
         filters,
     };
 
-    // Perform search
-    let results = state.searcher.search(&state.db_pool, &query).await;
+    // Perform search; an explicit alpha opts into weighted hybrid fusion
+    let results = match (&req.search_type, req.alpha) {
+        (SearchType::Hybrid, Some(alpha)) => {
+            state
+                .searcher
+                .hybrid_search_weighted(&state.db_pool, &query, alpha)
+                .await
+        }
+        _ => state.searcher.search(&state.db_pool, &query).await,
+    };
+
+    let results = match (results, reranker) {
+        (Ok(results), Some(reranker)) => {
+            let outcome = reranker.rerank(&req.query, results, limit).await;
+            Ok((outcome.results, outcome.reranked))
+        }
+        (results, _) => results.map(|results| (results, false)),
+    };
 
     match results {
-        Ok(search_results) => {
+        Ok((search_results, reranked)) => {
             let execution_time = start_time.elapsed().as_millis() as u64;
 
+            let snippets = SnippetConfig {
+                highlight: req.highlight,
+                ..SnippetConfig::default()
+            };
             let items: Vec<SearchResultItem> = search_results
                 .iter()
-                .map(|r| {
-                    let tags: Vec<String> = vec![];
-
-                    SearchResultItem {
-                        document_id: r.document_id.parse().unwrap_or(0),
-                        chunk_id: r.chunk_id.parse().unwrap_or(0),
-                        title: format!("Document {}", r.document_id),
-                        content: r.content.clone(),
-                        doc_type: "document".to_string(),
-                        score: r.score,
-                        tags,
-                        source_url: None,
-                        created_at: chrono::Utc::now(),
-                    }
-                })
+                .map(|r| search_result_item(r, &req.query, &snippets))
                 .collect();
 
             let response = SearchResponse {
@@ -628,6 +1106,7 @@ pub async fn search_documents(
                 total_results: search_results.len(),
                 search_type: req.search_type,
                 query: req.query,
+                reranked,
                 execution_time_ms: execution_time,
             };
 
@@ -641,6 +1120,28 @@ pub async fn search_documents(
     }
 }
 
+fn search_result_item(r: &SearchResult, query: &str, snippets: &SnippetConfig) -> SearchResultItem {
+    SearchResultItem {
+        document_id: r.document_id.parse().unwrap_or(0),
+        chunk_id: r.chunk_id.parse().unwrap_or(0),
+        title: format!("Document {}", r.document_id),
+        content: r.content.clone(),
+        snippet: extract_snippet(r, query, snippets),
+        chunk_index: r.chunk_index,
+        heading: r.heading.clone(),
+        char_start: r.char_start,
+        char_end: r.char_end,
+        doc_type: "document".to_string(),
+        score: r.score,
+        semantic_score: r.metadata.semantic_score,
+        keyword_score: r.metadata.keyword_score,
+        tags: r.tags.clone().unwrap_or_default(),
+        matched_tags: r.matched_tags.clone(),
+        source_url: None,
+        created_at: chrono::Utc::now(),
+    }
+}
+
 // ============================================================================
 // Indexing
 // ============================================================================
@@ -651,10 +1152,20 @@ pub async fn index_document(
     Json(req): Json<IndexDocumentRequest>,
 ) -> impl IntoResponse {
     let doc_id = req.document_id.clone();
-    let job_id = state
+    let job_id = match state
         .job_queue
         .submit_job(vec![req.document_id], req.force_reindex)
-        .await;
+        .await
+    {
+        Ok(job_id) => job_id,
+        Err(e) => {
+            return (
+                StatusCode::SERVICE_UNAVAILABLE,
+                Json(ApiResponse::<()>::error(e)),
+            )
+                .into_response()
+        }
+    };
 
     let response = IndexJobResponse {
         job_id,
@@ -681,10 +1192,20 @@ pub async fn batch_index_documents(
             .into_response();
     }
 
-    let job_id = state
+    let job_id = match state
         .job_queue
         .submit_job(req.document_ids.clone(), req.force_reindex)
-        .await;
+        .await
+    {
+        Ok(job_id) => job_id,
+        Err(e) => {
+            return (
+                StatusCode::SERVICE_UNAVAILABLE,
+                Json(ApiResponse::<()>::error(e)),
+            )
+                .into_response()
+        }
+    };
 
     let response = IndexJobResponse {
         job_id,
@@ -711,6 +1232,7 @@ pub async fn get_index_job_status(
                     super::jobs::JobStatus::Completed => IndexJobStatus::Completed,
                     super::jobs::JobStatus::Failed => IndexJobStatus::Failed,
                     super::jobs::JobStatus::Cancelled => IndexJobStatus::Failed,
+                    super::jobs::JobStatus::Interrupted => IndexJobStatus::Interrupted,
                 },
                 documents_total: job.progress.total,
                 documents_completed: job.progress.completed,
@@ -764,3 +1286,331 @@ pub async fn cancel_index_job(
         Err(e) => (StatusCode::BAD_REQUEST, Json(ApiResponse::<()>::error(e))).into_response(),
     }
 }
+
+// ============================================================================
+// Queue
+// ============================================================================
+
+/// List queue items
+///
+/// `GET /queue?stage=failed&priority=high&limit=50`
+///
+/// Response: `ApiResponse<Vec<QueueItem>>`, ordered by priority then age:
+///
+/// ```json
+/// { "success": true, "data": [ { "id": "…", "content": "…", "stage": "failed",
+///   "source": "note", "priority": 2, "retry_count": 1, "max_retries": 3,
+///   "last_error": "…", "created_at": 1700000000, "updated_at": 1700000000, … } ] }
+/// ```
+///
+/// Unknown `stage` or `priority` values return `400`.
+pub async fn list_queue(
+    State(state): State<Arc<ApiState>>,
+    Query(params): Query<QueueListQuery>,
+) -> impl IntoResponse {
+    let stage = match params.stage.as_deref().map(str::parse::<QueueStage>) {
+        Some(Err(e)) => {
+            return (StatusCode::BAD_REQUEST, Json(ApiResponse::<()>::error(e))).into_response()
+        }
+        other => other.and_then(Result::ok),
+    };
+    let priority = match params.priority.as_deref().map(str::parse::<QueuePriority>) {
+        Some(Err(e)) => {
+            return (StatusCode::BAD_REQUEST, Json(ApiResponse::<()>::error(e))).into_response()
+        }
+        other => other.and_then(Result::ok),
+    };
+
+    let limit = params.limit.min(500) as i32;
+    match crate::queue::list_queue_items(&state.db_pool, stage, priority, limit).await {
+        Ok(items) => Json(ApiResponse::success(items)).into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::<()>::error(format!(
+                "Failed to list queue: {}",
+                e
+            ))),
+        )
+            .into_response(),
+    }
+}
+
+/// Stream queue changes as server-sent events
+///
+/// `GET /queue/events`
+///
+/// Each event is named after its [`crate::queue::QueueEventKind`] (`enqueued`,
+/// `stage_changed`, `completed`, `failed`, `resync`), has its sequence
+/// number as the SSE id and a [`crate::queue::QueueEvent`] JSON payload:
+///
+/// ```text
+/// id: 42
+/// event: stage_changed
+/// data: {"seq":42,"kind":"stage_changed","item_id":"…","stage":"analyzing","at":1700000000}
+/// ```
+///
+/// Browsers resend the last id as `Last-Event-ID` when reconnecting and get
+/// the events they missed replayed; see [`crate::queue::events`] for the
+/// at-least-once delivery rules. The stream ends when the client disconnects.
+pub async fn queue_events(headers: axum::http::HeaderMap) -> impl IntoResponse {
+    use axum::response::sse::{Event, KeepAlive, Sse};
+    use futures::StreamExt;
+
+    let last_seen = headers
+        .get("last-event-id")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.trim().parse::<u64>().ok());
+
+    let events = QueueEventBus::global().stream(last_seen).map(|event| {
+        let data = serde_json::to_string(&event).unwrap_or_else(|_| "{}".to_string());
+        Ok::<Event, std::convert::Infallible>(
+            Event::default()
+                .id(event.seq.to_string())
+                .event(event.kind.as_str())
+                .data(data),
+        )
+    });
+
+    Sse::new(events).keep_alive(KeepAlive::default())
+}
+
+/// Add content to the processing queue
+///
+/// `POST /queue` with an [`EnqueueRequest`] body.
+///
+/// Response: `201` with `ApiResponse<QueueItem>` — the new item in the `inbox`
+/// stage, or the existing item if identical content is already queued.
+pub async fn enqueue_item(
+    State(state): State<Arc<ApiState>>,
+    Json(request): Json<EnqueueRequest>,
+) -> impl IntoResponse {
+    if request.content.trim().is_empty() {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::<()>::error(
+                "Content must not be empty".to_string(),
+            )),
+        )
+            .into_response();
+    }
+
+    let source = match request.source.as_deref().map(str::parse::<QueueSource>) {
+        Some(Ok(source)) => source,
+        Some(Err(e)) => {
+            return (StatusCode::BAD_REQUEST, Json(ApiResponse::<()>::error(e))).into_response()
+        }
+        None => QueueSource::Note,
+    };
+    let priority = match request.priority.as_deref().map(str::parse::<QueuePriority>) {
+        Some(Ok(priority)) => priority,
+        Some(Err(e)) => {
+            return (StatusCode::BAD_REQUEST, Json(ApiResponse::<()>::error(e))).into_response()
+        }
+        None => QueuePriority::Normal,
+    };
+
+    match crate::queue::enqueue(
+        &state.db_pool,
+        &request.content,
+        source,
+        priority,
+        request.repo_id.as_deref(),
+        request.file_path.as_deref(),
+        request.line_number,
+    )
+    .await
+    {
+        Ok(item) => (StatusCode::CREATED, Json(ApiResponse::success(item))).into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::<()>::error(format!(
+                "Failed to enqueue item: {}",
+                e
+            ))),
+        )
+            .into_response(),
+    }
+}
+
+/// Remove an item from the queue
+///
+/// `DELETE /queue/:id`
+///
+/// Response: `ApiResponse<()>` with a confirmation `message`, or `404` if the
+/// item does not exist.
+pub async fn delete_queue_item(
+    State(state): State<Arc<ApiState>>,
+    Path(id): Path<String>,
+) -> impl IntoResponse {
+    match crate::queue::delete_queue_item(&state.db_pool, &id).await {
+        Ok(true) => Json(ApiResponse::message(
+            "Queue item deleted successfully".to_string(),
+        ))
+        .into_response(),
+        Ok(false) => (
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::<()>::error("Queue item not found".to_string())),
+        )
+            .into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::<()>::error(format!(
+                "Failed to delete queue item: {}",
+                e
+            ))),
+        )
+            .into_response(),
+    }
+}
+
+/// Retry a failed or dead-letter queue item
+///
+/// `POST /queue/:id/retry`
+///
+/// Response: `ApiResponse<QueueItem>` with the item back in `pending_analysis`.
+/// Dead-letter items get their retry count reset. Returns `404` for unknown ids
+/// and `409` if the item is not in the `failed` or `dead_letter` stage.
+pub async fn retry_queue_item(
+    State(state): State<Arc<ApiState>>,
+    Path(id): Path<String>,
+) -> impl IntoResponse {
+    let item = match crate::queue::get_queue_item(&state.db_pool, &id).await {
+        Ok(item) => item,
+        Err(e) if matches!(e.downcast_ref(), Some(sqlx::Error::RowNotFound)) => {
+            return (
+                StatusCode::NOT_FOUND,
+                Json(ApiResponse::<()>::error("Queue item not found".to_string())),
+            )
+                .into_response()
+        }
+        Err(e) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::<()>::error(format!(
+                    "Failed to load queue item: {}",
+                    e
+                ))),
+            )
+                .into_response()
+        }
+    };
+
+    let retried = match crate::queue::retry_queue_item(&state.db_pool, &id).await {
+        Ok(retried) => retried,
+        Err(e) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::<()>::error(format!(
+                    "Failed to retry queue item: {}",
+                    e
+                ))),
+            )
+                .into_response()
+        }
+    };
+
+    if !retried {
+        return (
+            StatusCode::CONFLICT,
+            Json(ApiResponse::<()>::error(format!(
+                "Queue item is in stage '{}' and cannot be retried",
+                item.stage.as_str()
+            ))),
+        )
+            .into_response();
+    }
+
+    match crate::queue::get_queue_item(&state.db_pool, &id).await {
+        Ok(item) => Json(ApiResponse::success(item)).into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::<()>::error(format!(
+                "Failed to load queue item: {}",
+                e
+            ))),
+        )
+            .into_response(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(name: &str, status: &str, critical: bool) -> DependencyCheck {
+        DependencyCheck {
+            name: name.to_string(),
+            status: status.to_string(),
+            critical,
+            latency_ms: 1,
+            error: None,
+        }
+    }
+
+    #[test]
+    fn test_readiness_gated_by_critical_checks() {
+        let (code, body) = readiness_response(vec![
+            check("database", "up", true),
+            check("llm", "down", false),
+        ]);
+        assert_eq!(code, StatusCode::OK);
+        assert_eq!(body.status, "ready");
+
+        let (code, _) = readiness_response(vec![
+            check("database", "up", true),
+            check("llm", "skipped", true),
+        ]);
+        assert_eq!(code, StatusCode::OK);
+
+        let (code, body) = readiness_response(vec![
+            check("database", "down", true),
+            check("llm", "up", false),
+        ]);
+        assert_eq!(code, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(body.status, "not_ready");
+        assert_eq!(body.checks.len(), 2);
+    }
+
+    #[test]
+    fn test_keyword_result_highlights_matched_term() {
+        let result = SearchResult {
+            document_id: "doc-1".to_string(),
+            chunk_id: "7".to_string(),
+            chunk_index: 3,
+            content: "Chunks are embedded in batches. Failed embeddings are retried.".to_string(),
+            score: 1.0,
+            title: Some("Indexing".to_string()),
+            doc_type: None,
+            tags: None,
+            matched_tags: Vec::new(),
+            heading: Some("Retries".to_string()),
+            char_start: 480,
+            char_end: 542,
+            metadata: crate::search::SearchResultMetadata {
+                model: "keyword".to_string(),
+                dimension: 0,
+                semantic_match: false,
+                keyword_match: true,
+                semantic_score: None,
+                keyword_score: Some(1.0),
+            },
+        };
+
+        let item = search_result_item(&result, "embeddings", &SnippetConfig::default());
+        assert!(item.snippet.matched);
+        assert_eq!(
+            item.snippet.text,
+            "Chunks are embedded in batches. Failed <mark>embeddings</mark> are retried."
+        );
+        assert_eq!((item.char_start, item.char_end), (480, 542));
+        assert_eq!((item.snippet.char_start, item.snippet.char_end), (480, 542));
+        assert_eq!(item.chunk_index, 3);
+
+        let plain = SnippetConfig {
+            highlight: false,
+            ..SnippetConfig::default()
+        };
+        let item = search_result_item(&result, "embeddings", &plain);
+        assert!(!item.snippet.text.contains("<mark>"));
+    }
+}
@@ -4,38 +4,49 @@
 //! - Document management (upload, read, update, delete)
 //! - Semantic search (hybrid, semantic-only, keyword)
 //! - Background indexing with job queue
-//! - Authentication and rate limiting
+//! - Authentication, rate limiting and CORS
+//! - Processing queue inspection and control
 //! - System statistics and health checks
+//! - Auth and rate-limit reload on `SIGHUP` (see [`reload`])
 
 pub mod admin;
 pub mod auth;
+pub mod bulk;
+pub mod cors;
 pub mod handlers;
 pub mod jobs;
 pub mod proxy;
 pub mod proxy_client;
 pub mod rate_limit;
+pub mod reload;
 pub mod repos;
 pub mod types;
 
 use axum::{
+    extract::DefaultBodyLimit,
     middleware,
     routing::{delete, get, post, put},
     Router,
 };
 use std::sync::Arc;
 
-use crate::embeddings::{EmbeddingConfig, EmbeddingGenerator};
+use crate::embeddings::EmbeddingGenerator;
 use crate::indexing::IndexingConfig;
 use sqlx::PgPool;
 
-pub use auth::{generate_api_key, hash_api_key, AuthConfig, AuthResult};
-pub use handlers::ApiState;
+pub use auth::{
+    generate_api_key, hash_api_key, ApiKeyScope, AuthConfig, AuthResult, AuthenticatedKey,
+    Authorized,
+};
+pub use cors::CorsConfig;
+pub use handlers::{ApiState, ReadinessConfig};
 pub use jobs::{JobQueue, JobQueueConfig, JobStatus};
 pub use proxy::{proxy_router, ProxyState};
 pub use proxy_client::{
     ChatMessage, ChatReply, ChatRequestBuilder, ProxyClient, ProxyClientConfig,
 };
 pub use rate_limit::{RateLimitConfig, RateLimiter};
+pub use reload::{ApiConfigFile, ApiReloadHandle, Reloadable, API_CONFIG_ENV};
 pub use types::*;
 
 // ============================================================================
@@ -47,12 +58,70 @@ pub async fn create_api_router(
     db_pool: PgPool,
     auth_config: AuthConfig,
     rate_limit_config: RateLimitConfig,
+    cors_config: CorsConfig,
     indexing_config: IndexingConfig,
     job_queue_config: JobQueueConfig,
 ) -> Router {
-    // Initialize embedding generator
+    create_api_router_with_queue(
+        db_pool,
+        auth_config,
+        rate_limit_config,
+        cors_config,
+        indexing_config,
+        job_queue_config,
+    )
+    .await
+    .0
+}
+
+/// Create the API router and return its job queue as well
+///
+/// Hosts should call [`JobQueue::drain`] on the returned queue once the
+/// server stops accepting connections (see
+/// [`serve_until_shutdown`](crate::server::serve_until_shutdown)), so
+/// unfinished indexing jobs are persisted and resumed on the next start.
+pub async fn create_api_router_with_queue(
+    db_pool: PgPool,
+    auth_config: AuthConfig,
+    rate_limit_config: RateLimitConfig,
+    cors_config: CorsConfig,
+    indexing_config: IndexingConfig,
+    job_queue_config: JobQueueConfig,
+) -> (Router, Arc<JobQueue>) {
+    let (router, job_queue, _) = create_reloadable_api_router(
+        db_pool,
+        auth_config,
+        rate_limit_config,
+        cors_config,
+        indexing_config,
+        job_queue_config,
+    )
+    .await;
+    (router, job_queue)
+}
+
+/// Create the API router, its job queue, and a handle for swapping in new
+/// auth and rate-limit settings while it serves requests
+///
+/// Call [`ApiReloadHandle::spawn_sighup_listener`] on the handle to reload
+/// them from a config file on `SIGHUP`.
+pub async fn create_reloadable_api_router(
+    db_pool: PgPool,
+    auth_config: AuthConfig,
+    rate_limit_config: RateLimitConfig,
+    cors_config: CorsConfig,
+    mut indexing_config: IndexingConfig,
+    job_queue_config: JobQueueConfig,
+) -> (Router, Arc<JobQueue>, ApiReloadHandle) {
+    // Compare the stored index fingerprint with the active embedding model
+    if let Err(e) = indexing_config.load_index_metadata(&db_pool).await {
+        tracing::warn!("Could not check index metadata: {}", e);
+    }
+
+    // Initialize embedding generator with the backend named in the indexing config
     let embedding_generator = Arc::new(tokio::sync::Mutex::new(
-        EmbeddingGenerator::new(EmbeddingConfig::default()).unwrap(),
+        EmbeddingGenerator::new(indexing_config.embedding_config.clone())
+            .expect("Failed to create embedding generator"),
     ));
 
     // Create API state
@@ -66,23 +135,82 @@ pub async fn create_api_router(
         .await,
     );
 
+    // Resume jobs interrupted by the previous shutdown
+    let job_queue = api_state.job_queue.clone();
+    {
+        let job_queue = job_queue.clone();
+        tokio::spawn(async move {
+            if let Err(e) = job_queue.resume_pending().await {
+                tracing::warn!("Could not resume interrupted indexing jobs: {}", e);
+            }
+        });
+    }
+
     // Create rate limiter
     let rate_limiter = Arc::new(RateLimiter::new(rate_limit_config));
 
     // Create auth config
-    let auth_config = Arc::new(auth_config);
+    let auth_config = Arc::new(Reloadable::new(auth_config));
+    let reload_handle = ApiReloadHandle {
+        auth: auth_config.clone(),
+        rate_limiter: rate_limiter.clone(),
+    };
+
+    let router = api_routes().with_state(api_state);
+
+    let router = apply_middleware(router, rate_limiter, auth_config, &cors_config);
+
+    (router, job_queue, reload_handle)
+}
 
-    // Build router
-    let router = Router::new()
+/// All API routes, without middleware
+///
+/// Reads, search and indexing are open to `ReadOnly` keys; everything
+/// that creates, changes or removes data needs `ReadWrite`, and
+/// `/admin/*` needs `Admin`.
+fn api_routes() -> Router<Arc<ApiState>> {
+    let writes = Router::new()
+        // Documents
+        .route("/documents", post(handlers::upload_document))
+        .route(
+            "/documents/bulk",
+            post(handlers::bulk_upload_documents)
+                .layer(DefaultBodyLimit::max(bulk::MAX_BULK_UPLOAD_BYTES)),
+        )
+        .route("/documents/:id", put(handlers::update_document))
+        .route("/documents/:id", delete(handlers::delete_document))
+        .route(
+            "/documents/:id/revert/:version",
+            post(handlers::revert_document),
+        )
+        // Indexing
+        .route(
+            "/index/jobs/:job_id/cancel",
+            post(handlers::cancel_index_job),
+        )
+        // Processing queue
+        .route("/queue", post(handlers::enqueue_item))
+        .route("/queue/:id", delete(handlers::delete_queue_item))
+        .route("/queue/:id/retry", post(handlers::retry_queue_item))
+        .route_layer(middleware::from_fn(auth::require_read_write));
+
+    Router::new()
         // Health & Stats
         .route("/health", get(handlers::health_check))
+        .route("/health/live", get(handlers::liveness_check))
+        .route("/health/ready", get(handlers::readiness_check))
         .route("/stats", get(handlers::get_stats))
         // Documents
-        .route("/documents", post(handlers::upload_document))
         .route("/documents", get(handlers::list_documents))
         .route("/documents/:id", get(handlers::get_document))
-        .route("/documents/:id", put(handlers::update_document))
-        .route("/documents/:id", delete(handlers::delete_document))
+        .route(
+            "/documents/:id/versions",
+            get(handlers::list_document_versions),
+        )
+        .route(
+            "/documents/:id/versions/:version",
+            get(handlers::get_document_version),
+        )
         // Search
         .route("/search", post(handlers::search_documents))
         // Indexing
@@ -90,14 +218,21 @@ pub async fn create_api_router(
         .route("/index/batch", post(handlers::batch_index_documents))
         .route("/index/jobs", get(handlers::list_index_jobs))
         .route("/index/jobs/:job_id", get(handlers::get_index_job_status))
-        .route(
-            "/index/jobs/:job_id/cancel",
-            post(handlers::cancel_index_job),
-        )
+        // Processing queue
+        .route("/queue", get(handlers::list_queue))
+        .route("/queue/events", get(handlers::queue_events))
+        .merge(writes)
         .merge(admin::admin_router())
-        .with_state(api_state);
+}
 
-    // Apply middleware (rate limiting, then auth)
+/// Wrap the routes in rate limiting, then auth, then CORS. CORS is
+/// outermost so preflight requests are answered before auth runs.
+fn apply_middleware(
+    router: Router,
+    rate_limiter: Arc<RateLimiter>,
+    auth_config: Arc<Reloadable<AuthConfig>>,
+    cors_config: &CorsConfig,
+) -> Router {
     router
         .layer(middleware::from_fn_with_state(
             rate_limiter,
@@ -107,6 +242,7 @@ pub async fn create_api_router(
             auth_config,
             auth::auth_middleware,
         ))
+        .layer(cors_config.layer())
 }
 
 /// Create API router with default configuration
@@ -115,6 +251,7 @@ pub async fn create_default_api_router(db_pool: PgPool) -> Router {
         db_pool,
         AuthConfig::default(),
         RateLimitConfig::default(),
+        CorsConfig::default(),
         IndexingConfig::default(),
         JobQueueConfig::default(),
     )
@@ -130,6 +267,7 @@ pub async fn create_default_api_router(db_pool: PgPool) -> Router {
 pub struct ApiConfig {
     pub auth: AuthConfig,
     pub rate_limit: RateLimitConfig,
+    pub cors: CorsConfig,
     pub indexing: IndexingConfig,
     pub job_queue: JobQueueConfig,
 }
@@ -139,11 +277,12 @@ impl ApiConfig {
     pub fn production() -> Self {
         Self {
             auth: AuthConfig {
-                api_keys: Vec::new(),
+                api_keys: Default::default(),
                 require_auth: true,
                 allow_anonymous_read: false,
             },
             rate_limit: RateLimitConfig::strict(),
+            cors: CorsConfig::restrictive(),
             indexing: IndexingConfig::default(),
             job_queue: JobQueueConfig::default(),
         }
@@ -154,11 +293,21 @@ impl ApiConfig {
         Self {
             auth: AuthConfig::default(),
             rate_limit: RateLimitConfig::permissive(),
+            cors: CorsConfig::permissive(),
             indexing: IndexingConfig::default(),
             job_queue: JobQueueConfig::default(),
         }
     }
 
+    /// Take the hot-reloadable auth and rate-limit settings from a config
+    /// file, keeping the other settings
+    pub fn with_config_file(mut self, path: impl AsRef<std::path::Path>) -> anyhow::Result<Self> {
+        let file = ApiConfigFile::load(path)?;
+        self.auth = file.auth_config();
+        self.rate_limit = file.rate_limit_config();
+        Ok(self)
+    }
+
     /// Add API key
     pub fn with_api_key(mut self, key: String) -> Self {
         self.auth.add_key(&key);
@@ -171,6 +320,12 @@ impl ApiConfig {
         self
     }
 
+    /// Allow cross-origin calls from a browser app at `origin`
+    pub fn with_cors_origin(mut self, origin: impl Into<String>) -> Self {
+        self.cors = self.cors.with_origin(origin);
+        self
+    }
+
     /// Enable anonymous read access
     pub fn allow_anonymous_read(mut self) -> Self {
         self.auth.allow_anonymous_read = true;
@@ -179,10 +334,34 @@ impl ApiConfig {
 
     /// Build router with this configuration
     pub async fn build_router(self, db_pool: PgPool) -> Router {
-        create_api_router(
+        self.build_router_with_queue(db_pool).await.0
+    }
+
+    /// Build router with this configuration and return its job queue, for
+    /// hosts to [`drain`](JobQueue::drain) on shutdown
+    pub async fn build_router_with_queue(self, db_pool: PgPool) -> (Router, Arc<JobQueue>) {
+        create_api_router_with_queue(
             db_pool,
             self.auth,
             self.rate_limit,
+            self.cors,
+            self.indexing,
+            self.job_queue,
+        )
+        .await
+    }
+
+    /// Build router with this configuration, returning its job queue and a
+    /// handle for reloading the auth and rate-limit settings
+    pub async fn build_reloadable_router(
+        self,
+        db_pool: PgPool,
+    ) -> (Router, Arc<JobQueue>, ApiReloadHandle) {
+        create_reloadable_api_router(
+            db_pool,
+            self.auth,
+            self.rate_limit,
+            self.cors,
             self.indexing,
             self.job_queue,
         )
@@ -214,4 +393,140 @@ mod tests {
         assert!(config.auth.require_auth);
         assert!(!config.auth.allow_anonymous_read);
     }
+
+    #[tokio::test]
+    async fn test_read_only_key_cannot_reach_mutating_routes() {
+        use axum::{body::Body, http::Request, http::StatusCode};
+        use tower::ServiceExt;
+
+        // The pool never connects: every request here is turned away
+        // before a handler runs
+        let pool = PgPool::connect_lazy("postgres://localhost/unused").unwrap();
+        let indexing_config = IndexingConfig::default();
+        let embedding_generator = Arc::new(tokio::sync::Mutex::new(
+            EmbeddingGenerator::new(indexing_config.embedding_config.clone()).unwrap(),
+        ));
+        let state = Arc::new(
+            ApiState::new(
+                pool,
+                embedding_generator,
+                indexing_config,
+                JobQueueConfig::default(),
+            )
+            .await,
+        );
+        let mut auth = AuthConfig::default();
+        auth.add_key("ro_reader");
+        let app = apply_middleware(
+            api_routes().with_state(state),
+            Arc::new(RateLimiter::new(RateLimitConfig::permissive())),
+            Arc::new(Reloadable::new(auth)),
+            &CorsConfig::default(),
+        );
+
+        let mutating = [
+            ("POST", "/documents"),
+            ("POST", "/documents/bulk"),
+            ("PUT", "/documents/doc-1"),
+            ("DELETE", "/documents/doc-1"),
+            ("POST", "/documents/doc-1/revert/1"),
+            ("POST", "/index/jobs/job-1/cancel"),
+            ("POST", "/queue"),
+            ("DELETE", "/queue/item-1"),
+            ("POST", "/queue/item-1/retry"),
+            ("GET", "/admin/stats"),
+        ];
+        for (method, uri) in mutating {
+            let resp = app
+                .clone()
+                .oneshot(
+                    Request::builder()
+                        .method(method)
+                        .uri(uri)
+                        .header("X-API-Key", "ro_reader")
+                        .header("Content-Type", "application/json")
+                        .body(Body::from("{}"))
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            assert_eq!(resp.status(), StatusCode::FORBIDDEN, "{} {}", method, uri);
+        }
+
+        // Reads stay open to the same key
+        let resp = app
+            .oneshot(
+                Request::builder()
+                    .uri("/index/jobs/job-1")
+                    .header("X-API-Key", "ro_reader")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_cors_allows_configured_origin_and_skips_auth_for_preflight() {
+        use axum::{body::Body, http::Request, http::StatusCode};
+        use tower::ServiceExt;
+
+        let config = ApiConfig::production().with_cors_origin("https://dash.example.com");
+        let mut auth = config.auth.clone();
+        auth.add_key("ad_secret");
+        let app = apply_middleware(
+            Router::new().route("/stats", get(|| async { "ok" })),
+            Arc::new(RateLimiter::new(config.rate_limit.clone())),
+            Arc::new(Reloadable::new(auth)),
+            &config.cors,
+        );
+
+        let get_from = |origin: &'static str| {
+            Request::builder()
+                .uri("/stats")
+                .header("Origin", origin)
+                .header("X-API-Key", "ad_secret")
+                .body(Body::empty())
+                .unwrap()
+        };
+
+        let resp = app
+            .clone()
+            .oneshot(get_from("https://dash.example.com"))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(
+            resp.headers()["access-control-allow-origin"],
+            "https://dash.example.com"
+        );
+
+        let resp = app
+            .clone()
+            .oneshot(get_from("https://evil.example.com"))
+            .await
+            .unwrap();
+        assert!(resp.headers().get("access-control-allow-origin").is_none());
+
+        // Preflight carries no API key but must not be rejected by auth
+        let resp = app
+            .oneshot(
+                Request::builder()
+                    .method("OPTIONS")
+                    .uri("/stats")
+                    .header("Origin", "https://dash.example.com")
+                    .header("Access-Control-Request-Method", "POST")
+                    .header("Access-Control-Request-Headers", "x-api-key")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(
+            resp.headers()["access-control-allow-origin"],
+            "https://dash.example.com"
+        );
+    }
 }
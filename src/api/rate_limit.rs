@@ -1,16 +1,20 @@
 //! Rate limiting middleware using token bucket algorithm
 
 use axum::{
-    extract::{Request, State},
+    extract::{ConnectInfo, Request, State},
     http::StatusCode,
     middleware::Next,
     response::{IntoResponse, Response},
 };
 use chrono::{DateTime, Utc};
 use std::collections::HashMap;
+use std::net::SocketAddr;
 use std::sync::Arc;
 use tokio::sync::Mutex;
 
+use super::auth::{ApiKeyScope, AuthenticatedKey, Authorized};
+use super::reload::Reloadable;
+
 // ============================================================================
 // Configuration
 // ============================================================================
@@ -24,15 +28,13 @@ pub struct RateLimitConfig {
     pub window_seconds: u64,
     /// Whether to enable rate limiting
     pub enabled: bool,
+    /// Per-scope overrides of `max_requests` for authenticated keys
+    pub scope_limits: HashMap<ApiKeyScope, u32>,
 }
 
 impl Default for RateLimitConfig {
     fn default() -> Self {
-        Self {
-            max_requests: 100,
-            window_seconds: 60,
-            enabled: true,
-        }
+        Self::new(100, 60)
     }
 }
 
@@ -42,32 +44,37 @@ impl RateLimitConfig {
             max_requests,
             window_seconds,
             enabled: true,
+            scope_limits: HashMap::new(),
         }
     }
 
     pub fn permissive() -> Self {
-        Self {
-            max_requests: 1000,
-            window_seconds: 60,
-            enabled: true,
-        }
+        Self::new(1000, 60)
     }
 
     pub fn strict() -> Self {
-        Self {
-            max_requests: 20,
-            window_seconds: 60,
-            enabled: true,
-        }
+        Self::new(20, 60)
     }
 
     pub fn disabled() -> Self {
         Self {
-            max_requests: 0,
-            window_seconds: 0,
             enabled: false,
+            ..Self::new(0, 0)
         }
     }
+
+    /// Override the per-window limit for keys with the given scope
+    pub fn with_scope_limit(mut self, scope: ApiKeyScope, max_requests: u32) -> Self {
+        self.scope_limits.insert(scope, max_requests);
+        self
+    }
+
+    /// Per-window limit for a request with the given scope
+    pub fn limit_for(&self, scope: Option<ApiKeyScope>) -> u32 {
+        scope
+            .and_then(|s| self.scope_limits.get(&s).copied())
+            .unwrap_or(self.max_requests)
+    }
 }
 
 // ============================================================================
@@ -131,29 +138,91 @@ impl TokenBucket {
             (1.0 - self.tokens) / self.refill_rate
         }
     }
+
+    /// Get time until the bucket is full again (in seconds)
+    fn time_until_full(&self) -> f64 {
+        (self.capacity - self.tokens).max(0.0) / self.refill_rate
+    }
+
+    /// Whether the bucket has sat unused for a whole window. It has refilled
+    /// completely by then, so dropping it loses nothing.
+    fn is_idle(&self, now: DateTime<Utc>, window_seconds: u64) -> bool {
+        now - self.last_refill >= chrono::Duration::seconds(window_seconds as i64)
+    }
 }
 
 // ============================================================================
 // Rate Limiter
 // ============================================================================
 
+/// Per-client buckets, swept for idle ones at most once a window
+struct Buckets {
+    by_client: HashMap<String, TokenBucket>,
+    last_sweep: DateTime<Utc>,
+}
+
+impl Buckets {
+    /// Whether a window has passed since the last sweep
+    fn is_stale(&self, now: DateTime<Utc>, window_seconds: u64) -> bool {
+        now - self.last_sweep >= chrono::Duration::seconds(window_seconds as i64)
+    }
+
+    /// Drop buckets idle for a whole window
+    fn evict_idle(&mut self, now: DateTime<Utc>, window_seconds: u64) {
+        self.by_client
+            .retain(|_, bucket| !bucket.is_idle(now, window_seconds));
+        self.last_sweep = now;
+    }
+}
+
 /// Rate limiter state
 pub struct RateLimiter {
-    config: RateLimitConfig,
-    buckets: Arc<Mutex<HashMap<String, TokenBucket>>>,
+    config: Reloadable<RateLimitConfig>,
+    buckets: Arc<Mutex<Buckets>>,
 }
 
 impl RateLimiter {
     pub fn new(config: RateLimitConfig) -> Self {
         Self {
-            config,
-            buckets: Arc::new(Mutex::new(HashMap::new())),
+            config: Reloadable::new(config),
+            buckets: Arc::new(Mutex::new(Buckets {
+                by_client: HashMap::new(),
+                last_sweep: Utc::now(),
+            })),
         }
     }
 
+    /// Snapshot of the active configuration
+    pub fn config(&self) -> Arc<RateLimitConfig> {
+        self.config.load()
+    }
+
+    /// Swap in a new configuration. Buckets were sized for the old limits,
+    /// so every client starts again with a full budget under the new ones.
+    pub async fn reload(&self, config: RateLimitConfig) {
+        let mut buckets = self.buckets.lock().await;
+        self.config.store(config);
+        buckets.by_client.clear();
+    }
+
     /// Check if request is allowed for the given identifier
     pub async fn check_rate_limit(&self, identifier: &str) -> RateLimitResult {
-        if !self.config.enabled {
+        self.check_rate_limit_scoped(identifier, None).await
+    }
+
+    /// Check if request is allowed, sizing a new bucket by the caller's scope
+    ///
+    /// Each identifier gets its own bucket, so one client exhausting its
+    /// budget does not affect any other. Buckets left idle for a whole
+    /// window are dropped as new requests come in, so the map only holds
+    /// recently active clients.
+    pub async fn check_rate_limit_scoped(
+        &self,
+        identifier: &str,
+        scope: Option<ApiKeyScope>,
+    ) -> RateLimitResult {
+        let config = self.config.load();
+        if !config.enabled {
             return RateLimitResult::Allowed {
                 remaining: u32::MAX,
                 reset_after: 0,
@@ -161,19 +230,27 @@ impl RateLimiter {
         }
 
         let mut buckets = self.buckets.lock().await;
+        let now = Utc::now();
+        if buckets.is_stale(now, config.window_seconds) {
+            buckets.evict_idle(now, config.window_seconds);
+        }
 
         // Get or create bucket
-        let bucket = buckets.entry(identifier.to_string()).or_insert_with(|| {
-            let refill_rate = self.config.max_requests as f64 / self.config.window_seconds as f64;
-            TokenBucket::new(self.config.max_requests, refill_rate)
-        });
+        let bucket = buckets
+            .by_client
+            .entry(identifier.to_string())
+            .or_insert_with(|| {
+                let max_requests = config.limit_for(scope);
+                let refill_rate = max_requests as f64 / config.window_seconds as f64;
+                TokenBucket::new(max_requests, refill_rate)
+            });
 
         // Try to consume a token
         if bucket.try_consume() {
             let remaining = bucket.remaining();
             RateLimitResult::Allowed {
                 remaining,
-                reset_after: 0,
+                reset_after: bucket.time_until_full().ceil() as u64,
             }
         } else {
             let retry_after = bucket.time_until_next_token().ceil() as u64;
@@ -181,20 +258,22 @@ impl RateLimiter {
         }
     }
 
-    /// Clean up old buckets
+    /// Drop buckets idle for a whole window now, rather than on the next
+    /// sweep
     pub async fn cleanup(&self) {
-        let mut buckets = self.buckets.lock().await;
-        let cutoff = Utc::now() - chrono::Duration::seconds(self.config.window_seconds as i64 * 2);
-
-        buckets.retain(|_, bucket| bucket.last_refill > cutoff);
+        let window_seconds = self.config.load().window_seconds;
+        self.buckets
+            .lock()
+            .await
+            .evict_idle(Utc::now(), window_seconds);
     }
 
     /// Get stats
     pub async fn get_stats(&self) -> RateLimitStats {
         let buckets = self.buckets.lock().await;
         RateLimitStats {
-            total_clients: buckets.len(),
-            config: self.config.clone(),
+            total_clients: buckets.by_client.len(),
+            config: (*self.config.load()).clone(),
         }
     }
 }
@@ -218,32 +297,48 @@ pub struct RateLimitStats {
 // ============================================================================
 
 /// Rate limiting middleware
+///
+/// Budgets are tracked per API key that `auth_middleware` validated, with
+/// limits taken from the key's scope. Everything else, including requests
+/// with made-up keys while auth is off, is tracked per client IP at the
+/// default limit, so inventing keys doesn't buy a fresh budget.
 pub async fn rate_limit_middleware(
     State(limiter): State<Arc<RateLimiter>>,
     request: Request,
     next: Next,
 ) -> Response {
-    // Extract identifier (IP address or API key)
+    // Extract identifier (API key or IP address)
     let identifier = extract_identifier(&request);
+    let scope = request
+        .extensions()
+        .get::<Authorized>()
+        .filter(|_| identifier.starts_with("key:"))
+        .map(|authorized| authorized.0);
+    let config = limiter.config();
+    let limit = config.limit_for(scope);
 
     // Check rate limit
-    match limiter.check_rate_limit(&identifier).await {
-        RateLimitResult::Allowed { remaining, .. } => {
+    match limiter.check_rate_limit_scoped(&identifier, scope).await {
+        RateLimitResult::Allowed {
+            remaining,
+            reset_after,
+        } => {
             let mut response = next.run(request).await;
 
             // Add rate limit headers
             let headers = response.headers_mut();
-            headers.insert(
-                "X-RateLimit-Limit",
-                limiter.config.max_requests.to_string().parse().unwrap(),
-            );
+            headers.insert("X-RateLimit-Limit", limit.to_string().parse().unwrap());
             headers.insert(
                 "X-RateLimit-Remaining",
                 remaining.to_string().parse().unwrap(),
             );
+            headers.insert(
+                "X-RateLimit-Reset",
+                reset_after.to_string().parse().unwrap(),
+            );
             headers.insert(
                 "X-RateLimit-Window",
-                limiter.config.window_seconds.to_string().parse().unwrap(),
+                config.window_seconds.to_string().parse().unwrap(),
             );
 
             response
@@ -258,11 +353,12 @@ pub async fn rate_limit_middleware(
             // Add retry-after header
             let headers = response.headers_mut();
             headers.insert("Retry-After", retry_after.to_string().parse().unwrap());
+            headers.insert("X-RateLimit-Limit", limit.to_string().parse().unwrap());
+            headers.insert("X-RateLimit-Remaining", "0".parse().unwrap());
             headers.insert(
-                "X-RateLimit-Limit",
-                limiter.config.max_requests.to_string().parse().unwrap(),
+                "X-RateLimit-Reset",
+                retry_after.to_string().parse().unwrap(),
             );
-            headers.insert("X-RateLimit-Remaining", "0".parse().unwrap());
 
             response
         }
@@ -270,15 +366,12 @@ pub async fn rate_limit_middleware(
 }
 
 /// Extract identifier from request
+///
+/// Only keys the auth layer validated are used, by hash, so raw secrets
+/// never sit in the bucket map.
 fn extract_identifier(request: &Request) -> String {
-    // Try to get API key from headers first
-    if let Some(api_key) = request
-        .headers()
-        .get("X-API-Key")
-        .or_else(|| request.headers().get("Authorization"))
-        .and_then(|v| v.to_str().ok())
-    {
-        return format!("key:{}", api_key);
+    if let Some(AuthenticatedKey(hash)) = request.extensions().get::<AuthenticatedKey>() {
+        return format!("key:{}", hash);
     }
 
     // Fall back to IP address
@@ -300,6 +393,11 @@ fn extract_identifier(request: &Request) -> String {
         return format!("ip:{}", real_ip);
     }
 
+    // Peer address, when the server was started with connect info
+    if let Some(ConnectInfo(addr)) = request.extensions().get::<ConnectInfo<SocketAddr>>() {
+        return format!("ip:{}", addr.ip());
+    }
+
     // Default identifier
     "unknown".to_string()
 }
@@ -311,6 +409,7 @@ fn extract_identifier(request: &Request) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::api::auth::{auth_middleware, AuthConfig};
 
     #[test]
     fn test_token_bucket_consume() {
@@ -416,6 +515,140 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_scope_limits() {
+        let config = RateLimitConfig::new(2, 60).with_scope_limit(ApiKeyScope::Admin, 5);
+        let limiter = RateLimiter::new(config);
+
+        for _ in 0..5 {
+            match limiter
+                .check_rate_limit_scoped("admin", Some(ApiKeyScope::Admin))
+                .await
+            {
+                RateLimitResult::Allowed { .. } => {}
+                _ => panic!("Admin key should get the higher limit"),
+            }
+        }
+
+        for _ in 0..2 {
+            limiter
+                .check_rate_limit_scoped("reader", Some(ApiKeyScope::ReadOnly))
+                .await;
+        }
+        match limiter
+            .check_rate_limit_scoped("reader", Some(ApiKeyScope::ReadOnly))
+            .await
+        {
+            RateLimitResult::RateLimited { .. } => {}
+            _ => panic!("Read-only key should fall back to the default limit"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_middleware_keys_have_independent_budgets() {
+        use axum::{body::Body, middleware, routing::get, Router};
+        use tower::ServiceExt;
+
+        let limiter = Arc::new(RateLimiter::new(RateLimitConfig::new(2, 60)));
+        let auth = AuthConfig::new(vec!["key-a".to_string(), "key-b".to_string()]);
+        let app = Router::new()
+            .route("/", get(|| async { "ok" }))
+            .layer(middleware::from_fn_with_state(
+                limiter,
+                rate_limit_middleware,
+            ))
+            .layer(middleware::from_fn_with_state(
+                Arc::new(Reloadable::new(auth)),
+                auth_middleware,
+            ));
+
+        let call = |key: &'static str| {
+            let app = app.clone();
+            async move {
+                app.oneshot(
+                    Request::builder()
+                        .uri("/")
+                        .header("X-API-Key", key)
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap()
+            }
+        };
+
+        let first = call("key-a").await;
+        assert_eq!(first.status(), StatusCode::OK);
+        assert_eq!(first.headers()["X-RateLimit-Remaining"], "1");
+        assert!(first.headers().contains_key("X-RateLimit-Reset"));
+
+        assert_eq!(call("key-a").await.status(), StatusCode::OK);
+        assert_eq!(call("key-a").await.status(), StatusCode::TOO_MANY_REQUESTS);
+
+        // key-b's budget is untouched by key-a exhausting its own
+        let other = call("key-b").await;
+        assert_eq!(other.status(), StatusCode::OK);
+        assert_eq!(other.headers()["X-RateLimit-Remaining"], "1");
+    }
+
+    #[tokio::test]
+    async fn test_unvalidated_keys_share_the_client_budget() {
+        use axum::{body::Body, middleware, routing::get, Router};
+        use tower::ServiceExt;
+
+        // Auth is off, so any key is let through but none is validated
+        let limiter = Arc::new(RateLimiter::new(RateLimitConfig::new(2, 60)));
+        let app = Router::new()
+            .route("/", get(|| async { "ok" }))
+            .layer(middleware::from_fn_with_state(
+                limiter.clone(),
+                rate_limit_middleware,
+            ))
+            .layer(middleware::from_fn_with_state(
+                Arc::new(Reloadable::new(AuthConfig::default())),
+                auth_middleware,
+            ));
+
+        let mut statuses = Vec::new();
+        for key in ["made-up-1", "made-up-2", "made-up-3"] {
+            let resp = app
+                .clone()
+                .oneshot(
+                    Request::builder()
+                        .uri("/")
+                        .header("X-API-Key", key)
+                        .header("X-Real-IP", "203.0.113.7")
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            statuses.push(resp.status());
+        }
+        assert_eq!(
+            statuses,
+            vec![
+                StatusCode::OK,
+                StatusCode::OK,
+                StatusCode::TOO_MANY_REQUESTS
+            ]
+        );
+        assert_eq!(limiter.get_stats().await.total_clients, 1);
+    }
+
+    #[tokio::test]
+    async fn test_idle_buckets_are_evicted() {
+        let limiter = RateLimiter::new(RateLimitConfig::new(5, 1));
+        for i in 0..10 {
+            limiter.check_rate_limit(&format!("client{}", i)).await;
+        }
+        assert_eq!(limiter.get_stats().await.total_clients, 10);
+
+        tokio::time::sleep(std::time::Duration::from_millis(1100)).await;
+        limiter.check_rate_limit("fresh").await;
+        assert_eq!(limiter.get_stats().await.total_clients, 1);
+    }
+
     #[tokio::test]
     async fn test_cleanup() {
         let config = RateLimitConfig::new(5, 1);
@@ -8,6 +8,8 @@ use axum::{
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+use crate::search::{Snippet, TagExpression};
+
 // ============================================================================
 // Shared Error Type
 // ============================================================================
@@ -60,6 +62,13 @@ impl ApiError {
         }
     }
 
+    pub fn forbidden(msg: impl Into<String>) -> Self {
+        Self {
+            error: msg.into(),
+            code: "FORBIDDEN".to_string(),
+        }
+    }
+
     /// Convenience constructor: map any `Display` error into an internal error.
     pub fn from_error(e: impl std::fmt::Display) -> Self {
         Self::internal(e.to_string())
@@ -70,6 +79,7 @@ impl ApiError {
             "NOT_FOUND" => StatusCode::NOT_FOUND,
             "BAD_REQUEST" => StatusCode::BAD_REQUEST,
             "UNAUTHORIZED" => StatusCode::UNAUTHORIZED,
+            "FORBIDDEN" => StatusCode::FORBIDDEN,
             _ => StatusCode::INTERNAL_SERVER_ERROR,
         }
     }
@@ -228,12 +238,51 @@ pub struct UploadDocumentResponse {
     pub message: String,
 }
 
+/// A document created by a bulk upload
+#[derive(Debug, Clone, Serialize)]
+pub struct BulkUploadedDocument {
+    pub id: String,
+    pub title: String,
+    /// File name, or path inside the archive
+    pub file: String,
+}
+
+/// A file a bulk upload skipped
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BulkUploadFailure {
+    pub file: String,
+    pub error: String,
+}
+
+impl BulkUploadFailure {
+    pub fn new(file: impl Into<String>, error: impl Into<String>) -> Self {
+        Self {
+            file: file.into(),
+            error: error.into(),
+        }
+    }
+}
+
+/// Response for `POST /documents/bulk`
+#[derive(Debug, Clone, Serialize)]
+pub struct BulkUploadResponse {
+    /// Indexing job covering every created document
+    pub job_id: Option<String>,
+    pub status: String,
+    pub created: Vec<BulkUploadedDocument>,
+    pub failed: Vec<BulkUploadFailure>,
+    pub message: String,
+}
+
 /// Request to update document metadata
 /// Request to update document
 #[derive(Debug, Clone, Deserialize)]
 pub struct UpdateDocumentRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub title: Option<String>,
+    /// New content; the document is re-indexed when it changes
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tags: Option<Vec<String>>,
 }
@@ -255,6 +304,32 @@ pub struct DocumentResponse {
     pub chunk_count: i64,
 }
 
+/// One entry in a document's version history, without its content
+#[derive(Debug, Clone, Serialize)]
+pub struct DocumentVersionSummary {
+    pub version: i32,
+    pub title: String,
+    pub doc_type: Option<String>,
+    pub word_count: usize,
+    pub created_at: DateTime<Utc>,
+    pub is_current: bool,
+}
+
+/// A document as it was at one version
+#[derive(Debug, Clone, Serialize)]
+pub struct DocumentVersionResponse {
+    pub document_id: String,
+    pub version: i32,
+    pub title: String,
+    pub content: String,
+    pub content_type: Option<String>,
+    pub doc_type: Option<String>,
+    pub tags: Vec<String>,
+    pub source_url: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub is_current: bool,
+}
+
 /// List documents query parameters
 #[derive(Debug, Clone, Deserialize)]
 pub struct ListDocumentsQuery {
@@ -280,6 +355,17 @@ pub struct SearchRequest {
     pub limit: usize,
     #[serde(default)]
     pub search_type: SearchType,
+    /// Semantic vs. keyword blend for hybrid search (0.0 = keyword only,
+    /// 1.0 = semantic only). Omit to keep the searcher's default behavior.
+    #[serde(default)]
+    pub alpha: Option<f32>,
+    /// Reorder the top candidates with an LLM, when a rerank model is
+    /// configured
+    #[serde(default)]
+    pub rerank: bool,
+    /// Wrap query terms in the result snippets in `<mark>`
+    #[serde(default = "default_highlight")]
+    pub highlight: bool,
     #[serde(default)]
     pub filters: SearchFiltersRequest,
 }
@@ -288,6 +374,10 @@ fn default_search_limit() -> usize {
     10
 }
 
+fn default_highlight() -> bool {
+    true
+}
+
 /// Type of search to perform
 #[derive(Debug, Clone, Deserialize, Serialize, Default)]
 #[serde(rename_all = "lowercase")]
@@ -303,6 +393,8 @@ pub enum SearchType {
 pub struct SearchFiltersRequest {
     pub doc_type: Option<String>,
     pub tags: Option<Vec<String>>,
+    /// `all_of` / `any_of` / `none_of` tag lists
+    pub tag_expression: Option<TagExpression>,
     pub repo_id: Option<i64>,
     pub source_type: Option<String>,
     pub indexed_only: Option<bool>,
@@ -317,9 +409,27 @@ pub struct SearchResultItem {
     pub chunk_id: i64,
     pub title: String,
     pub content: String,
+    /// Window of the chunk around the query terms
+    pub snippet: Snippet,
+    /// Position of the chunk in its document
+    pub chunk_index: i64,
+    pub heading: Option<String>,
+    /// Char offsets of the chunk in the source document
+    pub char_start: i64,
+    pub char_end: i64,
     pub doc_type: String,
+    /// Fused score used for ranking
     pub score: f32,
+    /// Normalized semantic component, when the chunk matched semantically
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub semantic_score: Option<f32>,
+    /// Normalized keyword component, when the chunk matched by keyword
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub keyword_score: Option<f32>,
     pub tags: Vec<String>,
+    /// Tags that satisfied the request's tag expression
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub matched_tags: Vec<String>,
     pub source_url: Option<String>,
     pub created_at: DateTime<Utc>,
 }
@@ -331,6 +441,8 @@ pub struct SearchResponse {
     pub total_results: usize,
     pub search_type: SearchType,
     pub query: String,
+    /// Whether the results are in LLM rerank order
+    pub reranked: bool,
     pub execution_time_ms: u64,
 }
 
@@ -383,6 +495,41 @@ pub enum IndexJobStatus {
     Processing,
     Completed,
     Failed,
+    /// Unfinished at shutdown; resumes under the same job ID on restart
+    Interrupted,
+}
+
+// ============================================================================
+// Queue
+// ============================================================================
+
+/// Queue listing query parameters
+///
+/// `stage` takes the snake_case stage name (`pending_analysis`, `dead_letter`, ...);
+/// `priority` takes either the name (`high`) or the numeric level (`2`).
+#[derive(Debug, Clone, Deserialize)]
+pub struct QueueListQuery {
+    pub stage: Option<String>,
+    pub priority: Option<String>,
+    #[serde(default = "default_queue_limit")]
+    pub limit: u32,
+}
+
+fn default_queue_limit() -> u32 {
+    50
+}
+
+/// Request to add content to the processing queue
+#[derive(Debug, Clone, Deserialize)]
+pub struct EnqueueRequest {
+    pub content: String,
+    /// Defaults to `note`
+    pub source: Option<String>,
+    /// Defaults to `normal`
+    pub priority: Option<String>,
+    pub repo_id: Option<String>,
+    pub file_path: Option<String>,
+    pub line_number: Option<i32>,
 }
 
 // ============================================================================
@@ -396,6 +543,9 @@ pub struct StatsResponse {
     pub chunks: ChunkStats,
     pub search: SearchStats,
     pub indexing: IndexingStats,
+    /// Stored embeddings were built with a different model/dimension than the
+    /// active config; search results are unreliable until `POST /admin/reindex`
+    pub stale_index: bool,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -452,3 +602,29 @@ pub struct ServiceHealth {
     pub embeddings: bool,
     pub search: bool,
 }
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LivenessResponse {
+    pub status: String,
+    pub uptime_seconds: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ReadinessResponse {
+    /// `ready` when every critical dependency is up, `not_ready` otherwise
+    pub status: String,
+    pub checks: Vec<DependencyCheck>,
+}
+
+/// Result of probing one dependency
+#[derive(Debug, Clone, Serialize)]
+pub struct DependencyCheck {
+    pub name: String,
+    /// `up`, `down` or `skipped`
+    pub status: String,
+    /// Whether the service is unready while this dependency is down
+    pub critical: bool,
+    pub latency_ms: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
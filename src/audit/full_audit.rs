@@ -28,7 +28,7 @@ use tracing::{debug, error, info, warn};
 use uuid::Uuid;
 
 use crate::audit::runner::AuditRunnerConfig;
-use crate::grok_client::{FileScoreResult, GrokClient};
+use crate::grok_client::{FileScoreResult, GrokClient, ScoredIssue};
 
 // ============================================================================
 // Public types
@@ -122,7 +122,7 @@ impl FileAuditResult {
             maintainability_score: score.maintainability_score,
             severity,
             summary: score.summary,
-            issues: score.issues,
+            issues: score.issues.into_iter().map(|i| i.description).collect(),
             suggestions: score.suggestions,
             llm_scored,
         }
@@ -1089,7 +1089,7 @@ fn static_heuristic_score(_path: &str, content: &str) -> FileScoreResult {
         complexity_score,
         maintainability_score,
         summary,
-        issues,
+        issues: issues.into_iter().map(ScoredIssue::from).collect(),
         suggestions,
     }
 }
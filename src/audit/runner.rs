@@ -21,6 +21,7 @@ use crate::audit::types::{
 };
 use crate::error::{AuditError, Result};
 use crate::grok_client::{FileScoreResult, GrokClient};
+use crate::path_filter::PathFilter;
 use crate::static_analysis::{AnalysisRecommendation, StaticAnalyzer};
 use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
@@ -352,7 +353,11 @@ impl AuditRunner {
                     score.quality_score,
                     score.complexity_score,
                     score.maintainability_score,
-                    score.issues.first().cloned().unwrap_or_default()
+                    score
+                        .issues
+                        .first()
+                        .map(|i| i.description.as_str())
+                        .unwrap_or_default()
                 ),
                 recommendation: score
                     .suggestions
@@ -418,13 +423,11 @@ impl AuditRunnerWithGrok {
 
         // Apply request-level exclusion patterns
         if !request.exclude_patterns.is_empty() {
-            files.retain(|f| {
-                let s = f.to_string_lossy();
-                !request
-                    .exclude_patterns
-                    .iter()
-                    .any(|pat| s.contains(pat.as_str()))
-            });
+            let filter = PathFilter::new(Vec::new(), request.exclude_patterns.clone())?;
+            for pattern in filter.unmatched_patterns(files.iter().map(PathBuf::as_path)) {
+                warn!(run_id = %run_id, "Exclude pattern '{}' matches no files", pattern);
+            }
+            files.retain(|f| filter.matches(f));
         }
 
         let total_files = files.len();
@@ -256,7 +256,8 @@ pub struct AuditRequest {
     /// Maximum number of files to audit (0 = unlimited)
     #[serde(default)]
     pub max_files: usize,
-    /// File path patterns to exclude (glob syntax)
+    /// File path patterns to exclude, as globs over repo-relative paths
+    /// (see [`PathFilter`](crate::path_filter::PathFilter))
     #[serde(default)]
     pub exclude_patterns: Vec<String>,
     /// Caller-supplied metadata attached to the result as-is
@@ -18,9 +18,9 @@
 //! returned zero issues from the LLM.
 
 use anyhow::{Context, Result};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 use tokio::sync::RwLock;
 use tracing::{debug, error, info, warn};
@@ -28,12 +28,14 @@ use tracing::{debug, error, info, warn};
 use crate::cost_tracker::{CostTracker, StaticDecisionRecord};
 use crate::db::scan_events;
 use crate::db::{Database, Repository};
+use crate::git::GitManager;
 use crate::prompt_router::{PromptRouter, TierKind};
 use crate::refactor_assistant::RefactorAssistant;
 use crate::repo_cache_sql::RepoCacheSql;
 use crate::repo_manager::RepoManager;
 use crate::static_analysis::{AnalysisRecommendation, StaticAnalyzer};
 use crate::todo_scanner::TodoScanner;
+use crate::tree_state::{TreeState, TreeStateManager};
 
 /// Maximum file size to send to LLM analysis (100 KB)
 const MAX_ANALYSIS_FILE_SIZE: u64 = 100 * 1024;
@@ -45,6 +47,14 @@ const DEFAULT_SCAN_COST_BUDGET: f64 = 3.00;
 const COST_PER_MILLION_INPUT: f64 = 0.20;
 const COST_PER_MILLION_OUTPUT: f64 = 0.50;
 
+/// File extensions sent for analysis
+const ANALYZABLE_EXTENSIONS: &[&str] = &[
+    "rs", "py", "js", "ts", "tsx", "sh", "kt", "java", "go", "rb",
+];
+
+/// Directory under the repos dir holding each repository's tree state
+const TREE_STATE_DIR: &str = ".tree-state";
+
 /// Directories to always skip during scanning
 const SKIP_DIRS: &[&str] = &[
     "/dist/",
@@ -85,6 +95,28 @@ pub struct AutoScannerConfig {
     pub scan_cost_budget: f64,
 }
 
+impl AutoScannerConfig {
+    /// Read `AUTO_SCAN_ENABLED`, `AUTO_SCAN_INTERVAL` (minutes),
+    /// `AUTO_SCAN_MAX_CONCURRENT` and `AUTO_SCAN_COST_BUDGET`, falling back
+    /// to the defaults for unset or unparsable values
+    pub fn from_env() -> Self {
+        fn var<T: std::str::FromStr>(name: &str, default: T) -> T {
+            std::env::var(name)
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(default)
+        }
+
+        let defaults = Self::default();
+        Self {
+            enabled: var("AUTO_SCAN_ENABLED", defaults.enabled),
+            default_interval_minutes: var("AUTO_SCAN_INTERVAL", defaults.default_interval_minutes),
+            max_concurrent_scans: var("AUTO_SCAN_MAX_CONCURRENT", defaults.max_concurrent_scans),
+            scan_cost_budget: var("AUTO_SCAN_COST_BUDGET", defaults.scan_cost_budget),
+        }
+    }
+}
+
 impl Default for AutoScannerConfig {
     fn default() -> Self {
         Self {
@@ -126,6 +158,108 @@ pub struct RepoScanState {
     pub modified_files: Vec<PathBuf>,
 }
 
+/// Whether a repository last checked at `last_check` is due for a scan at
+/// `now`. Never-checked (or force-scanned) repositories are always due.
+fn scan_due(last_check: Option<i64>, interval_secs: i64, now: i64) -> bool {
+    match last_check {
+        Some(last_check) => now - last_check >= interval_secs,
+        None => true,
+    }
+}
+
+/// Files an incremental scan analyzes, and the tree state to save once all
+/// of them have been analyzed
+struct ScanPlan {
+    files: Vec<PathBuf>,
+    state: TreeState,
+    manager: TreeStateManager,
+}
+
+impl ScanPlan {
+    /// Diff `repo_path` against the tree state saved in `state_dir`. With no
+    /// saved state (or an unreadable one) every analyzable file is included.
+    fn new(repo_path: &Path, state_dir: &Path) -> Result<Self> {
+        let manager = TreeStateManager::new(repo_path)
+            .with_cache_dir(state_dir)
+            .with_extensions(ANALYZABLE_EXTENSIONS);
+        let state = manager.build_current_state()?;
+
+        let previous = manager.load_previous_state().unwrap_or_else(|e| {
+            warn!("Ignoring tree state in {}: {}", state_dir.display(), e);
+            None
+        });
+        let mut paths: Vec<String> = match previous {
+            Some(previous) => manager
+                .get_files_needing_analysis(&manager.diff(&previous, &state))
+                .into_iter()
+                .map(|file| file.path)
+                .collect(),
+            None => state.files.keys().cloned().collect(),
+        };
+        // Stable order, so checkpoint indices stay valid across retries
+        paths.sort();
+
+        let files = paths
+            .into_iter()
+            .filter(|path| AutoScanner::should_analyze_file(path))
+            .map(|path| repo_path.join(path))
+            .collect();
+
+        Ok(Self {
+            files,
+            state,
+            manager,
+        })
+    }
+
+    /// Record the tree as scanned, so the next plan only has later changes
+    fn save(&self) -> Result<()> {
+        self.manager.save_state(&self.state)?;
+        Ok(())
+    }
+}
+
+/// Whether a tracked repository's HEAD is the commit its last completed
+/// scan recorded, so there is nothing new to analyze
+fn head_unchanged(last_commit_hash: Option<&str>, head: &str) -> bool {
+    last_commit_hash == Some(head)
+}
+
+/// Ids of repositories with a scan in progress, so that a repository is
+/// never scanned twice at once
+#[derive(Debug, Clone, Default)]
+struct ActiveScans(Arc<Mutex<HashSet<String>>>);
+
+impl ActiveScans {
+    /// Claim `repo_id`, or `None` if it is already being scanned. The claim
+    /// is released when the guard is dropped.
+    fn claim(&self, repo_id: &str) -> Option<ActiveScanGuard> {
+        let mut active = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        if !active.insert(repo_id.to_string()) {
+            return None;
+        }
+        Some(ActiveScanGuard {
+            scans: self.clone(),
+            repo_id: repo_id.to_string(),
+        })
+    }
+}
+
+struct ActiveScanGuard {
+    scans: ActiveScans,
+    repo_id: String,
+}
+
+impl Drop for ActiveScanGuard {
+    fn drop(&mut self) {
+        self.scans
+            .0
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(&self.repo_id);
+    }
+}
+
 /// Background repository scanner
 pub struct AutoScanner {
     config: AutoScannerConfig,
@@ -133,6 +267,8 @@ pub struct AutoScanner {
     repos_dir: PathBuf,
     scan_states: Arc<RwLock<HashMap<String, RepoScanState>>>,
     repo_manager: Arc<RepoManager>,
+    /// Pulls tracked repositories before they are scanned
+    git_manager: Arc<GitManager>,
     /// Static analyzer for pre-filtering files before LLM analysis
     static_analyzer: Arc<StaticAnalyzer>,
     /// Prompt router for tier-based prompt selection (Minimal/Standard/DeepDive)
@@ -141,6 +277,8 @@ pub struct AutoScanner {
     todo_scanner: Arc<TodoScanner>,
     /// Cost tracker for logging static analysis decisions and savings
     cost_tracker: Option<Arc<CostTracker>>,
+    /// Repositories currently being scanned
+    active_scans: ActiveScans,
 }
 
 impl AutoScanner {
@@ -153,6 +291,10 @@ impl AutoScanner {
             RepoManager::new(&repos_dir, github_token).expect("Failed to create RepoManager"),
         );
 
+        let git_manager = Arc::new(
+            GitManager::new(repos_dir.clone(), false).expect("Failed to create GitManager"),
+        );
+
         let static_analyzer = Arc::new(StaticAnalyzer::new());
         let prompt_router = Arc::new(PromptRouter::new());
         let todo_scanner = Arc::new(TodoScanner::new().expect("Failed to create TodoScanner"));
@@ -163,10 +305,12 @@ impl AutoScanner {
             repos_dir,
             scan_states: Arc::new(RwLock::new(HashMap::new())),
             repo_manager,
+            git_manager,
             static_analyzer,
             prompt_router,
             todo_scanner,
             cost_tracker: None,
+            active_scans: ActiveScans::default(),
         }
     }
 
@@ -256,9 +400,19 @@ impl AutoScanner {
 
     /// Check if repo needs scanning and scan if necessary
     async fn check_and_scan_repo(&self, repo: &Repository) -> Result<()> {
+        let Some(_claim) = self.active_scans.claim(&repo.id) else {
+            debug!("Skipping {} - a scan is already running", repo.name);
+            return Ok(());
+        };
+
         let repo_name = &repo.name;
         let now = chrono::Utc::now().timestamp();
-        let interval_secs = repo.scan_interval_minutes as i64 * 60;
+        let interval_minutes = if repo.scan_interval_minutes > 0 {
+            repo.scan_interval_minutes as i64
+        } else {
+            self.config.default_interval_minutes as i64
+        };
+        let interval_secs = interval_minutes * 60;
 
         // ── On-demand project review (bypasses interval check) ──────────
         // The web UI sets review_requested = 1 when the user clicks
@@ -338,17 +492,19 @@ impl AutoScanner {
         }
 
         // Check if enough time has passed since last scan
-        if let Some(last_check) = repo.last_scan_check {
-            if now - last_check < interval_secs {
-                debug!(
-                    "Skipping {} - scanned {} seconds ago",
-                    repo.name,
-                    now - last_check
-                );
-                return Ok(());
-            }
+        if !scan_due(repo.last_scan_check, interval_secs, now) {
+            debug!(
+                "Skipping {} - checked {} seconds ago",
+                repo.name,
+                now - repo.last_scan_check.unwrap_or(now)
+            );
+            return Ok(());
         }
 
+        // Record the check up front so a failing scan waits for the next
+        // interval instead of being retried every loop iteration
+        self.update_last_scan_check(&repo.id, now).await?;
+
         info!("Scanning repository: {} ({})", repo.name, repo.path);
 
         // Track scan start time for duration calculation
@@ -429,46 +585,72 @@ impl AutoScanner {
             repo_path
         };
 
-        // Update repository if it exists (git pull)
-        if let Some(ref git_url) = repo.git_url {
-            match self.clone_or_update_repo(git_url, &repo.name) {
-                Ok(_) => {
-                    // Log successful update
-                    if let Err(e) = scan_events::log_info(
+        // Pull tracked repositories, and skip them if HEAD hasn't moved
+        // since the last completed scan
+        if repo.git_url.is_some() {
+            let pulled = match self.git_manager.pull(&repo_path) {
+                Ok(pulled) => pulled,
+                Err(e) => {
+                    warn!("Failed to pull {}: {}", repo.name, e);
+                    let message = format!("git pull failed: {}", e);
+                    if let Err(err) =
+                        crate::db::core::fail_scan(&self.pool, &repo.id, &message).await
+                    {
+                        error!("Failed to mark scan as failed: {}", err);
+                    }
+                    if let Err(err) = scan_events::log_error(
                         &self.pool,
                         Some(&repo.id),
-                        "git_update",
-                        &format!("Updated repository {}", repo.name),
+                        "git_update_error",
+                        &format!("Failed to pull {}", repo.name),
+                        &e.to_string(),
                     )
                     .await
                     {
-                        warn!("Failed to log git update event: {}", e);
+                        warn!("Failed to log git update error event: {}", err);
                     }
+                    return Ok(());
                 }
-                Err(e) => {
-                    warn!("Failed to update {}: {}", repo.name, e);
+            };
+
+            if pulled.changed() {
+                if let Err(e) = scan_events::log_info(
+                    &self.pool,
+                    Some(&repo.id),
+                    "git_update",
+                    &format!("Updated repository {} to {}", repo.name, pulled.head),
+                )
+                .await
+                {
+                    warn!("Failed to log git update event: {}", e);
                 }
             }
+
+            if head_unchanged(repo.last_commit_hash.as_deref(), &pulled.head) {
+                debug!(
+                    "Skipping {} - HEAD {} already scanned",
+                    repo.name, pulled.head
+                );
+                return Ok(());
+            }
         }
 
-        // Check for changes (both committed and uncommitted)
+        // Diff the tree against the state saved by the last completed scan
+        // (committed and uncommitted changes alike); the first scan of a
+        // repository has no saved state and covers every file
         let current_head = self.get_head_hash(&repo_path)?;
-        let changed_files = self
-            .get_changed_files(
-                &repo_path,
-                repo.last_commit_hash.as_deref(),
-                current_head.as_deref(),
-            )
-            .await?;
+        let plan = ScanPlan::new(&repo_path, &self.tree_state_dir(&repo.id))?;
+        let changed_files = &plan.files;
 
         if changed_files.is_empty() {
             debug!("No changes detected in {}", repo.name);
-            // Still update the commit hash so we don't re-diff the same range
+            if let Err(e) = plan.save() {
+                warn!("Failed to save tree state for {}: {}", repo.name, e);
+            }
+            // Still update the commit hash so an unchanged HEAD is skipped
             if let Some(ref hash) = current_head {
                 self.update_last_commit_hash(&repo.id, hash).await?;
             }
-            // Update last_scan_check for interval tracking
-            self.update_last_scan_check(&repo.id, now).await?;
             return Ok(());
         }
 
@@ -503,7 +685,7 @@ impl AutoScanner {
 
         // Analyze changed files with progress tracking
         let result = self
-            .analyze_changed_files_with_progress(&repo.id, repo_name, &repo_path, &changed_files)
+            .analyze_changed_files_with_progress(&repo.id, repo_name, &repo_path, changed_files)
             .await;
 
         match result {
@@ -601,12 +783,15 @@ impl AutoScanner {
                         }
                     }
 
+                    if let Err(e) = plan.save() {
+                        warn!("Failed to save tree state for {}: {}", repo.name, e);
+                    }
                     if let Some(ref hash) = current_head {
                         self.update_last_commit_hash(&repo.id, hash).await?;
                     }
                 } else {
                     info!(
-                        "Skipping commit hash update — budget halted scan. \
+                        "Skipping commit hash and tree state update — budget halted scan. \
                          Next cycle will resume from cache hits."
                     );
                 }
@@ -684,229 +869,12 @@ impl AutoScanner {
         }
     }
 
-    /// Get list of modified files from both committed and uncommitted changes
-    async fn get_changed_files(
-        &self,
-        repo_path: &Path,
-        last_commit_hash: Option<&str>,
-        current_head: Option<&str>,
-    ) -> Result<Vec<PathBuf>> {
-        use std::collections::HashSet;
-        use std::process::Command;
-
-        let mut changed_set: HashSet<PathBuf> = HashSet::new();
-
-        // 1. Check for committed changes since last known hash
-        if let (Some(old_hash), Some(new_hash)) = (last_commit_hash, current_head) {
-            if old_hash != new_hash {
-                let output = Command::new("git")
-                    .args(["diff", "--name-status", old_hash, new_hash])
-                    .current_dir(repo_path)
-                    .output();
-
-                match output {
-                    Ok(out) if out.status.success() => {
-                        let stdout = String::from_utf8_lossy(&out.stdout);
-                        for line in stdout.lines() {
-                            let parts: Vec<&str> = line.split('\t').collect();
-                            if parts.len() < 2 {
-                                continue;
-                            }
-                            let status = parts[0];
-                            // Skip deleted files
-                            if status.starts_with('D') {
-                                continue;
-                            }
-                            // For renames (R100), the new path is the last element
-                            let file_path = parts.last().unwrap().trim();
-                            if Self::should_analyze_file(file_path) {
-                                let full_path = repo_path.join(file_path);
-                                if full_path.exists() {
-                                    changed_set.insert(full_path);
-                                } else {
-                                    debug!(
-                                        "Skipping {} - file does not exist on disk (deleted in later commit$1)",
-                                        file_path
-                                    );
-                                }
-                            }
-                        }
-                        info!(
-                            "Found {} files changed between commits {}..{}",
-                            changed_set.len(),
-                            &old_hash[..8.min(old_hash.len())],
-                            &new_hash[..8.min(new_hash.len())]
-                        );
-                    }
-                    Ok(out) => {
-                        // git diff failed - old hash may no longer exist (force push, etc.)
-                        // Fall back to listing all files in the latest commit
-                        warn!(
-                            "git diff failed for {}..{} ({}), falling back to HEAD diff",
-                            &old_hash[..8.min(old_hash.len())],
-                            &new_hash[..8.min(new_hash.len())],
-                            String::from_utf8_lossy(&out.stderr).trim()
-                        );
-                        self.get_files_from_recent_commits(repo_path, &mut changed_set)?;
-                    }
-                    Err(e) => {
-                        warn!("Failed to run git diff: {}", e);
-                    }
-                }
-            }
-        } else if last_commit_hash.is_none() && current_head.is_some() {
-            // First scan - no stored hash yet. Check recent commits to seed initial analysis.
-            info!(
-                "First scan for {} - checking recent commits",
-                repo_path.display()
-            );
-            self.get_files_from_recent_commits(repo_path, &mut changed_set)?;
-        }
-
-        // 2. Also check for uncommitted changes (working tree + staged)
-        let output = Command::new("git")
-            .args(["status", "--porcelain"])
-            .current_dir(repo_path)
-            .output()
-            .context("Failed to run git status")?;
-
-        if output.status.success() {
-            let stdout = String::from_utf8_lossy(&output.stdout);
-            for line in stdout.lines() {
-                if line.len() < 3 {
-                    continue;
-                }
-
-                let status = &line[0..2];
-                let file_path = line[3..].trim();
-
-                // Skip deleted files
-                if status.contains('D') {
-                    continue;
-                }
-
-                if Self::should_analyze_file(file_path) {
-                    let full_path = repo_path.join(file_path);
-                    if full_path.exists() {
-                        changed_set.insert(full_path);
-                    } else {
-                        debug!("Skipping {} - file does not exist on disk", file_path);
-                    }
-                }
-            }
-        }
-
-        Ok(changed_set.into_iter().collect())
-    }
-
-    /// Get changed files from recent commits (used for first scan or fallback)
-    fn get_files_from_recent_commits(
-        &self,
-        repo_path: &Path,
-        changed_set: &mut std::collections::HashSet<PathBuf>,
-    ) -> Result<()> {
-        use std::process::Command;
-
-        // Try to get files changed in the last 5 commits first.
-        // This may fail for repos that have fewer than 5 commits (e.g. HEAD~5
-        // doesn't exist), so we fall back to listing every tracked file in HEAD.
-        let diff_output = Command::new("git")
-            .args(["diff", "--name-only", "HEAD~5", "HEAD"])
-            .current_dir(repo_path)
-            .output();
-
-        let used_diff = match diff_output {
-            Ok(ref out) if out.status.success() => {
-                let stdout = String::from_utf8_lossy(&out.stdout);
-                let mut found = false;
-                for line in stdout.lines() {
-                    let file_path = line.trim();
-                    if !file_path.is_empty() && Self::should_analyze_file(file_path) {
-                        let full_path = repo_path.join(file_path);
-                        if full_path.exists() {
-                            changed_set.insert(full_path);
-                            found = true;
-                        } else {
-                            debug!("Skipping {} - file does not exist on disk", file_path);
-                        }
-                    }
-                }
-                found
-            }
-            _ => {
-                debug!(
-                    "Could not get recent commits for {} (too few commits or git error) — \
-                     falling back to full tree listing",
-                    repo_path.display()
-                );
-                false
-            }
-        };
-
-        // Fallback: list every file tracked in HEAD so a brand-new or shallow
-        // clone still gets a full initial scan instead of being silently skipped.
-        if !used_diff {
-            info!(
-                "First-scan fallback: listing all tracked files in HEAD for {}",
-                repo_path.display()
-            );
-            let ls_output = Command::new("git")
-                .args(["ls-tree", "-r", "--name-only", "HEAD"])
-                .current_dir(repo_path)
-                .output();
-
-            match ls_output {
-                Ok(out) if out.status.success() => {
-                    let stdout = String::from_utf8_lossy(&out.stdout);
-                    for line in stdout.lines() {
-                        let file_path = line.trim();
-                        if !file_path.is_empty() && Self::should_analyze_file(file_path) {
-                            let full_path = repo_path.join(file_path);
-                            if full_path.exists() {
-                                changed_set.insert(full_path);
-                            } else {
-                                debug!("Skipping {} - file does not exist on disk", file_path);
-                            }
-                        }
-                    }
-                    info!(
-                        "ls-tree listed {} analyzable files for {}",
-                        changed_set.len(),
-                        repo_path.display()
-                    );
-                }
-                Ok(out) => {
-                    warn!(
-                        "git ls-tree failed for {}: {}",
-                        repo_path.display(),
-                        String::from_utf8_lossy(&out.stderr).trim()
-                    );
-                }
-                Err(e) => {
-                    warn!(
-                        "Failed to run git ls-tree for {}: {}",
-                        repo_path.display(),
-                        e
-                    );
-                }
-            }
-        }
-
-        Ok(())
-    }
-
     /// Check if a file extension is one we should analyze
     fn is_analyzable_file(file_path: &str) -> bool {
-        file_path.ends_with(".rs")
-            || file_path.ends_with(".py")
-            || file_path.ends_with(".js")
-            || file_path.ends_with(".ts")
-            || file_path.ends_with(".tsx")
-            || file_path.ends_with(".sh")
-            || file_path.ends_with(".kt")
-            || file_path.ends_with(".java")
-            || file_path.ends_with(".go")
-            || file_path.ends_with(".rb")
+        Path::new(file_path)
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| ANALYZABLE_EXTENSIONS.contains(&ext))
     }
 
     /// Check if a file should be skipped based on path patterns.
@@ -969,7 +937,7 @@ impl AutoScanner {
         let mut api_calls = 0i64;
         let mut budget_halted = false;
 
-        // Pre-filter files that match skip patterns (extra safety — get_changed_files
+        // Pre-filter files that match skip patterns (extra safety — ScanPlan
         // already filters, but files may have been added to the list via other paths)
         let analyzable_files: Vec<&PathBuf> = files
             .iter()
@@ -1597,12 +1565,17 @@ impl AutoScanner {
         })
     }
 
+    /// Where the tree state of the last completed scan of `repo_id` is kept
+    fn tree_state_dir(&self, repo_id: &str) -> PathBuf {
+        self.repos_dir.join(TREE_STATE_DIR).join(repo_id)
+    }
+
     /// Update last_scan_check timestamp
     async fn update_last_scan_check(&self, repo_id: &str, timestamp: i64) -> Result<()> {
         sqlx::query(
             r#"
             UPDATE repositories
-            SET last_scanned_at = $1
+            SET last_scan_check = $1
             WHERE id = $2
             "#,
         )
@@ -1656,10 +1629,12 @@ impl AutoScanner {
             repos_dir: self.repos_dir.clone(),
             scan_states: self.scan_states.clone(),
             repo_manager: self.repo_manager.clone(),
+            git_manager: self.git_manager.clone(),
             static_analyzer: self.static_analyzer.clone(),
             prompt_router: self.prompt_router.clone(),
             todo_scanner: self.todo_scanner.clone(),
             cost_tracker: self.cost_tracker.clone(),
+            active_scans: self.active_scans.clone(),
         }
     }
 
@@ -2456,6 +2431,7 @@ pub async fn force_scan(pool: &sqlx::PgPool, repo_id: &str) -> Result<()> {
         r#"
         UPDATE repositories
         SET last_scanned_at = NULL,
+            last_scan_check = NULL,
             last_commit_hash = NULL
         WHERE id = $1
         "#,
@@ -2485,6 +2461,80 @@ mod tests {
         assert!((config.scan_cost_budget - 3.00).abs() < f64::EPSILON);
     }
 
+    #[test]
+    fn test_scan_due() {
+        assert!(scan_due(None, 3600, 1_000));
+        assert!(!scan_due(Some(1_000), 3600, 1_000 + 3599));
+        assert!(scan_due(Some(1_000), 3600, 1_000 + 3600));
+    }
+
+    #[test]
+    fn test_unchanged_head_skips_scan() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let upstream = git2::Repository::init(temp.path().join("upstream")).unwrap();
+        std::fs::write(temp.path().join("upstream/lib.rs"), "fn main() {}").unwrap();
+        let mut index = upstream.index().unwrap();
+        index.add_path(Path::new("lib.rs")).unwrap();
+        let tree = upstream.find_tree(index.write_tree().unwrap()).unwrap();
+        let sig = git2::Signature::now("test", "test@example.com").unwrap();
+        upstream
+            .commit(Some("HEAD"), &sig, &sig, "init", &tree, &[])
+            .unwrap();
+
+        let git = GitManager::new(temp.path().join("repos"), false).unwrap();
+        let url = temp.path().join("upstream").display().to_string();
+        let clone = git.clone_repo(&url, Some("tracked")).unwrap();
+
+        // First scan: nothing recorded yet, so the scan runs
+        let pulled = git.pull(&clone).unwrap();
+        assert!(!head_unchanged(None, &pulled.head));
+
+        // Next cycle: no new commits upstream, HEAD matches the stored hash
+        let last_commit_hash = pulled.head;
+        let pulled = git.pull(&clone).unwrap();
+        assert!(!pulled.changed());
+        assert!(head_unchanged(Some(&last_commit_hash), &pulled.head));
+    }
+
+    #[test]
+    fn test_unchanged_tree_plans_no_files_on_second_run() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let repo = temp.path().join("repo");
+        let state_dir = temp.path().join("state");
+        std::fs::create_dir_all(repo.join("src")).unwrap();
+        std::fs::write(repo.join("src/main.rs"), "fn main() {}").unwrap();
+        std::fs::write(repo.join("src/util.py"), "pass").unwrap();
+        std::fs::write(repo.join("README.md"), "# readme").unwrap();
+
+        // First run: no saved state, so every analyzable file is scanned
+        let plan = ScanPlan::new(&repo, &state_dir).unwrap();
+        assert_eq!(
+            plan.files,
+            vec![repo.join("src/main.rs"), repo.join("src/util.py")]
+        );
+        plan.save().unwrap();
+
+        // Second run over the same tree analyzes nothing
+        let plan = ScanPlan::new(&repo, &state_dir).unwrap();
+        assert!(plan.files.is_empty());
+
+        // Only the edited file comes back
+        std::fs::write(repo.join("src/main.rs"), "fn main() { run() }").unwrap();
+        let plan = ScanPlan::new(&repo, &state_dir).unwrap();
+        assert_eq!(plan.files, vec![repo.join("src/main.rs")]);
+    }
+
+    #[test]
+    fn test_active_scans_one_claim_per_repo() {
+        let scans = ActiveScans::default();
+        let claim = scans.claim("repo-a").expect("first claim");
+        assert!(scans.claim("repo-a").is_none());
+        assert!(scans.claim("repo-b").is_some());
+
+        drop(claim);
+        assert!(scans.claim("repo-a").is_some());
+    }
+
     #[test]
     fn test_file_status() {
         let status = FileStatus::Modified;
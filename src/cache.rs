@@ -69,6 +69,14 @@ pub struct CacheStats {
 
     /// Total files analyzed (lifetime)
     pub total_files_analyzed: usize,
+
+    /// Bytes reclaimed from the entries file by compaction (lifetime)
+    #[serde(default)]
+    pub bytes_reclaimed: u64,
+
+    /// Tokens not spent thanks to cache hits (lifetime)
+    #[serde(default)]
+    pub tokens_saved: usize,
 }
 
 impl Default for CacheStats {
@@ -81,6 +89,8 @@ impl Default for CacheStats {
             last_updated: chrono::Utc::now().to_rfc3339(),
             estimated_savings: 0.0,
             total_files_analyzed: 0,
+            bytes_reclaimed: 0,
+            tokens_saved: 0,
         }
     }
 }
@@ -190,14 +200,14 @@ impl AuditCache {
         let stats_file = self.cache_dir.join("stats.json");
         let stats_json = serde_json::to_string_pretty(&*self.stats.borrow())
             .map_err(|e| AuditError::other(format!("Failed to serialize stats: {}", e)))?;
-        fs::write(&stats_file, stats_json)
+        write_atomic(&stats_file, stats_json.as_bytes())
             .map_err(|e| AuditError::other(format!("Failed to write stats: {}", e)))?;
 
         // Save entries
         let entries_file = self.cache_dir.join("entries.json");
         let entries_json = serde_json::to_string_pretty(&*self.entries.borrow())
             .map_err(|e| AuditError::other(format!("Failed to serialize entries: {}", e)))?;
-        fs::write(&entries_file, entries_json)
+        write_atomic(&entries_file, entries_json.as_bytes())
             .map_err(|e| AuditError::other(format!("Failed to write entries: {}", e)))?;
 
         debug!("Saved cache: {} entries", self.entries.borrow().len());
@@ -218,7 +228,14 @@ impl AuditCache {
             return Ok(None);
         }
 
-        let content_hash = self.hash_content(content);
+        self.get_by_hash(cache_key, &self.hash_content(content))
+    }
+
+    /// Get cache entry whose recorded content hash is `content_hash`
+    pub fn get_by_hash(&self, cache_key: &str, content_hash: &str) -> Result<Option<CacheEntry>> {
+        if !self.enabled {
+            return Ok(None);
+        }
 
         if let Some(entry) = self.entries.borrow().get(cache_key) {
             // Check if content has changed
@@ -258,6 +275,20 @@ impl AuditCache {
         Ok(())
     }
 
+    /// Record a cache hit that saved `tokens_saved` tokens worth `cost_saved` USD
+    pub fn record_hit(&self, tokens_saved: usize, cost_saved: f64) {
+        let mut stats = self.stats.borrow_mut();
+        stats.cache_hits += 1;
+        stats.tokens_saved += tokens_saved;
+        stats.estimated_savings += cost_saved;
+        stats.last_updated = chrono::Utc::now().to_rfc3339();
+    }
+
+    /// Record a cache miss (an API call was made)
+    pub fn record_miss(&self) {
+        self.stats.borrow_mut().cache_misses += 1;
+    }
+
     /// Get cache statistics (clone to avoid borrowing issues)
     pub fn stats(&self) -> CacheStats {
         self.stats.borrow().clone()
@@ -312,6 +343,62 @@ impl AuditCache {
         Ok(removed)
     }
 
+    /// Drop entries analyzed more than `max_age_days` ago and collapse
+    /// entries for the same file and content hash (e.g. stored under
+    /// different keys) to the newest one, then rewrite the cache file.
+    /// Returns the number of entries removed.
+    pub fn compact(&mut self, max_age_days: i64) -> Result<usize> {
+        if !self.enabled {
+            return Ok(0);
+        }
+
+        let entries_file = self.cache_dir.join("entries.json");
+        let size_before = fs::metadata(&entries_file).map(|m| m.len()).unwrap_or(0);
+
+        let cutoff = chrono::Utc::now() - chrono::Duration::days(max_age_days);
+        let analyzed_at = |entry: &CacheEntry| {
+            chrono::DateTime::parse_from_rfc3339(&entry.analyzed_at)
+                .ok()
+                .map(|t| t.with_timezone(&chrono::Utc))
+        };
+
+        let entries = self.entries.get_mut();
+        let before = entries.len();
+
+        // Entries with an unparseable timestamp are kept rather than guessed at
+        entries.retain(|_, entry| analyzed_at(entry).is_none_or(|t| t >= cutoff));
+
+        let mut newest: HashMap<(&str, &str), &String> = HashMap::new();
+        for (key, entry) in entries.iter() {
+            let group = (entry.file_path.as_str(), entry.content_hash.as_str());
+            let newer = newest.get(&group).is_none_or(|kept| {
+                (analyzed_at(entry), key) > (analyzed_at(&entries[*kept]), *kept)
+            });
+            if newer {
+                newest.insert(group, key);
+            }
+        }
+        let keep: std::collections::HashSet<String> = newest.into_values().cloned().collect();
+        entries.retain(|key, _| keep.contains(key));
+
+        let removed = before - entries.len();
+        self.stats.get_mut().total_entries = entries.len();
+        self.save()?;
+
+        let size_after = fs::metadata(&entries_file).map(|m| m.len()).unwrap_or(0);
+        let reclaimed = size_before.saturating_sub(size_after);
+        self.stats.get_mut().bytes_reclaimed += reclaimed;
+        if reclaimed > 0 {
+            self.save()?;
+        }
+
+        info!(
+            "Compacted audit cache: {} entries removed, {} bytes reclaimed",
+            removed, reclaimed
+        );
+        Ok(removed)
+    }
+
     /// Get cache hit rate as percentage
     pub fn hit_rate(&self) -> f64 {
         let stats = self.stats.borrow();
@@ -340,6 +427,30 @@ impl AuditCache {
     }
 }
 
+/// Write a file by writing a temp file next to it and renaming it into
+/// place, so readers and concurrent writers never see a partial file
+fn write_atomic(path: &Path, contents: &[u8]) -> std::io::Result<()> {
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or(0);
+    let file_name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let tmp = path.with_file_name(format!(
+        ".{}.{}.{}.tmp",
+        file_name,
+        std::process::id(),
+        nanos
+    ));
+
+    fs::write(&tmp, contents)?;
+    fs::rename(&tmp, path).inspect_err(|_| {
+        let _ = fs::remove_file(&tmp);
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -476,4 +587,73 @@ mod tests {
             assert_eq!(entry.analysis, analysis);
         }
     }
+
+    fn entry_at(
+        file_path: &str,
+        content: &str,
+        analyzed_at: chrono::DateTime<chrono::Utc>,
+    ) -> CacheEntry {
+        CacheEntry {
+            file_path: file_path.to_string(),
+            content_hash: AuditCache::disabled().hash_content(content),
+            analyzed_at: analyzed_at.to_rfc3339(),
+            provider: "xai".to_string(),
+            model: "grok-4".to_string(),
+            analysis: serde_json::json!({"score": 85, "notes": "x".repeat(200)}),
+            tokens_used: Some(100),
+            file_size: content.len(),
+        }
+    }
+
+    #[test]
+    fn test_compact_drops_old_and_duplicate_entries() {
+        let temp = TempDir::new().unwrap();
+        let config = crate::llm_config::CacheConfig::default();
+        let now = chrono::Utc::now();
+        let day = chrono::Duration::days(1);
+
+        {
+            let cache = AuditCache::new(temp.path(), &config).unwrap();
+            cache
+                .set("old.rs".into(), entry_at("old.rs", "old", now - day * 40))
+                .unwrap();
+            cache
+                .set("a.rs".into(), entry_at("a.rs", "a", now - day * 2))
+                .unwrap();
+            cache
+                .set("./a.rs".into(), entry_at("a.rs", "a", now - day))
+                .unwrap();
+            cache
+                .set("b.rs".into(), entry_at("b.rs", "b", now))
+                .unwrap();
+            cache.save().unwrap();
+        }
+
+        let mut cache = AuditCache::new(temp.path(), &config).unwrap();
+        assert_eq!(cache.compact(30).unwrap(), 2);
+        assert_eq!(cache.entry_count(), 2);
+        assert!(cache.get("./a.rs", "a").unwrap().is_some());
+        assert!(cache.get("a.rs", "a").unwrap().is_none());
+        assert!(cache.get("old.rs", "old").unwrap().is_none());
+        assert!(cache.stats().bytes_reclaimed > 0);
+
+        // The rewritten file reloads cleanly and leaves no temp files behind
+        let reloaded = AuditCache::new(temp.path(), &config).unwrap();
+        assert_eq!(reloaded.entry_count(), 2);
+        assert_eq!(
+            reloaded.stats().bytes_reclaimed,
+            cache.stats().bytes_reclaimed
+        );
+        let leftovers = fs::read_dir(cache.cache_dir())
+            .unwrap()
+            .filter(|e| {
+                e.as_ref()
+                    .unwrap()
+                    .file_name()
+                    .to_string_lossy()
+                    .ends_with(".tmp")
+            })
+            .count();
+        assert_eq!(leftovers, 0);
+    }
 }
@@ -0,0 +1,130 @@
+//! One-line audit summary for CI logs
+//!
+//! `rustassistant audit` ends with a single line that a CI step can grep
+//! for instead of parsing the whole report (it is left out under
+//! `--quiet`):
+//!
+//! ```text
+//! RUSTASSISTANT_SUMMARY {"version":1,"command":"audit","total_files":42,"total_issues":7,"issues_by_severity":{"critical":0,"high":1,"medium":4,"low":2,"info":0},"passed":false}
+//! ```
+//!
+//! The line starts with [`CI_SUMMARY_PREFIX`] and a single space, followed
+//! by compact JSON on the same line, so `^RUSTASSISTANT_SUMMARY (\{.*\})$`
+//! captures it. Fields are always present and always in this order:
+//!
+//! - `version`: shape version, bumped only on incompatible changes
+//! - `command`: the command that printed the line
+//! - `total_files`, `total_issues`: counts for the whole report
+//! - `issues_by_severity`: `critical`, `high`, `medium`, `low` and `info`
+//! - `passed`: `true` when there are no critical or high issues
+//!
+//! The line carries no color codes and is printed even when stdout isn't
+//! a terminal.
+
+use crate::types::{AuditReport, IssueSeverity};
+use serde::{Deserialize, Serialize};
+
+/// Prefix of the summary line
+pub const CI_SUMMARY_PREFIX: &str = "RUSTASSISTANT_SUMMARY";
+
+/// Version of the summary's JSON shape
+pub const CI_SUMMARY_VERSION: u32 = 1;
+
+/// Issue counts in a fixed order
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SeverityCounts {
+    pub critical: usize,
+    pub high: usize,
+    pub medium: usize,
+    pub low: usize,
+    pub info: usize,
+}
+
+/// Payload of the summary line
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CiSummary {
+    pub version: u32,
+    pub command: String,
+    pub total_files: usize,
+    pub total_issues: usize,
+    pub issues_by_severity: SeverityCounts,
+    pub passed: bool,
+}
+
+impl CiSummary {
+    /// Summary of an audit report
+    pub fn from_audit_report(report: &AuditReport) -> Self {
+        let count = |severity| {
+            report
+                .issues_by_severity
+                .get(&severity)
+                .copied()
+                .unwrap_or(0)
+        };
+        let issues_by_severity = SeverityCounts {
+            critical: count(IssueSeverity::Critical),
+            high: count(IssueSeverity::High),
+            medium: count(IssueSeverity::Medium),
+            low: count(IssueSeverity::Low),
+            info: count(IssueSeverity::Info),
+        };
+        Self {
+            version: CI_SUMMARY_VERSION,
+            command: "audit".to_string(),
+            total_files: report.summary.total_files,
+            total_issues: report.summary.total_issues,
+            passed: issues_by_severity.critical == 0 && issues_by_severity.high == 0,
+            issues_by_severity,
+        }
+    }
+
+    /// The full line, prefix included
+    pub fn to_line(&self) -> String {
+        format!(
+            "{} {}",
+            CI_SUMMARY_PREFIX,
+            serde_json::to_string(self).expect("summary serializes")
+        )
+    }
+
+    /// Parse a line printed by [`to_line`](Self::to_line)
+    pub fn parse_line(line: &str) -> Option<Self> {
+        let json = line.trim_end().strip_prefix(CI_SUMMARY_PREFIX)?;
+        serde_json::from_str(json.strip_prefix(' ')?).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn summary(high: usize) -> CiSummary {
+        CiSummary {
+            version: CI_SUMMARY_VERSION,
+            command: "audit".to_string(),
+            total_files: 42,
+            total_issues: 6 + high,
+            issues_by_severity: SeverityCounts {
+                critical: 0,
+                high,
+                medium: 4,
+                low: 2,
+                info: 0,
+            },
+            passed: high == 0,
+        }
+    }
+
+    #[test]
+    fn test_line_is_stable_and_round_trips() {
+        let line = summary(1).to_line();
+        assert_eq!(
+            line,
+            r#"RUSTASSISTANT_SUMMARY {"version":1,"command":"audit","total_files":42,"total_issues":7,"issues_by_severity":{"critical":0,"high":1,"medium":4,"low":2,"info":0},"passed":false}"#
+        );
+        assert!(!line.contains('\n'));
+        assert_eq!(CiSummary::parse_line(&line), Some(summary(1)));
+        assert_eq!(CiSummary::parse_line("RUSTASSISTANT_SUMMARY"), None);
+        assert_eq!(CiSummary::parse_line("📊 42 files"), None);
+    }
+}
@@ -5,18 +5,20 @@
 pub mod github_commands;
 pub mod queue_commands;
 pub mod research_backup_commands;
+pub mod style;
 pub mod task_commands;
 
 // Re-export command types
 pub use github_commands::{handle_github_command, GithubCommands};
 
 pub use queue_commands::{
-    handle_queue_command, handle_report_command, handle_scan_command, QueueCommands,
-    ReportCommands, ScanCommands,
+    handle_queue_command, handle_report_command, handle_scan_command, DeadLetterCommands,
+    QueueCommands, ReportCommands, ScanCommands,
 };
 
 pub use research_backup_commands::{
-    handle_backup_command, handle_research_command, BackupCommands, ResearchCommands,
+    analyze_research_document, handle_backup_command, handle_research_command, BackupCommands,
+    ResearchCommands,
 };
 
 pub use task_commands::{handle_task_command, TaskCommands};
@@ -7,8 +7,8 @@ use crate::db::queue::{
 };
 use crate::llm::grok::GrokAnalyzer;
 use crate::queue::processor::{
-    capture_note, capture_thought, get_pending_items, get_queue_stats, LlmAnalyzer,
-    ProcessorConfig, QueueProcessor,
+    capture_note, capture_thought, get_dead_letter_items, get_pending_items, get_queue_stats,
+    purge_dead_letter, requeue_dead_letter, LlmAnalyzer, ProcessorConfig, QueueProcessor,
 };
 use crate::scanner::github::{
     build_dir_tree, get_unanalyzed_files, save_dir_tree, scan_repo_for_todos, sync_repos_to_db,
@@ -48,7 +48,7 @@ pub enum QueueCommands {
 
     /// List items in a specific stage
     List {
-        /// Stage to list: inbox, pending, analyzing, ready, failed
+        /// Stage to list: inbox, pending, analyzing, ready, failed, dead
         #[arg(default_value = "inbox")]
         stage: String,
 
@@ -63,9 +63,13 @@ pub enum QueueCommands {
         #[arg(short, long, default_value = "10")]
         batch_size: i32,
 
-        /// Run once and exit (don't loop)
+        /// Drain the queue once and exit (don't loop)
         #[arg(long)]
         once: bool,
+
+        /// Maximum concurrent LLM analyses when draining with --once
+        #[arg(short = 'j', long, default_value = "4")]
+        concurrency: usize,
     },
 
     /// Retry failed items
@@ -73,6 +77,42 @@ pub enum QueueCommands {
         /// Specific item ID to retry (or all if not specified)
         id: Option<String>,
     },
+
+    /// Inspect and manage items that exhausted their retries
+    DeadLetter {
+        #[command(subcommand)]
+        action: DeadLetterCommands,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum DeadLetterCommands {
+    /// List dead-letter items
+    List {
+        /// Maximum items to show
+        #[arg(short, long, default_value = "20")]
+        limit: i32,
+    },
+
+    /// Move dead-letter items back to pending analysis
+    Requeue {
+        /// Item ID to requeue
+        id: Option<String>,
+
+        /// Requeue every dead-letter item
+        #[arg(long)]
+        all: bool,
+    },
+
+    /// Permanently delete dead-letter items
+    Purge {
+        /// Item ID to purge
+        id: Option<String>,
+
+        /// Purge every dead-letter item
+        #[arg(long)]
+        all: bool,
+    },
 }
 
 #[derive(Subcommand)]
@@ -224,6 +264,11 @@ pub async fn handle_queue_command(pool: &PgPool, cmd: QueueCommands) -> Result<(
                 "Failed:".dimmed(),
                 format!("{}", stats.failed).red()
             );
+            println!(
+                "  {} {}",
+                "Dead Letter:".dimmed(),
+                format!("{}", stats.dead_letter).magenta()
+            );
             println!("  {} {}", "Archived:".dimmed(), stats.archived);
             println!();
             println!("  {} {}", "Total Pending:".cyan(), stats.total_pending());
@@ -262,7 +307,11 @@ pub async fn handle_queue_command(pool: &PgPool, cmd: QueueCommands) -> Result<(
             }
         }
 
-        QueueCommands::Process { batch_size, once } => {
+        QueueCommands::Process {
+            batch_size,
+            once,
+            concurrency,
+        } => {
             let api_key =
                 std::env::var("XAI_API_KEY").expect("XAI_API_KEY must be set for processing");
 
@@ -277,11 +326,13 @@ pub async fn handle_queue_command(pool: &PgPool, cmd: QueueCommands) -> Result<(
             println!("🔄 Starting queue processor...");
 
             if once {
-                // Process one cycle and exit
-                // (Would need to expose individual methods for this)
+                let stats = processor.run_concurrent(concurrency).await?;
                 println!(
-                    "{} Single-pass processing not yet implemented",
-                    "⚠".yellow()
+                    "{} Queue drained — {} awaiting tagging, {} ready, {} failed",
+                    "✓".green(),
+                    stats.pending_tagging,
+                    stats.ready,
+                    format!("{}", stats.failed).red()
                 );
             } else {
                 processor.run().await?;
@@ -301,6 +352,71 @@ pub async fn handle_queue_command(pool: &PgPool, cmd: QueueCommands) -> Result<(
                 }
             }
         }
+
+        QueueCommands::DeadLetter { action } => handle_dead_letter_command(pool, action).await?,
+    }
+
+    Ok(())
+}
+
+async fn handle_dead_letter_command(pool: &PgPool, cmd: DeadLetterCommands) -> Result<()> {
+    match cmd {
+        DeadLetterCommands::List { limit } => {
+            let items = get_dead_letter_items(pool, limit).await?;
+
+            if items.is_empty() {
+                println!("{} No dead-letter items", "📭".dimmed());
+            } else {
+                println!("☠️  Dead-letter items ({}):\n", items.len());
+
+                for item in items {
+                    let preview: String = item.content.chars().take(60).collect();
+                    println!("  [{}] {}", item.id.dimmed(), preview.magenta());
+                    println!(
+                        "     {} {}/{} | {} {}",
+                        "Attempts:".dimmed(),
+                        item.retry_count,
+                        item.max_retries,
+                        "Last error:".dimmed(),
+                        item.last_error.as_deref().unwrap_or("-").red()
+                    );
+                    println!();
+                }
+            }
+        }
+
+        DeadLetterCommands::Requeue { id, all } => {
+            let ids: Vec<String> = match (id, all) {
+                (Some(id), _) => vec![id],
+                (None, true) => get_dead_letter_items(pool, i32::MAX)
+                    .await?
+                    .into_iter()
+                    .map(|item| item.id)
+                    .collect(),
+                (None, false) => {
+                    eprintln!("{} Specify an item ID or --all", "✗".red());
+                    return Ok(());
+                }
+            };
+
+            for id in ids {
+                if requeue_dead_letter(pool, &id).await? {
+                    println!("  {} Requeued {}", "✓".green(), id);
+                } else {
+                    println!("  {} {} is not a dead-letter item", "⚠".yellow(), id);
+                }
+            }
+        }
+
+        DeadLetterCommands::Purge { id, all } => {
+            if id.is_none() && !all {
+                eprintln!("{} Specify an item ID or --all", "✗".red());
+                return Ok(());
+            }
+
+            let removed = purge_dead_letter(pool, id.as_deref()).await?;
+            println!("{} Purged {} dead-letter item(s)", "✓".green(), removed);
+        }
     }
 
     Ok(())
@@ -862,6 +978,7 @@ fn parse_stage(s: &str) -> QueueStage {
         "tagging" | "pending_tagging" => QueueStage::PendingTagging,
         "ready" | "done" => QueueStage::Ready,
         "failed" | "error" => QueueStage::Failed,
+        "dead" | "dead_letter" => QueueStage::DeadLetter,
         "archived" => QueueStage::Archived,
         _ => QueueStage::Inbox,
     }
@@ -3,9 +3,11 @@
 use crate::backup::{print_rclone_setup_instructions, BackupConfig, BackupManager};
 use crate::llm::GrokClient;
 use crate::research::aggregator::Aggregator;
+use crate::research::document::DEFAULT_MAX_CHUNK_TOKENS;
 use crate::research::worker::{ResearchOrchestrator, WorkerConfig};
 use crate::research::{
-    get_research_with_results, list_research, save_research_request, ResearchDepth, ResearchRequest,
+    analyze_file, build_dependency_graph, get_research_with_results, list_research,
+    save_research_request, ResearchDepth, ResearchRequest,
 };
 use anyhow::Result;
 use clap::Subcommand;
@@ -66,6 +68,24 @@ pub enum ResearchCommands {
         /// Question to research
         question: String,
     },
+
+    /// Break a research document into a summary, key points and tasks
+    Analyze {
+        /// Markdown or text file to analyze
+        file: String,
+
+        /// Split documents larger than this into heading-bounded chunks
+        #[arg(long, default_value_t = DEFAULT_MAX_CHUNK_TOKENS)]
+        max_chunk_tokens: usize,
+
+        /// Print the tasks as a dependency-ordered plan
+        #[arg(long)]
+        plan: bool,
+
+        /// Write the breakdown to a file
+        #[arg(short, long)]
+        output: Option<String>,
+    },
 }
 
 pub async fn handle_research_command(pool: &PgPool, cmd: ResearchCommands) -> Result<()> {
@@ -219,7 +239,60 @@ pub async fn handle_research_command(pool: &PgPool, cmd: ResearchCommands) -> Re
 
             println!("{}", report.to_zed_format());
         }
+
+        ResearchCommands::Analyze {
+            file,
+            max_chunk_tokens,
+            plan,
+            output,
+        } => analyze_research_document(&file, max_chunk_tokens, plan, output.as_deref()).await?,
+    }
+
+    Ok(())
+}
+
+/// `research analyze`: break a document into tasks and, with `plan`, print
+/// them in dependency order. Needs no database, so the CLI runs it before
+/// connecting.
+pub async fn analyze_research_document(
+    file: &str,
+    max_chunk_tokens: usize,
+    plan: bool,
+    output: Option<&str>,
+) -> Result<()> {
+    println!("\n{} Analyzing: {}\n", "📄".bold(), file.cyan());
+
+    let llm = GrokClient::from_env()?;
+    let breakdown = analyze_file(&llm, file, max_chunk_tokens).await?;
+
+    let mut md = breakdown.to_markdown();
+    let graph = plan.then(|| build_dependency_graph(&breakdown.tasks));
+    if let Some(graph) = &graph {
+        for warning in &graph.warnings {
+            println!("{} {}", "⚠".yellow(), warning);
+        }
+        md.push_str(&graph.format_plan());
+    }
+
+    match output {
+        Some(path) => {
+            std::fs::write(path, &md)?;
+            println!("{} Breakdown written to {}", "✓".green(), path);
+            // The file has the full plan; keep the order on screen too
+            if let Some(graph) = &graph {
+                for (step, title) in graph.execution_order().iter().enumerate() {
+                    println!("  {}. {}", step + 1, title);
+                }
+            }
+        }
+        None => println!("{}", md),
     }
+    println!(
+        "{} {} chunk(s), ~{} tokens",
+        "✓".green(),
+        breakdown.chunks,
+        breakdown.total_tokens
+    );
 
     Ok(())
 }
@@ -0,0 +1,75 @@
+//! Terminal colors for CLI output
+//!
+//! Colors go through `colored`, which leaves output plain when stdout isn't
+//! a terminal or `NO_COLOR` is set; [`init`] adds the `--no-color` flag on
+//! top. JSON and CSV output is printed without these helpers, so it never
+//! contains escape codes.
+
+use colored::{ColoredString, Colorize};
+use std::io::IsTerminal;
+
+/// Whether output should be colored given the `--no-color` flag, the value
+/// of `NO_COLOR` and whether stdout is a terminal. A non-empty `NO_COLOR`
+/// turns color off, per <https://no-color.org>.
+pub fn color_enabled(no_color_flag: bool, no_color_env: Option<&str>, stdout_is_tty: bool) -> bool {
+    !no_color_flag && no_color_env.is_none_or(str::is_empty) && stdout_is_tty
+}
+
+/// Apply the color decision for this process; call once at startup
+pub fn init(no_color_flag: bool) {
+    let no_color_env = std::env::var("NO_COLOR").ok();
+    if !color_enabled(
+        no_color_flag,
+        no_color_env.as_deref(),
+        std::io::stdout().is_terminal(),
+    ) {
+        colored::control::set_override(false);
+    }
+}
+
+/// A severity or priority label (`critical`, `high`, `medium`, `low`,
+/// `info`, any case) in its color
+pub fn severity(label: &str) -> ColoredString {
+    match label.to_ascii_lowercase().as_str() {
+        "critical" => label.red().bold(),
+        "high" => label.red(),
+        "medium" => label.yellow(),
+        "low" => label.green(),
+        "info" => label.blue(),
+        _ => label.normal(),
+    }
+}
+
+/// `text` in green when `passed`, red otherwise
+pub fn pass_fail(passed: bool, text: &str) -> ColoredString {
+    if passed {
+        text.green()
+    } else {
+        text.red()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_no_color_env_yields_plain_output() {
+        assert!(!color_enabled(false, Some("1"), true));
+        assert!(!color_enabled(true, None, true));
+        assert!(!color_enabled(false, None, false));
+        // An empty NO_COLOR doesn't count
+        assert!(color_enabled(false, Some(""), true));
+
+        colored::control::set_override(false);
+        let line = format!(
+            "{} {} {}",
+            severity("CRITICAL"),
+            severity("low"),
+            pass_fail(false, "✗")
+        );
+        colored::control::unset_override();
+        assert_eq!(line, "CRITICAL low ✗");
+        assert!(!line.contains('\u{1b}'));
+    }
+}
@@ -32,7 +32,9 @@
 use crate::db::Database;
 use crate::grok_client::{FileScoreResult, GrokClient};
 use anyhow::{Context, Result};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 use std::process::Command;
 
@@ -42,7 +44,7 @@ pub struct CodeReviewer {
 }
 
 /// Review result for a single file
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct FileReview {
     /// File path
     pub path: String,
@@ -59,7 +61,7 @@ pub struct FileReview {
 }
 
 /// Review issue with severity
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ReviewIssue {
     /// Issue severity
     pub severity: IssueSeverity,
@@ -67,10 +69,32 @@ pub struct ReviewIssue {
     pub description: String,
     /// Optional line number
     pub line: Option<usize>,
+    /// Last line of a multi-line issue (inclusive); `None` means just `line`
+    #[serde(default)]
+    pub end_line: Option<usize>,
+    /// Full replacement text for `line..=end_line`, set only when the fix is
+    /// mechanical and safe to apply as-is
+    #[serde(default)]
+    pub suggestion: Option<String>,
+}
+
+/// Line-anchored comment for GitHub's pull request review API
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct GithubReviewComment {
+    /// File path relative to the repository root
+    pub path: String,
+    /// First line of a multi-line range
+    pub start_line: Option<usize>,
+    /// Line the comment is attached to (the last line of a range)
+    pub line: usize,
+    /// Comment body, including the suggestion block
+    pub body: String,
 }
 
 /// Issue severity levels
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(
+    Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, JsonSchema,
+)]
 pub enum IssueSeverity {
     /// Critical security or correctness issue
     Critical,
@@ -85,7 +109,7 @@ pub enum IssueSeverity {
 }
 
 /// Complete code review result
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct CodeReview {
     /// Repository path
     pub repo_path: String,
@@ -102,7 +126,7 @@ pub struct CodeReview {
 }
 
 /// Review statistics
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ReviewStats {
     /// Total files reviewed
     pub total_files: usize,
@@ -126,6 +150,24 @@ pub struct ReviewStats {
     pub total_lines_changed: usize,
 }
 
+/// Thresholds a review has to meet to pass, e.g. for gating CI on a PR
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReviewPolicy {
+    /// Maximum allowed issues per severity; severities not listed are unlimited
+    pub max_issues: BTreeMap<IssueSeverity, usize>,
+    /// Minimum average quality score (0-100)
+    pub min_avg_quality: f64,
+}
+
+/// Pass/fail outcome of checking a review against a [`ReviewPolicy`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReviewVerdict {
+    /// Whether the review met every threshold
+    pub passed: bool,
+    /// Why the review failed (empty when it passed)
+    pub reasons: Vec<String>,
+}
+
 impl CodeReviewer {
     /// Create a new code reviewer
     pub async fn new(db: Database) -> Result<Self> {
@@ -174,6 +216,46 @@ impl CodeReviewer {
         })
     }
 
+    /// Review only staged changes (`git diff --cached`), using the staged
+    /// content rather than the working tree
+    pub async fn review_staged(&self, repo_path: impl AsRef<Path>) -> Result<CodeReview> {
+        let repo_path = repo_path.as_ref();
+        let staged = staged_files(repo_path)?;
+
+        if staged.is_empty() {
+            return Ok(CodeReview {
+                repo_path: repo_path.to_string_lossy().to_string(),
+                base_branch: None,
+                files: vec![],
+                stats: ReviewStats::default(),
+                summary: "Nothing staged. Use `git add` to stage changes for review.".to_string(),
+                timestamp: chrono::Utc::now(),
+            });
+        }
+
+        let mut file_reviews = Vec::new();
+        for file in staged {
+            if let Ok(review) = self
+                .review_content(&file.path, &file.content, file.lines_changed)
+                .await
+            {
+                file_reviews.push(review);
+            }
+        }
+
+        let stats = self.calculate_stats(&file_reviews);
+        let summary = self.generate_summary(&stats);
+
+        Ok(CodeReview {
+            repo_path: repo_path.to_string_lossy().to_string(),
+            base_branch: None,
+            files: file_reviews,
+            stats,
+            summary,
+            timestamp: chrono::Utc::now(),
+        })
+    }
+
     /// Review specific files
     pub async fn review_files(&self, files: Vec<PathBuf>) -> Result<CodeReview> {
         let mut file_reviews = Vec::new();
@@ -201,7 +283,16 @@ impl CodeReviewer {
     async fn review_file(&self, path: &Path, lines_changed: usize) -> Result<FileReview> {
         let content = std::fs::read_to_string(path)
             .with_context(|| format!("Failed to read file: {}", path.display()))?;
+        self.review_content(path, &content, lines_changed).await
+    }
 
+    /// Review file content that may differ from what's on disk
+    async fn review_content(
+        &self,
+        path: &Path,
+        content: &str,
+        lines_changed: usize,
+    ) -> Result<FileReview> {
         // Skip very large files
         if content.len() > 100_000 {
             return Ok(FileReview {
@@ -212,6 +303,8 @@ impl CodeReviewer {
                     severity: IssueSeverity::Info,
                     description: "File too large for analysis (>100KB)".to_string(),
                     line: None,
+                    end_line: None,
+                    suggestion: None,
                 }],
                 suggestions: vec![],
                 lines_changed,
@@ -221,44 +314,54 @@ impl CodeReviewer {
         // Use Grok to score the file
         let score_result = self
             .grok_client
-            .score_file(path.to_str().unwrap(), &content)
+            .score_file(path.to_str().unwrap(), content)
             .await?;
 
         // Convert to review format
-        Ok(self.convert_to_file_review(path, score_result, lines_changed))
+        Ok(Self::convert_to_file_review(
+            path,
+            score_result,
+            lines_changed,
+        ))
     }
 
     /// Convert FileScoreResult to FileReview
     fn convert_to_file_review(
-        &self,
         path: &Path,
         score: FileScoreResult,
         lines_changed: usize,
     ) -> FileReview {
-        let mut issues = Vec::new();
-
-        // Categorize issues by severity
-        for issue in &score.issues {
-            let severity = self.determine_severity(issue, score.security_score);
-            issues.push(ReviewIssue {
-                severity,
-                description: issue.clone(),
-                line: None,
-            });
-        }
+        let issues = score
+            .issues
+            .into_iter()
+            .map(|issue| {
+                // A range or a replacement only makes sense anchored to a line
+                let end_line = issue
+                    .end_line
+                    .filter(|&end| issue.line.is_some_and(|start| end >= start));
+                let suggestion = issue.suggestion.filter(|_| issue.line.is_some());
+                ReviewIssue {
+                    severity: Self::determine_severity(&issue.description, score.security_score),
+                    description: issue.description,
+                    line: issue.line,
+                    end_line,
+                    suggestion,
+                }
+            })
+            .collect();
 
         FileReview {
             path: path.to_string_lossy().to_string(),
             score: score.overall_score,
             security_score: score.security_score,
             issues,
-            suggestions: score.suggestions.clone(),
+            suggestions: score.suggestions,
             lines_changed,
         }
     }
 
     /// Determine issue severity based on content and security score
-    fn determine_severity(&self, issue: &str, security_score: f64) -> IssueSeverity {
+    fn determine_severity(issue: &str, security_score: f64) -> IssueSeverity {
         let issue_lower = issue.to_lowercase();
 
         // Critical security issues
@@ -359,14 +462,7 @@ impl CodeReviewer {
 
     /// Check if file should be reviewed
     fn is_reviewable_file(&self, path: &Path) -> bool {
-        if let Some(ext) = path.extension() {
-            matches!(
-                ext.to_str().unwrap_or(""),
-                "rs" | "py" | "js" | "ts" | "java" | "kt" | "go" | "c" | "cpp" | "h" | "hpp"
-            )
-        } else {
-            false
-        }
+        is_reviewable_path(path)
     }
 
     /// Count changed lines for a file
@@ -503,6 +599,87 @@ impl CodeReviewer {
     }
 }
 
+/// Check whether a file has a reviewable source extension
+fn is_reviewable_path(path: &Path) -> bool {
+    if let Some(ext) = path.extension() {
+        matches!(
+            ext.to_str().unwrap_or(""),
+            "rs" | "py" | "js" | "ts" | "java" | "kt" | "go" | "c" | "cpp" | "h" | "hpp"
+        )
+    } else {
+        false
+    }
+}
+
+/// A staged file as it exists in the index
+struct StagedFile {
+    path: PathBuf,
+    content: String,
+    lines_changed: usize,
+}
+
+/// Collect reviewable files staged in the index, diffed against HEAD.
+/// Files in a repository with no commits yet count as fully staged.
+fn staged_files(repo_path: &Path) -> Result<Vec<StagedFile>> {
+    let repo = git2::Repository::discover(repo_path)
+        .with_context(|| format!("Not a git repository: {}", repo_path.display()))?;
+    let workdir = repo
+        .workdir()
+        .context("Cannot review staged changes in a bare repository")?
+        .to_path_buf();
+
+    let head_tree = match repo.head() {
+        Ok(head) => Some(head.peel_to_tree()?),
+        Err(e)
+            if e.code() == git2::ErrorCode::UnbornBranch
+                || e.code() == git2::ErrorCode::NotFound =>
+        {
+            None
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    let diff = repo.diff_tree_to_index(head_tree.as_ref(), None, None)?;
+    let mut files = Vec::new();
+
+    for (idx, delta) in diff.deltas().enumerate() {
+        if !matches!(
+            delta.status(),
+            git2::Delta::Added | git2::Delta::Modified | git2::Delta::Renamed | git2::Delta::Copied
+        ) {
+            continue;
+        }
+        let Some(rel_path) = delta.new_file().path() else {
+            continue;
+        };
+        let path = workdir.join(rel_path);
+        if !is_reviewable_path(&path) {
+            continue;
+        }
+
+        let blob = repo.find_blob(delta.new_file().id())?;
+        if blob.is_binary() {
+            continue;
+        }
+
+        let lines_changed = match git2::Patch::from_diff(&diff, idx)? {
+            Some(patch) => {
+                let (_, additions, deletions) = patch.line_stats()?;
+                additions + deletions
+            }
+            None => 0,
+        };
+
+        files.push(StagedFile {
+            path,
+            content: String::from_utf8_lossy(blob.content()).into_owned(),
+            lines_changed,
+        });
+    }
+
+    Ok(files)
+}
+
 impl CodeReview {
     /// Format review as markdown
     pub fn format_markdown(&self) -> String {
@@ -614,17 +791,63 @@ impl CodeReview {
         output
     }
 
-    /// Format as GitHub PR comment
+    /// Number of issues of the given severity across all files
+    pub fn issue_count(&self, severity: IssueSeverity) -> usize {
+        self.files
+            .iter()
+            .flat_map(|f| &f.issues)
+            .filter(|i| i.severity == severity)
+            .count()
+    }
+
+    /// Check the review against a policy
+    pub fn verdict(&self, policy: &ReviewPolicy) -> ReviewVerdict {
+        let mut reasons = Vec::new();
+
+        for (&severity, &max) in &policy.max_issues {
+            let count = self.issue_count(severity);
+            if count > max {
+                reasons.push(format!(
+                    "{} {} issue(s) found (max {})",
+                    count,
+                    severity.to_string().to_lowercase(),
+                    max
+                ));
+            }
+        }
+
+        // An empty review has no meaningful average
+        if !self.files.is_empty() && self.stats.avg_quality < policy.min_avg_quality {
+            reasons.push(format!(
+                "average quality {:.1} is below {:.1}",
+                self.stats.avg_quality, policy.min_avg_quality
+            ));
+        }
+
+        ReviewVerdict {
+            passed: reasons.is_empty(),
+            reasons,
+        }
+    }
+
+    /// Format as GitHub PR comment, judged by the default policy
     pub fn format_github_comment(&self) -> String {
+        self.format_github_comment_with_policy(&ReviewPolicy::default())
+    }
+
+    /// Format as GitHub PR comment, leading with the verdict under `policy`
+    pub fn format_github_comment_with_policy(&self, policy: &ReviewPolicy) -> String {
         let mut output = String::new();
 
-        // Summary with emoji
-        if self.stats.critical_issues > 0 {
-            output.push_str("## 🔴 Code Review - Action Required\n\n");
-        } else if self.stats.high_issues > 0 {
-            output.push_str("## ⚠️  Code Review - Issues Found\n\n");
+        let verdict = self.verdict(policy);
+        if verdict.passed {
+            output.push_str("## ✅ Code Review - Passed\n\n");
         } else {
-            output.push_str("## ✅ Code Review - Looks Good\n\n");
+            output.push_str("## ❌ Code Review - Failed\n\n");
+            for reason in &verdict.reasons {
+                output.push_str(&format!("- {}\n", reason));
+            }
+            output.push('\n');
         }
 
         output.push_str(&self.summary);
@@ -659,8 +882,134 @@ impl CodeReview {
             }
         }
 
+        // Mechanical fixes as apply-able suggestions
+        let suggested: Vec<_> = self
+            .files
+            .iter()
+            .flat_map(|f| f.issues.iter().map(move |i| (f, i)))
+            .filter_map(|(f, i)| i.suggested_change().map(|change| (f, i, change)))
+            .collect();
+
+        if !suggested.is_empty() {
+            output.push_str("### 💡 Suggested Changes\n\n");
+            for (file, issue, (start, end, replacement)) in suggested {
+                let lines = if end > start {
+                    format!("lines {}-{}", start, end)
+                } else {
+                    format!("line {}", start)
+                };
+                output.push_str(&format!(
+                    "**{}** {} — {:?}: {}\n\n",
+                    file.path, lines, issue.severity, issue.description
+                ));
+                output.push_str(&suggestion_block(replacement));
+                output.push('\n');
+            }
+        }
+
         output
     }
+
+    /// Line-anchored review comments for every issue with a suggested fix,
+    /// ready to post through GitHub's pull request review API
+    pub fn github_review_comments(&self) -> Vec<GithubReviewComment> {
+        self.files
+            .iter()
+            .flat_map(|f| f.issues.iter().map(move |i| (f, i)))
+            .filter_map(|(file, issue)| {
+                let (start, end, replacement) = issue.suggested_change()?;
+                Some(GithubReviewComment {
+                    path: file.path.clone(),
+                    start_line: (end > start).then_some(start),
+                    line: end,
+                    body: format!(
+                        "**{:?}:** {}\n\n{}",
+                        issue.severity,
+                        issue.description,
+                        suggestion_block(replacement)
+                    ),
+                })
+            })
+            .collect()
+    }
+}
+
+impl ReviewIssue {
+    /// Attach a replacement for lines `start..=end`
+    pub fn with_suggestion(mut self, start: usize, end: usize, replacement: &str) -> Self {
+        self.line = Some(start);
+        self.end_line = (end > start).then_some(end);
+        self.suggestion = Some(replacement.to_string());
+        self
+    }
+
+    /// Line range and replacement text, if this issue can be rendered as a
+    /// GitHub suggested change
+    fn suggested_change(&self) -> Option<(usize, usize, &str)> {
+        let start = self.line?;
+        let end = self.end_line.unwrap_or(start).max(start);
+        Some((start, end, self.suggestion.as_deref()?))
+    }
+}
+
+/// Render a ```suggestion block, using a longer fence if the replacement
+/// itself contains backticks
+fn suggestion_block(replacement: &str) -> String {
+    let mut longest = 0;
+    let mut run = 0;
+    for c in replacement.chars() {
+        run = if c == '`' { run + 1 } else { 0 };
+        longest = longest.max(run);
+    }
+    let fence = "`".repeat(longest.max(2) + 1);
+    format!(
+        "{}suggestion\n{}\n{}\n",
+        fence,
+        replacement.trim_end_matches('\n'),
+        fence
+    )
+}
+
+impl Default for ReviewPolicy {
+    /// No critical or high issues, and at least "Acceptable" quality
+    fn default() -> Self {
+        Self {
+            max_issues: BTreeMap::from([(IssueSeverity::Critical, 0), (IssueSeverity::High, 0)]),
+            min_avg_quality: 60.0,
+        }
+    }
+}
+
+impl ReviewPolicy {
+    /// Policy with no thresholds; every review passes
+    pub fn permissive() -> Self {
+        Self {
+            max_issues: BTreeMap::new(),
+            min_avg_quality: 0.0,
+        }
+    }
+
+    /// Set the maximum allowed issues for a severity
+    pub fn with_max_issues(mut self, severity: IssueSeverity, max: usize) -> Self {
+        self.max_issues.insert(severity, max);
+        self
+    }
+
+    /// Set the minimum average quality score
+    pub fn with_min_avg_quality(mut self, min: f64) -> Self {
+        self.min_avg_quality = min;
+        self
+    }
+}
+
+impl std::fmt::Display for ReviewVerdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.passed {
+            write!(f, "✅ passed")
+        } else {
+            write!(f, "❌ failed: {}", self.reasons.join("; "))
+        }
+    }
 }
 
 impl Default for ReviewStats {
@@ -691,3 +1040,251 @@ impl std::fmt::Display for IssueSeverity {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(path: &str, score: f64, severities: &[IssueSeverity]) -> FileReview {
+        FileReview {
+            path: path.to_string(),
+            score,
+            security_score: 90.0,
+            issues: severities
+                .iter()
+                .map(|&severity| ReviewIssue {
+                    severity,
+                    description: format!("{} issue", severity),
+                    line: None,
+                    end_line: None,
+                    suggestion: None,
+                })
+                .collect(),
+            suggestions: vec![],
+            lines_changed: 10,
+        }
+    }
+
+    fn review(files: Vec<FileReview>) -> CodeReview {
+        let avg_quality = if files.is_empty() {
+            0.0
+        } else {
+            files.iter().map(|f| f.score).sum::<f64>() / files.len() as f64
+        };
+        CodeReview {
+            repo_path: ".".to_string(),
+            base_branch: None,
+            stats: ReviewStats {
+                total_files: files.len(),
+                total_issues: files.iter().map(|f| f.issues.len()).sum(),
+                avg_quality,
+                ..ReviewStats::default()
+            },
+            files,
+            summary: String::new(),
+            timestamp: chrono::Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_verdict_default_policy() {
+        let clean = review(vec![file(
+            "a.rs",
+            85.0,
+            &[IssueSeverity::Low, IssueSeverity::Info],
+        )]);
+        let verdict = clean.verdict(&ReviewPolicy::default());
+        assert!(verdict.passed);
+        assert!(verdict.reasons.is_empty());
+
+        let bad = review(vec![
+            file("a.rs", 50.0, &[IssueSeverity::High]),
+            file(
+                "b.rs",
+                60.0,
+                &[IssueSeverity::Critical, IssueSeverity::High],
+            ),
+        ]);
+        let verdict = bad.verdict(&ReviewPolicy::default());
+        assert!(!verdict.passed);
+        assert_eq!(verdict.reasons.len(), 3);
+        assert!(verdict.reasons[0].contains("1 critical"));
+        assert!(verdict.reasons[1].contains("2 high"));
+        assert!(verdict.reasons[2].contains("below 60.0"));
+    }
+
+    #[test]
+    fn test_verdict_custom_policy() {
+        let r = review(vec![file(
+            "a.rs",
+            70.0,
+            &[
+                IssueSeverity::Medium,
+                IssueSeverity::Medium,
+                IssueSeverity::Info,
+            ],
+        )]);
+        assert!(r.verdict(&ReviewPolicy::permissive()).passed);
+
+        let strict = ReviewPolicy::permissive()
+            .with_max_issues(IssueSeverity::Medium, 1)
+            .with_min_avg_quality(80.0);
+        let verdict = r.verdict(&strict);
+        assert!(!verdict.passed);
+        assert_eq!(verdict.reasons.len(), 2);
+
+        // An empty review passes regardless of the quality floor
+        assert!(review(vec![]).verdict(&strict).passed);
+    }
+
+    #[test]
+    fn test_github_comment_suggestion_blocks() {
+        let mut a = file(
+            "src/a.rs",
+            90.0,
+            &[IssueSeverity::Low, IssueSeverity::Medium],
+        );
+        a.issues[0] = a.issues[0]
+            .clone()
+            .with_suggestion(12, 12, "    let x = value?;");
+        a.issues[1] = a.issues[1].clone().with_suggestion(
+            20,
+            22,
+            "    if let Some(v) = opt {\n        use_it(v);\n    }\n",
+        );
+        let mut b = file("src/b.rs", 90.0, &[IssueSeverity::High]);
+        b.issues[0].line = Some(5); // no mechanical fix
+        let comment = review(vec![a, b]).format_github_comment();
+
+        assert!(comment.contains(
+            "**src/a.rs** line 12 — Low: LOW issue\n\n```suggestion\n    let x = value?;\n```\n"
+        ));
+        assert!(comment.contains(
+            "**src/a.rs** lines 20-22 — Medium: MEDIUM issue\n\n```suggestion\n    if let Some(v) = opt {\n        use_it(v);\n    }\n```\n"
+        ));
+        assert!(!comment.contains("**src/b.rs** line 5"));
+        assert_eq!(comment.matches("```suggestion").count(), 2);
+    }
+
+    #[test]
+    fn test_github_review_comments_anchor_ranges() {
+        let mut a = file("src/a.rs", 90.0, &[IssueSeverity::Low, IssueSeverity::Low]);
+        a.issues[0] = a.issues[0].clone().with_suggestion(3, 3, "fn a() {}");
+        a.issues[1] = a.issues[1]
+            .clone()
+            .with_suggestion(7, 9, "let s = \"```\";");
+        let comments = review(vec![a]).github_review_comments();
+
+        assert_eq!(comments.len(), 2);
+        assert_eq!(comments[0].path, "src/a.rs");
+        assert_eq!((comments[0].start_line, comments[0].line), (None, 3));
+        assert_eq!((comments[1].start_line, comments[1].line), (Some(7), 9));
+        // Replacement containing a fence gets a longer one
+        assert!(comments[1]
+            .body
+            .contains("````suggestion\nlet s = \"```\";\n````"));
+    }
+
+    #[test]
+    fn test_file_review_takes_lines_and_suggestions_from_llm() {
+        let response = r#"{
+            "overall_score": 70, "security_score": 80, "quality_score": 70,
+            "complexity_score": 40, "maintainability_score": 70,
+            "summary": "ok",
+            "issues": [
+                "Missing documentation",
+                {"description": "unwrap on user input", "line": 4, "end_line": 5,
+                 "suggestion": "    let n = input.parse()?;"},
+                {"description": "Naming style", "line": 9},
+                {"description": "Floating fix", "suggestion": "fn b() {}"},
+                {"description": "Backwards range", "line": 8, "end_line": 2}
+            ],
+            "suggestions": ["Add docs"]
+        }"#;
+        let score: FileScoreResult = serde_json::from_str(response).unwrap();
+        let file = CodeReviewer::convert_to_file_review(Path::new("src/a.rs"), score, 10);
+
+        let located: Vec<_> = file
+            .issues
+            .iter()
+            .map(|i| (i.line, i.end_line, i.suggestion.as_deref()))
+            .collect();
+        assert_eq!(
+            located,
+            vec![
+                (None, None, None),
+                (Some(4), Some(5), Some("    let n = input.parse()?;")),
+                (Some(9), None, None),
+                // A replacement with no line to anchor it is dropped
+                (None, None, None),
+                (Some(8), None, None),
+            ]
+        );
+        assert_eq!(file.issues[1].severity, IssueSeverity::High);
+
+        let comments = review(vec![file]).github_review_comments();
+        assert_eq!(comments.len(), 1);
+        assert_eq!((comments[0].start_line, comments[0].line), (Some(4), 5));
+        assert!(comments[0].body.contains("let n = input.parse()?;"));
+    }
+
+    fn stage(repo: &git2::Repository, rel: &str, content: &str) {
+        std::fs::write(repo.workdir().unwrap().join(rel), content).unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new(rel)).unwrap();
+        index.write().unwrap();
+    }
+
+    #[test]
+    fn test_staged_files_new_repo_fully_staged() {
+        let temp = tempfile::tempdir().unwrap();
+        let repo = git2::Repository::init(temp.path()).unwrap();
+        assert!(staged_files(temp.path()).unwrap().is_empty());
+
+        stage(&repo, "main.rs", "fn main() {\n    println!(\"hi\");\n}\n");
+        stage(&repo, "notes.txt", "not code\n");
+
+        let files = staged_files(temp.path()).unwrap();
+        assert_eq!(files.len(), 1);
+        assert!(files[0].path.ends_with("main.rs"));
+        assert_eq!(files[0].lines_changed, 3);
+    }
+
+    #[test]
+    fn test_staged_files_ignore_unstaged_edits() {
+        let temp = tempfile::tempdir().unwrap();
+        let repo = git2::Repository::init(temp.path()).unwrap();
+        stage(&repo, "lib.rs", "pub fn a() {}\n");
+        stage(&repo, "other.rs", "pub fn b() {}\n");
+
+        let sig = git2::Signature::now("test", "test@example.com").unwrap();
+        let tree = repo
+            .find_tree(repo.index().unwrap().write_tree().unwrap())
+            .unwrap();
+        repo.commit(Some("HEAD"), &sig, &sig, "init", &tree, &[])
+            .unwrap();
+        assert!(staged_files(temp.path()).unwrap().is_empty());
+
+        stage(&repo, "lib.rs", "pub fn a() {}\npub fn c() {}\n");
+        // Unstaged working-tree edits are neither listed nor read
+        std::fs::write(temp.path().join("lib.rs"), "garbage").unwrap();
+        std::fs::write(temp.path().join("other.rs"), "garbage").unwrap();
+
+        let files = staged_files(temp.path()).unwrap();
+        assert_eq!(files.len(), 1);
+        assert!(files[0].path.ends_with("lib.rs"));
+        assert_eq!(files[0].content, "pub fn a() {}\npub fn c() {}\n");
+        assert_eq!(files[0].lines_changed, 1);
+    }
+
+    #[test]
+    fn test_github_comment_leads_with_verdict() {
+        let passed = review(vec![file("a.rs", 90.0, &[])]).format_github_comment();
+        assert!(passed.starts_with("## ✅"));
+
+        let failed =
+            review(vec![file("a.rs", 90.0, &[IssueSeverity::Critical])]).format_github_comment();
+        assert!(failed.starts_with("## ❌"));
+        assert!(failed.contains("- 1 critical issue(s) found (max 0)"));
+    }
+}
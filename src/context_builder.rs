@@ -8,9 +8,11 @@
 //!
 //! - Load entire repositories into context
 //! - Smart filtering by language, path, or recency
-//! - Query-aware context selection
+//! - Query-aware context selection, optionally ranked by embedding
+//!   similarity to the question ([`QueryBuilder::relevant_to`])
 //! - Cross-repository analysis
-//! - Token budget management
+//! - Token budget management (importance- and recency-ranked selection
+//!   with [`ContextBuilder::with_token_budget`])
 //! - Response caching
 //!
 //! ## Usage
@@ -39,9 +41,16 @@
 //! ```
 
 use crate::db::Database;
+use crate::embeddings::{Embedding, EmbeddingConfig, EmbeddingGenerator};
 use crate::repo_analysis::RepoAnalyzer;
+use crate::scoring::FileScorer;
 use anyhow::Result;
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use tracing::warn;
+use walkdir::WalkDir;
 
 /// Maximum tokens for Grok context window (grok-4-1-fast has 2M limit, use 1.5M to be safe)
 const MAX_CONTEXT_TOKENS: usize = 1_500_000;
@@ -49,6 +58,24 @@ const MAX_CONTEXT_TOKENS: usize = 1_500_000;
 /// Estimated tokens per character (conservative)
 const TOKENS_PER_CHAR: f64 = 0.3;
 
+/// Model whose tokenizer counts tokens for budgeted contexts
+const CONTEXT_MODEL: &str = "grok-4-1-fast-reasoning";
+
+/// Smallest leftover budget worth filling with a truncated file
+const MIN_TRUNCATED_TOKENS: usize = 256;
+
+/// Appended to files cut short to fit the budget
+const TRUNCATION_MARKER: &str = "\n[... truncated to fit the token budget ...]\n";
+
+/// Files kept by relevance ranking unless [`QueryBuilder::top_k`] says otherwise
+const DEFAULT_RELEVANT_FILES: usize = 20;
+
+/// Leading lines of a file embedded when it has no stored embedding
+const SUMMARY_LINES: usize = 40;
+
+/// Cap on the characters embedded per file summary
+const SUMMARY_MAX_CHARS: usize = 2000;
+
 /// Context builder for RAG queries
 #[derive(Clone)]
 pub struct ContextBuilder {
@@ -61,6 +88,17 @@ pub struct ContextBuilder {
     recent_only: Option<usize>,
     include_notes: bool,
     max_tokens: usize,
+    token_budget: Option<usize>,
+    relevance: Option<RelevanceQuery>,
+    file_focus: Option<(PathBuf, usize)>,
+}
+
+/// Question to rank files against, see [`ContextBuilder::rank_by_relevance`]
+#[derive(Clone)]
+struct RelevanceQuery {
+    question: String,
+    generator: Option<EmbeddingGenerator>,
+    top_k: usize,
 }
 
 /// Built context ready for LLM
@@ -112,6 +150,297 @@ pub struct ContextMetadata {
     pub estimated_tokens: usize,
     /// Whether context was truncated
     pub truncated: bool,
+    /// Budget the files were selected against, if any
+    #[serde(default)]
+    pub token_budget: Option<usize>,
+    /// Files (`repo/path`) cut short to fit the budget
+    #[serde(default)]
+    pub truncated_files: Vec<String>,
+    /// Files (`repo/path`) left out to stay within the budget
+    #[serde(default)]
+    pub dropped_files: Vec<String>,
+    /// Similarity of each relevance-ranked file to the question, highest
+    /// first; empty when embeddings were unavailable
+    #[serde(default)]
+    pub similarity_scores: Vec<FileSimilarity>,
+}
+
+/// Cosine similarity between a file and the question
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FileSimilarity {
+    /// File as `repo/path`
+    pub file: String,
+    pub score: f32,
+}
+
+/// A loaded file competing for a place in a token-budgeted context
+#[derive(Debug, Clone)]
+pub struct RankedFile {
+    pub file: ContextFile,
+    /// `FileScorer` importance (0-100)
+    pub importance: f64,
+    /// Last modification time, if known
+    pub modified: Option<DateTime<Utc>>,
+    /// Similarity to the question, when ranking by relevance
+    pub relevance: Option<f32>,
+    /// Import hops from the focus file of a [`ContextBuilder::with_file_and_deps`] build
+    pub hops: Option<usize>,
+}
+
+impl RankedFile {
+    /// Relevance to the question when known; otherwise importance weighted
+    /// 70/30 with recency, which halves every 30 days, with nearer
+    /// dependencies of a focus file always ahead of farther ones
+    fn rank(&self, now: DateTime<Utc>) -> f64 {
+        if let Some(similarity) = self.relevance {
+            return similarity as f64 * 100.0;
+        }
+
+        let recency = self
+            .modified
+            .map(|modified| {
+                let age_days = (now - modified).num_hours().max(0) as f64 / 24.0;
+                100.0 * 0.5f64.powf(age_days / 30.0)
+            })
+            .unwrap_or(0.0);
+        let score = self.importance * 0.7 + recency * 0.3;
+        score - self.hops.unwrap_or(0) as f64 * 100.0
+    }
+
+    fn label(&self) -> String {
+        format!("{}/{}", self.file.repository, self.file.path)
+    }
+}
+
+/// Files chosen to fit a token budget
+#[derive(Debug, Clone, Default)]
+pub struct BudgetSelection {
+    /// Included files, highest ranked first
+    pub files: Vec<ContextFile>,
+    /// Tokens used by the included files
+    pub tokens: usize,
+    /// Files (`repo/path`) included only in part
+    pub truncated: Vec<String>,
+    /// Files (`repo/path`) left out
+    pub dropped: Vec<String>,
+}
+
+/// Rank `candidates` by importance and recency and greedily include them
+/// until `budget` tokens are used. A file that doesn't fit is truncated
+/// when enough budget is left to make that worthwhile, and dropped
+/// otherwise; smaller, lower-ranked files may still fill the remainder.
+pub fn select_within_budget(
+    mut candidates: Vec<RankedFile>,
+    budget: usize,
+    now: DateTime<Utc>,
+) -> BudgetSelection {
+    candidates.sort_by(|a, b| {
+        b.rank(now)
+            .partial_cmp(&a.rank(now))
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| a.label().cmp(&b.label()))
+    });
+
+    let mut selection = BudgetSelection::default();
+    for candidate in candidates {
+        let remaining = budget - selection.tokens;
+        let tokens = count_tokens(&candidate.file.content);
+
+        if tokens <= remaining {
+            selection.tokens += tokens;
+            selection.files.push(candidate.file);
+        } else if remaining >= MIN_TRUNCATED_TOKENS {
+            let label = candidate.label();
+            let mut file = candidate.file;
+            let (content, tokens) = truncate_to_tokens(&file.content, remaining);
+            file.content = content;
+            selection.tokens += tokens;
+            selection.files.push(file);
+            selection.truncated.push(label);
+        } else {
+            selection.dropped.push(candidate.label());
+        }
+    }
+
+    selection
+}
+
+/// Score `candidates` against the question and keep the `top_k` most
+/// similar; `embeddings[i]` belongs to `candidates[i]`
+pub fn rank_by_similarity(
+    candidates: Vec<RankedFile>,
+    question: &Embedding,
+    embeddings: &[Embedding],
+    top_k: usize,
+) -> (Vec<RankedFile>, Vec<FileSimilarity>) {
+    let mut ranked: Vec<RankedFile> = candidates
+        .into_iter()
+        .zip(embeddings)
+        .map(|(mut candidate, embedding)| {
+            candidate.relevance = Some(question.cosine_similarity(embedding).unwrap_or(0.0));
+            candidate
+        })
+        .collect();
+    ranked.sort_by(|a, b| {
+        b.relevance
+            .partial_cmp(&a.relevance)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| a.label().cmp(&b.label()))
+    });
+    ranked.truncate(top_k);
+
+    let scores = ranked
+        .iter()
+        .map(|candidate| FileSimilarity {
+            file: candidate.label(),
+            score: candidate.relevance.unwrap_or(0.0),
+        })
+        .collect();
+    (ranked, scores)
+}
+
+/// Text embedded for a file without a stored embedding: its path and
+/// leading lines
+fn file_summary(file: &ContextFile) -> String {
+    let mut summary = format!("{}\n", file.path);
+    for line in file.content.lines().take(SUMMARY_LINES) {
+        summary.push_str(line);
+        summary.push('\n');
+    }
+    summary.chars().take(SUMMARY_MAX_CHARS).collect()
+}
+
+/// Mean of a file's chunk embeddings
+fn mean_embedding(vectors: &[Vec<f32>], model: &str) -> Option<Embedding> {
+    let dimension = vectors.first()?.len();
+    let mut mean = vec![0.0f32; dimension];
+    for vector in vectors {
+        for (sum, value) in mean.iter_mut().zip(vector) {
+            *sum += value;
+        }
+    }
+    for sum in &mut mean {
+        *sum /= vectors.len() as f32;
+    }
+    Some(Embedding::new(mean, model.to_string(), dimension))
+}
+
+/// Embed the question and every candidate, reusing embeddings stored in
+/// `stored` (keyed by `repo/path`) when they come from the same model
+async fn embed_candidates(
+    generator: &EmbeddingGenerator,
+    question: &str,
+    candidates: &[RankedFile],
+    stored: &HashMap<String, Embedding>,
+) -> Result<(Embedding, Vec<Embedding>)> {
+    let question = generator.embed(question).await?;
+
+    let missing: Vec<usize> = (0..candidates.len())
+        .filter(|&i| !stored.contains_key(&candidates[i].label()))
+        .collect();
+    let summaries: Vec<String> = missing
+        .iter()
+        .map(|&i| file_summary(&candidates[i].file))
+        .collect();
+    let summary_refs: Vec<&str> = summaries.iter().map(String::as_str).collect();
+    let mut generated: HashMap<usize, Embedding> = missing
+        .into_iter()
+        .zip(generator.embed_batch(&summary_refs).await?)
+        .collect();
+
+    let embeddings = candidates
+        .iter()
+        .enumerate()
+        .map(|(i, candidate)| match generated.remove(&i) {
+            Some(embedding) => embedding,
+            None => stored[&candidate.label()].clone(),
+        })
+        .collect();
+    Ok((question, embeddings))
+}
+
+/// `path` and the crate-local files it imports, transitively up to `depth`
+/// hops, each with its distance from `path`. Imports are resolved from Rust
+/// `mod` and `use` items against the files under the enclosing `src/`
+/// directory, and every file is visited once, so import cycles terminate.
+/// Non-Rust files have no dependencies.
+pub fn file_with_deps(path: &Path, depth: usize) -> Vec<(PathBuf, usize)> {
+    let mut result = vec![(path.to_path_buf(), 0)];
+    let Some(src) = path
+        .ancestors()
+        .find(|a| a.file_name() == Some("src".as_ref()))
+    else {
+        return result;
+    };
+
+    let modules: HashMap<Vec<String>, PathBuf> = WalkDir::new(src)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .filter_map(|e| {
+            let path = e.into_path();
+            crate::llm_audit::rust_module_path(&path).map(|module| (module, path))
+        })
+        .collect();
+
+    let mut visited: HashSet<PathBuf> = HashSet::from([path.to_path_buf()]);
+    let mut frontier = vec![path.to_path_buf()];
+    for hop in 1..=depth {
+        let mut next = Vec::new();
+        for file in &frontier {
+            let Some(module) = crate::llm_audit::rust_module_path(file) else {
+                continue;
+            };
+            let Ok(content) = std::fs::read_to_string(file) else {
+                continue;
+            };
+            for target in crate::llm_audit::resolve_rust_imports(&module, &content, &modules) {
+                if visited.insert(target.clone()) {
+                    result.push((target.clone(), hop));
+                    next.push(target.clone());
+                }
+            }
+        }
+        frontier = next;
+    }
+
+    result
+}
+
+fn count_tokens(text: &str) -> usize {
+    crate::tokenizer::count_tokens(text, CONTEXT_MODEL)
+}
+
+/// Keep whole leading lines of `content`, plus [`TRUNCATION_MARKER`], within
+/// `max_tokens`; returns the text and its token count
+fn truncate_to_tokens(content: &str, max_tokens: usize) -> (String, usize) {
+    let available = max_tokens.saturating_sub(count_tokens(TRUNCATION_MARKER));
+    let lines: Vec<&str> = content.split_inclusive('\n').collect();
+
+    let mut kept = 0;
+    let mut used = 0;
+    for line in &lines {
+        let tokens = count_tokens(line);
+        if used + tokens > available {
+            break;
+        }
+        used += tokens;
+        kept += 1;
+    }
+
+    // Per-line counts can differ slightly from the joined text's
+    loop {
+        let mut text = lines[..kept].concat();
+        text.push_str(TRUNCATION_MARKER);
+        let tokens = count_tokens(&text);
+        if tokens <= max_tokens {
+            return (text, tokens);
+        }
+        if kept == 0 {
+            return (String::new(), 0);
+        }
+        kept -= 1;
+    }
 }
 
 impl ContextBuilder {
@@ -132,6 +461,9 @@ impl ContextBuilder {
             recent_only: None,
             include_notes: false,
             max_tokens: MAX_CONTEXT_TOKENS, // Default to safe limit
+            token_budget: None,
+            relevance: None,
+            file_focus: None,
         }
     }
 
@@ -189,14 +521,188 @@ impl ContextBuilder {
         self
     }
 
+    /// Select files to fit a hard token budget. Matching files are ranked
+    /// by `FileScorer` importance and recency and included, most important
+    /// first, until the budget is used; the rest are truncated or dropped
+    /// and listed in [`ContextMetadata`]. Notes are counted first.
+    pub fn with_token_budget(mut self, max_tokens: usize) -> Self {
+        self.max_tokens = max_tokens;
+        self.token_budget = Some(max_tokens);
+        self
+    }
+
+    /// Keep the `top_k` files most similar to `question` (by embedding
+    /// cosine similarity), filled into the token budget in that order.
+    /// Stored document embeddings are reused; other files are embedded
+    /// from a short summary. Without a `generator` the default local model
+    /// is used, and if embedding fails the filtered files are ranked by
+    /// importance instead.
+    pub fn rank_by_relevance(
+        mut self,
+        question: impl Into<String>,
+        generator: Option<EmbeddingGenerator>,
+        top_k: usize,
+    ) -> Self {
+        self.relevance = Some(RelevanceQuery {
+            question: question.into(),
+            generator,
+            top_k,
+        });
+        self
+    }
+
+    /// Build the context around one file: `path` plus the files it imports,
+    /// up to `depth` hops through the import graph, instead of whole
+    /// repositories. Nearer files are kept first when the token budget
+    /// (or the default context limit) runs out.
+    pub fn with_file_and_deps(mut self, path: impl Into<PathBuf>, depth: usize) -> Self {
+        self.file_focus = Some((path.into(), depth));
+        self
+    }
+
+    /// Load the focus file and its dependencies as candidates
+    fn load_file_and_deps(
+        path: &Path,
+        depth: usize,
+        scorer: &FileScorer,
+    ) -> Result<(String, Vec<RankedFile>)> {
+        let root = path
+            .ancestors()
+            .find(|a| a.file_name() == Some("src".as_ref()))
+            .and_then(Path::parent)
+            .or_else(|| path.parent())
+            .unwrap_or(Path::new(""));
+        let repository = std::fs::canonicalize(root)
+            .ok()
+            .and_then(|p| p.file_name().map(|n| n.to_string_lossy().to_string()))
+            .unwrap_or_else(|| ".".to_string());
+
+        let mut candidates = Vec::new();
+        for (file, hops) in file_with_deps(path, depth) {
+            let content = match std::fs::read_to_string(&file) {
+                Ok(content) => content,
+                Err(e) if hops == 0 => {
+                    anyhow::bail!("Failed to read {}: {}", file.display(), e)
+                }
+                Err(_) => continue,
+            };
+            let importance = scorer
+                .score_file(&file, &content, &[], &[])
+                .map(|score| score.importance)
+                .unwrap_or(50.0);
+            let modified = std::fs::metadata(&file)
+                .and_then(|m| m.modified())
+                .ok()
+                .map(DateTime::<Utc>::from);
+
+            candidates.push(RankedFile {
+                file: ContextFile {
+                    repository: repository.clone(),
+                    path: file
+                        .strip_prefix(root)
+                        .unwrap_or(&file)
+                        .to_string_lossy()
+                        .to_string(),
+                    language: RepoAnalyzer::detect_language(&file),
+                    size: content.len(),
+                    content,
+                },
+                importance,
+                modified,
+                relevance: None,
+                hops: Some(hops),
+            });
+        }
+
+        Ok((repository, candidates))
+    }
+
+    /// Rank `candidates` by similarity to the relevance question. If
+    /// embeddings are unavailable the candidates come back unranked, with
+    /// no scores.
+    async fn rank_candidates(
+        &self,
+        query: &RelevanceQuery,
+        repos: &[crate::db::Repository],
+        candidates: Vec<RankedFile>,
+    ) -> (Vec<RankedFile>, Vec<FileSimilarity>) {
+        let generator = match &query.generator {
+            Some(generator) => generator.clone(),
+            None => match EmbeddingGenerator::new(EmbeddingConfig::default()) {
+                Ok(generator) => generator,
+                Err(e) => {
+                    warn!(
+                        "Embeddings unavailable, using filter-based selection: {}",
+                        e
+                    );
+                    return (candidates, Vec::new());
+                }
+            },
+        };
+
+        let mut stored = HashMap::new();
+        for repo in repos {
+            let rows = match crate::db::get_repo_file_embeddings(&self.db.pool, &repo.id).await {
+                Ok(rows) => rows,
+                Err(e) => {
+                    warn!("Stored embeddings unavailable for {}: {}", repo.name, e);
+                    continue;
+                }
+            };
+
+            let mut by_file: HashMap<String, Vec<Vec<f32>>> = HashMap::new();
+            for (path, row) in rows {
+                if row.model != generator.model_name()
+                    || row.dimension as usize != generator.dimension()
+                {
+                    continue;
+                }
+                if let Ok(vector) = row.parse_embedding() {
+                    by_file.entry(path).or_default().push(vector);
+                }
+            }
+            for (path, vectors) in by_file {
+                if let Some(mean) = mean_embedding(&vectors, generator.model_name()) {
+                    stored.insert(format!("{}/{}", repo.name, path), mean);
+                }
+            }
+        }
+
+        match embed_candidates(&generator, &query.question, &candidates, &stored).await {
+            Ok((question, embeddings)) => {
+                rank_by_similarity(candidates, &question, &embeddings, query.top_k)
+            }
+            Err(e) => {
+                warn!("Embedding failed, using filter-based selection: {}", e);
+                (candidates, Vec::new())
+            }
+        }
+    }
+
     /// Build the context
     pub async fn build(self) -> Result<Context> {
         let mut files = Vec::new();
+        let mut candidates: Vec<RankedFile> = Vec::new();
         let mut total_chars = 0usize;
         let mut languages_set = std::collections::HashSet::new();
+        let scorer = FileScorer::new();
+
+        // Budgeted, relevance-ranked and file-focused builds load every
+        // candidate first
+        let selects_after_loading =
+            self.token_budget.is_some() || self.relevance.is_some() || self.file_focus.is_some();
+
+        let mut repository_names = Vec::new();
+        if let Some((ref path, depth)) = self.file_focus {
+            let (repository, loaded) = Self::load_file_and_deps(path, depth, &scorer)?;
+            repository_names.push(repository);
+            candidates = loaded;
+        }
 
         // Get repositories to include
-        let repos = if self.repositories.is_empty() {
+        let repos = if self.file_focus.is_some() {
+            Vec::new()
+        } else if self.repositories.is_empty() {
             self.db.list_repositories().await?
         } else {
             let mut result = Vec::new();
@@ -273,9 +779,9 @@ impl ContextBuilder {
 
             // Load file contents
             for file_node in repo_files {
-                // Check token budget
+                // Check token budget (budgeted builds select after loading)
                 let estimated_tokens = (total_chars as f64 * TOKENS_PER_CHAR) as usize;
-                if estimated_tokens >= self.max_tokens {
+                if !selects_after_loading && estimated_tokens >= self.max_tokens {
                     break;
                 }
 
@@ -296,12 +802,33 @@ impl ContextBuilder {
                         .to_string();
 
                     let language = file_node.metadata.as_ref().and_then(|m| m.language.clone());
+                    let size = content.len();
+
+                    if selects_after_loading {
+                        let importance = scorer
+                            .score_file(&file_node.path, &content, &[], &[])
+                            .map(|score| score.importance)
+                            .unwrap_or(50.0);
+                        candidates.push(RankedFile {
+                            file: ContextFile {
+                                repository: repo.name.clone(),
+                                path: relative_path,
+                                content,
+                                language,
+                                size,
+                            },
+                            importance,
+                            modified: file_node.metadata.as_ref().map(|m| m.modified),
+                            relevance: None,
+                            hops: None,
+                        });
+                        continue;
+                    }
 
                     if let Some(ref lang) = language {
                         languages_set.insert(lang.clone());
                     }
 
-                    let size = content.len();
                     total_chars += size;
 
                     files.push(ContextFile {
@@ -316,7 +843,7 @@ impl ContextBuilder {
         }
 
         // Include notes if requested
-        let notes = if self.include_notes {
+        let mut notes = if self.include_notes {
             let inbox_notes = self
                 .db
                 .list_notes(Some(crate::db::NoteStatus::Inbox), None, None)
@@ -337,23 +864,76 @@ impl ContextBuilder {
             Vec::new()
         };
 
-        let estimated_tokens = (total_chars as f64 * TOKENS_PER_CHAR) as usize;
-        let truncated = estimated_tokens >= self.max_tokens;
+        let mut truncated_files = Vec::new();
+        let mut dropped_files = Vec::new();
+        let mut similarity_scores = Vec::new();
+        if let Some(ref query) = self.relevance {
+            let (ranked, scores) = self.rank_candidates(query, &repos, candidates).await;
+            candidates = ranked;
+            similarity_scores = scores;
+        }
+
+        let budget = match (self.token_budget, selects_after_loading) {
+            (Some(budget), _) => Some(budget),
+            (None, true) => Some(self.max_tokens),
+            (None, false) => None,
+        };
+        let (estimated_tokens, truncated) = match budget {
+            Some(budget) => {
+                // Notes are short and chosen by the user, so they go first
+                let mut note_tokens = 0;
+                notes.retain(|note| {
+                    let tokens = count_tokens(note);
+                    let fits = note_tokens + tokens <= budget;
+                    if fits {
+                        note_tokens += tokens;
+                    }
+                    fits
+                });
+                total_chars = notes.iter().map(|note| note.len()).sum();
+
+                let selection = select_within_budget(candidates, budget - note_tokens, Utc::now());
+                for file in &selection.files {
+                    if let Some(ref lang) = file.language {
+                        languages_set.insert(lang.clone());
+                    }
+                    total_chars += file.content.len();
+                }
+                files = selection.files;
+                truncated_files = selection.truncated;
+                dropped_files = selection.dropped;
+
+                let truncated = !truncated_files.is_empty() || !dropped_files.is_empty();
+                (note_tokens + selection.tokens, truncated)
+            }
+            None => {
+                let estimated_tokens = (total_chars as f64 * TOKENS_PER_CHAR) as usize;
+                (estimated_tokens, estimated_tokens >= self.max_tokens)
+            }
+        };
 
         let metadata = ContextMetadata {
             file_count: files.len(),
             note_count: notes.len(),
-            repository_count: repos.len(),
+            repository_count: repos.len() + repository_names.len(),
             languages: languages_set.into_iter().collect(),
             total_bytes: total_chars,
             estimated_tokens,
             truncated,
+            token_budget: budget,
+            truncated_files,
+            dropped_files,
+            similarity_scores,
         };
 
         Ok(Context {
             files,
             notes,
-            repositories: repos.into_iter().map(|r| r.name).collect(),
+            repositories: repos
+                .into_iter()
+                .map(|r| r.name)
+                .chain(repository_names)
+                .collect(),
             total_chars,
             estimated_tokens,
             metadata,
@@ -401,6 +981,12 @@ impl Context {
         if self.metadata.truncated {
             prompt.push_str("*Note: Context was truncated to fit token budget*\n");
         }
+        if !self.metadata.dropped_files.is_empty() {
+            prompt.push_str(&format!(
+                "*{} lower-priority files were left out to fit the token budget*\n",
+                self.metadata.dropped_files.len()
+            ));
+        }
 
         prompt
     }
@@ -460,6 +1046,9 @@ pub struct QueryBuilder {
     question: String,
     focus_files: Vec<String>,
     focus_language: Option<String>,
+    relevant_to: Option<String>,
+    embedding_generator: Option<EmbeddingGenerator>,
+    top_k: usize,
 }
 
 impl QueryBuilder {
@@ -470,9 +1059,38 @@ impl QueryBuilder {
             question: question.into(),
             focus_files: Vec::new(),
             focus_language: None,
+            relevant_to: None,
+            embedding_generator: None,
+            top_k: DEFAULT_RELEVANT_FILES,
         }
     }
 
+    /// Select the files most relevant to `question` by embedding
+    /// similarity instead of including every filtered file. Scores are
+    /// reported in [`ContextMetadata::similarity_scores`].
+    pub fn relevant_to(mut self, question: impl Into<String>) -> Self {
+        self.relevant_to = Some(question.into());
+        self
+    }
+
+    /// Embedding generator for [`Self::relevant_to`] (defaults to the local model)
+    pub fn with_embedding_generator(mut self, generator: EmbeddingGenerator) -> Self {
+        self.embedding_generator = Some(generator);
+        self
+    }
+
+    /// Number of relevant files to keep (default 20)
+    pub fn top_k(mut self, count: usize) -> Self {
+        self.top_k = count;
+        self
+    }
+
+    /// Limit the context to `max_tokens`
+    pub fn with_token_budget(mut self, max_tokens: usize) -> Self {
+        self.context_builder = self.context_builder.with_token_budget(max_tokens);
+        self
+    }
+
     /// Focus on specific files
     pub fn focus_on_files(mut self, paths: Vec<String>) -> Self {
         self.focus_files = paths;
@@ -508,6 +1126,14 @@ impl QueryBuilder {
             self.context_builder = self.context_builder.with_path(path);
         }
 
+        if let Some(question) = self.relevant_to {
+            self.context_builder = self.context_builder.rank_by_relevance(
+                question,
+                self.embedding_generator,
+                self.top_k,
+            );
+        }
+
         // Build context
         let context = self.context_builder.build().await?;
 
@@ -544,6 +1170,189 @@ mod tests {
         Ok(())
     }
 
+    fn ranked(path: &str, lines: usize, importance: f64, age_days: i64) -> RankedFile {
+        let content = "let value = compute(input);\n".repeat(lines);
+        RankedFile {
+            file: ContextFile {
+                repository: "repo".to_string(),
+                path: path.to_string(),
+                size: content.len(),
+                content,
+                language: Some("Rust".to_string()),
+            },
+            importance,
+            modified: Some(Utc::now() - chrono::Duration::days(age_days)),
+            relevance: None,
+            hops: None,
+        }
+    }
+
+    /// Embeds text as counts of a few keywords
+    struct KeywordBackend;
+
+    #[async_trait::async_trait]
+    impl crate::embeddings::EmbeddingBackend for KeywordBackend {
+        async fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+            Ok(texts
+                .iter()
+                .map(|text| {
+                    ["auth", "cache", "parse"]
+                        .iter()
+                        .map(|word| text.matches(word).count() as f32)
+                        .collect()
+                })
+                .collect())
+        }
+
+        fn model_name(&self) -> &str {
+            "keywords"
+        }
+
+        fn dimension(&self) -> usize {
+            3
+        }
+    }
+
+    fn with_content(path: &str, content: &str) -> RankedFile {
+        let mut file = ranked(path, 0, 50.0, 0);
+        file.file.content = content.to_string();
+        file
+    }
+
+    #[tokio::test]
+    async fn test_rank_by_similarity() {
+        let generator = EmbeddingGenerator::with_backend(
+            EmbeddingConfig::default(),
+            std::sync::Arc::new(KeywordBackend),
+        );
+        let candidates = vec![
+            with_content("src/cache.rs", "fn cache_get() {}\nfn cache_put() {}"),
+            with_content("src/auth.rs", "fn auth_login() {}"),
+            with_content("src/parser.rs", "fn parse() {}"),
+        ];
+        // A stored embedding stands in for the parser's summary
+        let mut stored = HashMap::new();
+        stored.insert(
+            "repo/src/parser.rs".to_string(),
+            Embedding::new(vec![1.0, 0.0, 0.0], "keywords".to_string(), 3),
+        );
+
+        let (question, embeddings) =
+            embed_candidates(&generator, "how does auth work?", &candidates, &stored)
+                .await
+                .unwrap();
+        let (ranked, scores) = rank_by_similarity(candidates, &question, &embeddings, 2);
+
+        let paths: Vec<&str> = ranked.iter().map(|f| f.file.path.as_str()).collect();
+        assert_eq!(paths, vec!["src/auth.rs", "src/parser.rs"]);
+        assert_eq!(scores[0].file, "repo/src/auth.rs");
+        assert!((scores[0].score - 1.0).abs() < 1e-6);
+        assert_eq!(scores.len(), 2);
+
+        // Relevance, not importance, decides the order within the budget
+        let selection = select_within_budget(ranked, 10_000, Utc::now());
+        assert_eq!(selection.files[0].path, "src/auth.rs");
+    }
+
+    #[test]
+    fn test_select_within_budget() {
+        let now = Utc::now();
+        let line_tokens = count_tokens("let value = compute(input);\n");
+        let candidates = vec![
+            ranked("src/minor.rs", 100, 20.0, 400),
+            ranked("src/core.rs", 100, 90.0, 1),
+            ranked("src/large.rs", 400, 60.0, 10),
+            ranked("src/tiny.rs", 2, 10.0, 400),
+            ranked("src/stale.rs", 100, 30.0, 400),
+        ];
+        let budget = 200 * line_tokens;
+
+        let selection = select_within_budget(candidates, budget, now);
+        assert!(selection.tokens <= budget);
+        assert_eq!(
+            selection.tokens,
+            selection
+                .files
+                .iter()
+                .map(|f| count_tokens(&f.content))
+                .sum::<usize>()
+        );
+
+        let paths: Vec<&str> = selection.files.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(paths, vec!["src/core.rs", "src/large.rs"]);
+        assert_eq!(selection.truncated, vec!["repo/src/large.rs"]);
+        assert!(selection.files[1].content.ends_with(TRUNCATION_MARKER));
+        assert_eq!(
+            selection.dropped,
+            vec!["repo/src/stale.rs", "repo/src/minor.rs", "repo/src/tiny.rs"]
+        );
+
+        // With too little left to truncate into, smaller files still fit
+        let candidates = vec![
+            ranked("src/core.rs", 100, 90.0, 1),
+            ranked("src/large.rs", 400, 60.0, 10),
+            ranked("src/tiny.rs", 2, 10.0, 400),
+        ];
+        let selection = select_within_budget(candidates, 110 * line_tokens, now);
+        let paths: Vec<&str> = selection.files.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(paths, vec!["src/core.rs", "src/tiny.rs"]);
+        assert_eq!(selection.dropped, vec!["repo/src/large.rs"]);
+        assert!(selection.truncated.is_empty());
+    }
+
+    #[test]
+    fn test_file_with_deps_follows_imports_and_cycles() {
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("src");
+        std::fs::create_dir_all(&src).unwrap();
+        let write = |name: &str, content: &str| std::fs::write(src.join(name), content).unwrap();
+        write(
+            "lib.rs",
+            "pub mod a;\npub mod b;\npub mod util;\npub mod unused;\n",
+        );
+        write("a.rs", "use crate::b::Beta;\npub struct Alpha;\n");
+        write(
+            "b.rs",
+            "use crate::a::Alpha;\nuse crate::util::helper;\npub struct Beta;\n",
+        );
+        write("util.rs", "use super::a;\npub fn helper() {}\n");
+        write("unused.rs", "pub fn nothing() {}\n");
+
+        let target = src.join("a.rs");
+        let names = |depth| -> Vec<(String, usize)> {
+            file_with_deps(&target, depth)
+                .into_iter()
+                .map(|(p, hops)| (p.file_name().unwrap().to_string_lossy().to_string(), hops))
+                .collect()
+        };
+        assert_eq!(names(0), vec![("a.rs".to_string(), 0)]);
+        assert_eq!(
+            names(1),
+            vec![("a.rs".to_string(), 0), ("b.rs".to_string(), 1)]
+        );
+        // a -> b -> util -> a cycles back without revisiting
+        assert_eq!(
+            names(5),
+            vec![
+                ("a.rs".to_string(), 0),
+                ("b.rs".to_string(), 1),
+                ("util.rs".to_string(), 2)
+            ]
+        );
+
+        let (_, candidates) =
+            ContextBuilder::load_file_and_deps(&target, 2, &FileScorer::new()).unwrap();
+        let paths: Vec<&str> = candidates.iter().map(|c| c.file.path.as_str()).collect();
+        assert_eq!(paths, vec!["src/a.rs", "src/b.rs", "src/util.rs"]);
+        assert_eq!(candidates[0].file.language.as_deref(), Some("Rust"));
+
+        // The focus file outranks its dependencies under a tight budget
+        let budget = count_tokens(&candidates[0].file.content) + 1;
+        let selection = select_within_budget(candidates, budget, Utc::now());
+        assert_eq!(selection.files[0].path, "src/a.rs");
+        assert_eq!(selection.dropped.len(), 2);
+    }
+
     #[test]
     fn test_token_estimation() {
         let chars = 10000;
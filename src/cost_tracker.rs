@@ -38,7 +38,7 @@
 
 use crate::error::AuditError;
 use anyhow::{Context, Result};
-use chrono::{DateTime, Datelike, Duration, Utc};
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
 use tracing::{debug, info, warn};
@@ -99,6 +99,40 @@ pub struct BudgetStatus {
     pub alerts: Vec<String>,
 }
 
+/// Spend on one model
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelCost {
+    pub model: String,
+    pub query_count: u64,
+    pub total_cost_usd: f64,
+    pub total_tokens: u64,
+}
+
+/// Spend on one UTC day
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DailyCost {
+    pub date: NaiveDate,
+    pub cost_usd: f64,
+    pub query_count: u64,
+}
+
+/// Spend overview shared by the `costs` CLI command and `GET /api/costs`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CostSummary {
+    pub total_cost_usd: f64,
+    pub total_queries: u64,
+    pub last_24h_usd: f64,
+    pub last_7d_usd: f64,
+    pub last_30d_usd: f64,
+    /// Most expensive model first
+    pub by_model: Vec<ModelCost>,
+    /// The last [`SUMMARY_DAYS`] days, oldest first; days without calls are 0
+    pub daily: Vec<DailyCost>,
+}
+
+/// Days covered by [`CostSummary::daily`]
+pub const SUMMARY_DAYS: u32 = 30;
+
 /// Record of a static analysis decision for cost tracking
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StaticDecisionRecord {
@@ -846,6 +880,76 @@ impl CostTracker {
     }
 
     /// Get combined daily report as structured data (for API/UI consumption)
+    /// Totals, per-model breakdown and daily spend for the last
+    /// [`SUMMARY_DAYS`] days. All zero when nothing has been logged yet.
+    pub async fn get_cost_summary(&self) -> Result<CostSummary> {
+        let (total_queries, total_cost_usd, last_24h_usd, last_7d_usd, last_30d_usd) =
+            sqlx::query_as::<_, (i64, f64, f64, f64, f64)>(
+                r#"
+                SELECT
+                    COUNT(*),
+                    COALESCE(SUM(cost_usd), 0.0),
+                    COALESCE(SUM(cost_usd) FILTER (WHERE timestamp >= NOW() - INTERVAL '1 day'), 0.0),
+                    COALESCE(SUM(cost_usd) FILTER (WHERE timestamp >= NOW() - INTERVAL '7 days'), 0.0),
+                    COALESCE(SUM(cost_usd) FILTER (WHERE timestamp >= NOW() - INTERVAL '30 days'), 0.0)
+                FROM llm_costs
+                "#,
+            )
+            .fetch_one(&self.pool)
+            .await
+            .context("Failed to fetch cost totals")?;
+
+        let by_model = sqlx::query_as::<_, (String, i64, f64, i64)>(
+            r#"
+            SELECT
+                model,
+                COUNT(*),
+                SUM(cost_usd),
+                SUM(input_tokens + output_tokens + COALESCE(cached_tokens, 0))
+            FROM llm_costs
+            GROUP BY model
+            ORDER BY SUM(cost_usd) DESC, model
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to fetch cost by model")?
+        .into_iter()
+        .map(|(model, count, cost, tokens)| ModelCost {
+            model,
+            query_count: count as u64,
+            total_cost_usd: cost,
+            total_tokens: tokens as u64,
+        })
+        .collect();
+
+        let today = Utc::now().date_naive();
+        let first_day = today - Duration::days(i64::from(SUMMARY_DAYS) - 1);
+        let daily_rows = sqlx::query_as::<_, (NaiveDate, f64, i64)>(
+            r#"
+            SELECT (timestamp AT TIME ZONE 'UTC')::DATE AS day, SUM(cost_usd), COUNT(*)
+            FROM llm_costs
+            WHERE timestamp >= $1::DATE
+            GROUP BY day
+            ORDER BY day
+            "#,
+        )
+        .bind(first_day)
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to fetch daily costs")?;
+
+        Ok(CostSummary {
+            total_cost_usd,
+            total_queries: total_queries as u64,
+            last_24h_usd,
+            last_7d_usd,
+            last_30d_usd,
+            by_model,
+            daily: fill_daily(&daily_rows, today, SUMMARY_DAYS),
+        })
+    }
+
     pub async fn get_combined_daily_report(
         &self,
     ) -> Result<(CostStats, SavingsReport, BudgetStatus)> {
@@ -856,6 +960,26 @@ impl CostTracker {
     }
 }
 
+/// One entry per day for the `days` days ending `today`, oldest first,
+/// taking spend from `rows` (date, cost, calls) and 0 for missing days
+fn fill_daily(rows: &[(NaiveDate, f64, i64)], today: NaiveDate, days: u32) -> Vec<DailyCost> {
+    (0..i64::from(days))
+        .rev()
+        .map(|back| {
+            let date = today - Duration::days(back);
+            let (cost_usd, query_count) = rows
+                .iter()
+                .find(|(day, _, _)| *day == date)
+                .map_or((0.0, 0), |(_, cost, count)| (*cost, *count as u64));
+            DailyCost {
+                date,
+                cost_usd,
+                query_count,
+            }
+        })
+        .collect()
+}
+
 impl SavingsReport {
     /// Format as a human-readable summary
     pub fn format_summary(&self) -> String {
@@ -895,6 +1019,32 @@ mod tests {
         .unwrap()
     }
 
+    #[test]
+    fn test_fill_daily_zero_fills_missing_days() {
+        let day = |d| NaiveDate::from_ymd_opt(2026, 3, d).unwrap();
+        let rows = vec![(day(8), 0.25, 3), (day(10), 1.5, 7)];
+
+        let daily = fill_daily(&rows, day(10), 4);
+        let values: Vec<_> = daily
+            .iter()
+            .map(|d| (d.date, d.cost_usd, d.query_count))
+            .collect();
+        assert_eq!(
+            values,
+            vec![
+                (day(7), 0.0, 0),
+                (day(8), 0.25, 3),
+                (day(9), 0.0, 0),
+                (day(10), 1.5, 7),
+            ]
+        );
+
+        // No data yet: still a full, all-zero series
+        let empty = fill_daily(&[], day(10), SUMMARY_DAYS);
+        assert_eq!(empty.len(), SUMMARY_DAYS as usize);
+        assert!(empty.iter().all(|d| d.cost_usd == 0.0));
+    }
+
     #[tokio::test]
     async fn test_cost_calculation() {
         let pool = create_test_pool().await;
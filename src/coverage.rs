@@ -0,0 +1,405 @@
+//! Coverage report ingestion
+//!
+//! Parses LCOV (`cargo llvm-cov --lcov`, `coverage.py lcov`, `c8`) and
+//! Cobertura XML (`coverage.xml`) into per-file line and function hit counts.
+//! The format is detected from the content, so callers can pass either.
+//!
+//! ```rust,no_run
+//! use rustassistant::coverage::CoverageReport;
+//!
+//! # fn example() -> anyhow::Result<()> {
+//! let report = CoverageReport::from_file("lcov.info")?;
+//! if let Some(file) = report.file("src/utils.rs") {
+//!     println!("{:.1}% of lines hit", file.line_coverage());
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use anyhow::{Context, Result};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+/// Coverage data for a set of source files
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CoverageReport {
+    /// Coverage by source path as written in the report
+    pub files: HashMap<String, FileCoverage>,
+}
+
+/// Line and function hits for one source file
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FileCoverage {
+    /// Source path as written in the report
+    pub path: String,
+    /// Hit count per instrumented line (1-based)
+    pub lines: BTreeMap<u32, u64>,
+    /// Function records, when the report has them
+    pub functions: Vec<FunctionCoverage>,
+}
+
+/// Hit count for one function
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FunctionCoverage {
+    /// Function name (may be mangled in LLVM output)
+    pub name: String,
+    /// Declaration line, when known
+    pub line: Option<u32>,
+    /// Number of times the function was entered
+    pub hits: u64,
+}
+
+impl CoverageReport {
+    /// Parse an LCOV or Cobertura report, detected from the content
+    pub fn parse(content: &str) -> Result<Self> {
+        let head = content.trim_start();
+        if head.starts_with("<?xml")
+            || head.starts_with("<coverage")
+            || head.contains("<!DOCTYPE coverage")
+        {
+            Self::from_cobertura(content)
+        } else if content.lines().any(|l| l.starts_with("SF:")) {
+            Self::from_lcov(content)
+        } else {
+            anyhow::bail!(
+                "Unrecognized coverage format: expected LCOV (SF: records) or Cobertura XML"
+            )
+        }
+    }
+
+    /// Read and parse a report file
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read coverage report: {}", path.display()))?;
+        Self::parse(&content)
+            .with_context(|| format!("Failed to parse coverage report: {}", path.display()))
+    }
+
+    /// Parse LCOV tracefile records (`SF`, `FN`, `FNDA`, `DA`, `end_of_record`)
+    pub fn from_lcov(content: &str) -> Result<Self> {
+        let mut report = Self::default();
+        let mut current: Option<FileCoverage> = None;
+        // FN lines come before their FNDA counts
+        let mut fn_lines: HashMap<String, u32> = HashMap::new();
+
+        for (i, line) in content.lines().enumerate() {
+            let line = line.trim();
+            let Some((tag, value)) = line.split_once(':') else {
+                if line == "end_of_record" {
+                    if let Some(file) = current.take() {
+                        report.merge(file);
+                    }
+                    fn_lines.clear();
+                }
+                continue;
+            };
+
+            if tag == "SF" {
+                if let Some(file) = current.take() {
+                    report.merge(file);
+                }
+                current = Some(FileCoverage {
+                    path: value.to_string(),
+                    ..Default::default()
+                });
+                continue;
+            }
+
+            let Some(file) = current.as_mut() else {
+                continue;
+            };
+            let bad = || format!("Malformed LCOV {} record on line {}", tag, i + 1);
+
+            match tag {
+                "DA" => {
+                    let mut parts = value.split(',');
+                    let number: u32 = parts.next().unwrap_or("").parse().with_context(bad)?;
+                    let hits: u64 = parts.next().unwrap_or("").parse().with_context(bad)?;
+                    *file.lines.entry(number).or_insert(0) += hits;
+                }
+                "FN" => {
+                    // FN:<line>[,<end line>],<name>
+                    let parts: Vec<&str> = value.split(',').collect();
+                    let number: u32 = parts.first().unwrap_or(&"").parse().with_context(bad)?;
+                    if let Some(name) = parts.last().filter(|_| parts.len() >= 2) {
+                        fn_lines.insert(name.to_string(), number);
+                    }
+                }
+                "FNDA" => {
+                    let (hits, name) = value.split_once(',').with_context(bad)?;
+                    let hits: u64 = hits.parse().with_context(bad)?;
+                    file.add_function(name, fn_lines.get(name).copied(), hits);
+                }
+                _ => {}
+            }
+        }
+
+        if let Some(file) = current.take() {
+            report.merge(file);
+        }
+        Ok(report)
+    }
+
+    /// Parse Cobertura XML (`<class filename>`, `<method>`, `<line number hits>`)
+    pub fn from_cobertura(content: &str) -> Result<Self> {
+        let tag_regex =
+            Regex::new(r"<(/?)(class|method|line)\b([^>]*?)(/?)>").expect("Invalid tag regex");
+        let attr_regex = Regex::new(r#"([\w-]+)\s*=\s*"([^"]*)""#).expect("Invalid attr regex");
+
+        let mut report = Self::default();
+        let mut class: Option<FileCoverage> = None;
+        // Method name with the lines seen inside it
+        let mut method: Option<(String, Vec<(u32, u64)>)> = None;
+
+        for caps in tag_regex.captures_iter(content) {
+            let closing = !caps[1].is_empty();
+            let self_closing = !caps[4].is_empty();
+            let attrs: HashMap<&str, &str> = attr_regex
+                .captures_iter(caps.get(3).map_or("", |m| m.as_str()))
+                .filter_map(|a| Some((a.get(1)?.as_str(), a.get(2)?.as_str())))
+                .collect();
+
+            match (&caps[2], closing) {
+                ("class", false) => {
+                    if let Some(file) = class.take() {
+                        report.merge(file);
+                    }
+                    let path = attrs
+                        .get("filename")
+                        .context("Cobertura <class> without a filename")?;
+                    class = Some(FileCoverage {
+                        path: path.to_string(),
+                        ..Default::default()
+                    });
+                    if self_closing {
+                        report.merge(class.take().expect("just set"));
+                    }
+                }
+                ("class", true) => {
+                    if let Some(file) = class.take() {
+                        report.merge(file);
+                    }
+                }
+                ("method", false) => {
+                    let name = attrs.get("name").copied().unwrap_or_default().to_string();
+                    method = Some((name, Vec::new()));
+                }
+                ("method", true) => {
+                    if let (Some((name, lines)), Some(file)) = (method.take(), class.as_mut()) {
+                        let first = lines.iter().map(|(n, _)| *n).min();
+                        let hits = lines.iter().map(|(_, h)| *h).max().unwrap_or(0);
+                        file.add_function(&name, first, hits);
+                    }
+                }
+                ("line", false) => {
+                    let number = attrs.get("number").and_then(|v| v.parse::<u32>().ok());
+                    let hits = attrs.get("hits").and_then(|v| v.parse::<u64>().ok());
+                    let (Some(number), Some(hits)) = (number, hits) else {
+                        continue;
+                    };
+                    match method.as_mut() {
+                        // Method lines repeat the class-level <lines>; count them once
+                        Some((_, lines)) => lines.push((number, hits)),
+                        None => {
+                            if let Some(file) = class.as_mut() {
+                                *file.lines.entry(number).or_insert(0) += hits;
+                            }
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        if let Some(file) = class.take() {
+            report.merge(file);
+        }
+        Ok(report)
+    }
+
+    /// Coverage for a source path, matching report paths by suffix
+    ///
+    /// Reports use absolute or package-relative paths, so `src/lib.rs`
+    /// matches `/home/me/project/src/lib.rs` and vice versa.
+    pub fn file(&self, path: impl AsRef<Path>) -> Option<&FileCoverage> {
+        let wanted = normalize(path.as_ref().to_string_lossy().as_ref());
+        if let Some(file) = self.files.get(&wanted) {
+            return Some(file);
+        }
+        self.files
+            .iter()
+            .filter(|(p, _)| path_suffix_match(p, &wanted))
+            .max_by_key(|(p, _)| p.len())
+            .map(|(_, f)| f)
+    }
+
+    /// Line coverage across every file, as a percentage
+    pub fn overall_line_coverage(&self) -> f64 {
+        let (hit, total) = self.files.values().fold((0, 0), |(h, t), f| {
+            (
+                h + f.lines.values().filter(|n| **n > 0).count(),
+                t + f.lines.len(),
+            )
+        });
+        percent(hit, total)
+    }
+
+    fn merge(&mut self, file: FileCoverage) {
+        let key = normalize(&file.path);
+        let entry = self.files.entry(key).or_insert_with(|| FileCoverage {
+            path: file.path.clone(),
+            ..Default::default()
+        });
+        for (line, hits) in file.lines {
+            *entry.lines.entry(line).or_insert(0) += hits;
+        }
+        for f in file.functions {
+            entry.add_function(&f.name, f.line, f.hits);
+        }
+    }
+}
+
+impl FileCoverage {
+    /// Percentage of instrumented lines hit at least once
+    pub fn line_coverage(&self) -> f64 {
+        percent(
+            self.lines.values().filter(|n| **n > 0).count(),
+            self.lines.len(),
+        )
+    }
+
+    /// Most hits on any instrumented line in `start..=end`, or `None` if none are instrumented
+    pub fn hits_in_range(&self, start: u32, end: u32) -> Option<u64> {
+        self.lines.range(start..=end).map(|(_, h)| *h).max()
+    }
+
+    /// Function record matching a source-level name
+    ///
+    /// Accepts exact names, path-qualified names (`module::name`,
+    /// `Class.name`) and Rust v0/legacy mangled symbols containing the
+    /// length-prefixed name (`3add`).
+    pub fn function(&self, name: &str) -> Option<&FunctionCoverage> {
+        let mangled = format!("{}{}", name.len(), name);
+        self.functions
+            .iter()
+            .filter(|f| {
+                f.name == name
+                    || f.name.ends_with(&format!("::{}", name))
+                    || f.name.ends_with(&format!(".{}", name))
+                    || (f.name.starts_with("_R") || f.name.starts_with("_ZN"))
+                        && f.name.contains(&mangled)
+            })
+            .max_by_key(|f| f.hits)
+    }
+
+    fn add_function(&mut self, name: &str, line: Option<u32>, hits: u64) {
+        match self.functions.iter_mut().find(|f| f.name == name) {
+            Some(existing) => {
+                existing.hits += hits;
+                existing.line = existing.line.or(line);
+            }
+            None => self.functions.push(FunctionCoverage {
+                name: name.to_string(),
+                line,
+                hits,
+            }),
+        }
+    }
+}
+
+fn normalize(path: &str) -> String {
+    path.replace('\\', "/").trim_start_matches("./").to_string()
+}
+
+/// Whether one path ends with the other on a component boundary
+fn path_suffix_match(a: &str, b: &str) -> bool {
+    let (long, short) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+    long.ends_with(short)
+        && (long.len() == short.len() || long[..long.len() - short.len()].ends_with('/'))
+}
+
+fn percent(hit: usize, total: usize) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        hit as f64 / total as f64 * 100.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LCOV: &str = "TN:\n\
+SF:/work/project/src/math.rs\n\
+FN:3,_RNvCs1234_7project3add\n\
+FN:8,_RNvCs1234_7project8subtract\n\
+FNDA:4,_RNvCs1234_7project3add\n\
+FNDA:0,_RNvCs1234_7project8subtract\n\
+DA:3,4\n\
+DA:4,4\n\
+DA:8,0\n\
+DA:9,0\n\
+end_of_record\n";
+
+    const COBERTURA: &str = r#"<?xml version="1.0" ?>
+<coverage line-rate="0.5">
+  <packages><package name="app"><classes>
+    <class name="utils.py" filename="app/utils.py" line-rate="0.5">
+      <methods>
+        <method name="load" signature="">
+          <lines><line number="2" hits="3"/></lines>
+        </method>
+      </methods>
+      <lines>
+        <line number="1" hits="1"/>
+        <line number="2" hits="3"/>
+        <line number="5" hits="0"/>
+        <line number="6" hits="0"/>
+      </lines>
+    </class>
+  </classes></package></packages>
+</coverage>"#;
+
+    #[test]
+    fn test_parse_lcov() {
+        let report = CoverageReport::parse(LCOV).unwrap();
+        let file = report.file("src/math.rs").unwrap();
+
+        assert_eq!(file.line_coverage(), 50.0);
+        assert_eq!(file.function("add").unwrap().hits, 4);
+        assert_eq!(file.function("add").unwrap().line, Some(3));
+        assert_eq!(file.function("subtract").unwrap().hits, 0);
+        assert!(file.function("sub").is_none());
+        assert_eq!(file.hits_in_range(8, 9), Some(0));
+        assert_eq!(file.hits_in_range(20, 30), None);
+    }
+
+    #[test]
+    fn test_parse_cobertura() {
+        let report = CoverageReport::parse(COBERTURA).unwrap();
+        let file = report.file("/repo/app/utils.py").unwrap();
+
+        // Method lines are not double counted
+        assert_eq!(file.lines.get(&2), Some(&3));
+        assert_eq!(file.line_coverage(), 50.0);
+        assert_eq!(file.function("load").unwrap().line, Some(2));
+        assert_eq!(report.overall_line_coverage(), 50.0);
+    }
+
+    #[test]
+    fn test_path_matching_respects_components() {
+        let report = CoverageReport::parse(LCOV).unwrap();
+        assert!(report.file("math.rs").is_some());
+        assert!(report.file("othermath.rs").is_none());
+    }
+
+    #[test]
+    fn test_rejects_unknown_format() {
+        assert!(CoverageReport::parse("{\"coverage\": 1}").is_err());
+        assert!(CoverageReport::parse("SF:a.rs\nDA:x,1\n").is_err());
+    }
+}
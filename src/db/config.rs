@@ -64,6 +64,46 @@ impl DatabaseConfig {
     }
 }
 
+/// When processed notes are archived automatically
+#[derive(Debug, Clone, PartialEq)]
+pub struct NoteCleanupPolicy {
+    /// Archive notes that have been processed for this many days
+    pub archive_processed_after_days: i64,
+    /// Run the cleanup when the CLI lists notes or recommends the next task
+    pub auto: bool,
+}
+
+impl Default for NoteCleanupPolicy {
+    fn default() -> Self {
+        Self {
+            archive_processed_after_days: 30,
+            auto: false,
+        }
+    }
+}
+
+impl NoteCleanupPolicy {
+    /// Load the policy from environment variables
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+
+        let archive_processed_after_days = std::env::var("RUSTASSISTANT_ARCHIVE_PROCESSED_DAYS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .filter(|days: &i64| *days >= 0)
+            .unwrap_or(defaults.archive_processed_after_days);
+
+        let auto = std::env::var("RUSTASSISTANT_AUTO_ARCHIVE")
+            .map(|v| v == "true" || v == "1")
+            .unwrap_or(defaults.auto);
+
+        Self {
+            archive_processed_after_days,
+            auto,
+        }
+    }
+}
+
 // ============================================================================
 // URL Resolution
 // ============================================================================
@@ -260,6 +300,15 @@ RUSTASSISTANT_DB_MAX_CONN
     Maximum connections in the pool.
     Default: 10
 
+RUSTASSISTANT_ARCHIVE_PROCESSED_DAYS
+    Archive processed notes after this many days (`note cleanup`).
+    Default: 30
+
+RUSTASSISTANT_AUTO_ARCHIVE
+    Run the note cleanup automatically from `note list` and `next`.
+    Values: true, false, 1, 0
+    Default: false
+
 RUSTASSISTANT_ENV
     Environment mode. Values: development, dev, production, prod
     Default: development (debug builds), production (release builds)
@@ -41,6 +41,10 @@ pub struct Note {
     pub status: String,
     #[sqlx(default)]
     pub repo_id: Option<String>,
+    /// When the note is due (unix seconds), if it has a due date
+    #[sqlx(default)]
+    #[serde(default)]
+    pub due_at: Option<i64>,
     pub created_at: i64,
     pub updated_at: i64,
     /// Comma-separated tags — populated from the `notes_with_tags` view when
@@ -68,6 +72,57 @@ impl Note {
             .map(|dt| dt.format("%Y-%m-%d %H:%M:%S").to_string())
             .unwrap_or_else(|| "unknown".to_string())
     }
+
+    /// Get formatted due date, if the note has one
+    pub fn due_at_formatted(&self) -> Option<String> {
+        self.due_at
+            .and_then(|due| chrono::DateTime::from_timestamp(due, 0))
+            .map(|dt| dt.format("%Y-%m-%d %H:%M").to_string())
+    }
+
+    /// Whether the note is past due at `now` and still open
+    pub fn is_overdue(&self, now: i64) -> bool {
+        self.due_at.is_some_and(|due| due < now)
+            && !matches!(self.status.as_str(), "archived" | "deleted")
+    }
+}
+
+/// Parse a due date relative to `now` (unix seconds).
+///
+/// Accepts relative offsets (`+3d`, `+2w`, `+12h`), dates (`2024-06-01`,
+/// due at the end of that day UTC), `YYYY-MM-DD HH:MM` (UTC) and RFC 3339
+/// timestamps.
+pub fn parse_due_date(input: &str, now: i64) -> DbResult<i64> {
+    let input = input.trim();
+    let invalid = || {
+        DbError::InvalidInput(format!(
+            "Invalid due date '{}': use +3d, +2w, +12h, 2024-06-01 or 2024-06-01 17:00",
+            input
+        ))
+    };
+
+    if let Some(offset) = input.strip_prefix('+') {
+        let (amount, unit) = offset.split_at(offset.len().saturating_sub(1));
+        let amount: i64 = amount.parse().map_err(|_| invalid())?;
+        let unit_secs = match unit {
+            "h" => 3_600,
+            "d" => 86_400,
+            "w" => 7 * 86_400,
+            _ => return Err(invalid()),
+        };
+        return Ok(now + amount * unit_secs);
+    }
+
+    if let Ok(date) = chrono::NaiveDate::parse_from_str(input, "%Y-%m-%d") {
+        let end_of_day = date.and_hms_opt(23, 59, 59).ok_or_else(invalid)?;
+        return Ok(end_of_day.and_utc().timestamp());
+    }
+    if let Ok(datetime) = chrono::NaiveDateTime::parse_from_str(input, "%Y-%m-%d %H:%M") {
+        return Ok(datetime.and_utc().timestamp());
+    }
+    chrono::DateTime::parse_from_rfc3339(input)
+        .map(|dt| dt.timestamp())
+        .map_err(|_| invalid())
 }
 
 /// A tag for categorizing notes
@@ -381,6 +436,9 @@ pub async fn create_note_with_tags(
         set_note_tags(pool, &id, tags).await?;
     }
 
+    // Record [[links]] to other notes
+    super::links::sync_note_links(&mut *pool.acquire().await?, &id, content).await?;
+
     Ok(Note {
         id,
         title,
@@ -392,6 +450,7 @@ pub async fn create_note_with_tags(
         },
         status: "active".to_string(),
         repo_id: repo_id.map(|s| s.to_string()),
+        due_at: None,
         created_at: now,
         updated_at: now,
     })
@@ -464,8 +523,35 @@ pub async fn list_notes(
     Ok(q.fetch_all(pool).await?)
 }
 
-/// Search notes by content or title
+/// Search notes by content or title, most relevant first.
+///
+/// Matches any of the query's words (as prefixes, after stemming) against
+/// the full-text index from migration 027, ranked by `ts_rank` so notes
+/// containing more of the words come first. Falls back to substring
+/// matching when the index is missing or finds nothing, which also covers
+/// queries that are mid-word fragments or only stop words.
 pub async fn search_notes(pool: &PgPool, query: &str, limit: i64) -> DbResult<Vec<Note>> {
+    if let Some(tsquery) = notes_tsquery(query) {
+        let ranked = sqlx::query_as::<_, Note>(
+            r#"
+            SELECT * FROM notes
+            WHERE search_vector @@ to_tsquery('english', $1)
+            ORDER BY ts_rank(search_vector, to_tsquery('english', $1)) DESC, created_at DESC
+            LIMIT $2
+            "#,
+        )
+        .bind(&tsquery)
+        .bind(limit)
+        .fetch_all(pool)
+        .await;
+
+        match ranked {
+            Ok(notes) if !notes.is_empty() => return Ok(notes),
+            Ok(_) => {}
+            Err(e) => tracing::debug!("Full-text note search unavailable, using ILIKE: {}", e),
+        }
+    }
+
     let search_pattern = format!("%{}%", query);
 
     Ok(sqlx::query_as::<_, Note>(
@@ -483,12 +569,79 @@ pub async fn search_notes(pool: &PgPool, query: &str, limit: i64) -> DbResult<Ve
     .await?)
 }
 
+/// Build a `to_tsquery` expression matching any word of `query` as a
+/// prefix, or `None` if the query has no words
+fn notes_tsquery(query: &str) -> Option<String> {
+    let terms: Vec<String> = query
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| format!("{}:*", word.to_lowercase()))
+        .collect();
+    (!terms.is_empty()).then(|| terms.join(" | "))
+}
+
 /// Update note status
 pub async fn update_note_status(pool: &PgPool, id: &str, status: &str) -> DbResult<()> {
     let now = chrono::Utc::now().timestamp();
 
-    let result = sqlx::query("UPDATE notes SET status = $1, updated_at = $2 WHERE id = $3")
-        .bind(status)
+    let result = sqlx::query(
+        r#"
+        UPDATE notes
+        SET status_changed_at = CASE WHEN status = $1 THEN status_changed_at ELSE $2 END,
+            status = $1,
+            updated_at = $2
+        WHERE id = $3
+        "#,
+    )
+    .bind(status)
+    .bind(now)
+    .bind(id)
+    .execute(pool)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(DbError::NotFound(format!("Note not found: {}", id)));
+    }
+
+    Ok(())
+}
+
+/// Archive notes that have been processed for more than
+/// `processed_older_than_days` days. Returns the number archived.
+///
+/// Notes processed before migration 030 have no status change time and
+/// are judged by `updated_at`.
+pub async fn archive_stale(pool: &PgPool, processed_older_than_days: i64) -> DbResult<u64> {
+    if processed_older_than_days < 0 {
+        return Err(DbError::InvalidInput(format!(
+            "Archive threshold must not be negative: {} days",
+            processed_older_than_days
+        )));
+    }
+    let now = chrono::Utc::now().timestamp();
+    let cutoff = now - processed_older_than_days * 86_400;
+
+    let result = sqlx::query(
+        r#"
+        UPDATE notes
+        SET status = 'archived', status_changed_at = $1, updated_at = $1
+        WHERE status = 'processed' AND COALESCE(status_changed_at, updated_at) < $2
+        "#,
+    )
+    .bind(now)
+    .bind(cutoff)
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected())
+}
+
+/// Set or clear a note's due date
+pub async fn set_note_due(pool: &PgPool, id: &str, due_at: Option<i64>) -> DbResult<()> {
+    let now = chrono::Utc::now().timestamp();
+
+    let result = sqlx::query("UPDATE notes SET due_at = $1, updated_at = $2 WHERE id = $3")
+        .bind(due_at)
         .bind(now)
         .bind(id)
         .execute(pool)
@@ -501,6 +654,111 @@ pub async fn update_note_status(pool: &PgPool, id: &str, status: &str) -> DbResu
     Ok(())
 }
 
+/// Notes past due that aren't archived or deleted, most overdue first
+pub async fn list_overdue(pool: &PgPool) -> DbResult<Vec<Note>> {
+    Ok(sqlx::query_as::<_, Note>(
+        r#"
+        SELECT * FROM notes
+        WHERE due_at < $1 AND status NOT IN ('archived', 'deleted')
+        ORDER BY due_at ASC
+        "#,
+    )
+    .bind(chrono::Utc::now().timestamp())
+    .fetch_all(pool)
+    .await?)
+}
+
+/// Replace a note's content (and title), re-parsing its links
+pub async fn update_note_content(pool: &PgPool, id: &str, content: &str) -> DbResult<()> {
+    let now = chrono::Utc::now().timestamp();
+    let title: String = content.chars().take(120).collect();
+    let mut tx = pool.begin().await?;
+
+    let result =
+        sqlx::query("UPDATE notes SET title = $1, content = $2, updated_at = $3 WHERE id = $4")
+            .bind(&title)
+            .bind(content)
+            .bind(now)
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(DbError::NotFound(format!("Note not found: {}", id)));
+    }
+
+    super::links::sync_note_links(&mut tx, id, content).await?;
+    tx.commit().await?;
+    Ok(())
+}
+
+/// Edit a note: replace its content (re-parsing links), its tags and
+/// optionally its status. The note ends up tagged with `tags` plus every
+/// `#tag` in the new content; tag rows are created as needed so `note_tags`
+/// never references an unknown tag, and dropped tags release their usage.
+pub async fn update_note(
+    pool: &PgPool,
+    id: &str,
+    content: &str,
+    tags: &[&str],
+    status: Option<&str>,
+) -> DbResult<Note> {
+    update_note_content(pool, id, content).await?;
+
+    let mut all_tags: Vec<String> = tags
+        .iter()
+        .map(|t| t.trim().to_lowercase())
+        .filter(|t| !t.is_empty())
+        .collect();
+    for tag in parse_hashtags(content) {
+        if !all_tags.contains(&tag) {
+            all_tags.push(tag);
+        }
+    }
+
+    let now = chrono::Utc::now().timestamp();
+    for tag in &all_tags {
+        sqlx::query(
+            "INSERT INTO tags (name, created_at, updated_at) VALUES ($1, $2, $2) ON CONFLICT DO NOTHING",
+        )
+        .bind(tag)
+        .bind(now)
+        .execute(pool)
+        .await?;
+    }
+    let tag_refs: Vec<&str> = all_tags.iter().map(String::as_str).collect();
+    set_note_tags(pool, id, &tag_refs).await?;
+
+    if let Some(status) = status {
+        update_note_status(pool, id, status).await?;
+    }
+
+    let mut note = get_note(pool, id).await?;
+    note.tags = (!all_tags.is_empty()).then(|| all_tags.join(","));
+    Ok(note)
+}
+
+/// `#tags` written inline in note text, lowercased, in order of first use.
+/// A tag starts with a letter and runs over letters, digits, `-` and `_`;
+/// it must begin the text or follow whitespace, so `#1` and `a#b` are not tags.
+pub fn parse_hashtags(content: &str) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+    for word in content.split_whitespace() {
+        let Some(rest) = word.strip_prefix('#') else {
+            continue;
+        };
+        let tag: String = rest
+            .chars()
+            .take_while(|c| c.is_alphanumeric() || *c == '-' || *c == '_')
+            .collect::<String>()
+            .to_lowercase();
+        if tag.starts_with(|c: char| c.is_alphabetic()) && !tags.contains(&tag) {
+            tags.push(tag);
+        }
+    }
+    tags
+}
+
 /// Delete a note
 pub async fn delete_note(pool: &PgPool, id: &str) -> DbResult<()> {
     let result = sqlx::query("DELETE FROM notes WHERE id = $1")
@@ -1342,6 +1600,7 @@ pub struct DbStats {
     pub total_repos: i64,
     pub total_tasks: i64,
     pub pending_tasks: i64,
+    pub overdue_notes: i64,
 }
 
 pub async fn get_stats(pool: &PgPool) -> DbResult<DbStats> {
@@ -1366,12 +1625,20 @@ pub async fn get_stats(pool: &PgPool) -> DbResult<DbStats> {
             .fetch_one(pool)
             .await?;
 
+    let overdue_notes: (i64,) = sqlx::query_as(
+        "SELECT COUNT(*) FROM notes WHERE due_at < $1 AND status NOT IN ('archived', 'deleted')",
+    )
+    .bind(chrono::Utc::now().timestamp())
+    .fetch_one(pool)
+    .await?;
+
     Ok(DbStats {
         total_notes: total_notes.0,
         inbox_notes: inbox_notes.0,
         total_repos: total_repos.0,
         total_tasks: total_tasks.0,
         pending_tasks: pending_tasks.0,
+        overdue_notes: overdue_notes.0,
     })
 }
 
@@ -1409,6 +1676,15 @@ mod tests {
         uuid::Uuid::new_v4().to_string()[..8].to_string()
     }
 
+    #[test]
+    fn test_parse_hashtags() {
+        assert_eq!(
+            parse_hashtags("Fix #Bug in #auth-flow, see #1 and a#b. #bug again #todo_later"),
+            vec!["bug", "auth-flow", "todo_later"]
+        );
+        assert!(parse_hashtags("no tags here").is_empty());
+    }
+
     #[tokio::test]
     async fn test_create_and_get_note() {
         let pool = setup_test_db().await;
@@ -1485,6 +1761,159 @@ mod tests {
         assert!(results[0].content.contains(&token));
     }
 
+    #[test]
+    fn test_parse_due_date() {
+        let now = 1_717_200_000; // 2024-06-01 00:00:00 UTC
+        assert_eq!(parse_due_date("+3d", now).unwrap(), now + 3 * 86_400);
+        assert_eq!(parse_due_date("+2w", now).unwrap(), now + 14 * 86_400);
+        assert_eq!(parse_due_date(" +12h ", now).unwrap(), now + 12 * 3_600);
+        assert_eq!(parse_due_date("2024-06-01", now).unwrap(), now + 86_399);
+        assert_eq!(
+            parse_due_date("2024-06-01 17:30", now).unwrap(),
+            now + 17 * 3_600 + 30 * 60
+        );
+        assert_eq!(
+            parse_due_date("2024-06-01T02:00:00+02:00", now).unwrap(),
+            now
+        );
+        for bad in ["", "+", "+3", "+3x", "+d", "tomorrow", "2024-13-01"] {
+            assert!(parse_due_date(bad, now).is_err(), "{:?} parsed", bad);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_list_overdue() {
+        let pool = setup_test_db().await;
+        let now = chrono::Utc::now().timestamp();
+
+        let s = uid();
+        let overdue = create_note(&pool, &format!("Overdue {}", s), None, None)
+            .await
+            .unwrap();
+        let upcoming = create_note(&pool, &format!("Upcoming {}", s), None, None)
+            .await
+            .unwrap();
+        let archived = create_note(&pool, &format!("Archived {}", s), None, None)
+            .await
+            .unwrap();
+        set_note_due(&pool, &overdue.id, Some(now - 3_600))
+            .await
+            .unwrap();
+        set_note_due(&pool, &upcoming.id, Some(now + 86_400))
+            .await
+            .unwrap();
+        set_note_due(&pool, &archived.id, Some(now - 3_600))
+            .await
+            .unwrap();
+        update_note_status(&pool, &archived.id, "archived")
+            .await
+            .unwrap();
+
+        let ids: Vec<String> = list_overdue(&pool)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|n| n.id)
+            .collect();
+        assert!(ids.contains(&overdue.id));
+        assert!(!ids.contains(&upcoming.id));
+        assert!(!ids.contains(&archived.id));
+        assert!(get_note(&pool, &overdue.id).await.unwrap().is_overdue(now));
+    }
+
+    #[tokio::test]
+    async fn test_archive_stale_only_archives_old_processed_notes() {
+        let pool = setup_test_db().await;
+        let s = uid();
+
+        let fresh = create_note(&pool, &format!("Fresh {}", s), None, None)
+            .await
+            .unwrap();
+        let stale = create_note(&pool, &format!("Stale archivable{}", s), None, None)
+            .await
+            .unwrap();
+        let active = create_note(&pool, &format!("Active {}", s), None, None)
+            .await
+            .unwrap();
+        update_note_status(&pool, &fresh.id, "processed")
+            .await
+            .unwrap();
+        update_note_status(&pool, &stale.id, "processed")
+            .await
+            .unwrap();
+        let long_ago = chrono::Utc::now().timestamp() - 40 * 86_400;
+        for id in [&stale.id, &active.id] {
+            sqlx::query("UPDATE notes SET status_changed_at = $1 WHERE id = $2")
+                .bind(long_ago)
+                .bind(id)
+                .execute(&pool)
+                .await
+                .unwrap();
+        }
+
+        let archived = archive_stale(&pool, 30).await.unwrap();
+        assert!(archived >= 1);
+        assert_eq!(
+            get_note(&pool, &fresh.id).await.unwrap().status,
+            "processed"
+        );
+        assert_eq!(get_note(&pool, &stale.id).await.unwrap().status, "archived");
+        assert_eq!(get_note(&pool, &active.id).await.unwrap().status, "active");
+
+        // Archived notes stay searchable
+        let found = search_notes(&pool, &format!("archivable{}", s), 10)
+            .await
+            .unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].id, stale.id);
+
+        assert!(archive_stale(&pool, -1).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_search_notes_ranks_notes_with_more_terms_first() {
+        let pool = setup_test_db().await;
+
+        let first = format!("ranking{}", uid());
+        let second = format!("relevance{}", uid());
+        // Created first, so recency alone would put it last
+        let both = create_note(
+            &pool,
+            &format!("Notes on {} and {} together", first, second),
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+        let one = create_note(&pool, &format!("Only about {}", second), None, None)
+            .await
+            .unwrap();
+
+        let results = search_notes(&pool, &format!("{} {}", first, second), 10)
+            .await
+            .unwrap();
+        let ids: Vec<&str> = results.iter().map(|n| n.id.as_str()).collect();
+        assert_eq!(ids, vec![both.id.as_str(), one.id.as_str()]);
+
+        // A mid-word fragment still finds the note through the ILIKE fallback
+        let fragment = &second["relevance".len() - 3..];
+        let results = search_notes(&pool, fragment, 10).await.unwrap();
+        assert_eq!(results.len(), 2);
+    }
+
+    #[test]
+    fn test_notes_tsquery() {
+        assert_eq!(
+            notes_tsquery("Tokio runtime!").as_deref(),
+            Some("tokio:* | runtime:*")
+        );
+        assert_eq!(
+            notes_tsquery("can't: (rust)").as_deref(),
+            Some("can:* | t:* | rust:*")
+        );
+        assert_eq!(notes_tsquery(" -- & | "), None);
+    }
+
     #[tokio::test]
     async fn test_repository_crud() {
         let pool = setup_test_db().await;
@@ -1760,6 +2189,16 @@ impl Database {
         Ok(Vec::new())
     }
 
+    /// Archive notes processed more than `processed_older_than_days` ago
+    pub async fn archive_stale(&self, processed_older_than_days: i64) -> DbResult<u64> {
+        archive_stale(&self.pool, processed_older_than_days).await
+    }
+
+    /// Notes past due that aren't archived or deleted, most overdue first
+    pub async fn list_overdue(&self) -> DbResult<Vec<Note>> {
+        list_overdue(&self.pool).await
+    }
+
     /// Get stats (legacy API)
     pub async fn get_stats(&self) -> DbResult<DatabaseStats> {
         let stats = get_stats(&self.pool).await?;
@@ -0,0 +1,151 @@
+//! Store for tasks produced by [`TaskGenerator`](crate::tasks::TaskGenerator)
+//!
+//! Generated tasks are keyed per repository by [`Task::dedup_key`], so a
+//! task keeps the id it was first stored under however many runs produce
+//! it again. Recording a run inserts new tasks, bumps `last_seen` on known
+//! ones and reopens any that had been closed.
+
+use super::core::{DbError, DbResult};
+use crate::types::{Category, Task, TaskPriority};
+use chrono::{TimeZone, Utc};
+use sqlx::{FromRow, PgPool};
+use std::path::PathBuf;
+
+/// Raw `generated_tasks` row
+#[derive(Debug, Clone, FromRow)]
+struct GeneratedTaskRow {
+    id: String,
+    title: String,
+    description: String,
+    file_path: String,
+    line_number: Option<i32>,
+    priority: String,
+    category: String,
+    tags: String,
+    first_seen: i64,
+}
+
+impl GeneratedTaskRow {
+    fn into_task(self) -> DbResult<Task> {
+        let priority: TaskPriority = parse_variant(&self.id, "priority", &self.priority)?;
+        let category: Category = parse_variant(&self.id, "category", &self.category)?;
+
+        Ok(Task {
+            id: self.id,
+            title: self.title,
+            description: self.description,
+            file: PathBuf::from(self.file_path),
+            line: self.line_number.map(|l| l as usize),
+            priority,
+            category,
+            created_at: Utc
+                .timestamp_opt(self.first_seen, 0)
+                .single()
+                .unwrap_or_else(Utc::now),
+            tags: self
+                .tags
+                .split(',')
+                .filter(|t| !t.is_empty())
+                .map(String::from)
+                .collect(),
+            depends_on: Vec::new(),
+        })
+    }
+}
+
+/// Parse a unit enum variant from its serde name
+fn parse_variant<T: serde::de::DeserializeOwned>(
+    id: &str,
+    field: &str,
+    value: &str,
+) -> DbResult<T> {
+    serde_json::from_value(serde_json::Value::from(value)).map_err(|_| {
+        DbError::InvalidInput(format!("generated task {}: bad {} '{}'", id, field, value))
+    })
+}
+
+/// Serde name of a unit enum variant, e.g. `TaskPriority::High` → "high"
+fn serde_name<T: serde::Serialize>(value: &T) -> String {
+    serde_json::to_value(value)
+        .ok()
+        .and_then(|v| v.as_str().map(String::from))
+        .unwrap_or_default()
+}
+
+/// Stored tasks for `repo_path`, optionally only those with `status`
+/// (`open` or `closed`), oldest first
+pub async fn load_generated_tasks(
+    pool: &PgPool,
+    repo_path: &str,
+    status: Option<&str>,
+) -> DbResult<Vec<Task>> {
+    let rows: Vec<GeneratedTaskRow> = sqlx::query_as(
+        r#"
+        SELECT id, title, description, file_path, line_number, priority, category, tags, first_seen
+        FROM generated_tasks
+        WHERE repo_path = $1 AND ($2::TEXT IS NULL OR status = $2)
+        ORDER BY first_seen, id
+        "#,
+    )
+    .bind(repo_path)
+    .bind(status)
+    .fetch_all(pool)
+    .await?;
+
+    rows.into_iter().map(GeneratedTaskRow::into_task).collect()
+}
+
+/// Record every task produced by a run for `repo_path`. Unknown tasks are
+/// inserted as open; known ones get their location and priority refreshed,
+/// `last_seen` bumped and are reopened if they had been closed.
+pub async fn record_generated_tasks(
+    pool: &PgPool,
+    repo_path: &str,
+    tasks: &[Task],
+) -> DbResult<()> {
+    let now = Utc::now().timestamp();
+    let mut tx = pool.begin().await?;
+    for task in tasks {
+        sqlx::query(
+            r#"
+            INSERT INTO generated_tasks
+                (id, repo_path, dedup_key, title, description, file_path, line_number,
+                 priority, category, tags, status, first_seen, last_seen)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, 'open', $11, $11)
+            ON CONFLICT (repo_path, dedup_key) DO UPDATE SET
+                line_number = EXCLUDED.line_number,
+                priority    = EXCLUDED.priority,
+                last_seen   = EXCLUDED.last_seen,
+                status      = 'open',
+                closed_at   = NULL
+            "#,
+        )
+        .bind(&task.id)
+        .bind(repo_path)
+        .bind(task.dedup_key())
+        .bind(&task.title)
+        .bind(&task.description)
+        .bind(task.file.display().to_string())
+        .bind(task.line.map(|l| l as i32))
+        .bind(serde_name(&task.priority))
+        .bind(serde_name(&task.category))
+        .bind(task.tags.join(","))
+        .bind(now)
+        .execute(&mut *tx)
+        .await?;
+    }
+    tx.commit().await?;
+    Ok(())
+}
+
+/// Close stored tasks by id, returning how many were open
+pub async fn close_generated_tasks(pool: &PgPool, ids: &[String]) -> DbResult<u64> {
+    let result = sqlx::query(
+        "UPDATE generated_tasks SET status = 'closed', closed_at = $1 WHERE id = ANY($2) AND status = 'open'",
+    )
+    .bind(Utc::now().timestamp())
+    .bind(ids)
+    .execute(pool)
+    .await?;
+    Ok(result.rows_affected())
+}
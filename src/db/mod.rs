@@ -6,6 +6,9 @@ pub mod chunks;
 pub mod config;
 pub mod core;
 pub mod documents;
+pub mod export;
+pub mod generated_tasks;
+pub mod links;
 pub mod queue;
 pub mod scan_events;
 
@@ -19,7 +22,7 @@ pub use chunks::{
 // Re-export configuration types and functions
 pub use config::{
     backup_database, ensure_data_dir, get_backup_path, get_data_dir, health_check, init_pool,
-    print_env_help, DatabaseConfig, DatabaseHealth,
+    print_env_help, DatabaseConfig, DatabaseHealth, NoteCleanupPolicy,
 };
 
 // Convenience type alias — consumers can use `db::PgPool` instead of `sqlx::PgPool`
@@ -28,6 +31,15 @@ pub use sqlx::PgPool;
 // Re-export core database types and functions
 pub use core::*;
 
+// Re-export export/import types
+pub use export::{DatabaseExport, ExistingIds, ImportSummary, EXPORT_FORMAT_VERSION};
+
+// Re-export generated task store functions
+pub use generated_tasks::{close_generated_tasks, load_generated_tasks, record_generated_tasks};
+
+// Re-export note link types and functions
+pub use links::{broken_links, extract_note_links, get_backlinks, get_note_links, NoteLink};
+
 // Re-export queue types and functions
 pub use queue::{
     create_queue_tables, FileAnalysis, QueueItem, QueuePriority, QueueSource, QueueStage,
@@ -52,12 +64,16 @@ pub use documents::{
     get_document_chunks,
     get_document_embeddings,
     get_document_tags,
+    get_document_version,
+    get_repo_file_embeddings,
     get_unindexed_documents,
+    list_document_versions,
     list_documents,
     list_ideas,
     // Tags functions
     list_tags,
     mark_document_indexed,
+    revert_document,
     // FTS5 search
     search_documents,
     search_documents_by_tags,
@@ -67,6 +83,7 @@ pub use documents::{
     store_embedding,
     update_document,
     update_idea_status,
+    DocumentVersion,
     Idea,
     Tag,
 };
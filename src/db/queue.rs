@@ -45,10 +45,46 @@ pub enum QueueStage {
     Ready,
     /// Processing failed (with retry count)
     Failed,
+    /// Failed more than `max_retries` times; parked until requeued or purged
+    DeadLetter,
     /// Archived/inactive
     Archived,
 }
 
+impl QueueStage {
+    /// Canonical string stored in `queue_items.stage`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            QueueStage::Inbox => "inbox",
+            QueueStage::PendingAnalysis => "pending_analysis",
+            QueueStage::Analyzing => "analyzing",
+            QueueStage::PendingTagging => "pending_tagging",
+            QueueStage::Ready => "ready",
+            QueueStage::Failed => "failed",
+            QueueStage::DeadLetter => "dead_letter",
+            QueueStage::Archived => "archived",
+        }
+    }
+}
+
+impl std::str::FromStr for QueueStage {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "inbox" => Ok(QueueStage::Inbox),
+            "pending_analysis" => Ok(QueueStage::PendingAnalysis),
+            "analyzing" => Ok(QueueStage::Analyzing),
+            "pending_tagging" => Ok(QueueStage::PendingTagging),
+            "ready" => Ok(QueueStage::Ready),
+            "failed" => Ok(QueueStage::Failed),
+            "dead_letter" => Ok(QueueStage::DeadLetter),
+            "archived" => Ok(QueueStage::Archived),
+            other => Err(format!("Unknown queue stage '{}'", other)),
+        }
+    }
+}
+
 /// Source type for queue items
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
 #[sqlx(type_name = "TEXT", rename_all = "snake_case")]
@@ -80,6 +116,38 @@ pub enum QueuePriority {
     Background = 5,
 }
 
+impl std::str::FromStr for QueuePriority {
+    type Err = String;
+
+    /// Accepts either the name (`"high"`) or the numeric level (`"2"`)
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "critical" | "1" => Ok(QueuePriority::Critical),
+            "high" | "2" => Ok(QueuePriority::High),
+            "normal" | "3" => Ok(QueuePriority::Normal),
+            "low" | "4" => Ok(QueuePriority::Low),
+            "background" | "5" => Ok(QueuePriority::Background),
+            other => Err(format!("Unknown queue priority '{}'", other)),
+        }
+    }
+}
+
+impl std::str::FromStr for QueueSource {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "note" => Ok(QueueSource::Note),
+            "todo_comment" | "todocomment" | "todo" => Ok(QueueSource::TodoComment),
+            "repo_file" | "repofile" | "file" => Ok(QueueSource::RepoFile),
+            "raw_thought" | "rawthought" | "thought" => Ok(QueueSource::RawThought),
+            "research" => Ok(QueueSource::Research),
+            "document" | "doc" => Ok(QueueSource::Document),
+            other => Err(format!("Unknown queue source '{}'", other)),
+        }
+    }
+}
+
 // ============================================================================
 // Queue Item Model
 // ============================================================================
@@ -125,6 +193,9 @@ pub struct QueueItem {
     /// Number of processing attempts
     pub retry_count: i32,
 
+    /// Failures allowed before the item moves to the dead-letter stage
+    pub max_retries: i32,
+
     /// Last error message if failed
     pub last_error: Option<String>,
 
@@ -274,6 +345,7 @@ pub async fn create_queue_tables(pool: &PgPool) -> Result<(), sqlx::Error> {
             category TEXT,
             score INTEGER,
             retry_count INTEGER NOT NULL DEFAULT 0,
+            max_retries INTEGER NOT NULL DEFAULT 3,
             last_error TEXT,
             content_hash TEXT NOT NULL,
             created_at INTEGER NOT NULL,
@@ -286,6 +358,13 @@ pub async fn create_queue_tables(pool: &PgPool) -> Result<(), sqlx::Error> {
     .execute(pool)
     .await?;
 
+    // Tables created before dead-letter support lack max_retries
+    sqlx::query(
+        "ALTER TABLE queue_items ADD COLUMN IF NOT EXISTS max_retries INTEGER NOT NULL DEFAULT 3",
+    )
+    .execute(pool)
+    .await?;
+
     // File analysis cache
     sqlx::query(
         r#"
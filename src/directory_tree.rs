@@ -6,7 +6,7 @@
 //! - Code statistics
 //! - Age/status indicators
 
-use crate::error::Result;
+use crate::error::{AuditError, Result};
 use crate::tag_schema::{
     CodeStatus, DirectoryNode, IssuesSummary, NodeStats, NodeType, SimpleIssueDetector,
 };
@@ -322,11 +322,52 @@ impl DirectoryTreeBuilder {
 
     /// Generate ASCII tree visualization
     pub fn to_ascii_tree(&self, node: &DirectoryNode, max_depth: usize) -> String {
+        self.to_ascii_tree_filtered(node, max_depth, 0)
+    }
+
+    /// Generate ASCII tree visualization, collapsing subtrees with fewer than
+    /// `min_issues` issues in total into one `(+N files, clean)` line per
+    /// directory
+    pub fn to_ascii_tree_filtered(
+        &self,
+        node: &DirectoryNode,
+        max_depth: usize,
+        min_issues: usize,
+    ) -> String {
         let mut output = String::new();
-        self.render_ascii_node(node, &mut output, "", true, 0, max_depth);
+        let limits = RenderLimits {
+            max_depth,
+            min_issues,
+        };
+        self.render_ascii_node(node, &mut output, "", true, 0, limits);
         output
     }
 
+    /// Copy of `node` cut down for display: children below `max_depth` and
+    /// subtrees with fewer than `min_issues` issues are dropped, as in
+    /// [`DirectoryTreeBuilder::to_ascii_tree_filtered`]. Stats and issue
+    /// counts still cover everything underneath.
+    pub fn collapse(
+        &self,
+        node: &DirectoryNode,
+        max_depth: usize,
+        min_issues: usize,
+    ) -> DirectoryNode {
+        let mut collapsed = DirectoryNode {
+            children: Vec::new(),
+            ..node.clone()
+        };
+        if max_depth > 0 {
+            collapsed.children = node
+                .children
+                .iter()
+                .filter(|child| child.issues.total() >= min_issues)
+                .map(|child| self.collapse(child, max_depth - 1, min_issues))
+                .collect();
+        }
+        collapsed
+    }
+
     /// Render a node as ASCII tree
     fn render_ascii_node(
         &self,
@@ -335,9 +376,9 @@ impl DirectoryTreeBuilder {
         prefix: &str,
         is_last: bool,
         depth: usize,
-        max_depth: usize,
+        limits: RenderLimits,
     ) {
-        if depth > max_depth {
+        if depth > limits.max_depth {
             return;
         }
 
@@ -379,19 +420,129 @@ impl DirectoryTreeBuilder {
         if let NodeType::Directory = node.node_type {
             let child_prefix = format!("{}{}", prefix, if is_last { "    " } else { "│   " });
 
-            for (i, child) in node.children.iter().enumerate() {
-                let is_last_child = i == node.children.len() - 1;
+            let (shown, collapsed): (Vec<_>, Vec<_>) = node
+                .children
+                .iter()
+                .partition(|child| child.issues.total() >= limits.min_issues);
+            let collapsed_files: usize = collapsed.iter().map(|c| count_files(c)).sum();
+
+            for (i, child) in shown.iter().enumerate() {
+                let is_last_child = i == shown.len() - 1 && collapsed_files == 0;
                 self.render_ascii_node(
                     child,
                     output,
                     &child_prefix,
                     is_last_child,
                     depth + 1,
-                    max_depth,
+                    limits,
                 );
             }
+
+            if collapsed_files > 0 && depth < limits.max_depth {
+                output.push_str(&format!(
+                    "{}└── (+{} {}, clean)\n",
+                    child_prefix,
+                    collapsed_files,
+                    if collapsed_files == 1 {
+                        "file"
+                    } else {
+                        "files"
+                    }
+                ));
+            }
         }
     }
+
+    /// Generate a Graphviz DOT digraph of the tree. Nodes are coloured from
+    /// green to red by issue density, edges are labelled with the nesting
+    /// depth, and nodes below `max_depth` are left out as in the ASCII view
+    pub fn to_dot(&self, node: &DirectoryNode, max_depth: usize) -> String {
+        let mut output = String::from("digraph tree {\n");
+        output.push_str("    rankdir=LR;\n");
+        output.push_str("    node [style=filled, fontname=\"Helvetica\"];\n");
+        output.push_str("    edge [fontsize=10];\n");
+        let mut next_id = 0;
+        self.render_dot_node(node, &mut output, &mut next_id, 0, max_depth);
+        output.push_str("}\n");
+        output
+    }
+
+    /// Render a node and its edges as DOT, returning its id
+    fn render_dot_node(
+        &self,
+        node: &DirectoryNode,
+        output: &mut String,
+        next_id: &mut usize,
+        depth: usize,
+        max_depth: usize,
+    ) -> usize {
+        let id = *next_id;
+        *next_id += 1;
+
+        let shape = match node.node_type {
+            NodeType::Directory => "folder",
+            NodeType::File => "note",
+        };
+        let mut label = escape_dot(&node.name);
+        if node.stats.lines_of_code > 0 {
+            label.push_str(&format!("\\n{} LOC", node.stats.lines_of_code));
+        }
+        if node.issues.total() > 0 {
+            label.push_str(&format!("\\n{} issues", node.issues.total()));
+        }
+        output.push_str(&format!(
+            "    n{} [label=\"{}\", shape={}, fillcolor=\"{}\"];\n",
+            id,
+            label,
+            shape,
+            density_color(node)
+        ));
+
+        if depth < max_depth {
+            for child in &node.children {
+                let child_id = self.render_dot_node(child, output, next_id, depth + 1, max_depth);
+                output.push_str(&format!(
+                    "    n{} -> n{} [label=\"{}\"];\n",
+                    id,
+                    child_id,
+                    depth + 1
+                ));
+            }
+        }
+
+        id
+    }
+}
+
+/// Issues per 100 lines at which a node is drawn fully red
+const DOT_RED_DENSITY: f64 = 5.0;
+
+/// HSV fill colour from green (no issues) to red (dense issues)
+fn density_color(node: &DirectoryNode) -> String {
+    let lines = node.stats.lines_of_code.max(1) as f64;
+    let density = node.issues.total() as f64 * 100.0 / lines;
+    let t = (density / DOT_RED_DENSITY).min(1.0);
+    format!("{:.3} 0.450 0.950", (1.0 - t) / 3.0)
+}
+
+/// Escape a string for use inside a quoted DOT label
+fn escape_dot(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Depth and issue limits for ASCII rendering
+#[derive(Debug, Clone, Copy)]
+struct RenderLimits {
+    max_depth: usize,
+    min_issues: usize,
+}
+
+/// Number of files in a subtree
+fn count_files(node: &DirectoryNode) -> usize {
+    match node.node_type {
+        NodeType::File => 1,
+        NodeType::Directory => node.children.iter().map(count_files).sum(),
+    }
 }
 
 /// Tree summary statistics
@@ -420,6 +571,75 @@ pub struct Hotspot {
     pub lines_of_code: usize,
 }
 
+/// Version of the saved tree JSON format
+pub const TREE_FORMAT_VERSION: u32 = 1;
+
+/// A scanned tree saved to JSON, so it can be rendered again without
+/// walking the filesystem
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct SavedTree {
+    pub version: u32,
+    /// Root path the tree was built from
+    pub root: PathBuf,
+    /// Unix timestamp of the scan
+    pub generated_at: i64,
+    pub summary: TreeSummary,
+    pub tree: DirectoryNode,
+}
+
+impl SavedTree {
+    /// Wrap a freshly built tree for saving
+    pub fn new(root: impl Into<PathBuf>, tree: DirectoryNode, summary: TreeSummary) -> Self {
+        Self {
+            version: TREE_FORMAT_VERSION,
+            root: root.into(),
+            generated_at: chrono::Utc::now().timestamp(),
+            summary,
+            tree,
+        }
+    }
+
+    /// Serialize as pretty-printed JSON
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Parse a saved tree, rejecting files from a newer format version
+    pub fn from_json(json: &str) -> Result<Self> {
+        let value = Self::migrate(serde_json::from_str(json)?)?;
+        serde_json::from_value(value).map_err(|e| {
+            AuditError::other(format!(
+                "Saved tree does not match format version {}: {}",
+                TREE_FORMAT_VERSION, e
+            ))
+        })
+    }
+
+    /// Upgrade a saved tree's JSON to [`TREE_FORMAT_VERSION`]. Version 1
+    /// is the only format so far, so there is nothing to convert yet.
+    pub fn migrate(value: serde_json::Value) -> Result<serde_json::Value> {
+        let version = value
+            .get("version")
+            .and_then(|v| v.as_u64())
+            .ok_or_else(|| AuditError::other("Not a saved directory tree: no format version"))?;
+        if version == 0 || version > u64::from(TREE_FORMAT_VERSION) {
+            return Err(AuditError::other(format!(
+                "Saved tree uses format version {}, this build reads up to version {}",
+                version, TREE_FORMAT_VERSION
+            )));
+        }
+        Ok(value)
+    }
+
+    /// Read and parse a saved tree file
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let json = fs::read_to_string(path)
+            .map_err(|e| AuditError::from(e).context(format!("reading {}", path.display())))?;
+        Self::from_json(&json)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -480,4 +700,141 @@ fn foo() {
         assert!(ascii.contains("📁"));
         assert!(ascii.contains("main.rs"));
     }
+
+    #[test]
+    fn test_ascii_tree_filtered_collapses_clean_subtrees() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+
+        fs::create_dir_all(root.join("src/clean")).unwrap();
+        fs::create_dir_all(root.join("src/messy")).unwrap();
+        fs::write(root.join("src/clean/a.rs"), "fn a() {}").unwrap();
+        fs::write(root.join("src/clean/b.rs"), "fn b() {}").unwrap();
+        fs::write(root.join("src/lib.rs"), "pub fn lib() {}").unwrap();
+        fs::write(
+            root.join("src/messy/bad.rs"),
+            "fn bad() {\n    let x = f().unwrap();\n    let y = g().unwrap();\n}",
+        )
+        .unwrap();
+        fs::write(root.join("src/messy/ok.rs"), "fn ok() {}").unwrap();
+
+        let builder = DirectoryTreeBuilder::new(root);
+        let tree = builder.build().unwrap();
+        let messy_issues = tree.children[0].children[1].issues.total();
+        assert!(messy_issues > 0);
+
+        // No threshold renders the same as the unfiltered tree
+        assert_eq!(
+            builder.to_ascii_tree_filtered(&tree, 5, 0),
+            builder.to_ascii_tree(&tree, 5)
+        );
+
+        let filtered = builder.to_ascii_tree_filtered(&tree, 5, messy_issues);
+        assert!(filtered.contains("messy"));
+        assert!(filtered.contains("bad.rs"));
+        assert!(!filtered.contains("📁clean"));
+        assert!(!filtered.contains("a.rs") && !filtered.contains("lib.rs"));
+        // clean/ (2 files) and lib.rs collapse under src/; ok.rs under messy/
+        assert!(filtered.contains("└── (+3 files, clean)\n"));
+        assert!(filtered.contains("└── (+1 file, clean)\n"));
+
+        // Hotspots still see every file
+        let hotspots = builder.find_hotspots(&tree, 10);
+        assert!(hotspots.iter().any(|h| h.name == "bad.rs"));
+    }
+
+    #[test]
+    fn test_saved_tree_round_trip_and_version_check() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        fs::create_dir(root.join("src")).unwrap();
+        fs::write(root.join("src/main.rs"), "// TODO: tidy\nfn main() {}\n").unwrap();
+
+        let builder = DirectoryTreeBuilder::new(root);
+        let tree = builder.build().unwrap();
+        let saved = SavedTree::new(root, tree.clone(), builder.generate_summary(&tree));
+        let json = saved.to_json().unwrap();
+
+        let loaded = SavedTree::from_json(&json).unwrap();
+        assert_eq!(loaded.summary.total_files, saved.summary.total_files);
+        assert_eq!(
+            builder.to_ascii_tree(&loaded.tree, 5),
+            builder.to_ascii_tree(&tree, 5)
+        );
+
+        let mut value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        value["version"] = serde_json::json!(TREE_FORMAT_VERSION + 1);
+        let err = SavedTree::from_json(&value.to_string()).unwrap_err();
+        assert!(err.to_string().contains("format version 2"));
+
+        let err = SavedTree::from_json(r#"{"name": "src"}"#).unwrap_err();
+        assert!(err.to_string().contains("no format version"));
+
+        value["version"] = serde_json::json!(TREE_FORMAT_VERSION);
+        value.as_object_mut().unwrap().remove("tree");
+        let err = SavedTree::from_json(&value.to_string()).unwrap_err();
+        assert!(err.to_string().contains("does not match"));
+    }
+
+    #[test]
+    fn test_dot_export() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        fs::create_dir_all(root.join("src/deep")).unwrap();
+        fs::write(root.join("src/ok.rs"), "fn ok() {}\n").unwrap();
+        fs::write(
+            root.join("src/deep/bad.rs"),
+            "// TODO: one\n// FIXME: two\nfn bad() { x.unwrap(); }\n",
+        )
+        .unwrap();
+
+        let builder = DirectoryTreeBuilder::new(root);
+        let tree = builder.build().unwrap();
+
+        let dot = builder.to_dot(&tree, 5);
+        assert!(dot.starts_with("digraph tree {\n"));
+        assert!(dot.ends_with("}\n"));
+        assert!(dot.contains("label=\"ok.rs\\n1 LOC\", shape=note, fillcolor=\"0.333 "));
+        assert!(dot.contains("label=\"bad.rs"));
+        assert!(dot.contains("shape=folder"));
+        assert!(dot.contains(" -> n1 [label=\"1\"];"));
+        assert_eq!(dot.matches('{').count(), dot.matches('}').count());
+
+        // Depth 1 keeps src/ but drops everything inside it
+        let shallow = builder.to_dot(&tree, 1);
+        assert!(shallow.contains("src"));
+        assert!(!shallow.contains("ok.rs") && !shallow.contains("bad.rs"));
+
+        assert_eq!(escape_dot("say \"hi\"\\"), "say \\\"hi\\\"\\\\");
+    }
+
+    #[test]
+    fn test_collapse_drops_clean_and_deep_subtrees() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+
+        fs::create_dir_all(root.join("src/clean")).unwrap();
+        fs::create_dir_all(root.join("src/messy/deep")).unwrap();
+        fs::write(root.join("src/clean/a.rs"), "fn a() {}").unwrap();
+        fs::write(
+            root.join("src/messy/deep/bad.rs"),
+            "fn bad() {\n    let x = f().unwrap();\n}",
+        )
+        .unwrap();
+
+        let builder = DirectoryTreeBuilder::new(root);
+        let tree = builder.build().unwrap();
+
+        let collapsed = builder.collapse(&tree, 2, 1);
+        let src = &collapsed.children[0];
+        let names: Vec<&str> = src.children.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["messy"]);
+        // messy sits at the depth limit, so its children are cut
+        assert!(src.children[0].children.is_empty());
+        assert_eq!(
+            src.children[0].issues.total(),
+            tree.children[0].children[1].issues.total()
+        );
+        assert_eq!(collapsed.stats.file_count, tree.stats.file_count);
+    }
 }
@@ -1,11 +1,13 @@
 //! Document Embedding Module
 //!
-//! This module provides embedding generation for the RAG system using fastembed.
-//! It handles model initialization, caching, and batch embedding generation.
+//! This module provides embedding generation for the RAG system. Embeddings are
+//! produced locally with fastembed by default, or by a remote OpenAI-compatible
+//! `/embeddings` API when [`EmbeddingConfig::backend`] selects one.
 //!
 //! # Features
 //!
 //! - **Multiple models**: Support for various embedding models
+//! - **Pluggable backends**: Local fastembed or remote API via [`EmbeddingBackend`]
 //! - **Batch processing**: Efficient batch embedding generation
 //! - **Model caching**: Lazy initialization and reuse
 //! - **Error handling**: Comprehensive error types
@@ -28,9 +30,11 @@
 //! ```
 
 use anyhow::{Context, Result};
+use async_trait::async_trait;
 use fastembed::{EmbeddingModel, InitOptions, TextEmbedding};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::RwLock;
 
 // ============================================================================
@@ -51,6 +55,10 @@ pub struct EmbeddingConfig {
 
     /// Cache directory for models (None = use default)
     pub cache_dir: Option<String>,
+
+    /// Where embeddings are computed (local fastembed unless configured otherwise)
+    #[serde(default)]
+    pub backend: EmbeddingBackendConfig,
 }
 
 impl Default for EmbeddingConfig {
@@ -60,6 +68,78 @@ impl Default for EmbeddingConfig {
             batch_size: 32,
             show_download_progress: true,
             cache_dir: None,
+            backend: EmbeddingBackendConfig::Local,
+        }
+    }
+}
+
+impl EmbeddingConfig {
+    /// Model identifier that will be recorded with stored embeddings
+    pub fn active_model(&self) -> &str {
+        match &self.backend {
+            EmbeddingBackendConfig::Local => self.model_name.name(),
+            EmbeddingBackendConfig::Remote(remote) => &remote.model,
+        }
+    }
+
+    /// Vector dimension produced by the configured backend
+    pub fn active_dimension(&self) -> usize {
+        match &self.backend {
+            EmbeddingBackendConfig::Local => self.model_name.dimension(),
+            EmbeddingBackendConfig::Remote(remote) => remote.dimension,
+        }
+    }
+}
+
+/// Which embedding backend to use
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum EmbeddingBackendConfig {
+    /// Run `model_name` locally with fastembed
+    #[default]
+    Local,
+    /// Call an OpenAI-compatible embeddings API
+    Remote(RemoteEmbeddingConfig),
+}
+
+/// Settings for an OpenAI-compatible `/embeddings` endpoint
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RemoteEmbeddingConfig {
+    /// API base URL, e.g. `https://api.openai.com/v1`
+    pub base_url: String,
+
+    /// Remote model identifier, e.g. `text-embedding-3-small`
+    pub model: String,
+
+    /// Vector dimension the model returns
+    pub dimension: usize,
+
+    /// Environment variable holding the API key (None = no auth header)
+    #[serde(default = "default_api_key_env")]
+    pub api_key_env: Option<String>,
+
+    /// Request timeout in seconds
+    #[serde(default = "default_remote_timeout")]
+    pub timeout_secs: u64,
+}
+
+fn default_api_key_env() -> Option<String> {
+    Some("OPENAI_API_KEY".to_string())
+}
+
+fn default_remote_timeout() -> u64 {
+    60
+}
+
+impl RemoteEmbeddingConfig {
+    /// OpenAI `text-embedding-3-small` (1536 dimensions)
+    pub fn openai_small() -> Self {
+        Self {
+            base_url: "https://api.openai.com/v1".to_string(),
+            model: "text-embedding-3-small".to_string(),
+            dimension: 1536,
+            api_key_env: default_api_key_env(),
+            timeout_secs: default_remote_timeout(),
         }
     }
 }
@@ -171,7 +251,13 @@ impl Embedding {
     /// Calculate cosine similarity with another embedding
     pub fn cosine_similarity(&self, other: &Embedding) -> Result<f32> {
         if self.vector.len() != other.vector.len() {
-            anyhow::bail!("Cannot compare embeddings with different dimensions");
+            anyhow::bail!(
+                "Cannot compare embeddings with different dimensions: {} ({}) vs {} ({})",
+                self.vector.len(),
+                self.model,
+                other.vector.len(),
+                other.model
+            );
         }
 
         let dot_product: f32 = self
@@ -193,22 +279,35 @@ impl Embedding {
 }
 
 // ============================================================================
-// Embedding Generator
+// Embedding Backends
 // ============================================================================
 
-/// Main embedding generator that wraps the fastembed model
-pub struct EmbeddingGenerator {
+/// A source of embedding vectors
+#[async_trait]
+pub trait EmbeddingBackend: Send + Sync {
+    /// Embed each text, returning one vector per input in the same order
+    async fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>>;
+
+    /// Model identifier recorded alongside stored embeddings
+    fn model_name(&self) -> &str;
+
+    /// Dimension of the vectors this backend produces
+    fn dimension(&self) -> usize;
+}
+
+/// Local embedding backend backed by fastembed
+pub struct LocalEmbeddingBackend {
     config: EmbeddingConfig,
     model: Arc<RwLock<Option<TextEmbedding>>>,
 }
 
-impl EmbeddingGenerator {
-    /// Create a new embedding generator with the given configuration
-    pub fn new(config: EmbeddingConfig) -> Result<Self> {
-        Ok(Self {
+impl LocalEmbeddingBackend {
+    /// Create a local backend; the model is loaded on first use
+    pub fn new(config: EmbeddingConfig) -> Self {
+        Self {
             config,
             model: Arc::new(RwLock::new(None)),
-        })
+        }
     }
 
     /// Initialize the embedding model (lazy loading)
@@ -251,37 +350,212 @@ impl EmbeddingGenerator {
 
         Ok(())
     }
+}
 
-    /// Generate embeddings for a batch of texts
-    pub async fn embed_batch(&self, texts: &[&str]) -> Result<Vec<Embedding>> {
-        if texts.is_empty() {
-            return Ok(Vec::new());
-        }
-
+#[async_trait]
+impl EmbeddingBackend for LocalEmbeddingBackend {
+    async fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
         // Ensure model is loaded
         self.ensure_model_loaded().await?;
 
         let mut model_guard = self.model.write().await;
         let model = model_guard.as_mut().context("Model not initialized")?;
 
-        // Convert texts to owned strings for fastembed
+        model
+            .embed(texts, Some(self.config.batch_size))
+            .context("Failed to generate embeddings")
+    }
+
+    fn model_name(&self) -> &str {
+        self.config.model_name.name()
+    }
+
+    fn dimension(&self) -> usize {
+        self.config.model_name.dimension()
+    }
+}
+
+/// Remote embedding backend for OpenAI-compatible `/embeddings` APIs
+pub struct RemoteEmbeddingBackend {
+    config: RemoteEmbeddingConfig,
+    batch_size: usize,
+    api_key: Option<String>,
+    client: reqwest::Client,
+}
+
+#[derive(Serialize)]
+struct RemoteEmbeddingRequest<'a> {
+    model: &'a str,
+    input: &'a [String],
+}
+
+#[derive(Deserialize)]
+struct RemoteEmbeddingResponse {
+    data: Vec<RemoteEmbeddingData>,
+}
+
+#[derive(Deserialize)]
+struct RemoteEmbeddingData {
+    #[serde(default)]
+    index: usize,
+    embedding: Vec<f32>,
+}
+
+impl RemoteEmbeddingBackend {
+    /// Create a remote backend, reading the API key from `api_key_env` if set
+    pub fn new(config: RemoteEmbeddingConfig, batch_size: usize) -> Result<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(config.timeout_secs))
+            .build()
+            .context("Failed to build HTTP client for remote embeddings")?;
+
+        let api_key = config
+            .api_key_env
+            .as_deref()
+            .and_then(|var| std::env::var(var).ok())
+            .filter(|key| !key.is_empty());
+
+        Ok(Self {
+            config,
+            batch_size: batch_size.max(1),
+            api_key,
+            client,
+        })
+    }
+
+    fn endpoint(&self) -> String {
+        format!("{}/embeddings", self.config.base_url.trim_end_matches('/'))
+    }
+
+    async fn embed_chunk(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+        let mut request = self
+            .client
+            .post(self.endpoint())
+            .json(&RemoteEmbeddingRequest {
+                model: &self.config.model,
+                input: texts,
+            });
+
+        if let Some(key) = &self.api_key {
+            request = request.bearer_auth(key);
+        }
+
+        let response = request
+            .send()
+            .await
+            .with_context(|| format!("Remote embedding request to {} failed", self.endpoint()))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            anyhow::bail!("Remote embedding API returned {}: {}", status, body);
+        }
+
+        let mut parsed: RemoteEmbeddingResponse = response
+            .json()
+            .await
+            .context("Failed to parse remote embedding response")?;
+
+        if parsed.data.len() != texts.len() {
+            anyhow::bail!(
+                "Remote embedding API returned {} vectors for {} inputs",
+                parsed.data.len(),
+                texts.len()
+            );
+        }
+
+        parsed.data.sort_by_key(|d| d.index);
+        Ok(parsed.data.into_iter().map(|d| d.embedding).collect())
+    }
+}
+
+#[async_trait]
+impl EmbeddingBackend for RemoteEmbeddingBackend {
+    async fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+        let mut vectors = Vec::with_capacity(texts.len());
+        for chunk in texts.chunks(self.batch_size) {
+            vectors.extend(self.embed_chunk(chunk).await?);
+        }
+        Ok(vectors)
+    }
+
+    fn model_name(&self) -> &str {
+        &self.config.model
+    }
+
+    fn dimension(&self) -> usize {
+        self.config.dimension
+    }
+}
+
+// ============================================================================
+// Embedding Generator
+// ============================================================================
+
+/// Main embedding generator; delegates to the backend named in its config
+#[derive(Clone)]
+pub struct EmbeddingGenerator {
+    config: EmbeddingConfig,
+    backend: Arc<dyn EmbeddingBackend>,
+}
+
+impl EmbeddingGenerator {
+    /// Create a new embedding generator with the given configuration
+    pub fn new(config: EmbeddingConfig) -> Result<Self> {
+        let backend: Arc<dyn EmbeddingBackend> = match &config.backend {
+            EmbeddingBackendConfig::Local => Arc::new(LocalEmbeddingBackend::new(config.clone())),
+            EmbeddingBackendConfig::Remote(remote) => Arc::new(RemoteEmbeddingBackend::new(
+                remote.clone(),
+                config.batch_size,
+            )?),
+        };
+
+        Ok(Self { config, backend })
+    }
+
+    /// Create a generator around a custom backend
+    pub fn with_backend(config: EmbeddingConfig, backend: Arc<dyn EmbeddingBackend>) -> Self {
+        Self { config, backend }
+    }
+
+    /// Generate embeddings for a batch of texts
+    ///
+    /// Fails if the backend returns vectors whose dimension differs from the
+    /// one it advertises, so mismatched vectors never reach storage.
+    pub async fn embed_batch(&self, texts: &[&str]) -> Result<Vec<Embedding>> {
+        if texts.is_empty() {
+            return Ok(Vec::new());
+        }
+
         let text_strings: Vec<String> = texts.iter().map(|s| s.to_string()).collect();
+        let embedding_vectors = self.backend.embed(&text_strings).await?;
 
-        // Generate embeddings
-        let embedding_vectors = model
-            .embed(text_strings, Some(self.config.batch_size))
-            .context("Failed to generate embeddings")?;
+        if embedding_vectors.len() != texts.len() {
+            anyhow::bail!(
+                "Embedding backend returned {} vectors for {} inputs",
+                embedding_vectors.len(),
+                texts.len()
+            );
+        }
 
         // Convert to our Embedding type
-        let model_name = self.config.model_name.name().to_string();
-        let dimension = self.config.model_name.dimension();
+        let model_name = self.backend.model_name().to_string();
+        let dimension = self.backend.dimension();
 
-        let embeddings = embedding_vectors
+        embedding_vectors
             .into_iter()
-            .map(|vec| Embedding::new(vec, model_name.clone(), dimension))
-            .collect();
-
-        Ok(embeddings)
+            .map(|vec| {
+                if vec.len() != dimension {
+                    anyhow::bail!(
+                        "Embedding dimension mismatch: model '{}' is configured for {} dimensions but returned {}",
+                        model_name,
+                        dimension,
+                        vec.len()
+                    );
+                }
+                Ok(Embedding::new(vec, model_name.clone(), dimension))
+            })
+            .collect()
     }
 
     /// Generate a single embedding
@@ -297,12 +571,12 @@ impl EmbeddingGenerator {
 
     /// Get the embedding dimension for the current model
     pub fn dimension(&self) -> usize {
-        self.config.model_name.dimension()
+        self.backend.dimension()
     }
 
     /// Get the model name
     pub fn model_name(&self) -> &str {
-        self.config.model_name.name()
+        self.backend.model_name()
     }
 }
 
@@ -409,6 +683,78 @@ mod tests {
         assert_eq!(config.model_name, EmbeddingModelType::BGESmallENV15);
         assert_eq!(config.batch_size, 32);
         assert!(config.show_download_progress);
+        assert_eq!(config.backend, EmbeddingBackendConfig::Local);
+    }
+
+    struct FixedBackend {
+        dimension: usize,
+        returned: usize,
+    }
+
+    #[async_trait]
+    impl EmbeddingBackend for FixedBackend {
+        async fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+            Ok(texts.iter().map(|_| vec![0.5; self.returned]).collect())
+        }
+
+        fn model_name(&self) -> &str {
+            "fixed"
+        }
+
+        fn dimension(&self) -> usize {
+            self.dimension
+        }
+    }
+
+    #[tokio::test]
+    async fn test_generator_uses_backend() {
+        let backend = Arc::new(FixedBackend {
+            dimension: 3,
+            returned: 3,
+        });
+        let generator = EmbeddingGenerator::with_backend(EmbeddingConfig::default(), backend);
+
+        let embeddings = generator.embed_batch(&["a", "b"]).await.unwrap();
+        assert_eq!(embeddings.len(), 2);
+        assert_eq!(embeddings[0].model, "fixed");
+        assert_eq!(generator.dimension(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_generator_rejects_dimension_mismatch() {
+        let backend = Arc::new(FixedBackend {
+            dimension: 384,
+            returned: 1536,
+        });
+        let generator = EmbeddingGenerator::with_backend(EmbeddingConfig::default(), backend);
+
+        let err = generator.embed("text").await.unwrap_err();
+        assert!(err.to_string().contains("dimension mismatch"));
+    }
+
+    #[test]
+    fn test_cosine_similarity_dimension_mismatch_is_error() {
+        let small = Embedding::new(vec![1.0; 3], "a".to_string(), 3);
+        let large = Embedding::new(vec![1.0; 4], "b".to_string(), 4);
+        assert!(small.cosine_similarity(&large).is_err());
+    }
+
+    #[test]
+    fn test_backend_config_deserialize() {
+        let config: EmbeddingConfig = serde_json::from_str(
+            r#"{"model_name":"BGESmallENV15","batch_size":16,"show_download_progress":false,
+                "cache_dir":null,"backend":{"type":"remote","base_url":"http://localhost:8080/v1",
+                "model":"nomic-embed-text","dimension":768}}"#,
+        )
+        .unwrap();
+
+        match config.backend {
+            EmbeddingBackendConfig::Remote(remote) => {
+                assert_eq!(remote.dimension, 768);
+                assert_eq!(remote.api_key_env.as_deref(), Some("OPENAI_API_KEY"));
+            }
+            other => panic!("expected remote backend, got {:?}", other),
+        }
     }
 
     #[test]
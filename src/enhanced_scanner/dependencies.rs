@@ -0,0 +1,821 @@
+//! Dependency vulnerability cross-reference
+//!
+//! Reads the lockfiles in a project (`Cargo.lock`, `package-lock.json` and
+//! pinned `requirements.txt` entries) and matches every locked version
+//! against a local advisory database in the RustSec `advisory-db` layout:
+//!
+//! ```text
+//! advisory-db/
+//!   crates/<package>/RUSTSEC-2023-0001.md
+//!   npm/<package>/<id>.md
+//!   pypi/<package>/<id>.md
+//! ```
+//!
+//! Each advisory is a markdown file whose leading ```` ```toml ```` block
+//! holds an `[advisory]` table (`id`, `package`, `title`, ...) and a
+//! `[versions]` table of `patched` and `unaffected` requirements; bare
+//! `.toml` advisories (the older layout) are read too. A locked version is
+//! vulnerable when it matches none of those requirements. Withdrawn
+//! advisories are ignored.
+//!
+//! The database is never fetched: point [`DependencyScanner`] at a clone
+//! (cargo-audit keeps one in `~/.cargo/advisory-db`). When it is missing
+//! the check is skipped.
+
+use crate::types::{Issue, IssueCategory, IssueSeverity};
+use anyhow::{Context, Result};
+use ignore::WalkBuilder;
+use serde::Deserialize;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use tracing::{debug, warn};
+
+/// Package ecosystem, named after its directory in the advisory database
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Ecosystem {
+    Crates,
+    Npm,
+    PyPi,
+}
+
+impl Ecosystem {
+    pub const ALL: [Ecosystem; 3] = [Ecosystem::Crates, Ecosystem::Npm, Ecosystem::PyPi];
+
+    /// Directory of this ecosystem's advisories
+    pub fn dir_name(self) -> &'static str {
+        match self {
+            Ecosystem::Crates => "crates",
+            Ecosystem::Npm => "npm",
+            Ecosystem::PyPi => "pypi",
+        }
+    }
+
+    /// Lockfile this ecosystem pins versions in
+    pub fn lockfile_name(self) -> &'static str {
+        match self {
+            Ecosystem::Crates => "Cargo.lock",
+            Ecosystem::Npm => "package-lock.json",
+            Ecosystem::PyPi => "requirements.txt",
+        }
+    }
+
+    fn from_lockfile(path: &Path) -> Option<Self> {
+        let name = path.file_name()?.to_str()?;
+        Self::ALL.into_iter().find(|e| e.lockfile_name() == name)
+    }
+
+    /// Package name as the database stores it (PyPI names are
+    /// case-insensitive and treat `_` and `.` like `-`)
+    fn normalize(self, name: &str) -> String {
+        match self {
+            Ecosystem::PyPi => name.to_lowercase().replace(['_', '.'], "-"),
+            _ => name.to_string(),
+        }
+    }
+}
+
+/// A locked dependency
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Dependency {
+    pub ecosystem: Ecosystem,
+    pub name: String,
+    pub version: String,
+    /// Lockfile the version came from, relative to the scanned root
+    pub lockfile: PathBuf,
+    /// Line of the entry in the lockfile (1-based, 0 if unknown)
+    pub line: usize,
+}
+
+/// Locked dependencies in one lockfile
+pub fn parse_lockfile(path: &Path, content: &str) -> Result<Vec<Dependency>> {
+    let ecosystem = Ecosystem::from_lockfile(path)
+        .with_context(|| format!("Not a supported lockfile: {}", path.display()))?;
+    let entries = match ecosystem {
+        Ecosystem::Crates => parse_cargo_lock(content)?,
+        Ecosystem::Npm => parse_package_lock(content)?,
+        Ecosystem::PyPi => parse_requirements(content),
+    };
+    Ok(entries
+        .into_iter()
+        .map(|(name, version, line)| Dependency {
+            ecosystem,
+            name,
+            version,
+            lockfile: path.to_path_buf(),
+            line,
+        })
+        .collect())
+}
+
+#[derive(Deserialize)]
+struct CargoLock {
+    #[serde(default)]
+    package: Vec<CargoLockPackage>,
+}
+
+#[derive(Deserialize)]
+struct CargoLockPackage {
+    name: String,
+    version: String,
+    source: Option<String>,
+}
+
+/// Registry and git packages in a `Cargo.lock`; workspace members have no
+/// `source` and are skipped
+fn parse_cargo_lock(content: &str) -> Result<Vec<(String, String, usize)>> {
+    let lock: CargoLock = toml::from_str(content).context("Invalid Cargo.lock")?;
+    let lines: Vec<&str> = content.lines().collect();
+    Ok(lock
+        .package
+        .into_iter()
+        .filter(|p| p.source.is_some())
+        .map(|p| {
+            let name_line = format!("name = \"{}\"", p.name);
+            let version_line = format!("version = \"{}\"", p.version);
+            let line = lines
+                .windows(2)
+                .position(|w| w[0].trim() == name_line && w[1].trim() == version_line)
+                .map_or(0, |i| i + 1);
+            (p.name, p.version, line)
+        })
+        .collect())
+}
+
+/// Packages in a `package-lock.json`: the `packages` map of lockfile v2/v3,
+/// or the nested `dependencies` of v1
+fn parse_package_lock(content: &str) -> Result<Vec<(String, String, usize)>> {
+    let lock: serde_json::Value =
+        serde_json::from_str(content).context("Invalid package-lock.json")?;
+    let line_of = |key: &str| {
+        let quoted = format!("\"{key}\"");
+        content
+            .lines()
+            .position(|l| l.trim_start().starts_with(&quoted))
+            .map_or(0, |i| i + 1)
+    };
+
+    let mut deps = Vec::new();
+    if let Some(packages) = lock.get("packages").and_then(|p| p.as_object()) {
+        for (key, entry) in packages {
+            // "" is the root project; "node_modules/a/node_modules/b" is b
+            let Some(idx) = key.rfind("node_modules/") else {
+                continue;
+            };
+            if entry.get("link").and_then(|l| l.as_bool()) == Some(true) {
+                continue;
+            }
+            if let Some(version) = entry.get("version").and_then(|v| v.as_str()) {
+                let name = key[idx + "node_modules/".len()..].to_string();
+                deps.push((name, version.to_string(), line_of(key)));
+            }
+        }
+    } else if let Some(dependencies) = lock.get("dependencies") {
+        collect_v1_dependencies(dependencies, &line_of, &mut deps);
+    }
+    Ok(deps)
+}
+
+fn collect_v1_dependencies(
+    dependencies: &serde_json::Value,
+    line_of: &dyn Fn(&str) -> usize,
+    deps: &mut Vec<(String, String, usize)>,
+) {
+    let Some(map) = dependencies.as_object() else {
+        return;
+    };
+    for (name, entry) in map {
+        if let Some(version) = entry.get("version").and_then(|v| v.as_str()) {
+            deps.push((name.clone(), version.to_string(), line_of(name)));
+        }
+        if let Some(nested) = entry.get("dependencies") {
+            collect_v1_dependencies(nested, line_of, deps);
+        }
+    }
+}
+
+/// `name==version` pins in a `requirements.txt`; ranges, options and
+/// includes can't be resolved without pip and are skipped
+fn parse_requirements(content: &str) -> Vec<(String, String, usize)> {
+    let mut deps = Vec::new();
+    for (index, line) in content.lines().enumerate() {
+        let line = line.split(" #").next().unwrap_or_default();
+        let line = line.split(';').next().unwrap_or_default().trim();
+        if line.is_empty() || line.starts_with('#') || line.starts_with('-') {
+            continue;
+        }
+        let Some((name, version)) = line.split_once("==") else {
+            continue;
+        };
+        let name = name.split('[').next().unwrap_or_default().trim();
+        let version = version.trim();
+        if name.is_empty() || version.is_empty() || version.contains(['*', ',']) {
+            continue;
+        }
+        deps.push((name.to_string(), version.to_string(), index + 1));
+    }
+    deps
+}
+
+/// A version such as `1.2.3` or `1.0.0-beta.2`; missing components are 0
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Version {
+    parts: [u64; 3],
+    pre: Option<String>,
+}
+
+impl Version {
+    pub fn parse(s: &str) -> Option<Self> {
+        let s = s.trim().trim_start_matches('v');
+        // Build metadata never affects precedence
+        let s = s.split('+').next()?;
+        let (release, pre) = match s.split_once('-') {
+            Some((release, pre)) => (release, Some(pre.to_string())),
+            None => (s, None),
+        };
+        let mut parts = [0; 3];
+        let mut components = release.split('.');
+        for part in &mut parts {
+            match components.next() {
+                Some(c) => *part = c.parse().ok()?,
+                None => break,
+            }
+        }
+        Some(Self { parts, pre })
+    }
+}
+
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.parts
+            .cmp(&other.parts)
+            .then_with(|| match (&self.pre, &other.pre) {
+                (None, None) => Ordering::Equal,
+                (None, Some(_)) => Ordering::Greater,
+                (Some(_), None) => Ordering::Less,
+                (Some(a), Some(b)) => a.cmp(b),
+            })
+    }
+}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let [major, minor, patch] = self.parts;
+        write!(f, "{major}.{minor}.{patch}")?;
+        if let Some(ref pre) = self.pre {
+            write!(f, "-{pre}")?;
+        }
+        Ok(())
+    }
+}
+
+/// A Cargo-style version requirement: comma-separated comparators that
+/// must all hold (`>= 1.2.0, < 1.4.0`, `^0.3.1`, `~2.1`, `=1.0.0`, `*`)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VersionReq {
+    source: String,
+    comparators: Vec<(Ordering, bool, Version)>,
+}
+
+impl VersionReq {
+    pub fn parse(s: &str) -> Option<Self> {
+        let mut comparators = Vec::new();
+        for part in s.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+            if part == "*" {
+                continue;
+            }
+            let (op, rest) = ["<=", ">=", "<", ">", "=", "^", "~"]
+                .iter()
+                .find_map(|op| part.strip_prefix(op).map(|rest| (*op, rest)))
+                .unwrap_or(("^", part));
+            let rest = rest.trim();
+            let given = rest.split('.').count();
+            let version = Version::parse(rest)?;
+            match op {
+                ">=" => comparators.push((Ordering::Greater, true, version)),
+                ">" => comparators.push((Ordering::Greater, false, version)),
+                "<=" => comparators.push((Ordering::Less, true, version)),
+                "<" => comparators.push((Ordering::Less, false, version)),
+                "=" => comparators.push((Ordering::Equal, true, version)),
+                _ => {
+                    let upper = upper_bound(op, &version, given);
+                    comparators.push((Ordering::Greater, true, version));
+                    comparators.push((Ordering::Less, false, upper));
+                }
+            }
+        }
+        Some(Self {
+            source: s.trim().to_string(),
+            comparators,
+        })
+    }
+
+    pub fn matches(&self, version: &Version) -> bool {
+        self.comparators
+            .iter()
+            .all(|(direction, inclusive, bound)| match version.cmp(bound) {
+                Ordering::Equal => *inclusive,
+                ordering => ordering == *direction,
+            })
+    }
+
+    /// The version this requirement starts at, if it is a lower bound like
+    /// `>= 1.2.3` or `^1.2.3`
+    pub fn lower_bound(&self) -> Option<&Version> {
+        self.comparators
+            .iter()
+            .find(|(direction, _, _)| *direction == Ordering::Greater)
+            .map(|(_, _, version)| version)
+    }
+}
+
+impl fmt::Display for VersionReq {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
+/// Exclusive upper bound of a caret or tilde requirement with `given`
+/// explicit components
+fn upper_bound(op: &str, version: &Version, given: usize) -> Version {
+    let [major, minor, patch] = version.parts;
+    let parts = if op == "~" {
+        match given {
+            1 => [major + 1, 0, 0],
+            _ => [major, minor + 1, 0],
+        }
+    } else if major > 0 || given == 1 {
+        [major + 1, 0, 0]
+    } else if minor > 0 || given == 2 {
+        [0, minor + 1, 0]
+    } else {
+        [0, 0, patch + 1]
+    };
+    Version { parts, pre: None }
+}
+
+/// One advisory
+#[derive(Debug, Clone)]
+pub struct Advisory {
+    pub id: String,
+    pub package: String,
+    pub ecosystem: Ecosystem,
+    pub title: String,
+    pub url: Option<String>,
+    /// `unmaintained`, `unsound`, `notice`... for non-vulnerability advisories
+    pub informational: Option<String>,
+    pub patched: Vec<VersionReq>,
+    pub unaffected: Vec<VersionReq>,
+}
+
+impl Advisory {
+    /// Whether `version` is neither patched nor unaffected
+    pub fn affects(&self, version: &Version) -> bool {
+        !self
+            .patched
+            .iter()
+            .chain(&self.unaffected)
+            .any(|req| req.matches(version))
+    }
+
+    /// Human-readable affected range, e.g. `< 1.2.3`
+    pub fn affected_range(&self) -> String {
+        let safe: Vec<&VersionReq> = self.patched.iter().chain(&self.unaffected).collect();
+        match safe.as_slice() {
+            [] => "all versions".to_string(),
+            [only] => match only.comparators.as_slice() {
+                [(Ordering::Greater, true, version)] => format!("< {version}"),
+                _ => format!("not {only}"),
+            },
+            _ => {
+                let safe: Vec<String> = safe.iter().map(|r| r.to_string()).collect();
+                format!("not {}", safe.join(" or "))
+            }
+        }
+    }
+
+    /// Oldest patched release, or `None` if no fix exists
+    pub fn fixed_version(&self) -> Option<&Version> {
+        self.patched
+            .iter()
+            .filter_map(VersionReq::lower_bound)
+            .min()
+    }
+}
+
+#[derive(Deserialize)]
+struct AdvisoryFile {
+    advisory: AdvisoryTable,
+    #[serde(default)]
+    versions: VersionsTable,
+}
+
+#[derive(Deserialize)]
+struct AdvisoryTable {
+    id: String,
+    package: String,
+    #[serde(default)]
+    title: String,
+    url: Option<String>,
+    informational: Option<String>,
+    withdrawn: Option<toml::Value>,
+}
+
+#[derive(Deserialize, Default)]
+struct VersionsTable {
+    #[serde(default)]
+    patched: Vec<String>,
+    #[serde(default)]
+    unaffected: Vec<String>,
+}
+
+/// Advisories loaded from disk, by package
+pub struct AdvisoryDb {
+    advisories: HashMap<(Ecosystem, String), Vec<Advisory>>,
+}
+
+impl AdvisoryDb {
+    /// Load every advisory under `root`; unreadable advisories are skipped
+    pub fn open(root: &Path) -> Result<Self> {
+        if !root.is_dir() {
+            anyhow::bail!("Advisory database not found: {}", root.display());
+        }
+        let mut advisories: HashMap<(Ecosystem, String), Vec<Advisory>> = HashMap::new();
+        for ecosystem in Ecosystem::ALL {
+            let dir = root.join(ecosystem.dir_name());
+            if !dir.is_dir() {
+                continue;
+            }
+            for entry in walkdir::WalkDir::new(&dir).into_iter().flatten() {
+                let path = entry.path();
+                if !entry.file_type().is_file()
+                    || path.extension().is_none_or(|e| e != "md" && e != "toml")
+                {
+                    continue;
+                }
+                match Self::load(path, ecosystem) {
+                    Ok(Some(advisory)) => advisories
+                        .entry((ecosystem, ecosystem.normalize(&advisory.package)))
+                        .or_default()
+                        .push(advisory),
+                    Ok(None) => {}
+                    Err(e) => debug!("Skipping advisory {}: {}", path.display(), e),
+                }
+            }
+        }
+        Ok(Self { advisories })
+    }
+
+    /// Parse one advisory file; `None` if it is withdrawn
+    fn load(path: &Path, ecosystem: Ecosystem) -> Result<Option<Advisory>> {
+        let content = std::fs::read_to_string(path)?;
+        Self::parse(&content, ecosystem)
+    }
+
+    fn parse(content: &str, ecosystem: Ecosystem) -> Result<Option<Advisory>> {
+        let toml_src = match content.trim_start().strip_prefix("```toml") {
+            Some(rest) => rest
+                .split_once("```")
+                .map(|(front_matter, _)| front_matter)
+                .context("Unterminated ```toml block")?,
+            None => content,
+        };
+        let file: AdvisoryFile = toml::from_str(toml_src)?;
+        if file.advisory.withdrawn.is_some() {
+            return Ok(None);
+        }
+        let reqs = |reqs: Vec<String>| -> Result<Vec<VersionReq>> {
+            reqs.iter()
+                .map(|r| VersionReq::parse(r).with_context(|| format!("Bad requirement: {r}")))
+                .collect()
+        };
+        Ok(Some(Advisory {
+            id: file.advisory.id,
+            package: file.advisory.package,
+            ecosystem,
+            title: file.advisory.title,
+            url: file.advisory.url,
+            informational: file.advisory.informational,
+            patched: reqs(file.versions.patched)?,
+            unaffected: reqs(file.versions.unaffected)?,
+        }))
+    }
+
+    pub fn len(&self) -> usize {
+        self.advisories.values().map(Vec::len).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.advisories.is_empty()
+    }
+
+    /// Advisories affecting a dependency's locked version
+    pub fn check(&self, dependency: &Dependency) -> Vec<&Advisory> {
+        let Some(version) = Version::parse(&dependency.version) else {
+            return Vec::new();
+        };
+        let key = (
+            dependency.ecosystem,
+            dependency.ecosystem.normalize(&dependency.name),
+        );
+        self.advisories
+            .get(&key)
+            .into_iter()
+            .flatten()
+            .filter(|advisory| advisory.affects(&version))
+            .collect()
+    }
+}
+
+/// A locked dependency matched by an advisory
+#[derive(Debug, Clone)]
+pub struct VulnerableDependency {
+    pub dependency: Dependency,
+    pub advisory: Advisory,
+}
+
+impl VulnerableDependency {
+    /// Audit issue on the lockfile entry. Informational advisories
+    /// (unmaintained, unsound) are Low; vulnerabilities are High.
+    pub fn to_issue(&self) -> Issue {
+        let dep = &self.dependency;
+        let advisory = &self.advisory;
+        let kind = advisory.informational.as_deref().unwrap_or("vulnerability");
+        let fixed = advisory.fixed_version().map_or_else(
+            || "no fixed release".to_string(),
+            |v| format!("fixed in {v}"),
+        );
+        let suggestion = match advisory.fixed_version() {
+            Some(v) => format!("Upgrade {} to {} or later", dep.name, v),
+            None => format!("Replace {} or accept the risk", dep.name),
+        };
+        let suggestion = match advisory.url {
+            Some(ref url) => format!("{suggestion} ({url})"),
+            None => suggestion,
+        };
+        Issue {
+            severity: if advisory.informational.is_some() {
+                IssueSeverity::Low
+            } else {
+                IssueSeverity::High
+            },
+            category: IssueCategory::Security,
+            file: dep.lockfile.clone(),
+            line: dep.line,
+            message: format!(
+                "{} {} {} ({}): {}; affected {}, {}",
+                dep.name,
+                dep.version,
+                kind,
+                advisory.id,
+                advisory.title,
+                advisory.affected_range(),
+                fixed
+            ),
+            suggestion: Some(suggestion),
+        }
+    }
+}
+
+/// Default advisory database location, where cargo-audit keeps its clone
+pub fn default_advisory_db() -> PathBuf {
+    dirs::home_dir()
+        .unwrap_or_default()
+        .join(".cargo")
+        .join("advisory-db")
+}
+
+/// Cross-references a project's lockfiles with an advisory database
+pub struct DependencyScanner {
+    advisory_db: PathBuf,
+}
+
+impl Default for DependencyScanner {
+    fn default() -> Self {
+        Self::new(default_advisory_db())
+    }
+}
+
+impl DependencyScanner {
+    pub fn new(advisory_db: impl Into<PathBuf>) -> Self {
+        Self {
+            advisory_db: advisory_db.into(),
+        }
+    }
+
+    pub fn advisory_db(&self) -> &Path {
+        &self.advisory_db
+    }
+
+    /// Vulnerable dependencies in every lockfile under `root` (honouring
+    /// `.gitignore`), or `None` if the advisory database is absent
+    pub fn scan(&self, root: &Path) -> Result<Option<Vec<VulnerableDependency>>> {
+        if !self.advisory_db.is_dir() {
+            warn!(
+                "Advisory database {} not found; skipping dependency check",
+                self.advisory_db.display()
+            );
+            return Ok(None);
+        }
+        let db = AdvisoryDb::open(&self.advisory_db)?;
+        debug!("Loaded {} advisories", db.len());
+
+        let mut found = Vec::new();
+        for entry in WalkBuilder::new(root).build().flatten() {
+            let path = entry.path();
+            if !path.is_file() || Ecosystem::from_lockfile(path).is_none() {
+                continue;
+            }
+            let relative = path.strip_prefix(root).unwrap_or(path);
+            let dependencies = match std::fs::read_to_string(path)
+                .map_err(anyhow::Error::from)
+                .and_then(|content| parse_lockfile(relative, &content))
+            {
+                Ok(dependencies) => dependencies,
+                Err(e) => {
+                    warn!("Skipping {}: {}", path.display(), e);
+                    continue;
+                }
+            };
+            for dependency in dependencies {
+                for advisory in db.check(&dependency) {
+                    found.push(VulnerableDependency {
+                        dependency: dependency.clone(),
+                        advisory: advisory.clone(),
+                    });
+                }
+            }
+        }
+        Ok(Some(found))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ADVISORY: &str = r#"```toml
+[advisory]
+id = "RUSTSEC-2021-0001"
+package = "smallvec"
+date = "2021-01-08"
+title = "Buffer overflow in SmallVec::insert_many"
+url = "https://github.com/servo/rust-smallvec/issues/252"
+
+[versions]
+patched = [">= 0.6.14, < 1.0.0", ">= 1.6.1"]
+unaffected = ["< 0.6.10"]
+```
+
+# Buffer overflow in SmallVec::insert_many
+"#;
+
+    fn req(s: &str) -> VersionReq {
+        VersionReq::parse(s).unwrap()
+    }
+
+    fn version(s: &str) -> Version {
+        Version::parse(s).unwrap()
+    }
+
+    #[test]
+    fn test_version_requirements() {
+        assert!(req(">= 1.2.0, < 1.4.0").matches(&version("1.3.9")));
+        assert!(!req(">= 1.2.0, < 1.4.0").matches(&version("1.4.0")));
+        assert!(req("^0.3.1").matches(&version("0.3.7")));
+        assert!(!req("^0.3.1").matches(&version("0.4.0")));
+        assert!(req("^1.2").matches(&version("1.9.0")));
+        assert!(req("~2.1").matches(&version("2.1.5")));
+        assert!(!req("~2.1").matches(&version("2.2.0")));
+        assert!(req("=1.0.0").matches(&version("1.0.0")));
+        assert!(!req("=1.0.0").matches(&version("1.0.1")));
+        assert!(req("*").matches(&version("9.9.9")));
+        assert!(version("1.0.0-beta.2") < version("1.0.0"));
+        assert!(!req(">= 1.0.0").matches(&version("1.0.0-rc.1")));
+    }
+
+    #[test]
+    fn test_advisory_matching() {
+        let advisory = AdvisoryDb::parse(ADVISORY, Ecosystem::Crates)
+            .unwrap()
+            .unwrap();
+        assert_eq!(advisory.id, "RUSTSEC-2021-0001");
+        assert!(advisory.affects(&version("1.6.0")));
+        assert!(advisory.affects(&version("0.6.12")));
+        assert!(!advisory.affects(&version("1.6.1")));
+        assert!(!advisory.affects(&version("0.6.14")));
+        assert!(!advisory.affects(&version("0.6.9")));
+        assert_eq!(advisory.fixed_version(), Some(&version("0.6.14")));
+
+        let withdrawn = ADVISORY.replace("[versions]", "withdrawn = \"2021-02-01\"\n\n[versions]");
+        assert!(AdvisoryDb::parse(&withdrawn, Ecosystem::Crates)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_parse_lockfiles() {
+        let cargo_lock = r#"
+version = 3
+
+[[package]]
+name = "myapp"
+version = "0.1.0"
+
+[[package]]
+name = "smallvec"
+version = "1.6.0"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+"#;
+        let deps = parse_lockfile(Path::new("Cargo.lock"), cargo_lock).unwrap();
+        assert_eq!(deps.len(), 1);
+        assert_eq!((deps[0].name.as_str(), deps[0].line), ("smallvec", 9));
+
+        let package_lock = r#"{
+  "lockfileVersion": 3,
+  "packages": {
+    "": { "name": "web" },
+    "node_modules/lodash": { "version": "4.17.20" },
+    "node_modules/a/node_modules/minimist": { "version": "1.2.5" }
+  }
+}"#;
+        let deps = parse_lockfile(Path::new("web/package-lock.json"), package_lock).unwrap();
+        let mut names: Vec<(&str, &str, usize)> = deps
+            .iter()
+            .map(|d| (d.name.as_str(), d.version.as_str(), d.line))
+            .collect();
+        names.sort();
+        assert_eq!(
+            names,
+            vec![("lodash", "4.17.20", 5), ("minimist", "1.2.5", 6)]
+        );
+
+        let requirements =
+            "# pinned\nRequests[socks]==2.25.0 ; python_version > '3'\nflask>=2\n-r dev.txt\n";
+        let deps = parse_lockfile(Path::new("requirements.txt"), requirements).unwrap();
+        assert_eq!(deps.len(), 1);
+        assert_eq!(
+            (
+                deps[0].name.as_str(),
+                deps[0].version.as_str(),
+                deps[0].line
+            ),
+            ("Requests", "2.25.0", 2)
+        );
+    }
+
+    #[test]
+    fn test_scan_reports_vulnerable_dependencies() {
+        let db = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(db.path().join("crates/smallvec")).unwrap();
+        std::fs::write(
+            db.path().join("crates/smallvec/RUSTSEC-2021-0001.md"),
+            ADVISORY,
+        )
+        .unwrap();
+        std::fs::create_dir_all(db.path().join("pypi/requests")).unwrap();
+        std::fs::write(
+            db.path().join("pypi/requests/PYSEC-2023-74.toml"),
+            "[advisory]\nid = \"PYSEC-2023-74\"\npackage = \"requests\"\ntitle = \"Proxy-Authorization leak\"\n\n[versions]\npatched = [\">= 2.31.0\"]\n",
+        )
+        .unwrap();
+
+        let project = tempfile::tempdir().unwrap();
+        std::fs::write(
+            project.path().join("Cargo.lock"),
+            "[[package]]\nname = \"smallvec\"\nversion = \"1.6.0\"\nsource = \"registry+https://github.com/rust-lang/crates.io-index\"\n",
+        )
+        .unwrap();
+        std::fs::write(
+            project.path().join("requirements.txt"),
+            "requests==2.25.0\n",
+        )
+        .unwrap();
+
+        let mut found = DependencyScanner::new(db.path())
+            .scan(project.path())
+            .unwrap()
+            .unwrap();
+        found.sort_by(|a, b| a.advisory.id.cmp(&b.advisory.id));
+        assert_eq!(found.len(), 2);
+
+        let issue = found[1].to_issue();
+        assert_eq!(issue.severity, IssueSeverity::High);
+        assert_eq!(issue.file, Path::new("Cargo.lock"));
+        assert_eq!(issue.line, 2);
+        assert!(issue.message.contains("RUSTSEC-2021-0001"));
+        assert!(issue.message.contains("fixed in 0.6.14"));
+        assert!(found[0].to_issue().message.contains("affected < 2.31.0"));
+
+        // No database: skipped rather than failing
+        let missing = DependencyScanner::new(db.path().join("missing"));
+        assert!(missing.scan(project.path()).unwrap().is_none());
+    }
+}
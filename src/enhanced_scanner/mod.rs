@@ -1,11 +1,24 @@
 //! Enhanced scanner with test running and deep context analysis
 
+pub mod dependencies;
+pub mod rust_risks;
+pub mod secrets;
+
+pub use dependencies::{AdvisoryDb, DependencyScanner, VulnerableDependency};
+pub use rust_risks::{RiskCounts, RiskKind, RustRiskScanner};
+pub use secrets::{SecretScanner, ALLOW_SECRET_MARKER};
+
+use crate::code_review::ReviewIssue;
 use crate::context::{ContextBuilder, GlobalContextBundle};
 use crate::error::Result;
 use crate::llm::{FileAuditResult, LlmClient};
-use crate::scanner::Scanner;
+use crate::path_filter::PathFilter;
+use crate::scanner::{ScanProgressCallback, Scanner};
 use crate::tests_runner::{TestResults, TestRunner};
-use crate::types::{AuditReport, AuditRequest, AuditSummary, Task, TaskPriority};
+use crate::types::{
+    AuditReport, AuditRequest, AuditSummary, Category, FileAnalysis, FilePriority, Issue,
+    IssueCategory, IssueSeverity, Task, TaskPriority,
+};
 use std::path::PathBuf;
 use tracing::{info, warn};
 
@@ -14,7 +27,6 @@ pub struct EnhancedScanner {
     /// Base scanner
     scanner: Scanner,
     /// Root directory
-    #[allow(dead_code)]
     root: PathBuf,
     /// Test runner
     test_runner: TestRunner,
@@ -26,6 +38,12 @@ pub struct EnhancedScanner {
     run_tests: bool,
     /// Whether to use deep analysis
     use_deep_analysis: bool,
+    /// Secret detection pass; `None` when disabled
+    secret_scanner: Option<SecretScanner>,
+    /// Rust unsafe/panic audit pass; `None` when disabled
+    rust_risk_scanner: Option<RustRiskScanner>,
+    /// Dependency advisory cross-reference; `None` when disabled
+    dependency_scanner: Option<DependencyScanner>,
 }
 
 impl EnhancedScanner {
@@ -52,6 +70,9 @@ impl EnhancedScanner {
             llm_client,
             run_tests: include_tests,
             use_deep_analysis,
+            secret_scanner: Some(SecretScanner::new()),
+            rust_risk_scanner: Some(RustRiskScanner::new()),
+            dependency_scanner: None,
         })
     }
 
@@ -67,6 +88,48 @@ impl EnhancedScanner {
         self
     }
 
+    /// Set whether to scan for leaked secrets (on by default)
+    pub fn with_secret_scan(mut self, enabled: bool) -> Self {
+        self.secret_scanner = enabled.then(SecretScanner::new);
+        self
+    }
+
+    /// Set whether to audit Rust files for unsafe code and panics (on by
+    /// default)
+    pub fn with_rust_risk_audit(mut self, enabled: bool) -> Self {
+        self.rust_risk_scanner = enabled.then(RustRiskScanner::new);
+        self
+    }
+
+    /// Include test files and test items in the Rust audit (off by
+    /// default). Test files are only audited if the scan includes them.
+    pub fn with_rust_risk_tests(mut self, include: bool) -> Self {
+        self.rust_risk_scanner = self
+            .rust_risk_scanner
+            .map(|scanner| scanner.with_tests(include));
+        self
+    }
+
+    /// Check locked dependencies against the advisory database at
+    /// `advisory_db` (off by default). The check is skipped if the
+    /// database doesn't exist.
+    pub fn with_dependency_check(mut self, advisory_db: impl Into<PathBuf>) -> Self {
+        self.dependency_scanner = Some(DependencyScanner::new(advisory_db));
+        self
+    }
+
+    /// Only audit files passing the include/exclude `filter`
+    pub fn with_path_filter(mut self, filter: PathFilter) -> Self {
+        self.scanner = self.scanner.with_path_filter(filter);
+        self
+    }
+
+    /// Report per-file progress of the static scan to `callback`
+    pub fn with_progress(mut self, callback: ScanProgressCallback) -> Self {
+        self.scanner = self.scanner.with_progress(callback);
+        self
+    }
+
     /// Run complete audit with all features
     pub async fn run_complete_audit(&self, request: &AuditRequest) -> Result<AuditReport> {
         info!("Starting enhanced audit with test running and deep analysis");
@@ -75,6 +138,38 @@ impl EnhancedScanner {
         info!("Step 1: Running static analysis...");
         let mut report = self.scanner.scan(request)?;
 
+        if let Some(ref secret_scanner) = self.secret_scanner {
+            info!("Step 1b: Scanning for leaked secrets...");
+            let found = self.add_secret_issues(secret_scanner, &mut report);
+            if found > 0 {
+                // Counts only; the values are never logged
+                warn!("Found {} possible secrets", found);
+            }
+        }
+
+        if let Some(ref rust_risk_scanner) = self.rust_risk_scanner {
+            info!("Step 1c: Auditing Rust unsafe code and panics...");
+            let counts = self.add_rust_risk_issues(rust_risk_scanner, &mut report);
+            info!(
+                "Rust audit: {} unsafe, {} transmute, {} unwrap, {} expect, {} panic, {} unreachable",
+                counts.unsafe_code,
+                counts.transmutes,
+                counts.unwraps,
+                counts.expects,
+                counts.panics,
+                counts.unreachables
+            );
+        }
+
+        if let Some(ref dependency_scanner) = self.dependency_scanner {
+            info!("Step 1d: Checking dependencies against advisories...");
+            match self.add_dependency_issues(dependency_scanner, &mut report) {
+                Ok(Some(found)) if found > 0 => warn!("Found {} vulnerable dependencies", found),
+                Ok(_) => {}
+                Err(e) => warn!("Dependency check failed: {}", e),
+            }
+        }
+
         // Step 2: Run tests if enabled
         let mut test_results = None;
         if self.run_tests || request.include_tests {
@@ -156,6 +251,80 @@ impl EnhancedScanner {
         Ok(report)
     }
 
+    /// Add secret findings to the report's files; returns how many were found
+    fn add_secret_issues(&self, secret_scanner: &SecretScanner, report: &mut AuditReport) -> usize {
+        let mut found = 0;
+        for file in &mut report.files {
+            let Ok(content) = std::fs::read_to_string(self.root.join(&file.path)) else {
+                continue;
+            };
+            let findings = secret_scanner.scan(&content);
+            found += findings.len();
+            file.issues
+                .extend(findings.into_iter().map(|f| secret_issue(&file.path, f)));
+        }
+        recount_issues(report);
+        found
+    }
+
+    /// Add Rust audit findings to the report's files, replacing the base
+    /// scanner's substring matches on `unsafe` and `unwrap()`
+    fn add_rust_risk_issues(
+        &self,
+        scanner: &RustRiskScanner,
+        report: &mut AuditReport,
+    ) -> RiskCounts {
+        let mut counts = RiskCounts::default();
+        for file in &mut report.files {
+            if file.path.extension().is_none_or(|e| e != "rs") {
+                continue;
+            }
+            let Ok(content) = std::fs::read_to_string(self.root.join(&file.path)) else {
+                continue;
+            };
+            let findings = scanner.scan_file(&file.path, &content);
+            counts.files += 1;
+            file.issues.retain(|issue| !is_substring_risk(issue));
+            for finding in findings {
+                counts.record(finding.kind);
+                file.issues.push(finding.to_issue(&file.path));
+            }
+            file.issues.sort_by_key(|issue| issue.line);
+        }
+        recount_issues(report);
+        counts
+    }
+
+    /// Add advisory matches to the report's lockfile entries, adding the
+    /// lockfile if the base scan skipped it. Returns how many were found,
+    /// or `None` if there is no advisory database.
+    fn add_dependency_issues(
+        &self,
+        scanner: &DependencyScanner,
+        report: &mut AuditReport,
+    ) -> anyhow::Result<Option<usize>> {
+        let Some(found) = scanner.scan(&self.root)? else {
+            return Ok(None);
+        };
+        for vulnerable in &found {
+            let issue = vulnerable.to_issue();
+            let index = match report.files.iter().position(|f| f.path == issue.file) {
+                Some(index) => index,
+                None => {
+                    report
+                        .files
+                        .push(lockfile_analysis(&self.root, &issue.file));
+                    report.summary.total_files += 1;
+                    report.files.len() - 1
+                }
+            };
+            report.files[index].issues.push(issue);
+        }
+        report.files.sort_by(|a, b| a.path.cmp(&b.path));
+        recount_issues(report);
+        Ok(Some(found.len()))
+    }
+
     /// Run all tests in the project
     fn run_tests(&self) -> Result<Vec<TestResults>> {
         info!("Discovering and running tests...");
@@ -335,6 +504,61 @@ impl EnhancedScanner {
     }
 }
 
+/// Issues the base scanner raises for any line containing `unsafe` or
+/// `unwrap()`, comments and tests included
+fn is_substring_risk(issue: &Issue) -> bool {
+    issue.message.starts_with("Unsafe code at line")
+        || issue.message.starts_with("unwrap() at line")
+}
+
+/// Recompute the report's issue totals from its files
+fn recount_issues(report: &mut AuditReport) {
+    report.issues_by_severity.clear();
+    for issue in report.files.iter().flat_map(|f| &f.issues) {
+        *report.issues_by_severity.entry(issue.severity).or_insert(0) += 1;
+    }
+    report.summary.total_issues = report.files.iter().map(|f| f.issues.len()).sum();
+    report.summary.critical_files = report
+        .files
+        .iter()
+        .filter(|f| {
+            f.issues
+                .iter()
+                .any(|i| i.severity == IssueSeverity::Critical)
+        })
+        .count();
+}
+
+/// Report entry for a lockfile the base scanner skipped (usually for size)
+fn lockfile_analysis(root: &std::path::Path, path: &std::path::Path) -> FileAnalysis {
+    let lines = std::fs::read_to_string(root.join(path))
+        .map(|content| content.lines().count())
+        .unwrap_or(0);
+    FileAnalysis {
+        path: path.to_path_buf(),
+        category: Category::Config,
+        priority: FilePriority::High,
+        lines,
+        doc_blocks: 0,
+        security_rating: None,
+        issues: Vec::new(),
+        llm_analysis: None,
+        tags: Vec::new(),
+    }
+}
+
+/// Audit issue for a secret finding
+fn secret_issue(path: &std::path::Path, finding: ReviewIssue) -> Issue {
+    Issue {
+        severity: IssueSeverity::Critical,
+        category: IssueCategory::Security,
+        file: path.to_path_buf(),
+        line: finding.line.unwrap_or(0),
+        message: finding.description,
+        suggestion: Some("Remove the secret from source and rotate it".to_string()),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -354,5 +578,54 @@ mod tests {
 
         assert!(scanner.run_tests);
         assert!(!scanner.use_deep_analysis);
+        assert!(scanner.secret_scanner.is_some());
+        let scanner = scanner.with_secret_scan(false);
+        assert!(scanner.secret_scanner.is_none());
+        assert!(scanner.rust_risk_scanner.is_some());
+    }
+
+    #[test]
+    fn test_rust_risk_issues_join_report() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("src")).unwrap();
+        std::fs::write(
+            dir.path().join("src/lib.rs"),
+            "pub fn f(v: &[u8]) -> u8 {\n    // unsafe here is only a comment\n    let x = unsafe { *v.as_ptr() };\n    v.first().copied().unwrap() + x\n}\n",
+        )
+        .unwrap();
+
+        let scanner = EnhancedScanner::new(dir.path().to_path_buf(), 1_000_000, false, None)
+            .unwrap()
+            .with_secret_scan(false);
+        let mut report = scanner
+            .scanner
+            .scan(&AuditRequest {
+                repository: dir.path().display().to_string(),
+                branch: None,
+                enable_llm: false,
+                focus: Vec::new(),
+                include_tests: false,
+            })
+            .unwrap();
+        let counts =
+            scanner.add_rust_risk_issues(scanner.rust_risk_scanner.as_ref().unwrap(), &mut report);
+        assert_eq!((counts.unsafe_code, counts.unwraps), (1, 1));
+
+        let file = report
+            .files
+            .iter()
+            .find(|f| f.path == std::path::Path::new("src/lib.rs"))
+            .unwrap();
+        let issues: Vec<(usize, IssueSeverity)> =
+            file.issues.iter().map(|i| (i.line, i.severity)).collect();
+        assert_eq!(
+            issues,
+            vec![(3, IssueSeverity::High), (4, IssueSeverity::Medium)]
+        );
+        assert_eq!(report.summary.total_issues, 2);
+        assert_eq!(
+            report.issues_by_severity.get(&IssueSeverity::High),
+            Some(&1)
+        );
     }
 }
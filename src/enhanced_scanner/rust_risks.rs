@@ -0,0 +1,474 @@
+//! Unsafe and panic audit for Rust sources
+//!
+//! Inventories the patterns that can crash or break memory safety:
+//!
+//! | Pattern                         | Severity |
+//! |---------------------------------|----------|
+//! | `unsafe` blocks, fns and impls  | High     |
+//! | `mem::transmute`                | High     |
+//! | `.unwrap()` / `.expect(..)`     | Medium   |
+//! | `panic!`                        | Medium   |
+//! | `unreachable!`                  | Low      |
+//!
+//! Comments and string literals are ignored. Test code is skipped unless
+//! opted in: files under `tests/` and `#[cfg(test)]` / `#[test]` items.
+
+use crate::types::{Issue, IssueCategory, IssueSeverity};
+use ignore::WalkBuilder;
+use regex::Regex;
+use std::path::{Component, Path, PathBuf};
+
+/// A risky pattern
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RiskKind {
+    Unsafe,
+    Transmute,
+    Unwrap,
+    Expect,
+    Panic,
+    Unreachable,
+}
+
+impl RiskKind {
+    pub fn severity(self) -> IssueSeverity {
+        match self {
+            RiskKind::Unsafe | RiskKind::Transmute => IssueSeverity::High,
+            RiskKind::Unwrap | RiskKind::Expect | RiskKind::Panic => IssueSeverity::Medium,
+            RiskKind::Unreachable => IssueSeverity::Low,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            RiskKind::Unsafe => "unsafe code",
+            RiskKind::Transmute => "mem::transmute",
+            RiskKind::Unwrap => ".unwrap()",
+            RiskKind::Expect => ".expect()",
+            RiskKind::Panic => "panic!",
+            RiskKind::Unreachable => "unreachable!",
+        }
+    }
+
+    fn suggestion(self) -> &'static str {
+        match self {
+            RiskKind::Unsafe => "Document the safety invariant or use a safe abstraction",
+            RiskKind::Transmute => "Use a safe conversion such as from_bits or bytemuck",
+            RiskKind::Unwrap | RiskKind::Expect => "Propagate the error with ? or handle it",
+            RiskKind::Panic => "Return an error instead of panicking",
+            RiskKind::Unreachable => "Check the invariant really holds",
+        }
+    }
+}
+
+/// One risky pattern at a line
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RiskFinding {
+    pub kind: RiskKind,
+    pub line: usize,
+}
+
+impl RiskFinding {
+    /// Audit issue for `path`
+    pub fn to_issue(&self, path: &Path) -> Issue {
+        let category = match self.kind {
+            RiskKind::Unsafe | RiskKind::Transmute => IssueCategory::Security,
+            _ => IssueCategory::CodeQuality,
+        };
+        Issue {
+            severity: self.kind.severity(),
+            category,
+            file: path.to_path_buf(),
+            line: self.line,
+            message: format!("{} at line {}", self.kind.label(), self.line),
+            suggestion: Some(self.kind.suggestion().to_string()),
+        }
+    }
+}
+
+/// Counts per pattern
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RiskCounts {
+    pub files: usize,
+    pub unsafe_code: usize,
+    pub transmutes: usize,
+    pub unwraps: usize,
+    pub expects: usize,
+    pub panics: usize,
+    pub unreachables: usize,
+}
+
+impl RiskCounts {
+    pub fn record(&mut self, kind: RiskKind) {
+        match kind {
+            RiskKind::Unsafe => self.unsafe_code += 1,
+            RiskKind::Transmute => self.transmutes += 1,
+            RiskKind::Unwrap => self.unwraps += 1,
+            RiskKind::Expect => self.expects += 1,
+            RiskKind::Panic => self.panics += 1,
+            RiskKind::Unreachable => self.unreachables += 1,
+        }
+    }
+
+    pub fn total(&self) -> usize {
+        self.unsafe_code
+            + self.transmutes
+            + self.unwraps
+            + self.expects
+            + self.panics
+            + self.unreachables
+    }
+}
+
+/// Finds risky patterns in Rust sources
+pub struct RustRiskScanner {
+    include_tests: bool,
+    patterns: Vec<(RiskKind, Regex)>,
+}
+
+impl Default for RustRiskScanner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RustRiskScanner {
+    pub fn new() -> Self {
+        Self {
+            include_tests: false,
+            patterns: vec![
+                (
+                    RiskKind::Unsafe,
+                    Regex::new(r"\bunsafe\s*(\{|fn\b|impl\b|trait\b|extern\b)").unwrap(),
+                ),
+                (
+                    RiskKind::Transmute,
+                    Regex::new(r"\btransmute(_copy)?\b").unwrap(),
+                ),
+                (RiskKind::Unwrap, Regex::new(r"\.unwrap\(\s*\)").unwrap()),
+                (RiskKind::Expect, Regex::new(r"\.expect\(").unwrap()),
+                (RiskKind::Panic, Regex::new(r"\bpanic!\s*[(\[{]").unwrap()),
+                (
+                    RiskKind::Unreachable,
+                    Regex::new(r"\bunreachable!\s*[(\[{]").unwrap(),
+                ),
+            ],
+        }
+    }
+
+    /// Also audit test files and test items
+    pub fn with_tests(mut self, include: bool) -> Self {
+        self.include_tests = include;
+        self
+    }
+
+    /// Whether `path` (relative to the crate) is a test file
+    pub fn is_test_path(path: &Path) -> bool {
+        let in_test_dir = path
+            .components()
+            .any(|c| matches!(c, Component::Normal(name) if name == "tests" || name == "test"));
+        let name = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or_default();
+        in_test_dir
+            || name == "tests.rs"
+            || name.ends_with("_test.rs")
+            || name.ends_with("_tests.rs")
+    }
+
+    /// Findings for a file, or none if it is excluded test code
+    pub fn scan_file(&self, path: &Path, content: &str) -> Vec<RiskFinding> {
+        if path.extension().is_none_or(|e| e != "rs")
+            || (!self.include_tests && Self::is_test_path(path))
+        {
+            return Vec::new();
+        }
+        self.scan(content)
+    }
+
+    /// Findings in Rust source, in line order
+    pub fn scan(&self, content: &str) -> Vec<RiskFinding> {
+        let mut findings = Vec::new();
+        let mut lexer = Lexer::default();
+        let mut test_item = TestItem::default();
+
+        for (index, line) in content.lines().enumerate() {
+            let code = lexer.strip(line);
+            if !self.include_tests && test_item.skip(&code) {
+                continue;
+            }
+            for (kind, pattern) in &self.patterns {
+                for _ in pattern.find_iter(&code) {
+                    findings.push(RiskFinding {
+                        kind: *kind,
+                        line: index + 1,
+                    });
+                }
+            }
+        }
+        findings
+    }
+
+    /// Counts for every Rust file under `root`, honouring `.gitignore`
+    pub fn scan_dir(&self, root: &Path) -> RiskCounts {
+        let mut counts = RiskCounts::default();
+        for entry in WalkBuilder::new(root).build().flatten() {
+            let path = entry.path();
+            if !path.is_file() {
+                continue;
+            }
+            let relative: PathBuf = path.strip_prefix(root).unwrap_or(path).to_path_buf();
+            let Ok(content) = std::fs::read_to_string(path) else {
+                continue;
+            };
+            let findings = self.scan_file(&relative, &content);
+            if relative.extension().is_some_and(|e| e == "rs")
+                && (self.include_tests || !Self::is_test_path(&relative))
+            {
+                counts.files += 1;
+            }
+            for finding in findings {
+                counts.record(finding.kind);
+            }
+        }
+        counts
+    }
+}
+
+/// Removes comments and string contents, tracking block comments and
+/// strings that span lines
+#[derive(Default)]
+struct Lexer {
+    block_comment_depth: usize,
+    in_string: bool,
+}
+
+impl Lexer {
+    fn strip(&mut self, line: &str) -> String {
+        let chars: Vec<char> = line.chars().collect();
+        let mut out = String::with_capacity(line.len());
+        let mut i = 0;
+        while i < chars.len() {
+            let c = chars[i];
+            let next = chars.get(i + 1).copied();
+            if self.block_comment_depth > 0 {
+                if c == '*' && next == Some('/') {
+                    self.block_comment_depth -= 1;
+                    i += 2;
+                } else if c == '/' && next == Some('*') {
+                    self.block_comment_depth += 1;
+                    i += 2;
+                } else {
+                    i += 1;
+                }
+                continue;
+            }
+            if self.in_string {
+                match c {
+                    '\\' => i += 2,
+                    '"' => {
+                        self.in_string = false;
+                        out.push('"');
+                        i += 1;
+                    }
+                    _ => i += 1,
+                }
+                continue;
+            }
+            match (c, next) {
+                ('/', Some('/')) => break,
+                ('/', Some('*')) => {
+                    self.block_comment_depth = 1;
+                    i += 2;
+                }
+                ('"', _) => {
+                    self.in_string = true;
+                    out.push('"');
+                    i += 1;
+                }
+                // Raw strings on one line: r"..." and r#"..."#
+                ('r', Some('"' | '#')) if i == 0 || !is_ident(chars[i - 1]) => {
+                    let hashes = chars[i + 1..].iter().take_while(|&&c| c == '#').count();
+                    let open = i + 1 + hashes;
+                    if chars.get(open) != Some(&'"') {
+                        out.push(c);
+                        i += 1;
+                        continue;
+                    }
+                    let closing: String = std::iter::once('"')
+                        .chain("#".repeat(hashes).chars())
+                        .collect();
+                    let rest: String = chars[open + 1..].iter().collect();
+                    out.push_str("\"\"");
+                    match rest.find(&closing) {
+                        Some(end) => i = open + 1 + rest[..end].chars().count() + closing.len(),
+                        None => i = chars.len(),
+                    }
+                }
+                // Char literals such as '"' or '{'
+                ('\'', _) if chars.get(i + 2) == Some(&'\'') && next != Some('\\') => {
+                    i += 3;
+                }
+                ('\'', Some('\\')) => {
+                    let end = chars[i + 2..].iter().position(|&c| c == '\'');
+                    i = end.map_or(chars.len(), |end| i + 3 + end);
+                }
+                _ => {
+                    out.push(c);
+                    i += 1;
+                }
+            }
+        }
+        out
+    }
+}
+
+fn is_ident(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// Tracks `#[cfg(test)]` and `#[test]` items so their bodies can be skipped
+#[derive(Default)]
+struct TestItem {
+    /// A test attribute was seen and its item hasn't opened yet
+    pending: bool,
+    /// Brace depth inside the skipped item
+    depth: usize,
+}
+
+impl TestItem {
+    /// Whether this (stripped) line belongs to test code
+    fn skip(&mut self, code: &str) -> bool {
+        if self.depth > 0 {
+            self.count_braces(code);
+            return true;
+        }
+        if code.contains("#[cfg(test)]") || code.contains("#[test]") || code.contains("::test]") {
+            self.pending = true;
+        }
+        if !self.pending {
+            return false;
+        }
+        if code.contains('{') {
+            self.pending = false;
+            self.count_braces(code);
+        } else if code.trim_end().ends_with(';') {
+            // `#[cfg(test)] use ...;` has no body
+            self.pending = false;
+        }
+        true
+    }
+
+    fn count_braces(&mut self, code: &str) {
+        for c in code.chars() {
+            match c {
+                '{' => self.depth += 1,
+                '}' => self.depth = self.depth.saturating_sub(1),
+                _ => {}
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = r#"
+use std::mem;
+
+/// Never call .unwrap() here
+pub fn parse(s: &str) -> u32 {
+    let msg = "don't panic!(now) or .unwrap()";
+    let v: u32 = s.parse().unwrap();
+    let brace = '{';
+    /* unsafe { block comment } */
+    let f: f32 = unsafe { mem::transmute(v) };
+    let w = s.parse::<u32>().expect("number");
+    if v > 10 {
+        panic!("too big: {}", msg);
+    }
+    match v { 0 => unreachable!(), _ => v + w + f as u32 }
+}
+
+unsafe impl Send for Wrapper {}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn t() {
+        assert_eq!(super::parse("1"), Some(1).unwrap());
+    }
+}
+
+#[test]
+fn outside() { None::<u8>.unwrap(); }
+
+fn after() -> u8 { Some(1).unwrap() }
+"#;
+
+    fn kinds(findings: &[RiskFinding]) -> Vec<(RiskKind, usize)> {
+        findings.iter().map(|f| (f.kind, f.line)).collect()
+    }
+
+    #[test]
+    fn test_scan_flags_patterns_outside_tests() {
+        let scanner = RustRiskScanner::new();
+        let findings = scanner.scan(SOURCE);
+        assert_eq!(
+            kinds(&findings),
+            vec![
+                (RiskKind::Unwrap, 7),
+                (RiskKind::Unsafe, 10),
+                (RiskKind::Transmute, 10),
+                (RiskKind::Expect, 11),
+                (RiskKind::Panic, 13),
+                (RiskKind::Unreachable, 15),
+                (RiskKind::Unsafe, 18),
+                (RiskKind::Unwrap, 31),
+            ]
+        );
+
+        let issue = findings[0].to_issue(Path::new("src/lib.rs"));
+        assert_eq!(issue.severity, IssueSeverity::Medium);
+        assert_eq!(issue.line, 7);
+        assert_eq!(
+            findings[1].to_issue(Path::new("src/lib.rs")).severity,
+            IssueSeverity::High
+        );
+
+        // Opting in also reports the two unwraps in test code
+        let with_tests = RustRiskScanner::new().with_tests(true).scan(SOURCE);
+        assert_eq!(
+            with_tests
+                .iter()
+                .filter(|f| f.kind == RiskKind::Unwrap)
+                .count(),
+            4
+        );
+    }
+
+    #[test]
+    fn test_test_files_excluded_by_default() {
+        let code = "fn f() { Some(1).unwrap(); }";
+        let scanner = RustRiskScanner::new();
+        assert!(scanner
+            .scan_file(Path::new("tests/api.rs"), code)
+            .is_empty());
+        assert!(scanner
+            .scan_file(Path::new("src/db/tests.rs"), code)
+            .is_empty());
+        assert!(scanner.scan_file(Path::new("src/main.py"), code).is_empty());
+        assert_eq!(scanner.scan_file(Path::new("src/lib.rs"), code).len(), 1);
+        let scanner = scanner.with_tests(true);
+        assert_eq!(scanner.scan_file(Path::new("tests/api.rs"), code).len(), 1);
+
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("src")).unwrap();
+        std::fs::create_dir_all(dir.path().join("tests")).unwrap();
+        std::fs::write(dir.path().join("src/lib.rs"), code).unwrap();
+        std::fs::write(dir.path().join("tests/it.rs"), code).unwrap();
+        let counts = RustRiskScanner::new().scan_dir(dir.path());
+        assert_eq!((counts.files, counts.unwraps, counts.total()), (1, 1, 1));
+    }
+}
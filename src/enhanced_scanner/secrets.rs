@@ -0,0 +1,462 @@
+//! Secret detection pass
+//!
+//! Finds leaked credentials with a set of high-confidence patterns (AWS
+//! keys, private key headers, xAI keys, Slack tokens, JWTs) and flags other
+//! quoted strings whose character entropy looks like a random key. Every
+//! finding is a [`ReviewIssue`] at [`IssueSeverity::Critical`].
+//!
+//! The matched value never leaves this module: messages only carry the
+//! rule, position, and a redacted form that keeps nothing but a well-known
+//! prefix such as `AKIA`.
+//!
+//! A line containing [`ALLOW_SECRET_MARKER`], or directly below a comment
+//! containing it, is not reported.
+
+use crate::code_review::{IssueSeverity, ReviewIssue};
+use regex::Regex;
+
+/// Inline marker that suppresses secret findings on its line and the next
+pub const ALLOW_SECRET_MARKER: &str = "rustassistant:allow-secret";
+
+/// Bits per character above which a quoted token counts as random
+const ENTROPY_THRESHOLD: f64 = 4.3;
+
+/// Shortest quoted token checked for entropy
+const MIN_ENTROPY_LEN: usize = 24;
+
+/// A pattern for one kind of credential
+struct SecretRule {
+    name: &'static str,
+    description: &'static str,
+    pattern: Regex,
+    /// Characters of the value that are a fixed, non-secret prefix and may
+    /// be shown, e.g. the `AKIA` of an AWS key
+    public_prefix: usize,
+    /// Skip values that look like placeholders (`your-key-here`, `${VAR}`)
+    skip_placeholders: bool,
+}
+
+/// A secret found on one line, as byte offsets into the line
+struct LineSecret {
+    start: usize,
+    end: usize,
+    rule: &'static str,
+    description: &'static str,
+    public_prefix: usize,
+}
+
+/// Scans file contents for credentials
+pub struct SecretScanner {
+    rules: Vec<SecretRule>,
+    quoted: Regex,
+}
+
+impl Default for SecretScanner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SecretScanner {
+    pub fn new() -> Self {
+        let rule =
+            |name, description, pattern: &str, public_prefix, skip_placeholders| SecretRule {
+                name,
+                description,
+                pattern: Regex::new(pattern).unwrap(),
+                public_prefix,
+                skip_placeholders,
+            };
+
+        Self {
+            rules: vec![
+                rule(
+                    "private-key",
+                    "private key",
+                    r"-----BEGIN (?:RSA |EC |DSA |OPENSSH |PGP |ENCRYPTED )?PRIVATE KEY(?: BLOCK)?-----",
+                    0,
+                    false,
+                ),
+                rule(
+                    "aws-access-key-id",
+                    "AWS access key ID",
+                    r"\b(?:AKIA|ASIA)[0-9A-Z]{16}\b",
+                    4,
+                    false,
+                ),
+                rule(
+                    "aws-secret-access-key",
+                    "AWS secret access key",
+                    r#"(?i)aws_?secret_?(?:access_?)?key["']?\s*[:=]\s*["']?([A-Za-z0-9/+=]{40})\b"#,
+                    0,
+                    true,
+                ),
+                rule(
+                    "xai-api-key",
+                    "xAI API key",
+                    r#"XAI_API_KEY["']?\s*[:=]\s*["']?([A-Za-z0-9_\-]{20,})"#,
+                    0,
+                    true,
+                ),
+                rule(
+                    "xai-api-key",
+                    "xAI API key",
+                    r"\bxai-[A-Za-z0-9]{32,}\b",
+                    4,
+                    true,
+                ),
+                rule(
+                    "slack-token",
+                    "Slack token",
+                    r"\bxox[abposr]-[0-9A-Za-z\-]{10,}\b",
+                    5,
+                    false,
+                ),
+                rule(
+                    "slack-webhook",
+                    "Slack webhook URL",
+                    r"https://hooks\.slack\.com/services/T[0-9A-Za-z]+/B[0-9A-Za-z]+/[0-9A-Za-z]+",
+                    29,
+                    false,
+                ),
+                rule(
+                    "jwt",
+                    "JSON Web Token",
+                    r"\beyJ[A-Za-z0-9_\-]{8,}\.eyJ[A-Za-z0-9_\-]{8,}\.[A-Za-z0-9_\-]{8,}",
+                    3,
+                    false,
+                ),
+            ],
+            quoted: Regex::new(r#"["'`]([A-Za-z0-9+/=_\-]+)["'`]"#).unwrap(),
+        }
+    }
+
+    /// Findings in `content`, one per secret, in line order
+    pub fn scan(&self, content: &str) -> Vec<ReviewIssue> {
+        let mut issues = Vec::new();
+        let mut previous_allows = false;
+
+        for (index, line) in content.lines().enumerate() {
+            let allowed = line.contains(ALLOW_SECRET_MARKER);
+            if allowed || previous_allows {
+                previous_allows = allowed && is_comment_only(line);
+                continue;
+            }
+            previous_allows = false;
+
+            for secret in self.line_secrets(line) {
+                issues.push(finding(
+                    index + 1,
+                    secret.start + 1,
+                    secret.description,
+                    secret.rule,
+                    &redact(&line[secret.start..secret.end], secret.public_prefix),
+                ));
+            }
+        }
+
+        issues
+    }
+
+    /// `text` with every secret [`scan`](Self::scan) would report replaced
+    /// by its redacted form, for showing untrusted output such as a
+    /// model's tool results. Allow markers are ignored, and the body of a
+    /// private key block is redacted along with its header.
+    pub fn redact_text(&self, text: &str) -> String {
+        let mut out = String::with_capacity(text.len());
+        let mut in_private_key = false;
+
+        for line in text.split_inclusive('\n') {
+            let body = line.trim_end_matches(['\n', '\r']);
+            let ending = &line[body.len()..];
+            if in_private_key {
+                in_private_key = !body.contains("-----END");
+                if in_private_key {
+                    out.push_str(&redact(body, 0));
+                    out.push_str(ending);
+                    continue;
+                }
+            }
+
+            let mut secrets = self.line_secrets(body);
+            secrets.sort_by_key(|s| s.start);
+            let mut last = 0;
+            for secret in secrets {
+                in_private_key |= secret.rule == "private-key" && !body.contains("-----END");
+                out.push_str(&body[last..secret.start]);
+                out.push_str(&redact(
+                    &body[secret.start..secret.end],
+                    secret.public_prefix,
+                ));
+                last = secret.end;
+            }
+            out.push_str(&body[last..]);
+            out.push_str(ending);
+        }
+
+        out
+    }
+
+    /// Non-overlapping secrets on `line`: rule matches first, then
+    /// high-entropy quoted tokens
+    fn line_secrets(&self, line: &str) -> Vec<LineSecret> {
+        let mut secrets: Vec<LineSecret> = Vec::new();
+        let mut taken: Vec<(usize, usize)> = Vec::new();
+
+        for rule in &self.rules {
+            for captures in rule.pattern.captures_iter(line) {
+                let secret = captures.get(1).or_else(|| captures.get(0)).unwrap();
+                let span = (secret.start(), secret.end());
+                if overlaps(&taken, span)
+                    || (rule.skip_placeholders && is_placeholder(secret.as_str()))
+                {
+                    continue;
+                }
+                taken.push(span);
+                secrets.push(LineSecret {
+                    start: span.0,
+                    end: span.1,
+                    rule: rule.name,
+                    description: rule.description,
+                    public_prefix: rule.public_prefix,
+                });
+            }
+        }
+
+        for captures in self.quoted.captures_iter(line) {
+            let token = captures.get(1).unwrap();
+            let span = (token.start(), token.end());
+            if token.as_str().len() < MIN_ENTROPY_LEN
+                || overlaps(&taken, span)
+                || !looks_random(token.as_str())
+            {
+                continue;
+            }
+            taken.push(span);
+            secrets.push(LineSecret {
+                start: span.0,
+                end: span.1,
+                rule: "high-entropy",
+                description: "high-entropy string",
+                public_prefix: 0,
+            });
+        }
+
+        secrets
+    }
+}
+
+fn finding(
+    line: usize,
+    column: usize,
+    description: &str,
+    rule: &str,
+    redacted: &str,
+) -> ReviewIssue {
+    ReviewIssue {
+        severity: IssueSeverity::Critical,
+        description: format!(
+            "Possible {} ({}) at column {} [{}]. Move it to a secret store and rotate it, \
+             or add `{}` if it is not a secret.",
+            description, redacted, column, rule, ALLOW_SECRET_MARKER
+        ),
+        line: Some(line),
+        end_line: None,
+        suggestion: None,
+    }
+}
+
+/// Keep `public_prefix` characters and replace the rest
+pub fn redact(secret: &str, public_prefix: usize) -> String {
+    let total = secret.chars().count();
+    let shown: String = secret.chars().take(public_prefix.min(total)).collect();
+    format!(
+        "{}[REDACTED {} chars]",
+        shown,
+        total - shown.chars().count()
+    )
+}
+
+/// Shannon entropy in bits per character
+pub fn shannon_entropy(s: &str) -> f64 {
+    let mut counts = std::collections::HashMap::new();
+    for c in s.chars() {
+        *counts.entry(c).or_insert(0usize) += 1;
+    }
+    let len = s.chars().count() as f64;
+    counts
+        .values()
+        .map(|&n| {
+            let p = n as f64 / len;
+            -p * p.log2()
+        })
+        .sum()
+}
+
+/// Mixed letters and digits with high entropy; hex digests stay below the
+/// threshold
+fn looks_random(token: &str) -> bool {
+    let has_digit = token.chars().any(|c| c.is_ascii_digit());
+    let has_upper = token.chars().any(|c| c.is_ascii_uppercase());
+    let has_lower = token.chars().any(|c| c.is_ascii_lowercase());
+    has_digit
+        && has_upper
+        && has_lower
+        && !is_placeholder(token)
+        && shannon_entropy(token) >= ENTROPY_THRESHOLD
+}
+
+fn is_placeholder(value: &str) -> bool {
+    let lower = value.to_lowercase();
+    [
+        "example",
+        "your",
+        "xxxx",
+        "changeme",
+        "placeholder",
+        "dummy",
+        "redacted",
+    ]
+    .iter()
+    .any(|word| lower.contains(word))
+        || value.starts_with('$')
+        || value.starts_with('<')
+}
+
+fn is_comment_only(line: &str) -> bool {
+    let trimmed = line.trim_start();
+    ["//", "#", "--", "/*", "*", "<!--"]
+        .iter()
+        .any(|prefix| trimmed.starts_with(prefix))
+}
+
+fn overlaps(taken: &[(usize, usize)], (start, end): (usize, usize)) -> bool {
+    taken.iter().any(|&(s, e)| start < e && s < end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Fixtures are assembled at runtime so this file holds no literal
+    // credentials
+    fn aws_key() -> String {
+        format!("AKIA{}", "Q7ZP4M2KD9XW3HJT")
+    }
+
+    fn jwt() -> String {
+        [
+            "eyJhbGciOiJIUzI1NiJ9",
+            "eyJzdWIiOiIxMjM0NTY3ODkwIn0",
+            "dBjftJeZ4CVPmB92K27uhbUJU1p1r_wW1gFWFOEjXk", // rustassistant:allow-secret
+        ]
+        .join(".")
+    }
+
+    #[test]
+    fn test_detects_rules_and_redacts() {
+        let scanner = SecretScanner::new();
+        let xai = format!("xai-{}", "k3J9sQ2mX7vB4nR8tW1yZ6cF5hL0pD2aG9eU3iO7"); // rustassistant:allow-secret
+        let slack = format!("xoxb-{}", "2048-9937-Ab3dE6fGh9JkL2");
+        let header = format!("-----BEGIN {}PRIVATE KEY-----", "RSA ");
+        let generic = "Zq8vN3rT6yB1mK4wX9pL2sD7fH5jA0cE"; // rustassistant:allow-secret
+        let content = format!(
+            "let id = \"{}\";\n\
+             XAI_API_KEY={}\n\
+             slack = \"{}\"\n\
+             {}\n\
+             auth: Bearer {}\n\
+             const SIGNING: &str = \"{}\";\n\
+             let digest = \"{}\";\n",
+            aws_key(),
+            xai,
+            slack,
+            header,
+            jwt(),
+            generic,
+            "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08"
+        );
+
+        let issues = scanner.scan(&content);
+        let rules: Vec<(usize, bool)> = issues
+            .iter()
+            .map(|i| (i.line.unwrap(), i.severity == IssueSeverity::Critical))
+            .collect();
+        assert_eq!(
+            rules,
+            vec![
+                (1, true),
+                (2, true),
+                (3, true),
+                (4, true),
+                (5, true),
+                (6, true)
+            ]
+        );
+        assert!(issues[0].description.contains("AWS access key ID"));
+        assert!(issues[0].description.contains("AKIA[REDACTED 16 chars]"));
+        assert!(issues[1].description.contains("[xai-api-key]"));
+        assert!(issues[4].description.contains("[jwt]"));
+        assert!(issues[5].description.contains("[high-entropy]"));
+
+        // No message contains any part of a secret beyond its public prefix
+        for secret in [aws_key(), xai, slack, jwt(), generic.to_string()] {
+            for issue in &issues {
+                assert!(!issue.description.contains(&secret[5..]));
+            }
+        }
+    }
+
+    #[test]
+    fn test_allowlist_and_placeholders() {
+        let scanner = SecretScanner::new();
+        let content = format!(
+            "let fixture = \"{}\"; // {}\n\
+             # {}\n\
+             token = \"{}\"\n\
+             XAI_API_KEY=your-xai-api-key-goes-here\n\
+             XAI_API_KEY=${{XAI_API_KEY_FROM_VAULT}}\n\
+             let after = \"{}\";\n",
+            aws_key(),
+            ALLOW_SECRET_MARKER,
+            ALLOW_SECRET_MARKER,
+            jwt(),
+            aws_key()
+        );
+
+        let issues = scanner.scan(&content);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].line, Some(6));
+    }
+
+    #[test]
+    fn test_redact_text_masks_secrets_in_place() {
+        let scanner = SecretScanner::new();
+        let header = format!("-----BEGIN {}PRIVATE KEY-----", "");
+        let text = format!(
+            "found key {} in env\n{}\nMIIEvQIBADANBgkqhkiG9w0BAQEFAASC\n-----END PRIVATE KEY-----\n# {} ok\n",
+            aws_key(),
+            header,
+            ALLOW_SECRET_MARKER
+        );
+
+        let redacted = scanner.redact_text(&text);
+        assert_eq!(
+            redacted,
+            format!(
+                "found key AKIA[REDACTED 16 chars] in env\n[REDACTED {} chars]\n[REDACTED 32 chars]\n-----END PRIVATE KEY-----\n# {} ok\n",
+                header.len(),
+                ALLOW_SECRET_MARKER
+            )
+        );
+        assert_eq!(scanner.redact_text("no secrets here"), "no secrets here");
+    }
+
+    #[test]
+    fn test_entropy() {
+        assert_eq!(shannon_entropy("aaaa"), 0.0);
+        assert!((shannon_entropy("abcd") - 2.0).abs() < 1e-9);
+        assert!(!looks_random("ThisIsAPlainIdentifierName1"));
+        assert_eq!(redact("secret", 0), "[REDACTED 6 chars]");
+    }
+}
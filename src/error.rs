@@ -54,6 +54,10 @@ pub enum AuditError {
     #[error("Parse error in {file}: {message}")]
     Parse { file: PathBuf, message: String },
 
+    /// Audit request that failed validation
+    #[error("Invalid audit request: {0}")]
+    InvalidRequest(String),
+
     /// Tag validation error
     #[error("Invalid tag: {0}")]
     InvalidTag(String),
@@ -74,6 +78,10 @@ pub enum AuditError {
     #[error("Operation timed out: {0}")]
     Timeout(String),
 
+    /// LLM response that couldn't be parsed into the expected structure
+    #[error("Malformed LLM response: {message}")]
+    MalformedResponse { message: String, raw: String },
+
     /// Generic error with context
     #[error("{context}: {source}")]
     WithContext {
@@ -109,6 +117,23 @@ impl AuditError {
     pub fn other(msg: impl Into<String>) -> Self {
         AuditError::Other(msg.into())
     }
+
+    /// Create a malformed-response error, keeping the raw text for debugging
+    pub fn malformed_response(message: impl Into<String>, raw: impl Into<String>) -> Self {
+        AuditError::MalformedResponse {
+            message: message.into(),
+            raw: raw.into(),
+        }
+    }
+
+    /// Raw LLM response attached to this error, if any
+    pub fn raw_response(&self) -> Option<&str> {
+        match self {
+            AuditError::MalformedResponse { raw, .. } => Some(raw),
+            AuditError::WithContext { source, .. } => source.raw_response(),
+            _ => None,
+        }
+    }
 }
 
 /// Extension trait for adding context to Results
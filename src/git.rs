@@ -231,6 +231,91 @@ impl GitManager {
         info!("Repository updated successfully");
         Ok(())
     }
+
+    /// Fetch the current branch from origin and fast-forward to it.
+    ///
+    /// Fails if the local branch has diverged from origin rather than
+    /// merging; the checkout is expected to be a read-only mirror.
+    pub fn pull(&self, repo_path: &Path) -> Result<PullResult> {
+        let repo = self.open(repo_path)?;
+        let branch = self.current_branch(repo_path)?;
+        let previous_head = repo
+            .head()
+            .ok()
+            .and_then(|head| head.target())
+            .map(|oid| oid.to_string());
+
+        info!("Pulling {} in {}", branch, repo_path.display());
+
+        let mut remote = repo
+            .find_remote("origin")
+            .map_err(|e| AuditError::other(format!("Failed to find remote 'origin': {}", e)))?;
+        let refspec = format!("+refs/heads/{0}:refs/remotes/origin/{0}", branch);
+        remote
+            .fetch(&[&refspec], None, None)
+            .map_err(|e| AuditError::other(format!("Failed to fetch from origin: {}", e)))?;
+
+        let tracking = repo
+            .find_reference(&format!("refs/remotes/origin/{}", branch))
+            .map_err(|e| AuditError::other(format!("Failed to find origin/{}: {}", branch, e)))?;
+        let upstream = repo.reference_to_annotated_commit(&tracking).map_err(|e| {
+            AuditError::other(format!("Failed to resolve origin/{}: {}", branch, e))
+        })?;
+        let head = upstream.id().to_string();
+
+        let (analysis, _) = repo
+            .merge_analysis(&[&upstream])
+            .map_err(|e| AuditError::other(format!("Failed to compare with origin: {}", e)))?;
+
+        if analysis.is_up_to_date() {
+            // The local branch may be ahead of origin; HEAD stays where it is
+            return Ok(PullResult {
+                head: previous_head.clone().unwrap_or(head),
+                previous_head,
+            });
+        }
+        if !analysis.is_fast_forward() {
+            return Err(AuditError::other(format!(
+                "{} has diverged from origin/{}; cannot fast-forward",
+                branch, branch
+            )));
+        }
+
+        let refname = format!("refs/heads/{}", branch);
+        repo.find_reference(&refname)
+            .and_then(|mut reference| reference.set_target(upstream.id(), "pull: fast-forward"))
+            .map_err(|e| AuditError::other(format!("Failed to fast-forward {}: {}", branch, e)))?;
+        repo.set_head(&refname)
+            .map_err(|e| AuditError::other(format!("Failed to set HEAD: {}", e)))?;
+        repo.checkout_head(Some(git2::build::CheckoutBuilder::default().force()))
+            .map_err(|e| AuditError::other(format!("Failed to check out {}: {}", branch, e)))?;
+
+        info!(
+            "Fast-forwarded {} to {}",
+            branch,
+            &head[..head.len().min(12)]
+        );
+        Ok(PullResult {
+            previous_head,
+            head,
+        })
+    }
+}
+
+/// Outcome of [`GitManager::pull`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PullResult {
+    /// HEAD commit before pulling (`None` for an empty repository)
+    pub previous_head: Option<String>,
+    /// HEAD commit after pulling
+    pub head: String,
+}
+
+impl PullResult {
+    /// Whether the pull moved HEAD
+    pub fn changed(&self) -> bool {
+        self.previous_head.as_deref() != Some(self.head.as_str())
+    }
 }
 
 /// Repository statistics
@@ -283,4 +368,43 @@ mod tests {
         // Now it is a repo
         assert!(manager.is_repository(temp.path()));
     }
+
+    fn commit_file(repo: &Repository, name: &str, content: &str) {
+        let workdir = repo.workdir().unwrap();
+        std::fs::write(workdir.join(name), content).unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new(name)).unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let sig = git2::Signature::now("test", "test@example.com").unwrap();
+        let parent = repo.head().ok().and_then(|h| h.peel_to_commit().ok());
+        let parents: Vec<&git2::Commit> = parent.iter().collect();
+        repo.commit(Some("HEAD"), &sig, &sig, name, &tree, &parents)
+            .unwrap();
+    }
+
+    #[test]
+    fn test_pull_fast_forwards_and_reports_no_change() {
+        let temp = TempDir::new().unwrap();
+        let upstream = Repository::init(temp.path().join("upstream")).unwrap();
+        commit_file(&upstream, "a.txt", "one");
+
+        let manager = GitManager::new(temp.path().join("workspace"), false).unwrap();
+        let url = temp.path().join("upstream").display().to_string();
+        let clone = manager.clone_repo(&url, Some("mirror")).unwrap();
+
+        // Nothing new upstream: HEAD stays put
+        let result = manager.pull(&clone).unwrap();
+        assert!(!result.changed());
+        assert_eq!(result.previous_head.as_deref(), Some(result.head.as_str()));
+
+        commit_file(&upstream, "b.txt", "two");
+        let result = manager.pull(&clone).unwrap();
+        assert!(result.changed());
+        assert_eq!(
+            result.head,
+            upstream.head().unwrap().target().unwrap().to_string()
+        );
+        assert!(clone.join("b.txt").exists());
+    }
 }
@@ -9,6 +9,7 @@
 //! - File scoring and analysis
 //! - Retry logic with exponential backoff
 //! - Response caching support
+//! - Multi-turn conversations with token-budgeted history
 //!
 //! ## Usage
 //!
@@ -53,6 +54,12 @@ const MAX_RETRIES: usize = 3;
 /// Initial retry delay in milliseconds
 const INITIAL_RETRY_DELAY_MS: u64 = 1000;
 
+/// Default token budget for the history sent with each conversation turn
+pub const DEFAULT_CONVERSATION_TOKENS: usize = 64_000;
+
+/// Per-message overhead (role and separators) in the chat format
+const MESSAGE_OVERHEAD_TOKENS: usize = 4;
+
 /// Grok API client with cost tracking and caching
 pub struct GrokClient {
     /// HTTP client
@@ -79,7 +86,7 @@ struct ChatCompletionRequest {
 }
 
 /// Chat message
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct Message {
     role: String,
     content: String,
@@ -120,6 +127,132 @@ pub struct AskResponse {
     pub cost_usd: f64,
 }
 
+/// One message in a [`Conversation`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConversationMessage {
+    /// "user" or "assistant"
+    pub role: String,
+    pub content: String,
+    /// Tokens billed for the request that produced this reply
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub total_tokens: Option<i64>,
+    /// Cost of the request that produced this reply
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cost_usd: Option<f64>,
+}
+
+impl ConversationMessage {
+    pub fn user(content: impl Into<String>) -> Self {
+        Self {
+            role: "user".to_string(),
+            content: content.into(),
+            total_tokens: None,
+            cost_usd: None,
+        }
+    }
+
+    pub fn assistant(content: impl Into<String>) -> Self {
+        Self {
+            role: "assistant".to_string(),
+            ..Self::user(content)
+        }
+    }
+
+    fn tokens(&self) -> usize {
+        crate::tokenizer::count_tokens(&self.content, GROK_MODEL) + MESSAGE_OVERHEAD_TOKENS
+    }
+}
+
+/// A multi-turn conversation whose history is sent with every question.
+///
+/// When the history grows past `max_context_tokens`, the oldest
+/// question/answer pairs are dropped; the newest question is always kept.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Conversation {
+    pub id: String,
+    pub messages: Vec<ConversationMessage>,
+    pub max_context_tokens: usize,
+    /// Messages dropped from the start of the history so far
+    #[serde(default)]
+    pub trimmed_messages: usize,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+impl Default for Conversation {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Conversation {
+    /// Start a conversation with a fresh id
+    pub fn new() -> Self {
+        Self::with_id(uuid::Uuid::new_v4().to_string())
+    }
+
+    /// Start a conversation with the given id
+    pub fn with_id(id: impl Into<String>) -> Self {
+        let now = chrono::Utc::now().timestamp();
+        Self {
+            id: id.into(),
+            messages: Vec::new(),
+            max_context_tokens: DEFAULT_CONVERSATION_TOKENS,
+            trimmed_messages: 0,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// Set the token budget for the history
+    pub fn with_max_context_tokens(mut self, max_tokens: usize) -> Self {
+        self.max_context_tokens = max_tokens;
+        self
+    }
+
+    /// Tokens the current history takes up
+    pub fn context_tokens(&self) -> usize {
+        self.messages.iter().map(ConversationMessage::tokens).sum()
+    }
+
+    /// Number of questions asked so far
+    pub fn turns(&self) -> usize {
+        self.messages.iter().filter(|m| m.role == "user").count()
+    }
+
+    /// Total cost of every reply in the conversation
+    pub fn total_cost(&self) -> f64 {
+        self.messages.iter().filter_map(|m| m.cost_usd).sum()
+    }
+
+    /// Drop the oldest messages until the history fits the budget, keeping
+    /// the history starting with a question. Returns how many were dropped.
+    pub fn trim_to_budget(&mut self) -> usize {
+        let mut tokens = self.context_tokens();
+        let mut dropped = 0;
+        while tokens > self.max_context_tokens && self.messages.len() > 1 {
+            tokens -= self.messages.remove(0).tokens();
+            dropped += 1;
+            if self.messages.len() > 1 && self.messages[0].role != "user" {
+                tokens -= self.messages.remove(0).tokens();
+                dropped += 1;
+            }
+        }
+        self.trimmed_messages += dropped;
+        dropped
+    }
+
+    fn api_messages(&self) -> Vec<Message> {
+        self.messages
+            .iter()
+            .map(|m| Message {
+                role: m.role.clone(),
+                content: m.content.clone(),
+            })
+            .collect()
+    }
+}
+
 /// File scoring result
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FileScoreResult {
@@ -136,11 +269,75 @@ pub struct FileScoreResult {
     /// Summary of findings
     pub summary: String,
     /// Specific issues found
-    pub issues: Vec<String>,
+    pub issues: Vec<ScoredIssue>,
     /// Suggestions for improvement
     pub suggestions: Vec<String>,
 }
 
+/// One issue in a [`FileScoreResult`]
+///
+/// The model may answer with a bare string, or with an object that also
+/// gives the lines involved and a drop-in replacement for them.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(from = "ScoredIssueRepr")]
+pub struct ScoredIssue {
+    pub description: String,
+    /// First line of the issue (1-based)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+    /// Last line of a multi-line issue (inclusive)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_line: Option<usize>,
+    /// Replacement text for `line..=end_line`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suggestion: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ScoredIssueRepr {
+    Text(String),
+    Located {
+        description: String,
+        #[serde(default)]
+        line: Option<usize>,
+        #[serde(default)]
+        end_line: Option<usize>,
+        #[serde(default)]
+        suggestion: Option<String>,
+    },
+}
+
+impl From<ScoredIssueRepr> for ScoredIssue {
+    fn from(repr: ScoredIssueRepr) -> Self {
+        match repr {
+            ScoredIssueRepr::Text(description) => description.into(),
+            ScoredIssueRepr::Located {
+                description,
+                line,
+                end_line,
+                suggestion,
+            } => Self {
+                description,
+                line,
+                end_line,
+                suggestion,
+            },
+        }
+    }
+}
+
+impl From<String> for ScoredIssue {
+    fn from(description: String) -> Self {
+        Self {
+            description,
+            line: None,
+            end_line: None,
+            suggestion: None,
+        }
+    }
+}
+
 impl Default for FileScoreResult {
     fn default() -> Self {
         Self {
@@ -274,10 +471,14 @@ impl GrokClient {
   "complexity_score": 0-100,
   "maintainability_score": 0-100,
   "summary": "brief summary",
-  "issues": ["issue1", "issue2"],
+  "issues": [
+    {{"description": "issue", "line": 12, "end_line": 14, "suggestion": "replacement for lines 12-14, only if the fix is mechanical"}}
+  ],
   "suggestions": ["suggestion1", "suggestion2"]
 }}
 
+Give "line" and "end_line" (1-based, inclusive) when an issue is tied to specific lines; leave out "suggestion" unless the replacement can be applied as-is.
+
 File: {}
 Content:
 ```
@@ -403,6 +604,122 @@ Code:
         })
     }
 
+    /// Ask the next question in a conversation.
+    ///
+    /// The question is appended to the history, the history is trimmed to
+    /// the conversation's token budget, and the whole history is sent. The
+    /// reply is appended with its token count and cost. If the request
+    /// fails, the question is removed again so it can be retried.
+    pub async fn continue_conversation(
+        &self,
+        conversation: &mut Conversation,
+        question: &str,
+    ) -> Result<AskResponse> {
+        conversation
+            .messages
+            .push(ConversationMessage::user(question));
+        let dropped = conversation.trim_to_budget();
+        if dropped > 0 {
+            info!(
+                "Conversation {}: dropped {} oldest messages to fit {} tokens",
+                conversation.id, dropped, conversation.max_context_tokens
+            );
+        }
+
+        let response = match self
+            .call_api_messages(conversation.api_messages(), "conversation", None)
+            .await
+        {
+            Ok(response) => response,
+            Err(e) => {
+                conversation.messages.pop();
+                return Err(e.context("Failed to continue conversation"));
+            }
+        };
+
+        let cost = self.calculate_cost(&response.usage);
+        info!(
+            "Conversation {} turn {}: {} tokens, ${:.4} (conversation total ${:.4})",
+            conversation.id,
+            conversation.turns(),
+            response.usage.total_tokens,
+            cost,
+            conversation.total_cost() + cost
+        );
+
+        conversation.messages.push(ConversationMessage {
+            total_tokens: Some(response.usage.total_tokens),
+            cost_usd: Some(cost),
+            ..ConversationMessage::assistant(response.content.clone())
+        });
+        conversation.updated_at = chrono::Utc::now().timestamp();
+
+        Ok(AskResponse {
+            content: response.content,
+            total_tokens: response.usage.total_tokens,
+            prompt_tokens: response.usage.prompt_tokens,
+            completion_tokens: response.usage.completion_tokens,
+            cost_usd: cost,
+        })
+    }
+
+    /// Save a conversation, replacing any earlier version with the same id
+    pub async fn save_conversation(&self, conversation: &Conversation) -> Result<()> {
+        let messages = serde_json::to_string(&conversation.messages)
+            .context("Failed to serialize conversation")?;
+        sqlx::query(
+            r#"
+            INSERT INTO conversations
+                (id, model, messages, max_context_tokens, trimmed_messages, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            ON CONFLICT (id) DO UPDATE SET
+                model = EXCLUDED.model,
+                messages = EXCLUDED.messages,
+                max_context_tokens = EXCLUDED.max_context_tokens,
+                trimmed_messages = EXCLUDED.trimmed_messages,
+                updated_at = EXCLUDED.updated_at
+            "#,
+        )
+        .bind(&conversation.id)
+        .bind(&self.model)
+        .bind(messages)
+        .bind(conversation.max_context_tokens as i64)
+        .bind(conversation.trimmed_messages as i64)
+        .bind(conversation.created_at)
+        .bind(conversation.updated_at)
+        .execute(self.db.pool())
+        .await
+        .context("Failed to save conversation")?;
+        Ok(())
+    }
+
+    /// Load a saved conversation by id
+    pub async fn load_conversation(&self, id: &str) -> Result<Option<Conversation>> {
+        let row: Option<(String, i64, i64, i64, i64)> = sqlx::query_as(
+            "SELECT messages, max_context_tokens, trimmed_messages, created_at, updated_at
+             FROM conversations WHERE id = $1",
+        )
+        .bind(id)
+        .fetch_optional(self.db.pool())
+        .await
+        .context("Failed to load conversation")?;
+
+        row.map(
+            |(messages, max_context_tokens, trimmed_messages, created_at, updated_at)| {
+                Ok(Conversation {
+                    id: id.to_string(),
+                    messages: serde_json::from_str(&messages)
+                        .context("Failed to parse saved conversation")?,
+                    max_context_tokens: max_context_tokens as usize,
+                    trimmed_messages: trimmed_messages as usize,
+                    created_at,
+                    updated_at,
+                })
+            },
+        )
+        .transpose()
+    }
+
     /// Ask a question with full repository context
     pub async fn ask_with_context(
         &self,
@@ -490,6 +807,21 @@ Code:
         prompt: &str,
         operation: &str,
         repository_id: Option<i64>,
+    ) -> Result<ApiResponse> {
+        let messages = vec![Message {
+            role: "user".to_string(),
+            content: prompt.to_string(),
+        }];
+        self.call_api_messages(messages, operation, repository_id)
+            .await
+    }
+
+    /// Call Grok API with a full message history, with retry logic
+    async fn call_api_messages(
+        &self,
+        messages: Vec<Message>,
+        operation: &str,
+        repository_id: Option<i64>,
     ) -> Result<ApiResponse> {
         let mut last_error = None;
 
@@ -506,7 +838,7 @@ Code:
                 tokio::time::sleep(delay).await;
             }
 
-            match self.call_api_once(prompt).await {
+            match self.call_api_once(&messages).await {
                 Ok(response) => {
                     // Calculate cost
                     let cost = self.calculate_cost(&response.usage);
@@ -546,7 +878,7 @@ Code:
     }
 
     /// Make a single API call
-    async fn call_api_once(&self, prompt: &str) -> Result<ApiResponse> {
+    async fn call_api_once(&self, messages: &[Message]) -> Result<ApiResponse> {
         let max_tokens = std::env::var("XAI_MAX_TOKENS")
             .ok()
             .and_then(|v| v.parse::<usize>().ok())
@@ -554,17 +886,15 @@ Code:
 
         let request = ChatCompletionRequest {
             model: self.model.clone(),
-            messages: vec![Message {
-                role: "user".to_string(),
-                content: prompt.to_string(),
-            }],
+            messages: messages.to_vec(),
             temperature: 0.3,
             max_tokens,
         };
 
         debug!(
-            "Calling Grok API with prompt length: {} chars",
-            prompt.len()
+            "Calling Grok API with {} messages, prompt length: {} chars",
+            messages.len(),
+            messages.iter().map(|m| m.content.len()).sum::<usize>()
         );
 
         let response = self
@@ -698,4 +1028,48 @@ mod tests {
         // 1000 * $0.20/1M + 500 * $0.50/1M = $0.0002 + $0.00025 = $0.00045
         assert!((cost - 0.00045).abs() < 0.00001);
     }
+
+    #[test]
+    fn test_conversation_trims_oldest_turns() {
+        let mut conversation = Conversation::with_id("test");
+        for i in 0..5 {
+            conversation
+                .messages
+                .push(ConversationMessage::user(format!(
+                    "question {} {}",
+                    i,
+                    "word ".repeat(40)
+                )));
+            conversation.messages.push(ConversationMessage {
+                cost_usd: Some(0.001),
+                ..ConversationMessage::assistant(format!("answer {} {}", i, "word ".repeat(40)))
+            });
+        }
+        conversation
+            .messages
+            .push(ConversationMessage::user("latest question"));
+
+        // Within budget: nothing is dropped
+        assert_eq!(conversation.trim_to_budget(), 0);
+        assert_eq!(conversation.turns(), 6);
+
+        // Budget for roughly two turns: oldest pairs go first
+        let pair_tokens = conversation.messages[0].tokens() + conversation.messages[1].tokens();
+        conversation.max_context_tokens = pair_tokens * 2 + 20;
+        assert_eq!(conversation.trim_to_budget(), 6);
+        assert!(conversation.context_tokens() <= conversation.max_context_tokens);
+        assert_eq!(conversation.trimmed_messages, 6);
+        assert_eq!(conversation.messages[0].role, "user");
+        assert!(conversation.messages[0].content.starts_with("question 3"));
+        assert!((conversation.total_cost() - 0.002).abs() < 1e-9);
+
+        // The newest question survives even when it alone is over budget
+        conversation.max_context_tokens = 1;
+        conversation.trim_to_budget();
+        assert_eq!(
+            conversation.messages,
+            vec![ConversationMessage::user("latest question")]
+        );
+        assert_eq!(conversation.trimmed_messages, 10);
+    }
 }
@@ -9,15 +9,20 @@
 //! - Retry logic with exponential backoff
 
 use crate::cache::{AuditCache, CacheEntry};
+use crate::enhanced_scanner::SecretScanner;
 use crate::error::{AuditError, Result};
 use crate::llm_config::LimitsConfig;
 use crate::scoring::FileScore;
 use crate::tree_state::FileCategory;
+use once_cell::sync::Lazy;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
 use std::time::Duration;
-use tokio::time::sleep;
+use tokio::sync::Semaphore;
+use tokio::time::{sleep, sleep_until, Instant};
 use tracing::{debug, error, info, warn};
 
 /// Default Grok 4.1 model for reasoning tasks
@@ -40,6 +45,20 @@ pub const SMALL_FILE_LOC: usize = 100;
 pub const MEDIUM_FILE_LOC: usize = 500;
 pub const LARGE_FILE_LOC: usize = 1000;
 
+/// Input budget for [`GrokReasoningClient::synthesize_summary`]; files past
+/// it are left out of the summary request, worst-scoring files first in
+/// line
+pub const SUMMARY_INPUT_TOKENS: usize = 32_000;
+
+/// Priorities kept in a [`RunSummary`]
+pub const SUMMARY_MAX_PRIORITIES: usize = 5;
+
+/// Issues listed per file in the summary request
+const SUMMARY_ISSUES_PER_FILE: usize = 3;
+
+/// Redacts secrets from captured tool input and output
+static SECRET_SCANNER: Lazy<SecretScanner> = Lazy::new(SecretScanner::new);
+
 /// Retry configuration for API calls
 #[derive(Debug, Clone)]
 pub struct RetryConfig {
@@ -121,6 +140,13 @@ pub struct GrokReasoningClient {
 
     /// Retry configuration
     retry_config: RetryConfig,
+
+    /// Batches [`analyze_all_batches`] sends at once
+    concurrency: usize,
+
+    /// Set when a request is rate limited; concurrent requests hold off
+    /// until then instead of piling more 429s onto the API
+    rate_limited_until: Mutex<Option<Instant>>,
 }
 
 /// Batch of files for analysis
@@ -221,6 +247,43 @@ pub struct FileAnalysisResult {
     /// Tokens used (populated by client, not LLM response)
     #[serde(default)]
     pub tokens_used: TokenUsage,
+
+    /// Why the analysis stopped early, if it did; scores and issues may
+    /// then be missing or incomplete
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub truncated_reason: Option<TruncatedReason>,
+}
+
+/// Why a file's analysis is incomplete
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TruncatedReason {
+    /// The request used all of its agentic turns (see
+    /// [`GrokReasoningClient::set_max_turns`]) before the model answered
+    MaxTurnsReached,
+}
+
+impl FileAnalysisResult {
+    /// Neutral-score result for a file the model gave no analysis for
+    fn placeholder(path: &str, summary: String) -> Self {
+        Self {
+            path: path.to_string(),
+            overall_score: default_score(),
+            security_score: default_score(),
+            quality_score: default_score(),
+            complexity_score: default_score(),
+            maintainability_score: default_score(),
+            summary,
+            issues: vec![],
+            improvements: vec![],
+            patterns: vec![],
+            dependencies: vec![],
+            test_coverage: None,
+            reasoning_trace: None,
+            tokens_used: TokenUsage::default(),
+            truncated_reason: None,
+        }
+    }
 }
 
 fn default_score() -> f64 {
@@ -339,6 +402,349 @@ pub struct BatchAnalysisResult {
 
     /// Number of tool calls made
     pub tool_calls_count: usize,
+
+    /// Server-side tool calls (e.g. code execution) the model made
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tool_invocations: Vec<ToolInvocation>,
+
+    /// Whether this result was served from the audit cache
+    #[serde(default)]
+    pub cache_hit: bool,
+}
+
+/// A server-side tool call made by the model during an agentic turn, with
+/// anything that looks like a secret redacted from its input and output
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ToolInvocation {
+    /// Tool name, e.g. `code_interpreter`
+    pub tool: String,
+
+    /// Code or arguments the model passed
+    #[serde(default)]
+    pub input: String,
+
+    /// What the tool returned: stdout, logs or result
+    #[serde(default)]
+    pub output: String,
+
+    /// Call status reported by the API
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+}
+
+/// "Tool Executions" section listing every tool call in `results`, or an
+/// empty string when the model made none
+pub fn format_tool_executions(results: &[BatchAnalysisResult]) -> String {
+    let mut out = String::new();
+    for result in results {
+        for (i, call) in result.tool_invocations.iter().enumerate() {
+            if out.is_empty() {
+                out.push_str("## Tool Executions\n");
+            }
+            out.push_str(&format!(
+                "\n### Batch {} · call {}: {}{}\n",
+                result.batch_id,
+                i + 1,
+                call.tool,
+                call.status
+                    .as_deref()
+                    .map(|s| format!(" ({})", s))
+                    .unwrap_or_default()
+            ));
+            out.push_str(&format!("\nInput:\n```\n{}\n```\n", call.input.trim_end()));
+            out.push_str(&format!(
+                "\nOutput:\n```\n{}\n```\n",
+                call.output.trim_end()
+            ));
+        }
+    }
+    out
+}
+
+/// Totals across a run of [`analyze_all_batches`]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BatchRunSummary {
+    /// Batches completed
+    pub batches: usize,
+
+    /// Batches served from the cache
+    pub cache_hits: usize,
+
+    /// Tokens spent on API calls
+    pub tokens_used: usize,
+
+    /// Tokens the cached batches originally cost
+    pub tokens_saved: usize,
+
+    /// Estimated USD saved by cache hits
+    pub estimated_savings: f64,
+
+    /// Files whose analysis stopped early (see [`truncated_paths`])
+    #[serde(default)]
+    pub truncated_files: usize,
+}
+
+impl BatchRunSummary {
+    /// Summarize a set of batch results
+    pub fn from_results(results: &[BatchAnalysisResult]) -> Self {
+        let pricing = crate::token_budget::TokenPricing::grok();
+        let mut summary = Self {
+            batches: results.len(),
+            ..Default::default()
+        };
+        for result in results {
+            summary.truncated_files += result
+                .file_results
+                .iter()
+                .filter(|r| r.truncated_reason.is_some())
+                .count();
+            if result.cache_hit {
+                summary.cache_hits += 1;
+                summary.tokens_saved += result.total_tokens.total_tokens;
+            } else {
+                summary.tokens_used += result.total_tokens.total_tokens;
+            }
+        }
+        summary.estimated_savings = pricing.estimate_cost(summary.tokens_saved);
+        summary
+    }
+
+    /// Cache hit rate as a percentage
+    pub fn hit_rate(&self) -> f64 {
+        if self.batches == 0 {
+            0.0
+        } else {
+            self.cache_hits as f64 / self.batches as f64 * 100.0
+        }
+    }
+}
+
+impl std::fmt::Display for BatchRunSummary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} batches, {} cached ({:.1}% hit rate), {} tokens used, {} saved (~${:.4})",
+            self.batches,
+            self.cache_hits,
+            self.hit_rate(),
+            self.tokens_used,
+            self.tokens_saved,
+            self.estimated_savings
+        )?;
+        if self.truncated_files > 0 {
+            write!(f, ", {} files truncated at max_turns", self.truncated_files)?;
+        }
+        Ok(())
+    }
+}
+
+/// Paths of files whose analysis stopped early, to re-run them with more
+/// turns (see [`retain_truncated`])
+pub fn truncated_paths(results: &[BatchAnalysisResult]) -> Vec<String> {
+    results
+        .iter()
+        .flat_map(|r| &r.file_results)
+        .filter(|r| r.truncated_reason.is_some())
+        .map(|r| r.path.clone())
+        .collect()
+}
+
+/// Narrow `batches` to the files whose analysis was truncated in
+/// `results`, dropping batches left empty. Raise the limit with
+/// [`GrokReasoningClient::set_max_turns`] and pass these to
+/// [`analyze_all_batches`] to finish them.
+pub fn retain_truncated(
+    batches: Vec<FileBatch>,
+    results: &[BatchAnalysisResult],
+) -> Vec<FileBatch> {
+    let truncated: std::collections::HashSet<String> =
+        truncated_paths(results).into_iter().collect();
+    batches
+        .into_iter()
+        .filter_map(|mut batch| {
+            batch.files.retain(|f| truncated.contains(&f.path));
+            if batch.files.is_empty() {
+                return None;
+            }
+            batch.estimated_tokens = batch
+                .files
+                .iter()
+                .map(|f| GrokReasoningClient::estimate_tokens(&f.content))
+                .sum();
+            Some(batch)
+        })
+        .collect()
+}
+
+/// Cross-cutting summary of a run, from
+/// [`GrokReasoningClient::synthesize_summary`]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RunSummary {
+    /// A few sentences on the overall state of the code
+    #[serde(default)]
+    pub overview: String,
+
+    /// Themes that run across many files
+    #[serde(default)]
+    pub themes: Vec<String>,
+
+    /// Problems rooted in the codebase's structure rather than one file
+    #[serde(default)]
+    pub systemic_problems: Vec<String>,
+
+    /// What to fix first, most important first
+    #[serde(default)]
+    pub top_priorities: Vec<String>,
+
+    /// Files whose results were sent to the model
+    #[serde(default)]
+    pub files_considered: usize,
+
+    /// Files left out to stay within [`SUMMARY_INPUT_TOKENS`]
+    #[serde(default)]
+    pub files_omitted: usize,
+
+    /// Tokens spent on the summary call
+    #[serde(default)]
+    pub tokens_used: TokenUsage,
+
+    /// Estimated USD cost of the summary call
+    #[serde(default)]
+    pub cost_usd: f64,
+}
+
+impl std::fmt::Display for RunSummary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{}", self.overview)?;
+        for (title, items) in [
+            ("Themes", &self.themes),
+            ("Systemic problems", &self.systemic_problems),
+        ] {
+            if !items.is_empty() {
+                writeln!(f, "\n{}:", title)?;
+                for item in items {
+                    writeln!(f, "  - {}", item)?;
+                }
+            }
+        }
+        if !self.top_priorities.is_empty() {
+            writeln!(f, "\nTop priorities:")?;
+            for (i, item) in self.top_priorities.iter().enumerate() {
+                writeln!(f, "  {}. {}", i + 1, item)?;
+            }
+        }
+        write!(
+            f,
+            "\n({} files summarized{}, {} tokens, ~${:.4})",
+            self.files_considered,
+            if self.files_omitted > 0 {
+                format!(", {} omitted", self.files_omitted)
+            } else {
+                String::new()
+            },
+            self.tokens_used.total_tokens,
+            self.cost_usd
+        )
+    }
+}
+
+/// Model response for a run summary
+#[derive(Debug, Deserialize)]
+struct RunSummaryResponse {
+    #[serde(default)]
+    overview: String,
+    #[serde(default)]
+    themes: Vec<String>,
+    #[serde(default)]
+    systemic_problems: Vec<String>,
+    #[serde(default)]
+    top_priorities: Vec<String>,
+}
+
+/// Per-file scores and top issues of a run, condensed for the summary
+/// request
+struct RunDigest {
+    text: String,
+    files_considered: usize,
+    files_omitted: usize,
+}
+
+impl RunDigest {
+    /// Digest of `results` within about `max_tokens`. Run totals come
+    /// first, then files from lowest to highest score until the budget
+    /// runs out.
+    fn new(results: &[BatchAnalysisResult], max_tokens: usize) -> Self {
+        let mut files: Vec<&FileAnalysisResult> =
+            results.iter().flat_map(|r| &r.file_results).collect();
+        files.sort_by(|a, b| {
+            a.overall_score
+                .total_cmp(&b.overall_score)
+                .then_with(|| a.path.cmp(&b.path))
+        });
+
+        let issues = files.iter().flat_map(|f| &f.issues);
+        let count = |severity: &str| issues.clone().filter(|i| i.severity == severity).count();
+        let average = if files.is_empty() {
+            0.0
+        } else {
+            files.iter().map(|f| f.overall_score).sum::<f64>() / files.len() as f64
+        };
+        let mut text = format!(
+            "Run totals: {} files, average score {:.1}, issues: {} critical, {} high, {} medium, {} low\n\n",
+            files.len(),
+            average,
+            count("critical"),
+            count("high"),
+            count("medium"),
+            count("low")
+        );
+
+        let mut files_considered = 0;
+        for file in &files {
+            let mut entry = format!(
+                "{} (overall {:.0}, security {:.0}, quality {:.0}): {}\n",
+                file.path,
+                file.overall_score,
+                file.security_score,
+                file.quality_score,
+                file.summary.lines().next().unwrap_or_default()
+            );
+            let mut top: Vec<&IdentifiedIssue> = file.issues.iter().collect();
+            top.sort_by_key(|i| severity_rank(&i.severity));
+            for issue in top.into_iter().take(SUMMARY_ISSUES_PER_FILE) {
+                entry.push_str(&format!(
+                    "  - [{}] {}: {}\n",
+                    issue.severity, issue.category, issue.description
+                ));
+            }
+
+            if GrokReasoningClient::estimate_tokens(&text)
+                + GrokReasoningClient::estimate_tokens(&entry)
+                > max_tokens
+            {
+                break;
+            }
+            text.push_str(&entry);
+            files_considered += 1;
+        }
+
+        Self {
+            text,
+            files_considered,
+            files_omitted: files.len() - files_considered,
+        }
+    }
+}
+
+/// Sort rank of an issue severity, most severe first
+fn severity_rank(severity: &str) -> usize {
+    match severity {
+        "critical" => 0,
+        "high" => 1,
+        "medium" => 2,
+        "low" => 3,
+        _ => 4,
+    }
 }
 
 /// Request for xAI Responses API
@@ -377,9 +783,36 @@ struct ResponsesResponse {
     output: Vec<OutputItem>,
     #[serde(default)]
     usage: Option<UsageInfo>,
-    #[allow(dead_code)]
     #[serde(default)]
     status: Option<String>,
+    #[serde(default)]
+    incomplete_details: Option<IncompleteDetails>,
+}
+
+/// Why a response is `incomplete`
+#[derive(Debug, Deserialize)]
+struct IncompleteDetails {
+    #[serde(default)]
+    reason: Option<String>,
+}
+
+impl ResponsesResponse {
+    /// Whether the request ran out of agentic turns: the API says so, or
+    /// the output ends on a tool call the model never got to follow up
+    fn turn_limit_reached(&self) -> bool {
+        let incomplete_for_turns = self.status.as_deref() == Some("incomplete")
+            && self
+                .incomplete_details
+                .as_ref()
+                .and_then(|d| d.reason.as_deref())
+                .is_some_and(|reason| reason.contains("turn"));
+        incomplete_for_turns
+            || self.output.last().is_some_and(|item| {
+                item.output_type
+                    .as_deref()
+                    .is_some_and(|t| t.ends_with("_call"))
+            })
+    }
 }
 
 /// Output item in response - this is a message object
@@ -394,6 +827,192 @@ struct OutputItem {
     text: Option<String>,
     #[serde(default)]
     role: Option<String>,
+    /// Tool calls (`*_call` items): tool name, input and result
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    code: Option<String>,
+    #[serde(default)]
+    arguments: Option<serde_json::Value>,
+    #[serde(default)]
+    input: Option<serde_json::Value>,
+    #[serde(default)]
+    outputs: Option<serde_json::Value>,
+    #[serde(default)]
+    output: Option<serde_json::Value>,
+    #[serde(default)]
+    result: Option<serde_json::Value>,
+    #[serde(default)]
+    status: Option<String>,
+}
+
+impl OutputItem {
+    /// The tool call this item records, if it is one
+    fn tool_invocation(&self) -> Option<ToolInvocation> {
+        let output_type = self.output_type.as_deref()?;
+        let tool = output_type.strip_suffix("_call")?;
+        let input = self
+            .code
+            .clone()
+            .or_else(|| self.arguments.as_ref().map(value_text))
+            .or_else(|| self.input.as_ref().map(value_text))
+            .unwrap_or_default();
+        let output = [&self.outputs, &self.output, &self.result]
+            .into_iter()
+            .flatten()
+            .map(value_text)
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        Some(ToolInvocation {
+            tool: self.name.clone().unwrap_or_else(|| tool.to_string()),
+            input: SECRET_SCANNER.redact_text(&input),
+            output: SECRET_SCANNER.redact_text(&output),
+            status: self.status.clone(),
+        })
+    }
+}
+
+/// Readable text of a tool input or output: strings as they are, the text
+/// fields of output objects (`logs`, `stdout`, …), anything else as JSON
+fn value_text(value: &serde_json::Value) -> String {
+    use serde_json::Value;
+
+    match value {
+        Value::Null => String::new(),
+        Value::String(s) => s.clone(),
+        Value::Array(items) => items
+            .iter()
+            .map(value_text)
+            .filter(|s| !s.is_empty())
+            .collect::<Vec<_>>()
+            .join("\n"),
+        Value::Object(fields) => {
+            let texts: Vec<String> = ["logs", "stdout", "stderr", "text", "output", "result"]
+                .iter()
+                .filter_map(|key| fields.get(*key))
+                .map(value_text)
+                .filter(|s| !s.is_empty())
+                .collect();
+            if texts.is_empty() {
+                value.to_string()
+            } else {
+                texts.join("\n")
+            }
+        }
+        other => other.to_string(),
+    }
+}
+
+/// Text, token usage and tool calls of one API response
+#[derive(Debug)]
+struct ApiReply {
+    /// Model text; may be empty or partial when `truncated` is set
+    content: String,
+    usage: TokenUsage,
+    tool_invocations: Vec<ToolInvocation>,
+    truncated: Option<TruncatedReason>,
+}
+
+impl ApiReply {
+    fn from_response(response: ResponsesResponse) -> Result<Self> {
+        // Debug: log raw response structure
+        tracing::debug!("API response output items: {}", response.output.len());
+        for (i, item) in response.output.iter().enumerate() {
+            tracing::debug!(
+                "Output item {}: type={:?}, role={:?}, has_text={}, has_content={}",
+                i,
+                item.output_type,
+                item.role,
+                item.text.is_some(),
+                item.content.is_some()
+            );
+        }
+
+        let content = response
+            .output
+            .iter()
+            .filter_map(|o| {
+                // Try direct text field first (legacy)
+                if let Some(text) = &o.text {
+                    tracing::debug!("Found direct text field");
+                    return Some(text.clone());
+                }
+                // Try content field
+                if let Some(content) = &o.content {
+                    match content {
+                        OutputContent::Text(s) => {
+                            tracing::debug!("Found content as text string");
+                            return Some(s.clone());
+                        }
+                        OutputContent::Items(items) => {
+                            tracing::debug!("Found content as {} items", items.len());
+                            // Find the first text content item
+                            for item in items {
+                                if let Some(text) = &item.text {
+                                    tracing::debug!(
+                                        "Found text in content item: {} chars",
+                                        text.len()
+                                    );
+                                    return Some(text.clone());
+                                }
+                            }
+                        }
+                    }
+                }
+                None
+            })
+            .next();
+        let truncated = response
+            .turn_limit_reached()
+            .then_some(TruncatedReason::MaxTurnsReached);
+        let content = match (content, truncated) {
+            (Some(content), _) => content,
+            (None, Some(_)) => String::new(),
+            (None, None) => return Err(AuditError::other("No content in response".to_string())),
+        };
+        if truncated.is_some() {
+            warn!(
+                "Response stopped at the max_turns limit after {} output items",
+                response.output.len()
+            );
+        }
+
+        tracing::debug!("Extracted content length: {} chars", content.len());
+        tracing::debug!("Content preview: {}", &content[..content.len().min(500)]);
+
+        // Extract token usage from response
+        let usage = if let Some(usage) = response.usage {
+            TokenUsage {
+                prompt_tokens: usage.input_tokens,
+                completion_tokens: usage.output_tokens,
+                reasoning_tokens: usage
+                    .output_tokens_details
+                    .map(|d| d.reasoning_tokens)
+                    .unwrap_or(0),
+                cached_tokens: usage
+                    .input_tokens_details
+                    .map(|d| d.cached_tokens)
+                    .unwrap_or(0),
+                total_tokens: usage.total_tokens,
+            }
+        } else {
+            TokenUsage::default()
+        };
+
+        let tool_invocations = response
+            .output
+            .iter()
+            .filter_map(OutputItem::tool_invocation)
+            .collect();
+
+        Ok(Self {
+            content,
+            usage,
+            tool_invocations,
+            truncated,
+        })
+    }
 }
 
 /// Output content - can be array of content items or a string
@@ -465,6 +1084,8 @@ impl GrokReasoningClient {
             enable_reasoning: true,
             _timeout: Duration::from_secs(300),
             retry_config: RetryConfig::default(),
+            concurrency: 1,
+            rate_limited_until: Mutex::new(None),
         })
     }
 
@@ -529,9 +1150,16 @@ impl GrokReasoningClient {
         self.temperature = temperature;
     }
 
-    /// Estimate tokens for content
+    /// Set how many batches [`analyze_all_batches`] analyzes at once
+    /// (default 1, at least 1)
+    pub fn set_concurrency(&mut self, concurrency: usize) {
+        self.concurrency = concurrency.max(1);
+    }
+
+    /// Count tokens for content when the model is not known
+    /// (`cl100k_base`)
     pub fn estimate_tokens(content: &str) -> usize {
-        (content.len() as f64 * TOKENS_PER_CHAR) as usize
+        crate::tokenizer::count_tokens(content, "")
     }
 
     /// Create batches from files for optimal context usage
@@ -575,7 +1203,7 @@ impl GrokReasoningClient {
             current_tokens = 0;
 
             for file in category_files {
-                let file_tokens = Self::estimate_tokens(&file.content) + 500; // Buffer for prompt
+                let file_tokens = crate::tokenizer::count_tokens(&file.content, &self.model) + 500; // Buffer for prompt
 
                 // Check if adding this file would exceed limits
                 if current_tokens + file_tokens > max_batch_tokens && !current_batch.is_empty() {
@@ -773,12 +1401,17 @@ When analyzing multiple files, return a JSON array of file results."#,
             file.content
         );
 
-        let (response, token_usage) = self.call_api(&system_prompt, &user_prompt).await?;
+        let reply = self.call_api(&system_prompt, &user_prompt).await?;
         let processing_time = start.elapsed().as_millis() as u64;
 
         // Parse response
-        let mut result = self.parse_single_file_response(&response, &file.path)?;
-        result.tokens_used = token_usage;
+        let mut result = match reply.truncated {
+            None => self.parse_single_file_response(&reply.content, &file.path)?,
+            Some(reason) => self
+                .partial_results(&reply.content, &[file], reason)
+                .remove(0),
+        };
+        result.tokens_used = reply.usage;
 
         info!(
             "Analyzed {} in {}ms - Score: {:.0}",
@@ -824,7 +1457,7 @@ When analyzing multiple files, return a JSON array of file results."#,
 
         let mut all_results = cached_results;
         let mut total_tokens = TokenUsage::default();
-        let tool_calls_count = 0;
+        let mut tool_invocations = Vec::new();
 
         // Analyze uncached files
         if !files_to_analyze.is_empty() {
@@ -843,10 +1476,15 @@ When analyzing multiple files, return a JSON array of file results."#,
                 ));
             }
 
-            let (response, batch_token_usage) = self.call_api(&system_prompt, &user_prompt).await?;
+            let reply = self.call_api(&system_prompt, &user_prompt).await?;
+            let batch_token_usage = reply.usage;
+            tool_invocations = reply.tool_invocations;
 
             // Parse batch response
-            let mut new_results = self.parse_batch_response(&response, &files_to_analyze)?;
+            let mut new_results = match reply.truncated {
+                None => self.parse_batch_response(&reply.content, &files_to_analyze)?,
+                Some(reason) => self.partial_results(&reply.content, &files_to_analyze, reason),
+            };
 
             // Distribute token usage across files in batch (proportionally by content size)
             let total_content_size: usize = files_to_analyze.iter().map(|f| f.content.len()).sum();
@@ -867,9 +1505,13 @@ When analyzing multiple files, return a JSON array of file results."#,
                 };
             }
 
-            // Cache new results
+            // Cache new results; truncated ones should be re-run instead
             if let Some(c) = cache {
-                for (file, result) in files_to_analyze.iter().zip(new_results.iter()) {
+                for (file, result) in files_to_analyze
+                    .iter()
+                    .zip(new_results.iter())
+                    .filter(|(_, r)| r.truncated_reason.is_none())
+                {
                     if let Ok(analysis_json) = serde_json::to_value(result) {
                         let entry = CacheEntry {
                             file_path: file.path.clone(),
@@ -913,26 +1555,105 @@ When analyzing multiple files, return a JSON array of file results."#,
             batch_insights,
             total_tokens,
             processing_time_ms: processing_time,
-            tool_calls_count,
+            tool_calls_count: tool_invocations.len(),
+            tool_invocations,
+            cache_hit: false,
         })
     }
 
-    /// Generate insights across a batch of file results
-    fn generate_batch_insights(&self, results: &[FileAnalysisResult]) -> String {
-        let avg_score: f64 =
-            results.iter().map(|r| r.overall_score).sum::<f64>() / results.len() as f64;
-        let avg_security: f64 =
-            results.iter().map(|r| r.security_score).sum::<f64>() / results.len() as f64;
-
-        let critical_issues: usize = results
-            .iter()
-            .flat_map(|r| &r.issues)
-            .filter(|i| i.severity == "critical")
-            .count();
+    /// Cache key for a whole batch: changes whenever the model, request
+    /// settings, prompt or any file's content would change the response
+    pub fn batch_cache_key(&self, batch: &FileBatch) -> String {
+        use sha2::{Digest, Sha256};
+
+        let mut hasher = Sha256::new();
+        hasher.update(self.model.as_bytes());
+        hasher.update([
+            self.enable_reasoning as u8,
+            self.enable_code_execution as u8,
+        ]);
+        hasher.update(self.max_turns.to_le_bytes());
+        hasher.update(self.max_tokens.to_le_bytes());
+        hasher.update(self.temperature.to_le_bytes());
+        hasher.update(self.build_analysis_system_prompt(batch.category).as_bytes());
+        for file in &batch.files {
+            hasher.update(file.path.as_bytes());
+            hasher.update([0]);
+            hasher.update(file.content_hash.as_bytes());
+            hasher.update([0]);
+        }
+        format!("batch:{:x}", hasher.finalize())
+    }
 
-        let high_issues: usize = results
-            .iter()
-            .flat_map(|r| &r.issues)
+    /// Analyze a batch, returning the cached result when the batch key
+    /// matches a previous run
+    pub async fn analyze_batch_cached(
+        &self,
+        batch: &FileBatch,
+        cache: Option<&AuditCache>,
+    ) -> Result<BatchAnalysisResult> {
+        let Some(cache) = cache.filter(|c| c.is_enabled()) else {
+            return self.analyze_batch(batch, None).await;
+        };
+
+        let key = self.batch_cache_key(batch);
+        if let Some(entry) = cache.get_by_hash(&key, &key)? {
+            if let Ok(mut result) = serde_json::from_value::<BatchAnalysisResult>(entry.analysis) {
+                let tokens = result.total_tokens.total_tokens;
+                let pricing = crate::token_budget::TokenPricing::grok();
+                cache.record_hit(tokens, pricing.estimate_cost(tokens));
+                debug!("Batch cache hit: {}", batch.batch_id);
+
+                result.batch_id = batch.batch_id;
+                result.processing_time_ms = 0;
+                result.cache_hit = true;
+                return Ok(result);
+            }
+        }
+
+        cache.record_miss();
+        let result = self.analyze_batch(batch, Some(cache)).await?;
+        let truncated = result
+            .file_results
+            .iter()
+            .any(|r| r.truncated_reason.is_some());
+        if truncated {
+            return Ok(result);
+        }
+        if let Ok(analysis) = serde_json::to_value(&result) {
+            cache.set(
+                key.clone(),
+                CacheEntry {
+                    file_path: key.clone(),
+                    content_hash: key,
+                    analyzed_at: chrono::Utc::now().to_rfc3339(),
+                    provider: "xai".to_string(),
+                    model: self.model.clone(),
+                    analysis,
+                    tokens_used: Some(result.total_tokens.total_tokens),
+                    file_size: batch.files.iter().map(|f| f.content.len()).sum(),
+                },
+            )?;
+        }
+        Ok(result)
+    }
+
+    /// Generate insights across a batch of file results
+    fn generate_batch_insights(&self, results: &[FileAnalysisResult]) -> String {
+        let avg_score: f64 =
+            results.iter().map(|r| r.overall_score).sum::<f64>() / results.len() as f64;
+        let avg_security: f64 =
+            results.iter().map(|r| r.security_score).sum::<f64>() / results.len() as f64;
+
+        let critical_issues: usize = results
+            .iter()
+            .flat_map(|r| &r.issues)
+            .filter(|i| i.severity == "critical")
+            .count();
+
+        let high_issues: usize = results
+            .iter()
+            .flat_map(|r| &r.issues)
             .filter(|i| i.severity == "high")
             .count();
 
@@ -965,12 +1686,82 @@ When analyzing multiple files, return a JSON array of file results."#,
         insight
     }
 
+    /// Summarize a whole run of [`analyze_all_batches`]: themes, systemic
+    /// problems and the top priorities across files, in one more API call.
+    /// Only per-file scores and top issues are sent, capped at
+    /// [`SUMMARY_INPUT_TOKENS`]; the call's own tokens and cost are
+    /// recorded on the summary. A run without file results is summarized
+    /// without calling the API.
+    pub async fn synthesize_summary(&self, results: &[BatchAnalysisResult]) -> Result<RunSummary> {
+        let digest = RunDigest::new(results, SUMMARY_INPUT_TOKENS);
+        if digest.files_considered == 0 && digest.files_omitted == 0 {
+            return Ok(RunSummary {
+                overview: "No files were analyzed.".to_string(),
+                ..Default::default()
+            });
+        }
+
+        let system_prompt = format!(
+            r#"You are a principal engineer summarizing a code audit for the team that owns the code.
+You are given per-file scores and the top issues found in each file.
+Look across files: name recurring themes and problems rooted in the codebase's
+structure, not in any single file, and say what to fix first.
+
+Respond with only a JSON object:
+{{
+  "overview": "2-3 sentences on the overall state of the code",
+  "themes": ["theme seen across many files", ...],
+  "systemic_problems": ["problem and where it shows up", ...],
+  "top_priorities": ["most important action first", ...]
+}}
+List at most {} top priorities."#,
+            SUMMARY_MAX_PRIORITIES
+        );
+        let mut user_prompt = format!(
+            "Summarize this audit of {} files",
+            digest.files_considered + digest.files_omitted
+        );
+        if digest.files_omitted > 0 {
+            user_prompt.push_str(&format!(
+                " ({} highest-scoring files left out for length)",
+                digest.files_omitted
+            ));
+        }
+        user_prompt.push_str(":\n\n");
+        user_prompt.push_str(&digest.text);
+
+        let reply = self.call_api(&system_prompt, &user_prompt).await?;
+        let tokens_used = reply.usage;
+        let json = self.extract_json(&reply.content)?;
+        let parsed: RunSummaryResponse = serde_json::from_str(&json)
+            .map_err(|e| AuditError::other(format!("Failed to parse run summary: {}", e)))?;
+
+        let pricing = crate::token_budget::TokenPricing::grok();
+        let cost_usd = pricing.calculate_cost(
+            tokens_used.prompt_tokens,
+            tokens_used.completion_tokens + tokens_used.reasoning_tokens,
+        );
+        info!(
+            "Run summary: {} tokens (~${:.4})",
+            tokens_used.total_tokens, cost_usd
+        );
+
+        let mut top_priorities = parsed.top_priorities;
+        top_priorities.truncate(SUMMARY_MAX_PRIORITIES);
+        Ok(RunSummary {
+            overview: parsed.overview,
+            themes: parsed.themes,
+            systemic_problems: parsed.systemic_problems,
+            top_priorities,
+            files_considered: digest.files_considered,
+            files_omitted: digest.files_omitted,
+            tokens_used,
+            cost_usd,
+        })
+    }
+
     /// Call the xAI Responses API with retry logic
-    async fn call_api(
-        &self,
-        system_prompt: &str,
-        user_prompt: &str,
-    ) -> Result<(String, TokenUsage)> {
+    async fn call_api(&self, system_prompt: &str, user_prompt: &str) -> Result<ApiReply> {
         let mut last_error: Option<AuditError> = None;
 
         for attempt in 0..=self.retry_config.max_retries {
@@ -982,6 +1773,7 @@ When analyzing multiple files, return a JSON array of file results."#,
                 );
                 sleep(delay).await;
             }
+            self.wait_for_rate_limit().await;
 
             match self.call_api_once(system_prompt, user_prompt).await {
                 Ok(result) => {
@@ -995,6 +1787,9 @@ When analyzing multiple files, return a JSON array of file results."#,
                     // Check if error is retryable
                     if Self::is_retryable_error(&error_str) {
                         warn!("Retryable error on attempt {}: {}", attempt, error_str);
+                        if Self::is_rate_limit_error(&error_str) {
+                            self.pause_for_rate_limit(self.retry_config.delay_for_attempt(attempt));
+                        }
                         last_error = Some(e);
                         continue;
                     } else {
@@ -1010,6 +1805,34 @@ When analyzing multiple files, return a JSON array of file results."#,
             .unwrap_or_else(|| AuditError::other("API call failed after all retries".to_string())))
     }
 
+    /// Wait out a rate-limit pause set by this or a concurrent request
+    async fn wait_for_rate_limit(&self) {
+        let until = *self.rate_limited_until.lock().unwrap();
+        if let Some(until) = until.filter(|until| *until > Instant::now()) {
+            debug!(
+                "Holding request for {:?} after a rate limit",
+                until - Instant::now()
+            );
+            sleep_until(until).await;
+        }
+    }
+
+    /// Hold all requests from this client for `delay`
+    fn pause_for_rate_limit(&self, delay: Duration) {
+        let until = Instant::now() + delay;
+        let mut paused = self.rate_limited_until.lock().unwrap();
+        if paused.is_none_or(|current| current < until) {
+            *paused = Some(until);
+        }
+    }
+
+    fn is_rate_limit_error(error: &str) -> bool {
+        let error = error.to_lowercase();
+        ["429", "rate limit", "too many requests"]
+            .iter()
+            .any(|p| error.contains(p))
+    }
+
     /// Check if an error is retryable
     fn is_retryable_error(error: &str) -> bool {
         let retryable_patterns = [
@@ -1032,11 +1855,7 @@ When analyzing multiple files, return a JSON array of file results."#,
     }
 
     /// Single API call attempt (no retry)
-    async fn call_api_once(
-        &self,
-        system_prompt: &str,
-        user_prompt: &str,
-    ) -> Result<(String, TokenUsage)> {
+    async fn call_api_once(&self, system_prompt: &str, user_prompt: &str) -> Result<ApiReply> {
         let mut tools = Vec::new();
 
         if self.enable_code_execution {
@@ -1094,79 +1913,7 @@ When analyzing multiple files, return a JSON array of file results."#,
             .await
             .map_err(|e| AuditError::other(format!("Failed to parse response: {}", e)))?;
 
-        // Extract text content from response
-        // Debug: log raw response structure
-        tracing::debug!("API response output items: {}", response_body.output.len());
-        for (i, item) in response_body.output.iter().enumerate() {
-            tracing::debug!(
-                "Output item {}: type={:?}, role={:?}, has_text={}, has_content={}",
-                i,
-                item.output_type,
-                item.role,
-                item.text.is_some(),
-                item.content.is_some()
-            );
-        }
-
-        let content = response_body
-            .output
-            .iter()
-            .filter_map(|o| {
-                // Try direct text field first (legacy)
-                if let Some(text) = &o.text {
-                    tracing::debug!("Found direct text field");
-                    return Some(text.clone());
-                }
-                // Try content field
-                if let Some(content) = &o.content {
-                    match content {
-                        OutputContent::Text(s) => {
-                            tracing::debug!("Found content as text string");
-                            return Some(s.clone());
-                        }
-                        OutputContent::Items(items) => {
-                            tracing::debug!("Found content as {} items", items.len());
-                            // Find the first text content item
-                            for item in items {
-                                if let Some(text) = &item.text {
-                                    tracing::debug!(
-                                        "Found text in content item: {} chars",
-                                        text.len()
-                                    );
-                                    return Some(text.clone());
-                                }
-                            }
-                        }
-                    }
-                }
-                None
-            })
-            .next()
-            .ok_or_else(|| AuditError::other("No content in response".to_string()))?;
-
-        tracing::debug!("Extracted content length: {} chars", content.len());
-        tracing::debug!("Content preview: {}", &content[..content.len().min(500)]);
-
-        // Extract token usage from response
-        let token_usage = if let Some(usage) = response_body.usage {
-            TokenUsage {
-                prompt_tokens: usage.input_tokens,
-                completion_tokens: usage.output_tokens,
-                reasoning_tokens: usage
-                    .output_tokens_details
-                    .map(|d| d.reasoning_tokens)
-                    .unwrap_or(0),
-                cached_tokens: usage
-                    .input_tokens_details
-                    .map(|d| d.cached_tokens)
-                    .unwrap_or(0),
-                total_tokens: usage.total_tokens,
-            }
-        } else {
-            TokenUsage::default()
-        };
-
-        Ok((content, token_usage))
+        ApiReply::from_response(response_body)
     }
 
     /// Parse response for a single file
@@ -1216,6 +1963,7 @@ When analyzing multiple files, return a JSON array of file results."#,
                         test_coverage: None,
                         reasoning_trace: Some(response.to_string()),
                         tokens_used: TokenUsage::default(),
+                        truncated_reason: None,
                     })
                     .collect());
             }
@@ -1266,10 +2014,50 @@ When analyzing multiple files, return a JSON array of file results."#,
                 test_coverage: None,
                 reasoning_trace: None,
                 tokens_used: TokenUsage::default(),
+                truncated_reason: None,
             })
             .collect())
     }
 
+    /// One result per file, in `files` order, from a response cut short for
+    /// `reason`: whatever file results the model had already produced,
+    /// and placeholders for the rest, all flagged as truncated
+    fn partial_results(
+        &self,
+        response: &str,
+        files: &[&FileForAnalysis],
+        reason: TruncatedReason,
+    ) -> Vec<FileAnalysisResult> {
+        let parsed: Vec<FileAnalysisResult> = self
+            .extract_json(response)
+            .ok()
+            .and_then(|json| {
+                serde_json::from_str::<Vec<FileAnalysisResult>>(&json)
+                    .or_else(|_| serde_json::from_str(&json).map(|r| vec![r]))
+                    .ok()
+            })
+            .unwrap_or_default();
+        let mut by_path: HashMap<String, FileAnalysisResult> =
+            parsed.into_iter().map(|r| (r.path.clone(), r)).collect();
+
+        files
+            .iter()
+            .map(|file| {
+                let mut result = by_path.remove(&file.path).unwrap_or_else(|| {
+                    FileAnalysisResult::placeholder(
+                        &file.path,
+                        format!(
+                            "Analysis stopped at the max_turns limit ({}) before this file was assessed",
+                            self.max_turns
+                        ),
+                    )
+                });
+                result.truncated_reason = Some(reason);
+                result
+            })
+            .collect()
+    }
+
     /// Extract JSON from response (may be wrapped in markdown code blocks)
     fn extract_json(&self, response: &str) -> Result<String> {
         let trimmed = response.trim();
@@ -1329,50 +2117,277 @@ When analyzing multiple files, return a JSON array of file results."#,
     pub fn retry_config(&self) -> &RetryConfig {
         &self.retry_config
     }
+
+    /// Batches analyzed at once by [`analyze_all_batches`]
+    pub fn concurrency(&self) -> usize {
+        self.concurrency
+    }
+}
+
+/// Progress of [`analyze_all_batches`], reported after each batch in the
+/// order batches finish
+#[derive(Debug, Clone, Copy)]
+pub struct BatchProgress<'a> {
+    /// Batches finished so far, including failed ones
+    pub completed: usize,
+    /// Batches in the run
+    pub total: usize,
+    /// Tokens spent on API calls so far (cache hits cost nothing)
+    pub tokens_used: usize,
+    /// What just finished, e.g. "Batch 3 (12 files)"
+    pub message: &'a str,
 }
 
 /// Progress callback for batch analysis
-pub type ProgressCallback = Box<dyn Fn(usize, usize, &str) + Send + Sync>;
+pub type ProgressCallback = Box<dyn Fn(&BatchProgress) + Send + Sync>;
+
+/// Append-only record of finished batches, so an interrupted run can
+/// pick up where it stopped
+///
+/// Each line of the file is one JSON object, `{"key": ..., "result": ...}`,
+/// where `key` is the batch's [`GrokReasoningClient::batch_cache_key`]
+/// and `result` its [`BatchAnalysisResult`]. A line is written and flushed
+/// as soon as its batch finishes, so a crash loses at most the batches in
+/// flight. Truncated and failed batches aren't recorded and run again on
+/// resume.
+pub struct BatchJournal {
+    path: PathBuf,
+    file: Mutex<std::fs::File>,
+    entries: Mutex<HashMap<String, BatchAnalysisResult>>,
+}
 
-/// Analyze multiple batches with progress reporting
+#[derive(Serialize, Deserialize)]
+struct JournalEntry {
+    key: String,
+    result: BatchAnalysisResult,
+}
+
+impl BatchJournal {
+    /// Open the journal at `path`. With `resume`, batches already in the
+    /// file are kept and skipped by [`analyze_all_batches_resumable`];
+    /// otherwise the file starts empty. A partly written last line, left
+    /// by a crash mid-write, is dropped.
+    pub fn open(path: impl Into<PathBuf>, resume: bool) -> Result<Self> {
+        use std::io::Write;
+
+        let path = path.into();
+        let mut entries = HashMap::new();
+        let mut kept = String::new();
+        if resume && path.exists() {
+            for (i, line) in std::fs::read_to_string(&path)?.lines().enumerate() {
+                if line.trim().is_empty() {
+                    continue;
+                }
+                match serde_json::from_str::<JournalEntry>(line) {
+                    Ok(entry) => {
+                        kept.push_str(line);
+                        kept.push('\n');
+                        entries.insert(entry.key, entry.result);
+                    }
+                    Err(e) => warn!(
+                        "Skipping unreadable line {} of {}: {}",
+                        i + 1,
+                        path.display(),
+                        e
+                    ),
+                }
+            }
+        }
+
+        // Rewrite with just the readable entries so appends start on a
+        // fresh line
+        let mut file = std::fs::File::create(&path)?;
+        file.write_all(kept.as_bytes())?;
+        file.flush()?;
+        if !entries.is_empty() {
+            info!(
+                "Resuming from {}: {} batches already done",
+                path.display(),
+                entries.len()
+            );
+        }
+
+        Ok(Self {
+            path,
+            file: Mutex::new(file),
+            entries: Mutex::new(entries),
+        })
+    }
+
+    /// Journal file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Number of batches recorded
+    pub fn len(&self) -> usize {
+        self.entries.lock().unwrap().len()
+    }
+
+    /// Whether no batches are recorded
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Recorded result for the batch with this key
+    fn get(&self, key: &str) -> Option<BatchAnalysisResult> {
+        self.entries.lock().unwrap().get(key).cloned()
+    }
+
+    /// Record a finished batch and flush it to disk
+    fn append(&self, key: String, result: &BatchAnalysisResult) -> Result<()> {
+        use std::io::Write;
+
+        let entry = JournalEntry {
+            key,
+            result: result.clone(),
+        };
+        let line = serde_json::to_string(&entry)?;
+        {
+            let mut file = self.file.lock().unwrap();
+            writeln!(file, "{}", line)?;
+            file.flush()?;
+        }
+        self.entries.lock().unwrap().insert(entry.key, entry.result);
+        Ok(())
+    }
+
+    /// Write `results` to `path` as one pretty-printed JSON array, the
+    /// final report of a finished run
+    pub fn write_report(results: &[BatchAnalysisResult], path: &Path) -> Result<()> {
+        std::fs::write(path, serde_json::to_string_pretty(results)? + "\n")?;
+        Ok(())
+    }
+}
+
+/// Analyze multiple batches with progress reporting. Batches whose key
+/// (see [`GrokReasoningClient::batch_cache_key`]) is in `cache` are served
+/// from it without an API call.
+///
+/// Up to [`GrokReasoningClient::concurrency`] batches are in flight at
+/// once. Results come back in the order of `batches` whatever order they
+/// finish in, and progress is reported one batch at a time.
 pub async fn analyze_all_batches(
     client: &GrokReasoningClient,
     batches: Vec<FileBatch>,
     cache: Option<&AuditCache>,
     progress: Option<ProgressCallback>,
 ) -> Result<Vec<BatchAnalysisResult>> {
-    let total_batches = batches.len();
-    let mut results = Vec::new();
+    run_batches(client, batches, cache, progress, None).await
+}
 
-    for (i, batch) in batches.into_iter().enumerate() {
-        if let Some(ref cb) = progress {
-            cb(
-                i + 1,
-                total_batches,
-                &format!(
-                    "Analyzing batch {} ({} files)",
-                    batch.batch_id,
-                    batch.files.len()
-                ),
-            );
-        }
+/// [`analyze_all_batches`], recording each finished batch in `journal`
+/// and skipping batches it already holds. The results cover every batch,
+/// including those finished by an earlier, interrupted run.
+pub async fn analyze_all_batches_resumable(
+    client: &GrokReasoningClient,
+    batches: Vec<FileBatch>,
+    cache: Option<&AuditCache>,
+    progress: Option<ProgressCallback>,
+    journal: &BatchJournal,
+) -> Result<Vec<BatchAnalysisResult>> {
+    run_batches(client, batches, cache, progress, Some(journal)).await
+}
 
-        match client.analyze_batch(&batch, cache).await {
-            Ok(result) => {
-                info!(
-                    "Batch {} complete: {} files in {}ms",
-                    result.batch_id,
-                    result.file_results.len(),
-                    result.processing_time_ms
-                );
-                results.push(result);
+async fn run_batches(
+    client: &GrokReasoningClient,
+    batches: Vec<FileBatch>,
+    cache: Option<&AuditCache>,
+    progress: Option<ProgressCallback>,
+    journal: Option<&BatchJournal>,
+) -> Result<Vec<BatchAnalysisResult>> {
+    let total_batches = batches.len();
+    let semaphore = Semaphore::new(client.concurrency());
+    // Batches completed and tokens used, updated together so progress
+    // reports stay consistent
+    let totals = Mutex::new((0usize, 0usize));
+
+    let runs = batches.iter().map(|batch| async {
+        let key = journal.map(|_| client.batch_cache_key(batch));
+        let recorded = journal.zip(key.as_deref()).and_then(|(j, key)| j.get(key));
+        let resumed = recorded.is_some();
+        let message = format!(
+            "Batch {} ({} files{})",
+            batch.batch_id,
+            batch.files.len(),
+            if resumed { ", resumed" } else { "" }
+        );
+
+        let result = match recorded {
+            Some(mut result) => {
+                debug!("Batch {} already in journal", batch.batch_id);
+                result.batch_id = batch.batch_id;
+                Some(result)
             }
-            Err(e) => {
-                warn!("Batch {} failed: {}", batch.batch_id, e);
-                // Continue with other batches
+            None => {
+                let _permit = semaphore
+                    .acquire()
+                    .await
+                    .expect("semaphore is never closed");
+                match client.analyze_batch_cached(batch, cache).await {
+                    Ok(result) => {
+                        info!(
+                            "Batch {} complete: {} files in {}ms{}",
+                            result.batch_id,
+                            result.file_results.len(),
+                            result.processing_time_ms,
+                            if result.cache_hit { " (cached)" } else { "" }
+                        );
+                        Some(result)
+                    }
+                    Err(e) => {
+                        warn!("Batch {} failed: {}", batch.batch_id, e);
+                        // Continue with other batches
+                        None
+                    }
+                }
             }
+        };
+
+        if let (Some(journal), Some(key), Some(result)) = (journal, key, result.as_ref()) {
+            let truncated = result
+                .file_results
+                .iter()
+                .any(|r| r.truncated_reason.is_some());
+            if !resumed && !truncated {
+                if let Err(e) = journal.append(key, result) {
+                    warn!(
+                        "Failed to record batch {} in {}: {}",
+                        batch.batch_id,
+                        journal.path().display(),
+                        e
+                    );
+                }
+            }
+        }
+
+        let mut totals = totals.lock().unwrap();
+        totals.0 += 1;
+        if let Some(result) = result.as_ref().filter(|r| !r.cache_hit && !resumed) {
+            totals.1 += result.total_tokens.total_tokens;
+        }
+        if let Some(ref cb) = progress {
+            cb(&BatchProgress {
+                completed: totals.0,
+                total: total_batches,
+                tokens_used: totals.1,
+                message: &message,
+            });
+        }
+        result
+    });
+    let results: Vec<BatchAnalysisResult> = futures::future::join_all(runs)
+        .await
+        .into_iter()
+        .flatten()
+        .collect();
+
+    if let Some(cache) = cache {
+        if let Err(e) = cache.save() {
+            warn!("Failed to save audit cache: {}", e);
         }
     }
+    info!("Batch run: {}", BatchRunSummary::from_results(&results));
 
     Ok(results)
 }
@@ -1436,6 +2451,8 @@ mod tests {
             enable_reasoning: true,
             _timeout: Duration::from_secs(300),
             retry_config: RetryConfig::default(),
+            concurrency: 1,
+            rate_limited_until: Mutex::new(None),
         };
 
         let files: Vec<FileForAnalysis> = (0..20)
@@ -1472,6 +2489,8 @@ mod tests {
             enable_reasoning: true,
             _timeout: Duration::from_secs(300),
             retry_config: RetryConfig::default(),
+            concurrency: 1,
+            rate_limited_until: Mutex::new(None),
         };
 
         let response = r#"{"score": 85}"#;
@@ -1493,6 +2512,8 @@ mod tests {
             enable_reasoning: true,
             _timeout: Duration::from_secs(300),
             retry_config: RetryConfig::default(),
+            concurrency: 1,
+            rate_limited_until: Mutex::new(None),
         };
 
         let response = r#"Here's the analysis:
@@ -1510,4 +2531,512 @@ mod tests {
         assert_eq!(format!("{:?}", FileCategory::Janus), "Janus");
         assert_eq!(format!("{:?}", FileCategory::Clients), "Clients");
     }
+
+    fn test_client() -> GrokReasoningClient {
+        GrokReasoningClient::new("test".to_string()).unwrap()
+    }
+
+    fn test_batch() -> FileBatch {
+        FileBatch {
+            files: vec![FileForAnalysis {
+                path: "src/lib.rs".to_string(),
+                content: "fn a() {}".to_string(),
+                lines: 1,
+                score: None,
+                category: FileCategory::Audit,
+                content_hash: "abc".to_string(),
+            }],
+            batch_id: 0,
+            estimated_tokens: 10,
+            priority: 1.0,
+            category: FileCategory::Audit,
+        }
+    }
+
+    fn scored_file(path: &str, score: f64, issues: &[(&str, &str)]) -> FileAnalysisResult {
+        FileAnalysisResult {
+            path: path.to_string(),
+            overall_score: score,
+            security_score: score,
+            quality_score: score,
+            complexity_score: 50.0,
+            maintainability_score: score,
+            summary: format!("{} summary", path),
+            issues: issues
+                .iter()
+                .map(|(severity, description)| IdentifiedIssue {
+                    severity: severity.to_string(),
+                    category: "quality".to_string(),
+                    line: None,
+                    description: description.to_string(),
+                    suggested_fix: None,
+                })
+                .collect(),
+            improvements: vec![],
+            patterns: vec![],
+            dependencies: vec![],
+            test_coverage: None,
+            reasoning_trace: None,
+            tokens_used: TokenUsage::default(),
+            truncated_reason: None,
+        }
+    }
+
+    #[test]
+    fn test_run_digest_keeps_worst_files_within_budget() {
+        let results = vec![BatchAnalysisResult {
+            batch_id: 0,
+            file_results: vec![
+                scored_file("src/good.rs", 95.0, &[]),
+                scored_file(
+                    "src/bad.rs",
+                    20.0,
+                    &[("low", "naming"), ("critical", "sql injection")],
+                ),
+                scored_file("src/ok.rs", 70.0, &[("medium", "long function")]),
+            ],
+            batch_insights: None,
+            total_tokens: TokenUsage::default(),
+            processing_time_ms: 0,
+            tool_calls_count: 0,
+            tool_invocations: vec![],
+            cache_hit: false,
+        }];
+
+        let digest = RunDigest::new(&results, SUMMARY_INPUT_TOKENS);
+        assert_eq!((digest.files_considered, digest.files_omitted), (3, 0));
+        assert!(digest.text.starts_with("Run totals: 3 files"));
+        assert!(digest.text.contains("1 critical, 0 high, 1 medium, 1 low"));
+        // Worst file first, its most severe issue first
+        let bad = digest.text.find("src/bad.rs").unwrap();
+        assert!(bad < digest.text.find("src/ok.rs").unwrap());
+        assert!(digest.text.find("sql injection").unwrap() < digest.text.find("naming").unwrap());
+
+        // A budget with room for just the worst file drops the others
+        let ok = digest.text.find("src/ok.rs").unwrap();
+        let budget = GrokReasoningClient::estimate_tokens(&digest.text[..bad])
+            + GrokReasoningClient::estimate_tokens(&digest.text[bad..ok]);
+        let tight = RunDigest::new(&results, budget);
+        assert_eq!((tight.files_considered, tight.files_omitted), (1, 2));
+        assert!(tight.text.contains("src/bad.rs"));
+        assert!(!tight.text.contains("src/good.rs"));
+    }
+
+    #[test]
+    fn test_tool_calls_are_captured_and_redacted() {
+        let aws_key = format!("AKIA{}", "Q7ZP4M2KD9XW3HJT");
+        let response: ResponsesResponse = serde_json::from_value(serde_json::json!({
+            "output": [
+                {
+                    "type": "code_interpreter_call",
+                    "code": "import os\nprint(os.environ)",
+                    "outputs": [{"type": "logs", "logs": format!("{{'AWS_KEY': '{}'}}", aws_key)}],
+                    "status": "completed"
+                },
+                {
+                    "type": "message",
+                    "role": "assistant",
+                    "content": [{"type": "output_text", "text": "[]"}]
+                }
+            ],
+            "usage": {"input_tokens": 10, "output_tokens": 5, "total_tokens": 15}
+        }))
+        .unwrap();
+
+        let reply = ApiReply::from_response(response).unwrap();
+        assert_eq!(reply.content, "[]");
+        assert_eq!(reply.usage.total_tokens, 15);
+        assert_eq!(
+            reply.tool_invocations,
+            vec![ToolInvocation {
+                tool: "code_interpreter".to_string(),
+                input: "import os\nprint(os.environ)".to_string(),
+                output: "{'AWS_KEY': 'AKIA[REDACTED 16 chars]'}".to_string(),
+                status: Some("completed".to_string()),
+            }]
+        );
+
+        let result = BatchAnalysisResult {
+            batch_id: 2,
+            file_results: vec![],
+            batch_insights: None,
+            total_tokens: reply.usage,
+            processing_time_ms: 0,
+            tool_calls_count: 1,
+            tool_invocations: reply.tool_invocations,
+            cache_hit: false,
+        };
+        let section = format_tool_executions(&[result]);
+        assert!(section.starts_with("## Tool Executions"));
+        assert!(section.contains("### Batch 2 · call 1: code_interpreter (completed)"));
+        assert!(!section.contains(&aws_key));
+        assert_eq!(format_tool_executions(&[]), "");
+    }
+
+    #[tokio::test]
+    async fn test_max_turns_returns_truncated_partial_results() {
+        use axum::{routing::post, Json, Router};
+
+        // Every response ends on another tool call, as if the model always
+        // wanted one more turn; the first file's result made it out
+        let app = Router::new().route(
+            "/responses",
+            post(|| async {
+                Json(serde_json::json!({
+                    "status": "completed",
+                    "output": [
+                        {
+                            "type": "message",
+                            "role": "assistant",
+                            "content": [{
+                                "type": "output_text",
+                                "text": r#"[{"path": "src/lib.rs", "overall_score": 40}]"#
+                            }]
+                        },
+                        {"type": "code_interpreter_call", "code": "print(1)", "status": "in_progress"}
+                    ],
+                    "usage": {"input_tokens": 100, "output_tokens": 20, "total_tokens": 120}
+                }))
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await });
+
+        let mut client = test_client();
+        client.base_url = format!("http://{}", addr);
+        let mut batch = test_batch();
+        let mut second = batch.files[0].clone();
+        second.path = "src/b.rs".to_string();
+        batch.files.push(second);
+
+        let results = analyze_all_batches(&client, vec![batch.clone()], None, None)
+            .await
+            .unwrap();
+        let files = &results[0].file_results;
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0].path, "src/lib.rs");
+        assert_eq!(files[0].overall_score, 40.0);
+        assert_eq!(files[1].path, "src/b.rs");
+        assert!(files[1].summary.contains("max_turns limit (5)"));
+        assert!(files
+            .iter()
+            .all(|f| f.truncated_reason == Some(TruncatedReason::MaxTurnsReached)));
+        assert_eq!(results[0].tool_calls_count, 1);
+
+        let summary = BatchRunSummary::from_results(&results);
+        assert_eq!(summary.truncated_files, 2);
+        assert!(summary
+            .to_string()
+            .ends_with("2 files truncated at max_turns"));
+
+        let retry = retain_truncated(vec![batch], &results);
+        assert_eq!(retry.len(), 1);
+        assert_eq!(retry[0].files.len(), 2);
+        assert_eq!(truncated_paths(&results), vec!["src/lib.rs", "src/b.rs"]);
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_batches_match_sequential_run() {
+        use axum::{routing::post, Json, Router};
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        // Scores each batch's file from its name, slower for low numbers so
+        // batches finish out of order, and records peak concurrency
+        let in_flight = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+        let (gauge, high_water) = (in_flight.clone(), peak.clone());
+        let app = Router::new().route(
+            "/responses",
+            post(move |body: String| {
+                let (gauge, high_water) = (gauge.clone(), high_water.clone());
+                async move {
+                    let now = gauge.fetch_add(1, Ordering::SeqCst) + 1;
+                    high_water.fetch_max(now, Ordering::SeqCst);
+                    let start = body.find("src/f").unwrap() + "src/f".len();
+                    let n: u64 = body[start..start + 1].parse().unwrap();
+                    sleep(Duration::from_millis(20 * (6 - n))).await;
+                    gauge.fetch_sub(1, Ordering::SeqCst);
+                    Json(serde_json::json!({
+                        "status": "completed",
+                        "output": [{
+                            "type": "message",
+                            "role": "assistant",
+                            "content": [{
+                                "type": "output_text",
+                                "text": format!(r#"[{{"path": "src/f{}.rs", "overall_score": {}}}]"#, n, 50 + n)
+                            }]
+                        }],
+                        "usage": {"input_tokens": 10 * n, "output_tokens": n, "total_tokens": 11 * n}
+                    }))
+                }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await });
+
+        let batches: Vec<FileBatch> = (1..=5)
+            .map(|n| {
+                let mut batch = test_batch();
+                batch.batch_id = n;
+                batch.files[0].path = format!("src/f{}.rs", n);
+                batch
+            })
+            .collect();
+        let run = |concurrency: usize| {
+            let batches = batches.clone();
+            async move {
+                let mut client = test_client();
+                client.base_url = format!("http://{}", addr);
+                client.set_concurrency(concurrency);
+                let seen = Arc::new(Mutex::new(Vec::new()));
+                let sink = seen.clone();
+                let progress: ProgressCallback = Box::new(move |p: &BatchProgress| {
+                    sink.lock().unwrap().push((p.completed, p.tokens_used));
+                });
+                let results = analyze_all_batches(&client, batches, None, Some(progress))
+                    .await
+                    .unwrap();
+                let seen = seen.lock().unwrap().clone();
+                (results, seen)
+            }
+        };
+
+        let (sequential, _) = run(1).await;
+        assert_eq!(peak.swap(0, Ordering::SeqCst), 1);
+        let (concurrent, progress) = run(3).await;
+        assert_eq!(peak.load(Ordering::SeqCst), 3);
+
+        let scores = |results: &[BatchAnalysisResult]| -> Vec<(usize, String, f64)> {
+            results
+                .iter()
+                .map(|r| {
+                    let file = &r.file_results[0];
+                    (r.batch_id, file.path.clone(), file.overall_score)
+                })
+                .collect()
+        };
+        assert_eq!(scores(&concurrent), scores(&sequential));
+        assert_eq!(concurrent[0].batch_id, 1);
+        assert_eq!(
+            BatchRunSummary::from_results(&concurrent).tokens_used,
+            BatchRunSummary::from_results(&sequential).tokens_used
+        );
+
+        // Progress counts up one batch at a time and ends at the full total
+        let completed: Vec<usize> = progress.iter().map(|p| p.0).collect();
+        assert_eq!(completed, vec![1, 2, 3, 4, 5]);
+        assert!(progress.windows(2).all(|w| w[0].1 < w[1].1));
+        assert_eq!(progress.last().unwrap().1, 11 * 15);
+    }
+
+    #[tokio::test]
+    async fn test_interrupted_run_resumes_from_journal() {
+        use axum::{http::StatusCode, routing::post, Json, Router};
+        use std::sync::atomic::{AtomicBool, Ordering};
+        use std::sync::Arc;
+
+        // Batch 3 fails until `healthy` is set, standing in for a run that
+        // dies partway; every request's file number is recorded
+        let healthy = Arc::new(AtomicBool::new(false));
+        let requested = Arc::new(Mutex::new(Vec::new()));
+        let (ok, log) = (healthy.clone(), requested.clone());
+        let app = Router::new().route(
+            "/responses",
+            post(move |body: String| {
+                let (ok, log) = (ok.clone(), log.clone());
+                async move {
+                    let start = body.find("src/f").unwrap() + "src/f".len();
+                    let n: u64 = body[start..start + 1].parse().unwrap();
+                    log.lock().unwrap().push(n);
+                    if n == 3 && !ok.load(Ordering::SeqCst) {
+                        return Err(StatusCode::BAD_REQUEST);
+                    }
+                    Ok(Json(serde_json::json!({
+                        "status": "completed",
+                        "output": [{
+                            "type": "message",
+                            "role": "assistant",
+                            "content": [{
+                                "type": "output_text",
+                                "text": format!(r#"[{{"path": "src/f{}.rs", "overall_score": {}}}]"#, n, 50 + n)
+                            }]
+                        }],
+                        "usage": {"input_tokens": 10, "output_tokens": 1, "total_tokens": 11}
+                    })))
+                }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await });
+
+        let mut client = test_client();
+        client.base_url = format!("http://{}", addr);
+        client.retry_config.max_retries = 0;
+        let batches: Vec<FileBatch> = (1..=3)
+            .map(|n| {
+                let mut batch = test_batch();
+                batch.batch_id = n;
+                batch.files[0].path = format!("src/f{}.rs", n);
+                batch
+            })
+            .collect();
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("batches.ndjson");
+        let journal = BatchJournal::open(&path, false).unwrap();
+        let first = analyze_all_batches_resumable(&client, batches.clone(), None, None, &journal)
+            .await
+            .unwrap();
+        assert_eq!(first.len(), 2);
+        assert_eq!(journal.len(), 2);
+        drop(journal);
+
+        // A crash mid-write leaves a partial last line
+        let mut contents = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(contents.lines().count(), 2);
+        contents.push_str(r#"{"key": "trunc"#);
+        std::fs::write(&path, contents).unwrap();
+
+        healthy.store(true, Ordering::SeqCst);
+        requested.lock().unwrap().clear();
+        let journal = BatchJournal::open(&path, true).unwrap();
+        assert_eq!(journal.len(), 2);
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let sink = seen.clone();
+        let progress: ProgressCallback = Box::new(move |p: &BatchProgress| {
+            sink.lock().unwrap().push((p.completed, p.tokens_used));
+        });
+        let resumed =
+            analyze_all_batches_resumable(&client, batches, None, Some(progress), &journal)
+                .await
+                .unwrap();
+
+        // Only the missing batch is sent, and only its tokens are counted
+        assert_eq!(*requested.lock().unwrap(), vec![3]);
+        assert_eq!(seen.lock().unwrap().last(), Some(&(3, 11)));
+        let scores: Vec<(usize, f64)> = resumed
+            .iter()
+            .map(|r| (r.batch_id, r.file_results[0].overall_score))
+            .collect();
+        assert_eq!(scores, vec![(1, 51.0), (2, 52.0), (3, 53.0)]);
+        assert_eq!(std::fs::read_to_string(&path).unwrap().lines().count(), 3);
+
+        let report = dir.path().join("report.json");
+        BatchJournal::write_report(&resumed, &report).unwrap();
+        let written: Vec<BatchAnalysisResult> =
+            serde_json::from_str(&std::fs::read_to_string(&report).unwrap()).unwrap();
+        assert_eq!(written.len(), 3);
+    }
+
+    #[test]
+    fn test_rate_limit_pause_is_shared() {
+        let client = test_client();
+        assert!(GrokReasoningClient::is_rate_limit_error(
+            "HTTP 429 Too Many Requests"
+        ));
+        assert!(!GrokReasoningClient::is_rate_limit_error(
+            "503 Service Unavailable"
+        ));
+
+        client.pause_for_rate_limit(Duration::from_secs(60));
+        let until = client.rate_limited_until.lock().unwrap().unwrap();
+        // A shorter pause from another request doesn't cut it short
+        client.pause_for_rate_limit(Duration::from_millis(1));
+        assert_eq!(*client.rate_limited_until.lock().unwrap(), Some(until));
+    }
+
+    #[tokio::test]
+    async fn test_empty_run_summary_skips_api_call() {
+        // The bogus key would fail if a request were made
+        let summary = test_client().synthesize_summary(&[]).await.unwrap();
+        assert_eq!(summary.files_considered, 0);
+        assert_eq!(summary.tokens_used.total_tokens, 0);
+    }
+
+    #[test]
+    fn test_batch_cache_key_tracks_settings() {
+        let batch = test_batch();
+        let base = test_client().batch_cache_key(&batch);
+        assert!(base.starts_with("batch:"));
+        assert_eq!(base, test_client().batch_cache_key(&batch));
+
+        let mut other_model = test_client();
+        other_model.model = "grok-other".to_string();
+        assert_ne!(base, other_model.batch_cache_key(&batch));
+
+        let mut no_reasoning = test_client();
+        no_reasoning.enable_reasoning = !no_reasoning.enable_reasoning;
+        assert_ne!(base, no_reasoning.batch_cache_key(&batch));
+
+        let mut no_code_exec = test_client();
+        no_code_exec.enable_code_execution = !no_code_exec.enable_code_execution;
+        assert_ne!(base, no_code_exec.batch_cache_key(&batch));
+
+        let mut changed = test_batch();
+        changed.files[0].content_hash = "def".to_string();
+        assert_ne!(base, test_client().batch_cache_key(&changed));
+    }
+
+    #[tokio::test]
+    async fn test_analyze_all_batches_uses_batch_cache() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = crate::llm_config::CacheConfig::default();
+        let cache = AuditCache::new(dir.path(), &config).unwrap();
+        let client = test_client();
+        let batch = test_batch();
+
+        let key = client.batch_cache_key(&batch);
+        let cached = BatchAnalysisResult {
+            batch_id: 7,
+            file_results: Vec::new(),
+            batch_insights: Some("cached insight".to_string()),
+            total_tokens: TokenUsage {
+                prompt_tokens: 800,
+                completion_tokens: 200,
+                total_tokens: 1000,
+                ..Default::default()
+            },
+            processing_time_ms: 1234,
+            tool_calls_count: 0,
+            tool_invocations: vec![],
+            cache_hit: false,
+        };
+        cache
+            .set(
+                key.clone(),
+                CacheEntry {
+                    file_path: key.clone(),
+                    content_hash: key,
+                    analyzed_at: chrono::Utc::now().to_rfc3339(),
+                    provider: "xai".to_string(),
+                    model: client.model.clone(),
+                    analysis: serde_json::to_value(&cached).unwrap(),
+                    tokens_used: Some(1000),
+                    file_size: 9,
+                },
+            )
+            .unwrap();
+
+        // A miss would hit the network with a bogus key and be dropped
+        let results = analyze_all_batches(&client, vec![batch], Some(&cache), None)
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 1);
+        assert!(results[0].cache_hit);
+        assert_eq!(results[0].batch_id, 0);
+        assert_eq!(results[0].batch_insights.as_deref(), Some("cached insight"));
+
+        let stats = cache.stats();
+        assert_eq!(stats.cache_hits, 1);
+        assert_eq!(stats.tokens_saved, 1000);
+        assert!(stats.estimated_savings > 0.0);
+
+        let summary = BatchRunSummary::from_results(&results);
+        assert_eq!(summary.hit_rate(), 100.0);
+        assert_eq!(summary.tokens_saved, 1000);
+    }
 }
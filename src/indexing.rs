@@ -58,6 +58,11 @@ pub struct IndexingConfig {
 
     /// Whether to overwrite existing embeddings
     pub overwrite_existing: bool,
+
+    /// Model and dimension the stored index was built with, as recorded in
+    /// `index_metadata`. Populated by [`IndexingConfig::load_index_metadata`].
+    #[serde(default)]
+    pub stored_embedding: Option<EmbeddingFingerprint>,
 }
 
 impl Default for IndexingConfig {
@@ -67,10 +72,131 @@ impl Default for IndexingConfig {
             embedding_config: EmbeddingConfig::default(),
             max_batch_size: 32,
             overwrite_existing: false,
+            stored_embedding: None,
         }
     }
 }
 
+/// Embedding model name and dimension an index was built with
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EmbeddingFingerprint {
+    pub model: String,
+    pub dimension: usize,
+}
+
+impl IndexingConfig {
+    /// Fingerprint of the active embedding configuration
+    pub fn embedding_fingerprint(&self) -> EmbeddingFingerprint {
+        EmbeddingFingerprint {
+            model: self.embedding_config.active_model().to_string(),
+            dimension: self.embedding_config.active_dimension(),
+        }
+    }
+
+    /// Whether stored vectors were built with a different model or dimension
+    /// than the active embedding config, making them incomparable with new queries
+    pub fn needs_reindex(&self) -> bool {
+        self.stored_embedding
+            .as_ref()
+            .is_some_and(|stored| *stored != self.embedding_fingerprint())
+    }
+
+    /// Load the recorded index fingerprint from the database.
+    ///
+    /// If nothing is recorded yet, the fingerprint is inferred from existing
+    /// embeddings, or — for an empty index — the active config is recorded.
+    pub async fn load_index_metadata(&mut self, pool: &PgPool) -> Result<()> {
+        let stored = match read_index_fingerprint(pool).await? {
+            Some(stored) => stored,
+            None => {
+                let inferred: Option<(String, i64)> =
+                    sqlx::query_as("SELECT model, dimension FROM document_embeddings LIMIT 1")
+                        .fetch_optional(pool)
+                        .await
+                        .context("Failed to inspect stored embeddings")?;
+
+                let fingerprint = match inferred {
+                    Some((model, dimension)) => EmbeddingFingerprint {
+                        model,
+                        dimension: dimension as usize,
+                    },
+                    None => self.embedding_fingerprint(),
+                };
+                record_index_fingerprint(pool, &fingerprint).await?;
+                fingerprint
+            }
+        };
+
+        self.stored_embedding = Some(stored);
+
+        if self.needs_reindex() {
+            let active = self.embedding_fingerprint();
+            if let Some(stored) = &self.stored_embedding {
+                tracing::warn!(
+                    "Stored index was built with {} ({}D) but the active embedding model is {} ({}D); \
+                     reindex with POST /admin/reindex",
+                    stored.model,
+                    stored.dimension,
+                    active.model,
+                    active.dimension
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Read the recorded index fingerprint, if any
+async fn read_index_fingerprint(pool: &PgPool) -> Result<Option<EmbeddingFingerprint>> {
+    let rows: Vec<(String, String)> = sqlx::query_as(
+        "SELECT key, value FROM index_metadata WHERE key IN ('embedding_model', 'embedding_dimension')",
+    )
+    .fetch_all(pool)
+    .await
+    .context("Failed to read index metadata")?;
+
+    let model = rows.iter().find(|(k, _)| k == "embedding_model");
+    let dimension = rows
+        .iter()
+        .find(|(k, _)| k == "embedding_dimension")
+        .and_then(|(_, v)| v.parse::<usize>().ok());
+
+    Ok(match (model, dimension) {
+        (Some((_, model)), Some(dimension)) => Some(EmbeddingFingerprint {
+            model: model.clone(),
+            dimension,
+        }),
+        _ => None,
+    })
+}
+
+/// Record the fingerprint the index is (now) built with
+pub async fn record_index_fingerprint(
+    pool: &PgPool,
+    fingerprint: &EmbeddingFingerprint,
+) -> Result<()> {
+    let now = chrono::Utc::now().timestamp();
+
+    for (key, value) in [
+        ("embedding_model", fingerprint.model.clone()),
+        ("embedding_dimension", fingerprint.dimension.to_string()),
+    ] {
+        sqlx::query(
+            "INSERT INTO index_metadata (key, value, updated_at) VALUES ($1, $2, $3)
+             ON CONFLICT (key) DO UPDATE SET value = EXCLUDED.value, updated_at = EXCLUDED.updated_at",
+        )
+        .bind(key)
+        .bind(value)
+        .bind(now)
+        .execute(pool)
+        .await
+        .context("Failed to write index metadata")?;
+    }
+
+    Ok(())
+}
+
 // ============================================================================
 // Indexing Results
 // ============================================================================
@@ -468,6 +594,24 @@ mod tests {
         let config = IndexingConfig::default();
         assert_eq!(config.max_batch_size, 32);
         assert!(!config.overwrite_existing);
+        assert!(!config.needs_reindex());
+    }
+
+    #[test]
+    fn test_needs_reindex_on_fingerprint_change() {
+        let mut config = IndexingConfig::default();
+        config.stored_embedding = Some(config.embedding_fingerprint());
+        assert!(!config.needs_reindex());
+
+        config.embedding_config.model_name = crate::embeddings::EmbeddingModelType::BGEBaseENV15;
+        assert!(config.needs_reindex());
+
+        // Same dimension, different model still invalidates the index
+        config.stored_embedding = Some(EmbeddingFingerprint {
+            model: "all-MiniLM-L6-v2".to_string(),
+            dimension: 768,
+        });
+        assert!(config.needs_reindex());
     }
 
     #[test]
@@ -30,6 +30,7 @@ pub mod cache;
 pub mod cache_layer;
 pub mod cache_migrate;
 pub mod chunking;
+pub mod ci_summary;
 pub mod cli;
 pub mod code_chunker;
 pub mod code_review;
@@ -37,6 +38,7 @@ pub mod config;
 pub mod context;
 pub mod context_builder;
 pub mod cost_tracker;
+pub mod coverage;
 pub mod db;
 pub mod directory_tree;
 pub mod doc_generator;
@@ -49,6 +51,7 @@ pub mod github;
 pub mod grok_client;
 pub mod grok_reasoning;
 pub mod indexing;
+pub mod license;
 pub mod llm;
 pub mod llm_audit;
 pub mod llm_config;
@@ -56,7 +59,9 @@ pub mod metrics;
 pub mod model_router;
 pub mod multi_tenant;
 pub mod ollama_client;
+pub mod output_schema;
 pub mod parser;
+pub mod path_filter;
 pub mod prompt_hashes;
 pub mod prompt_router;
 pub mod query_analytics;
@@ -69,6 +74,7 @@ pub mod repo_cache;
 pub mod repo_cache_sql;
 pub mod repo_manager;
 pub mod repo_sync;
+pub mod rerank;
 pub mod research;
 pub mod response_cache;
 pub mod scanner;
@@ -87,16 +93,19 @@ pub mod tests_runner;
 pub mod todo;
 pub mod todo_scanner;
 pub mod token_budget;
+pub mod tokenizer;
 pub mod tree_state;
 pub mod types;
 pub mod vector_index;
 pub mod webhooks;
+pub mod workspace;
 
 pub use api::{
-    create_api_router, create_default_api_router, generate_api_key, hash_api_key, ApiConfig,
-    ApiResponse, ApiState, AuthConfig, AuthResult, IndexJobResponse, IndexJobStatus,
-    IndexStatusResponse, JobQueue, JobQueueConfig, JobStatus, PaginatedResponse, RateLimitConfig,
-    RateLimiter, SearchRequest, SearchResponse, SearchType, UploadDocumentRequest,
+    create_api_router, create_api_router_with_queue, create_default_api_router,
+    create_reloadable_api_router, generate_api_key, hash_api_key, ApiConfig, ApiKeyScope,
+    ApiReloadHandle, ApiResponse, ApiState, AuthConfig, AuthResult, CorsConfig, IndexJobResponse,
+    IndexJobStatus, IndexStatusResponse, JobQueue, JobQueueConfig, JobStatus, PaginatedResponse,
+    RateLimitConfig, RateLimiter, SearchRequest, SearchResponse, SearchType, UploadDocumentRequest,
     UploadDocumentResponse,
 };
 pub use cache::{AuditCache, CacheEntry, CacheStats};
@@ -114,70 +123,83 @@ pub use code_chunker::{
     CodeChunker, DedupEntry, DedupIndex, EntityType,
 };
 pub use code_review::{
-    CodeReview, CodeReviewer, FileReview, IssueSeverity, ReviewIssue, ReviewStats,
+    CodeReview, CodeReviewer, FileReview, GithubReviewComment, IssueSeverity, ReviewIssue,
+    ReviewPolicy, ReviewStats, ReviewVerdict,
 };
 pub use config::Config;
 pub use context::{ContextBuilder as OldContextBuilder, GlobalContextBundle};
 pub use context_builder::{Context, ContextBuilder, ContextFile, QueryBuilder};
 pub use cost_tracker::{
-    BudgetStatus, CostStats, CostTracker, OperationCost, SavingsReport, StaticDecisionRecord,
-    TokenUsage,
+    BudgetStatus, CostStats, CostSummary, CostTracker, DailyCost, ModelCost, OperationCost,
+    SavingsReport, StaticDecisionRecord, TokenUsage,
 };
+pub use coverage::{CoverageReport, FileCoverage, FunctionCoverage};
 pub use db::{
     add_repository, create_note, create_task, delete_note, get_next_task, get_note, get_repository,
     get_repository_by_path, get_stats, init_db, list_notes, list_repositories, list_tasks,
     remove_repository, search_notes, update_note_status, update_repository_analysis,
     update_task_status, DbError, DbResult, DbStats, Note, Repository, Task,
 };
-pub use directory_tree::{DirectoryTreeBuilder, Hotspot, TreeSummary};
+pub use directory_tree::{DirectoryTreeBuilder, Hotspot, SavedTree, TreeSummary};
 pub use doc_generator::{DocGenerator, FunctionDoc, ModuleDoc, ParameterDoc, ReadmeContent};
 pub use embeddings::{
-    Embedding, EmbeddingConfig, EmbeddingGenerator, EmbeddingModelType, EmbeddingStats,
+    Embedding, EmbeddingBackend, EmbeddingBackendConfig, EmbeddingConfig, EmbeddingGenerator,
+    EmbeddingModelType, EmbeddingStats, LocalEmbeddingBackend, RemoteEmbeddingBackend,
+    RemoteEmbeddingConfig,
 };
 pub use enhanced_scanner::EnhancedScanner;
 pub use error::{AuditError, Result};
 pub use formatter::{BatchFormatResult, CodeFormatter, FormatMode, FormatResult, Formatter};
-pub use git::GitManager;
-pub use grok_client::{FileScoreResult, GrokClient, QuickAnalysisResult};
+pub use git::{GitManager, PullResult};
+pub use grok_client::{
+    Conversation, ConversationMessage, FileScoreResult, GrokClient, QuickAnalysisResult,
+    ScoredIssue,
+};
 pub use grok_reasoning::{
-    analyze_all_batches, BatchAnalysisResult, FileAnalysisResult as GrokFileAnalysisResult,
-    FileBatch, FileForAnalysis, GrokReasoningClient, IdentifiedIssue, Improvement, RetryConfig,
+    analyze_all_batches, analyze_all_batches_resumable, BatchAnalysisResult, BatchJournal,
+    BatchProgress, BatchRunSummary, FileAnalysisResult as GrokFileAnalysisResult, FileBatch,
+    FileForAnalysis, GrokReasoningClient, IdentifiedIssue, Improvement, RetryConfig, RunSummary,
+    ToolInvocation, TruncatedReason,
 };
 pub use indexing::{
-    BatchIndexer, DocumentIndexer, IndexingConfig, IndexingProgress, IndexingResult, IndexingStage,
+    BatchIndexer, DocumentIndexer, EmbeddingFingerprint, IndexingConfig, IndexingProgress,
+    IndexingResult, IndexingStage,
 };
 pub use llm::{
     GrokAnalyzer, ProjectPhase, ProjectPlan, StandardizationIssue, StandardizationReport,
     TodoAnalysis,
 };
 pub use llm_audit::{
-    ArchitectureInsights, AuditMode, FileAnalysis, FileLlmAnalysis, FileRelationships,
-    FullAuditResult, LlmAuditor, MasterReview, Recommendation, RegularAuditResult, SecurityConcern,
+    ArchitectureInsights, AuditLlm, AuditMode, AuditUsage, FileAnalysis, FileLlmAnalysis,
+    FileRelationships, FullAuditResult, JanusAuditReport, JanusMapping, JanusMappings, LlmAuditor,
+    MappingCheck, MappingReport, MasterReview, Recommendation, RegularAuditResult, SecurityConcern,
     TechDebtArea,
 };
 pub use llm_config::{
-    claude_models, CacheConfig, FileSelectionConfig, LimitsConfig, LlmConfig, ProviderConfig,
-    LLM_CONFIG_FILE,
+    claude_models, default_focus_heuristics, CacheConfig, FileSelectionConfig, FocusHeuristic,
+    LimitsConfig, LlmConfig, ModelPricing, ProviderConfig, LLM_CONFIG_FILE,
 };
+pub use path_filter::PathFilter;
 pub use query_router::{Action, QueryIntent, QueryRouter, RoutingStats, UserContext};
-pub use query_templates::{QueryTemplate, TemplateCategory, TemplateRegistry};
+pub use query_templates::{QueryTemplate, TemplateCategory, TemplateRegistry, TemplateSource};
 pub use queue::{
-    advance_stage, capture_note, capture_thought, capture_todo, enqueue, get_pending_items,
-    get_queue_item, get_queue_stats, get_retriable_items, mark_failed, update_analysis,
-    AnalysisResult, FileAnalysisResult as QueueFileAnalysisResult, LlmAnalyzer, ProcessorConfig,
-    QueueProcessor, QueueStats,
+    advance_stage, capture_note, capture_thought, capture_todo, delete_queue_item, enqueue,
+    get_dead_letter_items, get_pending_items, get_queue_item, get_queue_stats, get_retriable_items,
+    list_queue_items, mark_failed, purge_dead_letter, requeue_dead_letter, retry_queue_item,
+    update_analysis, AnalysisResult, FileAnalysisResult as QueueFileAnalysisResult, LlmAnalyzer,
+    ProcessorConfig, QueueEvent, QueueEventBus, QueueEventKind, QueueProcessor, QueueStats,
 };
 pub use refactor_assistant::{
-    CodeLocation, CodeSmell, CodeSmellType, EffortEstimate, PlanStep, RefactorAssistant,
-    RefactoringAnalysis, RefactoringExample, RefactoringPlan, RefactoringPriority,
-    RefactoringSuggestion, RefactoringType, Risk, SmellSeverity,
+    CalibrationFactor, CodeLocation, CodeSmell, CodeSmellType, DuplicateGroup, EffortEstimate,
+    PlanStep, RefactorAssistant, RefactoringAnalysis, RefactoringExample, RefactoringPlan,
+    RefactoringPriority, RefactoringSuggestion, RefactoringType, Risk, SmellSeverity,
 };
 pub use repo_analysis::{
     FileMetadata, LanguageStats, RepoAnalyzer, RepoNodeType, RepoTree, TreeNode,
 };
 pub use repo_cache::{
-    CacheSetParams, CacheStats as RepoCacheStats, CacheStrategy, CacheType, RepoCache,
-    RepoCacheEntry,
+    hash_source, CacheLimits, CacheSetParams, CacheStats as RepoCacheStats, CacheStrategy,
+    CacheType, RepoCache, RepoCacheEntry,
 };
 pub use repo_cache_sql::{
     CacheEntry as RepoCacheEntrySql, CacheStats as RepoCacheStatsSql, CacheTypeStats,
@@ -208,7 +230,7 @@ pub use scoring::{
 };
 pub use search::{
     SearchConfig, SearchFilters, SearchQuery, SearchResult, SearchResultMetadata, SearchStats,
-    SemanticSearcher,
+    SemanticSearcher, TagExpression,
 };
 pub use server::run_server;
 pub use static_analysis::{
@@ -217,17 +239,18 @@ pub use static_analysis::{
     SecurityFinding, SkipReason, StaticAnalysisResult, StaticAnalyzer, StaticAnalyzerConfig,
 };
 pub use tag_schema::{
-    CodeAge, CodeStatus, Complexity, DirectoryNode, IssuesSummary, NodeStats, NodeType, Priority,
-    SimpleIssueDetector, TagCategory, TagSchema, TagValidation,
+    CodeAge, CodeStatus, Complexity, DirectoryNode, FixConfidence, IssuesSummary, NodeStats,
+    NodeType, Priority, SimpleIssueDetector, TagCategory, TagFix, TagRules, TagSchema,
+    TagValidation,
 };
 pub use tags::TagScanner;
-pub use tasks::TaskGenerator;
+pub use tasks::{GitHubIssueSpec, TaskGenerator};
 pub use telemetry::{init_telemetry, shutdown_telemetry, TelemetryConfig};
 pub use test_generator::{
     Fixture, GeneratedTests, TestCase, TestFramework, TestGapAnalysis, TestGenerator, TestType,
     UntestFunction,
 };
-pub use tests_runner::{TestResults, TestRunner};
+pub use tests_runner::{TestOutcome, TestRecord, TestResults, TestRunner};
 pub use todo_scanner::{TodoItem, TodoPriority, TodoScanner, TodoSummary};
 pub use token_budget::{BudgetConfig, ModelTokenStats, MonthlyTracker, TokenPricing, TokenStats};
 pub use tree_state::{
@@ -243,12 +266,13 @@ pub use webhooks::{
     DeliveryStatus, WebhookConfig, WebhookDelivery, WebhookEndpoint, WebhookEvent, WebhookManager,
     WebhookPayload,
 };
+pub use workspace::{find_member, workspace_members, WorkspaceMember};
 
 /// Re-export commonly used types
 pub mod prelude {
     pub use crate::api::{
-        create_api_router, create_default_api_router, ApiConfig, ApiResponse, ApiState, AuthConfig,
-        RateLimitConfig, SearchRequest, SearchType,
+        create_api_router, create_api_router_with_queue, create_default_api_router, ApiConfig,
+        ApiResponse, ApiState, AuthConfig, RateLimitConfig, SearchRequest, SearchType,
     };
     pub use crate::chunking::{chunk_document, ChunkConfig, ChunkData};
     pub use crate::code_chunker::{
@@ -258,8 +282,8 @@ pub mod prelude {
     pub use crate::context::{ContextBuilder as OldContextBuilder, GlobalContextBundle};
     pub use crate::context_builder::{Context, ContextBuilder, ContextFile, QueryBuilder};
     pub use crate::cost_tracker::{
-        BudgetStatus, CostStats, CostTracker, OperationCost, SavingsReport, StaticDecisionRecord,
-        TokenUsage,
+        BudgetStatus, CostStats, CostSummary, CostTracker, DailyCost, ModelCost, OperationCost,
+        SavingsReport, StaticDecisionRecord, TokenUsage,
     };
     pub use crate::db::{
         add_repository, create_note, create_task, delete_note, get_next_task, get_note,
@@ -270,26 +294,32 @@ pub mod prelude {
     };
     pub use crate::directory_tree::{DirectoryTreeBuilder, Hotspot, TreeSummary};
     pub use crate::embeddings::{
-        Embedding, EmbeddingConfig, EmbeddingGenerator, EmbeddingModelType, EmbeddingStats,
+        Embedding, EmbeddingBackend, EmbeddingBackendConfig, EmbeddingConfig, EmbeddingGenerator,
+        EmbeddingModelType, EmbeddingStats, LocalEmbeddingBackend, RemoteEmbeddingBackend,
+        RemoteEmbeddingConfig,
     };
     pub use crate::enhanced_scanner::EnhancedScanner;
     pub use crate::error::{AuditError, Result};
     pub use crate::git::GitManager;
     pub use crate::grok_client::{FileScoreResult, GrokClient, QuickAnalysisResult};
     pub use crate::grok_reasoning::{
-        analyze_all_batches, BatchAnalysisResult, FileAnalysisResult as GrokFileAnalysisResult,
-        FileBatch, FileForAnalysis, GrokReasoningClient, IdentifiedIssue, Improvement, RetryConfig,
+        analyze_all_batches, analyze_all_batches_resumable, BatchAnalysisResult, BatchJournal,
+        BatchProgress, BatchRunSummary, FileAnalysisResult as GrokFileAnalysisResult, FileBatch,
+        FileForAnalysis, GrokReasoningClient, IdentifiedIssue, Improvement, RetryConfig,
+        RunSummary, ToolInvocation, TruncatedReason,
     };
     pub use crate::indexing::{
-        BatchIndexer, DocumentIndexer, IndexingConfig, IndexingProgress, IndexingResult,
-        IndexingStage,
+        BatchIndexer, DocumentIndexer, EmbeddingFingerprint, IndexingConfig, IndexingProgress,
+        IndexingResult, IndexingStage,
     };
     pub use crate::llm::{
         GrokAnalyzer, ProjectPhase, ProjectPlan, StandardizationIssue, StandardizationReport,
         TodoAnalysis,
     };
     pub use crate::query_router::{Action, QueryIntent, QueryRouter, RoutingStats, UserContext};
-    pub use crate::query_templates::{QueryTemplate, TemplateCategory, TemplateRegistry};
+    pub use crate::query_templates::{
+        QueryTemplate, TemplateCategory, TemplateRegistry, TemplateSource,
+    };
     pub use crate::queue::{
         advance_stage, capture_note, capture_thought, capture_todo, enqueue, get_pending_items,
         get_queue_item, get_queue_stats, get_retriable_items, mark_failed, update_analysis,
@@ -312,7 +342,7 @@ pub mod prelude {
     };
     pub use crate::search::{
         SearchConfig, SearchFilters, SearchQuery, SearchResult, SearchResultMetadata, SearchStats,
-        SemanticSearcher,
+        SemanticSearcher, TagExpression,
     };
 
     pub use crate::prompt_router::{PromptRouter, PromptRouterConfig, PromptTier, TierKind};
@@ -320,8 +350,9 @@ pub mod prelude {
         AnalysisRecommendation, StaticAnalysisResult, StaticAnalyzer,
     };
     pub use crate::tag_schema::{
-        CodeAge, CodeStatus, Complexity, DirectoryNode, IssuesSummary, NodeStats, NodeType,
-        Priority, SimpleIssueDetector, TagCategory, TagSchema, TagValidation,
+        CodeAge, CodeStatus, Complexity, DirectoryNode, FixConfidence, IssuesSummary, NodeStats,
+        NodeType, Priority, SimpleIssueDetector, TagCategory, TagFix, TagRules, TagSchema,
+        TagValidation,
     };
     pub use crate::tags::TagScanner;
     pub use crate::tasks::TaskGenerator;
@@ -0,0 +1,463 @@
+//! Dependency license report
+//!
+//! Reads the packages locked in a project's `Cargo.lock`, looks up each
+//! one's `license` in the unpacked crate sources under
+//! `$CARGO_HOME/registry/src` (or an SPDX map file for crates that aren't
+//! there), and groups the dependencies by license. When the project itself
+//! declares a permissive license, copyleft dependencies are flagged.
+//!
+//! SPDX expressions are evaluated the way a licensee would: `OR` (and the
+//! legacy `/`) picks the least restrictive choice, `AND` the most, and
+//! `WITH` exceptions are ignored. Missing or unparseable licenses are
+//! grouped as `unknown`.
+//!
+//! ```rust,no_run
+//! use rustassistant::license::LicenseScanner;
+//!
+//! # fn example() -> anyhow::Result<()> {
+//! let report = LicenseScanner::new().scan(".".as_ref())?;
+//! for conflict in &report.conflicts {
+//!     println!("{} {} is {}", conflict.name, conflict.version, conflict.license);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::enhanced_scanner::dependencies::parse_lockfile;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+/// Group name for dependencies without a usable license
+pub const UNKNOWN_LICENSE: &str = "unknown";
+
+/// How restrictive a license is, least first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum LicenseKind {
+    Permissive,
+    /// File- or library-level copyleft (MPL, LGPL, EPL)
+    WeakCopyleft,
+    /// Whole-work copyleft (GPL, AGPL)
+    StrongCopyleft,
+    Unknown,
+}
+
+impl LicenseKind {
+    /// Kind of a single SPDX license identifier
+    pub fn of_id(id: &str) -> Self {
+        let id = id.trim_end_matches('+');
+        let upper = id.to_ascii_uppercase();
+        const PERMISSIVE: &[&str] = &[
+            "MIT",
+            "MIT-0",
+            "APACHE-2.0",
+            "APACHE-1.1",
+            "BSD-2-CLAUSE",
+            "BSD-3-CLAUSE",
+            "BSD-3-CLAUSE-CLEAR",
+            "0BSD",
+            "ISC",
+            "ZLIB",
+            "UNLICENSE",
+            "CC0-1.0",
+            "BSL-1.0",
+            "UNICODE-DFS-2016",
+            "UNICODE-3.0",
+            "WTFPL",
+            "X11",
+            "PSF-2.0",
+            "PYTHON-2.0",
+            "OPENSSL",
+            "CDLA-PERMISSIVE-2.0",
+            "NCSA",
+        ];
+        if PERMISSIVE.contains(&upper.as_str()) {
+            LicenseKind::Permissive
+        } else if upper.starts_with("AGPL") || upper.starts_with("GPL") {
+            LicenseKind::StrongCopyleft
+        } else if upper.starts_with("LGPL")
+            || upper.starts_with("MPL")
+            || upper.starts_with("EPL")
+            || upper.starts_with("CDDL")
+            || upper.starts_with("EUPL")
+        {
+            LicenseKind::WeakCopyleft
+        } else {
+            LicenseKind::Unknown
+        }
+    }
+
+    pub fn is_copyleft(self) -> bool {
+        matches!(
+            self,
+            LicenseKind::WeakCopyleft | LicenseKind::StrongCopyleft
+        )
+    }
+}
+
+/// Kind of an SPDX expression such as `MIT OR Apache-2.0`, or `None` if it
+/// doesn't parse
+pub fn classify(expression: &str) -> Option<LicenseKind> {
+    let spaced = expression
+        .replace('(', " ( ")
+        .replace(')', " ) ")
+        .replace('/', " OR ");
+    let tokens: Vec<&str> = spaced.split_whitespace().collect();
+    let mut parser = ExprParser { tokens, pos: 0 };
+    let kind = parser.or_expr()?;
+    (parser.pos == parser.tokens.len()).then_some(kind)
+}
+
+/// Recursive-descent parser over SPDX tokens; `AND` binds tighter than `OR`
+struct ExprParser<'a> {
+    tokens: Vec<&'a str>,
+    pos: usize,
+}
+
+impl<'a> ExprParser<'a> {
+    fn next_is(&self, keyword: &str) -> bool {
+        self.tokens
+            .get(self.pos)
+            .is_some_and(|t| t.eq_ignore_ascii_case(keyword))
+    }
+
+    fn or_expr(&mut self) -> Option<LicenseKind> {
+        let mut kind = self.and_expr()?;
+        while self.next_is("OR") {
+            self.pos += 1;
+            kind = kind.min(self.and_expr()?);
+        }
+        Some(kind)
+    }
+
+    fn and_expr(&mut self) -> Option<LicenseKind> {
+        let mut kind = self.license()?;
+        while self.next_is("AND") {
+            self.pos += 1;
+            kind = kind.max(self.license()?);
+        }
+        Some(kind)
+    }
+
+    fn license(&mut self) -> Option<LicenseKind> {
+        let token = *self.tokens.get(self.pos)?;
+        self.pos += 1;
+        let kind = match token {
+            "(" => {
+                let kind = self.or_expr()?;
+                (self.tokens.get(self.pos) == Some(&")")).then_some(())?;
+                self.pos += 1;
+                kind
+            }
+            ")" => return None,
+            _ if ["AND", "OR", "WITH"]
+                .iter()
+                .any(|k| token.eq_ignore_ascii_case(k)) =>
+            {
+                return None
+            }
+            id => LicenseKind::of_id(id),
+        };
+        if self.next_is("WITH") {
+            // The exception only relaxes the license; its name is skipped
+            self.pos += 1;
+            self.tokens.get(self.pos)?;
+            self.pos += 1;
+        }
+        Some(kind)
+    }
+}
+
+/// A dependency and its declared license
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DependencyLicense {
+    pub name: String,
+    pub version: String,
+    /// SPDX expression, or [`UNKNOWN_LICENSE`]
+    pub license: String,
+    pub kind: LicenseKind,
+}
+
+/// A copyleft dependency of a permissively licensed project
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LicenseConflict {
+    pub name: String,
+    pub version: String,
+    pub license: String,
+    pub kind: LicenseKind,
+}
+
+/// Dependencies grouped by license
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LicenseReport {
+    /// The project's own `license`, if its Cargo.toml declares one
+    pub project_license: Option<String>,
+    pub project_kind: Option<LicenseKind>,
+    /// Dependencies by license expression, each group sorted by name
+    pub groups: BTreeMap<String, Vec<DependencyLicense>>,
+    /// Copyleft dependencies, only checked for permissive projects
+    pub conflicts: Vec<LicenseConflict>,
+}
+
+impl LicenseReport {
+    /// Build the report for a project license and its dependencies
+    pub fn new(project_license: Option<String>, dependencies: Vec<DependencyLicense>) -> Self {
+        let project_kind = project_license
+            .as_deref()
+            .map(|l| classify(l).unwrap_or(LicenseKind::Unknown));
+        let mut groups: BTreeMap<String, Vec<DependencyLicense>> = BTreeMap::new();
+        let mut conflicts = Vec::new();
+        for dependency in dependencies {
+            if project_kind == Some(LicenseKind::Permissive) && dependency.kind.is_copyleft() {
+                conflicts.push(LicenseConflict {
+                    name: dependency.name.clone(),
+                    version: dependency.version.clone(),
+                    license: dependency.license.clone(),
+                    kind: dependency.kind,
+                });
+            }
+            groups
+                .entry(dependency.license.clone())
+                .or_default()
+                .push(dependency);
+        }
+        for group in groups.values_mut() {
+            group.sort_by(|a, b| a.name.cmp(&b.name).then_with(|| a.version.cmp(&b.version)));
+        }
+        conflicts.sort_by(|a, b| b.kind.cmp(&a.kind).then_with(|| a.name.cmp(&b.name)));
+        Self {
+            project_license,
+            project_kind,
+            groups,
+            conflicts,
+        }
+    }
+
+    pub fn total(&self) -> usize {
+        self.groups.values().map(Vec::len).sum()
+    }
+
+    /// Dependencies whose license couldn't be determined
+    pub fn unknown(&self) -> &[DependencyLicense] {
+        self.groups
+            .get(UNKNOWN_LICENSE)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+}
+
+/// Looks up dependency licenses for a project
+pub struct LicenseScanner {
+    /// Unpacked crate sources, one directory per registry
+    registry_src: Vec<PathBuf>,
+    /// Licenses by `name@version` or `name`, from an SPDX map file
+    spdx_map: HashMap<String, String>,
+}
+
+impl Default for LicenseScanner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LicenseScanner {
+    /// Scanner reading the local cargo registry
+    pub fn new() -> Self {
+        let cargo_home = std::env::var_os("CARGO_HOME")
+            .map(PathBuf::from)
+            .or_else(|| dirs::home_dir().map(|home| home.join(".cargo")))
+            .unwrap_or_default();
+        let registry_src = std::fs::read_dir(cargo_home.join("registry").join("src"))
+            .map(|entries| {
+                entries
+                    .flatten()
+                    .map(|e| e.path())
+                    .filter(|p| p.is_dir())
+                    .collect()
+            })
+            .unwrap_or_default();
+        Self {
+            registry_src,
+            spdx_map: HashMap::new(),
+        }
+    }
+
+    /// Look crates up in these registry source directories instead
+    pub fn with_registry_src(mut self, dirs: Vec<PathBuf>) -> Self {
+        self.registry_src = dirs;
+        self
+    }
+
+    /// Load an SPDX map: a TOML or JSON table of `name` or `name@version`
+    /// to license expression. It takes precedence over crate metadata.
+    pub fn with_spdx_map(mut self, path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let map: HashMap<String, String> = if path.extension().is_some_and(|e| e == "json") {
+            serde_json::from_str(&content)?
+        } else {
+            toml::from_str(&content)?
+        };
+        self.spdx_map.extend(map);
+        Ok(self)
+    }
+
+    /// License report for the project at `root`
+    pub fn scan(&self, root: &Path) -> Result<LicenseReport> {
+        let lock_path = root.join("Cargo.lock");
+        let content = std::fs::read_to_string(&lock_path)
+            .with_context(|| format!("No Cargo.lock in {}", root.display()))?;
+        let dependencies = parse_lockfile(Path::new("Cargo.lock"), &content)?
+            .into_iter()
+            .map(|dep| {
+                let declared = self.license_of(&dep.name, &dep.version);
+                let kind = declared.as_deref().and_then(classify);
+                let (license, kind) = match (declared, kind) {
+                    (Some(license), Some(kind)) => (license, kind),
+                    _ => (UNKNOWN_LICENSE.to_string(), LicenseKind::Unknown),
+                };
+                DependencyLicense {
+                    name: dep.name,
+                    version: dep.version,
+                    license,
+                    kind,
+                }
+            })
+            .collect();
+        Ok(LicenseReport::new(project_license(root), dependencies))
+    }
+
+    /// Declared license of a crate, from the SPDX map or its Cargo.toml
+    fn license_of(&self, name: &str, version: &str) -> Option<String> {
+        if let Some(license) = self
+            .spdx_map
+            .get(&format!("{name}@{version}"))
+            .or_else(|| self.spdx_map.get(name))
+        {
+            return Some(license.clone());
+        }
+        self.registry_src.iter().find_map(|registry| {
+            let manifest = registry
+                .join(format!("{name}-{version}"))
+                .join("Cargo.toml");
+            manifest_license(&manifest)
+        })
+    }
+}
+
+/// `license` of a manifest's package, or of its workspace
+fn manifest_license(manifest: &Path) -> Option<String> {
+    let content = std::fs::read_to_string(manifest).ok()?;
+    let value: toml::Value = toml::from_str(&content).ok()?;
+    let license_in = |table: Option<&toml::Value>| {
+        table
+            .and_then(|t| t.get("license"))
+            .and_then(|l| l.as_str())
+            .map(str::to_string)
+    };
+    license_in(value.get("package"))
+        .or_else(|| license_in(value.get("workspace").and_then(|w| w.get("package"))))
+}
+
+/// The project's own license from its root Cargo.toml
+pub fn project_license(root: &Path) -> Option<String> {
+    manifest_license(&root.join("Cargo.toml"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_expressions() {
+        assert_eq!(classify("MIT"), Some(LicenseKind::Permissive));
+        assert_eq!(classify("MIT OR Apache-2.0"), Some(LicenseKind::Permissive));
+        assert_eq!(classify("MIT/Apache-2.0"), Some(LicenseKind::Permissive));
+        assert_eq!(
+            classify("GPL-3.0-only OR MIT"),
+            Some(LicenseKind::Permissive)
+        );
+        assert_eq!(
+            classify("MIT AND GPL-2.0+"),
+            Some(LicenseKind::StrongCopyleft)
+        );
+        assert_eq!(
+            classify("(MIT OR Apache-2.0) AND MPL-2.0"),
+            Some(LicenseKind::WeakCopyleft)
+        );
+        assert_eq!(
+            classify("Apache-2.0 WITH LLVM-exception"),
+            Some(LicenseKind::Permissive)
+        );
+        assert_eq!(classify("LicenseRef-Custom"), Some(LicenseKind::Unknown));
+        assert_eq!(classify("MIT OR"), None);
+        assert_eq!(classify("(MIT"), None);
+        assert_eq!(classify(""), None);
+    }
+
+    #[test]
+    fn test_scan_groups_and_flags_copyleft() {
+        let project = tempfile::tempdir().unwrap();
+        std::fs::write(
+            project.path().join("Cargo.toml"),
+            "[package]\nname = \"app\"\nversion = \"0.1.0\"\nlicense = \"MIT\"\n",
+        )
+        .unwrap();
+        let source = "source = \"registry+https://github.com/rust-lang/crates.io-index\"";
+        let lock: String = [
+            ("serde", "1.0.0"),
+            ("gplcrate", "0.2.0"),
+            ("mystery", "1.0.0"),
+            ("mapped", "2.0.0"),
+        ]
+        .iter()
+        .map(|(name, version)| {
+            format!("[[package]]\nname = \"{name}\"\nversion = \"{version}\"\n{source}\n\n")
+        })
+        .collect();
+        std::fs::write(project.path().join("Cargo.lock"), lock).unwrap();
+
+        let registry = tempfile::tempdir().unwrap();
+        for (dir, license) in [
+            ("serde-1.0.0", "MIT OR Apache-2.0"),
+            ("gplcrate-0.2.0", "GPL-3.0-only"),
+            ("mystery-1.0.0", "not a (license"),
+        ] {
+            std::fs::create_dir_all(registry.path().join(dir)).unwrap();
+            std::fs::write(
+                registry.path().join(dir).join("Cargo.toml"),
+                format!("[package]\nlicense = \"{license}\"\n"),
+            )
+            .unwrap();
+        }
+        let map = registry.path().join("spdx.toml");
+        std::fs::write(&map, "\"mapped@2.0.0\" = \"LGPL-2.1-or-later\"\n").unwrap();
+
+        let report = LicenseScanner::new()
+            .with_registry_src(vec![registry.path().to_path_buf()])
+            .with_spdx_map(&map)
+            .unwrap()
+            .scan(project.path())
+            .unwrap();
+
+        assert_eq!(report.project_kind, Some(LicenseKind::Permissive));
+        assert_eq!(report.total(), 4);
+        assert_eq!(report.unknown().len(), 1);
+        assert_eq!(report.unknown()[0].name, "mystery");
+        assert_eq!(report.groups["MIT OR Apache-2.0"][0].name, "serde");
+        let flagged: Vec<(&str, LicenseKind)> = report
+            .conflicts
+            .iter()
+            .map(|c| (c.name.as_str(), c.kind))
+            .collect();
+        assert_eq!(
+            flagged,
+            vec![
+                ("gplcrate", LicenseKind::StrongCopyleft),
+                ("mapped", LicenseKind::WeakCopyleft)
+            ]
+        );
+    }
+}
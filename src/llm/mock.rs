@@ -0,0 +1,300 @@
+//! Offline mock LLM provider
+//!
+//! **For testing and demos only.** [`MockProvider`] stands in for a real
+//! model when the provider is `mock` (`--provider mock`, `LLM_PROVIDER=mock`
+//! or `LlmClient::new_with_provider(_, "mock".into(), ..)`). It needs no API
+//! key, makes no network calls and reports zero token usage, so every call
+//! costs $0.
+//!
+//! Responses are deterministic and derived from the prompt: files are found
+//! by their `File:` / `### File:` headers, scores shrink as files get
+//! longer, and any file containing `unwrap` gets a canned issue. Structured
+//! requests get an instance of the requested JSON schema, so the output
+//! parses wherever a real model's would. None of it says anything about
+//! the code's actual quality.
+
+use super::compat::{Issue, LlmAnalysisResult, LlmUsage};
+use serde_json::{Map, Value};
+
+/// Model name reported for mock responses
+pub const MOCK_MODEL: &str = "mock";
+
+/// Description of the issue reported for files containing `unwrap`
+const UNWRAP_ISSUE: &str = "Uses unwrap(), which panics on None or Err";
+
+/// Suggested fix for [`UNWRAP_ISSUE`]
+const UNWRAP_SUGGESTION: &str = "Propagate the error with `?` or handle it explicitly";
+
+/// Deterministic stand-in for an LLM; see the [module docs](self)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MockProvider;
+
+impl MockProvider {
+    /// Answer a prompt. With a schema the content is a JSON instance of it;
+    /// without one it is a short plain-text analysis.
+    pub fn respond(&self, user: &str, schema: Option<&Value>) -> LlmAnalysisResult {
+        let files = MockFile::parse(user);
+        let whole = MockFile {
+            path: files
+                .first()
+                .map(|f| f.path.clone())
+                .unwrap_or_else(|| "input".to_string()),
+            content: user.to_string(),
+        };
+        let score = files
+            .iter()
+            .map(MockFile::score)
+            .min_by(f64::total_cmp)
+            .unwrap_or_else(|| whole.score());
+        let flagged: Vec<&MockFile> = files.iter().filter(|f| f.has_unwrap()).collect();
+
+        let content = match schema {
+            Some(schema) => {
+                let instance = Instance {
+                    files: &files,
+                    file: if files.len() == 1 { &files[0] } else { &whole },
+                };
+                instance.generate(schema, "").to_string()
+            }
+            None => format!(
+                "Mock analysis of {} file(s): score {:.0}/100, {} issue(s) found.",
+                files.len().max(1),
+                score,
+                flagged.len()
+            ),
+        };
+
+        LlmAnalysisResult {
+            summary: content.lines().take(3).collect::<Vec<_>>().join(" "),
+            content,
+            model: MOCK_MODEL.to_string(),
+            importance: score / 10.0,
+            security_rating: if flagged.is_empty() { "A" } else { "B" }.to_string(),
+            issues: flagged
+                .iter()
+                .map(|f| Issue {
+                    severity: "Medium".to_string(),
+                    description: format!("{}: {}", f.path, UNWRAP_ISSUE),
+                    suggestion: Some(UNWRAP_SUGGESTION.to_string()),
+                })
+                .collect(),
+            deprecated_files: Vec::new(),
+            missing_types: Vec::new(),
+            security_concerns: Vec::new(),
+            architecture_issues: Vec::new(),
+            tokens_used: Some(0),
+            usage: Some(LlmUsage::default()),
+        }
+    }
+}
+
+/// A file quoted in a prompt
+struct MockFile {
+    path: String,
+    content: String,
+}
+
+impl MockFile {
+    /// Files introduced by `File: <path>` or `### File: <path>` lines, each
+    /// running to the next header
+    fn parse(prompt: &str) -> Vec<Self> {
+        let mut files: Vec<Self> = Vec::new();
+        for line in prompt.lines() {
+            let header = line
+                .strip_prefix("### File: ")
+                .or_else(|| line.strip_prefix("File: "));
+            match (header, files.last_mut()) {
+                (Some(path), _) => files.push(Self {
+                    path: path.trim().to_string(),
+                    content: String::new(),
+                }),
+                (None, Some(file)) => {
+                    file.content.push_str(line);
+                    file.content.push('\n');
+                }
+                (None, None) => {}
+            }
+        }
+        files
+    }
+
+    /// 95 for a short file, one point less per 20 lines, never below 40
+    fn score(&self) -> f64 {
+        let lines = self.content.lines().count() as f64;
+        (95.0 - (lines / 20.0).floor()).max(40.0)
+    }
+
+    fn has_unwrap(&self) -> bool {
+        self.content.contains("unwrap")
+    }
+}
+
+/// Builds schema instances for one prompt
+struct Instance<'a> {
+    files: &'a [MockFile],
+    /// File the value being generated describes
+    file: &'a MockFile,
+}
+
+impl Instance<'_> {
+    /// A value matching `schema`; `key` is the property it will be stored
+    /// under, which picks canned content for issue-like arrays
+    fn generate(&self, schema: &Value, key: &str) -> Value {
+        if let Some(choices) = schema.get("enum").and_then(Value::as_array) {
+            let medium = choices.iter().find(|c| c.as_str() == Some("Medium"));
+            return medium
+                .or_else(|| choices.first())
+                .cloned()
+                .unwrap_or(Value::Null);
+        }
+
+        let kind = match schema.get("type") {
+            Some(Value::Array(types)) => types.iter().find_map(Value::as_str).unwrap_or("null"),
+            Some(Value::String(kind)) => kind.as_str(),
+            _ if schema.get("properties").is_some() => "object",
+            _ => "null",
+        };
+        match kind {
+            "object" => self.object(schema),
+            "array" => self.array(schema, key),
+            "string" => Value::String(self.string(key)),
+            "number" => serde_json::json!(self.number(schema)),
+            "integer" => serde_json::json!(self.number(schema).round() as i64),
+            "boolean" => Value::Bool(false),
+            _ => Value::Null,
+        }
+    }
+
+    fn object(&self, schema: &Value) -> Value {
+        let mut object = Map::new();
+        if let Some(properties) = schema.get("properties").and_then(Value::as_object) {
+            for (key, property) in properties {
+                object.insert(key.clone(), self.generate(property, key));
+            }
+        }
+        Value::Object(object)
+    }
+
+    fn array(&self, schema: &Value, key: &str) -> Value {
+        let items = schema.get("items").cloned().unwrap_or(Value::Null);
+        let per_file = items
+            .get("properties")
+            .is_some_and(|p| p.get("path").is_some() || p.get("file").is_some());
+
+        let values: Vec<Value> = if per_file && !self.files.is_empty() {
+            self.files
+                .iter()
+                .map(|file| {
+                    Instance {
+                        files: self.files,
+                        file,
+                    }
+                    .generate(&items, key)
+                })
+                .collect()
+        } else if is_issue_key(key) && self.file.has_unwrap() {
+            vec![self.generate(&items, key)]
+        } else {
+            Vec::new()
+        };
+        Value::Array(values)
+    }
+
+    fn string(&self, key: &str) -> String {
+        match key {
+            "path" | "file" => self.file.path.clone(),
+            "suggestion" | "recommendation" => UNWRAP_SUGGESTION.to_string(),
+            _ if is_issue_key(key) || key == "description" => {
+                format!("{}: {}", self.file.path, UNWRAP_ISSUE)
+            }
+            _ => format!("Mock {} for {}", key.replace('_', " "), self.file.path),
+        }
+    }
+
+    /// The file's score, clamped to the schema's bounds
+    fn number(&self, schema: &Value) -> f64 {
+        let bound = |name| schema.get(name).and_then(Value::as_f64);
+        let mut score = self.file.score();
+        if let Some(max) = bound("maximum") {
+            // Scale 0-100 scores down to ranges like 0-10
+            if max < 100.0 {
+                score = score / 100.0 * max;
+            }
+            score = score.min(max);
+        }
+        if let Some(min) = bound("minimum") {
+            score = score.max(min);
+        }
+        score
+    }
+}
+
+/// Whether a property holds findings, so it gets the canned unwrap issue
+fn is_issue_key(key: &str) -> bool {
+    [
+        "issue",
+        "concern",
+        "observation",
+        "weakness",
+        "problem",
+        "finding",
+    ]
+    .iter()
+    .any(|word| key.contains(word))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_responses_are_deterministic_and_schema_shaped() {
+        let schema = serde_json::json!({
+            "type": "object",
+            "properties": {
+                "files": { "type": "array", "items": {
+                    "type": "object",
+                    "properties": {
+                        "path": { "type": "string" },
+                        "importance": { "type": "string", "enum": ["Critical", "High", "Medium", "Low"] },
+                        "security_observations": { "type": "array", "items": { "type": "string" } },
+                        "score": { "type": "number", "minimum": 0, "maximum": 100 },
+                    },
+                }},
+            },
+        });
+        let long = "let x = 1;\n".repeat(200);
+        let prompt = format!(
+            "### File: src/a.rs\nfn a() {{ b().unwrap(); }}\n### File: src/b.rs\n{}",
+            long
+        );
+
+        let first = MockProvider.respond(&prompt, Some(&schema));
+        assert_eq!(
+            first.content,
+            MockProvider.respond(&prompt, Some(&schema)).content
+        );
+        assert_eq!(first.model, MOCK_MODEL);
+        assert_eq!(first.usage.unwrap().total(), 0);
+
+        let value: Value = serde_json::from_str(&first.content).unwrap();
+        let files = value["files"].as_array().unwrap();
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0]["path"], "src/a.rs");
+        assert_eq!(files[0]["importance"], "Medium");
+        assert_eq!(
+            files[0]["security_observations"].as_array().unwrap().len(),
+            1
+        );
+        assert!(files[1]["security_observations"]
+            .as_array()
+            .unwrap()
+            .is_empty());
+        // Longer files score lower
+        assert!(files[0]["score"].as_f64().unwrap() > files[1]["score"].as_f64().unwrap());
+
+        let plain = MockProvider.respond("File: src/a.rs\n\nlet v = x.unwrap();\n", None);
+        assert_eq!(plain.issues.len(), 1);
+        assert!(plain.issues[0].description.contains("unwrap"));
+    }
+}
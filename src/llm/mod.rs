@@ -4,6 +4,7 @@
 
 pub mod compat;
 pub mod grok;
+pub mod mock;
 pub mod simple_client;
 
 // Re-export main types
@@ -13,7 +14,10 @@ pub use grok::{
 };
 
 // Re-export compatibility types
-pub use compat::{FileAuditResult, LlmAnalysisResult, LlmClient};
+pub use compat::{FileAuditResult, LlmAnalysisResult, LlmClient, LlmCompletion, LlmUsage};
+
+// Offline provider for tests and demos
+pub use mock::MockProvider;
 
 // Re-export simple client for research system
 pub use simple_client::GrokClient;
@@ -4,16 +4,22 @@
 //! - **Regular Audit**: Holistic codebase analysis, entire codebase in context
 //! - **Full Audit**: File-by-file deep dive with scoring and master review
 
-use crate::cache::AuditCache;
-use crate::error::Result;
-use crate::llm::LlmClient;
-use crate::llm_config::LlmConfig;
-use crate::scoring::{CodebaseScore, FileScore, TodoBreakdown};
-use crate::types::Category;
+use crate::cache::{AuditCache, CacheEntry};
+use crate::error::{AuditError, Result};
+use crate::llm::{LlmClient, LlmCompletion};
+use crate::llm_config::{FocusHeuristic, LlmConfig};
+use crate::scanner::Scanner;
+use crate::scoring::{CodebaseScore, FileScore, FileScorer};
+use crate::tags::TagScanner;
+use crate::todo_scanner::TodoScanner;
+use crate::types::{AuditRequest, Category};
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::fs;
 use std::path::{Path, PathBuf};
-use tracing::{info, warn};
+use std::sync::Arc;
+use tracing::{debug, info, warn};
 
 /// Audit mode selection
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -54,7 +60,7 @@ pub struct RegularAuditResult {
     /// Technical debt areas
     pub tech_debt_areas: Vec<TechDebtArea>,
 
-    /// Recommendations
+    /// Recommendations, highest [`Recommendation::priority_score`] first
     pub recommendations: Vec<Recommendation>,
 
     /// Overall health rating (0-100)
@@ -85,8 +91,100 @@ pub struct FullAuditResult {
     /// Architecture insights
     pub architecture_insights: ArchitectureInsights,
 
+    /// Per-file improvement suggestions, highest
+    /// [`Recommendation::priority_score`] first
+    #[serde(default)]
+    pub recommendations: Vec<Recommendation>,
+
     /// Overall health rating (0-100)
     pub overall_health: f64,
+
+    /// API usage and files left out by the budget
+    #[serde(default)]
+    pub usage: AuditUsage,
+}
+
+/// API usage of a full audit run
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AuditUsage {
+    /// Requests sent to the LLM
+    pub api_calls: usize,
+
+    /// Estimated prompt tokens sent
+    pub input_tokens: usize,
+
+    /// Estimated response tokens received
+    pub output_tokens: usize,
+
+    /// Input tokens served from the provider's prompt cache
+    #[serde(default)]
+    pub cached_input_tokens: usize,
+
+    /// Estimated cost in USD
+    pub estimated_cost_usd: f64,
+
+    /// Files whose analysis came from the cache
+    pub cached_files: usize,
+
+    /// Selected files not analyzed because the budget ran out
+    pub skipped_files: Vec<PathBuf>,
+
+    /// Requests served per model; more than one entry means a fallback
+    /// model stood in for the primary
+    #[serde(default)]
+    pub models_used: BTreeMap<String, usize>,
+}
+
+impl AuditUsage {
+    /// Whether another request of this size stays within the limits
+    fn within_budget(&self, config: &LlmConfig, input_tokens: usize, output_tokens: usize) -> bool {
+        let limits = &config.limits;
+        if limits
+            .max_daily_calls
+            .is_some_and(|max| self.api_calls >= max)
+        {
+            return false;
+        }
+
+        let tokens = self.input_tokens + self.output_tokens + input_tokens + output_tokens;
+        if limits.max_daily_tokens.is_some_and(|max| tokens > max) {
+            return false;
+        }
+
+        let cost = self.estimated_cost_usd + config.estimate_cost(input_tokens, output_tokens);
+        !config.check_budget(cost).is_exceeded()
+    }
+
+    /// Record a completed request, using the provider's token counts when
+    /// reported (prompt-cache reads are billed at the cached rate) and
+    /// estimates otherwise
+    fn record_call(&mut self, config: &LlmConfig, input_tokens: usize, completion: &LlmCompletion) {
+        self.api_calls += 1;
+        *self
+            .models_used
+            .entry(completion.model.clone())
+            .or_default() += 1;
+
+        // Priced with the model that actually served the request
+        let model = &completion.model;
+        match completion.usage {
+            Some(usage) => {
+                self.input_tokens +=
+                    usage.input_tokens + usage.cache_read_tokens + usage.cache_write_tokens;
+                self.cached_input_tokens += usage.cache_read_tokens;
+                self.output_tokens += usage.output_tokens;
+                self.estimated_cost_usd += config.estimate_cost_with_cache(model, &usage);
+            }
+            None => {
+                let output_tokens =
+                    crate::tokenizer::count_tokens(&completion.content, &completion.model);
+                self.input_tokens += input_tokens;
+                self.output_tokens += output_tokens;
+                self.estimated_cost_usd +=
+                    config.estimate_cost_for_model(model, input_tokens, output_tokens);
+            }
+        }
+    }
 }
 
 /// Individual file analysis from Full audit
@@ -242,6 +340,112 @@ pub struct Recommendation {
 
     /// Expected benefit
     pub benefit: String,
+
+    /// Estimated effort (Small, Medium, Large)
+    #[serde(default = "default_effort")]
+    pub effort: String,
+
+    /// Files the recommendation applies to, relative to the project root
+    #[serde(default)]
+    pub files: Vec<PathBuf>,
+
+    /// Where the recommendation ranks; see [`Recommendation::score`].
+    /// Filled in by the auditor, not the model.
+    #[serde(default)]
+    pub priority_score: f64,
+}
+
+fn default_effort() -> String {
+    "Medium".to_string()
+}
+
+/// Importance assumed for a recommendation with no scored files: the
+/// midpoint of [`FileScore::importance`]
+const DEFAULT_FILE_IMPORTANCE: f64 = 50.0;
+
+/// Minimum [`Recommendation::impact_effort_ratio`] for a quick win, e.g.
+/// High priority at Medium effort or Medium priority at Small effort
+pub const QUICK_WIN_RATIO: f64 = 1.5;
+
+impl Recommendation {
+    /// Weight of the priority: Critical 4, High 3, Medium 2, anything else 1
+    pub fn severity_weight(&self) -> f64 {
+        match self.priority.to_lowercase().as_str() {
+            "critical" => 4.0,
+            "high" => 3.0,
+            "medium" => 2.0,
+            _ => 1.0,
+        }
+    }
+
+    /// Cost of the effort: Small 1, Large 3, anything else (Medium) 2
+    pub fn effort_points(&self) -> f64 {
+        match self.effort.to_lowercase().as_str() {
+            "small" | "trivial" => 1.0,
+            "large" | "very_large" => 3.0,
+            _ => 2.0,
+        }
+    }
+
+    /// `severity_weight / effort_points`: how much a recommendation is
+    /// worth per unit of work, from 1/3 (Low, Large) to 4 (Critical, Small)
+    pub fn impact_effort_ratio(&self) -> f64 {
+        self.severity_weight() / self.effort_points()
+    }
+
+    /// Whether the ratio reaches [`QUICK_WIN_RATIO`]
+    pub fn is_quick_win(&self) -> bool {
+        self.impact_effort_ratio() >= QUICK_WIN_RATIO
+    }
+
+    /// Priority score for a given importance (0-100) of the affected files:
+    ///
+    /// `priority_score = 12.5 × impact_effort_ratio × (1 + importance / 100)`
+    ///
+    /// The result runs from about 4.2 (Low, Large, unimportant files) to
+    /// 100 (Critical, Small, importance 100).
+    pub fn score(&self, importance: f64) -> f64 {
+        12.5 * self.impact_effort_ratio() * (1.0 + importance.clamp(0.0, 100.0) / 100.0)
+    }
+}
+
+/// Set each recommendation's [`Recommendation::priority_score`] and sort
+/// highest first; ties keep their original order. A recommendation's
+/// importance is the mean [`FileScore::importance`] of its files found in
+/// `importance`, or [`DEFAULT_FILE_IMPORTANCE`] if none are.
+pub fn prioritize_recommendations(
+    recommendations: &mut [Recommendation],
+    importance: &HashMap<PathBuf, f64>,
+) {
+    for rec in recommendations.iter_mut() {
+        let scores: Vec<f64> = rec
+            .files
+            .iter()
+            .filter_map(|f| importance.get(f).copied())
+            .collect();
+        let file_importance = if scores.is_empty() {
+            DEFAULT_FILE_IMPORTANCE
+        } else {
+            scores.iter().sum::<f64>() / scores.len() as f64
+        };
+        rec.priority_score = rec.score(file_importance);
+    }
+    recommendations.sort_by(|a, b| b.priority_score.total_cmp(&a.priority_score));
+}
+
+/// Up to `limit` quick wins, best ratio first, then by priority score
+pub fn quick_wins(recommendations: &[Recommendation], limit: usize) -> Vec<&Recommendation> {
+    let mut wins: Vec<&Recommendation> = recommendations
+        .iter()
+        .filter(|r| r.is_quick_win())
+        .collect();
+    wins.sort_by(|a, b| {
+        b.impact_effort_ratio()
+            .total_cmp(&a.impact_effort_ratio())
+            .then(b.priority_score.total_cmp(&a.priority_score))
+    });
+    wins.truncate(limit);
+    wins
 }
 
 impl Default for FileRelationships {
@@ -255,14 +459,49 @@ impl Default for FileRelationships {
     }
 }
 
+/// Backend the auditor sends prompts to; implemented by [`LlmClient`]
+#[async_trait::async_trait]
+pub trait AuditLlm: Send + Sync {
+    /// Request a JSON response matching `schema`
+    async fn complete_structured(
+        &self,
+        system: &str,
+        user: &str,
+        schema_name: &str,
+        schema: &serde_json::Value,
+    ) -> Result<LlmCompletion>;
+}
+
+#[async_trait::async_trait]
+impl AuditLlm for LlmClient {
+    async fn complete_structured(
+        &self,
+        system: &str,
+        user: &str,
+        schema_name: &str,
+        schema: &serde_json::Value,
+    ) -> Result<LlmCompletion> {
+        LlmClient::complete_structured(self, system, user, schema_name, schema).await
+    }
+}
+
 /// Enhanced LLM auditor with Regular and Full modes
 pub struct LlmAuditor {
-    llm_client: LlmClient,
+    llm_client: Arc<dyn AuditLlm>,
     cache: Option<AuditCache>,
     config: LlmConfig,
 }
 
 impl LlmAuditor {
+    /// Create an auditor around an existing LLM backend
+    pub fn with_llm(llm: Arc<dyn AuditLlm>, config: LlmConfig, cache: Option<AuditCache>) -> Self {
+        Self {
+            llm_client: llm,
+            cache,
+            config,
+        }
+    }
+
     /// Create a new LLM auditor with the specified provider and project root
     pub fn new_with_provider(provider: &str, project_root: &Path) -> Result<Self> {
         // Load config
@@ -298,6 +537,12 @@ impl LlmAuditor {
                 "anthropic".to_string(),
                 16000,
             ),
+            // Offline, deterministic responses; for testing only
+            "mock" => (
+                crate::llm::mock::MOCK_MODEL.to_string(),
+                "mock".to_string(),
+                config.provider.max_tokens,
+            ),
             _ => (
                 config.provider.default_model.clone(),
                 config.provider.default_provider.clone(),
@@ -311,7 +556,9 @@ impl LlmAuditor {
             model,
             max_tokens,
             config.provider.temperature,
-        )?;
+        )?
+        .with_prompt_caching(config.provider.prompt_caching)
+        .with_fallback_models(config.provider.fallback_models.clone());
 
         // Initialize cache if enabled
         let cache = if config.cache.enabled {
@@ -329,11 +576,7 @@ impl LlmAuditor {
             None
         };
 
-        Ok(Self {
-            llm_client,
-            cache,
-            config,
-        })
+        Ok(Self::with_llm(Arc::new(llm_client), config, cache))
     }
 
     /// Create a new LLM auditor (defaults to xai provider)
@@ -358,164 +601,525 @@ impl LlmAuditor {
     pub async fn run_regular_audit(
         &self,
         project_path: &Path,
-        _focus_areas: Vec<String>,
+        focus_areas: Vec<String>,
     ) -> Result<RegularAuditResult> {
         info!("🔍 Running Regular Audit on: {:?}", project_path);
 
-        // Collect file contents for analysis
-        let rust_files = self.find_rust_files(project_path)?;
-        let mut file_contents = Vec::new();
+        // Collect file contents for analysis, narrowed to the focus areas
+        let rust_files: Vec<PathBuf> = self
+            .find_rust_files(project_path)?
+            .iter()
+            .map(|p| p.strip_prefix(project_path).unwrap_or(p).to_path_buf())
+            .collect();
+        let rust_files = self.select_files_for_focus(rust_files, &focus_areas);
+        self.audit_files(project_path, &rust_files, &focus_areas)
+            .await
+    }
+
+    /// Regular audit over the given files (relative to `project_path`)
+    async fn audit_files(
+        &self,
+        project_path: &Path,
+        rust_files: &[PathBuf],
+        focus_areas: &[String],
+    ) -> Result<RegularAuditResult> {
+        let mut files_summary = Vec::new();
 
-        for path in rust_files.iter().take(10) {
-            if let Ok(content) = fs::read_to_string(path) {
-                let path_str = path.to_string_lossy().to_string();
-                file_contents.push((path_str, content));
+        for rel_path in rust_files.iter().take(10) {
+            let path = project_path.join(rel_path);
+            if let Ok(content) = fs::read_to_string(&path) {
+                let preview = content.lines().take(40).collect::<Vec<_>>().join("\n");
+                files_summary.push(format!("File: {}\n{}\n...", rel_path.display(), preview));
             }
         }
 
-        let file_refs: Vec<(&str, &str)> = file_contents
+        let system = "You are a senior software architect auditing an entire codebase. \
+            Assess architecture, patterns, security, code quality, technical debt and give \
+            prioritized recommendations. Respond only with JSON matching the provided schema.";
+        let focus = if focus_areas.is_empty() {
+            String::new()
+        } else {
+            format!("Focus areas: {}\n\n", focus_areas.join(", "))
+        };
+        let user = format!(
+            "{}Codebase Files:\n{}\n\nRate overall_health and confidence from 0 to 100.",
+            focus,
+            files_summary.join("\n\n")
+        );
+
+        let completion = self
+            .llm_client
+            .complete_structured(
+                system,
+                &user,
+                "regular_audit_result",
+                &regular_audit_schema(),
+            )
+            .await?;
+        let raw = completion.content;
+
+        let mut result = parse_regular_audit(&raw).inspect_err(|e| {
+            warn!("Regular audit response could not be parsed: {}", e);
+            debug!("Raw regular audit response:\n{}", raw);
+        })?;
+        let importance = self.file_importance(project_path, &result.recommendations);
+        prioritize_recommendations(&mut result.recommendations, &importance);
+        Ok(result)
+    }
+
+    /// [`FileScore::importance`] of each file the recommendations mention,
+    /// scored with [`FileScorer`]; files that can't be read are left out
+    fn file_importance(
+        &self,
+        project_path: &Path,
+        recommendations: &[Recommendation],
+    ) -> HashMap<PathBuf, f64> {
+        let files: BTreeSet<&PathBuf> = recommendations.iter().flat_map(|r| &r.files).collect();
+        if files.is_empty() {
+            return HashMap::new();
+        }
+        let (Ok(tag_scanner), Ok(todo_scanner)) = (TagScanner::new(), TodoScanner::new()) else {
+            return HashMap::new();
+        };
+        let scorer = FileScorer::new();
+
+        files
+            .into_iter()
+            .filter_map(|rel| {
+                let path = project_path.join(rel);
+                let content = fs::read_to_string(&path).ok()?;
+                let tags = tag_scanner.scan_file(&path).unwrap_or_default();
+                let todos = todo_scanner.scan_file(&path).unwrap_or_default();
+                let score = scorer.score_file(rel, &content, &tags, &todos).ok()?;
+                Some((rel.clone(), score.importance))
+            })
+            .collect()
+    }
+
+    /// Load JANUS mappings from `explicit`, else [`JANUS_MAPPINGS_FILE`] in
+    /// the project root or its `config/` directory, else the built-in set
+    pub fn load_janus_mappings(
+        project_root: &Path,
+        explicit: Option<&Path>,
+    ) -> Result<JanusMappings> {
+        let candidates = match explicit {
+            Some(path) => vec![path.to_path_buf()],
+            None => vec![
+                project_root.join(JANUS_MAPPINGS_FILE),
+                project_root.join("config").join(JANUS_MAPPINGS_FILE),
+            ],
+        };
+
+        for path in candidates {
+            if explicit.is_some() || path.exists() {
+                info!("Loading JANUS mappings from: {}", path.display());
+                let content = fs::read_to_string(&path).map_err(|e| {
+                    AuditError::config(format!("Failed to read {}: {}", path.display(), e))
+                })?;
+                return JanusMappings::from_toml(&content);
+            }
+        }
+
+        JanusMappings::from_toml(DEFAULT_JANUS_MAPPINGS)
+    }
+
+    /// Check without any LLM call that each mapped component exists in the
+    /// tree, either as a module named after it or a file annotated with
+    /// `@audit-tag: <region>`
+    pub fn verify_mappings(path: &Path, mappings: &JanusMappings) -> Result<MappingReport> {
+        let mut checks: Vec<MappingCheck> = mappings
+            .mappings
             .iter()
-            .map(|(p, c)| (p.as_str(), c.as_str()))
+            .map(|m| MappingCheck {
+                region: m.region.clone(),
+                component: m.component.clone(),
+                found: false,
+                files: Vec::new(),
+            })
             .collect();
 
-        // Use analyze_codebase for holistic analysis
-        let analysis = self.llm_client.analyze_codebase(&file_refs).await?;
-
-        // Parse into regular audit result
-        Ok(RegularAuditResult {
-            mode: AuditMode::Regular,
-            architecture_assessment: format!(
-                "Analyzed codebase with {} deprecated files, {} missing types, {} security concerns",
-                analysis.deprecated_files.len(),
-                analysis.missing_types.len(),
-                analysis.security_concerns.len()
-            ),
-            patterns: vec![
-                "Focus areas analyzed".to_string(),
-                format!("Security concerns: {}", analysis.security_concerns.len()),
-            ],
-            security_concerns: analysis
-                .security_concerns
-                .iter()
-                .map(|concern| SecurityConcern {
-                    severity: "Medium".to_string(),
-                    description: concern.clone(),
-                    affected_areas: vec![],
-                    recommendation: "Review and address".to_string(),
-                })
-                .collect(),
-            quality_observations: vec![
-                format!("Deprecated files: {}", analysis.deprecated_files.len()),
-                format!("Architecture issues: {}", analysis.architecture_issues.len()),
-            ],
-            tech_debt_areas: Vec::new(),
-            recommendations: vec![Recommendation {
-                priority: "High".to_string(),
-                category: "Architecture".to_string(),
-                recommendation: "Review and address identified issues".to_string(),
-                benefit: "Improved code quality and maintainability".to_string(),
-            }],
-            overall_health: 70.0,
-            confidence: 75.0,
+        let mut files_scanned = 0;
+        let walk = ignore::WalkBuilder::new(path).hidden(true).build();
+        for entry in walk.flatten() {
+            let file = entry.path();
+            let is_source = file
+                .extension()
+                .and_then(|e| e.to_str())
+                .is_some_and(|e| JANUS_SOURCE_EXTENSIONS.contains(&e));
+            if !file.is_file() || !is_source {
+                continue;
+            }
+            files_scanned += 1;
+
+            let rel = file.strip_prefix(path).unwrap_or(file);
+            let segments = mapping_segments(rel);
+            let annotations = fs::read_to_string(file)
+                .map(|content| mapping_annotations(&content))
+                .unwrap_or_default();
+
+            for (mapping, check) in mappings.mappings.iter().zip(checks.iter_mut()) {
+                let names: Vec<String> = std::iter::once(&mapping.region)
+                    .chain(&mapping.modules)
+                    .map(|n| normalize_mapping_name(n))
+                    .collect();
+                let by_path = segments.iter().any(|s| names.contains(s));
+                let by_annotation = annotations
+                    .iter()
+                    .any(|a| names.iter().any(|n| a.contains(n.as_str())));
+                if by_path || by_annotation {
+                    check.found = true;
+                    check.files.push(rel.to_path_buf());
+                }
+            }
+        }
+
+        for check in &mut checks {
+            check.files.sort();
+        }
+        Ok(MappingReport {
+            checks,
+            files_scanned,
+        })
+    }
+
+    /// Audit a JANUS codebase with the mapping focus areas. With `verify`,
+    /// [`Self::verify_mappings`] runs first: only files implementing found
+    /// components are sent, missing components become recommendations, and
+    /// no API call is made when nothing is found.
+    pub async fn run_janus_audit(
+        &self,
+        project_path: &Path,
+        mappings: &JanusMappings,
+        verify: bool,
+    ) -> Result<JanusAuditReport> {
+        if !verify {
+            let audit = self
+                .run_regular_audit(project_path, mappings.focus_areas())
+                .await?;
+            return Ok(JanusAuditReport {
+                mapping_report: None,
+                audit: Some(audit),
+            });
+        }
+
+        let report = Self::verify_mappings(project_path, mappings)?;
+        info!(
+            "JANUS mappings: {}/{} components found",
+            report.found_count(),
+            report.checks.len()
+        );
+
+        let found: Vec<&JanusMapping> = mappings
+            .mappings
+            .iter()
+            .zip(&report.checks)
+            .filter(|(_, check)| check.found)
+            .map(|(mapping, _)| mapping)
+            .collect();
+        if found.is_empty() {
+            warn!("No mapped JANUS components found; skipping LLM audit");
+            return Ok(JanusAuditReport {
+                mapping_report: Some(report),
+                audit: None,
+            });
+        }
+
+        let focus: Vec<String> = found.iter().map(|m| m.focus_area()).collect();
+        let files: Vec<PathBuf> = report
+            .checks
+            .iter()
+            .flat_map(|c| c.files.iter().cloned())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .filter(|f| f.extension().and_then(|e| e.to_str()) == Some("rs"))
+            .collect();
+
+        let mut audit = self.audit_files(project_path, &files, &focus).await?;
+        audit.recommendations.extend(report.findings());
+        let importance = self.file_importance(project_path, &audit.recommendations);
+        prioritize_recommendations(&mut audit.recommendations, &importance);
+
+        Ok(JanusAuditReport {
+            mapping_report: Some(report),
+            audit: Some(audit),
         })
     }
 
+    /// Narrow `files` to those matching any of the focus keywords (see
+    /// [`default_focus_heuristics`]). Returns `files` unchanged when `focus`
+    /// is empty or nothing matches.
+    pub fn select_files_for_focus(&self, files: Vec<PathBuf>, focus: &[String]) -> Vec<PathBuf> {
+        let heuristics: Vec<FocusHeuristic> = focus
+            .iter()
+            .filter(|f| !f.trim().is_empty())
+            .map(|f| self.config.focus_heuristic(f))
+            .collect();
+        if heuristics.is_empty() {
+            return files;
+        }
+
+        let total = files.len();
+        let (selected, rest): (Vec<PathBuf>, Vec<PathBuf>) = files
+            .into_iter()
+            .partition(|path| heuristics.iter().any(|h| h.matches(path)));
+        if selected.is_empty() {
+            warn!(
+                "No files match focus {:?}; analyzing all {} files",
+                focus, total
+            );
+            return rest;
+        }
+
+        info!(
+            "Focus {:?} narrowed analysis to {} of {} files",
+            focus,
+            selected.len(),
+            total
+        );
+        selected
+    }
+
     /// Run a full audit (file-by-file deep dive)
+    ///
+    /// Scans the project, ranks files with [`FileScorer`], analyzes the
+    /// selected ones in batches (reusing cached analyses), derives file
+    /// relationships from Rust imports and synthesizes a master review.
+    /// Stops sending requests once the `LlmConfig` limits would be exceeded;
+    /// the files left out are listed in [`AuditUsage::skipped_files`].
     pub async fn run_full_audit(&self, project_path: &Path) -> Result<FullAuditResult> {
         info!("🔬 Running Full Audit on: {:?}", project_path);
 
-        // 1. Collect and analyze top files
-        let mut file_analyses = Vec::new();
-        let mut analyzed_paths = Vec::new();
-
-        // Find Rust files
-        let rust_files = self.find_rust_files(project_path)?;
-
-        // Analyze top 10 files to avoid excessive API calls
-        for path in rust_files.iter().take(10) {
-            if let Ok(content) = fs::read_to_string(path) {
-                // Use Rust category for .rs files
-                // Detect category from file path
-                let category = Category::from_path(path.to_str().unwrap_or(""));
-                let analysis = self
-                    .llm_client
-                    .analyze_file(path, &content, category)
-                    .await?;
-
-                // Create a basic score
-                let mut score = FileScore::new(path.clone());
-                score.importance = analysis.importance * 100.0;
-
-                // Convert letter grade to numeric score (A=100, B=80, C=60, D=40, F=20)
-                score.security = match analysis.security_rating.trim().to_uppercase().as_str() {
-                    "A" => 100.0,
-                    "B" => 80.0,
-                    "C" => 60.0,
-                    "D" => 40.0,
-                    "F" => 20.0,
-                    _ => 50.0, // Default/unknown
-                };
-
-                score.risk = if analysis.issues.iter().any(|i| i.severity == "critical") {
-                    90.0
-                } else if analysis.issues.iter().any(|i| i.severity == "high") {
-                    70.0
-                } else {
-                    30.0
-                };
-
-                file_analyses.push(FileAnalysis {
-                    path: path.clone(),
-                    score: score.clone(),
-                    llm_analysis: FileLlmAnalysis {
-                        purpose: "Analyzed file".to_string(),
-                        importance: analysis.importance.to_string(),
-                        key_functionality: vec![analysis.summary.clone()],
-                        dependencies: vec![],
-                        security_observations: analysis
-                            .issues
-                            .iter()
-                            .filter_map(|i| {
-                                if i.severity == "critical" || i.severity == "high" {
-                                    Some(i.description.clone())
-                                } else {
-                                    None
-                                }
-                            })
-                            .collect(),
-                        quality_assessment: format!(
-                            "Security rating: {}",
-                            analysis.security_rating
-                        ),
-                        improvement_suggestions: analysis
-                            .issues
-                            .iter()
-                            .filter_map(|i| i.suggestion.clone())
-                            .collect(),
-                    },
-                    relationships: FileRelationships::default(),
-                });
-
-                analyzed_paths.push(path.clone());
+        // 1. Scan and score every file
+        let scanner = Scanner::new(
+            project_path.to_path_buf(),
+            self.config.file_selection.max_file_size_bytes,
+            false,
+        )?;
+        let report = scanner.scan(&AuditRequest {
+            repository: project_path.to_string_lossy().to_string(),
+            branch: None,
+            enable_llm: true,
+            focus: Vec::new(),
+            include_tests: false,
+        })?;
+
+        let scorer = FileScorer::new();
+        let todo_scanner = TodoScanner::new()?;
+        let mut scanned = Vec::new();
+        for file in report.files {
+            // Skip hidden directories such as the audit cache itself
+            let hidden = file
+                .path
+                .components()
+                .any(|c| c.as_os_str().to_string_lossy().starts_with('.'));
+            if hidden {
+                continue;
+            }
+            let full_path = project_path.join(&file.path);
+            let Ok(content) = fs::read_to_string(&full_path) else {
+                continue;
+            };
+            let todos = todo_scanner.scan_file(&full_path).unwrap_or_default();
+            let score = scorer.score_file(&file.path, &content, &file.tags, &todos)?;
+            scanned.push(AuditCandidate {
+                path: file.path,
+                category: file.category,
+                content,
+                score,
+            });
+        }
+        scanned.sort_by(|a, b| {
+            b.score
+                .maintenance_priority
+                .total_cmp(&a.score.maintenance_priority)
+        });
+
+        let all_scores: Vec<FileScore> = scanned.iter().map(|c| c.score.clone()).collect();
+        let mut codebase_score = CodebaseScore::from_file_scores(&all_scores);
+        codebase_score.total_files = scanned.len();
+
+        // 2. Relationships across all scanned files, not just analyzed ones
+        let mut relationships = build_relationships(&scanned);
+
+        // 3. Select files within the configured thresholds
+        let selected: Vec<AuditCandidate> = scanned
+            .into_iter()
+            .filter(|c| {
+                self.config.should_analyze_file(
+                    &c.path,
+                    c.content.len(),
+                    c.score.importance,
+                    c.score.risk,
+                )
+            })
+            .take(self.config.file_selection.max_files_per_run)
+            .collect();
+        info!("Selected {} files for analysis", selected.len());
+
+        // 4. Per-file analysis in batches
+        let mut usage = AuditUsage::default();
+        let mut llm_results: HashMap<PathBuf, FileLlmAnalysis> = HashMap::new();
+        let mut pending = Vec::new();
+        for candidate in &selected {
+            match self.cached_file_analysis(candidate) {
+                Some(analysis) => {
+                    usage.cached_files += 1;
+                    llm_results.insert(candidate.path.clone(), analysis);
+                }
+                None => pending.push(candidate),
             }
         }
 
-        // 3. Build codebase score from analyses
-        let codebase_score =
-            self.build_codebase_score_from_analyses(&file_analyses, rust_files.len())?;
+        let batches = batch_candidates(&pending);
+        let total_batches = batches.len();
+        for (i, batch) in batches.into_iter().enumerate() {
+            let (system, user) = file_batch_prompt(&batch);
+            let input_tokens = self.config.count_tokens(&system) + self.config.count_tokens(&user);
+            let output_tokens = batch.len() * FILE_ANALYSIS_OUTPUT_TOKENS;
+            if !usage.within_budget(&self.config, input_tokens, output_tokens) {
+                warn!("LLM budget reached; skipping remaining files");
+                usage
+                    .skipped_files
+                    .extend(batch.iter().map(|c| c.path.clone()));
+                continue;
+            }
 
-        // 4. Generate master review
-        let master_review = self.generate_master_review(&file_analyses).await?;
+            info!(
+                "Analyzing batch {}/{} ({} files)",
+                i + 1,
+                total_batches,
+                batch.len()
+            );
+            let completion = match self
+                .llm_client
+                .complete_structured(&system, &user, "file_batch_analysis", &file_batch_schema())
+                .await
+            {
+                Ok(completion) => completion,
+                Err(e) => {
+                    warn!("Batch {} failed: {}", i + 1, e);
+                    continue;
+                }
+            };
+            usage.record_call(&self.config, input_tokens, &completion);
+            let raw = completion.content;
+
+            let response: FileBatchResponse = match parse_json_response(&raw, "file batch", |_| {})
+            {
+                Ok(response) => response,
+                Err(e) => {
+                    warn!("Batch {} response could not be parsed: {}", i + 1, e);
+                    debug!("Raw batch response:\n{}", raw);
+                    continue;
+                }
+            };
+            let mut by_path: HashMap<String, FileLlmAnalysis> = response
+                .files
+                .into_iter()
+                .map(|entry| (normalize_path(&entry.path), entry.analysis))
+                .collect();
+            for candidate in batch {
+                let key = normalize_path(&candidate.path.to_string_lossy());
+                match by_path.remove(&key) {
+                    Some(analysis) => {
+                        self.cache_file_analysis(candidate, &analysis);
+                        llm_results.insert(candidate.path.clone(), analysis);
+                    }
+                    None => warn!("No analysis returned for {}", candidate.path.display()),
+                }
+            }
+        }
+
+        if let Some(ref cache) = self.cache {
+            if let Err(e) = cache.save() {
+                warn!("Failed to save audit cache: {}", e);
+            }
+        }
+
+        let file_analyses: Vec<FileAnalysis> = selected
+            .into_iter()
+            .filter_map(|candidate| {
+                let llm_analysis = llm_results.remove(&candidate.path)?;
+                let relationships = relationships.remove(&candidate.path).unwrap_or_default();
+                Some(FileAnalysis {
+                    path: candidate.path,
+                    score: candidate.score,
+                    llm_analysis,
+                    relationships,
+                })
+            })
+            .collect();
+        let file_analyses = with_related_files(file_analyses);
 
-        // 5. Identify critical files
-        let critical_files: Vec<PathBuf> = file_analyses
+        // 5. Master review from the aggregate
+        let (master_review, architecture_patterns, anti_patterns) = self
+            .generate_master_review(&file_analyses, &codebase_score, &mut usage)
+            .await;
+
+        // 6. Critical files, riskiest first
+        let mut critical: Vec<&FileAnalysis> = file_analyses
             .iter()
-            .filter(|fa| fa.score.risk > 70.0 || fa.score.importance > 80.0)
-            .take(5)
-            .map(|fa| fa.path.clone())
+            .filter(|fa| {
+                fa.llm_analysis.importance.eq_ignore_ascii_case("critical")
+                    || fa.score.needs_immediate_attention()
+            })
+            .collect();
+        critical.sort_by(|a, b| b.score.risk.total_cmp(&a.score.risk));
+        let critical_files: Vec<PathBuf> = critical.iter().map(|fa| fa.path.clone()).collect();
+
+        let avg_fan_out = if file_analyses.is_empty() {
+            0.0
+        } else {
+            file_analyses
+                .iter()
+                .map(|fa| fa.relationships.depends_on.len())
+                .sum::<usize>() as f64
+                / file_analyses.len() as f64
+        };
+        let architecture_insights = ArchitectureInsights {
+            patterns: architecture_patterns,
+            separation_of_concerns: master_review.architecture_quality,
+            modularity: (100.0 - avg_fan_out * 10.0).clamp(0.0, 100.0),
+            dependency_complexity: match avg_fan_out {
+                f if f < 2.0 => "Low",
+                f if f < 5.0 => "Moderate",
+                _ => "High",
+            }
+            .to_string(),
+            anti_patterns,
+        };
+
+        let mut recommendations: Vec<Recommendation> = file_analyses
+            .iter()
+            .flat_map(|fa| {
+                fa.llm_analysis
+                    .improvement_suggestions
+                    .iter()
+                    .map(|suggestion| Recommendation {
+                        priority: fa.llm_analysis.importance.clone(),
+                        category: "Improvement".to_string(),
+                        recommendation: suggestion.clone(),
+                        benefit: String::new(),
+                        effort: default_effort(),
+                        files: vec![fa.path.clone()],
+                        priority_score: 0.0,
+                    })
+            })
             .collect();
+        let importance: HashMap<PathBuf, f64> = file_analyses
+            .iter()
+            .map(|fa| (fa.path.clone(), fa.score.importance))
+            .collect();
+        prioritize_recommendations(&mut recommendations, &importance);
 
         let overall_health = codebase_score.overall_health;
+        info!(
+            "Full audit complete: {} files analyzed ({} cached), {} API calls, ~${:.4}",
+            file_analyses.len(),
+            usage.cached_files,
+            usage.api_calls,
+            usage.estimated_cost_usd
+        );
 
         Ok(FullAuditResult {
             mode: AuditMode::Full,
@@ -523,14 +1127,10 @@ impl LlmAuditor {
             codebase_score,
             master_review,
             critical_files,
-            architecture_insights: ArchitectureInsights {
-                patterns: vec!["Rust codebase".to_string()],
-                separation_of_concerns: 65.0,
-                modularity: 70.0,
-                dependency_complexity: "Moderate".to_string(),
-                anti_patterns: vec![],
-            },
+            architecture_insights,
+            recommendations,
             overall_health,
+            usage,
         })
     }
 
@@ -590,98 +1190,938 @@ impl LlmAuditor {
         Ok(results)
     }
 
-    /// Build codebase score from file analyses
-    fn build_codebase_score_from_analyses(
+    /// Cached analysis for a file whose content hasn't changed
+    fn cached_file_analysis(&self, candidate: &AuditCandidate) -> Option<FileLlmAnalysis> {
+        let cache = self.cache.as_ref()?;
+        let key = full_audit_cache_key(&candidate.path);
+        let entry = cache.get(&key, &candidate.content).ok()??;
+        let analysis = serde_json::from_value(entry.analysis).ok()?;
+        cache.record_hit(entry.tokens_used.unwrap_or(0), 0.0);
+        Some(analysis)
+    }
+
+    fn cache_file_analysis(&self, candidate: &AuditCandidate, analysis: &FileLlmAnalysis) {
+        let Some(ref cache) = self.cache else {
+            return;
+        };
+        let Ok(value) = serde_json::to_value(analysis) else {
+            return;
+        };
+        cache.record_miss();
+        let entry = CacheEntry {
+            file_path: candidate.path.to_string_lossy().to_string(),
+            content_hash: cache.hash_content(&candidate.content),
+            analyzed_at: chrono::Utc::now().to_rfc3339(),
+            provider: self.config.provider.default_provider.clone(),
+            model: self.config.provider.default_model.clone(),
+            analysis: value,
+            tokens_used: Some(
+                self.config.count_tokens(&candidate.content) + FILE_ANALYSIS_OUTPUT_TOKENS,
+            ),
+            file_size: candidate.content.len(),
+        };
+        if let Err(e) = cache.set(full_audit_cache_key(&candidate.path), entry) {
+            warn!(
+                "Failed to cache analysis for {}: {}",
+                candidate.path.display(),
+                e
+            );
+        }
+    }
+
+    /// Synthesize the master review, falling back to a summary computed
+    /// from the file analyses when the budget is spent or the call fails.
+    /// Also returns the architecture patterns and anti-patterns identified.
+    async fn generate_master_review(
         &self,
         analyses: &[FileAnalysis],
-        total_files: usize,
-    ) -> Result<CodebaseScore> {
-        let mut avg_score = FileScore::new(PathBuf::from("average"));
-
-        if !analyses.is_empty() {
-            let count = analyses.len() as f64;
-            for analysis in analyses {
-                avg_score.importance += analysis.score.importance;
-                avg_score.risk += analysis.score.risk;
-                avg_score.security += analysis.score.security;
-                avg_score.quality += analysis.score.quality;
+        codebase_score: &CodebaseScore,
+        usage: &mut AuditUsage,
+    ) -> (MasterReview, Vec<String>, Vec<String>) {
+        let fallback = || {
+            let mut layers: Vec<String> = analyses
+                .iter()
+                .map(|fa| format!("{} layer", fa.relationships.layer))
+                .collect();
+            layers.sort();
+            layers.dedup();
+            (
+                summarize_master_review(analyses, codebase_score),
+                layers,
+                Vec::new(),
+            )
+        };
+        if analyses.is_empty() {
+            return fallback();
+        }
+
+        let (system, user) = master_review_prompt(analyses, codebase_score);
+        let input_tokens = self.config.count_tokens(&system) + self.config.count_tokens(&user);
+        if !usage.within_budget(&self.config, input_tokens, MASTER_REVIEW_OUTPUT_TOKENS) {
+            warn!("LLM budget reached; summarizing master review locally");
+            return fallback();
+        }
+
+        let completion = match self
+            .llm_client
+            .complete_structured(&system, &user, "master_review", &master_review_schema())
+            .await
+        {
+            Ok(completion) => completion,
+            Err(e) => {
+                warn!("Master review failed: {}", e);
+                return fallback();
+            }
+        };
+        usage.record_call(&self.config, input_tokens, &completion);
+        let raw = completion.content;
+
+        match parse_json_response::<MasterReviewResponse>(&raw, "master review", |_| {}) {
+            Ok(response) => (
+                response.review,
+                response.architecture_patterns,
+                response.anti_patterns,
+            ),
+            Err(e) => {
+                warn!("Master review response could not be parsed: {}", e);
+                debug!("Raw master review response:\n{}", raw);
+                fallback()
             }
-            avg_score.importance /= count;
-            avg_score.risk /= count;
-            avg_score.security /= count;
-            avg_score.quality /= count;
         }
+    }
+}
 
-        let critical_files: Vec<PathBuf> = analyses
-            .iter()
-            .filter(|a| a.score.risk > 70.0)
-            .map(|a| a.path.clone())
-            .collect();
+/// Built-in JANUS mappings, used when the project has no mapping file
+const DEFAULT_JANUS_MAPPINGS: &str = include_str!("../config/janus_mappings.toml");
 
-        let high_priority_files: Vec<PathBuf> = analyses
-            .iter()
-            .filter(|a| a.score.importance > 70.0)
-            .map(|a| a.path.clone())
-            .collect();
+/// JANUS mapping file name, looked up in the project root and `config/`
+pub const JANUS_MAPPINGS_FILE: &str = "janus_mappings.toml";
 
-        let overall_health = 100.0 - avg_score.risk;
-        let tech_debt = avg_score.tech_debt;
+/// Source files considered when verifying JANUS mappings
+const JANUS_SOURCE_EXTENSIONS: &[&str] = &["rs", "py", "kt", "kts", "ts", "swift"];
 
-        Ok(CodebaseScore {
-            total_files,
-            averages: avg_score,
-            critical_files,
-            high_priority_files,
-            healthiest_files: Vec::new(),
-            unhealthiest_files: Vec::new(),
-            total_todos: TodoBreakdown {
-                high: 0,
-                medium: 0,
-                low: 0,
-                total: 0,
-            },
-            total_tech_debt: tech_debt,
-            overall_health,
-        })
+/// Brain region → component mappings (`janus_mappings.toml`)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct JanusMappings {
+    #[serde(rename = "mapping", default)]
+    pub mappings: Vec<JanusMapping>,
+}
+
+/// One brain region and the component implementing it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JanusMapping {
+    /// Brain region, e.g. "hippocampus"
+    pub region: String,
+
+    /// Component the region maps to
+    pub component: String,
+
+    /// Module or directory names implementing the component
+    #[serde(default)]
+    pub modules: Vec<String>,
+
+    /// Focus instruction for the LLM
+    #[serde(default)]
+    pub focus: String,
+}
+
+impl JanusMapping {
+    /// Focus area for the LLM, defaulting to "region: component"
+    pub fn focus_area(&self) -> String {
+        if self.focus.trim().is_empty() {
+            format!("{}: {}", self.region, self.component)
+        } else {
+            self.focus.clone()
+        }
     }
+}
 
-    /// Generate master review from file analyses
-    async fn generate_master_review(&self, analyses: &[FileAnalysis]) -> Result<MasterReview> {
-        let summary = format!(
-            "Analyzed {} high-priority files. Security and quality metrics collected.",
-            analyses.len()
+impl JanusMappings {
+    pub fn from_toml(content: &str) -> Result<Self> {
+        toml::from_str(content)
+            .map_err(|e| AuditError::config(format!("Failed to parse JANUS mappings: {}", e)))
+    }
+
+    /// Focus areas for every mapping
+    pub fn focus_areas(&self) -> Vec<String> {
+        self.mappings.iter().map(JanusMapping::focus_area).collect()
+    }
+}
+
+/// Result of checking one mapping against the tree
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MappingCheck {
+    pub region: String,
+    pub component: String,
+    pub found: bool,
+
+    /// Files implementing the component
+    pub files: Vec<PathBuf>,
+}
+
+/// Static verification of JANUS mappings
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MappingReport {
+    pub checks: Vec<MappingCheck>,
+
+    /// Source files examined
+    pub files_scanned: usize,
+}
+
+impl MappingReport {
+    pub fn found_count(&self) -> usize {
+        self.checks.iter().filter(|c| c.found).count()
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.checks.iter().all(|c| c.found)
+    }
+
+    /// A recommendation for each missing component
+    pub fn findings(&self) -> Vec<Recommendation> {
+        self.checks
+            .iter()
+            .filter(|c| !c.found)
+            .map(|c| Recommendation {
+                priority: "High".to_string(),
+                category: "Architecture".to_string(),
+                recommendation: format!(
+                    "No module implements {} ({}); add it or annotate the implementing file with `@audit-tag: {}`",
+                    c.region, c.component, c.region
+                ),
+                benefit: "Keeps the implementation aligned with the JANUS design".to_string(),
+                effort: default_effort(),
+                files: Vec::new(),
+                priority_score: 0.0,
+            })
+            .collect()
+    }
+
+    pub fn to_markdown(&self) -> String {
+        let mut md = format!(
+            "## JANUS Mapping Verification\n\n{}/{} components found ({} source files scanned)\n\n",
+            self.found_count(),
+            self.checks.len(),
+            self.files_scanned
         );
+        md.push_str("| | Region | Component | Files |\n|---|---|---|---|\n");
+        for check in &self.checks {
+            let files = if check.files.is_empty() {
+                "-".to_string()
+            } else {
+                let mut shown: Vec<String> = check
+                    .files
+                    .iter()
+                    .take(3)
+                    .map(|f| format!("`{}`", f.display()))
+                    .collect();
+                if check.files.len() > 3 {
+                    shown.push(format!("+{} more", check.files.len() - 3));
+                }
+                shown.join(", ")
+            };
+            md.push_str(&format!(
+                "| {} | {} | {} | {} |\n",
+                if check.found { "✅" } else { "❌" },
+                check.region,
+                check.component,
+                files
+            ));
+        }
+        md
+    }
+}
 
-        let mut top_priorities = Vec::new();
-        let mut weaknesses = Vec::new();
+/// Report from [`LlmAuditor::run_janus_audit`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JanusAuditReport {
+    /// Static mapping check, when requested
+    pub mapping_report: Option<MappingReport>,
+
+    /// LLM audit, unless skipped
+    pub audit: Option<RegularAuditResult>,
+}
+
+impl JanusAuditReport {
+    pub fn to_markdown(&self) -> String {
+        let mut md = String::from("# JANUS Audit\n\n");
+
+        if let Some(ref report) = self.mapping_report {
+            md.push_str(&report.to_markdown());
+            md.push('\n');
+        }
+
+        let Some(ref audit) = self.audit else {
+            md.push_str("_LLM audit skipped._\n");
+            return md;
+        };
 
-        for analysis in analyses {
-            if !analysis.llm_analysis.security_observations.is_empty() {
-                top_priorities.push(format!(
-                    "Address security issues in {}",
-                    analysis.path.display()
+        md.push_str(&format!(
+            "## Assessment\n\n{}\n\nOverall health: {:.0}/100 (confidence {:.0}%)\n\n",
+            audit.architecture_assessment, audit.overall_health, audit.confidence
+        ));
+        if !audit.security_concerns.is_empty() {
+            md.push_str("## Security Concerns\n\n");
+            for concern in &audit.security_concerns {
+                md.push_str(&format!(
+                    "- **{}**: {} — {}\n",
+                    concern.severity, concern.description, concern.recommendation
                 ));
             }
-            if !analysis.llm_analysis.improvement_suggestions.is_empty() {
-                weaknesses.push(analysis.llm_analysis.improvement_suggestions[0].clone());
+            md.push('\n');
+        }
+        let wins = quick_wins(&audit.recommendations, 3);
+        if !wins.is_empty() {
+            md.push_str("## Top Quick Wins\n\n");
+            for rec in wins {
+                md.push_str(&format!(
+                    "- **{}** ({} effort): {}\n",
+                    rec.priority, rec.effort, rec.recommendation
+                ));
+            }
+            md.push('\n');
+        }
+        if !audit.recommendations.is_empty() {
+            md.push_str("## Recommendations\n\n");
+            for rec in &audit.recommendations {
+                md.push_str(&format!(
+                    "- **{}** ({}, {} effort, score {:.0}): {}\n",
+                    rec.priority, rec.category, rec.effort, rec.priority_score, rec.recommendation
+                ));
             }
+            md.push('\n');
         }
 
-        Ok(MasterReview {
-            executive_summary: summary,
-            top_priorities,
-            strengths: vec!["Structured codebase".to_string()],
-            weaknesses,
-            architecture_quality: 70.0,
-            code_consistency: 75.0,
-            test_coverage_assessment: "Test coverage not measured".to_string(),
-            sustainability: 70.0,
-            strategic_recommendations: vec![
-                "Implement automated testing".to_string(),
-                "Address security concerns".to_string(),
-            ],
+        md
+    }
+}
+
+/// Lowercase with `-` and spaces as `_`, for matching mapping names
+fn normalize_mapping_name(name: &str) -> String {
+    name.trim().to_lowercase().replace(['-', ' '], "_")
+}
+
+/// Directory names and file stem of a path, normalized
+fn mapping_segments(path: &Path) -> Vec<String> {
+    let mut segments: Vec<String> = path
+        .parent()
+        .into_iter()
+        .flat_map(|p| p.components())
+        .map(|c| normalize_mapping_name(&c.as_os_str().to_string_lossy()))
+        .collect();
+    if let Some(stem) = path.file_stem() {
+        segments.push(normalize_mapping_name(&stem.to_string_lossy()));
+    }
+    segments
+}
+
+/// Values of `@audit-tag:` annotations, normalized
+fn mapping_annotations(content: &str) -> Vec<String> {
+    content
+        .lines()
+        .filter_map(|line| line.split_once("@audit-tag:"))
+        .map(|(_, value)| normalize_mapping_name(value))
+        .collect()
+}
+
+/// Expected response tokens per analyzed file, for budget checks
+const FILE_ANALYSIS_OUTPUT_TOKENS: usize = 600;
+
+/// Expected response tokens for the master review, for budget checks
+const MASTER_REVIEW_OUTPUT_TOKENS: usize = 2_000;
+
+/// Maximum source characters sent in one batch request
+const BATCH_MAX_CHARS: usize = 48_000;
+
+/// Maximum files per batch request
+const BATCH_MAX_FILES: usize = 8;
+
+/// A scanned and scored file considered for a full audit
+struct AuditCandidate {
+    /// Path relative to the project root
+    path: PathBuf,
+    category: Category,
+    content: String,
+    score: FileScore,
+}
+
+fn normalize_path(path: &str) -> String {
+    path.trim().trim_start_matches("./").replace('\\', "/")
+}
+
+fn full_audit_cache_key(path: &Path) -> String {
+    format!("full_audit:{}", normalize_path(&path.to_string_lossy()))
+}
+
+/// Group files into batches bounded by character count and file count
+fn batch_candidates<'a>(candidates: &[&'a AuditCandidate]) -> Vec<Vec<&'a AuditCandidate>> {
+    let mut batches: Vec<Vec<&AuditCandidate>> = Vec::new();
+    let mut current = Vec::new();
+    let mut current_chars = 0;
+
+    for &candidate in candidates {
+        let chars = candidate.content.len().min(BATCH_MAX_CHARS);
+        if !current.is_empty()
+            && (current.len() >= BATCH_MAX_FILES || current_chars + chars > BATCH_MAX_CHARS)
+        {
+            batches.push(std::mem::take(&mut current));
+            current_chars = 0;
+        }
+        current.push(candidate);
+        current_chars += chars;
+    }
+    if !current.is_empty() {
+        batches.push(current);
+    }
+
+    batches
+}
+
+fn file_batch_prompt(batch: &[&AuditCandidate]) -> (String, String) {
+    let system = "You are a senior engineer performing a file-by-file code audit. For every \
+        file given, report its purpose, importance (Critical, High, Medium or Low), key \
+        functionality, what it depends on, security observations, a short quality assessment \
+        and concrete improvement suggestions. Respond only with JSON matching the provided \
+        schema, with one entry per file using the exact path given."
+        .to_string();
+
+    let mut user = String::new();
+    for candidate in batch {
+        let mut content = candidate.content.as_str();
+        let mut truncated = false;
+        if content.len() > BATCH_MAX_CHARS {
+            let mut end = BATCH_MAX_CHARS;
+            while !content.is_char_boundary(end) {
+                end -= 1;
+            }
+            content = &content[..end];
+            truncated = true;
+        }
+        user.push_str(&format!(
+            "### File: {}\nCategory: {:?}\nPriority: {} (risk {:.0}, importance {:.0})\n```\n{}\n{}```\n\n",
+            candidate.path.display(),
+            candidate.category,
+            candidate.score.priority_rating(),
+            candidate.score.risk,
+            candidate.score.importance,
+            content,
+            if truncated { "... (truncated)\n" } else { "" }
+        ));
+    }
+
+    (system, user)
+}
+
+#[derive(Debug, Deserialize)]
+struct FileBatchResponse {
+    files: Vec<FileBatchEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FileBatchEntry {
+    path: String,
+    #[serde(flatten)]
+    analysis: FileLlmAnalysis,
+}
+
+#[derive(Debug, Deserialize)]
+struct MasterReviewResponse {
+    #[serde(flatten)]
+    review: MasterReview,
+    #[serde(default)]
+    architecture_patterns: Vec<String>,
+    #[serde(default)]
+    anti_patterns: Vec<String>,
+}
+
+fn master_review_prompt(analyses: &[FileAnalysis], score: &CodebaseScore) -> (String, String) {
+    let system = "You are a principal engineer writing the master review of a code audit. \
+        From the per-file findings, write an executive summary, the top priorities, strengths \
+        and weaknesses of the system, scores from 0 to 100, and strategic recommendations. \
+        Respond only with JSON matching the provided schema."
+        .to_string();
+
+    let mut user = format!(
+        "Codebase: {} files scanned, overall health {:.0}, average risk {:.0}, {} TODOs.\n\nFindings:\n",
+        score.total_files, score.overall_health, score.averages.risk, score.total_todos.total
+    );
+    for fa in analyses {
+        user.push_str(&format!(
+            "- {} [{}; {} layer; risk {:.0}; {} dependents]: {}\n",
+            fa.path.display(),
+            fa.llm_analysis.importance,
+            fa.relationships.layer,
+            fa.score.risk,
+            fa.relationships.depended_by.len(),
+            fa.llm_analysis.purpose
+        ));
+        for observation in &fa.llm_analysis.security_observations {
+            user.push_str(&format!("  - security: {}\n", observation));
+        }
+        for suggestion in fa.llm_analysis.improvement_suggestions.iter().take(2) {
+            user.push_str(&format!("  - suggestion: {}\n", suggestion));
+        }
+    }
+
+    (system, user)
+}
+
+/// Master review computed from the file analyses alone
+fn summarize_master_review(analyses: &[FileAnalysis], score: &CodebaseScore) -> MasterReview {
+    let with_security: Vec<&FileAnalysis> = analyses
+        .iter()
+        .filter(|fa| !fa.llm_analysis.security_observations.is_empty())
+        .collect();
+
+    let mut top_priorities: Vec<String> = with_security
+        .iter()
+        .map(|fa| format!("Address security issues in {}", fa.path.display()))
+        .collect();
+    top_priorities.extend(
+        analyses
+            .iter()
+            .filter(|fa| fa.score.needs_immediate_attention())
+            .map(|fa| format!("Reduce risk in {}", fa.path.display())),
+    );
+    top_priorities.dedup();
+
+    let healthy = analyses
+        .iter()
+        .filter(|fa| fa.score.health_score() >= 60.0)
+        .count();
+    let mut strengths = Vec::new();
+    if healthy > 0 {
+        strengths.push(format!(
+            "{} of {} analyzed files are in good health",
+            healthy,
+            analyses.len()
+        ));
+    }
+    if with_security.is_empty() && !analyses.is_empty() {
+        strengths.push("No security concerns found in analyzed files".to_string());
+    }
+
+    let weaknesses: Vec<String> = analyses
+        .iter()
+        .filter_map(|fa| fa.llm_analysis.improvement_suggestions.first().cloned())
+        .collect();
+
+    MasterReview {
+        executive_summary: format!(
+            "Analyzed {} of {} files. {} have security observations; overall health is {:.0}/100.",
+            analyses.len(),
+            score.total_files,
+            with_security.len(),
+            score.overall_health
+        ),
+        top_priorities,
+        strengths,
+        weaknesses,
+        architecture_quality: score.averages.quality,
+        code_consistency: score.averages.quality,
+        test_coverage_assessment: "Test coverage not measured".to_string(),
+        sustainability: (100.0 - score.averages.tech_debt).clamp(0.0, 100.0),
+        strategic_recommendations: Vec::new(),
+    }
+}
+
+/// Architectural layer guessed from a file's path
+fn infer_layer(path: &Path, category: Category) -> String {
+    let lower = path.to_string_lossy().to_lowercase();
+    let tokens: Vec<&str> = lower
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|t| !t.is_empty())
+        .collect();
+    let has = |names: &[&str]| tokens.iter().any(|t| names.contains(t));
+
+    let layer = if category == Category::Tests || has(&["test", "tests"]) {
+        "Tests"
+    } else if has(&["bin", "cli", "main"]) {
+        "CLI"
+    } else if has(&["api", "server", "handler", "handlers", "routes", "web"]) {
+        "API"
+    } else if has(&["db", "repo", "store", "storage", "migrations", "cache"]) {
+        "Data Access"
+    } else if category == Category::Config || has(&["config", "settings"]) {
+        "Configuration"
+    } else {
+        "Core Logic"
+    };
+    layer.to_string()
+}
+
+/// Module path of a Rust file under `src/` (`src/a/b.rs` and
+/// `src/a/b/mod.rs` are `a::b`; `src/lib.rs` is the crate root)
+pub(crate) fn rust_module_path(path: &Path) -> Option<Vec<String>> {
+    if path.extension().and_then(|e| e.to_str()) != Some("rs") {
+        return None;
+    }
+    let components: Vec<String> = path
+        .with_extension("")
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy().to_string())
+        .collect();
+    let src = components.iter().position(|c| c == "src")?;
+    let mut module = components[src + 1..].to_vec();
+
+    match module.last().map(String::as_str) {
+        Some("mod") => {
+            module.pop();
+        }
+        Some("lib" | "main") if module.len() == 1 => module.clear(),
+        _ => {}
+    }
+    Some(module)
+}
+
+/// Crate-local module paths referenced by `mod` and `use` items
+pub(crate) fn rust_imports(module: &[String], content: &str) -> Vec<Vec<String>> {
+    let mut imports = Vec::new();
+
+    for line in content.lines() {
+        let mut item = line.trim();
+        if let Some(rest) = item.strip_prefix("pub") {
+            item = match rest.strip_prefix('(') {
+                Some(vis) => vis.split_once(')').map_or("", |(_, after)| after),
+                None => rest,
+            }
+            .trim_start();
+        }
+
+        if let Some(name) = item.strip_prefix("mod ").and_then(|m| m.strip_suffix(';')) {
+            let mut path = module.to_vec();
+            path.push(name.trim().to_string());
+            imports.push(path);
+            continue;
+        }
+
+        let Some(tree) = item.strip_prefix("use ") else {
+            continue;
+        };
+        let tree = tree.trim_end_matches(';').trim();
+        let paths: Vec<String> = match (tree.find('{'), tree.rfind('}')) {
+            (Some(open), Some(close)) if open < close => {
+                let prefix = tree[..open].trim_end_matches("::");
+                tree[open + 1..close]
+                    .split(',')
+                    .map(|part| part.split('{').next().unwrap_or("").trim())
+                    .filter(|part| !part.is_empty())
+                    .map(|part| format!("{}::{}", prefix, part))
+                    .collect()
+            }
+            _ => vec![tree.to_string()],
+        };
+
+        for path in paths {
+            let path = path.split(" as ").next().unwrap_or("");
+            let mut segments = path
+                .split("::")
+                .map(str::trim)
+                .filter(|s| !s.is_empty() && *s != "*");
+            let mut resolved = match segments.next() {
+                Some("crate") => Vec::new(),
+                Some("self") => module.to_vec(),
+                Some("super") => module[..module.len().saturating_sub(1)].to_vec(),
+                _ => continue,
+            };
+            for segment in segments {
+                match segment {
+                    "super" => {
+                        resolved.pop();
+                    }
+                    "self" => {}
+                    s => resolved.push(s.to_string()),
+                }
+            }
+            imports.push(resolved);
+        }
+    }
+
+    imports
+}
+
+/// Files among `modules` that the source `content` of `module` imports.
+/// Each import resolves to its longest module prefix, since
+/// `crate::a::b::Item` lives in `a::b`.
+pub(crate) fn resolve_rust_imports<'a, T>(
+    module: &[String],
+    content: &str,
+    modules: &'a HashMap<Vec<String>, T>,
+) -> Vec<&'a T> {
+    rust_imports(module, content)
+        .iter()
+        .filter_map(|import| {
+            (0..=import.len())
+                .rev()
+                .find_map(|len| modules.get(&import[..len]))
+        })
+        .collect()
+}
+
+/// Dependency relationships between scanned files, from Rust imports
+fn build_relationships(files: &[AuditCandidate]) -> HashMap<PathBuf, FileRelationships> {
+    let modules: HashMap<Vec<String>, &PathBuf> = files
+        .iter()
+        .filter_map(|f| rust_module_path(&f.path).map(|m| (m, &f.path)))
+        .collect();
+
+    let mut relationships: HashMap<PathBuf, FileRelationships> = files
+        .iter()
+        .map(|f| {
+            (
+                f.path.clone(),
+                FileRelationships {
+                    layer: infer_layer(&f.path, f.category),
+                    ..Default::default()
+                },
+            )
         })
+        .collect();
+
+    for file in files {
+        let Some(module) = rust_module_path(&file.path) else {
+            continue;
+        };
+        let depends_on: BTreeSet<PathBuf> = resolve_rust_imports(&module, &file.content, &modules)
+            .into_iter()
+            .filter(|&&target| *target != file.path)
+            .map(|&target| target.clone())
+            .collect();
+
+        for target in &depends_on {
+            if let Some(rel) = relationships.get_mut(target) {
+                rel.depended_by.push(file.path.clone());
+            }
+        }
+        if let Some(rel) = relationships.get_mut(&file.path) {
+            rel.depends_on = depends_on.into_iter().collect();
+        }
+    }
+
+    relationships
+}
+
+/// Fill `related` with analyzed files in the same directory that aren't
+/// already direct dependencies
+fn with_related_files(mut analyses: Vec<FileAnalysis>) -> Vec<FileAnalysis> {
+    let paths: Vec<PathBuf> = analyses.iter().map(|fa| fa.path.clone()).collect();
+    for fa in &mut analyses {
+        let parent = fa.path.parent();
+        fa.relationships.related = paths
+            .iter()
+            .filter(|p| **p != fa.path && p.parent() == parent)
+            .filter(|p| {
+                !fa.relationships.depends_on.contains(p)
+                    && !fa.relationships.depended_by.contains(p)
+            })
+            .take(5)
+            .cloned()
+            .collect();
+    }
+    analyses
+}
+
+/// Strict JSON schema object with every property required
+fn strict_object(properties: serde_json::Value) -> serde_json::Value {
+    let required: Vec<&String> = properties
+        .as_object()
+        .map(|p| p.keys().collect())
+        .unwrap_or_default();
+    serde_json::json!({
+        "type": "object",
+        "properties": properties,
+        "required": required,
+        "additionalProperties": false,
+    })
+}
+
+fn schema_array_of(items: serde_json::Value) -> serde_json::Value {
+    serde_json::json!({ "type": "array", "items": items })
+}
+
+fn schema_string() -> serde_json::Value {
+    serde_json::json!({ "type": "string" })
+}
+
+fn schema_score() -> serde_json::Value {
+    serde_json::json!({ "type": "number", "minimum": 0, "maximum": 100 })
+}
+
+/// JSON schema for a batch of [`FileLlmAnalysis`] results keyed by path
+pub fn file_batch_schema() -> serde_json::Value {
+    let strings = || schema_array_of(schema_string());
+    strict_object(serde_json::json!({
+        "files": schema_array_of(strict_object(serde_json::json!({
+            "path": schema_string(),
+            "purpose": schema_string(),
+            "importance": { "type": "string", "enum": ["Critical", "High", "Medium", "Low"] },
+            "key_functionality": strings(),
+            "dependencies": strings(),
+            "security_observations": strings(),
+            "quality_assessment": schema_string(),
+            "improvement_suggestions": strings(),
+        }))),
+    }))
+}
+
+/// JSON schema for a [`MasterReview`] plus identified architecture patterns
+pub fn master_review_schema() -> serde_json::Value {
+    let strings = || schema_array_of(schema_string());
+    strict_object(serde_json::json!({
+        "executive_summary": schema_string(),
+        "top_priorities": strings(),
+        "strengths": strings(),
+        "weaknesses": strings(),
+        "architecture_quality": schema_score(),
+        "code_consistency": schema_score(),
+        "test_coverage_assessment": schema_string(),
+        "sustainability": schema_score(),
+        "strategic_recommendations": strings(),
+        "architecture_patterns": strings(),
+        "anti_patterns": strings(),
+    }))
+}
+
+/// JSON schema for the LLM's part of a [`RegularAuditResult`] (everything
+/// but `mode`), in the strict form structured-output modes require
+pub fn regular_audit_schema() -> serde_json::Value {
+    let strings = || schema_array_of(schema_string());
+    strict_object(serde_json::json!({
+        "architecture_assessment": schema_string(),
+        "patterns": strings(),
+        "security_concerns": schema_array_of(strict_object(serde_json::json!({
+            "severity": { "type": "string", "enum": ["Critical", "High", "Medium", "Low"] },
+            "description": schema_string(),
+            "affected_areas": strings(),
+            "recommendation": schema_string(),
+        }))),
+        "quality_observations": strings(),
+        "tech_debt_areas": schema_array_of(strict_object(serde_json::json!({
+            "area": schema_string(),
+            "severity": { "type": "string", "enum": ["High", "Medium", "Low"] },
+            "effort": schema_string(),
+            "impact": schema_string(),
+        }))),
+        "recommendations": schema_array_of(strict_object(serde_json::json!({
+            "priority": { "type": "string", "enum": ["Critical", "High", "Medium", "Low"] },
+            "category": schema_string(),
+            "recommendation": schema_string(),
+            "benefit": schema_string(),
+            "effort": { "type": "string", "enum": ["Small", "Medium", "Large"] },
+            "files": strings(),
+        }))),
+        "overall_health": schema_score(),
+        "confidence": schema_score(),
+    }))
+}
+
+/// Parse a regular audit response, repairing markdown fences, surrounding
+/// prose and trailing commas. Fails with [`AuditError::MalformedResponse`]
+/// carrying the raw text rather than returning a partial result.
+pub fn parse_regular_audit(raw: &str) -> Result<RegularAuditResult> {
+    let mut result: RegularAuditResult = parse_json_response(raw, "regular audit result", |v| {
+        if let Some(object) = v.as_object_mut() {
+            object
+                .entry("mode")
+                .or_insert_with(|| serde_json::json!(AuditMode::Regular));
+        }
+    })?;
+    result.overall_health = result.overall_health.clamp(0.0, 100.0);
+    result.confidence = result.confidence.clamp(0.0, 100.0);
+    Ok(result)
+}
+
+/// Parse a JSON object out of an LLM response, trying the text as-is and
+/// then with trailing commas removed. `prepare` can fill in fields the model
+/// isn't asked for before deserializing.
+fn parse_json_response<T: DeserializeOwned>(
+    raw: &str,
+    what: &str,
+    prepare: impl Fn(&mut serde_json::Value),
+) -> Result<T> {
+    let extracted = extract_json_object(raw);
+    let repaired = strip_trailing_commas(extracted);
+
+    let mut last_error = String::from("no JSON object found");
+    for candidate in [extracted, repaired.as_str()] {
+        let mut value: serde_json::Value = match serde_json::from_str(candidate) {
+            Ok(value) => value,
+            Err(e) => {
+                last_error = e.to_string();
+                continue;
+            }
+        };
+        if !value.is_object() {
+            last_error = "response is not a JSON object".to_string();
+            continue;
+        }
+        prepare(&mut value);
+
+        match serde_json::from_value(value) {
+            Ok(result) => return Ok(result),
+            Err(e) => last_error = e.to_string(),
+        }
     }
+
+    Err(AuditError::malformed_response(
+        format!("invalid {}: {}", what, last_error),
+        raw,
+    ))
+}
+
+/// The JSON object in a response: the contents of a code fence if there is
+/// one, trimmed to the outermost braces
+fn extract_json_object(raw: &str) -> &str {
+    let mut text = raw.trim();
+
+    if let Some(start) = text.find("```") {
+        let body = &text[start + 3..];
+        let body = body.find('\n').map_or(body, |i| &body[i + 1..]);
+        text = body.find("```").map_or(body, |end| &body[..end]).trim();
+    }
+
+    match (text.find('{'), text.rfind('}')) {
+        (Some(start), Some(end)) if start < end => &text[start..=end],
+        _ => text,
+    }
+}
+
+/// Remove commas directly before a closing `}` or `]`, leaving string
+/// contents alone
+fn strip_trailing_commas(json: &str) -> String {
+    let mut out = String::with_capacity(json.len());
+    let mut in_string = false;
+    let mut escaped = false;
+    let mut chars = json.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if in_string {
+            out.push(c);
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+
+        match c {
+            '"' => {
+                in_string = true;
+                out.push(c);
+            }
+            ',' => {
+                let rest = chars.clone().find(|c| !c.is_whitespace());
+                if !matches!(rest, Some('}') | Some(']')) {
+                    out.push(c);
+                }
+            }
+            _ => out.push(c),
+        }
+    }
+
+    out
 }
 
 impl Default for LlmAuditor {
@@ -713,4 +2153,510 @@ mod tests {
         let _auditor = LlmAuditor::new(project_root);
         // Placeholder test - actual tests need LLM integration
     }
+
+    const AUDIT_JSON: &str = r#"{
+        "architecture_assessment": "Layered, with a thin CLI",
+        "patterns": ["builder", "repository",],
+        "security_concerns": [
+            {"severity": "High", "description": "Key logged, here", "affected_areas": ["src/a.rs"], "recommendation": "Redact"},
+        ],
+        "quality_observations": [],
+        "tech_debt_areas": [],
+        "recommendations": [],
+        "overall_health": 140,
+        "confidence": 80,
+    }"#;
+
+    #[test]
+    fn test_parse_regular_audit_fenced_with_trailing_commas() {
+        let raw = format!(
+            "Here is the audit:\n\n```json\n{}\n```\nLet me know!",
+            AUDIT_JSON
+        );
+        let result = parse_regular_audit(&raw).unwrap();
+
+        assert_eq!(result.mode, AuditMode::Regular);
+        assert_eq!(result.patterns, vec!["builder", "repository"]);
+        assert_eq!(result.security_concerns.len(), 1);
+        assert_eq!(result.security_concerns[0].description, "Key logged, here");
+        assert_eq!(result.overall_health, 100.0);
+        assert_eq!(result.confidence, 80.0);
+
+        assert_eq!(
+            strip_trailing_commas(r#"{"a": [1, 2, ], "b": ",}", }"#),
+            r#"{"a": [1, 2 ], "b": ",}" }"#
+        );
+    }
+
+    #[test]
+    fn test_parse_regular_audit_failure_keeps_raw_response() {
+        let raw = "```json\n{\"architecture_assessment\": \"cut off\"\n```";
+        let err = parse_regular_audit(raw).unwrap_err();
+
+        assert!(matches!(err, AuditError::MalformedResponse { .. }));
+        assert_eq!(err.raw_response(), Some(raw));
+
+        let schema = regular_audit_schema();
+        assert_eq!(schema["additionalProperties"], false);
+        assert_eq!(schema["required"].as_array().unwrap().len(), 8);
+    }
+
+    /// Canned responses keyed by schema name, recording each request
+    struct MockLlm {
+        calls: std::sync::Mutex<Vec<String>>,
+    }
+
+    #[async_trait::async_trait]
+    impl AuditLlm for MockLlm {
+        async fn complete_structured(
+            &self,
+            _system: &str,
+            user: &str,
+            schema_name: &str,
+            _schema: &serde_json::Value,
+        ) -> Result<LlmCompletion> {
+            self.calls.lock().unwrap().push(schema_name.to_string());
+            let content = match schema_name {
+                "file_batch_analysis" => {
+                    let files: Vec<serde_json::Value> = user
+                        .lines()
+                        .filter_map(|l| l.strip_prefix("### File: "))
+                        .map(|path| {
+                            let auth = path.ends_with("auth.rs");
+                            serde_json::json!({
+                                "path": path,
+                                "purpose": format!("Purpose of {}", path),
+                                "importance": if auth { "Critical" } else { "Medium" },
+                                "key_functionality": [],
+                                "dependencies": [],
+                                "security_observations":
+                                    if auth { vec!["Hardcoded secret"] } else { vec![] },
+                                "quality_assessment": "Fine",
+                                "improvement_suggestions": ["Add docs"],
+                            })
+                        })
+                        .collect();
+                    Ok(format!(
+                        "```json\n{}\n```",
+                        serde_json::json!({ "files": files })
+                    ))
+                }
+                "master_review" => Ok(serde_json::json!({
+                    "executive_summary": "Small crate with one risky module",
+                    "top_priorities": ["Remove the hardcoded key"],
+                    "strengths": ["Clear module boundaries"],
+                    "weaknesses": ["Secrets in source"],
+                    "architecture_quality": 72,
+                    "code_consistency": 80,
+                    "test_coverage_assessment": "No tests",
+                    "sustainability": 65,
+                    "strategic_recommendations": ["Add a secrets manager"],
+                    "architecture_patterns": ["Layered"],
+                    "anti_patterns": [],
+                })
+                .to_string()),
+                "regular_audit_result" => {
+                    let files = user.lines().filter(|l| l.starts_with("File: ")).count();
+                    Ok(serde_json::json!({
+                        "architecture_assessment": format!("Reviewed {} files", files),
+                        "patterns": [],
+                        "security_concerns": [],
+                        "quality_observations": [],
+                        "tech_debt_areas": [],
+                        "recommendations": [],
+                        "overall_health": 70,
+                        "confidence": 60,
+                    })
+                    .to_string())
+                }
+                other => Err(AuditError::other(format!("unexpected schema {}", other))),
+            }?;
+            Ok(LlmCompletion {
+                content,
+                model: "mock".to_string(),
+                usage: None,
+            })
+        }
+    }
+
+    fn mock_project() -> (tempfile::TempDir, LlmConfig) {
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("src");
+        fs::create_dir_all(&src).unwrap();
+        fs::write(
+            src.join("lib.rs"),
+            "pub mod auth;\npub mod util;\n\nuse crate::util::helper;\n",
+        )
+        .unwrap();
+        fs::write(
+            src.join("auth.rs"),
+            "use crate::util::{helper, Config};\n\npub const KEY: &str = \"secret\";\n\npub fn login() {\n    helper();\n}\n",
+        )
+        .unwrap();
+        fs::write(
+            src.join("util.rs"),
+            "pub struct Config;\n\npub fn helper() {}\n",
+        )
+        .unwrap();
+
+        let mut config = LlmConfig::enabled_default();
+        config.file_selection.min_importance_score = 0.0;
+        config.file_selection.min_risk_score = 0.0;
+        (dir, config)
+    }
+
+    #[tokio::test]
+    async fn test_full_audit_pipeline_with_mock_llm() {
+        let (dir, config) = mock_project();
+        let cache = AuditCache::new(dir.path(), &config.cache).unwrap();
+        let llm = Arc::new(MockLlm {
+            calls: std::sync::Mutex::new(Vec::new()),
+        });
+        let auditor = LlmAuditor::with_llm(llm.clone(), config, Some(cache));
+
+        let result = auditor.run_full_audit(dir.path()).await.unwrap();
+        assert_eq!(result.mode, AuditMode::Full);
+        assert_eq!(
+            *llm.calls.lock().unwrap(),
+            vec!["file_batch_analysis", "master_review"]
+        );
+        assert_eq!(result.file_analyses.len(), 3);
+        assert_eq!(result.codebase_score.total_files, 3);
+        assert_eq!(result.usage.api_calls, 2);
+        assert!(result.usage.estimated_cost_usd > 0.0);
+
+        let find = |name: &str| {
+            result
+                .file_analyses
+                .iter()
+                .find(|fa| fa.path == Path::new("src").join(name))
+                .unwrap()
+        };
+        let auth = find("auth.rs");
+        assert_eq!(auth.llm_analysis.purpose, "Purpose of src/auth.rs");
+        assert_eq!(
+            auth.relationships.depends_on,
+            vec![PathBuf::from("src/util.rs")]
+        );
+        assert_eq!(auth.relationships.layer, "Core Logic");
+        let mut dependents = find("util.rs").relationships.depended_by.clone();
+        dependents.sort();
+        assert_eq!(
+            dependents,
+            vec![PathBuf::from("src/auth.rs"), PathBuf::from("src/lib.rs")]
+        );
+        assert_eq!(
+            find("lib.rs").relationships.depends_on,
+            vec![PathBuf::from("src/auth.rs"), PathBuf::from("src/util.rs")]
+        );
+
+        assert_eq!(result.critical_files, vec![PathBuf::from("src/auth.rs")]);
+        assert_eq!(
+            result.master_review.executive_summary,
+            "Small crate with one risky module"
+        );
+        assert_eq!(result.architecture_insights.patterns, vec!["Layered"]);
+
+        // Unchanged files come from the cache; only the review is regenerated
+        let rerun = auditor.run_full_audit(dir.path()).await.unwrap();
+        assert_eq!(rerun.usage.cached_files, 3);
+        assert_eq!(rerun.usage.api_calls, 1);
+        assert_eq!(rerun.file_analyses.len(), 3);
+        assert_eq!(rerun.codebase_score.total_files, 3);
+    }
+
+    #[tokio::test]
+    async fn test_full_audit_with_mock_provider_is_free() {
+        let (dir, config) = mock_project();
+        let client =
+            LlmClient::new_with_provider(String::new(), "mock".into(), "mock".into(), 1000, 0.0)
+                .unwrap();
+        let auditor = LlmAuditor::with_llm(Arc::new(client), config, None);
+
+        let result = auditor.run_full_audit(dir.path()).await.unwrap();
+        assert_eq!(result.file_analyses.len(), 3);
+        assert_eq!(result.usage.api_calls, 2);
+        assert_eq!(result.usage.estimated_cost_usd, 0.0);
+        assert_eq!(result.usage.models_used.get("mock"), Some(&2));
+
+        let regular = auditor.run_regular_audit(dir.path(), vec![]).await.unwrap();
+        assert!((0.0..=100.0).contains(&regular.overall_health));
+    }
+
+    #[tokio::test]
+    async fn test_full_audit_respects_call_budget() {
+        let (dir, mut config) = mock_project();
+        config.limits.max_daily_calls = Some(0);
+        let llm = Arc::new(MockLlm {
+            calls: std::sync::Mutex::new(Vec::new()),
+        });
+        let auditor = LlmAuditor::with_llm(llm.clone(), config, None);
+
+        let result = auditor.run_full_audit(dir.path()).await.unwrap();
+        assert!(llm.calls.lock().unwrap().is_empty());
+        assert!(result.file_analyses.is_empty());
+        assert_eq!(result.usage.skipped_files.len(), 3);
+        assert!(result
+            .master_review
+            .executive_summary
+            .starts_with("Analyzed 0 of 3 files"));
+    }
+
+    #[test]
+    fn test_usage_records_and_prices_serving_model() {
+        let mut config = LlmConfig::default();
+        config.provider.default_provider = "anthropic".to_string();
+        let completion = |model: &str| LlmCompletion {
+            content: String::new(),
+            model: model.to_string(),
+            usage: Some(crate::llm::LlmUsage {
+                input_tokens: 1_000_000,
+                ..Default::default()
+            }),
+        };
+
+        let mut usage = AuditUsage::default();
+        usage.record_call(&config, 0, &completion("claude-opus-4-20250514"));
+        usage.record_call(&config, 0, &completion("claude-sonnet-4-20250514"));
+
+        assert_eq!(usage.models_used.get("claude-sonnet-4-20250514"), Some(&1));
+        assert_eq!(usage.models_used.len(), 2);
+        assert!((usage.estimated_cost_usd - 18.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_select_files_for_focus() {
+        let mut config = LlmConfig::default();
+        config
+            .focus_heuristics
+            .push(FocusHeuristic::new("security", &["vault"]));
+        let auditor = LlmAuditor::with_llm(
+            Arc::new(MockLlm {
+                calls: std::sync::Mutex::new(Vec::new()),
+            }),
+            config,
+            None,
+        );
+        let files: Vec<PathBuf> = [
+            "src/auth/login.rs",
+            "src/crypto.rs",
+            "src/vault_client.rs",
+            "src/parser.rs",
+            "tests/parser_test.rs",
+        ]
+        .iter()
+        .map(PathBuf::from)
+        .collect();
+
+        let security = auditor.select_files_for_focus(files.clone(), &["Security".to_string()]);
+        assert_eq!(
+            security,
+            vec![
+                PathBuf::from("src/auth/login.rs"),
+                PathBuf::from("src/crypto.rs"),
+                PathBuf::from("src/vault_client.rs"),
+            ]
+        );
+
+        let tests = auditor.select_files_for_focus(files.clone(), &["tests".to_string()]);
+        assert_eq!(tests, vec![PathBuf::from("tests/parser_test.rs")]);
+
+        // Unknown keywords match path segments directly
+        let parser = auditor.select_files_for_focus(files.clone(), &["parser".to_string()]);
+        assert_eq!(parser.len(), 2);
+
+        assert_eq!(auditor.select_files_for_focus(files.clone(), &[]), files);
+        assert_eq!(
+            auditor.select_files_for_focus(files.clone(), &["kubernetes".to_string()]),
+            files
+        );
+    }
+
+    fn janus_project() -> (tempfile::TempDir, JanusMappings) {
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("src");
+        fs::create_dir_all(src.join("hippocampus")).unwrap();
+        fs::write(src.join("hippocampus/mod.rs"), "pub struct ReplayBuffer;\n").unwrap();
+        fs::write(
+            src.join("exec.rs"),
+            "// @audit-tag: Cerebellum order execution\npub fn submit() {}\n",
+        )
+        .unwrap();
+        fs::write(src.join("lib.rs"), "pub mod exec;\npub mod hippocampus;\n").unwrap();
+
+        let mappings = JanusMappings::from_toml(
+            r#"
+            [[mapping]]
+            region = "hippocampus"
+            component = "Experience replay"
+
+            [[mapping]]
+            region = "cerebellum"
+            component = "Order execution"
+            modules = ["execution"]
+            focus = "cerebellum: execution timing"
+
+            [[mapping]]
+            region = "amygdala"
+            component = "Risk detection"
+            modules = ["risk"]
+            "#,
+        )
+        .unwrap();
+        (dir, mappings)
+    }
+
+    #[test]
+    fn test_verify_mappings_finds_modules_and_annotations() {
+        let (dir, mappings) = janus_project();
+        let report = LlmAuditor::verify_mappings(dir.path(), &mappings).unwrap();
+
+        assert_eq!(report.files_scanned, 3);
+        assert_eq!(report.found_count(), 2);
+        assert!(!report.is_complete());
+        assert_eq!(
+            report.checks[0].files,
+            vec![PathBuf::from("src/hippocampus/mod.rs")]
+        );
+        assert_eq!(report.checks[1].files, vec![PathBuf::from("src/exec.rs")]);
+
+        let findings = report.findings();
+        assert_eq!(findings.len(), 1);
+        assert!(findings[0].recommendation.contains("amygdala"));
+        assert!(report
+            .to_markdown()
+            .contains("| ❌ | amygdala | Risk detection | - |"));
+
+        let defaults = JanusMappings::from_toml(DEFAULT_JANUS_MAPPINGS).unwrap();
+        assert!(defaults.mappings.iter().any(|m| m.region == "hippocampus"));
+        assert_eq!(mappings.focus_areas()[0], "hippocampus: Experience replay");
+    }
+
+    #[tokio::test]
+    async fn test_janus_audit_sends_only_mapped_files() {
+        let (dir, mappings) = janus_project();
+        let llm = Arc::new(MockLlm {
+            calls: std::sync::Mutex::new(Vec::new()),
+        });
+        let auditor = LlmAuditor::with_llm(llm.clone(), LlmConfig::default(), None);
+
+        let report = auditor
+            .run_janus_audit(dir.path(), &mappings, true)
+            .await
+            .unwrap();
+        assert_eq!(*llm.calls.lock().unwrap(), vec!["regular_audit_result"]);
+        let audit = report.audit.as_ref().unwrap();
+        assert_eq!(audit.architecture_assessment, "Reviewed 2 files");
+        assert_eq!(audit.recommendations.len(), 1);
+        assert!(report
+            .to_markdown()
+            .contains("No module implements amygdala"));
+
+        // Nothing mapped: no API call at all
+        let none = JanusMappings::from_toml(
+            "[[mapping]]\nregion = \"thalamus\"\ncomponent = \"Routing\"\n",
+        )
+        .unwrap();
+        let report = auditor
+            .run_janus_audit(dir.path(), &none, true)
+            .await
+            .unwrap();
+        assert!(report.audit.is_none());
+        assert_eq!(llm.calls.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_rust_imports_resolve_crate_paths() {
+        let module = vec!["audit".to_string(), "runner".to_string()];
+        let imports = rust_imports(
+            &module,
+            "use crate::cache::{AuditCache, entry::Entry as E};\nuse super::types::*;\nuse std::fs;\npub(crate) mod steps;\n",
+        );
+        assert_eq!(
+            imports,
+            vec![
+                vec!["cache", "AuditCache"],
+                vec!["cache", "entry", "Entry"],
+                vec!["audit", "types"],
+                vec!["audit", "runner", "steps"],
+            ]
+        );
+        assert_eq!(
+            rust_module_path(Path::new("src/audit/mod.rs")),
+            Some(vec!["audit".to_string()])
+        );
+        assert_eq!(rust_module_path(Path::new("src/lib.rs")), Some(vec![]));
+
+        // Items resolve to the longest known module; unknown paths are dropped
+        let module_key = |m: &str| m.split("::").map(String::from).collect::<Vec<_>>();
+        let modules = HashMap::from([
+            (module_key("cache"), "src/cache/mod.rs"),
+            (module_key("cache::entry"), "src/cache/entry.rs"),
+        ]);
+        let targets = resolve_rust_imports(
+            &module,
+            "use crate::cache::{AuditCache, entry::Entry};\nuse crate::other::Thing;\n",
+            &modules,
+        );
+        assert_eq!(targets, vec![&"src/cache/mod.rs", &"src/cache/entry.rs"]);
+    }
+
+    fn rec(priority: &str, effort: &str, files: &[&str]) -> Recommendation {
+        Recommendation {
+            priority: priority.to_string(),
+            category: "Test".to_string(),
+            recommendation: format!("{} / {}", priority, effort),
+            benefit: String::new(),
+            effort: effort.to_string(),
+            files: files.iter().map(PathBuf::from).collect(),
+            priority_score: 0.0,
+        }
+    }
+
+    #[test]
+    fn test_recommendation_scoring_formula() {
+        assert_eq!(rec("Critical", "Small", &[]).score(100.0), 100.0);
+        assert_eq!(rec("High", "Medium", &[]).impact_effort_ratio(), 1.5);
+        // 12.5 × (2 / 1) × (1 + 50 / 100)
+        assert_eq!(rec("Medium", "Small", &[]).score(50.0), 37.5);
+        assert!((rec("Low", "Large", &[]).score(0.0) - 12.5 / 3.0).abs() < 1e-9);
+        // Unknown labels fall back to Low priority and Medium effort
+        assert_eq!(rec("Someday", "Unclear", &[]).impact_effort_ratio(), 0.5);
+    }
+
+    #[test]
+    fn test_prioritize_and_quick_wins() {
+        let mut recs = vec![
+            rec("Low", "Small", &["src/util.rs"]),
+            rec("High", "Large", &["src/core.rs"]),
+            rec("High", "Large", &["src/util.rs"]),
+            rec("Medium", "Small", &[]),
+        ];
+        let importance: HashMap<PathBuf, f64> = [
+            (PathBuf::from("src/core.rs"), 90.0),
+            (PathBuf::from("src/util.rs"), 10.0),
+        ]
+        .into_iter()
+        .collect();
+        prioritize_recommendations(&mut recs, &importance);
+
+        // 37.5, 23.75, then a 13.75 tie kept in input order
+        let order: Vec<&str> = recs.iter().map(|r| r.recommendation.as_str()).collect();
+        assert_eq!(
+            order,
+            vec![
+                "Medium / Small",
+                "High / Large",
+                "Low / Small",
+                "High / Large"
+            ]
+        );
+        // Same priority and effort: the more important file goes first
+        assert_eq!(recs[1].files, vec![PathBuf::from("src/core.rs")]);
+        assert_eq!(recs[0].priority_score, 37.5);
+
+        let wins = quick_wins(&recs, 3);
+        assert_eq!(wins.len(), 1);
+        assert_eq!(wins[0].recommendation, "Medium / Small");
+    }
 }
@@ -33,6 +33,117 @@ pub struct LlmConfig {
 
     /// Cache settings
     pub cache: CacheConfig,
+
+    /// Extra focus keywords, or extra path tokens for built-in ones
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub focus_heuristics: Vec<FocusHeuristic>,
+}
+
+/// Maps a focus keyword (as in `--focus security`) to the files it covers.
+/// A file matches when one of its path segments, split on non-alphanumeric
+/// characters and lowercased, starts with one of `path_tokens`.
+///
+/// ```toml
+/// [[focus_heuristics]]
+/// keyword = "trading"
+/// path_tokens = ["order", "exchange", "broker"]
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FocusHeuristic {
+    pub keyword: String,
+    pub path_tokens: Vec<String>,
+}
+
+impl FocusHeuristic {
+    pub fn new(keyword: &str, path_tokens: &[&str]) -> Self {
+        Self {
+            keyword: keyword.to_string(),
+            path_tokens: path_tokens.iter().map(|t| t.to_string()).collect(),
+        }
+    }
+
+    /// Whether `path` falls under this focus
+    pub fn matches(&self, path: &Path) -> bool {
+        let lower = path.to_string_lossy().to_lowercase();
+        lower
+            .split(|c: char| !c.is_ascii_alphanumeric())
+            .filter(|segment| !segment.is_empty())
+            .any(|segment| {
+                self.path_tokens
+                    .iter()
+                    .any(|token| segment.starts_with(token.as_str()))
+            })
+    }
+}
+
+/// Built-in focus keywords:
+///
+/// | Keyword       | Files whose path has a segment starting with                     |
+/// |---------------|------------------------------------------------------------------|
+/// | `security`    | auth, crypto, secret, password, token, session, jwt, oauth, tls, ssl, cert, permission, sanitiz, network/net, http, socket, server, api |
+/// | `tests`       | test, spec, fixture, mock                                        |
+/// | `performance` | cache, pool, queue, batch, worker, async, bench, index, stream   |
+/// | `api`         | api, server, handler, route, endpoint, web, http                 |
+/// | `database`    | db, database, sql, migration, repo, store, storage, schema       |
+/// | `config`      | config, setting, env                                             |
+/// | `errors`      | error, retry, recover, fallback                                  |
+///
+/// Any other keyword is used as a path token on its own, and entries in
+/// [`LlmConfig::focus_heuristics`] add to (or define) keywords.
+pub fn default_focus_heuristics() -> Vec<FocusHeuristic> {
+    vec![
+        FocusHeuristic::new(
+            "security",
+            &[
+                "auth",
+                "crypto",
+                "secret",
+                "password",
+                "token",
+                "session",
+                "jwt",
+                "oauth",
+                "tls",
+                "ssl",
+                "cert",
+                "permission",
+                "sanitiz",
+                "net",
+                "http",
+                "socket",
+                "server",
+                "api",
+            ],
+        ),
+        FocusHeuristic::new("tests", &["test", "spec", "fixture", "mock"]),
+        FocusHeuristic::new(
+            "performance",
+            &[
+                "cache", "pool", "queue", "batch", "worker", "async", "bench", "index", "stream",
+            ],
+        ),
+        FocusHeuristic::new(
+            "api",
+            &[
+                "api", "server", "handler", "route", "endpoint", "web", "http",
+            ],
+        ),
+        FocusHeuristic::new(
+            "database",
+            &[
+                "db",
+                "database",
+                "sql",
+                "migration",
+                "repo",
+                "store",
+                "storage",
+                "schema",
+            ],
+        ),
+        FocusHeuristic::new("config", &["config", "setting", "env"]),
+        FocusHeuristic::new("errors", &["error", "retry", "recover", "fallback"]),
+    ]
 }
 
 /// File selection configuration
@@ -80,6 +191,20 @@ pub struct ProviderConfig {
 
     /// Temperature for LLM responses
     pub temperature: f64,
+
+    /// Use Anthropic prompt caching for the system prompt; disable to rule
+    /// out stale cached prefixes while debugging
+    #[serde(default = "default_prompt_caching")]
+    pub prompt_caching: bool,
+
+    /// Models to try, in order, when the primary model is overloaded, rate
+    /// limited or unavailable (same provider and API key)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fallback_models: Vec<String>,
+}
+
+fn default_prompt_caching() -> bool {
+    true
 }
 
 /// Cost and quota limits
@@ -111,6 +236,11 @@ pub struct LimitsConfig {
     pub anthropic_cost_per_1m_input_tokens: Option<f64>,
     pub anthropic_cost_per_1m_output_tokens: Option<f64>,
 
+    /// Anthropic prompt-cache pricing (USD per 1M tokens); default to 10%
+    /// (reads) and 125% (writes) of the input price
+    pub anthropic_cost_per_1m_cache_read_tokens: Option<f64>,
+    pub anthropic_cost_per_1m_cache_write_tokens: Option<f64>,
+
     /// Maximum retries for API calls
     pub max_retries: usize,
 
@@ -170,6 +300,8 @@ impl Default for ProviderConfig {
             api_key: None,
             max_tokens: 16000,
             temperature: 0.2,
+            prompt_caching: true,
+            fallback_models: Vec::new(),
         }
     }
 }
@@ -204,6 +336,8 @@ impl Default for LimitsConfig {
             // Claude Opus 4.5 pricing (as of 2025) - premium model for deep analysis
             anthropic_cost_per_1m_input_tokens: Some(15.0),
             anthropic_cost_per_1m_output_tokens: Some(75.0),
+            anthropic_cost_per_1m_cache_read_tokens: Some(1.5),
+            anthropic_cost_per_1m_cache_write_tokens: Some(18.75),
             max_retries: 3,
             retry_delay_ms: 1000,
             exponential_backoff: true,
@@ -280,6 +414,11 @@ impl LlmConfig {
 
     /// Get API key for a specific provider
     pub fn get_api_key_for_provider(&self, provider: &str) -> Result<String> {
+        // The mock provider runs offline
+        if provider.eq_ignore_ascii_case("mock") {
+            return Ok(String::new());
+        }
+
         // Determine which env var to check based on provider
         let env_var = match provider.to_lowercase().as_str() {
             "anthropic" | "claude" => "ANTHROPIC_API_KEY",
@@ -379,6 +518,32 @@ impl LlmConfig {
         true
     }
 
+    /// Heuristic for a focus keyword: the built-in one extended with any
+    /// configured tokens, or the keyword itself as a path token
+    pub fn focus_heuristic(&self, keyword: &str) -> FocusHeuristic {
+        let keyword = keyword.trim().to_lowercase();
+        let mut heuristic = default_focus_heuristics()
+            .into_iter()
+            .find(|h| h.keyword == keyword)
+            .unwrap_or_else(|| FocusHeuristic {
+                keyword: keyword.clone(),
+                path_tokens: Vec::new(),
+            });
+
+        for custom in &self.focus_heuristics {
+            if custom.keyword.to_lowercase() == keyword {
+                heuristic
+                    .path_tokens
+                    .extend(custom.path_tokens.iter().map(|t| t.to_lowercase()));
+            }
+        }
+        if heuristic.path_tokens.is_empty() {
+            heuristic.path_tokens.push(keyword);
+        }
+
+        heuristic
+    }
+
     /// Print configuration summary
     /// Check if using Anthropic/Claude provider
     pub fn is_anthropic(&self) -> bool {
@@ -459,6 +624,66 @@ impl LlmConfig {
         );
     }
 
+    /// Prices for `model`. Claude models are priced by family, with the
+    /// configured Anthropic prices applying to Opus; other models use the
+    /// default provider's prices. An empty model means the configured one.
+    pub fn model_pricing(&self, model: &str) -> ModelPricing {
+        let model = model.to_lowercase();
+        let is_claude = model.starts_with("claude") || (model.is_empty() && self.is_anthropic());
+        if !is_claude {
+            let limits = &self.limits;
+            return ModelPricing {
+                input: limits.cost_per_1m_input_tokens,
+                output: limits.cost_per_1m_output_tokens,
+                cache_read: limits.cost_per_1m_input_tokens,
+                cache_write: limits.cost_per_1m_input_tokens,
+            };
+        }
+
+        let family = |input: f64, output: f64| ModelPricing {
+            input,
+            output,
+            cache_read: input * 0.1,
+            cache_write: input * 1.25,
+        };
+        if model.contains("sonnet") {
+            family(3.0, 15.0)
+        } else if model.contains("haiku") {
+            family(0.8, 4.0)
+        } else {
+            let input = self
+                .limits
+                .anthropic_cost_per_1m_input_tokens
+                .unwrap_or(15.0);
+            ModelPricing {
+                output: self
+                    .limits
+                    .anthropic_cost_per_1m_output_tokens
+                    .unwrap_or(75.0),
+                cache_read: self
+                    .limits
+                    .anthropic_cost_per_1m_cache_read_tokens
+                    .unwrap_or(input * 0.1),
+                cache_write: self
+                    .limits
+                    .anthropic_cost_per_1m_cache_write_tokens
+                    .unwrap_or(input * 1.25),
+                input,
+            }
+        }
+    }
+
+    /// Tokens `text` takes up for the configured model
+    pub fn count_tokens(&self, text: &str) -> usize {
+        crate::tokenizer::count_tokens(text, &self.provider.default_model)
+    }
+
+    /// Estimated cost of sending `prompt` and getting `output_tokens` back,
+    /// with the prompt counted by the tokenizer
+    pub fn estimate_prompt_cost(&self, prompt: &str, output_tokens: usize) -> f64 {
+        self.estimate_cost(self.count_tokens(prompt), output_tokens)
+    }
+
     /// Calculate estimated cost for token usage
     pub fn estimate_cost(&self, input_tokens: usize, output_tokens: usize) -> f64 {
         let input_cost = (input_tokens as f64 / 1_000_000.0) * self.limits.cost_per_1m_input_tokens;
@@ -467,6 +692,38 @@ impl LlmConfig {
         input_cost + output_cost
     }
 
+    /// Estimated cost of a response from `model`, priced with
+    /// [`Self::model_pricing`] so a fallback model is billed at its own
+    /// rate. Estimated token counts (no usage reported) are all input.
+    pub fn estimate_cost_for_model(
+        &self,
+        model: &str,
+        input_tokens: usize,
+        output_tokens: usize,
+    ) -> f64 {
+        self.estimate_cost_with_cache(
+            model,
+            &crate::llm::LlmUsage {
+                input_tokens,
+                output_tokens,
+                ..Default::default()
+            },
+        )
+    }
+
+    /// Estimated cost of a response from `model` whose input was partly
+    /// served from (or written to) the Anthropic prompt cache. Other
+    /// providers bill cached tokens as regular input here.
+    pub fn estimate_cost_with_cache(&self, model: &str, usage: &crate::llm::LlmUsage) -> f64 {
+        let per_token = |per_1m: f64| per_1m / 1_000_000.0;
+        let pricing = self.model_pricing(model);
+
+        usage.input_tokens as f64 * per_token(pricing.input)
+            + usage.cache_read_tokens as f64 * per_token(pricing.cache_read)
+            + usage.cache_write_tokens as f64 * per_token(pricing.cache_write)
+            + usage.output_tokens as f64 * per_token(pricing.output)
+    }
+
     /// Check if we're within budget
     pub fn check_budget(&self, current_cost: f64) -> BudgetStatus {
         if let Some(max_cost) = self.limits.max_monthly_cost_usd {
@@ -488,6 +745,15 @@ impl LlmConfig {
     }
 }
 
+/// Prices for one model, in USD per 1M tokens
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ModelPricing {
+    pub input: f64,
+    pub output: f64,
+    pub cache_read: f64,
+    pub cache_write: f64,
+}
+
 /// Budget status for cost tracking
 #[derive(Debug, Clone)]
 pub enum BudgetStatus {
@@ -585,6 +851,34 @@ mod tests {
         assert!(config.enabled);
     }
 
+    #[test]
+    fn test_estimate_cost_with_cache_discounts_cache_reads() {
+        let mut config = LlmConfig::default();
+        config.provider.default_provider = "anthropic".to_string();
+        let usage = crate::llm::LlmUsage {
+            input_tokens: 1_000_000,
+            output_tokens: 0,
+            cache_read_tokens: 1_000_000,
+            cache_write_tokens: 0,
+        };
+        // $15 uncached + $1.50 cached
+        assert!((config.estimate_cost_with_cache("", &usage) - 16.5).abs() < 1e-9);
+        // A Sonnet fallback is billed at Sonnet rates
+        assert!(
+            (config.estimate_cost_with_cache(claude_models::CLAUDE_SONNET_4, &usage) - 3.3).abs()
+                < 1e-9
+        );
+        assert!((config.estimate_cost_for_model("grok-4", 1_000_000, 0) - 0.30).abs() < 1e-9);
+        assert_eq!(config.count_tokens("Hello, world!"), 4);
+
+        // Old config files without the prompt-caching keys still load
+        let provider: ProviderConfig = toml::from_str(
+            "default_provider = \"anthropic\"\ndefault_model = \"m\"\nmax_tokens = 10\ntemperature = 0.2\n",
+        )
+        .unwrap();
+        assert!(provider.prompt_caching);
+    }
+
     #[test]
     fn test_glob_match() {
         assert!(glob_match("**/*.rs", "src/main.rs"));
@@ -0,0 +1,63 @@
+//! JSON Schemas for rustassistant's serialized output
+//!
+//! Each schema is derived from the struct it describes (via `schemars`), so
+//! it changes whenever the serialized shape does. Tools consuming our JSON
+//! can fetch one with the hidden `rustassistant schema <name>` command.
+
+use schemars::schema::RootSchema;
+use schemars::schema_for;
+
+/// Names accepted by [`output_schema`], with the type each one describes
+pub const OUTPUT_SCHEMAS: &[(&str, &str)] = &[
+    ("audit-report", "AuditReport written by `audit --output`"),
+    ("file-score", "FileScore in `GET /api/repos/:id/detail`"),
+    (
+        "review",
+        "CodeReview produced by `review diff` and `review staged`",
+    ),
+    ("todo", "ScanOutput printed by `todo scan --json`"),
+];
+
+/// JSON Schema for the output type called `name` (see [`OUTPUT_SCHEMAS`])
+pub fn output_schema(name: &str) -> Option<RootSchema> {
+    let schema = match name {
+        "audit-report" => schema_for!(crate::types::AuditReport),
+        "file-score" => schema_for!(crate::scoring::FileScore),
+        "review" => schema_for!(crate::code_review::CodeReview),
+        "todo" => schema_for!(crate::todo::ScanOutput),
+        _ => return None,
+    };
+    Some(schema)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_listed_schema_is_generated() {
+        for (name, _) in OUTPUT_SCHEMAS {
+            let schema = output_schema(name).unwrap_or_else(|| panic!("no schema for {}", name));
+            let json = serde_json::to_value(&schema).unwrap();
+            assert_eq!(json["type"], "object", "{}", name);
+        }
+        assert!(output_schema("nope").is_none());
+    }
+
+    #[test]
+    fn test_audit_report_schema_tracks_struct_fields() {
+        let json = serde_json::to_value(output_schema("audit-report").unwrap()).unwrap();
+        let properties = json["properties"].as_object().unwrap();
+        for field in ["schema_version", "files", "issues_by_severity", "summary"] {
+            assert!(properties.contains_key(field), "missing {}", field);
+        }
+        // Optional fields aren't required
+        let required: Vec<&str> = json["required"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter_map(|v| v.as_str())
+            .collect();
+        assert!(!required.contains(&"test_results"));
+    }
+}
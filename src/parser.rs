@@ -2,7 +2,9 @@
 //!
 //! This module provides Rust-aware parsing for static analysis,
 //! using regex patterns to extract function signatures, types,
-//! imports, and calculate complexity metrics.
+//! imports, and calculate complexity metrics. [`extract_symbols`]
+//! covers Python and TypeScript/JavaScript, and [`extract_code_blocks`]
+//! pulls fenced code out of markdown.
 
 use crate::error::Result;
 use crate::types::Category;
@@ -501,6 +503,478 @@ pub struct ComplexityMetrics {
     pub lloc: usize,
 }
 
+/// Code block extracted from a markdown document
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CodeBlock {
+    /// Language from the fence info string, canonicalized (`rs` -> `rust`).
+    /// `None` for bare fences and indented blocks.
+    pub language: Option<String>,
+    /// First line of the block (1-based, the opening fence for fenced blocks)
+    pub start_line: usize,
+    /// Last line of the block (1-based, the closing fence if present)
+    pub end_line: usize,
+    /// Block contents without fences or indentation
+    pub content: String,
+    /// Whether the block was fenced (``` or ~~~) rather than indented
+    pub fenced: bool,
+}
+
+/// Map common info-string aliases to one canonical language name
+pub fn canonical_language(tag: &str) -> String {
+    let tag = tag.trim().to_lowercase();
+    match tag.as_str() {
+        "rs" => "rust",
+        "py" | "python3" => "python",
+        "ts" | "tsx" => "typescript",
+        "js" | "jsx" | "node" => "javascript",
+        "sh" | "shell" | "zsh" => "bash",
+        "yml" => "yaml",
+        "golang" => "go",
+        _ => return tag,
+    }
+    .to_string()
+}
+
+/// Opening fence: up to 3 spaces, then a run of backticks or tildes
+fn parse_fence_open(line: &str) -> Option<(char, usize, usize, &str)> {
+    let indent = line.len() - line.trim_start_matches(' ').len();
+    if indent > 3 {
+        return None;
+    }
+    let rest = &line[indent..];
+    let ch = rest.chars().next().filter(|c| *c == '`' || *c == '~')?;
+    let len = rest.len() - rest.trim_start_matches(ch).len();
+    if len < 3 {
+        return None;
+    }
+    let info = rest[len..].trim();
+    // A backtick info string can't contain backticks (that's inline code)
+    if ch == '`' && info.contains('`') {
+        return None;
+    }
+    Some((ch, len, indent, info))
+}
+
+/// Closing fence: same character, at least as long as the opener, nothing after
+fn is_fence_close(line: &str, ch: char, open_len: usize) -> bool {
+    let indent = line.len() - line.trim_start_matches(' ').len();
+    if indent > 3 {
+        return false;
+    }
+    let rest = &line[indent..];
+    let len = rest.len() - rest.trim_start_matches(ch).len();
+    len >= open_len && rest[len..].trim().is_empty()
+}
+
+/// Strip 4 columns of indentation (a leading tab counts as 4)
+fn strip_code_indent(line: &str) -> Option<&str> {
+    line.strip_prefix('\t')
+        .or_else(|| line.strip_prefix("    "))
+}
+
+/// Extract fenced and indented code blocks from markdown
+///
+/// Follows the CommonMark fence rules: a fence closes only on the same
+/// character with at least as many markers, so a ```` ```` ```` block can
+/// contain ``` lines. An unclosed fence runs to the end of the document.
+///
+/// Known limitations: indented blocks are only recognized after a blank
+/// line (or at the start of the document), and indentation inside list
+/// items or block quotes is not tracked, so indented list continuations
+/// may be reported as code.
+pub fn extract_code_blocks(markdown: &str) -> Vec<CodeBlock> {
+    let lines: Vec<&str> = markdown.lines().collect();
+    let mut blocks = Vec::new();
+    let mut prev_blank = true;
+    let mut i = 0;
+
+    while i < lines.len() {
+        let line = lines[i];
+
+        if let Some((ch, open_len, indent, info)) = parse_fence_open(line) {
+            let language = info
+                .split(|c: char| c.is_whitespace() || c == ',')
+                .next()
+                .map(|tag| tag.trim_start_matches(['{', '.']).trim_end_matches('}'))
+                .filter(|tag| !tag.is_empty())
+                .map(canonical_language);
+
+            let start = i;
+            let mut body = Vec::new();
+            let mut end = lines.len() - 1;
+            i += 1;
+            while i < lines.len() {
+                if is_fence_close(lines[i], ch, open_len) {
+                    end = i;
+                    break;
+                }
+                // Content lines lose up to the opener's indentation
+                let l = lines[i];
+                let strip = indent.min(l.len() - l.trim_start_matches(' ').len());
+                body.push(&l[strip..]);
+                i += 1;
+            }
+
+            blocks.push(CodeBlock {
+                language,
+                start_line: start + 1,
+                end_line: end + 1,
+                content: body.join("\n"),
+                fenced: true,
+            });
+            i = end + 1;
+            prev_blank = false;
+            continue;
+        }
+
+        if prev_blank && strip_code_indent(line).is_some_and(|l| !l.trim().is_empty()) {
+            let start = i;
+            let mut last = i;
+            let mut body = Vec::new();
+            while i < lines.len() {
+                let l = lines[i];
+                if let Some(code) = strip_code_indent(l) {
+                    body.push(code);
+                    if !code.trim().is_empty() {
+                        last = i;
+                    }
+                } else if l.trim().is_empty() {
+                    body.push("");
+                } else {
+                    break;
+                }
+                i += 1;
+            }
+            // Trailing blank lines belong to the surrounding document
+            body.truncate(last - start + 1);
+
+            blocks.push(CodeBlock {
+                language: None,
+                start_line: start + 1,
+                end_line: last + 1,
+                content: body.join("\n"),
+                fenced: false,
+            });
+            i = last + 1;
+            prev_blank = false;
+            continue;
+        }
+
+        prev_blank = line.trim().is_empty();
+        i += 1;
+    }
+
+    blocks
+}
+
+/// Symbol kind reported by [`extract_symbols`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SymbolKind {
+    Function,
+    Class,
+    Method,
+}
+
+/// Symbol visibility
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Visibility {
+    Public,
+    Protected,
+    Private,
+}
+
+/// Function, class or method found in a source file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Symbol {
+    /// Symbol name
+    pub name: String,
+    /// Symbol kind
+    pub kind: SymbolKind,
+    /// Visibility (exported / underscore-prefixed rules per language)
+    pub visibility: Visibility,
+    /// First line of the definition (1-based)
+    pub start_line: usize,
+    /// Last line of the body (1-based)
+    pub end_line: usize,
+    /// Declaration without its body, e.g. `def load(path: str) -> dict`
+    pub signature: String,
+}
+
+/// Extract functions, classes and methods from Python or TypeScript source
+///
+/// `language` accepts the same names as [`canonical_language`] (`python`,
+/// `py`, `typescript`, `ts`, `javascript`, `js`). Other languages return an
+/// empty list; Rust is covered by [`Parser`].
+///
+/// This is a line-based scan, not a grammar. Known limitations:
+/// - Python: spans come from indentation, so a body continued on a line that
+///   is less indented (inside brackets or a triple-quoted string) ends early.
+///   Decorators are not part of the span. Names starting with one underscore
+///   are private; dunder methods are public.
+/// - TypeScript/JavaScript: one declaration per line is recognized, and the
+///   first `{` after a declaration is taken as its body, so object-typed or
+///   defaulted parameters can confuse the span. Template literal
+///   interpolation (`${...}`) is treated as part of the string. Function
+///   expressions other than module-level `const f = (...) =>` are not
+///   reported, and an expression-bodied arrow function without a trailing
+///   `;` keeps its span open until the next declaration.
+pub fn extract_symbols(content: &str, language: &str) -> Vec<Symbol> {
+    match canonical_language(language).as_str() {
+        "python" => extract_python_symbols(content),
+        "typescript" | "javascript" => extract_ts_symbols(content),
+        _ => Vec::new(),
+    }
+}
+
+fn indent_width(line: &str) -> usize {
+    line.chars()
+        .take_while(|c| c.is_whitespace())
+        .map(|c| if c == '\t' { 4 } else { 1 })
+        .sum()
+}
+
+fn python_visibility(name: &str) -> Visibility {
+    let dunder = name.starts_with("__") && name.ends_with("__");
+    if name.starts_with('_') && !dunder {
+        Visibility::Private
+    } else {
+        Visibility::Public
+    }
+}
+
+fn extract_python_symbols(content: &str) -> Vec<Symbol> {
+    let def_regex = Regex::new(r"^\s*(?:async\s+)?def\s+(\w+)\s*\(").expect("Invalid def regex");
+    let class_regex = Regex::new(r"^\s*class\s+(\w+)").expect("Invalid class regex");
+
+    let lines: Vec<&str> = content.lines().collect();
+    let mut symbols = Vec::new();
+    // Enclosing definitions as (indent, kind)
+    let mut scopes: Vec<(usize, SymbolKind)> = Vec::new();
+
+    for (i, line) in lines.iter().enumerate() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        let indent = indent_width(line);
+        scopes.retain(|(scope_indent, _)| *scope_indent < indent);
+
+        let (name, kind) = if let Some(caps) = def_regex.captures(line) {
+            let in_class = scopes.last().is_some_and(|(_, k)| *k == SymbolKind::Class);
+            let kind = if in_class {
+                SymbolKind::Method
+            } else {
+                SymbolKind::Function
+            };
+            (caps[1].to_string(), kind)
+        } else if let Some(caps) = class_regex.captures(line) {
+            (caps[1].to_string(), SymbolKind::Class)
+        } else {
+            continue;
+        };
+
+        // The header may span lines; it ends at a `:` outside brackets
+        let mut header = Vec::new();
+        let mut depth = 0i32;
+        let mut header_end = i;
+        'header: for (j, l) in lines.iter().enumerate().skip(i) {
+            header.push(l.trim());
+            header_end = j;
+            for c in l.chars() {
+                match c {
+                    '(' | '[' | '{' => depth += 1,
+                    ')' | ']' | '}' => depth -= 1,
+                    _ => {}
+                }
+            }
+            if depth <= 0 && l.trim_end().ends_with(':') {
+                break 'header;
+            }
+        }
+        let signature = header
+            .join(" ")
+            .trim_end()
+            .trim_end_matches(':')
+            .trim_end()
+            .to_string();
+
+        // The body is every following line indented deeper than the header
+        let mut end = header_end;
+        for (j, l) in lines.iter().enumerate().skip(header_end + 1) {
+            let t = l.trim();
+            if t.is_empty() {
+                continue;
+            }
+            if indent_width(l) <= indent && !t.starts_with('#') {
+                break;
+            }
+            end = j;
+        }
+
+        symbols.push(Symbol {
+            visibility: python_visibility(&name),
+            name,
+            kind,
+            start_line: i + 1,
+            end_line: end + 1,
+            signature,
+        });
+        scopes.push((indent, kind));
+    }
+
+    symbols
+}
+
+/// Declaration text up to the body's opening brace at bracket depth zero
+fn ts_signature(line: &str) -> String {
+    let mut depth = 0i32;
+    for (idx, c) in line.char_indices() {
+        match c {
+            '(' | '[' | '<' => depth += 1,
+            ')' | ']' => depth -= 1,
+            // `=>` is not a closing angle bracket
+            '>' if !line[..idx].ends_with('=') => depth -= 1,
+            '{' if depth <= 0 => return line[..idx].trim().to_string(),
+            _ => {}
+        }
+    }
+    line.trim().trim_end_matches(';').trim_end().to_string()
+}
+
+fn extract_ts_symbols(content: &str) -> Vec<Symbol> {
+    let function_regex = Regex::new(
+        r"^\s*(export\s+)?(?:default\s+)?(?:declare\s+)?(?:async\s+)?function\s*\*?\s*(\w+)\s*(?:<[^>]*>)?\s*\(",
+    )
+    .expect("Invalid function regex");
+    let class_regex =
+        Regex::new(r"^\s*(export\s+)?(?:default\s+)?(?:declare\s+)?(?:abstract\s+)?class\s+(\w+)")
+            .expect("Invalid class regex");
+    let arrow_regex = Regex::new(
+        r"^\s*(export\s+)?(?:const|let|var)\s+(\w+)\s*(?::[^=]+)?=\s*(?:async\s+)?(?:\([^)]*\)|\w+)\s*(?::\s*[^=]+)?=>",
+    )
+    .expect("Invalid arrow fn regex");
+    let method_regex = Regex::new(
+        r"^\s*((?:(?:public|private|protected|static|readonly|async|override|abstract|get|set)\s+)*)\*?\s*(#?\w+)\s*\??\s*(?:<[^>]*>)?\s*\(",
+    )
+    .expect("Invalid method regex");
+    const NOT_METHODS: &[&str] = &[
+        "if", "for", "while", "switch", "catch", "return", "function", "new", "await", "typeof",
+        "super", "this",
+    ];
+
+    let mut symbols: Vec<Symbol> = Vec::new();
+    // Owner of each open brace (index into `symbols`)
+    let mut braces: Vec<Option<usize>> = Vec::new();
+    // Declaration waiting for its body's `{`
+    let mut pending: Option<usize> = None;
+    let mut in_block_comment = false;
+
+    for (i, line) in content.lines().enumerate() {
+        let line_no = i + 1;
+        let in_class_body = braces
+            .last()
+            .and_then(|owner| *owner)
+            .is_some_and(|idx| symbols[idx].kind == SymbolKind::Class);
+
+        let found = if in_block_comment {
+            None
+        } else if in_class_body {
+            method_regex.captures(line).and_then(|caps| {
+                let name = caps[2].to_string();
+                if NOT_METHODS.contains(&name.as_str()) {
+                    return None;
+                }
+                let modifiers = caps.get(1).map_or("", |m| m.as_str());
+                let visibility = if name.starts_with('#') || modifiers.contains("private") {
+                    Visibility::Private
+                } else if modifiers.contains("protected") {
+                    Visibility::Protected
+                } else {
+                    Visibility::Public
+                };
+                Some((name, SymbolKind::Method, visibility))
+            })
+        } else {
+            let exported = |is_export: bool| {
+                if is_export {
+                    Visibility::Public
+                } else {
+                    Visibility::Private
+                }
+            };
+            if let Some(caps) = function_regex.captures(line) {
+                let visibility = exported(caps.get(1).is_some());
+                Some((caps[2].to_string(), SymbolKind::Function, visibility))
+            } else if let Some(caps) = class_regex.captures(line) {
+                let visibility = exported(caps.get(1).is_some());
+                Some((caps[2].to_string(), SymbolKind::Class, visibility))
+            } else if braces.is_empty() {
+                // Arrow functions only count at module level
+                arrow_regex.captures(line).map(|caps| {
+                    let visibility = exported(caps.get(1).is_some());
+                    (caps[2].to_string(), SymbolKind::Function, visibility)
+                })
+            } else {
+                None
+            }
+        };
+
+        if let Some((name, kind, visibility)) = found {
+            symbols.push(Symbol {
+                name,
+                kind,
+                visibility,
+                start_line: line_no,
+                end_line: line_no,
+                signature: ts_signature(line),
+            });
+            pending = Some(symbols.len() - 1);
+        }
+
+        // Track braces, skipping strings and comments
+        let chars: Vec<char> = line.chars().collect();
+        let mut quote: Option<char> = None;
+        let mut j = 0;
+        while j < chars.len() {
+            let c = chars[j];
+            let next = chars.get(j + 1).copied();
+            if in_block_comment {
+                if c == '*' && next == Some('/') {
+                    in_block_comment = false;
+                    j += 1;
+                }
+            } else if let Some(q) = quote {
+                if c == '\\' {
+                    j += 1;
+                } else if c == q {
+                    quote = None;
+                }
+            } else {
+                match c {
+                    '/' if next == Some('/') => break,
+                    '/' if next == Some('*') => {
+                        in_block_comment = true;
+                        j += 1;
+                    }
+                    '"' | '\'' | '`' => quote = Some(c),
+                    '{' => braces.push(pending.take()),
+                    '}' => {
+                        if let Some(Some(idx)) = braces.pop() {
+                            symbols[idx].end_line = line_no;
+                        }
+                    }
+                    // Overloads and abstract methods have no body
+                    ';' if pending.is_some() => pending = None,
+                    _ => {}
+                }
+            }
+            j += 1;
+        }
+    }
+
+    symbols
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -684,4 +1158,194 @@ impl MyStruct {
         assert_eq!(functions[0].param_count, 2); // &self + x
         assert_eq!(functions[1].param_count, 1); // &mut self only
     }
+
+    #[test]
+    fn test_extract_code_blocks_language_and_span() {
+        let md = "# Notes\n\n```rust\nfn main() {}\n```\n\ntext\n\n```\nplain\n```\n";
+        let blocks = extract_code_blocks(md);
+
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(blocks[0].language.as_deref(), Some("rust"));
+        assert_eq!((blocks[0].start_line, blocks[0].end_line), (3, 5));
+        assert_eq!(blocks[0].content, "fn main() {}");
+        assert!(blocks[0].fenced);
+        assert_eq!(blocks[1].language, None);
+        assert_eq!(blocks[1].content, "plain");
+    }
+
+    #[test]
+    fn test_extract_code_blocks_fence_lengths() {
+        // A four-backtick fence contains a three-backtick one
+        let md = "````markdown\n```rs\nlet x = 1;\n```\n````\n";
+        let blocks = extract_code_blocks(md);
+
+        assert_eq!(blocks.len(), 1);
+        assert_eq!(blocks[0].language.as_deref(), Some("markdown"));
+        assert_eq!(blocks[0].content, "```rs\nlet x = 1;\n```");
+        assert_eq!(blocks[0].end_line, 5);
+
+        // A closer may be longer than the opener, but not shorter
+        let md = "```py\nprint(1)\n``\n`````\n";
+        let blocks = extract_code_blocks(md);
+        assert_eq!(blocks[0].language.as_deref(), Some("python"));
+        assert_eq!(blocks[0].content, "print(1)\n``");
+    }
+
+    #[test]
+    fn test_extract_code_blocks_tilde_fences() {
+        let md = "~~~ts\nconst a = `x`;\n```\n~~~\n";
+        let blocks = extract_code_blocks(md);
+
+        assert_eq!(blocks.len(), 1);
+        assert_eq!(blocks[0].language.as_deref(), Some("typescript"));
+        // Backticks never close a tilde fence
+        assert_eq!(blocks[0].content, "const a = `x`;\n```");
+    }
+
+    #[test]
+    fn test_extract_code_blocks_info_string_and_unclosed() {
+        let blocks = extract_code_blocks("```rust,ignore\nbody\n```\n");
+        assert_eq!(blocks[0].language.as_deref(), Some("rust"));
+
+        let blocks = extract_code_blocks("``` {.python}\nbody\n```\n");
+        assert_eq!(blocks[0].language.as_deref(), Some("python"));
+
+        let blocks = extract_code_blocks("text\n```go\nfunc f() {}\n");
+        assert_eq!(blocks[0].end_line, 3);
+        assert_eq!(blocks[0].content, "func f() {}");
+    }
+
+    #[test]
+    fn test_extract_code_blocks_indented() {
+        let md = "Intro\n\n    let a = 1;\n\n    let b = 2;\n\nAfter\n    not code\n";
+        let blocks = extract_code_blocks(md);
+
+        assert_eq!(blocks.len(), 1);
+        assert!(!blocks[0].fenced);
+        assert_eq!(blocks[0].language, None);
+        assert_eq!((blocks[0].start_line, blocks[0].end_line), (3, 5));
+        assert_eq!(blocks[0].content, "let a = 1;\n\nlet b = 2;");
+    }
+
+    #[test]
+    fn test_extract_python_symbols() {
+        let src = r#"
+import os
+
+def load(path: str,
+         strict: bool = False) -> dict:
+    """Load a file."""
+    with open(path) as f:
+        return parse(f)
+
+class Store:
+    def __init__(self, root):
+        self.root = root
+
+    def _cache_key(self, name):
+        return name
+
+    async def fetch(self, name):
+        def helper():
+            pass
+        return helper()
+
+def _private():
+    pass
+"#;
+        let symbols = extract_symbols(src, "py");
+        let names: Vec<_> = symbols.iter().map(|s| (s.name.as_str(), s.kind)).collect();
+        assert_eq!(
+            names,
+            vec![
+                ("load", SymbolKind::Function),
+                ("Store", SymbolKind::Class),
+                ("__init__", SymbolKind::Method),
+                ("_cache_key", SymbolKind::Method),
+                ("fetch", SymbolKind::Method),
+                ("helper", SymbolKind::Function),
+                ("_private", SymbolKind::Function),
+            ]
+        );
+
+        let load = &symbols[0];
+        assert_eq!(
+            load.signature,
+            "def load(path: str, strict: bool = False) -> dict"
+        );
+        assert_eq!((load.start_line, load.end_line), (4, 8));
+        assert_eq!(symbols[1].end_line, 20);
+        assert_eq!(symbols[2].visibility, Visibility::Public);
+        assert_eq!(symbols[3].visibility, Visibility::Private);
+        assert_eq!(symbols[6].visibility, Visibility::Private);
+    }
+
+    #[test]
+    fn test_extract_typescript_symbols() {
+        let src = r#"
+import { x } from "./x";
+
+export function add(a: number, b: number): number {
+  return a + b; // "}" in a comment
+}
+
+function helper(s: string) {
+  const t = "{not a brace";
+  return t;
+}
+
+export const double = (n: number): number => n * 2;
+
+export class Counter {
+  private count = 0;
+
+  constructor(start: number) {
+    this.count = start;
+  }
+
+  public increment(): void {
+    if (this.count > 0) {
+      this.count++;
+    }
+  }
+
+  protected async reset(): Promise<void> {}
+
+  #secret() {}
+}
+"#;
+        let symbols = extract_symbols(src, "typescript");
+        let names: Vec<_> = symbols.iter().map(|s| (s.name.as_str(), s.kind)).collect();
+        assert_eq!(
+            names,
+            vec![
+                ("add", SymbolKind::Function),
+                ("helper", SymbolKind::Function),
+                ("double", SymbolKind::Function),
+                ("Counter", SymbolKind::Class),
+                ("constructor", SymbolKind::Method),
+                ("increment", SymbolKind::Method),
+                ("reset", SymbolKind::Method),
+                ("#secret", SymbolKind::Method),
+            ]
+        );
+
+        assert_eq!(
+            symbols[0].signature,
+            "export function add(a: number, b: number): number"
+        );
+        assert_eq!((symbols[0].start_line, symbols[0].end_line), (4, 6));
+        assert_eq!((symbols[1].start_line, symbols[1].end_line), (8, 11));
+        assert_eq!(symbols[1].visibility, Visibility::Private);
+        assert_eq!(symbols[2].end_line, 13);
+        assert_eq!((symbols[3].start_line, symbols[3].end_line), (15, 31));
+        assert_eq!(symbols[5].end_line, 26);
+        assert_eq!(symbols[6].visibility, Visibility::Protected);
+        assert_eq!(symbols[7].visibility, Visibility::Private);
+    }
+
+    #[test]
+    fn test_extract_symbols_unknown_language() {
+        assert!(extract_symbols("fn main() {}", "rust").is_empty());
+    }
 }
@@ -0,0 +1,220 @@
+//! Include/exclude filters on repo-relative paths
+//!
+//! A [`PathFilter`] narrows the files a scan has already walked (after
+//! `.gitignore` and the scanner's own skips). Patterns are globs, compiled
+//! with `globset`, matched against paths relative to the repository root:
+//!
+//! - `*` matches within one path component, `?` one character, `[abc]`,
+//!   `[a-z]` or `[!a-z]` one character from (or not from) a set, `{a,b}`
+//!   either alternative, and `**` any number of components
+//! - a pattern without a `/` matches at any depth, like `.gitignore`
+//!   (`*.rs` is `**/*.rs`)
+//! - a pattern that matches a directory covers everything below it
+//!   (`src/` or `src` is `src/**`)
+//!
+//! With any include patterns only files matching one of them are kept;
+//! exclude patterns then remove files. Exclude wins over include.
+
+use crate::error::{AuditError, Result};
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+use std::path::Path;
+
+/// Include/exclude globs over repo-relative paths
+#[derive(Debug, Clone, Default)]
+pub struct PathFilter {
+    include: GlobSet,
+    exclude: GlobSet,
+    /// Include then exclude globs in one set, to count matches per pattern
+    all: GlobSet,
+    /// Index into `patterns` of each glob in `all`
+    owners: Vec<usize>,
+    /// Patterns as given, for messages
+    patterns: Vec<String>,
+}
+
+impl PathFilter {
+    /// Filter from `--include` and `--exclude` patterns; fails on a
+    /// pattern that isn't a valid glob
+    pub fn new(include: Vec<String>, exclude: Vec<String>) -> Result<Self> {
+        let patterns: Vec<String> = include.iter().chain(&exclude).cloned().collect();
+        let (all, owners) = glob_set(&patterns)?;
+        Ok(Self {
+            include: glob_set(&include)?.0,
+            exclude: glob_set(&exclude)?.0,
+            all,
+            owners,
+            patterns,
+        })
+    }
+
+    /// Whether the filter has no patterns and so keeps everything
+    pub fn is_empty(&self) -> bool {
+        self.include.is_empty() && self.exclude.is_empty()
+    }
+
+    /// Whether the file at `rel_path` (relative to the repository root) is kept
+    pub fn matches(&self, rel_path: &Path) -> bool {
+        let included = self.include.is_empty() || self.include.is_match(rel_path);
+        included && !self.exclude.is_match(rel_path)
+    }
+
+    /// Include and exclude patterns, as given, that match none of
+    /// `rel_paths`; usually a typo worth warning about
+    pub fn unmatched_patterns<'a>(
+        &self,
+        rel_paths: impl IntoIterator<Item = &'a Path>,
+    ) -> Vec<&str> {
+        let mut counts = vec![0usize; self.patterns.len()];
+        let mut hits = Vec::new();
+        for path in rel_paths {
+            self.all.matches_into(path, &mut hits);
+            for &glob in &hits {
+                counts[self.owners[glob]] += 1;
+            }
+        }
+        counts
+            .iter()
+            .zip(&self.patterns)
+            .filter(|(&count, _)| count == 0)
+            .map(|(_, pattern)| pattern.as_str())
+            .collect()
+    }
+}
+
+/// One set matching any of `patterns`, each with its directory form, and
+/// the index of the pattern each glob in the set came from
+fn glob_set(patterns: &[String]) -> Result<(GlobSet, Vec<usize>)> {
+    let mut builder = GlobSetBuilder::new();
+    let mut owners = Vec::new();
+    for (i, pattern) in patterns.iter().enumerate() {
+        for glob in globs(pattern) {
+            let glob = GlobBuilder::new(&glob)
+                .literal_separator(true)
+                .build()
+                .map_err(|e| {
+                    AuditError::InvalidRequest(format!("invalid path pattern '{}': {}", pattern, e))
+                })?;
+            builder.add(glob);
+            owners.push(i);
+        }
+    }
+    let set = builder
+        .build()
+        .map_err(|e| AuditError::InvalidRequest(format!("invalid path patterns: {}", e)))?;
+    Ok((set, owners))
+}
+
+/// `./src/` → `**/src` and `**/src/**`; `*.rs` → `**/*.rs` and `**/*.rs/**`
+fn globs(pattern: &str) -> Vec<String> {
+    let pattern = pattern
+        .trim()
+        .replace('\\', "/")
+        .trim_start_matches("./")
+        .trim_end_matches('/')
+        .to_string();
+    let pattern = if pattern.contains('/') {
+        pattern
+    } else {
+        format!("**/{}", pattern)
+    };
+    if pattern.ends_with("/**") {
+        vec![pattern]
+    } else {
+        vec![format!("{}/**", pattern), pattern]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kept(filter: &PathFilter, path: &str) -> bool {
+        filter.matches(Path::new(path))
+    }
+
+    #[test]
+    fn test_mixed_include_exclude() {
+        let filter = PathFilter::new(
+            vec!["src/".into(), "*.toml".into()],
+            vec!["src/generated/**".into(), "*_test.rs".into()],
+        )
+        .unwrap();
+
+        assert!(kept(&filter, "src/main.rs"));
+        assert!(kept(&filter, "src/api/handlers.rs"));
+        assert!(kept(&filter, "Cargo.toml"));
+        assert!(kept(&filter, "config/app.toml"));
+
+        // Not included
+        assert!(!kept(&filter, "examples/demo.rs"));
+        assert!(!kept(&filter, "README.md"));
+
+        // Included, but exclude wins
+        assert!(!kept(&filter, "src/generated/schema.rs"));
+        assert!(!kept(&filter, "src/api/handlers_test.rs"));
+    }
+
+    #[test]
+    fn test_exclude_only_keeps_the_rest() {
+        let filter = PathFilter::new(vec![], vec!["examples".into()]).unwrap();
+        assert!(kept(&filter, "src/lib.rs"));
+        assert!(!kept(&filter, "examples/demo.rs"));
+        assert!(!kept(&filter, "crates/core/examples/demo.rs"));
+        assert!(PathFilter::default().is_empty());
+        assert!(kept(&PathFilter::default(), "anything/at/all.rs"));
+    }
+
+    #[test]
+    fn test_recursive_globs_and_classes() {
+        let filter = PathFilter::new(vec!["src/**/*.rs".into()], vec![]).unwrap();
+        assert!(kept(&filter, "src/lib.rs"));
+        assert!(kept(&filter, "src/api/v1/handlers.rs"));
+        // Not a loose substring match
+        assert!(!kept(&filter, "src/notes.rs.bak"));
+        assert!(!kept(&filter, "docs/src/lib.rs"));
+        assert!(!kept(&filter, "examples/.rs/demo.txt"));
+
+        let filter = PathFilter::new(
+            vec!["crates/core-[ab]/**".into()],
+            vec!["*_v[0-9].rs".into()],
+        )
+        .unwrap();
+        assert!(kept(&filter, "crates/core-a/src/lib.rs"));
+        assert!(!kept(&filter, "crates/core-c/src/lib.rs"));
+        assert!(!kept(&filter, "crates/core-b/src/api_v2.rs"));
+        assert!(kept(&filter, "crates/core-b/src/api_vx.rs"));
+
+        let filter = PathFilter::new(vec!["*.{toml,md}".into()], vec![]).unwrap();
+        assert!(kept(&filter, "docs/guide.md"));
+        assert!(!kept(&filter, "src/lib.rs"));
+    }
+
+    #[test]
+    fn test_unmatched_patterns() {
+        let filter = PathFilter::new(
+            vec!["src/**/*.rs".into(), "*.kt".into()],
+            vec!["target/**".into(), "src/gen".into()],
+        )
+        .unwrap();
+        let paths = [Path::new("src/lib.rs"), Path::new("src/gen/schema.rs")];
+        assert_eq!(filter.unmatched_patterns(paths), vec!["*.kt", "target/**"]);
+
+        // One file matched by several patterns counts for each of them
+        let filter =
+            PathFilter::new(vec!["src".into(), "*.rs".into()], vec!["lib.rs".into()]).unwrap();
+        assert!(filter
+            .unmatched_patterns([Path::new("src/lib.rs")])
+            .is_empty());
+        assert!(PathFilter::default()
+            .unmatched_patterns([Path::new("src/lib.rs")])
+            .is_empty());
+    }
+
+    #[test]
+    fn test_invalid_pattern_is_rejected() {
+        let err = PathFilter::new(vec!["src/[a-".into()], vec![])
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("src/[a-"), "{}", err);
+    }
+}
@@ -99,7 +99,7 @@ pub enum Action {
     SearchDatabase(String),
 
     /// Call Grok with full context
-    CallGrok(Context),
+    CallGrok(Box<Context>),
 
     /// Call Grok with minimal context (for simple questions)
     CallGrokMinimal(String),
@@ -208,13 +208,13 @@ impl QueryRouter {
             QueryIntent::RepoAnalysis => {
                 self.stats.grok_calls += 1;
                 let context = self.build_analysis_context(query, user_context).await?;
-                Ok(Action::CallGrok(context))
+                Ok(Action::CallGrok(Box::new(context)))
             }
 
             QueryIntent::TaskGeneration => {
                 self.stats.grok_calls += 1;
                 let context = self.build_task_context(query, user_context).await?;
-                Ok(Action::CallGrok(context))
+                Ok(Action::CallGrok(Box::new(context)))
             }
 
             QueryIntent::CodeQuestion => {
@@ -255,7 +255,7 @@ impl QueryRouter {
             QueryIntent::Generic => {
                 self.stats.grok_calls += 1;
                 let context = self.build_generic_context(query, user_context).await?;
-                Ok(Action::CallGrok(context))
+                Ok(Action::CallGrok(Box::new(context)))
             }
         }
     }
@@ -9,6 +9,7 @@
 //! - Template customization
 //! - Cost-optimized patterns
 //! - Batch-friendly templates
+//! - User-defined templates loaded from `.toml` files
 //!
 //! ## Usage
 //!
@@ -28,10 +29,31 @@
 //!     Ok(())
 //! }
 //! ```
+//!
+//! ## User Templates
+//!
+//! Drop `.toml` files into `~/.config/rustassistant/templates/` (or any
+//! directory passed to [`TemplateRegistry::load_dir`]). Each file holds one
+//! template; user templates replace built-ins with the same name.
+//!
+//! ```toml
+//! name = "license_check"
+//! description = "Check a file for license headers"
+//! pattern = "Check {file} for a {license} license header."
+//! required_vars = ["file"]
+//! operation = "quick_analysis"
+//! estimated_tokens = 200
+//! cache_ttl = 168
+//!
+//! [optional_vars]
+//! license = "MIT"
+//! ```
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use tracing::warn;
 
 /// Query template with variable substitution
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -43,8 +65,10 @@ pub struct QueryTemplate {
     /// Template pattern with {variable} placeholders
     pub pattern: String,
     /// Required variables
+    #[serde(default)]
     pub required_vars: Vec<String>,
     /// Optional variables with defaults
+    #[serde(default)]
     pub optional_vars: HashMap<String, String>,
     /// Expected operation type
     pub operation: String,
@@ -82,9 +106,28 @@ impl TemplateCategory {
     }
 }
 
+/// Where a registered template came from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TemplateSource {
+    /// Shipped with rustassistant
+    Builtin,
+    /// Loaded from a user template file
+    User(PathBuf),
+}
+
+impl TemplateSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TemplateSource::Builtin => "builtin",
+            TemplateSource::User(_) => "user",
+        }
+    }
+}
+
 /// Registry of query templates
 pub struct TemplateRegistry {
     templates: HashMap<String, QueryTemplate>,
+    sources: HashMap<String, TemplateSource>,
 }
 
 impl Default for TemplateRegistry {
@@ -98,11 +141,63 @@ impl TemplateRegistry {
     pub fn new() -> Self {
         let mut registry = Self {
             templates: HashMap::new(),
+            sources: HashMap::new(),
         };
         registry.register_builtin_templates();
         registry
     }
 
+    /// Default directory for user templates (`~/.config/rustassistant/templates`)
+    pub fn default_user_dir() -> Option<PathBuf> {
+        dirs::config_dir().map(|d| d.join("rustassistant").join("templates"))
+    }
+
+    /// Load every `.toml` template in `dir`, merging over existing templates.
+    ///
+    /// User templates win on name collision (a warning is logged). Returns the
+    /// number of templates loaded. A missing directory loads nothing; a file
+    /// that fails to parse or validate aborts the load with its filename in
+    /// the error.
+    pub fn load_dir(&mut self, dir: impl AsRef<Path>) -> Result<usize> {
+        let dir = dir.as_ref();
+        if !dir.is_dir() {
+            return Ok(0);
+        }
+
+        let mut paths: Vec<PathBuf> = std::fs::read_dir(dir)
+            .with_context(|| format!("Failed to read template directory {}", dir.display()))?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|p| p.is_file() && p.extension().is_some_and(|ext| ext == "toml"))
+            .collect();
+        paths.sort();
+
+        let mut loaded = 0;
+        for path in paths {
+            let template = QueryTemplate::from_file(&path)?;
+
+            if let Some(existing) = self.sources.get(&template.name) {
+                warn!(
+                    "User template '{}' from {} overrides {} template",
+                    template.name,
+                    path.display(),
+                    existing.as_str()
+                );
+            }
+
+            self.sources
+                .insert(template.name.clone(), TemplateSource::User(path));
+            self.templates.insert(template.name.clone(), template);
+            loaded += 1;
+        }
+
+        Ok(loaded)
+    }
+
+    /// Get the source of a template by name
+    pub fn source(&self, name: &str) -> Option<&TemplateSource> {
+        self.sources.get(name)
+    }
+
     /// Register all built-in templates
     fn register_builtin_templates(&mut self) {
         // Security templates
@@ -386,6 +481,9 @@ impl TemplateRegistry {
 
     /// Add a template to the registry
     pub fn add_template(&mut self, template: QueryTemplate) {
+        self.sources
+            .entry(template.name.clone())
+            .or_insert(TemplateSource::Builtin);
         self.templates.insert(template.name.clone(), template);
     }
 
@@ -424,6 +522,69 @@ impl TemplateRegistry {
 }
 
 impl QueryTemplate {
+    /// Parse and validate a template from a `.toml` file
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read template {}", path.display()))?;
+        let template: QueryTemplate = toml::from_str(&content)
+            .with_context(|| format!("Failed to parse template {}", path.display()))?;
+        template
+            .validate()
+            .with_context(|| format!("Invalid template {}", path.display()))?;
+        Ok(template)
+    }
+
+    /// Placeholder names referenced in the pattern (`{name}`)
+    pub fn placeholders(&self) -> HashSet<String> {
+        let mut found = HashSet::new();
+        let mut rest = self.pattern.as_str();
+        while let Some(start) = rest.find('{') {
+            let after = &rest[start + 1..];
+            match after.find('}') {
+                Some(end) => {
+                    let name = &after[..end];
+                    if !name.is_empty() && name.chars().all(|c| c.is_alphanumeric() || c == '_') {
+                        found.insert(name.to_string());
+                    }
+                    rest = &after[end + 1..];
+                }
+                None => break,
+            }
+        }
+        found
+    }
+
+    /// Check that declared variables and pattern placeholders agree
+    pub fn validate(&self) -> Result<()> {
+        if self.name.trim().is_empty() {
+            anyhow::bail!("Template name must not be empty");
+        }
+
+        let placeholders = self.placeholders();
+
+        for required in &self.required_vars {
+            if !placeholders.contains(required) {
+                anyhow::bail!(
+                    "Required variable '{}' is not referenced in the pattern",
+                    required
+                );
+            }
+        }
+
+        for placeholder in &placeholders {
+            if !self.required_vars.contains(placeholder)
+                && !self.optional_vars.contains_key(placeholder)
+            {
+                anyhow::bail!(
+                    "Pattern placeholder '{{{}}}' is not declared in required_vars or optional_vars",
+                    placeholder
+                );
+            }
+        }
+
+        Ok(())
+    }
+
     /// Render the template with provided variables
     pub fn render(&self, vars: &[(&str, &str)]) -> Result<String> {
         let mut result = self.pattern.clone();
@@ -522,6 +683,79 @@ mod tests {
         assert!(template.is_ok());
     }
 
+    #[test]
+    fn test_builtin_templates_validate() {
+        let registry = TemplateRegistry::new();
+        for template in registry.list() {
+            template.validate().unwrap();
+            assert_eq!(
+                registry.source(&template.name),
+                Some(&TemplateSource::Builtin)
+            );
+        }
+    }
+
+    #[test]
+    fn test_load_dir_overrides_builtin() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("security_audit.toml"),
+            r#"
+name = "security_audit"
+description = "Custom audit"
+pattern = "Audit {file} against {standard}"
+required_vars = ["file"]
+operation = "security_audit"
+estimated_tokens = 300
+cache_ttl = 24
+
+[optional_vars]
+standard = "OWASP"
+"#,
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("notes.txt"), "ignored").unwrap();
+
+        let mut registry = TemplateRegistry::new();
+        let loaded = registry.load_dir(dir.path()).unwrap();
+        assert_eq!(loaded, 1);
+
+        let template = registry.get("security_audit").unwrap();
+        assert_eq!(template.description, "Custom audit");
+        assert_eq!(
+            template.render(&[("file", "auth.rs")]).unwrap(),
+            "Audit auth.rs against OWASP"
+        );
+        assert!(matches!(
+            registry.source("security_audit"),
+            Some(TemplateSource::User(_))
+        ));
+    }
+
+    #[test]
+    fn test_load_dir_reports_bad_file() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("broken.toml"),
+            r#"
+name = "broken"
+description = "Missing placeholder"
+pattern = "Analyze the code"
+required_vars = ["file"]
+operation = "quick_analysis"
+estimated_tokens = 100
+cache_ttl = 24
+"#,
+        )
+        .unwrap();
+
+        let mut registry = TemplateRegistry::new();
+        let err = registry.load_dir(dir.path()).unwrap_err();
+        let message = format!("{:#}", err);
+        assert!(message.contains("broken.toml"));
+        assert!(message.contains("'file'"));
+    }
+
     #[test]
     fn test_template_search() {
         let registry = TemplateRegistry::new();
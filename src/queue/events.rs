@@ -0,0 +1,284 @@
+//! Live queue events
+//!
+//! The queue operations in [`super::processor`] publish a [`QueueEvent`]
+//! whenever an item is enqueued, changes stage, completes or fails.
+//! Subscribers such as the `GET /queue/events` SSE endpoint receive them
+//! through a process-wide broadcast channel ([`QueueEventBus::global`]).
+//!
+//! # Delivery
+//!
+//! Delivery is at-least-once within a replay window. Every event carries a
+//! sequence number and the bus keeps the last [`REPLAY_CAPACITY`] events; a
+//! subscriber that reconnects with the last sequence it saw has everything
+//! after it replayed. Events can therefore arrive twice, so clients should
+//! apply them as idempotent state updates keyed by `item_id`. When a gap
+//! cannot be replayed — the subscriber was further behind than the buffer,
+//! or fell behind the live channel — it gets a [`QueueEventKind::Resync`]
+//! event instead and should re-read the queue.
+//!
+//! Only operations in this process are seen: items advanced by a processor
+//! running elsewhere (e.g. `rustassistant queue process`) produce no events
+//! here.
+
+use chrono::Utc;
+use futures::stream::{self, Stream, StreamExt};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use tokio::sync::broadcast;
+use tokio_stream::wrappers::BroadcastStream;
+
+use crate::db::queue::QueueStage;
+
+/// Events kept for replay to reconnecting subscribers
+pub const REPLAY_CAPACITY: usize = 256;
+
+static GLOBAL_BUS: Lazy<Arc<QueueEventBus>> =
+    Lazy::new(|| Arc::new(QueueEventBus::new(REPLAY_CAPACITY)));
+
+/// What happened to a queue item
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QueueEventKind {
+    /// A new item entered the inbox
+    Enqueued,
+    /// An item moved to another stage
+    StageChanged,
+    /// An item reached `ready`
+    Completed,
+    /// Processing an item failed; it is `failed` or `dead_letter` now
+    Failed,
+    /// Events were missed; re-read the queue
+    Resync,
+}
+
+impl QueueEventKind {
+    /// Event name, as used for the SSE `event:` field
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Enqueued => "enqueued",
+            Self::StageChanged => "stage_changed",
+            Self::Completed => "completed",
+            Self::Failed => "failed",
+            Self::Resync => "resync",
+        }
+    }
+}
+
+/// A change to a queue item
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueueEvent {
+    /// Position in the event sequence, starting at 1
+    pub seq: u64,
+    pub kind: QueueEventKind,
+    /// Affected item (absent for `resync`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub item_id: Option<String>,
+    /// Stage the item is in now
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stage: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Unix seconds
+    pub at: i64,
+}
+
+/// Broadcast channel of queue events with a replay buffer
+pub struct QueueEventBus {
+    sender: broadcast::Sender<QueueEvent>,
+    state: Mutex<BusState>,
+    capacity: usize,
+}
+
+struct BusState {
+    last_seq: u64,
+    recent: VecDeque<QueueEvent>,
+}
+
+impl QueueEventBus {
+    /// Create a bus keeping `capacity` events for replay
+    pub fn new(capacity: usize) -> Self {
+        let capacity = capacity.max(1);
+        let (sender, _) = broadcast::channel(capacity);
+        Self {
+            sender,
+            state: Mutex::new(BusState {
+                last_seq: 0,
+                recent: VecDeque::with_capacity(capacity),
+            }),
+            capacity,
+        }
+    }
+
+    /// The bus the queue operations publish to
+    pub fn global() -> Arc<QueueEventBus> {
+        Arc::clone(&GLOBAL_BUS)
+    }
+
+    /// Publish an event for `item_id`, returning it with its sequence number
+    pub fn publish(
+        &self,
+        kind: QueueEventKind,
+        item_id: &str,
+        stage: QueueStage,
+        error: Option<&str>,
+    ) -> QueueEvent {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        state.last_seq += 1;
+        let event = QueueEvent {
+            seq: state.last_seq,
+            kind,
+            item_id: Some(item_id.to_string()),
+            stage: Some(stage.as_str().to_string()),
+            error: error.map(String::from),
+            at: Utc::now().timestamp(),
+        };
+        if state.recent.len() == self.capacity {
+            state.recent.pop_front();
+        }
+        state.recent.push_back(event.clone());
+        // Sent under the lock so subscribers see events in sequence order;
+        // an error only means nobody is listening
+        let _ = self.sender.send(event.clone());
+        event
+    }
+
+    /// Subscribe, replaying events after `last_seen` when given. Returns
+    /// the replay (a lone `resync` if the gap is no longer buffered) and a
+    /// receiver for everything published afterwards.
+    pub fn subscribe(
+        &self,
+        last_seen: Option<u64>,
+    ) -> (Vec<QueueEvent>, broadcast::Receiver<QueueEvent>) {
+        let state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let receiver = self.sender.subscribe();
+
+        let replay = match last_seen {
+            None => Vec::new(),
+            Some(seen) if seen >= state.last_seq => Vec::new(),
+            Some(seen) => {
+                let oldest = state.recent.front().map_or(u64::MAX, |e| e.seq);
+                if seen + 1 < oldest {
+                    vec![resync_event(state.last_seq)]
+                } else {
+                    state
+                        .recent
+                        .iter()
+                        .filter(|e| e.seq > seen)
+                        .cloned()
+                        .collect()
+                }
+            }
+        };
+        (replay, receiver)
+    }
+
+    /// Replay plus live events as a stream. Falling behind the live channel
+    /// yields a `resync` event rather than ending the stream; the stream
+    /// ends only when dropped, e.g. when an SSE client disconnects.
+    pub fn stream(self: &Arc<Self>, last_seen: Option<u64>) -> impl Stream<Item = QueueEvent> {
+        let (replay, receiver) = self.subscribe(last_seen);
+        let bus = Arc::clone(self);
+        let live = BroadcastStream::new(receiver).map(move |received| match received {
+            Ok(event) => event,
+            Err(_lagged) => resync_event(bus.last_seq()),
+        });
+        stream::iter(replay).chain(live)
+    }
+
+    /// Sequence number of the latest event (0 before any)
+    pub fn last_seq(&self) -> u64 {
+        self.state
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .last_seq
+    }
+}
+
+fn resync_event(seq: u64) -> QueueEvent {
+    QueueEvent {
+        seq,
+        kind: QueueEventKind::Resync,
+        item_id: None,
+        stage: None,
+        error: None,
+        at: Utc::now().timestamp(),
+    }
+}
+
+/// Publish to the global bus
+pub(crate) fn publish(kind: QueueEventKind, item_id: &str, stage: QueueStage, error: Option<&str>) {
+    QueueEventBus::global().publish(kind, item_id, stage, error);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(events: &[QueueEvent]) -> Vec<(u64, QueueEventKind)> {
+        events.iter().map(|e| (e.seq, e.kind)).collect()
+    }
+
+    #[test]
+    fn test_reconnect_replays_missed_events() {
+        let bus = QueueEventBus::new(4);
+        bus.publish(QueueEventKind::Enqueued, "a", QueueStage::Inbox, None);
+        bus.publish(
+            QueueEventKind::StageChanged,
+            "a",
+            QueueStage::PendingAnalysis,
+            None,
+        );
+        bus.publish(QueueEventKind::Completed, "a", QueueStage::Ready, None);
+
+        let (replay, _) = bus.subscribe(Some(1));
+        assert_eq!(
+            kinds(&replay),
+            vec![
+                (2, QueueEventKind::StageChanged),
+                (3, QueueEventKind::Completed)
+            ]
+        );
+
+        // Up to date, or a fresh subscriber: nothing to replay
+        assert!(bus.subscribe(Some(3)).0.is_empty());
+        assert!(bus.subscribe(None).0.is_empty());
+    }
+
+    #[test]
+    fn test_gap_beyond_buffer_asks_for_resync() {
+        let bus = QueueEventBus::new(2);
+        for _ in 0..5 {
+            bus.publish(QueueEventKind::Enqueued, "a", QueueStage::Inbox, None);
+        }
+
+        let (replay, _) = bus.subscribe(Some(1));
+        assert_eq!(kinds(&replay), vec![(5, QueueEventKind::Resync)]);
+
+        // Seq 3 was the last one lost, so 4 and 5 can still be replayed
+        let (replay, _) = bus.subscribe(Some(3));
+        assert_eq!(replay.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_stream_delivers_live_events_in_order() {
+        let bus = Arc::new(QueueEventBus::new(8));
+        bus.publish(QueueEventKind::Enqueued, "a", QueueStage::Inbox, None);
+
+        let mut events = Box::pin(bus.stream(Some(0)));
+        bus.publish(
+            QueueEventKind::Failed,
+            "a",
+            QueueStage::Failed,
+            Some("boom"),
+        );
+
+        let first = events.next().await.unwrap();
+        assert_eq!((first.seq, first.kind), (1, QueueEventKind::Enqueued));
+        let second = events.next().await.unwrap();
+        assert_eq!((second.seq, second.kind), (2, QueueEventKind::Failed));
+        assert_eq!(second.error.as_deref(), Some("boom"));
+        assert_eq!(second.stage.as_deref(), Some("failed"));
+    }
+}
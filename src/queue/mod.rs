@@ -2,11 +2,15 @@
 //!
 //! Provides staged processing pipeline for content from raw input to tagged, searchable knowledge.
 
+pub mod events;
 pub mod processor;
 
 // Re-export main types
+pub use events::{QueueEvent, QueueEventBus, QueueEventKind};
 pub use processor::{
-    advance_stage, capture_note, capture_thought, capture_todo, enqueue, get_pending_items,
-    get_queue_item, get_queue_stats, get_retriable_items, mark_failed, update_analysis,
-    AnalysisResult, FileAnalysisResult, LlmAnalyzer, ProcessorConfig, QueueProcessor, QueueStats,
+    advance_stage, capture_note, capture_thought, capture_todo, delete_queue_item, enqueue,
+    get_dead_letter_items, get_pending_items, get_queue_item, get_queue_stats, get_retriable_items,
+    list_queue_items, mark_failed, purge_dead_letter, requeue_dead_letter, retry_queue_item,
+    update_analysis, AnalysisResult, FileAnalysisResult, LlmAnalyzer, ProcessorConfig,
+    QueueProcessor, QueueStats,
 };
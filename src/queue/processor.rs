@@ -13,6 +13,7 @@
 //! `capture_note`, and `capture_todo`. Consider migrating these to write
 //! to the `tasks` table as well, then retiring `queue_items` entirely.
 
+use super::events::{self, QueueEventKind};
 use crate::db::core::create_task;
 use crate::db::queue::{QueueItem, QueuePriority, QueueSource, QueueStage};
 use crate::tag_schema::{CodeStatus, TagCategory};
@@ -20,7 +21,10 @@ use anyhow::Result;
 use chrono::Utc;
 use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
+use std::future::Future;
+use std::sync::{Arc, RwLock};
 use std::time::Duration;
+use tokio::task::JoinSet;
 use tokio::time::sleep;
 use tracing::{error, info, warn};
 
@@ -80,6 +84,7 @@ pub async fn enqueue(
     .await?;
 
     info!("Enqueued item {} from {:?}", id, source);
+    events::publish(QueueEventKind::Enqueued, &id, QueueStage::Inbox, None);
     get_queue_item(pool, &id).await
 }
 
@@ -104,6 +109,7 @@ pub async fn advance_stage(pool: &PgPool, id: &str) -> Result<QueueStage> {
         QueueStage::PendingTagging => QueueStage::Ready,
         QueueStage::Ready => QueueStage::Ready, // Already done
         QueueStage::Failed => QueueStage::PendingAnalysis, // Retry
+        QueueStage::DeadLetter => QueueStage::DeadLetter, // Requeue explicitly
         QueueStage::Archived => QueueStage::Archived,
     };
 
@@ -115,7 +121,7 @@ pub async fn advance_stage(pool: &PgPool, id: &str) -> Result<QueueStage> {
     };
 
     sqlx::query("UPDATE queue_items SET stage = $1, updated_at = $2, processed_at = COALESCE($3, processed_at) WHERE id = $4")
-        .bind(format!("{:?}", next).to_lowercase())
+        .bind(next.as_str())
         .bind(now)
         .bind(processed_at)
         .bind(id)
@@ -123,24 +129,47 @@ pub async fn advance_stage(pool: &PgPool, id: &str) -> Result<QueueStage> {
         .await?;
 
     info!("Item {} moved from {:?} to {:?}", id, current, next);
+    if next != current {
+        let kind = if next == QueueStage::Ready {
+            QueueEventKind::Completed
+        } else {
+            QueueEventKind::StageChanged
+        };
+        events::publish(kind, id, next, None);
+    }
     Ok(next)
 }
 
-/// Mark item as failed
-pub async fn mark_failed(pool: &PgPool, id: &str, error: &str) -> Result<()> {
+/// Mark item as failed, returning the stage it ended up in.
+///
+/// Increments `retry_count`; once it reaches the item's `max_retries` the
+/// item moves to [`QueueStage::DeadLetter`] instead of `Failed`.
+pub async fn mark_failed(pool: &PgPool, id: &str, error: &str) -> Result<QueueStage> {
     let now = Utc::now().timestamp();
 
-    sqlx::query(
-        "UPDATE queue_items SET stage = 'failed', last_error = $1, retry_count = retry_count + 1, updated_at = $2 WHERE id = $3"
+    let (stage,): (String,) = sqlx::query_as(
+        r#"
+        UPDATE queue_items
+        SET stage = CASE WHEN retry_count + 1 >= max_retries THEN 'dead_letter' ELSE 'failed' END,
+            last_error = $1, retry_count = retry_count + 1, updated_at = $2
+        WHERE id = $3
+        RETURNING stage
+    "#,
     )
     .bind(error)
     .bind(now)
     .bind(id)
-    .execute(pool)
+    .fetch_one(pool)
     .await?;
 
-    error!("Item {} failed: {}", id, error);
-    Ok(())
+    let stage = parse_stage(&stage);
+    if stage == QueueStage::DeadLetter {
+        error!("Item {} moved to dead letter: {}", id, error);
+    } else {
+        error!("Item {} failed: {}", id, error);
+    }
+    events::publish(QueueEventKind::Failed, id, stage, Some(error));
+    Ok(stage)
 }
 
 /// Update item with analysis results
@@ -172,12 +201,10 @@ pub async fn get_pending_items(
     stage: QueueStage,
     limit: i32,
 ) -> Result<Vec<QueueItem>> {
-    let stage_str = format!("{:?}", stage).to_lowercase();
-
     sqlx::query_as::<_, QueueItem>(
         "SELECT * FROM queue_items WHERE stage = $1 ORDER BY priority ASC, created_at ASC LIMIT $2",
     )
-    .bind(&stage_str)
+    .bind(stage.as_str())
     .bind(limit)
     .fetch_all(pool)
     .await
@@ -185,9 +212,12 @@ pub async fn get_pending_items(
 }
 
 /// Get items that failed but can be retried
+///
+/// Dead-letter items are never returned; `max_retries` further caps the
+/// per-item limit stored on each row.
 pub async fn get_retriable_items(pool: &PgPool, max_retries: i32) -> Result<Vec<QueueItem>> {
     sqlx::query_as::<_, QueueItem>(
-        "SELECT * FROM queue_items WHERE stage = 'failed' AND retry_count < $1 ORDER BY priority ASC"
+        "SELECT * FROM queue_items WHERE stage = 'failed' AND retry_count < $1 AND retry_count < max_retries ORDER BY priority ASC"
     )
     .bind(max_retries)
     .fetch_all(pool)
@@ -195,6 +225,134 @@ pub async fn get_retriable_items(pool: &PgPool, max_retries: i32) -> Result<Vec<
     .map_err(Into::into)
 }
 
+/// Get items that exhausted their retries
+pub async fn get_dead_letter_items(pool: &PgPool, limit: i32) -> Result<Vec<QueueItem>> {
+    sqlx::query_as::<_, QueueItem>(
+        "SELECT * FROM queue_items WHERE stage = 'dead_letter' ORDER BY priority ASC, updated_at DESC LIMIT $1",
+    )
+    .bind(limit)
+    .fetch_all(pool)
+    .await
+    .map_err(Into::into)
+}
+
+/// Move a dead-letter item back to pending analysis with a fresh retry budget.
+///
+/// Returns `false` if the item does not exist or is not dead-lettered.
+pub async fn requeue_dead_letter(pool: &PgPool, id: &str) -> Result<bool> {
+    let now = Utc::now().timestamp();
+
+    let result = sqlx::query(
+        "UPDATE queue_items SET stage = 'pending_analysis', retry_count = 0, updated_at = $1 WHERE id = $2 AND stage = 'dead_letter'",
+    )
+    .bind(now)
+    .bind(id)
+    .execute(pool)
+    .await?;
+
+    if result.rows_affected() > 0 {
+        info!("Requeued dead-letter item {}", id);
+        events::publish(
+            QueueEventKind::StageChanged,
+            id,
+            QueueStage::PendingAnalysis,
+            None,
+        );
+    }
+    Ok(result.rows_affected() > 0)
+}
+
+/// Delete dead-letter items — a single item when `id` is given, otherwise all.
+///
+/// Returns the number of rows removed.
+pub async fn purge_dead_letter(pool: &PgPool, id: Option<&str>) -> Result<u64> {
+    let result = match id {
+        Some(id) => {
+            sqlx::query("DELETE FROM queue_items WHERE id = $1 AND stage = 'dead_letter'")
+                .bind(id)
+                .execute(pool)
+                .await?
+        }
+        None => {
+            sqlx::query("DELETE FROM queue_items WHERE stage = 'dead_letter'")
+                .execute(pool)
+                .await?
+        }
+    };
+
+    info!("Purged {} dead-letter item(s)", result.rows_affected());
+    Ok(result.rows_affected())
+}
+
+/// List queue items, optionally filtered by stage and/or priority
+pub async fn list_queue_items(
+    pool: &PgPool,
+    stage: Option<QueueStage>,
+    priority: Option<QueuePriority>,
+    limit: i32,
+) -> Result<Vec<QueueItem>> {
+    sqlx::query_as::<_, QueueItem>(
+        r#"
+        SELECT * FROM queue_items
+        WHERE ($1::TEXT IS NULL OR stage = $1)
+          AND ($2::INTEGER IS NULL OR priority = $2)
+        ORDER BY priority ASC, created_at ASC
+        LIMIT $3
+        "#,
+    )
+    .bind(stage.map(|s| s.as_str()))
+    .bind(priority.map(|p| p as i32))
+    .bind(limit)
+    .fetch_all(pool)
+    .await
+    .map_err(Into::into)
+}
+
+/// Delete a queue item regardless of stage
+///
+/// Returns `false` if no item with that id exists.
+pub async fn delete_queue_item(pool: &PgPool, id: &str) -> Result<bool> {
+    let result = sqlx::query("DELETE FROM queue_items WHERE id = $1")
+        .bind(id)
+        .execute(pool)
+        .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Send a failed or dead-letter item back to pending analysis.
+///
+/// Dead-letter items get a fresh retry budget; failed items keep their count.
+/// Returns `false` if the item does not exist or is in any other stage.
+pub async fn retry_queue_item(pool: &PgPool, id: &str) -> Result<bool> {
+    let now = Utc::now().timestamp();
+
+    let result = sqlx::query(
+        r#"
+        UPDATE queue_items
+        SET stage = 'pending_analysis',
+            retry_count = CASE WHEN stage = 'dead_letter' THEN 0 ELSE retry_count END,
+            updated_at = $1
+        WHERE id = $2 AND stage IN ('failed', 'dead_letter')
+        "#,
+    )
+    .bind(now)
+    .bind(id)
+    .execute(pool)
+    .await?;
+
+    if result.rows_affected() > 0 {
+        info!("Retrying queue item {}", id);
+        events::publish(
+            QueueEventKind::StageChanged,
+            id,
+            QueueStage::PendingAnalysis,
+            None,
+        );
+    }
+    Ok(result.rows_affected() > 0)
+}
+
 /// Get queue statistics
 pub async fn get_queue_stats(pool: &PgPool) -> Result<QueueStats> {
     let counts: Vec<(String, i64)> =
@@ -211,6 +369,7 @@ pub async fn get_queue_stats(pool: &PgPool) -> Result<QueueStats> {
             "pending_tagging" => stats.pending_tagging = count,
             "ready" => stats.ready = count,
             "failed" => stats.failed = count,
+            "dead_letter" => stats.dead_letter = count,
             "archived" => stats.archived = count,
             _ => {}
         }
@@ -249,7 +408,7 @@ pub struct AnalysisResult {
 }
 
 /// Queue statistics
-#[derive(Debug, Default, Serialize)]
+#[derive(Debug, Clone, Default, Serialize)]
 pub struct QueueStats {
     pub inbox: i64,
     pub pending_analysis: i64,
@@ -257,6 +416,7 @@ pub struct QueueStats {
     pub pending_tagging: i64,
     pub ready: i64,
     pub failed: i64,
+    pub dead_letter: i64,
     pub archived: i64,
 }
 
@@ -300,7 +460,8 @@ impl Default for ProcessorConfig {
 pub struct QueueProcessor {
     pool: PgPool,
     config: ProcessorConfig,
-    llm_client: Box<dyn LlmAnalyzer + Send + Sync>,
+    llm_client: Arc<dyn LlmAnalyzer + Send + Sync>,
+    live_stats: Arc<RwLock<QueueStats>>,
 }
 
 /// Trait for LLM analysis (implement with your Grok client)
@@ -341,8 +502,99 @@ impl QueueProcessor {
         Self {
             pool,
             config,
-            llm_client,
+            llm_client: Arc::from(llm_client),
+            live_stats: Arc::new(RwLock::new(QueueStats::default())),
+        }
+    }
+
+    /// Snapshot of the queue statistics maintained by [`Self::run_concurrent`]
+    pub fn live_stats(&self) -> QueueStats {
+        self.live_stats
+            .read()
+            .map(|s| s.clone())
+            .unwrap_or_default()
+    }
+
+    /// Drain pending items with at most `max_in_flight` analyses running at once.
+    ///
+    /// Items are started in `QueuePriority` order (Critical first). Successful
+    /// analyses are stored via `update_analysis`, failures via `mark_failed`,
+    /// and retriable failures are fed back in until `max_retries` is reached.
+    /// Returns once the inbox and pending-analysis stages are empty.
+    pub async fn run_concurrent(&self, max_in_flight: usize) -> Result<QueueStats> {
+        let max_in_flight = max_in_flight.max(1);
+        info!(max_in_flight, "Concurrent queue drain started");
+
+        self.refresh_live_stats().await?;
+
+        loop {
+            self.process_inbox().await?;
+
+            let limit = self.config.batch_size.max(max_in_flight as i32);
+            let mut items =
+                get_pending_items(&self.pool, QueueStage::PendingAnalysis, limit).await?;
+
+            if items.is_empty() {
+                self.retry_failed().await?;
+                items = get_pending_items(&self.pool, QueueStage::PendingAnalysis, limit).await?;
+                if items.is_empty() {
+                    break;
+                }
+            }
+
+            for item in &items {
+                advance_stage(&self.pool, &item.id).await?;
+            }
+            self.refresh_live_stats().await?;
+
+            let pool = &self.pool;
+            let live_stats = &self.live_stats;
+            analyze_bounded(
+                Arc::clone(&self.llm_client),
+                items,
+                max_in_flight,
+                |item, outcome| async move {
+                    match outcome {
+                        Ok(analysis) => {
+                            update_analysis(pool, &item.id, &analysis).await?;
+                            info!(
+                                "Analyzed item {}: category={}, score={}",
+                                item.id, analysis.category, analysis.score
+                            );
+                            if let Ok(mut stats) = live_stats.write() {
+                                stats.analyzing -= 1;
+                                stats.pending_tagging += 1;
+                            }
+                        }
+                        Err(e) => {
+                            let stage = mark_failed(pool, &item.id, &e.to_string()).await?;
+                            if let Ok(mut stats) = live_stats.write() {
+                                stats.analyzing -= 1;
+                                if stage == QueueStage::DeadLetter {
+                                    stats.dead_letter += 1;
+                                } else {
+                                    stats.failed += 1;
+                                }
+                            }
+                        }
+                    }
+                    Ok(())
+                },
+            )
+            .await?;
         }
+
+        self.refresh_live_stats().await?;
+        info!("Concurrent queue drain finished — queue is empty");
+        Ok(self.live_stats())
+    }
+
+    async fn refresh_live_stats(&self) -> Result<()> {
+        let stats = get_queue_stats(&self.pool).await?;
+        if let Ok(mut live) = self.live_stats.write() {
+            *live = stats;
+        }
+        Ok(())
     }
 
     /// Run the processor loop
@@ -561,6 +813,44 @@ impl QueueProcessor {
 // Helper Functions
 // ============================================================================
 
+/// Run `analyze_content` over `items` with at most `max_in_flight` calls
+/// outstanding, invoking `on_complete` for each result as it finishes.
+///
+/// Items are started in priority order (lowest `priority` value first).
+async fn analyze_bounded<F, Fut>(
+    analyzer: Arc<dyn LlmAnalyzer + Send + Sync>,
+    mut items: Vec<QueueItem>,
+    max_in_flight: usize,
+    mut on_complete: F,
+) -> Result<()>
+where
+    F: FnMut(QueueItem, Result<AnalysisResult>) -> Fut,
+    Fut: Future<Output = Result<()>>,
+{
+    items.sort_by_key(|item| item.priority);
+    let mut pending = items.into_iter();
+    let mut in_flight = JoinSet::new();
+
+    loop {
+        while in_flight.len() < max_in_flight.max(1) {
+            let Some(item) = pending.next() else { break };
+            let analyzer = Arc::clone(&analyzer);
+            in_flight.spawn(async move {
+                let outcome = analyzer.analyze_content(&item.content, &item.source).await;
+                (item, outcome)
+            });
+        }
+
+        match in_flight.join_next().await {
+            Some(Ok((item, outcome))) => on_complete(item, outcome).await?,
+            Some(Err(e)) => error!("Queue analysis task panicked: {}", e),
+            None => break,
+        }
+    }
+
+    Ok(())
+}
+
 // ============================================================================
 // Tag Refinement Helpers
 // ============================================================================
@@ -685,11 +975,13 @@ fn build_task_title(item: &QueueItem, tags: &[String]) -> String {
 fn parse_stage(s: &str) -> QueueStage {
     match s {
         "inbox" => QueueStage::Inbox,
-        "pending_analysis" => QueueStage::PendingAnalysis,
+        // Older rows were written with the Debug-derived spelling
+        "pending_analysis" | "pendinganalysis" => QueueStage::PendingAnalysis,
         "analyzing" => QueueStage::Analyzing,
-        "pending_tagging" => QueueStage::PendingTagging,
+        "pending_tagging" | "pendingtagging" => QueueStage::PendingTagging,
         "ready" => QueueStage::Ready,
         "failed" => QueueStage::Failed,
+        "dead_letter" => QueueStage::DeadLetter,
         "archived" => QueueStage::Archived,
         _ => QueueStage::Inbox,
     }
@@ -757,3 +1049,167 @@ pub async fn capture_todo(
     )
     .await
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Mutex;
+
+    struct MockAnalyzer {
+        in_flight: AtomicUsize,
+        max_seen: AtomicUsize,
+        started: Mutex<Vec<String>>,
+    }
+
+    impl MockAnalyzer {
+        fn new() -> Self {
+            Self {
+                in_flight: AtomicUsize::new(0),
+                max_seen: AtomicUsize::new(0),
+                started: Mutex::new(Vec::new()),
+            }
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl LlmAnalyzer for MockAnalyzer {
+        async fn analyze_content(&self, content: &str, _source: &str) -> Result<AnalysisResult> {
+            self.started.lock().unwrap().push(content.to_string());
+            let now = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            self.max_seen.fetch_max(now, Ordering::SeqCst);
+            sleep(Duration::from_millis(20)).await;
+            self.in_flight.fetch_sub(1, Ordering::SeqCst);
+
+            if content.contains("fail") {
+                anyhow::bail!("mock failure");
+            }
+            Ok(AnalysisResult {
+                summary: content.to_string(),
+                tags: vec![],
+                category: "idea".to_string(),
+                score: 5,
+                action_items: vec![],
+                related_topics: vec![],
+                suggested_project: None,
+            })
+        }
+
+        async fn analyze_file(
+            &self,
+            _content: &str,
+            _file_path: &str,
+            _language: &str,
+        ) -> Result<FileAnalysisResult> {
+            anyhow::bail!("not used")
+        }
+    }
+
+    fn item(id: &str, priority: QueuePriority) -> QueueItem {
+        QueueItem {
+            id: id.to_string(),
+            content: id.to_string(),
+            stage: "analyzing".to_string(),
+            source: "note".to_string(),
+            priority: priority as i32,
+            repo_id: None,
+            file_path: None,
+            line_number: None,
+            analysis: None,
+            tags: None,
+            category: None,
+            score: None,
+            retry_count: 0,
+            max_retries: 3,
+            last_error: None,
+            content_hash: id.to_string(),
+            created_at: 0,
+            updated_at: 0,
+            processed_at: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_analyze_bounded_respects_cap() {
+        let analyzer = Arc::new(MockAnalyzer::new());
+        let items: Vec<QueueItem> = (0..10)
+            .map(|i| {
+                let id = if i % 4 == 0 {
+                    format!("fail-{}", i)
+                } else {
+                    format!("item-{}", i)
+                };
+                item(&id, QueuePriority::Normal)
+            })
+            .collect();
+
+        let ok = Arc::new(AtomicUsize::new(0));
+        let failed = Arc::new(AtomicUsize::new(0));
+        analyze_bounded(analyzer.clone(), items, 3, |_item, outcome| {
+            let ok = ok.clone();
+            let failed = failed.clone();
+            async move {
+                match outcome {
+                    Ok(_) => ok.fetch_add(1, Ordering::SeqCst),
+                    Err(_) => failed.fetch_add(1, Ordering::SeqCst),
+                };
+                Ok(())
+            }
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(analyzer.max_seen.load(Ordering::SeqCst), 3);
+        assert_eq!(ok.load(Ordering::SeqCst), 7);
+        assert_eq!(failed.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_analyze_bounded_starts_critical_first() {
+        let analyzer = Arc::new(MockAnalyzer::new());
+        let items = vec![
+            item("low", QueuePriority::Low),
+            item("normal", QueuePriority::Normal),
+            item("critical", QueuePriority::Critical),
+            item("high", QueuePriority::High),
+        ];
+
+        analyze_bounded(analyzer.clone(), items, 1, |_, _| async { Ok(()) })
+            .await
+            .unwrap();
+
+        let started = analyzer.started.lock().unwrap().clone();
+        assert_eq!(started, vec!["critical", "high", "normal", "low"]);
+        assert_eq!(analyzer.max_seen.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_parse_stage_round_trips() {
+        for stage in [
+            QueueStage::Inbox,
+            QueueStage::PendingAnalysis,
+            QueueStage::Analyzing,
+            QueueStage::PendingTagging,
+            QueueStage::Ready,
+            QueueStage::Failed,
+            QueueStage::DeadLetter,
+            QueueStage::Archived,
+        ] {
+            assert_eq!(parse_stage(stage.as_str()), stage);
+            assert_eq!(stage.as_str().parse::<QueueStage>(), Ok(stage));
+        }
+        assert_eq!(parse_stage("pendinganalysis"), QueueStage::PendingAnalysis);
+        assert!("bogus".parse::<QueueStage>().is_err());
+    }
+
+    #[test]
+    fn test_parse_priority_by_name_or_level() {
+        assert_eq!("high".parse::<QueuePriority>(), Ok(QueuePriority::High));
+        assert_eq!("2".parse::<QueuePriority>(), Ok(QueuePriority::High));
+        assert_eq!(
+            "Critical".parse::<QueuePriority>(),
+            Ok(QueuePriority::Critical)
+        );
+        assert!("urgent".parse::<QueuePriority>().is_err());
+    }
+}
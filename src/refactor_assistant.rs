@@ -32,12 +32,17 @@
 use crate::db::Database;
 use crate::grok_client::GrokClient;
 use anyhow::{Context, Result};
+use proc_macro2::{Delimiter, Spacing, Span, TokenStream, TokenTree};
 use serde::{Deserialize, Serialize};
 use std::path::Path;
+use syn::spanned::Spanned;
+use syn::visit::{self, Visit};
+use syn::Token;
 
 /// Refactoring assistant with AI-powered analysis
 pub struct RefactorAssistant {
     grok_client: GrokClient,
+    db: Database,
 }
 
 /// Complete refactoring analysis for a file or directory
@@ -222,6 +227,53 @@ pub enum EffortEstimate {
     VeryLarge,
 }
 
+/// How far off past estimates were for one code smell type
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CalibrationFactor {
+    /// Smell type the history is for
+    pub smell_type: CodeSmellType,
+    /// Number of completed refactorings recorded
+    pub samples: usize,
+    /// Total actual hours divided by total estimated hours
+    pub observed_ratio: f64,
+    /// Multiplier applied to new estimates (shrunk towards 1.0 and capped)
+    pub factor: f64,
+}
+
+/// Samples needed before the observed ratio counts as much as the heuristic.
+const CALIBRATION_PRIOR_SAMPLES: f64 = 3.0;
+/// Calibration never scales an estimate by more than this (or less than its inverse).
+const MAX_CALIBRATION_FACTOR: f64 = 3.0;
+
+impl CalibrationFactor {
+    /// Build a factor from `(estimated_hours, actual_hours)` pairs.
+    ///
+    /// The observed ratio is shrunk towards 1.0 in log space with a weight of
+    /// `n / (n + 3)`, so a single overrun moves estimates a quarter of the
+    /// way, and the result is clamped to `[1/3, 3]`.
+    pub fn from_history(smell_type: CodeSmellType, history: &[(f64, f64)]) -> Option<Self> {
+        let estimated: f64 = history.iter().map(|(e, _)| e).sum();
+        let actual: f64 = history.iter().map(|(_, a)| a).sum();
+        if history.is_empty() || estimated <= 0.0 || actual <= 0.0 {
+            return None;
+        }
+
+        let samples = history.len();
+        let observed_ratio = actual / estimated;
+        let weight = samples as f64 / (samples as f64 + CALIBRATION_PRIOR_SAMPLES);
+        let factor = (observed_ratio.ln() * weight)
+            .exp()
+            .clamp(1.0 / MAX_CALIBRATION_FACTOR, MAX_CALIBRATION_FACTOR);
+
+        Some(Self {
+            smell_type,
+            samples,
+            observed_ratio,
+            factor,
+        })
+    }
+}
+
 /// Refactoring example (before/after)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RefactoringExample {
@@ -280,11 +332,27 @@ pub struct Risk {
     pub severity: SmellSeverity,
 }
 
+/// A set of near-identical code blocks found in more than one place
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DuplicateGroup {
+    /// Where each copy lives (`item_name` is the enclosing function)
+    pub locations: Vec<CodeLocation>,
+    /// Length in lines of the longest copy
+    pub lines: usize,
+    /// Source of the first copy
+    pub snippet: String,
+    /// File the shared helper should go in
+    pub suggested_target: String,
+    /// Name for the extracted helper (only set when asked of the LLM)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suggested_name: Option<String>,
+}
+
 impl RefactorAssistant {
     /// Create a new refactoring assistant
     pub async fn new(db: Database) -> Result<Self> {
-        let grok_client = GrokClient::from_env(db).await?;
-        Ok(Self { grok_client })
+        let grok_client = GrokClient::from_env(db.clone()).await?;
+        Ok(Self { grok_client, db })
     }
 
     /// Analyze a file for refactoring opportunities
@@ -293,10 +361,29 @@ impl RefactorAssistant {
         let content = std::fs::read_to_string(file_path)
             .with_context(|| format!("Failed to read file: {}", file_path.display()))?;
 
-        self.analyze_content(file_path.to_string_lossy().to_string(), &content)
+        self.analyze_content(file_path.to_string_lossy().to_string(), &content, None)
             .await
     }
 
+    /// Analyze a file with `related` code (e.g. the modules it imports)
+    /// sent along as context; smells are only reported for the file itself
+    pub async fn analyze_file_with_context(
+        &self,
+        file_path: impl AsRef<Path>,
+        related: &str,
+    ) -> Result<RefactoringAnalysis> {
+        let file_path = file_path.as_ref();
+        let content = std::fs::read_to_string(file_path)
+            .with_context(|| format!("Failed to read file: {}", file_path.display()))?;
+
+        self.analyze_content(
+            file_path.to_string_lossy().to_string(),
+            &content,
+            Some(related),
+        )
+        .await
+    }
+
     /// Analyze directory for refactoring opportunities
     pub async fn analyze_directory(
         &self,
@@ -327,8 +414,9 @@ impl RefactorAssistant {
         &self,
         file_path: String,
         content: &str,
+        related: Option<&str>,
     ) -> Result<RefactoringAnalysis> {
-        let prompt = format!(
+        let mut prompt = format!(
             r#"Analyze this code for refactoring opportunities. Return ONLY valid JSON with this structure:
 {{
   "code_smells": [
@@ -393,9 +481,21 @@ For each smell, provide:
             content
         );
 
+        // Related code goes in the context; only the analyzed code is in scope
+        let context = match related {
+            Some(related) => {
+                prompt.push_str(
+                    "\n\nThe context holds the modules this code imports, for reference only. \
+                     Report smells in the code to analyze, not in the context.",
+                );
+                related
+            }
+            None => content,
+        };
+
         let tracked = self
             .grok_client
-            .ask_tracked(&prompt, Some(content), "refactor_analysis")
+            .ask_tracked(&prompt, Some(context), "refactor_analysis")
             .await
             .context("Failed to analyze code for refactoring")?;
 
@@ -536,6 +636,176 @@ Suggest:
         self.parse_suggestion_response(&response)
     }
 
+    /// Extract lines `start_line..=end_line` (1-based) of a Rust file into a
+    /// new function called `new_name`, without calling the LLM.
+    ///
+    /// Variables declared before the range and read inside it become
+    /// parameters (by value, `&` or `&mut` depending on how they're used);
+    /// `let` bindings still needed after the range become the return value.
+    /// Selections that can't be hoisted safely — an early `return`, a
+    /// `break` out of an outer loop, a result that borrows a parameter —
+    /// are rejected with an error saying why.
+    pub fn extract_function(
+        file_path: impl AsRef<Path>,
+        start_line: usize,
+        end_line: usize,
+        new_name: &str,
+    ) -> Result<RefactoringExample> {
+        let file_path = file_path.as_ref();
+        if file_path.extension().and_then(|e| e.to_str()) != Some("rs") {
+            anyhow::bail!(
+                "extract function only supports Rust files: {}",
+                file_path.display()
+            );
+        }
+        let content = std::fs::read_to_string(file_path)
+            .with_context(|| format!("Failed to read file: {}", file_path.display()))?;
+
+        extract_rust_function(&content, start_line, end_line, new_name)
+    }
+
+    /// Find copy-pasted blocks of at least `min_lines` lines of code across
+    /// the Rust files under `path`, without calling the LLM.
+    ///
+    /// Each file is parsed with syn and its function bodies are split into
+    /// statements of the token stream; every window of `min_lines`
+    /// statements is hashed with identifiers and literals normalized away,
+    /// so copies that only differ in naming, values, comments or line
+    /// wrapping still match. Files that don't parse are skipped.
+    /// Overlapping windows shared by the same locations are merged into one
+    /// maximal block. Groups are ordered by duplicated lines, largest first.
+    pub fn find_duplicates(path: impl AsRef<Path>, min_lines: usize) -> Vec<DuplicateGroup> {
+        let path = path.as_ref();
+        let files: Vec<(String, String)> = walkdir::WalkDir::new(path)
+            .follow_links(true)
+            .into_iter()
+            .filter_entry(|e| {
+                let name = e.file_name().to_string_lossy();
+                e.depth() == 0 || !(name.starts_with('.') || name == "target")
+            })
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file())
+            .filter(|e| e.path().extension().is_some_and(|ext| ext == "rs"))
+            .filter_map(|e| {
+                let content = std::fs::read_to_string(e.path()).ok()?;
+                Some((e.path().to_string_lossy().to_string(), content))
+            })
+            .collect();
+
+        find_duplicate_blocks(&files, min_lines.max(2))
+    }
+
+    /// Ask the LLM for a name for the helper a duplicate group should
+    /// be extracted into.
+    pub async fn suggest_duplicate_name(&self, group: &DuplicateGroup) -> Result<String> {
+        let prompt = format!(
+            r#"This block of code is duplicated in {} places. Suggest a name for a helper
+function that the copies could be replaced with. Reply with ONLY the function
+name in snake_case.
+
+```
+{}
+```"#,
+            group.locations.len(),
+            group.snippet
+        );
+
+        let response = self
+            .grok_client
+            .ask(&prompt, None)
+            .await
+            .context("Failed to name duplicated block")?;
+
+        let name: String = response
+            .trim()
+            .trim_matches('`')
+            .chars()
+            .take_while(|c| c.is_alphanumeric() || *c == '_')
+            .collect();
+        if name.is_empty() {
+            anyhow::bail!("LLM did not return a function name: {}", response.trim());
+        }
+        Ok(name)
+    }
+
+    /// Record a finished refactoring so future estimates can be calibrated.
+    pub async fn record_completed_refactoring(
+        &self,
+        smell_type: CodeSmellType,
+        estimated: EffortEstimate,
+        actual_hours: f64,
+        file_path: Option<&str>,
+    ) -> Result<()> {
+        if !(actual_hours.is_finite() && actual_hours > 0.0) {
+            anyhow::bail!("actual effort must be a positive number of hours");
+        }
+        sqlx::query(
+            r#"
+            INSERT INTO refactoring_effort_history
+                (smell_type, estimated, estimated_hours, actual_hours, file_path, completed_at)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            "#,
+        )
+        .bind(format!("{:?}", smell_type))
+        .bind(format!("{:?}", estimated))
+        .bind(estimated.hours())
+        .bind(actual_hours)
+        .bind(file_path)
+        .bind(chrono::Utc::now().timestamp())
+        .execute(self.db.pool())
+        .await
+        .context("Failed to record refactoring effort")?;
+        Ok(())
+    }
+
+    /// Calibration factors for every smell type with recorded history.
+    pub async fn calibration_factors(&self) -> Result<Vec<CalibrationFactor>> {
+        let rows: Vec<(String, f64, f64)> = sqlx::query_as(
+            "SELECT smell_type, estimated_hours, actual_hours FROM refactoring_effort_history",
+        )
+        .fetch_all(self.db.pool())
+        .await
+        .context("Failed to load refactoring effort history")?;
+
+        let mut by_type: std::collections::BTreeMap<String, Vec<(f64, f64)>> =
+            std::collections::BTreeMap::new();
+        for (smell_type, estimated, actual) in rows {
+            by_type
+                .entry(smell_type)
+                .or_default()
+                .push((estimated, actual));
+        }
+
+        Ok(by_type
+            .into_iter()
+            .filter_map(|(smell_type, history)| {
+                CalibrationFactor::from_history(self.parse_smell_type(&smell_type), &history)
+            })
+            .collect())
+    }
+
+    /// Effort estimate for fixing `smell`, scaled by how long past fixes of
+    /// the same smell type actually took. Without history this is the
+    /// plain [`EffortEstimate::for_smell`] heuristic.
+    pub async fn calibrated_estimate(&self, smell: &CodeSmell) -> Result<EffortEstimate> {
+        let base = EffortEstimate::for_smell(smell);
+
+        let history: Vec<(f64, f64)> = sqlx::query_as(
+            "SELECT estimated_hours, actual_hours FROM refactoring_effort_history WHERE smell_type = $1",
+        )
+        .bind(format!("{:?}", smell.smell_type))
+        .fetch_all(self.db.pool())
+        .await
+        .context("Failed to load refactoring effort history")?;
+
+        Ok(
+            match CalibrationFactor::from_history(smell.smell_type, &history) {
+                Some(calibration) => EffortEstimate::from_hours(base.hours() * calibration.factor),
+                None => base,
+            },
+        )
+    }
+
     /// Parse refactoring analysis response
     fn parse_refactoring_response(
         &self,
@@ -1005,13 +1275,7 @@ impl RefactoringAnalysis {
 
                 if let Some(example) = &suggestion.example {
                     output.push_str("**Example:**\n\n");
-                    output.push_str("Before:\n```rust\n");
-                    output.push_str(&example.before);
-                    output.push_str("\n```\n\n");
-                    output.push_str("After:\n```rust\n");
-                    output.push_str(&example.after);
-                    output.push_str("\n```\n\n");
-                    output.push_str(&format!("*{}*\n\n", example.explanation));
+                    output.push_str(&example.format_markdown());
                 }
             }
         }
@@ -1144,3 +1408,2035 @@ impl std::fmt::Display for RefactoringType {
         }
     }
 }
+
+impl EffortEstimate {
+    /// Representative number of hours for this bucket
+    pub fn hours(&self) -> f64 {
+        match self {
+            EffortEstimate::Trivial => 0.25,
+            EffortEstimate::Small => 1.0,
+            EffortEstimate::Medium => 4.0,
+            EffortEstimate::Large => 16.0,
+            EffortEstimate::VeryLarge => 40.0,
+        }
+    }
+
+    /// Bucket a number of hours (8-hour days)
+    pub fn from_hours(hours: f64) -> Self {
+        if hours < 0.5 {
+            EffortEstimate::Trivial
+        } else if hours <= 2.0 {
+            EffortEstimate::Small
+        } else if hours <= 8.0 {
+            EffortEstimate::Medium
+        } else if hours <= 24.0 {
+            EffortEstimate::Large
+        } else {
+            EffortEstimate::VeryLarge
+        }
+    }
+
+    /// Heuristic effort for fixing a smell: by smell type, one step larger
+    /// for critical findings and one step smaller for low-severity ones
+    pub fn for_smell(smell: &CodeSmell) -> Self {
+        const STEPS: [EffortEstimate; 5] = [
+            EffortEstimate::Trivial,
+            EffortEstimate::Small,
+            EffortEstimate::Medium,
+            EffortEstimate::Large,
+            EffortEstimate::VeryLarge,
+        ];
+        let base: usize = match smell.smell_type {
+            CodeSmellType::MagicNumbers | CodeSmellType::DeadCode => 0,
+            CodeSmellType::UnsafeUnwrapping
+            | CodeSmellType::LongParameterList
+            | CodeSmellType::ComplexConditional
+            | CodeSmellType::DeepNesting
+            | CodeSmellType::DuplicatedCode
+            | CodeSmellType::LongFunction
+            | CodeSmellType::PrimitiveObsession => 1,
+            CodeSmellType::MissingErrorHandling
+            | CodeSmellType::FeatureEnvy
+            | CodeSmellType::LargeModule => 2,
+            CodeSmellType::ShotgunSurgery
+            | CodeSmellType::DivergentChange
+            | CodeSmellType::TightCoupling
+            | CodeSmellType::GodObject => 3,
+        };
+        let step = match smell.severity {
+            SmellSeverity::Critical => base + 1,
+            SmellSeverity::Low => base.saturating_sub(1),
+            SmellSeverity::High | SmellSeverity::Medium => base,
+        };
+        STEPS[step.min(STEPS.len() - 1)]
+    }
+}
+
+impl RefactoringExample {
+    /// Format example as markdown
+    pub fn format_markdown(&self) -> String {
+        let mut output = String::new();
+        output.push_str("Before:\n```rust\n");
+        output.push_str(&self.before);
+        output.push_str("\n```\n\n");
+        output.push_str("After:\n```rust\n");
+        output.push_str(&self.after);
+        output.push_str("\n```\n\n");
+        output.push_str(&format!("*{}*\n\n", self.explanation));
+        output
+    }
+}
+
+// ── Rust source tokens ───────────────────────────────────────────────────────
+//
+// The deterministic (non-LLM) refactorings below parse the file with syn for
+// its items and reason about statements over the proc_macro2 token stream,
+// which has already dropped comments and whitespace. With the
+// `span-locations` feature every token knows its line and byte range.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TokKind {
+    Ident,
+    Keyword,
+    Lifetime,
+    Literal,
+    Punct,
+}
+
+#[derive(Debug, Clone)]
+struct Tok {
+    kind: TokKind,
+    text: String,
+    /// 1-based line of the first character
+    line: usize,
+    /// Byte range in the source
+    start: usize,
+    end: usize,
+    /// For an opening bracket, the index of the one closing it
+    close: Option<usize>,
+}
+
+impl Tok {
+    fn is(&self, text: &str) -> bool {
+        self.text == text
+    }
+}
+
+/// Parse `source` as a Rust file: the syntax tree plus its flattened tokens.
+fn parse_rust(source: &str) -> syn::Result<(syn::File, Vec<Tok>)> {
+    let file = syn::parse_file(source)?;
+    let stream: TokenStream = source.parse()?;
+    Ok((file, flatten_tokens(source, stream)))
+}
+
+/// Flatten a token stream lexed from `source`. Delimited groups become an
+/// opening and a closing token, a lifetime's quote is joined to its name,
+/// and joint punctuation is glued into the operator it spells.
+fn flatten_tokens(source: &str, stream: TokenStream) -> Vec<Tok> {
+    fn push(source: &str, tokens: &mut Vec<Tok>, kind: TokKind, from: Span, to: Span) {
+        let (start, end) = (from.byte_range().start, to.byte_range().end);
+        tokens.push(Tok {
+            kind,
+            text: source.get(start..end).unwrap_or_default().to_string(),
+            line: from.start().line,
+            start,
+            end,
+            close: None,
+        });
+    }
+
+    fn walk(source: &str, stream: TokenStream, tokens: &mut Vec<Tok>) {
+        let trees: Vec<TokenTree> = stream.into_iter().collect();
+        let mut i = 0;
+        while i < trees.len() {
+            let mut len = 1;
+            match &trees[i] {
+                TokenTree::Group(group) if group.delimiter() == Delimiter::None => {
+                    walk(source, group.stream(), tokens)
+                }
+                TokenTree::Group(group) => {
+                    let open = tokens.len();
+                    let (span_open, span_close) = (group.span_open(), group.span_close());
+                    push(source, tokens, TokKind::Punct, span_open, span_open);
+                    walk(source, group.stream(), tokens);
+                    tokens[open].close = Some(tokens.len());
+                    push(source, tokens, TokKind::Punct, span_close, span_close);
+                }
+                TokenTree::Ident(ident) => {
+                    // syn only accepts an identifier that isn't a keyword.
+                    let single: TokenStream = trees[i].clone().into();
+                    let kind = if syn::parse2::<syn::Ident>(single).is_ok() {
+                        TokKind::Ident
+                    } else {
+                        TokKind::Keyword
+                    };
+                    push(source, tokens, kind, ident.span(), ident.span());
+                }
+                TokenTree::Literal(lit) => {
+                    push(source, tokens, TokKind::Literal, lit.span(), lit.span())
+                }
+                TokenTree::Punct(punct) => match trees.get(i + 1) {
+                    Some(TokenTree::Ident(name)) if punct.as_char() == '\'' => {
+                        push(source, tokens, TokKind::Lifetime, punct.span(), name.span());
+                        len = 2;
+                    }
+                    _ => {
+                        len = (2..=3)
+                            .rev()
+                            .find(|&n| is_operator(&trees[i..], n))
+                            .unwrap_or(1);
+                        let last = trees[i + len - 1].span();
+                        push(source, tokens, TokKind::Punct, punct.span(), last);
+                    }
+                },
+            }
+            i += len;
+        }
+    }
+
+    let mut tokens = Vec::new();
+    walk(source, stream, &mut tokens);
+    tokens
+}
+
+/// Whether the first `len` trees are joint punctuation spelling a single
+/// operator such as `::`, `=>` or `<<=`.
+fn is_operator(trees: &[TokenTree], len: usize) -> bool {
+    let Some(run) = trees.get(..len) else {
+        return false;
+    };
+    let joint = run.iter().enumerate().all(|(k, tree)| {
+        matches!(tree, TokenTree::Punct(p) if k + 1 == len || p.spacing() == Spacing::Joint)
+    });
+    if !joint {
+        return false;
+    }
+    let run: TokenStream = run.iter().cloned().collect();
+    syn::parse2::<syn::BinOp>(run.clone()).is_ok()
+        || syn::parse2::<syn::RangeLimits>(run.clone()).is_ok()
+        || syn::parse2::<Token![::]>(run.clone()).is_ok()
+        || syn::parse2::<Token![=>]>(run.clone()).is_ok()
+        || syn::parse2::<Token![->]>(run).is_ok()
+}
+
+/// Index range `[from, to)` of the tokens covered by `node`.
+fn token_range(tokens: &[Tok], node: &impl Spanned) -> (usize, usize) {
+    let bytes = node.span().byte_range();
+    (
+        tokens.partition_point(|t| t.start < bytes.start),
+        tokens.partition_point(|t| t.start < bytes.end),
+    )
+}
+
+fn text_of(source: &str, tokens: &[Tok], from: usize, to: usize) -> String {
+    if from >= to {
+        return String::new();
+    }
+    source[tokens[from].start..tokens[to - 1].end]
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn node_text(source: &str, tokens: &[Tok], node: &impl Spanned) -> String {
+    let (from, to) = token_range(tokens, node);
+    text_of(source, tokens, from, to)
+}
+
+/// A function item with a body.
+struct FnItem<'a> {
+    name: String,
+    sig: &'a syn::Signature,
+    block: &'a syn::Block,
+    /// `Some(is_trait_impl)` for a method of an `impl` block
+    impl_kind: Option<bool>,
+    /// Index of the `fn` keyword
+    fn_tok: usize,
+    body_open: usize,
+    body_close: usize,
+}
+
+/// Every function with a body in `file`, nested ones included.
+fn fn_items<'a>(file: &'a syn::File, tokens: &[Tok]) -> Vec<FnItem<'a>> {
+    struct Collector<'a, 't> {
+        tokens: &'t [Tok],
+        impls: Vec<bool>,
+        items: Vec<FnItem<'a>>,
+    }
+
+    impl<'a> Collector<'a, '_> {
+        fn add(&mut self, sig: &'a syn::Signature, block: &'a syn::Block) {
+            let (body_open, body_end) = token_range(self.tokens, block);
+            self.items.push(FnItem {
+                name: sig.ident.to_string(),
+                sig,
+                block,
+                impl_kind: self.impls.last().copied(),
+                fn_tok: token_range(self.tokens, &sig.fn_token).0,
+                body_open,
+                body_close: body_end - 1,
+            });
+        }
+    }
+
+    impl<'a> Visit<'a> for Collector<'a, '_> {
+        fn visit_item_impl(&mut self, item: &'a syn::ItemImpl) {
+            self.impls.push(item.trait_.is_some());
+            visit::visit_item_impl(self, item);
+            self.impls.pop();
+        }
+
+        fn visit_item_fn(&mut self, item: &'a syn::ItemFn) {
+            self.add(&item.sig, &item.block);
+            visit::visit_item_fn(self, item);
+        }
+
+        fn visit_impl_item_fn(&mut self, item: &'a syn::ImplItemFn) {
+            self.add(&item.sig, &item.block);
+            visit::visit_impl_item_fn(self, item);
+        }
+
+        fn visit_trait_item_fn(&mut self, item: &'a syn::TraitItemFn) {
+            if let Some(block) = &item.default {
+                self.add(&item.sig, block);
+            }
+            visit::visit_trait_item_fn(self, item);
+        }
+    }
+
+    let mut collector = Collector {
+        tokens,
+        impls: Vec::new(),
+        items: Vec::new(),
+    };
+    collector.visit_file(file);
+    collector.items
+}
+
+// ── Extract function ─────────────────────────────────────────────────────────
+
+const PRIMITIVE_TYPES: &[&str] = &[
+    "i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16", "u32", "u64", "u128", "usize", "f32",
+    "f64", "bool", "char", "str",
+];
+
+/// Methods that take `&mut self` on common std types; a local receiving one
+/// of these has to be passed as `&mut`.
+const MUTATING_METHODS: &[&str] = &[
+    "push",
+    "push_str",
+    "push_back",
+    "push_front",
+    "pop",
+    "pop_back",
+    "pop_front",
+    "insert",
+    "remove",
+    "clear",
+    "extend",
+    "append",
+    "truncate",
+    "retain",
+    "drain",
+    "sort",
+    "sort_by",
+    "sort_by_key",
+    "sort_unstable",
+    "dedup",
+    "reverse",
+    "entry",
+    "get_mut",
+    "iter_mut",
+    "swap",
+    "resize",
+    "split_off",
+    "take",
+];
+
+/// A name bound by a `let` statement (one per name for destructuring).
+struct LetBinding {
+    name: String,
+    mutable: bool,
+    /// Token index of the `let` keyword
+    let_tok: usize,
+    /// Token index of the name in the pattern
+    name_tok: usize,
+    ty: Option<(usize, usize)>,
+    init: Option<(usize, usize)>,
+    /// Token index of the terminating `;` (or the `{` of an `if let`)
+    end: usize,
+}
+
+/// Names bound in a function body: `let` statements (including `if let`
+/// and `while let`), and the scoped bindings of `for` loops, closure
+/// parameters and match arms as `(token index, name)`.
+struct Bindings {
+    lets: Vec<LetBinding>,
+    scoped: Vec<(usize, String)>,
+}
+
+impl Bindings {
+    fn of(tokens: &[Tok], block: &syn::Block) -> Self {
+        struct Collector<'t> {
+            tokens: &'t [Tok],
+            bindings: Bindings,
+        }
+
+        impl Collector<'_> {
+            fn add_let(
+                &mut self,
+                let_token: &Token![let],
+                pat: &syn::Pat,
+                init: Option<&syn::Expr>,
+                end: usize,
+            ) {
+                // A single binding keeps its annotation; destructuring
+                // patterns bind several names whose types we can't see.
+                let (pat, ty) = match pat {
+                    syn::Pat::Type(typed) => (&*typed.pat, Some(&*typed.ty)),
+                    pat => (pat, None),
+                };
+                let simple =
+                    matches!(pat, syn::Pat::Ident(p) if p.by_ref.is_none() && p.subpat.is_none());
+                let let_tok = token_range(self.tokens, let_token).0;
+                for ident in pat_bindings(pat) {
+                    self.bindings.lets.push(LetBinding {
+                        name: ident.ident.to_string(),
+                        mutable: ident.mutability.is_some(),
+                        let_tok,
+                        name_tok: token_range(self.tokens, &ident.ident).0,
+                        ty: ty.filter(|_| simple).map(|ty| token_range(self.tokens, ty)),
+                        init: init
+                            .filter(|_| simple)
+                            .map(|init| token_range(self.tokens, init)),
+                        end,
+                    });
+                }
+            }
+
+            fn add_scoped(&mut self, pat: &syn::Pat) {
+                for ident in pat_bindings(pat) {
+                    let at = token_range(self.tokens, &ident.ident).0;
+                    self.bindings.scoped.push((at, ident.ident.to_string()));
+                }
+            }
+        }
+
+        impl<'a> Visit<'a> for Collector<'_> {
+            fn visit_local(&mut self, local: &'a syn::Local) {
+                let end = token_range(self.tokens, &local.semi_token).0;
+                let init = local.init.as_ref().map(|init| &*init.expr);
+                self.add_let(&local.let_token, &local.pat, init, end);
+                visit::visit_local(self, local);
+            }
+
+            fn visit_expr_let(&mut self, expr: &'a syn::ExprLet) {
+                let end = token_range(self.tokens, &*expr.expr).1;
+                self.add_let(&expr.let_token, &expr.pat, None, end);
+                visit::visit_expr_let(self, expr);
+            }
+
+            fn visit_expr_for_loop(&mut self, expr: &'a syn::ExprForLoop) {
+                self.add_scoped(&expr.pat);
+                visit::visit_expr_for_loop(self, expr);
+            }
+
+            fn visit_expr_closure(&mut self, expr: &'a syn::ExprClosure) {
+                for input in &expr.inputs {
+                    self.add_scoped(input);
+                }
+                visit::visit_expr_closure(self, expr);
+            }
+
+            fn visit_arm(&mut self, arm: &'a syn::Arm) {
+                self.add_scoped(&arm.pat);
+                visit::visit_arm(self, arm);
+            }
+        }
+
+        let mut collector = Collector {
+            tokens,
+            bindings: Bindings {
+                lets: Vec::new(),
+                scoped: Vec::new(),
+            },
+        };
+        collector.visit_block(block);
+        collector.bindings
+    }
+
+    /// `let` bindings whose `let` is in `tokens[from..to]`.
+    fn lets_in(&self, from: usize, to: usize) -> Vec<&LetBinding> {
+        self.lets
+            .iter()
+            .filter(|b| (from..to).contains(&b.let_tok))
+            .collect()
+    }
+
+    /// Names bound by `for` loops, closure parameters and match arms in
+    /// `tokens[from..to]`. Scoping is ignored: these are never parameters.
+    fn scoped_in(&self, from: usize, to: usize) -> std::collections::HashSet<&str> {
+        self.scoped
+            .iter()
+            .filter(|(at, _)| (from..to).contains(at))
+            .map(|(_, name)| name.as_str())
+            .collect()
+    }
+}
+
+/// The variables a pattern binds; capitalized names are enum variants or
+/// constants, not bindings.
+fn pat_bindings(pat: &syn::Pat) -> Vec<&syn::PatIdent> {
+    let mut out = Vec::new();
+    let mut stack = vec![pat];
+    while let Some(pat) = stack.pop() {
+        match pat {
+            syn::Pat::Ident(ident) => {
+                let name = ident.ident.to_string();
+                if name.starts_with(|c: char| c.is_lowercase() || c == '_') {
+                    out.push(ident);
+                }
+                if let Some((_, sub)) = &ident.subpat {
+                    stack.push(sub);
+                }
+            }
+            syn::Pat::Or(or) => stack.extend(&or.cases),
+            syn::Pat::Paren(paren) => stack.push(&paren.pat),
+            syn::Pat::Reference(reference) => stack.push(&reference.pat),
+            syn::Pat::Slice(slice) => stack.extend(&slice.elems),
+            syn::Pat::Struct(s) => stack.extend(s.fields.iter().map(|f| &*f.pat)),
+            syn::Pat::Tuple(tuple) => stack.extend(&tuple.elems),
+            syn::Pat::TupleStruct(tuple) => stack.extend(&tuple.elems),
+            syn::Pat::Type(typed) => stack.push(&typed.pat),
+            _ => {}
+        }
+    }
+    out.sort_by_key(|ident| ident.ident.span().byte_range().start);
+    out
+}
+
+/// Best-effort type of a `let` initializer.
+fn infer_init_type(tokens: &[Tok], (from, to): (usize, usize)) -> Option<String> {
+    let init = &tokens[from..to];
+    let first = init.first()?;
+
+    if init.len() >= 2 && init[init.len() - 2].is("as") {
+        return Some(init[init.len() - 1].text.clone());
+    }
+    if init.len() == 1 {
+        let t = &first.text;
+        return match first.kind {
+            TokKind::Literal if t.starts_with('"') => Some("&str".to_string()),
+            TokKind::Literal if t.starts_with('\'') => Some("char".to_string()),
+            TokKind::Literal if t.starts_with(|c: char| c.is_ascii_digit()) => {
+                let suffix = PRIMITIVE_TYPES
+                    .iter()
+                    .find(|p| t.ends_with(**p) && t.len() > p.len());
+                // Unsuffixed literals take their type from context.
+                suffix.map(|p| p.to_string())
+            }
+            TokKind::Keyword if t == "true" || t == "false" => Some("bool".to_string()),
+            _ => None,
+        };
+    }
+    if first.is("format") && init[1].is("!") {
+        return Some("String".to_string());
+    }
+    // `x.to_string()`, `"..".to_owned()`
+    let last_call = init.iter().rev().nth(2).map(|t| t.text.as_str());
+    if init.len() >= 4
+        && init[init.len() - 4].is(".")
+        && (last_call == Some("to_string")
+            || (last_call == Some("to_owned") && first.text.starts_with('"')))
+    {
+        return Some("String".to_string());
+    }
+    let is_type_name = first.kind == TokKind::Ident && first.text.starts_with(char::is_uppercase);
+    if is_type_name && init[1].is("{") {
+        return Some(first.text.clone());
+    }
+    if is_type_name
+        && init[1].is("::")
+        && init
+            .get(2)
+            .is_some_and(|t| matches!(t.text.as_str(), "new" | "default" | "from"))
+        && init.get(3).is_some_and(|t| t.is("("))
+        && tokens[from + 3].close == Some(to - 1)
+    {
+        // Containers need their element types spelled out; leave those open.
+        if matches!(
+            first.text.as_str(),
+            "Vec"
+                | "HashMap"
+                | "HashSet"
+                | "BTreeMap"
+                | "BTreeSet"
+                | "VecDeque"
+                | "Option"
+                | "Box"
+                | "Rc"
+                | "Arc"
+        ) {
+            return None;
+        }
+        return Some(first.text.clone());
+    }
+    None
+}
+
+fn is_copy_type(ty: &str) -> bool {
+    ty.starts_with('&') || PRIMITIVE_TYPES.contains(&ty) || ty == "()"
+}
+
+/// The shared-borrow form of `ty` for a read-only parameter.
+fn borrowed_type(ty: &str) -> String {
+    if ty == "String" {
+        return "&str".to_string();
+    }
+    if let Some(inner) = ty.strip_prefix("Vec<").and_then(|t| t.strip_suffix('>')) {
+        return format!("&[{}]", inner);
+    }
+    format!("&{}", ty)
+}
+
+/// Whether the identifier token at `i` is a plain variable reference (not
+/// a field, method, path segment, macro, or struct field label).
+fn is_variable_ref(tokens: &[Tok], i: usize) -> bool {
+    let tok = &tokens[i];
+    if tok.kind != TokKind::Ident
+        || PRIMITIVE_TYPES.contains(&tok.text.as_str())
+        || !tok.text.starts_with(|c: char| c.is_lowercase() || c == '_')
+    {
+        return false;
+    }
+    let prev = i.checked_sub(1).map(|p| tokens[p].text.as_str());
+    let next = tokens.get(i + 1).map(|t| t.text.as_str());
+    !matches!(prev, Some(".") | Some("::") | Some("as"))
+        && !matches!(next, Some("::") | Some("!") | Some(":"))
+}
+
+/// How the variable referenced at `occurrences` is changed:
+/// `(reassigned, mutated_in_place)`.
+fn mutations(tokens: &[Tok], occurrences: &[usize]) -> (bool, bool) {
+    let reassigned = occurrences.iter().any(|&k| {
+        tokens.get(k + 1).is_some_and(|t| is_assign_op(&t.text))
+            && !(k > 0 && tokens[k - 1].is("*"))
+    });
+    let mutated = occurrences.iter().any(|&k| {
+        let next = tokens.get(k + 1).map(|t| t.text.as_str());
+        let field_assign =
+            next == Some(".") && tokens.get(k + 3).is_some_and(|t| is_assign_op(&t.text));
+        let method = next == Some(".")
+            && tokens
+                .get(k + 2)
+                .is_some_and(|t| MUTATING_METHODS.contains(&t.text.as_str()))
+            && tokens.get(k + 3).is_some_and(|t| t.is("("));
+        let index_assign = next == Some("[")
+            && tokens[k + 1]
+                .close
+                .and_then(|c| tokens.get(c + 1))
+                .is_some_and(|t| is_assign_op(&t.text));
+        let explicit = k >= 2 && tokens[k - 1].is("mut") && tokens[k - 2].is("&");
+        let deref_assign = k > 0
+            && tokens[k - 1].is("*")
+            && tokens.get(k + 1).is_some_and(|t| is_assign_op(&t.text));
+        field_assign || method || index_assign || explicit || deref_assign
+    });
+    (reassigned, mutated)
+}
+
+/// Methods whose result borrows from the receiver.
+const BORROWING_METHODS: &[&str] = &[
+    "iter",
+    "iter_mut",
+    "chars",
+    "bytes",
+    "lines",
+    "keys",
+    "values",
+    "get",
+    "get_mut",
+    "first",
+    "last",
+    "trim",
+    "trim_start",
+    "trim_end",
+    "split",
+    "split_whitespace",
+    "windows",
+    "chunks",
+];
+
+/// The parameter an initializer borrows from, if its value is a reference
+/// into one (`&p`, `p.iter()`, `p.as_str()`, ...). Chains that end in an
+/// owning call such as `.collect()` don't count.
+fn borrowed_param<'a>(
+    tokens: &'a [Tok],
+    (from, to): (usize, usize),
+    params: &[&str],
+) -> Option<&'a str> {
+    let is_param = |k: usize| {
+        tokens
+            .get(k)
+            .is_some_and(|t| params.contains(&t.text.as_str()))
+            && is_variable_ref(tokens, k)
+    };
+    if tokens[from].is("&") {
+        let k = if tokens.get(from + 1).is_some_and(|t| t.is("mut")) {
+            from + 2
+        } else {
+            from + 1
+        };
+        if k < to && is_param(k) {
+            return Some(tokens[k].text.as_str());
+        }
+    }
+
+    // Final method call: `... . method ( ... )` closing at the end.
+    let last = to.checked_sub(1)?;
+    if !tokens[last].is(")") {
+        return None;
+    }
+    let open = (from..last)
+        .rev()
+        .find(|&k| tokens[k].close == Some(last))?;
+    let method = tokens.get(open.checked_sub(1)?)?;
+    let dot = open.checked_sub(2)?;
+    if dot < from || !tokens[dot].is(".") {
+        return None;
+    }
+    if !(method.text.starts_with("as_") || BORROWING_METHODS.contains(&method.text.as_str())) {
+        return None;
+    }
+    (from..dot)
+        .find(|&k| is_param(k))
+        .map(|k| tokens[k].text.as_str())
+}
+
+fn is_assign_op(text: &str) -> bool {
+    matches!(
+        text,
+        "=" | "+=" | "-=" | "*=" | "/=" | "%=" | "^=" | "&=" | "|=" | "<<=" | ">>="
+    )
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ParamMode {
+    /// Moved or copied in
+    Value,
+    /// Reassigned inside the block: taken as `mut x: T`, handed back if needed
+    MutValue,
+    /// Mutated in place through a field, index or `&mut self` method
+    MutRef,
+    /// Read-only borrow
+    Ref,
+}
+
+struct ExtractParam {
+    name: String,
+    ty: Option<String>,
+    mode: ParamMode,
+}
+
+struct ExtractOutput {
+    name: String,
+    ty: Option<String>,
+    mutable: bool,
+    /// An existing variable rebound from the result rather than a new `let`
+    rebind: bool,
+}
+
+/// Deterministic extract-function for Rust source.
+fn extract_rust_function(
+    source: &str,
+    start_line: usize,
+    end_line: usize,
+    new_name: &str,
+) -> Result<RefactoringExample> {
+    if start_line == 0 || end_line < start_line {
+        anyhow::bail!("invalid line range {}-{}", start_line, end_line);
+    }
+    let lines: Vec<&str> = source.lines().collect();
+    if end_line > lines.len() {
+        anyhow::bail!(
+            "line range {}-{} is past the end of the file ({} lines)",
+            start_line,
+            end_line,
+            lines.len()
+        );
+    }
+
+    let (file, tokens) = parse_rust(source).context("failed to parse the file as Rust")?;
+    let fns = fn_items(&file, &tokens);
+    let span = fns
+        .iter()
+        .filter(|s| tokens[s.body_open].line < start_line && tokens[s.body_close].line > end_line)
+        .max_by_key(|s| s.body_open)
+        .with_context(|| {
+            format!(
+                "lines {}-{} are not inside a function body",
+                start_line, end_line
+            )
+        })?;
+
+    let block: Vec<usize> = (span.body_open + 1..span.body_close)
+        .filter(|&i| (start_line..=end_line).contains(&tokens[i].line))
+        .collect();
+    let (Some(&bs), Some(&be)) = (block.first(), block.last()) else {
+        anyhow::bail!("lines {}-{} contain no code", start_line, end_line);
+    };
+    let be = be + 1;
+
+    // ── structural checks ────────────────────────────────────────────────
+    let mut depth = 0i32;
+    for tok in &tokens[bs..be] {
+        match tok.text.as_str() {
+            "(" | "[" | "{" => depth += 1,
+            ")" | "]" | "}" => depth -= 1,
+            _ => {}
+        }
+        if depth < 0 {
+            break;
+        }
+    }
+    if depth != 0 {
+        anyhow::bail!(
+            "lines {}-{} don't cover whole statements (unbalanced brackets)",
+            start_line,
+            end_line
+        );
+    }
+    if !matches!(tokens[be - 1].text.as_str(), ";" | "}") {
+        anyhow::bail!(
+            "lines {}-{} end in a tail expression; bind it with `let` or extend the selection",
+            start_line,
+            end_line
+        );
+    }
+    let has = |text: &str| tokens[bs..be].iter().any(|t| t.is(text));
+    if has("return") {
+        anyhow::bail!(
+            "the selection contains an early `return`, which can't be moved into a helper"
+        );
+    }
+    if (has("break") || has("continue")) && !(has("loop") || has("while") || has("for")) {
+        anyhow::bail!("the selection uses `break`/`continue` for a loop outside of it");
+    }
+
+    let uses_self = has("self");
+    let uses_try = has("?");
+    let is_async = tokens[bs..be]
+        .windows(2)
+        .any(|w| w[0].is(".") && w[1].is("await"));
+    let impl_kind = span.impl_kind;
+    let in_trait_impl = impl_kind == Some(true);
+    if in_trait_impl && (uses_self || has("Self")) {
+        anyhow::bail!(
+            "the selection uses `self` inside a trait impl; a new method can't be added there"
+        );
+    }
+
+    // ── types of everything in scope ─────────────────────────────────────
+    let mut types: std::collections::HashMap<String, Option<String>> =
+        std::collections::HashMap::new();
+    for input in &span.sig.inputs {
+        if let syn::FnArg::Typed(typed) = input {
+            if let syn::Pat::Ident(ident) = &*typed.pat {
+                types.insert(
+                    ident.ident.to_string(),
+                    Some(node_text(source, &tokens, &*typed.ty)),
+                );
+            }
+        }
+    }
+    let bindings = Bindings::of(&tokens, span.block);
+    for binding in bindings.lets_in(span.body_open + 1, bs) {
+        let ty = match (binding.ty, binding.init) {
+            (Some((f, t)), _) => Some(text_of(source, &tokens, f, t)),
+            (None, Some(init)) => infer_init_type(&tokens, init),
+            _ => None,
+        };
+        types.insert(binding.name.clone(), ty);
+    }
+    let earlier: std::collections::HashSet<&str> = (span.body_open + 1..bs)
+        .filter(|&i| is_variable_ref(&tokens, i))
+        .map(|i| tokens[i].text.as_str())
+        .collect();
+
+    // ── bindings introduced by the block ─────────────────────────────────
+    let block_lets = bindings.lets_in(bs, be);
+    let block_scoped = bindings.scoped_in(bs, be);
+    let bound_before = |name: &str, at: usize| {
+        block_lets
+            .iter()
+            .any(|b| b.name == name && (b.end < at || b.name_tok == at))
+    };
+    let block_depth = |at: usize| {
+        let mut depth = 0i32;
+        for tok in &tokens[bs..at] {
+            match tok.text.as_str() {
+                "{" | "(" | "[" => depth += 1,
+                "}" | ")" | "]" => depth -= 1,
+                _ => {}
+            }
+        }
+        depth
+    };
+
+    let after_lets = bindings.lets_in(be, span.body_close);
+    let uses_after = |name: &str| -> Vec<usize> {
+        (be..span.body_close)
+            .filter(|&i| {
+                tokens[i].is(name)
+                    && is_variable_ref(&tokens, i)
+                    && !after_lets
+                        .iter()
+                        .any(|b| b.name == name && (b.end < i || b.name_tok == i))
+            })
+            .collect()
+    };
+    let used_after = |name: &str| !uses_after(name).is_empty();
+
+    // ── parameters ───────────────────────────────────────────────────────
+    let mut params: Vec<ExtractParam> = Vec::new();
+    for i in bs..be {
+        let name = tokens[i].text.as_str();
+        if !is_variable_ref(&tokens, i)
+            || name == span.name
+            || bound_before(name, i)
+            || block_scoped.contains(name)
+            || params.iter().any(|p| p.name == name)
+            || !(types.contains_key(name) || earlier.contains(name))
+        {
+            continue;
+        }
+        // A call of something that isn't a known local is a function.
+        if tokens.get(i + 1).is_some_and(|t| t.is("(")) && !types.contains_key(name) {
+            continue;
+        }
+
+        let occurrences: Vec<usize> = (bs..be)
+            .filter(|&k| tokens[k].is(name) && is_variable_ref(&tokens, k))
+            .filter(|&k| !bound_before(name, k))
+            .collect();
+        let (reassigned, mutated) = mutations(&tokens, &occurrences);
+
+        let ty = types.get(name).cloned().flatten();
+        let already_ref = ty.as_deref().is_some_and(|t| t.starts_with('&'));
+        let mode = if reassigned {
+            ParamMode::MutValue
+        } else if mutated && !already_ref {
+            ParamMode::MutRef
+        } else if mutated || ty.as_deref().is_some_and(is_copy_type) || !used_after(name) {
+            ParamMode::Value
+        } else {
+            ParamMode::Ref
+        };
+        params.push(ExtractParam {
+            name: name.to_string(),
+            ty,
+            mode,
+        });
+    }
+
+    // ── outputs ──────────────────────────────────────────────────────────
+    let param_names: Vec<&str> = params.iter().map(|p| p.name.as_str()).collect();
+    let mut outputs: Vec<ExtractOutput> = Vec::new();
+    for binding in block_lets.iter().filter(|b| block_depth(b.name_tok) == 0) {
+        if outputs.iter().any(|o| o.name == binding.name) || !used_after(&binding.name) {
+            continue;
+        }
+        if let Some((from, to)) = binding.init {
+            let borrows = borrowed_param(&tokens, (from, to), &param_names);
+            if let Some(param) = borrows {
+                anyhow::bail!(
+                    "`{}` borrows from `{}` and is used after the selection; \
+                     the helper would need explicit lifetimes, so extract it by hand",
+                    binding.name,
+                    param
+                );
+            }
+        }
+        let ty = match (binding.ty, binding.init) {
+            (Some((f, t)), _) => Some(text_of(source, &tokens, f, t)),
+            (None, Some(init)) => infer_init_type(&tokens, init),
+            _ => None,
+        };
+        // Only keep `mut` on the new binding if the rest of the function needs it.
+        let (reassigned, mutated) = mutations(&tokens, &uses_after(&binding.name));
+        outputs.push(ExtractOutput {
+            name: binding.name.clone(),
+            ty,
+            mutable: binding.mutable && (reassigned || mutated),
+            rebind: false,
+        });
+    }
+    for param in params.iter().filter(|p| p.mode == ParamMode::MutValue) {
+        if used_after(&param.name) && !outputs.iter().any(|o| o.name == param.name) {
+            outputs.push(ExtractOutput {
+                name: param.name.clone(),
+                ty: param.ty.clone(),
+                mutable: true,
+                rebind: true,
+            });
+        }
+    }
+
+    // ── signature ────────────────────────────────────────────────────────
+    let ty_or_placeholder = |ty: &Option<String>| ty.clone().unwrap_or_else(|| "_".to_string());
+    let mut sig_params: Vec<String> = Vec::new();
+    let mut call_args: Vec<String> = Vec::new();
+    let receiver = if uses_self {
+        let mut_self = (bs..be).any(|k| {
+            tokens[k].is("self")
+                && ((k >= 2 && tokens[k - 1].is("mut") && tokens[k - 2].is("&"))
+                    || (tokens.get(k + 1).is_some_and(|t| t.is("."))
+                        && (tokens.get(k + 3).is_some_and(|t| is_assign_op(&t.text))
+                            || (tokens.get(k + 3).is_some_and(|t| t.is("."))
+                                && tokens.get(k + 4).is_some_and(|t| {
+                                    MUTATING_METHODS.contains(&t.text.as_str())
+                                })))))
+        });
+        let receiver = if mut_self { "&mut self" } else { "&self" };
+        sig_params.push(receiver.to_string());
+        Some(receiver)
+    } else {
+        None
+    };
+    for param in &params {
+        let ty = ty_or_placeholder(&param.ty);
+        let (sig, arg) = match param.mode {
+            ParamMode::Value => (format!("{}: {}", param.name, ty), param.name.clone()),
+            ParamMode::MutValue => (format!("mut {}: {}", param.name, ty), param.name.clone()),
+            ParamMode::MutRef => (
+                format!("{}: &mut {}", param.name, ty),
+                format!("&mut {}", param.name),
+            ),
+            ParamMode::Ref => (
+                format!("{}: {}", param.name, borrowed_type(&ty)),
+                format!("&{}", param.name),
+            ),
+        };
+        sig_params.push(sig);
+        call_args.push(arg);
+    }
+
+    let out_type = match outputs.as_slice() {
+        [] => None,
+        [one] => Some(ty_or_placeholder(&one.ty)),
+        many => Some(format!(
+            "({})",
+            many.iter()
+                .map(|o| ty_or_placeholder(&o.ty))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )),
+    };
+    let out_expr = match outputs.as_slice() {
+        [] => None,
+        [one] => Some(one.name.clone()),
+        many => Some(format!(
+            "({})",
+            many.iter()
+                .map(|o| o.name.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        )),
+    };
+
+    let (ret_type, tail) = if uses_try {
+        let syn::ReturnType::Type(_, ret) = &span.sig.output else {
+            anyhow::bail!(
+                "the selection uses `?` but `{}` doesn't return a Result or Option",
+                span.name
+            );
+        };
+        // `Result<T, E>` or `Option<T>`, possibly behind a path.
+        let generic = match &**ret {
+            syn::Type::Path(path) => {
+                path.path
+                    .segments
+                    .last()
+                    .and_then(|last| match &last.arguments {
+                        syn::PathArguments::AngleBracketed(args) => Some((path, last, args)),
+                        _ => None,
+                    })
+            }
+            _ => None,
+        };
+        let wrapper = generic
+            .map(|(path, last, _)| {
+                let from = token_range(&tokens, path).0;
+                let to = token_range(&tokens, &last.ident).1;
+                text_of(source, &tokens, from, to)
+            })
+            .unwrap_or_default();
+        let is_option = wrapper.ends_with("Option");
+        if !(wrapper.ends_with("Result") || is_option) {
+            anyhow::bail!(
+                "the selection uses `?` but the return type of `{}` isn't a generic Result or Option",
+                span.name
+            );
+        }
+        let mut args: Vec<String> = generic
+            .map(|(_, _, args)| {
+                args.args
+                    .iter()
+                    .map(|arg| node_text(source, &tokens, arg))
+                    .collect()
+            })
+            .unwrap_or_default();
+        let inner = out_type.clone().unwrap_or_else(|| "()".to_string());
+        if args.is_empty() {
+            args.push(inner);
+        } else {
+            args[0] = inner;
+        }
+        let ctor = if is_option { "Some" } else { "Ok" };
+        (
+            Some(format!("{}<{}>", wrapper, args.join(", "))),
+            Some(format!(
+                "{}({})",
+                ctor,
+                out_expr.clone().unwrap_or_else(|| "()".to_string())
+            )),
+        )
+    } else {
+        (out_type.clone(), out_expr.clone())
+    };
+
+    // Carry the enclosing generics over when a parameter mentions them.
+    let mut generics = String::new();
+    let mut where_clause = String::new();
+    let sig_generics = &span.sig.generics;
+    if !sig_generics.params.is_empty() {
+        let names: Vec<String> = sig_generics
+            .params
+            .iter()
+            .map(|param| match param {
+                syn::GenericParam::Type(ty) => ty.ident.to_string(),
+                syn::GenericParam::Lifetime(lt) => lt.lifetime.to_string(),
+                syn::GenericParam::Const(c) => c.ident.to_string(),
+            })
+            .collect();
+        let signature_text = format!(
+            "{} {}",
+            sig_params.join(" "),
+            ret_type.clone().unwrap_or_default()
+        );
+        let mentioned = signature_text
+            .parse::<TokenStream>()
+            .map(|stream| flatten_tokens(&signature_text, stream))
+            .unwrap_or_default();
+        if names.iter().any(|n| mentioned.iter().any(|t| t.is(n))) {
+            generics = node_text(source, &tokens, sig_generics);
+            if let Some(clause) = &sig_generics.where_clause {
+                where_clause = format!(" {}", node_text(source, &tokens, clause));
+            }
+        }
+    }
+
+    // ── assemble ─────────────────────────────────────────────────────────
+    let fn_line = tokens[span.fn_tok].line;
+    let close_line = tokens[span.body_close].line;
+    let indent_of = |line: &str| line.len() - line.trim_start().len();
+    let fn_indent = &lines[fn_line - 1][..indent_of(lines[fn_line - 1])];
+    let call_indent = &lines[start_line - 1][..indent_of(lines[start_line - 1])];
+    let selected = &lines[start_line - 1..end_line];
+    let min_indent = selected
+        .iter()
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| indent_of(l))
+        .min()
+        .unwrap_or(0);
+
+    // `&mut x` for a parameter that is now itself `&mut` becomes just `x`.
+    let mut_refs: Vec<&str> = params
+        .iter()
+        .filter(|p| p.mode == ParamMode::MutRef)
+        .map(|p| p.name.as_str())
+        .collect();
+    let mut rewrites: Vec<(usize, usize, String)> = Vec::new();
+    for k in bs + 2..be {
+        if mut_refs.contains(&tokens[k].text.as_str())
+            && is_variable_ref(&tokens, k)
+            && tokens[k - 1].is("mut")
+            && tokens[k - 2].is("&")
+        {
+            rewrites.push((tokens[k - 2].start, tokens[k].end, tokens[k].text.clone()));
+        }
+    }
+    let line_starts: Vec<usize> = std::iter::once(0)
+        .chain(source.match_indices('\n').map(|(i, _)| i + 1))
+        .collect();
+    let selection_start = line_starts[start_line - 1];
+    let selection_text = {
+        let end = line_starts
+            .get(end_line)
+            .map_or(source.len(), |&next| next - 1);
+        let mut text = source[selection_start..end].to_string();
+        for (from, to, with) in rewrites.iter().rev() {
+            text.replace_range(from - selection_start..to - selection_start, with);
+        }
+        text
+    };
+
+    let body_indent = format!("{}    ", fn_indent);
+    let mut new_fn = String::new();
+    new_fn.push_str(fn_indent);
+    if is_async {
+        new_fn.push_str("async ");
+    }
+    new_fn.push_str(&format!(
+        "fn {}{}({})",
+        new_name,
+        generics,
+        sig_params.join(", ")
+    ));
+    if let Some(ret) = &ret_type {
+        new_fn.push_str(&format!(" -> {}", ret));
+    }
+    new_fn.push_str(&where_clause);
+    new_fn.push_str(" {\n");
+    for line in selection_text.lines() {
+        if line.trim().is_empty() {
+            new_fn.push('\n');
+        } else {
+            new_fn.push_str(&format!(
+                "{}{}\n",
+                body_indent,
+                &line[min_indent.min(indent_of(line))..]
+            ));
+        }
+    }
+    if let Some(tail) = &tail {
+        new_fn.push_str(&format!("{}{}\n", body_indent, tail));
+    }
+    new_fn.push_str(fn_indent);
+    new_fn.push('}');
+
+    let callee = match (receiver, impl_kind) {
+        (Some(_), _) => format!("self.{}", new_name),
+        (None, Some(false)) => format!("Self::{}", new_name),
+        _ => new_name.to_string(),
+    };
+    let mut call = format!("{}({})", callee, call_args.join(", "));
+    if is_async {
+        call.push_str(".await");
+    }
+    if uses_try {
+        call.push('?');
+    }
+    let binding = |o: &ExtractOutput| {
+        if o.mutable {
+            format!("mut {}", o.name)
+        } else {
+            o.name.clone()
+        }
+    };
+    let names = |outs: &[ExtractOutput]| {
+        outs.iter()
+            .map(|o| o.name.as_str())
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    let call_line = match outputs.as_slice() {
+        [] => format!("{}{};", call_indent, call),
+        [one] if one.rebind => format!("{}{} = {};", call_indent, one.name, call),
+        [one] => format!("{}let {} = {};", call_indent, binding(one), call),
+        many if many.iter().all(|o| !o.rebind) => format!(
+            "{}let ({}) = {};",
+            call_indent,
+            many.iter().map(binding).collect::<Vec<_>>().join(", "),
+            call
+        ),
+        // Existing variables are assigned in place; declare the new ones first.
+        many => {
+            let mut text = String::new();
+            for o in many.iter().filter(|o| !o.rebind) {
+                text.push_str(&format!("{}let {};\n", call_indent, binding(o)));
+            }
+            text.push_str(&format!("{}({}) = {};", call_indent, names(many), call));
+            text
+        }
+    };
+
+    let before = lines[fn_line - 1..close_line].join("\n");
+    let mut after_lines: Vec<String> = lines[fn_line - 1..start_line - 1]
+        .iter()
+        .map(|l| l.to_string())
+        .collect();
+    after_lines.push(call_line);
+    after_lines.extend(lines[end_line..close_line].iter().map(|l| l.to_string()));
+    let after = format!("{}\n\n{}", after_lines.join("\n"), new_fn);
+
+    // ── explanation ──────────────────────────────────────────────────────
+    let mut explanation = format!(
+        "Moved lines {}-{} of `{}` into `{}`",
+        start_line, end_line, span.name, new_name
+    );
+    if params.is_empty() {
+        explanation.push_str(", which takes no parameters");
+    } else {
+        let described: Vec<String> = params
+            .iter()
+            .map(|p| {
+                let how = match p.mode {
+                    ParamMode::Value => "by value",
+                    ParamMode::MutValue => "by value, reassigned",
+                    ParamMode::MutRef => "by mutable reference",
+                    ParamMode::Ref => "by reference",
+                };
+                format!("`{}` ({})", p.name, how)
+            })
+            .collect();
+        explanation.push_str(&format!(", taking {}", described.join(", ")));
+    }
+    match outputs.as_slice() {
+        [] => explanation.push('.'),
+        outs => explanation.push_str(&format!(
+            " and returning {}.",
+            outs.iter()
+                .map(|o| format!("`{}`", o.name))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )),
+    }
+    if uses_try {
+        explanation
+            .push_str(" The `?` operator is kept, so the helper returns the same error type.");
+    }
+    if in_trait_impl {
+        explanation.push_str(&format!(
+            " `{}` is inside a trait impl; place `{}` outside the impl block.",
+            span.name, new_name
+        ));
+    }
+    if params.iter().any(|p| p.ty.is_none()) || outputs.iter().any(|o| o.ty.is_none()) {
+        explanation
+            .push_str(" Types marked `_` couldn't be inferred from the source; fill them in.");
+    }
+
+    Ok(RefactoringExample {
+        before,
+        after,
+        explanation,
+    })
+}
+
+// ── Duplicate detection ──────────────────────────────────────────────────────
+
+/// A statement-sized run of tokens in a function body, ending after `;`,
+/// `{` or `}`. Comparing these instead of raw lines makes the match
+/// independent of how the code is wrapped.
+struct CodeUnit {
+    line: usize,
+    end_line: usize,
+    normalized: Vec<String>,
+}
+
+/// A window occurrence: `(file index, function index, first unit index)`.
+type WindowAt = (usize, usize, usize);
+
+struct FnUnits {
+    file: usize,
+    name: String,
+    units: Vec<CodeUnit>,
+}
+
+/// Identifier names, literals and lifetimes are replaced with placeholders;
+/// keywords and punctuation are kept so the shape of the code still counts.
+fn normalize_token(tok: &Tok) -> String {
+    match tok.kind {
+        TokKind::Ident => "$id".to_string(),
+        TokKind::Literal => "$lit".to_string(),
+        TokKind::Lifetime => "$lt".to_string(),
+        TokKind::Keyword | TokKind::Punct => tok.text.clone(),
+    }
+}
+
+fn find_duplicate_blocks(files: &[(String, String)], min_lines: usize) -> Vec<DuplicateGroup> {
+    use std::collections::hash_map::DefaultHasher;
+    use std::collections::{HashMap, HashSet};
+    use std::hash::{Hash, Hasher};
+
+    // Windows made of nothing but braces and `Ok(())` aren't worth reporting.
+    let min_tokens = min_lines * 4;
+
+    let mut functions: Vec<FnUnits> = Vec::new();
+    for (file_idx, (_, source)) in files.iter().enumerate() {
+        // Files syn can't parse (half-edited, or not Rust at all) are skipped.
+        let Ok((file, tokens)) = parse_rust(source) else {
+            continue;
+        };
+        let spans = fn_items(&file, &tokens);
+        for span in &spans {
+            // Nested functions are scanned on their own.
+            let nested: Vec<(usize, usize)> = spans
+                .iter()
+                .filter(|o| o.fn_tok > span.body_open && o.body_close < span.body_close)
+                .map(|o| (o.fn_tok, o.body_close))
+                .collect();
+            let mut units: Vec<CodeUnit> = Vec::new();
+            let mut open = false;
+            let body = (span.body_open + 1..span.body_close)
+                .filter(|k| !nested.iter().any(|&(from, to)| (from..=to).contains(k)));
+            for tok in body.map(|k| &tokens[k]) {
+                match units.last_mut() {
+                    Some(last) if open => {
+                        last.end_line = tok.line;
+                        last.normalized.push(normalize_token(tok));
+                    }
+                    _ => units.push(CodeUnit {
+                        line: tok.line,
+                        end_line: tok.line,
+                        normalized: vec![normalize_token(tok)],
+                    }),
+                }
+                open = !matches!(tok.text.as_str(), ";" | "{" | "}");
+            }
+            functions.push(FnUnits {
+                file: file_idx,
+                name: span.name.clone(),
+                units,
+            });
+        }
+    }
+
+    // Hash every window of `min_lines` units.
+    let mut by_hash: HashMap<u64, Vec<WindowAt>> = HashMap::new();
+    for (fn_idx, func) in functions.iter().enumerate() {
+        for start in 0..func.units.len().saturating_sub(min_lines - 1) {
+            let window = &func.units[start..start + min_lines];
+            if window.iter().map(|l| l.normalized.len()).sum::<usize>() < min_tokens {
+                continue;
+            }
+            let mut hasher = DefaultHasher::new();
+            for line in window {
+                line.normalized.hash(&mut hasher);
+            }
+            by_hash
+                .entry(hasher.finish())
+                .or_default()
+                .push((func.file, fn_idx, start));
+        }
+    }
+
+    // Keep windows seen more than once, dropping self-overlapping repeats
+    // within one function.
+    let mut groups: Vec<Vec<WindowAt>> = by_hash
+        .into_values()
+        .filter_map(|mut members| {
+            members.sort();
+            let mut kept: Vec<WindowAt> = Vec::new();
+            for m in members {
+                let overlaps = kept
+                    .last()
+                    .is_some_and(|&(_, f, s)| f == m.1 && m.2 < s + min_lines);
+                if !overlaps {
+                    kept.push(m);
+                }
+            }
+            (kept.len() > 1).then_some(kept)
+        })
+        .collect();
+    groups.sort();
+
+    // Merge runs of windows where every copy continues one line further.
+    let known: HashSet<Vec<WindowAt>> = groups.iter().cloned().collect();
+    let shift = |members: &[WindowAt], by: isize| -> Option<Vec<WindowAt>> {
+        members
+            .iter()
+            .map(|&(file, f, s)| Some((file, f, s.checked_add_signed(by)?)))
+            .collect()
+    };
+    let mut result: Vec<DuplicateGroup> = Vec::new();
+    for members in &groups {
+        if shift(members, -1).is_some_and(|prev| known.contains(&prev)) {
+            continue; // continuation of an earlier run
+        }
+        let mut extra = 0;
+        while shift(members, extra as isize + 1).is_some_and(|next| known.contains(&next)) {
+            extra += 1;
+        }
+        let window_units = min_lines + extra;
+
+        let locations: Vec<CodeLocation> = members
+            .iter()
+            .map(|&(file, f, s)| {
+                let func = &functions[f];
+                CodeLocation {
+                    file: files[file].0.clone(),
+                    line_start: Some(func.units[s].line),
+                    line_end: Some(func.units[s + window_units - 1].end_line),
+                    item_name: Some(func.name.clone()),
+                }
+            })
+            .collect();
+        let lines = locations
+            .iter()
+            .filter_map(|l| Some(l.line_end? - l.line_start? + 1))
+            .max()
+            .unwrap_or(0);
+        if lines < min_lines {
+            continue;
+        }
+
+        let first = &locations[0];
+        let (start, end) = (first.line_start.unwrap_or(1), first.line_end.unwrap_or(1));
+        let snippet = files[members[0].0]
+            .1
+            .lines()
+            .skip(start - 1)
+            .take(end + 1 - start)
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        result.push(DuplicateGroup {
+            suggested_target: duplicate_target(&locations),
+            locations,
+            lines,
+            snippet,
+            suggested_name: None,
+        });
+    }
+
+    result.sort_by(|a, b| {
+        (b.lines * b.locations.len())
+            .cmp(&(a.lines * a.locations.len()))
+            .then_with(|| a.locations[0].file.cmp(&b.locations[0].file))
+            .then_with(|| a.locations[0].line_start.cmp(&b.locations[0].line_start))
+    });
+    result
+}
+
+/// Where a helper shared by `locations` should live: the file itself when
+/// all copies are in one file, otherwise the module root of the closest
+/// directory containing all of them.
+fn duplicate_target(locations: &[CodeLocation]) -> String {
+    let first = Path::new(&locations[0].file);
+    if locations.iter().all(|l| Path::new(&l.file) == first) {
+        return locations[0].file.clone();
+    }
+
+    let mut common = first.parent().map(Path::to_path_buf).unwrap_or_default();
+    while !locations
+        .iter()
+        .all(|l| Path::new(&l.file).starts_with(&common))
+    {
+        if !common.pop() {
+            break;
+        }
+    }
+    ["mod.rs", "lib.rs"]
+        .iter()
+        .map(|root| common.join(root))
+        .find(|candidate| candidate.exists())
+        .unwrap_or_else(|| common.join("util.rs"))
+        .to_string_lossy()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn extract(source: &str, start: usize, end: usize) -> RefactoringExample {
+        extract_rust_function(source, start, end, "extracted").unwrap()
+    }
+
+    #[test]
+    fn extract_function_infers_params_and_single_return() {
+        let source = r#"fn report(items: &[u32], label: &str) -> String {
+    let scale: u32 = 2;
+    let mut total: u32 = 0;
+    for item in items {
+        total += item * scale;
+    }
+    format!("{}: {}", label, total)
+}
+"#;
+        let example = extract(source, 3, 6);
+
+        assert_eq!(example.before, source.trim_end());
+        assert_eq!(
+            example.after,
+            r#"fn report(items: &[u32], label: &str) -> String {
+    let scale: u32 = 2;
+    let total = extracted(items, scale);
+    format!("{}: {}", label, total)
+}
+
+fn extracted(items: &[u32], scale: u32) -> u32 {
+    let mut total: u32 = 0;
+    for item in items {
+        total += item * scale;
+    }
+    total
+}"#
+        );
+        assert!(example.explanation.contains("returning `total`"));
+        assert!(!example.explanation.contains("`_`"));
+    }
+
+    #[test]
+    fn extract_function_passes_mutated_locals_by_mut_ref() {
+        let source = r#"fn build(names: &[&str]) -> Vec<String> {
+    let mut out: Vec<String> = Vec::new();
+    for name in names {
+        out.push(name.to_uppercase());
+    }
+    out
+}
+"#;
+        let example = extract(source, 3, 5);
+
+        assert!(example.after.contains("    extracted(names, &mut out);\n"));
+        assert!(example
+            .after
+            .contains("fn extracted(names: &[&str], out: &mut Vec<String>) {"));
+    }
+
+    #[test]
+    fn extract_function_hands_back_reassigned_values() {
+        let source = r#"fn score(base: i64) -> i64 {
+    let mut count: i64 = base;
+    count += 1;
+    count *= 2;
+    count
+}
+"#;
+        let example = extract(source, 3, 4);
+
+        assert!(example.after.contains("    count = extracted(count);\n"));
+        assert!(example.after.contains(
+            "fn extracted(mut count: i64) -> i64 {\n    count += 1;\n    count *= 2;\n    count\n}"
+        ));
+    }
+
+    #[test]
+    fn extract_function_borrows_values_still_needed_afterwards() {
+        let source = r#"fn greet(name: String) -> String {
+    let greeting = String::from("hello");
+    println!("{} {}", greeting, name);
+    greeting + &name
+}
+"#;
+        let example = extract(source, 3, 3);
+
+        assert!(example.after.contains("    extracted(&greeting, &name);\n"));
+        assert!(example
+            .after
+            .contains("fn extracted(greeting: &str, name: &str) {"));
+    }
+
+    #[test]
+    fn extract_function_keeps_question_mark_and_await() {
+        let source = r#"async fn load(path: &str) -> anyhow::Result<usize> {
+    let text = tokio::fs::read_to_string(path).await?;
+    let n = text.len();
+    Ok(n)
+}
+"#;
+        let example = extract(source, 2, 2);
+
+        assert!(example
+            .after
+            .contains("    let text = extracted(path).await?;\n"));
+        assert!(example
+            .after
+            .contains("async fn extracted(path: &str) -> anyhow::Result<_> {"));
+        assert!(example.after.contains("    Ok(text)\n}"));
+        assert!(example.explanation.contains("`_`"));
+    }
+
+    #[test]
+    fn extract_function_in_impl_uses_self() {
+        let source = r#"struct Counter {
+    total: u32,
+}
+
+impl Counter {
+    fn bump(&mut self, by: u32) {
+        let step: u32 = by * 2;
+        self.total += step;
+        println!("{}", self.total);
+    }
+
+    fn reset(&mut self) {
+        let zero: u32 = 0;
+        println!("reset");
+        self.total = zero;
+    }
+}
+"#;
+        let example = extract(source, 8, 9);
+        assert!(example.after.contains("        self.extracted(step);\n"));
+        assert!(example
+            .after
+            .contains("    fn extracted(&mut self, step: u32) {\n        self.total += step;"));
+
+        let example = extract(source, 14, 14);
+        assert!(example.after.contains("        Self::extracted();\n"));
+    }
+
+    #[test]
+    fn extract_function_ignores_closure_and_match_bindings() {
+        let source = r#"fn count(values: &[Option<u8>], limit: u8) -> usize {
+    let mut hits: usize = 0;
+    for value in values {
+        match value {
+            Some(v) if *v > limit => hits += 1,
+            _ => {}
+        }
+    }
+    let doubled: Vec<u8> = values.iter().flatten().map(|x| x * 2).collect();
+    hits + doubled.len()
+}
+"#;
+        let example = extract(source, 3, 9);
+        assert!(example.after.contains(
+            "fn extracted(values: &[Option<u8>], limit: u8, mut hits: usize) -> (Vec<u8>, usize) {"
+        ));
+        assert!(example
+            .after
+            .contains("    let doubled;\n    (doubled, hits) = extracted(values, limit, hits);\n"));
+        assert!(example.after.contains("    (doubled, hits)\n}"));
+    }
+
+    #[test]
+    fn extract_function_carries_generics_mentioned_by_params() {
+        let source = r#"fn largest<T: PartialOrd + Copy>(items: &[T]) -> Option<T>
+where
+    T: std::fmt::Debug,
+{
+    let mut best = *items.first()?;
+    for &item in items {
+        if item > best { best = item; }
+    }
+    Some(best)
+}
+"#;
+        let example = extract(source, 6, 8);
+        assert!(example
+            .after
+            .contains("    best = extracted(items, best);\n"));
+        assert!(example.after.contains(
+            "fn extracted<T: PartialOrd + Copy>(items: &[T], mut best: _) -> _ where T: std::fmt::Debug, {"
+        ));
+    }
+
+    #[test]
+    fn extract_function_rejects_unsafe_selections() {
+        let early_return = r#"fn f(x: u32) -> u32 {
+    if x > 3 {
+        return 0;
+    }
+    x
+}
+"#;
+        let err = extract_rust_function(early_return, 2, 4, "g").unwrap_err();
+        assert!(err.to_string().contains("return"), "{}", err);
+
+        let unbalanced = r#"fn f(x: u32) -> u32 {
+    if x > 3 {
+        println!("big");
+    }
+    x
+}
+"#;
+        let err = extract_rust_function(unbalanced, 2, 3, "g").unwrap_err();
+        assert!(err.to_string().contains("whole statements"), "{}", err);
+
+        let borrow = r#"fn f(text: String) -> usize {
+    let words = text.split(' ');
+    words.count()
+}
+"#;
+        let err = extract_rust_function(borrow, 2, 2, "g").unwrap_err();
+        assert!(err.to_string().contains("borrows from `text`"), "{}", err);
+
+        let outside = "const X: u32 = 1;\nfn f() {}\n";
+        assert!(extract_rust_function(outside, 1, 1, "g").is_err());
+    }
+
+    #[test]
+    fn parsed_tokens_skip_strings_and_comments() {
+        let source = "fn f() { let s = \"a // b { \"; // c {\n/* d { */ let c = '{'; \
+                      let r = r#\"}\"#; 'a: loop { s::x += 1; } }";
+        let (file, tokens) = parse_rust(source).unwrap();
+        let texts: Vec<&str> = tokens.iter().map(|t| t.text.as_str()).collect();
+        assert_eq!(
+            texts,
+            vec![
+                "fn",
+                "f",
+                "(",
+                ")",
+                "{",
+                "let",
+                "s",
+                "=",
+                "\"a // b { \"",
+                ";",
+                "let",
+                "c",
+                "=",
+                "'{'",
+                ";",
+                "let",
+                "r",
+                "=",
+                "r#\"}\"#",
+                ";",
+                "'a",
+                ":",
+                "loop",
+                "{",
+                "s",
+                "::",
+                "x",
+                "+=",
+                "1",
+                ";",
+                "}",
+                "}"
+            ]
+        );
+        assert_eq!(tokens[10].line, 2);
+        assert_eq!(tokens[5].kind, TokKind::Keyword);
+        assert_eq!(tokens[20].kind, TokKind::Lifetime);
+        assert_eq!(tokens[23].close, Some(30));
+
+        let fns = fn_items(&file, &tokens);
+        assert_eq!(fns.len(), 1);
+        assert_eq!(
+            (fns[0].fn_tok, fns[0].body_open, fns[0].body_close),
+            (0, 4, 31)
+        );
+    }
+
+    // ── find_duplicates ──────────────────────────────────────────────────────
+
+    #[test]
+    fn find_duplicates_matches_renamed_copies_across_files() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("src/api")).unwrap();
+        std::fs::write(
+            dir.path().join("src/api/users.rs"),
+            r#"fn load_users(path: &str) -> Vec<String> {
+    let text = std::fs::read_to_string(path).unwrap_or_default();
+    let mut names = Vec::new();
+    for line in text.lines() {
+        if line.starts_with('#') {
+            continue;
+        }
+        names.push(line.trim().to_string());
+    }
+    names
+}
+"#,
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("src/api/groups.rs"),
+            r#"fn unrelated() -> u32 {
+    42
+}
+
+fn load_groups(file: &str) -> Vec<String> {
+    let data = std::fs::read_to_string(file).unwrap_or_default();
+    let mut groups = Vec::new();
+    for entry in data.lines() {
+        if entry.starts_with(';') { continue; }
+        groups.push(entry.trim().to_string());
+    }
+    groups
+}
+"#,
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("src/api/mod.rs"),
+            "mod groups;\nmod users;\n",
+        )
+        .unwrap();
+
+        let groups = RefactorAssistant::find_duplicates(dir.path(), 4);
+        assert_eq!(groups.len(), 1, "{:#?}", groups);
+        let group = &groups[0];
+        assert_eq!(group.locations.len(), 2);
+
+        let mut found: Vec<(String, usize, usize)> = group
+            .locations
+            .iter()
+            .map(|l| {
+                (
+                    l.item_name.clone().unwrap(),
+                    l.line_start.unwrap(),
+                    l.line_end.unwrap(),
+                )
+            })
+            .collect();
+        found.sort();
+        // `if line.starts_with(..) { continue; }` spans three lines in one
+        // copy and one in the other, so the blocks differ in line count.
+        assert_eq!(
+            found,
+            vec![
+                ("load_groups".to_string(), 6, 12),
+                ("load_users".to_string(), 2, 10),
+            ]
+        );
+        assert!(group.suggested_target.ends_with("src/api/mod.rs"));
+        assert!(group.snippet.contains("read_to_string"));
+        assert!(group.suggested_name.is_none());
+    }
+
+    #[test]
+    fn find_duplicates_respects_min_lines_and_structure() {
+        let files = vec![
+            (
+                "a.rs".to_string(),
+                "fn a(x: u32) -> u32 {\n    let y = x + 1;\n    let z = y * 2;\n    z - 3\n}\n"
+                    .to_string(),
+            ),
+            (
+                "a.rs".to_string(),
+                "fn b(p: u32) -> u32 {\n    let q = p + 7;\n    let r = q * 9;\n    r - 1\n}\n"
+                    .to_string(),
+            ),
+            (
+                "c.rs".to_string(),
+                "fn c(x: u32) -> u32 {\n    let y = x + 1;\n    let z = y.pow(2);\n    z - 3\n}\n"
+                    .to_string(),
+            ),
+        ];
+
+        let groups = find_duplicate_blocks(&files, 3);
+        assert_eq!(groups.len(), 1);
+        let names: Vec<_> = groups[0]
+            .locations
+            .iter()
+            .map(|l| l.item_name.as_deref().unwrap())
+            .collect();
+        assert_eq!(names, vec!["a", "b"]);
+        assert_eq!(groups[0].lines, 3);
+        assert_eq!(groups[0].suggested_target, "a.rs");
+
+        assert!(find_duplicate_blocks(&files, 4).is_empty());
+    }
+
+    #[test]
+    fn find_duplicates_ignores_comments_and_skips_unparsable_files() {
+        let files = vec![
+            (
+                "a.rs".to_string(),
+                "fn a(x: u32) -> u32 {\n    let y = x + 1; // bump\n    let z = y * 2;\n    z - 3\n}\n"
+                    .to_string(),
+            ),
+            (
+                "b.rs".to_string(),
+                "fn b(p: u32) -> u32 {\n    /* { */ let q = p + 7;\n    let r = q * 9;\n    r - 1\n}\n"
+                    .to_string(),
+            ),
+            (
+                "broken.rs".to_string(),
+                "fn c(x: u32) -> u32 {\n    let y = x + 1;\n    let z = y * 2;\n    z - 3\n"
+                    .to_string(),
+            ),
+        ];
+
+        let groups = find_duplicate_blocks(&files, 3);
+        assert_eq!(groups.len(), 1, "{:#?}", groups);
+        let files: Vec<_> = groups[0]
+            .locations
+            .iter()
+            .map(|l| l.file.as_str())
+            .collect();
+        assert_eq!(files, vec!["a.rs", "b.rs"]);
+    }
+
+    // ── effort calibration ───────────────────────────────────────────────────
+
+    fn smell(smell_type: CodeSmellType, severity: SmellSeverity) -> CodeSmell {
+        CodeSmell {
+            smell_type,
+            severity,
+            description: String::new(),
+            location: None,
+            impact: String::new(),
+        }
+    }
+
+    #[test]
+    fn effort_hours_round_trip() {
+        for effort in [
+            EffortEstimate::Trivial,
+            EffortEstimate::Small,
+            EffortEstimate::Medium,
+            EffortEstimate::Large,
+            EffortEstimate::VeryLarge,
+        ] {
+            assert_eq!(EffortEstimate::from_hours(effort.hours()), effort);
+        }
+    }
+
+    #[test]
+    fn heuristic_estimate_scales_with_severity() {
+        let long_fn = |s| EffortEstimate::for_smell(&smell(CodeSmellType::LongFunction, s));
+        assert_eq!(long_fn(SmellSeverity::Low), EffortEstimate::Trivial);
+        assert_eq!(long_fn(SmellSeverity::Medium), EffortEstimate::Small);
+        assert_eq!(long_fn(SmellSeverity::Critical), EffortEstimate::Medium);
+        assert_eq!(
+            EffortEstimate::for_smell(&smell(CodeSmellType::GodObject, SmellSeverity::Critical)),
+            EffortEstimate::VeryLarge
+        );
+    }
+
+    #[test]
+    fn calibration_needs_history() {
+        assert!(CalibrationFactor::from_history(CodeSmellType::LongFunction, &[]).is_none());
+        assert!(
+            CalibrationFactor::from_history(CodeSmellType::LongFunction, &[(0.0, 1.0)]).is_none()
+        );
+    }
+
+    #[test]
+    fn calibration_shrinks_single_data_points() {
+        // One task that took 16x the estimate only moves a quarter of the way
+        // (in log space): 16^(1/4) = 2.
+        let one =
+            CalibrationFactor::from_history(CodeSmellType::LongFunction, &[(1.0, 16.0)]).unwrap();
+        assert_eq!(one.samples, 1);
+        assert_eq!(one.observed_ratio, 16.0);
+        assert!((one.factor - 2.0).abs() < 1e-9, "{}", one.factor);
+
+        // Consistent history converges on the observed ratio...
+        let many = CalibrationFactor::from_history(CodeSmellType::LongFunction, &[(1.0, 2.0); 27])
+            .unwrap();
+        assert!(
+            (many.factor - 2f64.powf(0.9)).abs() < 1e-9,
+            "{}",
+            many.factor
+        );
+
+        // ...but is always capped.
+        let huge = CalibrationFactor::from_history(CodeSmellType::GodObject, &[(1.0, 1000.0); 50])
+            .unwrap();
+        assert_eq!(huge.factor, MAX_CALIBRATION_FACTOR);
+        let tiny =
+            CalibrationFactor::from_history(CodeSmellType::GodObject, &[(100.0, 1.0); 50]).unwrap();
+        assert_eq!(tiny.factor, 1.0 / MAX_CALIBRATION_FACTOR);
+
+        // A Small (1h) estimate doubled lands in Small still; tripled becomes Medium.
+        assert_eq!(
+            EffortEstimate::from_hours(EffortEstimate::Small.hours() * one.factor),
+            EffortEstimate::Small
+        );
+        assert_eq!(
+            EffortEstimate::from_hours(EffortEstimate::Small.hours() * huge.factor),
+            EffortEstimate::Medium
+        );
+    }
+}
@@ -272,7 +272,7 @@ impl RepoAnalyzer {
     }
 
     /// Detect programming language from file extension
-    fn detect_language(path: &Path) -> Option<String> {
+    pub(crate) fn detect_language(path: &Path) -> Option<String> {
         let extension = path.extension()?.to_str()?;
 
         let language = match extension.to_lowercase().as_str() {
@@ -23,6 +23,7 @@
 //! - Automatic cache miss/hit tracking
 //! - JSON storage for human readability
 //! - Repository-specific caching (no global cache pollution)
+//! - Optional LRU eviction with entry and byte caps ([`CacheLimits`])
 //!
 //! ## Usage
 //!
@@ -61,6 +62,7 @@ use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
 use tracing::{debug, info};
 
 /// Directory name for repo-level cache
@@ -76,6 +78,25 @@ pub enum CacheStrategy {
     Local,
 }
 
+/// Size limits for a repository cache; `None` means unbounded
+///
+/// When a `set` pushes the cache past a limit, least-recently-used entries
+/// are evicted until it fits again.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CacheLimits {
+    /// Maximum number of entries across all cache types
+    pub max_entries: Option<usize>,
+    /// Maximum total size of entry files in bytes
+    pub max_bytes: Option<u64>,
+}
+
+impl CacheLimits {
+    /// Whether any limit is configured
+    pub fn is_bounded(&self) -> bool {
+        self.max_entries.is_some() || self.max_bytes.is_some()
+    }
+}
+
 /// Compute stable hash of repository path
 fn compute_repo_hash(path: &Path) -> String {
     let canonical = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
@@ -154,6 +175,10 @@ pub struct RepoCacheEntry {
 
     /// Cache type
     pub cache_type: String,
+
+    /// Last time the entry was written or served (Unix microseconds)
+    #[serde(default)]
+    pub last_accessed: Option<i64>,
 }
 
 /// Default schema version
@@ -183,6 +208,22 @@ pub struct CacheSetParams<'a> {
     pub schema_version: Option<u32>,
 }
 
+/// All cache types, in subdirectory order
+const ALL_CACHE_TYPES: [CacheType; 4] = [
+    CacheType::Analysis,
+    CacheType::Docs,
+    CacheType::Refactor,
+    CacheType::Todos,
+];
+
+/// On-disk entry file, as seen by eviction
+struct EntryFile {
+    path: PathBuf,
+    bytes: u64,
+    last_accessed: i64,
+    type_index: usize,
+}
+
 /// Repository cache manager
 pub struct RepoCache {
     /// Cache directory (.rustassistant)
@@ -190,6 +231,12 @@ pub struct RepoCache {
 
     /// Whether cache is enabled
     enabled: bool,
+
+    /// Size limits enforced on `set`
+    limits: CacheLimits,
+
+    /// Entries evicted by this instance, per cache type
+    evictions: [AtomicU64; 4],
 }
 
 impl RepoCache {
@@ -228,6 +275,8 @@ impl RepoCache {
         let cache = Self {
             cache_dir,
             enabled: true,
+            limits: CacheLimits::default(),
+            evictions: Default::default(),
         };
 
         // Initialize cache structure
@@ -248,9 +297,22 @@ impl RepoCache {
         Self {
             cache_dir: PathBuf::new(),
             enabled: false,
+            limits: CacheLimits::default(),
+            evictions: Default::default(),
         }
     }
 
+    /// Bound the cache size, evicting least-recently-used entries on `set`
+    pub fn with_limits(mut self, limits: CacheLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Get the configured size limits
+    pub fn limits(&self) -> CacheLimits {
+        self.limits
+    }
+
     /// Check if cache is enabled
     pub fn is_enabled(&self) -> bool {
         self.enabled
@@ -339,9 +401,7 @@ Add to `.gitignore` if you prefer not to track cache files:
 
     /// Calculate SHA-256 hash of content
     fn hash_content(&self, content: &str) -> String {
-        let mut hasher = Sha256::new();
-        hasher.update(content.as_bytes());
-        format!("{:x}", hasher.finalize())
+        hash_source(content)
     }
 
     /// Compute multi-factor cache key
@@ -399,6 +459,30 @@ Add to `.gitignore` if you prefer not to track cache files:
         current_content: &str,
         model: Option<&str>,
         prompt_hash: Option<&str>,
+    ) -> anyhow::Result<Option<RepoCacheEntry>> {
+        let current_hash = self.hash_content(current_content);
+        self.get_validated(cache_type, file_path, &current_hash, model, prompt_hash)
+    }
+
+    /// Get a cached entry only if it was computed from content with
+    /// `current_hash` (see [`hash_source`]), for callers that already know
+    /// the hash and don't want to re-read the file
+    pub fn get_if_fresh(
+        &self,
+        cache_type: CacheType,
+        file_path: &str,
+        current_hash: &str,
+    ) -> anyhow::Result<Option<RepoCacheEntry>> {
+        self.get_validated(cache_type, file_path, current_hash, None, None)
+    }
+
+    fn get_validated(
+        &self,
+        cache_type: CacheType,
+        file_path: &str,
+        current_hash: &str,
+        model: Option<&str>,
+        prompt_hash: Option<&str>,
     ) -> anyhow::Result<Option<RepoCacheEntry>> {
         if !self.enabled {
             return Ok(None);
@@ -419,7 +503,6 @@ Add to `.gitignore` if you prefer not to track cache files:
         let entry: RepoCacheEntry = serde_json::from_str(&content)?;
 
         // Check if content hash matches
-        let current_hash = self.hash_content(current_content);
         if entry.file_hash != current_hash {
             debug!(
                 "Cache STALE (content changed): {} / {}",
@@ -462,7 +545,7 @@ Add to `.gitignore` if you prefer not to track cache files:
                 .unwrap_or_else(|| crate::prompt_hashes::get_prompt_hash_for_type(cache_type));
             let current_model = model.unwrap_or(&entry.model);
             let expected_key = self.compute_cache_key(
-                &current_hash,
+                current_hash,
                 current_model,
                 &current_prompt,
                 entry.schema_version,
@@ -479,6 +562,14 @@ Add to `.gitignore` if you prefer not to track cache files:
         }
 
         debug!("Cache HIT: {} / {}", cache_type.subdirectory(), file_path);
+
+        // Access times only matter for eviction, so skip the rewrite when
+        // the cache is unbounded
+        let mut entry = entry;
+        if self.limits.is_bounded() {
+            entry.last_accessed = Some(chrono::Utc::now().timestamp_micros());
+            fs::write(&cache_file, serde_json::to_string_pretty(&entry)?)?;
+        }
         Ok(Some(entry))
     }
 
@@ -514,6 +605,7 @@ Add to `.gitignore` if you prefer not to track cache files:
             tokens_used: params.tokens_used,
             file_size: params.content.len(),
             cache_type: params.cache_type.subdirectory().to_string(),
+            last_accessed: Some(chrono::Utc::now().timestamp_micros()),
         };
 
         let cache_file = self.cache_file_path(params.cache_type, params.file_path);
@@ -532,9 +624,151 @@ Add to `.gitignore` if you prefer not to track cache files:
             params.cache_type.subdirectory(),
             params.file_path
         );
+
+        if self.limits.is_bounded() {
+            self.enforce_limits(&cache_file)?;
+        }
         Ok(())
     }
 
+    /// List every entry file with its size and last access time
+    fn entry_files(&self) -> anyhow::Result<Vec<EntryFile>> {
+        let mut files = Vec::new();
+        for (type_index, cache_type) in ALL_CACHE_TYPES.iter().enumerate() {
+            let dir = self.cache_dir.join("cache").join(cache_type.subdirectory());
+            if !dir.exists() {
+                continue;
+            }
+            for entry in fs::read_dir(&dir)? {
+                let entry = entry?;
+                let path = entry.path();
+                if !entry.file_type()?.is_file() || path.extension().is_none_or(|e| e != "json") {
+                    continue;
+                }
+                let metadata = entry.metadata()?;
+                files.push(EntryFile {
+                    bytes: metadata.len(),
+                    last_accessed: entry_last_accessed(&path, &metadata),
+                    path,
+                    type_index,
+                });
+            }
+        }
+        Ok(files)
+    }
+
+    /// Evict least-recently-used entries until the configured limits hold,
+    /// never evicting `keep` (the entry just written)
+    fn enforce_limits(&self, keep: &Path) -> anyhow::Result<()> {
+        let files = self.entry_files()?;
+        let mut entries = files.len();
+        let mut bytes: u64 = files.iter().map(|f| f.bytes).sum();
+
+        let over = |entries: usize, bytes: u64| {
+            self.limits.max_entries.is_some_and(|max| entries > max)
+                || self.limits.max_bytes.is_some_and(|max| bytes > max)
+        };
+        if !over(entries, bytes) {
+            return Ok(());
+        }
+
+        for file in lru_order(files) {
+            if !over(entries, bytes) {
+                break;
+            }
+            if file.path == keep {
+                continue;
+            }
+            self.evict_file(&file)?;
+            entries -= 1;
+            bytes = bytes.saturating_sub(file.bytes);
+        }
+        Ok(())
+    }
+
+    fn evict_file(&self, file: &EntryFile) -> anyhow::Result<()> {
+        fs::remove_file(&file.path)?;
+        self.evictions[file.type_index].fetch_add(1, Ordering::Relaxed);
+        debug!("Cache EVICT: {}", file.path.display());
+        Ok(())
+    }
+
+    /// Evict least-recently-used entries until the cache takes at most
+    /// `target_bytes` on disk. Returns the number of entries evicted.
+    pub fn evict_to(&self, target_bytes: u64) -> anyhow::Result<usize> {
+        if !self.enabled {
+            return Ok(0);
+        }
+
+        let files = self.entry_files()?;
+        let mut bytes: u64 = files.iter().map(|f| f.bytes).sum();
+        let mut evicted = 0;
+
+        for file in lru_order(files) {
+            if bytes <= target_bytes {
+                break;
+            }
+            self.evict_file(&file)?;
+            bytes = bytes.saturating_sub(file.bytes);
+            evicted += 1;
+        }
+
+        if evicted > 0 {
+            info!("Evicted {} cache entries ({} bytes left)", evicted, bytes);
+        }
+        Ok(evicted)
+    }
+
+    /// Fraction of the tightest configured limit currently in use
+    fn utilization(&self) -> anyhow::Result<Option<f64>> {
+        if !self.limits.is_bounded() {
+            return Ok(None);
+        }
+        let files = self.entry_files()?;
+        let bytes: u64 = files.iter().map(|f| f.bytes).sum();
+
+        let by_entries = self
+            .limits
+            .max_entries
+            .map(|max| files.len() as f64 / max.max(1) as f64);
+        let by_bytes = self
+            .limits
+            .max_bytes
+            .map(|max| bytes as f64 / max.max(1) as f64);
+        Ok(by_entries.into_iter().chain(by_bytes).reduce(f64::max))
+    }
+
+    /// Drop every entry whose source file under `repo` is gone or no longer
+    /// hashes to the recorded `file_hash`. Returns the number dropped.
+    pub fn invalidate_changed(&self, repo: &Path) -> anyhow::Result<usize> {
+        if !self.enabled {
+            return Ok(0);
+        }
+
+        let mut removed = 0;
+        for file in self.entry_files()? {
+            let Ok(json) = fs::read_to_string(&file.path) else {
+                continue;
+            };
+            let Ok(entry) = serde_json::from_str::<RepoCacheEntry>(&json) else {
+                continue;
+            };
+
+            let fresh = fs::read_to_string(repo.join(&entry.file_path))
+                .is_ok_and(|content| hash_source(&content) == entry.file_hash);
+            if !fresh {
+                fs::remove_file(&file.path)?;
+                debug!("Cache INVALIDATE (source changed): {}", entry.file_path);
+                removed += 1;
+            }
+        }
+
+        if removed > 0 {
+            info!("Invalidated {} stale cache entries", removed);
+        }
+        Ok(removed)
+    }
+
     /// Clear all cache entries of a specific type
     pub fn clear_type(&self, cache_type: CacheType) -> anyhow::Result<usize> {
         if !self.enabled {
@@ -594,8 +828,14 @@ Add to `.gitignore` if you prefer not to track cache files:
             return Ok(CacheStats::default());
         }
 
+        let type_index = ALL_CACHE_TYPES
+            .iter()
+            .position(|t| *t == cache_type)
+            .unwrap_or(0);
         let mut stats = CacheStats {
             cache_type: cache_type.subdirectory().to_string(),
+            evictions: self.evictions[type_index].load(Ordering::Relaxed),
+            utilization: self.utilization()?,
             ..Default::default()
         };
 
@@ -734,6 +974,50 @@ pub struct CacheStats {
     pub total_tokens: usize,
     /// Total file size cached
     pub total_file_size: usize,
+    /// Entries evicted by this cache instance
+    #[serde(default)]
+    pub evictions: u64,
+    /// Cache-wide use of the tightest size limit (1.0 = full), if bounded
+    #[serde(default)]
+    pub utilization: Option<f64>,
+}
+
+/// Last access time of an entry file, falling back to its mtime for entries
+/// written before access times were recorded
+fn entry_last_accessed(path: &Path, metadata: &fs::Metadata) -> i64 {
+    #[derive(Deserialize)]
+    struct AccessOnly {
+        #[serde(default)]
+        last_accessed: Option<i64>,
+    }
+
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|content| serde_json::from_str::<AccessOnly>(&content).ok())
+        .and_then(|e| e.last_accessed)
+        .or_else(|| {
+            let modified = metadata.modified().ok()?;
+            let since_epoch = modified.duration_since(std::time::UNIX_EPOCH).ok()?;
+            Some(since_epoch.as_micros() as i64)
+        })
+        .unwrap_or(0)
+}
+
+/// SHA-256 hash of source content, as stored in [`RepoCacheEntry::file_hash`]
+pub fn hash_source(content: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(content.as_bytes());
+    format!("{:x}", hasher.finalize())
+}
+
+/// Sort entry files oldest-accessed first
+fn lru_order(mut files: Vec<EntryFile>) -> Vec<EntryFile> {
+    files.sort_by(|a, b| {
+        a.last_accessed
+            .cmp(&b.last_accessed)
+            .then_with(|| a.path.cmp(&b.path))
+    });
+    files
 }
 
 #[cfg(test)]
@@ -892,6 +1176,135 @@ mod tests {
         assert_eq!(removed, 1); // Only docs left
     }
 
+    fn entry_params<'a>(file_path: &'a str, content: &'a str) -> CacheSetParams<'a> {
+        CacheSetParams {
+            cache_type: CacheType::Refactor,
+            file_path,
+            content,
+            provider: "xai",
+            model: "grok-beta",
+            result: serde_json::json!({"score": 95}),
+            tokens_used: Some(10),
+            prompt_hash: None,
+            schema_version: None,
+        }
+    }
+
+    #[test]
+    fn test_lru_eviction_past_entry_cap() {
+        let temp = TempDir::new().unwrap();
+        let cache = RepoCache::new_with_strategy(temp.path(), CacheStrategy::Local)
+            .unwrap()
+            .with_limits(CacheLimits {
+                max_entries: Some(3),
+                max_bytes: None,
+            });
+
+        cache.set(entry_params("src/a.rs", "a")).unwrap();
+        cache.set(entry_params("src/b.rs", "b")).unwrap();
+        cache.set(entry_params("src/c.rs", "c")).unwrap();
+
+        // Touch a and c so b is the least recently used
+        assert!(cache
+            .get(CacheType::Refactor, "src/a.rs", "a")
+            .unwrap()
+            .is_some());
+        assert!(cache
+            .get(CacheType::Refactor, "src/c.rs", "c")
+            .unwrap()
+            .is_some());
+
+        cache.set(entry_params("src/d.rs", "d")).unwrap();
+
+        assert!(cache
+            .get(CacheType::Refactor, "src/b.rs", "b")
+            .unwrap()
+            .is_none());
+        for (file, content) in [("src/a.rs", "a"), ("src/c.rs", "c"), ("src/d.rs", "d")] {
+            assert!(
+                cache
+                    .get(CacheType::Refactor, file, content)
+                    .unwrap()
+                    .is_some(),
+                "{} should survive",
+                file
+            );
+        }
+
+        let stats = cache.stats(CacheType::Refactor).unwrap();
+        assert_eq!(stats.total_entries, 3);
+        assert_eq!(stats.evictions, 1);
+        assert_eq!(stats.utilization, Some(1.0));
+    }
+
+    #[test]
+    fn test_evict_to_target_bytes() {
+        let temp = TempDir::new().unwrap();
+        let cache = RepoCache::new_with_strategy(temp.path(), CacheStrategy::Local).unwrap();
+
+        cache.set(entry_params("src/a.rs", "a")).unwrap();
+        cache.set(entry_params("src/b.rs", "b")).unwrap();
+        cache.set(entry_params("src/c.rs", "c")).unwrap();
+        assert_eq!(cache.stats(CacheType::Refactor).unwrap().utilization, None);
+
+        let one_entry = cache.entry_files().unwrap()[0].bytes;
+        let evicted = cache.evict_to(one_entry).unwrap();
+        assert_eq!(evicted, 2);
+
+        // The newest entry is the one kept
+        assert!(cache
+            .get(CacheType::Refactor, "src/c.rs", "c")
+            .unwrap()
+            .is_some());
+        assert_eq!(cache.stats(CacheType::Refactor).unwrap().evictions, 2);
+        assert_eq!(cache.evict_to(0).unwrap(), 1);
+    }
+
+    #[test]
+    fn test_get_if_fresh_hash_mismatch() {
+        let temp = TempDir::new().unwrap();
+        let cache = RepoCache::new_with_strategy(temp.path(), CacheStrategy::Local).unwrap();
+        cache.set(entry_params("src/a.rs", "fn a() {}")).unwrap();
+
+        let entry = cache
+            .get_if_fresh(CacheType::Refactor, "src/a.rs", &hash_source("fn a() {}"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(entry.file_hash, hash_source("fn a() {}"));
+
+        assert!(cache
+            .get_if_fresh(
+                CacheType::Refactor,
+                "src/a.rs",
+                &hash_source("fn a() { 1 }")
+            )
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_invalidate_changed_sources() {
+        let temp = TempDir::new().unwrap();
+        let cache = RepoCache::new_with_strategy(temp.path(), CacheStrategy::Local).unwrap();
+        fs::create_dir_all(temp.path().join("src")).unwrap();
+        for (file, content) in [("src/a.rs", "fn a() {}"), ("src/b.rs", "fn b() {}")] {
+            fs::write(temp.path().join(file), content).unwrap();
+            cache.set(entry_params(file, content)).unwrap();
+        }
+        cache
+            .set(entry_params("src/gone.rs", "fn gone() {}"))
+            .unwrap();
+
+        fs::write(temp.path().join("src/b.rs"), "fn b() { changed() }").unwrap();
+
+        assert_eq!(cache.invalidate_changed(temp.path()).unwrap(), 2);
+        assert!(cache
+            .get(CacheType::Refactor, "src/a.rs", "fn a() {}")
+            .unwrap()
+            .is_some());
+        assert_eq!(cache.stats(CacheType::Refactor).unwrap().total_entries, 1);
+    }
+
     #[test]
     fn test_cache_stats() {
         let temp = TempDir::new().unwrap();
@@ -0,0 +1,365 @@
+//! LLM reranking of search results
+//!
+//! Fusion of semantic and keyword scores gets the right chunks into the top
+//! results but not always in the right order. The reranker sends the query
+//! and the top candidates' snippets to an LLM, which returns the candidates
+//! most relevant first.
+//!
+//! Reranking is opt-in per request (`rerank: true`) and only available when a
+//! model is configured:
+//!
+//! | Variable                          | Default                 |
+//! |-----------------------------------|-------------------------|
+//! | `RUSTASSISTANT_RERANK_MODEL`      | (none) — reranking off  |
+//! | `XAI_API_KEY`                     | (none) — reranking off  |
+//! | `RUSTASSISTANT_RERANK_URL`        | `https://api.x.ai/v1`   |
+//! | `RUSTASSISTANT_RERANK_CANDIDATES` | `20`, at most `50`      |
+//!
+//! Every call logs its token usage and cost. If the LLM is unreachable or
+//! its reply can't be parsed, the fusion order is returned unchanged.
+
+use crate::search::SearchResult;
+use anyhow::{Context, Result};
+use std::time::Duration;
+use tracing::{info, warn};
+
+/// Candidates sent to the LLM when not configured
+pub const DEFAULT_RERANK_CANDIDATES: usize = 20;
+
+/// Upper bound on candidates per call, to keep prompts and cost small
+pub const MAX_RERANK_CANDIDATES: usize = 50;
+
+/// Characters of each candidate's content included in the prompt
+const SNIPPET_CHARS: usize = 600;
+
+/// Grok 4.1 Fast pricing (per million tokens)
+const COST_PER_MILLION_INPUT: f64 = 0.20;
+const COST_PER_MILLION_OUTPUT: f64 = 0.50;
+
+/// Reranker settings
+#[derive(Debug, Clone)]
+pub struct RerankConfig {
+    pub model: String,
+    pub api_key: String,
+    /// OpenAI-compatible API base, without `/chat/completions`
+    pub base_url: String,
+    /// Candidates retrieved and sent to the LLM, capped at
+    /// [`MAX_RERANK_CANDIDATES`]
+    pub candidates: usize,
+    pub timeout: Duration,
+}
+
+impl RerankConfig {
+    pub fn new(model: impl Into<String>, api_key: impl Into<String>) -> Self {
+        Self {
+            model: model.into(),
+            api_key: api_key.into(),
+            base_url: "https://api.x.ai/v1".to_string(),
+            candidates: DEFAULT_RERANK_CANDIDATES,
+            timeout: Duration::from_secs(20),
+        }
+    }
+
+    /// Config from the environment, or `None` if no model or key is set
+    pub fn from_env() -> Option<Self> {
+        let model = std::env::var("RUSTASSISTANT_RERANK_MODEL")
+            .ok()
+            .filter(|m| !m.trim().is_empty())?;
+        let api_key = std::env::var("XAI_API_KEY")
+            .ok()
+            .filter(|k| !k.is_empty())?;
+
+        let mut config = Self::new(model, api_key);
+        if let Ok(url) = std::env::var("RUSTASSISTANT_RERANK_URL") {
+            config.base_url = url.trim_end_matches('/').to_string();
+        }
+        if let Some(candidates) = std::env::var("RUSTASSISTANT_RERANK_CANDIDATES")
+            .ok()
+            .and_then(|n| n.parse().ok())
+        {
+            config.candidates = candidates;
+        }
+        Some(config)
+    }
+
+    /// Candidates to retrieve for a request wanting `top_k` results
+    pub fn candidate_count(&self, top_k: usize) -> usize {
+        self.candidates.clamp(1, MAX_RERANK_CANDIDATES).max(top_k)
+    }
+}
+
+/// Result of a rerank call
+#[derive(Debug, Clone)]
+pub struct RerankOutcome {
+    pub results: Vec<SearchResult>,
+    /// Whether the LLM ordering was applied
+    pub reranked: bool,
+    pub cost_usd: f64,
+}
+
+/// Reorders search results with an LLM
+pub struct Reranker {
+    config: RerankConfig,
+    client: reqwest::Client,
+}
+
+impl Reranker {
+    pub fn new(config: RerankConfig) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(config.timeout)
+            .build()
+            .unwrap_or_default();
+        Self { config, client }
+    }
+
+    pub fn config(&self) -> &RerankConfig {
+        &self.config
+    }
+
+    /// Rerank `results` (in fusion order) and keep the best `top_k`.
+    ///
+    /// Only the first [`RerankConfig::candidate_count`] results are sent.
+    /// Failures are logged and fall back to the fusion order.
+    pub async fn rerank(
+        &self,
+        query: &str,
+        mut results: Vec<SearchResult>,
+        top_k: usize,
+    ) -> RerankOutcome {
+        results.truncate(self.config.candidate_count(top_k));
+        if results.len() < 2 {
+            results.truncate(top_k);
+            return RerankOutcome {
+                results,
+                reranked: false,
+                cost_usd: 0.0,
+            };
+        }
+
+        match self.request_ordering(query, &results).await {
+            Ok((order, cost_usd)) => RerankOutcome {
+                results: apply_ordering(results, &order, top_k),
+                reranked: true,
+                cost_usd,
+            },
+            Err(e) => {
+                warn!(
+                    model = %self.config.model,
+                    error = %format!("{:#}", e),
+                    "Rerank failed; keeping fusion order"
+                );
+                results.truncate(top_k);
+                RerankOutcome {
+                    results,
+                    reranked: false,
+                    cost_usd: 0.0,
+                }
+            }
+        }
+    }
+
+    /// Ask the LLM for an ordering; returns it with the call's cost
+    async fn request_ordering(
+        &self,
+        query: &str,
+        candidates: &[SearchResult],
+    ) -> Result<(Vec<usize>, f64)> {
+        let body = serde_json::json!({
+            "model": self.config.model,
+            "messages": [
+                {
+                    "role": "system",
+                    "content": "You rank search results by relevance to a query. \
+                                Reply with only a JSON array of candidate numbers, \
+                                most relevant first."
+                },
+                { "role": "user", "content": build_prompt(query, candidates) }
+            ],
+            "temperature": 0.0,
+            "max_tokens": 16 + candidates.len() * 4,
+        });
+
+        let response = self
+            .client
+            .post(format!("{}/chat/completions", self.config.base_url))
+            .header("Authorization", format!("Bearer {}", self.config.api_key))
+            .json(&body)
+            .send()
+            .await
+            .context("Rerank request failed")?;
+        let status = response.status();
+        if !status.is_success() {
+            let text = response.text().await.unwrap_or_default();
+            anyhow::bail!("Rerank API error {}: {}", status, text);
+        }
+        let json: serde_json::Value = response.json().await.context("Invalid rerank response")?;
+
+        let prompt_tokens = json["usage"]["prompt_tokens"].as_u64().unwrap_or(0);
+        let completion_tokens = json["usage"]["completion_tokens"].as_u64().unwrap_or(0);
+        let cost_usd = (prompt_tokens as f64 / 1_000_000.0) * COST_PER_MILLION_INPUT
+            + (completion_tokens as f64 / 1_000_000.0) * COST_PER_MILLION_OUTPUT;
+        info!(
+            model = %self.config.model,
+            candidates = candidates.len(),
+            prompt_tokens,
+            completion_tokens,
+            cost_usd,
+            "Reranked search results"
+        );
+
+        let reply = json["choices"][0]["message"]["content"]
+            .as_str()
+            .context("No content in rerank response")?;
+        let order = parse_ordering(reply, candidates.len())
+            .with_context(|| format!("Unparseable rerank reply: {}", reply))?;
+        Ok((order, cost_usd))
+    }
+}
+
+/// The query followed by numbered candidate snippets
+pub fn build_prompt(query: &str, candidates: &[SearchResult]) -> String {
+    let mut prompt = format!("Query: {}\n\nCandidates:\n", query);
+    for (i, candidate) in candidates.iter().enumerate() {
+        let snippet: String = candidate.content.chars().take(SNIPPET_CHARS).collect();
+        let title = candidate.title.as_deref().unwrap_or("untitled");
+        prompt.push_str(&format!("\n[{}] {}\n{}\n", i + 1, title, snippet.trim()));
+    }
+    prompt.push_str(&format!(
+        "\nReturn all {} candidate numbers as a JSON array, most relevant first.",
+        candidates.len()
+    ));
+    prompt
+}
+
+/// Zero-based candidate order from a reply such as `[3, 1, 2]`.
+///
+/// Numbers are one-based in the reply. Duplicates and out-of-range numbers
+/// are dropped, and candidates the LLM left out follow in fusion order.
+/// `None` if the reply has no JSON array of numbers.
+pub fn parse_ordering(reply: &str, candidate_count: usize) -> Option<Vec<usize>> {
+    let start = reply.find('[')?;
+    let end = start + reply[start..].find(']')?;
+    let ranked: Vec<serde_json::Value> = serde_json::from_str(&reply[start..=end]).ok()?;
+
+    let mut order = Vec::with_capacity(candidate_count);
+    for value in ranked {
+        let number = value
+            .as_u64()
+            .or_else(|| value.as_str().and_then(|s| s.trim().parse().ok()));
+        let Some(index) = number.and_then(|n| (n as usize).checked_sub(1)) else {
+            continue;
+        };
+        if index < candidate_count && !order.contains(&index) {
+            order.push(index);
+        }
+    }
+    if order.is_empty() {
+        return None;
+    }
+    for index in 0..candidate_count {
+        if !order.contains(&index) {
+            order.push(index);
+        }
+    }
+    Some(order)
+}
+
+/// Reorder `results` by `order` and keep the first `top_k`
+pub fn apply_ordering(
+    results: Vec<SearchResult>,
+    order: &[usize],
+    top_k: usize,
+) -> Vec<SearchResult> {
+    let mut slots: Vec<Option<SearchResult>> = results.into_iter().map(Some).collect();
+    order
+        .iter()
+        .filter_map(|&i| slots.get_mut(i).and_then(Option::take))
+        .take(top_k)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search::SearchResultMetadata;
+
+    fn result(chunk_id: &str, content: &str) -> SearchResult {
+        SearchResult {
+            document_id: "doc".to_string(),
+            chunk_id: chunk_id.to_string(),
+            chunk_index: 0,
+            content: content.to_string(),
+            score: 0.5,
+            title: Some(format!("Title {}", chunk_id)),
+            doc_type: None,
+            tags: None,
+            matched_tags: Vec::new(),
+            heading: None,
+            char_start: 0,
+            char_end: 0,
+            metadata: SearchResultMetadata {
+                model: "test".to_string(),
+                dimension: 0,
+                semantic_match: true,
+                keyword_match: false,
+                semantic_score: None,
+                keyword_score: None,
+            },
+        }
+    }
+
+    fn ids(results: &[SearchResult]) -> Vec<&str> {
+        results.iter().map(|r| r.chunk_id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_parse_and_apply_ordering() {
+        assert_eq!(parse_ordering("[3, 1, 2]", 3), Some(vec![2, 0, 1]));
+        // Prose around the array, duplicates, strings, out-of-range and
+        // missing numbers
+        assert_eq!(
+            parse_ordering("Ranking: [\"2\", 2, 9, 4] done", 4),
+            Some(vec![1, 3, 0, 2])
+        );
+        assert_eq!(parse_ordering("no idea", 3), None);
+        assert_eq!(parse_ordering("[0]", 3), None);
+        assert_eq!(parse_ordering("[]", 3), None);
+
+        let results = vec![result("a", ""), result("b", ""), result("c", "")];
+        let reranked = apply_ordering(results, &[2, 0, 1], 2);
+        assert_eq!(ids(&reranked), vec!["c", "a"]);
+    }
+
+    #[test]
+    fn test_prompt_and_candidate_cap() {
+        let long = "x".repeat(SNIPPET_CHARS * 2);
+        let prompt = build_prompt("async traits", &[result("a", "first"), result("b", &long)]);
+        assert!(prompt.starts_with("Query: async traits"));
+        assert!(prompt.contains("[1] Title a\nfirst"));
+        assert!(prompt.contains("[2] Title b"));
+        assert!(!prompt.contains(&long));
+        assert!(prompt.contains("all 2 candidate numbers"));
+
+        let mut config = RerankConfig::new("grok-4-1-fast", "key");
+        assert_eq!(config.candidate_count(5), DEFAULT_RERANK_CANDIDATES);
+        config.candidates = 500;
+        assert_eq!(config.candidate_count(5), MAX_RERANK_CANDIDATES);
+        // Never fewer candidates than requested results
+        config.candidates = 3;
+        assert_eq!(config.candidate_count(10), 10);
+    }
+
+    #[tokio::test]
+    async fn test_unreachable_llm_keeps_fusion_order() {
+        let mut config = RerankConfig::new("grok-4-1-fast", "key");
+        config.base_url = "http://127.0.0.1:9".to_string();
+        config.timeout = Duration::from_secs(2);
+        let reranker = Reranker::new(config);
+
+        let results = vec![result("a", "one"), result("b", "two"), result("c", "three")];
+        let outcome = reranker.rerank("query", results, 2).await;
+        assert!(!outcome.reranked);
+        assert_eq!(outcome.cost_usd, 0.0);
+        assert_eq!(ids(&outcome.results), vec!["a", "b"]);
+    }
+}
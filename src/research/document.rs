@@ -0,0 +1,594 @@
+//! Document Analysis
+//!
+//! Breaks a research document (whitepaper, spec, notes) into a summary,
+//! key points and tasks. Documents larger than one LLM call are split on
+//! heading boundaries, analyzed chunk by chunk, and merged back together.
+
+use super::graph::ResearchTask;
+use crate::llm::GrokClient;
+use crate::parser::{canonical_language, extract_code_blocks};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Default chunk budget, leaving room for the prompt and the response
+pub const DEFAULT_MAX_CHUNK_TOKENS: usize = 6000;
+
+/// Rough token estimate, matching the research workers
+fn estimate_tokens(text: &str) -> usize {
+    text.len() / 4
+}
+
+// ============================================================================
+// Chunking
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DocumentChunk {
+    pub index: usize,
+    /// Heading of the first section in the chunk
+    pub section: String,
+    pub content: String,
+    pub estimated_tokens: usize,
+}
+
+/// A heading and the lines under it, up to the next heading
+struct Section {
+    heading: Option<String>,
+    text: String,
+}
+
+/// Split at ATX headings (`#`..`######`), ignoring `#` lines inside fenced
+/// code blocks
+fn split_sections(content: &str) -> Vec<Section> {
+    let fenced: Vec<(usize, usize)> = extract_code_blocks(content)
+        .into_iter()
+        .filter(|block| block.fenced)
+        .map(|block| (block.start_line, block.end_line))
+        .collect();
+    let in_fence = |line_no: usize| {
+        fenced
+            .iter()
+            .any(|&(start, end)| (start..=end).contains(&line_no))
+    };
+
+    let mut sections = Vec::new();
+    let mut current = Section {
+        heading: None,
+        text: String::new(),
+    };
+
+    for (i, line) in content.lines().enumerate() {
+        if let Some(title) = heading_title(line).filter(|_| !in_fence(i + 1)) {
+            if current.heading.is_some() || !current.text.trim().is_empty() {
+                sections.push(current);
+            }
+            current = Section {
+                heading: Some(title.to_string()),
+                text: String::new(),
+            };
+        }
+        current.text.push_str(line);
+        current.text.push('\n');
+    }
+
+    if current.heading.is_some() || !current.text.trim().is_empty() {
+        sections.push(current);
+    }
+    sections
+}
+
+fn heading_title(line: &str) -> Option<&str> {
+    let hashes = line.chars().take_while(|&c| c == '#').count();
+    if !(1..=6).contains(&hashes) {
+        return None;
+    }
+    let rest = &line[hashes..];
+    if !rest.is_empty() && !rest.starts_with(' ') {
+        return None;
+    }
+    Some(rest.trim().trim_end_matches('#').trim())
+}
+
+/// Break an oversized section into pieces under `max_tokens`, on paragraph
+/// boundaries where possible. Continuation pieces repeat the heading so
+/// each chunk keeps its context.
+fn split_oversized(section: &Section, max_tokens: usize) -> Vec<String> {
+    let max_bytes = max_tokens * 4;
+    let heading_line = section
+        .text
+        .lines()
+        .next()
+        .filter(|_| section.heading.is_some())
+        .map(|l| format!("{}\n", l));
+    let body = match &heading_line {
+        Some(h) => &section.text[h.len().min(section.text.len())..],
+        None => section.text.as_str(),
+    };
+
+    let mut pieces = Vec::new();
+    let mut current = heading_line.clone().unwrap_or_default();
+    let prefix_len = current.len();
+
+    let flush = |current: &mut String, pieces: &mut Vec<String>| {
+        if current.len() > prefix_len {
+            pieces.push(std::mem::take(current));
+            *current = heading_line.clone().unwrap_or_default();
+        }
+    };
+
+    for paragraph in body.split_inclusive("\n\n") {
+        if current.len() + paragraph.len() > max_bytes {
+            flush(&mut current, &mut pieces);
+        }
+        if prefix_len + paragraph.len() <= max_bytes {
+            current.push_str(paragraph);
+            continue;
+        }
+        // A single paragraph over budget: hard-split on char boundaries
+        let room = max_bytes.saturating_sub(prefix_len).max(1);
+        let mut rest = paragraph;
+        while !rest.is_empty() {
+            let mut cut = room.min(rest.len());
+            while !rest.is_char_boundary(cut) {
+                cut -= 1;
+            }
+            if cut == 0 {
+                cut = rest.chars().next().map_or(rest.len(), char::len_utf8);
+            }
+            current.push_str(&rest[..cut]);
+            flush(&mut current, &mut pieces);
+            rest = &rest[cut..];
+        }
+    }
+    flush(&mut current, &mut pieces);
+    pieces
+}
+
+/// Split a document into chunks of at most `max_chunk_tokens` (estimated),
+/// packing whole sections together and only splitting inside a section
+/// when it doesn't fit on its own
+pub fn split_document(content: &str, max_chunk_tokens: usize) -> Vec<DocumentChunk> {
+    let max_chunk_tokens = max_chunk_tokens.max(1);
+    let mut raw: Vec<(String, String)> = Vec::new();
+    let mut current: Option<(String, String)> = None;
+
+    for section in split_sections(content) {
+        let title = section
+            .heading
+            .clone()
+            .unwrap_or_else(|| "(preamble)".to_string());
+
+        if estimate_tokens(&section.text) > max_chunk_tokens {
+            raw.extend(current.take());
+            for piece in split_oversized(&section, max_chunk_tokens) {
+                raw.push((title.clone(), piece));
+            }
+            continue;
+        }
+
+        match &mut current {
+            Some((_, text))
+                if estimate_tokens(text) + estimate_tokens(&section.text) <= max_chunk_tokens =>
+            {
+                text.push_str(&section.text);
+            }
+            _ => {
+                raw.extend(current.take());
+                current = Some((title, section.text));
+            }
+        }
+    }
+    raw.extend(current);
+
+    raw.into_iter()
+        .enumerate()
+        .map(|(index, (section, content))| DocumentChunk {
+            index,
+            section,
+            estimated_tokens: estimate_tokens(&content),
+            content,
+        })
+        .collect()
+}
+
+// ============================================================================
+// Breakdown
+// ============================================================================
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ResearchBreakdown {
+    pub title: String,
+    pub summary: String,
+    pub key_points: Vec<String>,
+    pub tasks: Vec<ResearchTask>,
+
+    /// Section headings covered, in document order
+    #[serde(default)]
+    pub sections: Vec<String>,
+
+    /// Estimated tokens across every LLM call that went into this breakdown
+    #[serde(default)]
+    pub total_tokens: i64,
+
+    /// Number of chunks analyzed
+    #[serde(default)]
+    pub chunks: usize,
+}
+
+impl ResearchBreakdown {
+    /// Merge per-chunk breakdowns, given in document order. Key points and
+    /// tasks repeated across chunks are kept once, at their first position;
+    /// duplicate tasks pool their dependencies.
+    pub fn merge(title: impl Into<String>, parts: Vec<ResearchBreakdown>) -> Self {
+        let key = |s: &str| s.trim().to_lowercase();
+        let mut merged = ResearchBreakdown {
+            title: title.into(),
+            ..Default::default()
+        };
+        let mut summaries = Vec::new();
+        let mut task_index: HashMap<String, usize> = HashMap::new();
+
+        for part in parts {
+            if !part.summary.trim().is_empty() {
+                summaries.push(part.summary.trim().to_string());
+            }
+            for point in part.key_points {
+                if !merged.key_points.iter().any(|p| key(p) == key(&point)) {
+                    merged.key_points.push(point);
+                }
+            }
+            for section in part.sections {
+                if merged.sections.last() != Some(&section) {
+                    merged.sections.push(section);
+                }
+            }
+            for task in part.tasks {
+                match task_index.get(&key(&task.title)) {
+                    Some(&i) => {
+                        let existing = &mut merged.tasks[i];
+                        for dep in task.dependencies {
+                            if !existing.dependencies.iter().any(|d| key(d) == key(&dep)) {
+                                existing.dependencies.push(dep);
+                            }
+                        }
+                        if task.description.len() > existing.description.len() {
+                            existing.description = task.description;
+                        }
+                        if existing.language.is_none() {
+                            existing.language = task.language;
+                        }
+                    }
+                    None => {
+                        task_index.insert(key(&task.title), merged.tasks.len());
+                        merged.tasks.push(task);
+                    }
+                }
+            }
+            merged.total_tokens += part.total_tokens;
+            merged.chunks += part.chunks;
+        }
+
+        merged.summary = summaries.join("\n\n");
+        merged
+    }
+
+    /// Format as markdown
+    pub fn to_markdown(&self) -> String {
+        let mut md = String::new();
+
+        md.push_str(&format!("# Research Breakdown: {}\n\n", self.title));
+        md.push_str(&format!(
+            "**Chunks:** {} | **Tokens:** {}\n\n",
+            self.chunks, self.total_tokens
+        ));
+
+        md.push_str("## Summary\n\n");
+        md.push_str(&self.summary);
+        md.push_str("\n\n");
+
+        if !self.key_points.is_empty() {
+            md.push_str("## Key Points\n\n");
+            for point in &self.key_points {
+                md.push_str(&format!("- {}\n", point));
+            }
+            md.push('\n');
+        }
+
+        if !self.tasks.is_empty() {
+            md.push_str("## Tasks\n\n");
+            for task in &self.tasks {
+                md.push_str(&format!("- **{}**", task.title));
+                if let Some(language) = &task.language {
+                    md.push_str(&format!(" `{}`", language));
+                }
+                if !task.description.is_empty() {
+                    md.push_str(&format!(": {}", task.description));
+                }
+                if !task.dependencies.is_empty() {
+                    md.push_str(&format!(" _(after: {})_", task.dependencies.join(", ")));
+                }
+                md.push('\n');
+            }
+            md.push('\n');
+        }
+
+        md
+    }
+}
+
+// ============================================================================
+// Analysis
+// ============================================================================
+
+/// Analyze a research document, chunking it if it exceeds `max_chunk_tokens`
+pub async fn analyze_document(
+    llm: &GrokClient,
+    title: &str,
+    content: &str,
+    max_chunk_tokens: usize,
+) -> Result<ResearchBreakdown> {
+    let chunks = split_document(content, max_chunk_tokens);
+    let mut parts = Vec::with_capacity(chunks.len());
+
+    for chunk in &chunks {
+        let part = analyze_chunk(llm, title, chunk, chunks.len())
+            .await
+            .with_context(|| {
+                format!(
+                    "Failed to analyze chunk {} ({})",
+                    chunk.index + 1,
+                    chunk.section
+                )
+            })?;
+        parts.push(part);
+    }
+
+    Ok(ResearchBreakdown::merge(title, parts))
+}
+
+/// Read and analyze a research document from disk
+pub async fn analyze_file(
+    llm: &GrokClient,
+    path: impl AsRef<Path>,
+    max_chunk_tokens: usize,
+) -> Result<ResearchBreakdown> {
+    let path = path.as_ref();
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let title = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| path.display().to_string());
+
+    analyze_document(llm, &title, &content, max_chunk_tokens).await
+}
+
+async fn analyze_chunk(
+    llm: &GrokClient,
+    title: &str,
+    chunk: &DocumentChunk,
+    total_chunks: usize,
+) -> Result<ResearchBreakdown> {
+    let prompt = format!(
+        r#"Break down this research document into actionable work.
+
+Document: {title}
+Part {part} of {total}
+
+---
+{content}
+---
+
+Respond in this exact JSON format:
+{{
+    "summary": "1-2 paragraph summary of this part",
+    "key_points": ["point 1", "point 2", "..."],
+    "tasks": [
+        {{"title": "short task title", "description": "what to do", "dependencies": ["title of a task that must come first"], "language": "language of the code it involves, if any"}}
+    ]
+}}
+
+Only reference dependencies by the exact title of another task."#,
+        title = title,
+        part = chunk.index + 1,
+        total = total_chunks,
+        content = chunk.content,
+    );
+
+    let response = llm.generate(&prompt, 4096).await?;
+    let tokens = estimate_tokens(&prompt) + estimate_tokens(&response);
+
+    #[derive(Deserialize)]
+    struct ChunkResponse {
+        #[serde(default)]
+        summary: String,
+        #[serde(default)]
+        key_points: Vec<String>,
+        #[serde(default)]
+        tasks: Vec<ResearchTask>,
+    }
+
+    let parsed: ChunkResponse = serde_json::from_str(&response)
+        .or_else(|_| {
+            let start = response.find('{').unwrap_or(0);
+            let end = response.rfind('}').map(|i| i + 1).unwrap_or(response.len());
+            serde_json::from_str(&response[start..end])
+        })
+        .unwrap_or_else(|_| ChunkResponse {
+            summary: response.clone(),
+            key_points: vec![],
+            tasks: vec![],
+        });
+
+    let mut tasks = parsed.tasks;
+    assign_languages(&mut tasks, &chunk.content);
+
+    let mut sections: Vec<String> = split_sections(&chunk.content)
+        .into_iter()
+        .filter_map(|s| s.heading)
+        .collect();
+    if sections.is_empty() {
+        sections.push(chunk.section.clone());
+    }
+
+    Ok(ResearchBreakdown {
+        title: title.to_string(),
+        summary: parsed.summary,
+        key_points: parsed.key_points,
+        tasks,
+        sections,
+        total_tokens: tokens as i64,
+        chunks: 1,
+    })
+}
+
+/// Canonicalize the languages the LLM gave, and give tasks without one the
+/// language of the chunk's code blocks (the most common, first on a tie)
+fn assign_languages(tasks: &mut [ResearchTask], content: &str) {
+    let mut counts: Vec<(String, usize)> = Vec::new();
+    for language in extract_code_blocks(content)
+        .into_iter()
+        .filter_map(|block| block.language)
+    {
+        match counts.iter_mut().find(|(l, _)| *l == language) {
+            Some((_, n)) => *n += 1,
+            None => counts.push((language, 1)),
+        }
+    }
+    // max_by_key keeps the last maximum, so walk backwards to prefer the first
+    let dominant = counts
+        .iter()
+        .rev()
+        .max_by_key(|(_, n)| *n)
+        .map(|(l, _)| l.clone());
+
+    for task in tasks {
+        task.language = match task.language.as_deref().map(str::trim) {
+            Some(l) if !l.is_empty() => Some(canonical_language(l)),
+            _ => dominant.clone(),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_document_on_headings() {
+        let doc = "Intro text.\n\n# One\n\nalpha\n\n```sh\n# not a heading\n```\n\n## Two\n\nbeta\n\n# Three\n\ngamma\n";
+
+        // Everything fits: one chunk
+        let chunks = split_document(doc, 1000);
+        assert_eq!(chunks.len(), 1);
+        assert_eq!(chunks[0].content, doc);
+
+        // Tight budget: sections pack while they fit, the code fence stays in "One"
+        let chunks = split_document(doc, 12);
+        let sections: Vec<_> = chunks.iter().map(|c| c.section.as_str()).collect();
+        assert_eq!(sections, vec!["(preamble)", "One", "Two"]);
+        assert!(chunks[1].content.contains("# not a heading"));
+        assert!(chunks[2].content.contains("# Three"));
+        assert_eq!(
+            chunks
+                .iter()
+                .map(|c| c.content.as_str())
+                .collect::<String>(),
+            doc
+        );
+    }
+
+    #[test]
+    fn test_longer_fence_is_not_closed_by_shorter_one() {
+        let doc = "# Guide\n\n````md\n```\n# inside\n```\n````\n\n# After\n\ntext\n";
+        let headings: Vec<_> = split_sections(doc)
+            .into_iter()
+            .filter_map(|s| s.heading)
+            .collect();
+        assert_eq!(headings, vec!["Guide", "After"]);
+    }
+
+    #[test]
+    fn test_split_oversized_section_keeps_heading() {
+        let para = "word ".repeat(30);
+        let doc = format!("# Big\n\n{p}\n\n{p}\n\n{p}\n", p = para.trim());
+        let chunks = split_document(&doc, 50);
+
+        assert!(chunks.len() >= 3);
+        for chunk in &chunks {
+            assert_eq!(chunk.section, "Big");
+            assert!(chunk.content.starts_with("# Big\n"));
+            assert!(chunk.estimated_tokens <= 50);
+        }
+    }
+
+    #[test]
+    fn test_merge_dedups_and_sums_tokens() {
+        let part = |summary: &str, sections: &[&str], tasks: Vec<ResearchTask>, tokens| {
+            ResearchBreakdown {
+                summary: summary.to_string(),
+                key_points: vec!["Shared point".to_string()],
+                sections: sections.iter().map(|s| s.to_string()).collect(),
+                tasks,
+                total_tokens: tokens,
+                chunks: 1,
+                ..Default::default()
+            }
+        };
+        let merged = ResearchBreakdown::merge(
+            "paper",
+            vec![
+                part(
+                    "first",
+                    &["Intro", "Design"],
+                    vec![ResearchTask::new("Prototype"), ResearchTask::new("Survey")],
+                    100,
+                ),
+                part(
+                    "second",
+                    &["Design", "Evaluation"],
+                    vec![
+                        ResearchTask::new("prototype").depends_on("Survey"),
+                        ResearchTask::new("Benchmark").depends_on("Prototype"),
+                    ],
+                    250,
+                ),
+            ],
+        );
+
+        assert_eq!(merged.summary, "first\n\nsecond");
+        assert_eq!(merged.key_points, vec!["Shared point"]);
+        assert_eq!(merged.sections, vec!["Intro", "Design", "Evaluation"]);
+        let titles: Vec<_> = merged.tasks.iter().map(|t| t.title.as_str()).collect();
+        assert_eq!(titles, vec!["Prototype", "Survey", "Benchmark"]);
+        assert_eq!(merged.tasks[0].dependencies, vec!["Survey"]);
+        assert_eq!(merged.total_tokens, 350);
+        assert_eq!(merged.chunks, 2);
+    }
+
+    #[test]
+    fn test_tasks_take_language_from_code_blocks() {
+        let content =
+            "# Port\n\n```py\nprint(1)\n```\n\n```rs\nfn a() {}\n```\n\n```rust\nfn b() {}\n```\n";
+        let mut tasks = vec![
+            ResearchTask::new("Port parser"),
+            ResearchTask::new("Script"),
+        ];
+        tasks[1].language = Some("Python3".to_string());
+        assign_languages(&mut tasks, content);
+
+        assert_eq!(tasks[0].language.as_deref(), Some("rust"));
+        assert_eq!(tasks[1].language.as_deref(), Some("python"));
+
+        // Ties go to the first block; no code blocks leaves the task untargeted
+        let mut tasks = vec![ResearchTask::new("Port")];
+        assign_languages(&mut tasks, "```ts\nx\n```\n```go\ny\n```\n");
+        assert_eq!(tasks[0].language.as_deref(), Some("typescript"));
+
+        let mut tasks = vec![ResearchTask::new("Read")];
+        assign_languages(&mut tasks, "Just prose.\n");
+        assert_eq!(tasks[0].language, None);
+    }
+}
@@ -0,0 +1,345 @@
+//! Research Task Graph
+//!
+//! Turns the `dependencies` of research tasks into a graph: cycle detection,
+//! a suggested execution order, and a Mermaid diagram for visualization.
+
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::Entry;
+use std::collections::{BTreeSet, HashMap};
+
+// ============================================================================
+// Task Model
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResearchTask {
+    pub title: String,
+    #[serde(default)]
+    pub description: String,
+
+    /// Titles of tasks that must be done first
+    #[serde(default)]
+    pub dependencies: Vec<String>,
+
+    /// Language the task targets, canonicalized (`rs` -> `rust`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+}
+
+impl ResearchTask {
+    pub fn new(title: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            description: String::new(),
+            dependencies: Vec::new(),
+            language: None,
+        }
+    }
+
+    pub fn depends_on(mut self, title: impl Into<String>) -> Self {
+        self.dependencies.push(title.into());
+        self
+    }
+}
+
+// ============================================================================
+// Task Graph
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskGraph {
+    /// Task titles, indexed in input order
+    pub nodes: Vec<String>,
+
+    /// `(dependency, dependent)` index pairs
+    pub edges: Vec<(usize, usize)>,
+
+    /// Suggested execution order (dependencies first)
+    pub order: Vec<usize>,
+
+    /// Dependency cycles, each as a list of node indices
+    pub cycles: Vec<Vec<usize>>,
+
+    /// Tasks that can't be scheduled because they are in or behind a cycle
+    pub blocked: Vec<usize>,
+
+    /// Unknown or duplicate task references
+    pub warnings: Vec<String>,
+}
+
+/// Build the dependency graph for a set of tasks. Dependencies are matched
+/// against task titles case-insensitively; unknown names become warnings.
+pub fn build_dependency_graph(tasks: &[ResearchTask]) -> TaskGraph {
+    let key = |s: &str| s.trim().to_lowercase();
+
+    let mut index: HashMap<String, usize> = HashMap::new();
+    let mut warnings = Vec::new();
+    for (i, task) in tasks.iter().enumerate() {
+        match index.entry(key(&task.title)) {
+            Entry::Vacant(e) => {
+                e.insert(i);
+            }
+            Entry::Occupied(_) => warnings.push(format!(
+                "Duplicate task title \"{}\"; dependencies resolve to the first one",
+                task.title
+            )),
+        }
+    }
+
+    let mut edges = BTreeSet::new();
+    for (i, task) in tasks.iter().enumerate() {
+        for dep in &task.dependencies {
+            match index.get(&key(dep)) {
+                Some(&d) => {
+                    edges.insert((d, i));
+                }
+                None => warnings.push(format!(
+                    "Task \"{}\" depends on unknown task \"{}\"",
+                    task.title, dep
+                )),
+            }
+        }
+    }
+    let edges: Vec<_> = edges.into_iter().collect();
+
+    let n = tasks.len();
+    let mut successors = vec![Vec::new(); n];
+    let mut in_degree = vec![0usize; n];
+    for &(from, to) in &edges {
+        successors[from].push(to);
+        in_degree[to] += 1;
+    }
+
+    // Kahn's algorithm, always taking the earliest ready task so the order
+    // stays close to the original one
+    let mut ready: BTreeSet<usize> = (0..n).filter(|&i| in_degree[i] == 0).collect();
+    let mut order = Vec::with_capacity(n);
+    while let Some(next) = ready.pop_first() {
+        order.push(next);
+        for &succ in &successors[next] {
+            in_degree[succ] -= 1;
+            if in_degree[succ] == 0 {
+                ready.insert(succ);
+            }
+        }
+    }
+
+    let scheduled: BTreeSet<usize> = order.iter().copied().collect();
+    let blocked: Vec<usize> = (0..n).filter(|i| !scheduled.contains(i)).collect();
+    let cycles = find_cycles(&successors, &blocked);
+
+    TaskGraph {
+        nodes: tasks.iter().map(|t| t.title.clone()).collect(),
+        edges,
+        order,
+        cycles,
+        blocked,
+        warnings,
+    }
+}
+
+/// Strongly connected components (Tarjan) among `candidates` that form a
+/// cycle: more than one node, or a node depending on itself
+fn find_cycles(successors: &[Vec<usize>], candidates: &[usize]) -> Vec<Vec<usize>> {
+    struct Tarjan<'a> {
+        successors: &'a [Vec<usize>],
+        index: Vec<Option<usize>>,
+        low: Vec<usize>,
+        on_stack: Vec<bool>,
+        stack: Vec<usize>,
+        next: usize,
+        components: Vec<Vec<usize>>,
+    }
+
+    impl Tarjan<'_> {
+        fn visit(&mut self, v: usize) {
+            self.index[v] = Some(self.next);
+            self.low[v] = self.next;
+            self.next += 1;
+            self.stack.push(v);
+            self.on_stack[v] = true;
+
+            for &w in &self.successors[v] {
+                match self.index[w] {
+                    None => {
+                        self.visit(w);
+                        self.low[v] = self.low[v].min(self.low[w]);
+                    }
+                    Some(iw) if self.on_stack[w] => self.low[v] = self.low[v].min(iw),
+                    Some(_) => {}
+                }
+            }
+
+            if Some(self.low[v]) == self.index[v] {
+                let mut component = Vec::new();
+                while let Some(w) = self.stack.pop() {
+                    self.on_stack[w] = false;
+                    component.push(w);
+                    if w == v {
+                        break;
+                    }
+                }
+                component.sort_unstable();
+                self.components.push(component);
+            }
+        }
+    }
+
+    let n = successors.len();
+    let mut tarjan = Tarjan {
+        successors,
+        index: vec![None; n],
+        low: vec![0; n],
+        on_stack: vec![false; n],
+        stack: Vec::new(),
+        next: 0,
+        components: Vec::new(),
+    };
+    for &v in candidates {
+        if tarjan.index[v].is_none() {
+            tarjan.visit(v);
+        }
+    }
+
+    let mut cycles: Vec<_> = tarjan
+        .components
+        .into_iter()
+        .filter(|c| c.len() > 1 || successors[c[0]].contains(&c[0]))
+        .collect();
+    cycles.sort();
+    cycles
+}
+
+impl TaskGraph {
+    pub fn has_cycles(&self) -> bool {
+        !self.cycles.is_empty()
+    }
+
+    /// Task titles in suggested execution order
+    pub fn execution_order(&self) -> Vec<&str> {
+        self.order.iter().map(|&i| self.nodes[i].as_str()).collect()
+    }
+
+    /// Render as a Mermaid flowchart; tasks in cycles are highlighted
+    pub fn to_mermaid(&self) -> String {
+        let mut out = String::from("graph TD\n");
+
+        for (i, title) in self.nodes.iter().enumerate() {
+            out.push_str(&format!(
+                "    t{}[\"{}\"]\n",
+                i,
+                title.replace('"', "#quot;")
+            ));
+        }
+        for &(from, to) in &self.edges {
+            out.push_str(&format!("    t{} --> t{}\n", from, to));
+        }
+
+        if self.has_cycles() {
+            out.push_str("    classDef cycle stroke:#d33,stroke-width:2px\n");
+            let members: BTreeSet<usize> = self.cycles.iter().flatten().copied().collect();
+            let ids: Vec<String> = members.iter().map(|i| format!("t{}", i)).collect();
+            out.push_str(&format!("    class {} cycle\n", ids.join(",")));
+        }
+
+        out
+    }
+
+    /// Format the execution plan as markdown
+    pub fn format_plan(&self) -> String {
+        let mut md = String::from("## Execution Plan\n\n");
+
+        for (step, &i) in self.order.iter().enumerate() {
+            md.push_str(&format!("{}. {}\n", step + 1, self.nodes[i]));
+        }
+        md.push('\n');
+
+        if self.has_cycles() {
+            md.push_str("### Dependency Cycles\n\n");
+            for cycle in &self.cycles {
+                let mut names: Vec<&str> = cycle.iter().map(|&i| self.nodes[i].as_str()).collect();
+                names.push(names[0]);
+                md.push_str(&format!("- {}\n", names.join(" → ")));
+            }
+            md.push('\n');
+
+            md.push_str("Blocked until the cycles are broken:\n\n");
+            for &i in &self.blocked {
+                md.push_str(&format!("- {}\n", self.nodes[i]));
+            }
+            md.push('\n');
+        }
+
+        if !self.warnings.is_empty() {
+            md.push_str("### Warnings\n\n");
+            for warning in &self.warnings {
+                md.push_str(&format!("- ⚠️ {}\n", warning));
+            }
+            md.push('\n');
+        }
+
+        md.push_str("```mermaid\n");
+        md.push_str(&self.to_mermaid());
+        md.push_str("```\n");
+
+        md
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_topological_order_and_warnings() {
+        let tasks = vec![
+            ResearchTask::new("Write report")
+                .depends_on("Benchmark")
+                .depends_on("survey"),
+            ResearchTask::new("Survey"),
+            ResearchTask::new("Benchmark")
+                .depends_on("Survey")
+                .depends_on("Procure hardware"),
+            ResearchTask::new("Unrelated"),
+        ];
+        let graph = build_dependency_graph(&tasks);
+
+        assert!(!graph.has_cycles());
+        assert!(graph.blocked.is_empty());
+        assert_eq!(
+            graph.execution_order(),
+            vec!["Survey", "Benchmark", "Write report", "Unrelated"]
+        );
+        assert_eq!(graph.warnings.len(), 1);
+        assert!(graph.warnings[0].contains("\"Procure hardware\""));
+
+        let mermaid = graph.to_mermaid();
+        assert!(mermaid.starts_with("graph TD\n"));
+        assert!(mermaid.contains("    t1[\"Survey\"]\n"));
+        assert!(mermaid.contains("    t1 --> t2\n"));
+        assert!(mermaid.contains("    t2 --> t0\n"));
+        assert!(!mermaid.contains("classDef"));
+    }
+
+    #[test]
+    fn test_cycles_are_detected_and_blocked() {
+        let tasks = vec![
+            ResearchTask::new("A").depends_on("C"),
+            ResearchTask::new("B").depends_on("A"),
+            ResearchTask::new("C").depends_on("B"),
+            ResearchTask::new("D").depends_on("A"),
+            ResearchTask::new("E").depends_on("E"),
+            ResearchTask::new("F"),
+        ];
+        let graph = build_dependency_graph(&tasks);
+
+        assert_eq!(graph.cycles, vec![vec![0, 1, 2], vec![4]]);
+        assert_eq!(graph.blocked, vec![0, 1, 2, 3, 4]);
+        assert_eq!(graph.execution_order(), vec!["F"]);
+
+        let plan = graph.format_plan();
+        assert!(plan.contains("- A → B → C → A\n"));
+        assert!(plan.contains("- E → E\n"));
+        assert!(graph.to_mermaid().contains("    class t0,t1,t2,t4 cycle\n"));
+    }
+}
@@ -4,8 +4,15 @@
 //! aggregate findings, and produce comprehensive reports.
 
 pub mod aggregator;
+pub mod document;
+pub mod graph;
 pub mod worker;
 
+pub use document::{
+    analyze_document, analyze_file, split_document, DocumentChunk, ResearchBreakdown,
+};
+pub use graph::{build_dependency_graph, ResearchTask, TaskGraph};
+
 use serde::{Deserialize, Serialize};
 use sqlx::{FromRow, PgPool};
 use uuid::Uuid;
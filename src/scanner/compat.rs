@@ -4,16 +4,20 @@
 //! that was used by enhanced_scanner and server modules.
 
 use crate::error::Result;
+use crate::path_filter::PathFilter;
 use crate::tags::TagScanner;
 use crate::types::{
     AuditReport, AuditRequest, AuditSummary, Category, FileAnalysis, FilePriority, Issue,
-    IssueCategory, IssueSeverity, SystemMap,
+    IssueCategory, IssueSeverity, SystemMap, AUDIT_REPORT_SCHEMA_VERSION,
 };
 use ignore::WalkBuilder;
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::{Path, PathBuf};
-use tracing::{debug, info};
+use tracing::{debug, info, warn};
+
+/// Called after each walked file with (files done, total files, file path)
+pub type ScanProgressCallback = Box<dyn Fn(usize, usize, &Path) + Send + Sync>;
 
 /// Scanner for analyzing codebases (compatibility layer)
 pub struct Scanner {
@@ -25,6 +29,10 @@ pub struct Scanner {
     max_file_size: usize,
     /// Whether to include tests
     include_tests: bool,
+    /// Include/exclude globs applied to walked files
+    path_filter: PathFilter,
+    /// Per-file progress reporting
+    progress: Option<ScanProgressCallback>,
 }
 
 impl Scanner {
@@ -37,9 +45,23 @@ impl Scanner {
             tag_scanner,
             max_file_size,
             include_tests,
+            path_filter: PathFilter::default(),
+            progress: None,
         })
     }
 
+    /// Only scan walked files that pass `filter`
+    pub fn with_path_filter(mut self, filter: PathFilter) -> Self {
+        self.path_filter = filter;
+        self
+    }
+
+    /// Report progress to `callback` as files are scanned
+    pub fn with_progress(mut self, callback: ScanProgressCallback) -> Self {
+        self.progress = Some(callback);
+        self
+    }
+
     /// Scan the codebase and generate a report
     pub fn scan(&self, _request: &AuditRequest) -> Result<AuditReport> {
         info!("Starting codebase scan at {}", self.root.display());
@@ -57,7 +79,7 @@ impl Scanner {
         let tasks = Vec::new();
 
         // Count issues by severity
-        let mut issues_by_severity = HashMap::new();
+        let mut issues_by_severity = BTreeMap::new();
         for file in &files {
             for issue in &file.issues {
                 *issues_by_severity.entry(issue.severity).or_insert(0) += 1;
@@ -65,6 +87,7 @@ impl Scanner {
         }
 
         Ok(AuditReport {
+            schema_version: AUDIT_REPORT_SCHEMA_VERSION,
             id: uuid::Uuid::new_v4().to_string(),
             repository: self.root.to_string_lossy().to_string(),
             branch: "main".to_string(),
@@ -86,8 +109,8 @@ impl Scanner {
         // Create a simple system map compatible with current types
         Ok(SystemMap {
             total_files: 0,
-            files_by_category: HashMap::new(),
-            lines_by_category: HashMap::new(),
+            files_by_category: BTreeMap::new(),
+            lines_by_category: BTreeMap::new(),
             dependencies: Vec::new(),
             mermaid_diagram: None,
         })
@@ -102,12 +125,33 @@ impl Scanner {
             .git_ignore(true)
             .build();
 
-        for entry in walk.flatten() {
-            let path = entry.path();
-            if path.is_file() {
-                if let Some(analysis) = self.scan_file(path)? {
-                    analyses.push(analysis);
-                }
+        // Walk first so progress can report a total; sorted so reports
+        // list files in the same order on every run
+        let mut paths: Vec<PathBuf> = walk
+            .flatten()
+            .map(|entry| entry.into_path())
+            .filter(|path| path.is_file())
+            .collect();
+        if !self.path_filter.is_empty() {
+            let rel_path =
+                |path: &PathBuf| path.strip_prefix(&self.root).unwrap_or(path).to_path_buf();
+            let rel_paths: Vec<PathBuf> = paths.iter().map(rel_path).collect();
+            for pattern in self
+                .path_filter
+                .unmatched_patterns(rel_paths.iter().map(PathBuf::as_path))
+            {
+                warn!("Pattern '{}' matches no files", pattern);
+            }
+            paths.retain(|path| self.path_filter.matches(&rel_path(path)));
+        }
+        paths.sort();
+
+        for (i, path) in paths.iter().enumerate() {
+            if let Some(analysis) = self.scan_file(path)? {
+                analyses.push(analysis);
+            }
+            if let Some(ref progress) = self.progress {
+                progress(i + 1, paths.len(), path);
             }
         }
 
@@ -322,3 +366,65 @@ fn calculate_priority(issues: &[Issue], category: &Category) -> FilePriority {
         FilePriority::Low
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_progress_reports_each_filtered_file() {
+        let temp = TempDir::new().unwrap();
+        fs::create_dir_all(temp.path().join("src")).unwrap();
+        fs::create_dir_all(temp.path().join("examples")).unwrap();
+        fs::write(temp.path().join("src/lib.rs"), "pub fn a() {}").unwrap();
+        fs::write(temp.path().join("src/main.rs"), "fn main() {}").unwrap();
+        fs::write(temp.path().join("examples/demo.rs"), "fn main() {}").unwrap();
+
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&seen);
+        let scanner = Scanner::new(temp.path().to_path_buf(), 1_000_000, false)
+            .unwrap()
+            .with_path_filter(PathFilter::new(vec!["src".into()], vec![]).unwrap())
+            .with_progress(Box::new(move |done, total, _path| {
+                sink.lock().unwrap().push((done, total));
+            }));
+
+        let files = scanner.scan_files().unwrap();
+        assert_eq!(files.len(), 2);
+        assert_eq!(*seen.lock().unwrap(), vec![(1, 2), (2, 2)]);
+    }
+
+    #[test]
+    fn test_same_scan_serializes_identically() {
+        let temp = TempDir::new().unwrap();
+        for dir in ["src/net", "src/auth", "config"] {
+            fs::create_dir_all(temp.path().join(dir)).unwrap();
+        }
+        fs::write(temp.path().join("src/lib.rs"), "// TODO: split\n").unwrap();
+        fs::write(temp.path().join("src/net/mod.rs"), "// FIXME: retry\n").unwrap();
+        fs::write(temp.path().join("src/auth/mod.rs"), "// HACK\n// TODO\n").unwrap();
+        fs::write(temp.path().join("config/app.toml"), "# todo\n").unwrap();
+
+        let scanner = Scanner::new(temp.path().to_path_buf(), 1_000_000, false).unwrap();
+        let request = AuditRequest::builder()
+            .repository(temp.path().display().to_string())
+            .build()
+            .unwrap();
+        let render = || {
+            let mut report = scanner.scan(&request).unwrap();
+            report.id = "fixed".to_string();
+            report.created_at = chrono::DateTime::UNIX_EPOCH;
+            serde_json::to_string(&report).unwrap()
+        };
+
+        let first = render();
+        assert_eq!(first, render());
+
+        let report: AuditReport = serde_json::from_str(&first).unwrap();
+        let paths: Vec<&Path> = report.files.iter().map(|f| f.path.as_path()).collect();
+        assert!(paths.windows(2).all(|w| w[0] <= w[1]), "{:?}", paths);
+        assert!(first.contains(r#""issues_by_severity":{"medium":2,"low":3}"#));
+    }
+}
@@ -13,4 +13,4 @@ pub use github::{
 };
 
 // Re-export compatibility scanner
-pub use compat::Scanner;
+pub use compat::{ScanProgressCallback, Scanner};
@@ -10,11 +10,12 @@
 use crate::error::Result;
 use crate::todo_scanner::{TodoItem, TodoPriority};
 use crate::types::AuditTag;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
 
 /// File score with multiple dimensions
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct FileScore {
     /// File path
     pub path: PathBuf,
@@ -45,7 +46,7 @@ pub struct FileScore {
 }
 
 /// Detailed breakdown of score components
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, JsonSchema)]
 pub struct ScoreBreakdown {
     /// Audit tags found
     pub audit_tags: Vec<String>,
@@ -79,7 +80,7 @@ pub struct ScoreBreakdown {
 }
 
 /// TODO breakdown by priority
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, JsonSchema)]
 pub struct TodoBreakdown {
     pub high: usize,
     pub medium: usize,
@@ -88,7 +89,7 @@ pub struct TodoBreakdown {
 }
 
 /// Complexity indicators
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ComplexityIndicators {
     /// Unwrap/panic patterns (Rust)
     pub unwraps_and_panics: usize,
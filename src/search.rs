@@ -8,6 +8,8 @@
 //! - **Vector similarity search**: Find semantically similar documents
 //! - **Top-k retrieval**: Get the most relevant results
 //! - **Filtering**: Filter by document type, tags, repository, dates
+//! - **Tag expressions**: Scope any search to documents with all of, any of,
+//!   or none of a set of tags
 //! - **Hybrid search**: Combine semantic and keyword search
 //! - **Relevance scoring**: Rank results by relevance
 //!
@@ -108,6 +110,10 @@ pub struct SearchFilters {
     /// Filter by tags (any of these tags)
     pub tags: Option<Vec<String>>,
 
+    /// Filter by a tag expression with AND/OR/NOT semantics
+    #[serde(default)]
+    pub tag_expression: Option<TagExpression>,
+
     /// Filter by repository ID
     pub repo_id: Option<i64>,
 
@@ -134,6 +140,7 @@ impl Default for SearchFilters {
         Self {
             doc_type: None,
             tags: None,
+            tag_expression: None,
             repo_id: None,
             source_type: None,
             created_after: None,
@@ -143,6 +150,139 @@ impl Default for SearchFilters {
     }
 }
 
+/// Tag filter: a document matches when it has every tag in `all_of`, at
+/// least one tag in `any_of` (if any are given), and no tag in `none_of`.
+/// Tags are compared case-insensitively; empty lists don't constrain.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TagExpression {
+    pub all_of: Vec<String>,
+    pub any_of: Vec<String>,
+    pub none_of: Vec<String>,
+}
+
+impl TagExpression {
+    /// Whether the expression has no constraints
+    pub fn is_empty(&self) -> bool {
+        normalize_tags(&self.all_of).is_empty()
+            && normalize_tags(&self.any_of).is_empty()
+            && normalize_tags(&self.none_of).is_empty()
+    }
+
+    /// Whether a document with `tags` satisfies the expression
+    pub fn matches(&self, tags: &[String]) -> bool {
+        let tags = normalize_tags(tags);
+        let any_of = normalize_tags(&self.any_of);
+        normalize_tags(&self.all_of)
+            .iter()
+            .all(|t| tags.contains(t))
+            && (any_of.is_empty() || any_of.iter().any(|t| tags.contains(t)))
+            && !normalize_tags(&self.none_of)
+                .iter()
+                .any(|t| tags.contains(t))
+    }
+
+    /// The document's tags named in `all_of` or `any_of`, in document order
+    pub fn matched_tags(&self, tags: &[String]) -> Vec<String> {
+        let wanted: Vec<String> = normalize_tags(&self.all_of)
+            .into_iter()
+            .chain(normalize_tags(&self.any_of))
+            .collect();
+        let mut matched: Vec<String> = Vec::new();
+        for tag in tags {
+            let normalized = tag.trim().to_lowercase();
+            if wanted.contains(&normalized)
+                && !matched.iter().any(|m| m.to_lowercase() == normalized)
+            {
+                matched.push(tag.trim().to_string());
+            }
+        }
+        matched
+    }
+
+    /// SQL condition on the documents alias `d`, or `None` if unconstrained
+    fn sql_condition(&self) -> Option<String> {
+        let mut conditions = Vec::new();
+        let all_of = normalize_tags(&self.all_of);
+        if !all_of.is_empty() {
+            conditions.push(format!(
+                "{} @> {}",
+                DOCUMENT_TAG_SET,
+                sql_text_array(&all_of)
+            ));
+        }
+        let any_of = normalize_tags(&self.any_of);
+        if !any_of.is_empty() {
+            conditions.push(format!(
+                "{} && {}",
+                DOCUMENT_TAG_SET,
+                sql_text_array(&any_of)
+            ));
+        }
+        let none_of = normalize_tags(&self.none_of);
+        if !none_of.is_empty() {
+            conditions.push(format!(
+                "NOT ({} && {})",
+                DOCUMENT_TAG_SET,
+                sql_text_array(&none_of)
+            ));
+        }
+
+        if conditions.is_empty() {
+            None
+        } else {
+            Some(format!("({})", conditions.join(" AND ")))
+        }
+    }
+}
+
+/// Lowercased tags of document `d`. The API stores `documents.tags` as a
+/// JSON array and the CLI as comma-separated text that is also mirrored in
+/// `document_tags`, so all three are read.
+const DOCUMENT_TAG_SET: &str = "ARRAY(
+    SELECT lower(trim(t)) FROM unnest(
+        CASE WHEN d.tags LIKE '[%'
+             THEN ARRAY(SELECT json_array_elements_text(d.tags::json))
+             ELSE string_to_array(d.tags, ',')
+        END) AS t
+    WHERE trim(t) <> ''
+    UNION
+    SELECT lower(dt.tag) FROM document_tags dt WHERE dt.document_id = d.id)";
+
+fn normalize_tags(tags: &[String]) -> Vec<String> {
+    let mut normalized: Vec<String> = Vec::new();
+    for tag in tags {
+        let tag = tag.trim().to_lowercase();
+        if !tag.is_empty() && !normalized.contains(&tag) {
+            normalized.push(tag);
+        }
+    }
+    normalized
+}
+
+/// `ARRAY['a', 'b']::text[]` with quotes escaped
+fn sql_text_array(tags: &[String]) -> String {
+    let literals: Vec<String> = tags
+        .iter()
+        .map(|tag| format!("'{}'", tag.replace('\'', "''")))
+        .collect();
+    format!("ARRAY[{}]::text[]", literals.join(", "))
+}
+
+/// Parse stored tags, which are a JSON array or comma-separated text
+fn parse_tag_list(tags: Option<String>) -> Option<Vec<String>> {
+    let tags = tags?;
+    if let Ok(parsed) = serde_json::from_str::<Vec<String>>(&tags) {
+        return Some(parsed);
+    }
+    let split: Vec<String> = tags
+        .split(',')
+        .map(|t| t.trim().to_string())
+        .filter(|t| !t.is_empty())
+        .collect();
+    (!split.is_empty()).then_some(split)
+}
+
 // ============================================================================
 // Search Results
 // ============================================================================
@@ -174,6 +314,10 @@ pub struct SearchResult {
     /// Document tags
     pub tags: Option<Vec<String>>,
 
+    /// Document tags that satisfied the query's tag expression
+    #[serde(default)]
+    pub matched_tags: Vec<String>,
+
     /// Heading context for this chunk
     pub heading: Option<String>,
 
@@ -222,7 +366,17 @@ pub struct SemanticSearcher {
 impl SemanticSearcher {
     /// Create a new semantic searcher
     pub async fn new(config: SearchConfig) -> Result<Self> {
-        let embedding_config = crate::embeddings::EmbeddingConfig::default();
+        Self::with_embedding_config(config, crate::embeddings::EmbeddingConfig::default()).await
+    }
+
+    /// Create a semantic searcher that embeds queries with the given backend config
+    ///
+    /// This must match the config used at indexing time, otherwise query and
+    /// stored vectors will not be comparable.
+    pub async fn with_embedding_config(
+        config: SearchConfig,
+        embedding_config: crate::embeddings::EmbeddingConfig,
+    ) -> Result<Self> {
         let embedding_generator = EmbeddingGenerator::new(embedding_config)
             .context("Failed to create embedding generator")?;
 
@@ -241,11 +395,12 @@ impl SemanticSearcher {
             query.top_k.min(self.config.max_top_k)
         };
 
-        if self.config.use_hybrid_search {
-            self.hybrid_search(pool, query, top_k).await
+        let results = if self.config.use_hybrid_search {
+            self.hybrid_search(pool, query, top_k).await?
         } else {
-            self.semantic_search_only(pool, query, top_k).await
-        }
+            self.semantic_search_only(pool, query, top_k).await?
+        };
+        Ok(with_matched_tags(results, &query.filters))
     }
 
     /// Perform semantic-only search
@@ -272,6 +427,8 @@ impl SemanticSearcher {
             return Ok(Vec::new());
         }
 
+        check_dimensions(&query_embedding, &candidates)?;
+
         // Calculate similarities
         tracing::debug!(
             "Calculating similarities for {} candidates",
@@ -314,6 +471,7 @@ impl SemanticSearcher {
                 title: candidate.title,
                 doc_type: candidate.doc_type,
                 tags: candidate.tags,
+                matched_tags: Vec::new(),
                 heading: candidate.heading,
                 char_start: candidate.char_start,
                 char_end: candidate.char_end,
@@ -350,6 +508,35 @@ impl SemanticSearcher {
         Ok(merged)
     }
 
+    /// Perform hybrid search with an explicit semantic/keyword blend
+    ///
+    /// `alpha` is the weight given to the semantic score (0.0 = keyword only,
+    /// 1.0 = semantic only). Each score distribution is min-max normalized
+    /// before fusion so neither dominates purely by scale. The normalized
+    /// component scores are kept in the result metadata.
+    pub async fn hybrid_search_weighted(
+        &self,
+        pool: &PgPool,
+        query: &SearchQuery,
+        alpha: f32,
+    ) -> Result<Vec<SearchResult>> {
+        if !(0.0..=1.0).contains(&alpha) {
+            anyhow::bail!("alpha must be between 0.0 and 1.0, got {}", alpha);
+        }
+
+        let top_k = if query.top_k == 0 {
+            self.config.default_top_k
+        } else {
+            query.top_k.min(self.config.max_top_k)
+        };
+
+        let semantic_results = self.semantic_search_only(pool, query, top_k * 2).await?;
+        let keyword_results = self.keyword_search(pool, query, top_k * 2).await?;
+
+        let fused = fuse_weighted(semantic_results, keyword_results, alpha, top_k);
+        Ok(with_matched_tags(fused, &query.filters))
+    }
+
     /// Perform keyword-based search
     async fn keyword_search(
         &self,
@@ -357,7 +544,10 @@ impl SemanticSearcher {
         query: &SearchQuery,
         top_k: usize,
     ) -> Result<Vec<SearchResult>> {
-        let filter_clause = self.build_filter_clause(&query.filters);
+        // The base query already has a WHERE, so chain the filters with AND
+        let filter_clause = self
+            .build_filter_clause(&query.filters)
+            .replacen("WHERE ", "AND ", 1);
 
         let sql = format!(
             "SELECT
@@ -373,10 +563,10 @@ impl SemanticSearcher {
                 d.tags
              FROM document_chunks c
              JOIN documents d ON c.document_id = d.id
-             WHERE c.content LIKE ?
+             WHERE c.content ILIKE $1
              {}
              ORDER BY c.chunk_index ASC
-             LIMIT ?",
+             LIMIT $2",
             filter_clause
         );
 
@@ -393,8 +583,7 @@ impl SemanticSearcher {
             .into_iter()
             .enumerate()
             .map(|(idx, row)| {
-                let tags_str: Option<String> = row.try_get("tags").ok();
-                let tags = tags_str.and_then(|s| serde_json::from_str(&s).ok());
+                let tags = parse_tag_list(row.try_get("tags").ok().flatten());
 
                 // Simple scoring based on rank
                 let score = 1.0 / (idx as f32 + 1.0);
@@ -408,6 +597,7 @@ impl SemanticSearcher {
                     title: row.try_get("title").ok(),
                     doc_type: row.try_get("doc_type").ok(),
                     tags,
+                    matched_tags: Vec::new(),
                     heading: row.try_get("heading").ok(),
                     char_start: row.get("char_start"),
                     char_end: row.get("char_end"),
@@ -535,8 +725,7 @@ impl SemanticSearcher {
                     }
                 };
 
-                let tags_str: Option<String> = row.try_get("tags").ok();
-                let tags = tags_str.and_then(|s| serde_json::from_str(&s).ok());
+                let tags = parse_tag_list(row.try_get("tags").ok().flatten());
 
                 Some(CandidateEmbedding {
                     chunk_id: row.get("chunk_id"),
@@ -598,6 +787,14 @@ impl SemanticSearcher {
             }
         }
 
+        if let Some(condition) = filters
+            .tag_expression
+            .as_ref()
+            .and_then(TagExpression::sql_condition)
+        {
+            conditions.push(condition);
+        }
+
         if conditions.is_empty() {
             String::new()
         } else {
@@ -620,6 +817,110 @@ impl SemanticSearcher {
     }
 }
 
+/// Fail with an actionable error if stored vectors don't match the query's dimension
+/// Record which tags of each result satisfied the tag expression
+fn with_matched_tags(mut results: Vec<SearchResult>, filters: &SearchFilters) -> Vec<SearchResult> {
+    if let Some(expression) = &filters.tag_expression {
+        for result in &mut results {
+            result.matched_tags = expression.matched_tags(result.tags.as_deref().unwrap_or(&[]));
+        }
+    }
+    results
+}
+
+fn check_dimensions(query: &Embedding, candidates: &[CandidateEmbedding]) -> Result<()> {
+    if let Some(mismatch) = candidates
+        .iter()
+        .find(|c| c.embedding.vector.len() != query.vector.len())
+    {
+        anyhow::bail!(
+            "Embedding dimension mismatch: query embedded with '{}' ({} dimensions) but stored \
+             chunks use '{}' ({} dimensions); reindex documents after changing the embedding model",
+            query.model,
+            query.vector.len(),
+            mismatch.model,
+            mismatch.embedding.vector.len()
+        );
+    }
+    Ok(())
+}
+
+/// Scale scores into [0, 1] by min-max normalization
+///
+/// A distribution with no spread (including a single result) maps to 1.0.
+fn min_max_normalize(scores: &[f32]) -> Vec<f32> {
+    let min = scores.iter().copied().fold(f32::INFINITY, f32::min);
+    let max = scores.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+    let range = max - min;
+
+    scores
+        .iter()
+        .map(|&s| {
+            if range > f32::EPSILON {
+                (s - min) / range
+            } else {
+                1.0
+            }
+        })
+        .collect()
+}
+
+/// Fuse semantic and keyword results as `alpha * semantic + (1 - alpha) * keyword`
+///
+/// Both lists are normalized independently; a chunk missing from one list
+/// contributes 0.0 for that component.
+fn fuse_weighted(
+    semantic_results: Vec<SearchResult>,
+    keyword_results: Vec<SearchResult>,
+    alpha: f32,
+    top_k: usize,
+) -> Vec<SearchResult> {
+    let semantic_norm =
+        min_max_normalize(&semantic_results.iter().map(|r| r.score).collect::<Vec<_>>());
+    let keyword_norm =
+        min_max_normalize(&keyword_results.iter().map(|r| r.score).collect::<Vec<_>>());
+
+    let mut fused: HashMap<String, SearchResult> = HashMap::new();
+
+    for (mut result, norm) in semantic_results.into_iter().zip(semantic_norm) {
+        result.metadata.semantic_match = true;
+        result.metadata.semantic_score = Some(norm);
+        fused.insert(result.chunk_id.clone(), result);
+    }
+
+    for (mut result, norm) in keyword_results.into_iter().zip(keyword_norm) {
+        fused
+            .entry(result.chunk_id.clone())
+            .and_modify(|existing| {
+                existing.metadata.keyword_match = true;
+                existing.metadata.keyword_score = Some(norm);
+            })
+            .or_insert_with(|| {
+                result.metadata.keyword_match = true;
+                result.metadata.keyword_score = Some(norm);
+                result
+            });
+    }
+
+    let mut merged: Vec<SearchResult> = fused
+        .into_values()
+        .map(|mut result| {
+            let semantic = result.metadata.semantic_score.unwrap_or(0.0);
+            let keyword = result.metadata.keyword_score.unwrap_or(0.0);
+            result.score = alpha * semantic + (1.0 - alpha) * keyword;
+            result
+        })
+        .collect();
+
+    merged.sort_by(|a, b| {
+        b.score
+            .partial_cmp(&a.score)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+    merged.truncate(top_k);
+    merged
+}
+
 // ============================================================================
 // Internal Types
 // ============================================================================
@@ -642,6 +943,155 @@ struct CandidateEmbedding {
     char_end: i64,
 }
 
+// ============================================================================
+// Snippets
+// ============================================================================
+
+/// How result snippets are cut and highlighted
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnippetConfig {
+    /// Characters of the chunk kept around the first match
+    pub window_chars: usize,
+    /// Wrap query terms in `<mark>`; the snippet is then HTML-escaped
+    pub highlight: bool,
+}
+
+impl Default for SnippetConfig {
+    fn default() -> Self {
+        Self {
+            window_chars: 240,
+            highlight: true,
+        }
+    }
+}
+
+/// A window of a result chunk around the query terms
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Snippet {
+    pub text: String,
+    /// Char offsets of the window in the source document
+    pub char_start: i64,
+    pub char_end: i64,
+    /// Whether a query term occurs in the window
+    pub matched: bool,
+}
+
+/// Lowercased words of a query, ignoring single characters
+pub fn query_terms(query: &str) -> Vec<String> {
+    let mut terms: Vec<String> = Vec::new();
+    for word in query.split(|c: char| !c.is_alphanumeric() && c != '_') {
+        let word = word.to_lowercase();
+        if word.chars().count() > 1 && !terms.contains(&word) {
+            terms.push(word);
+        }
+    }
+    terms
+}
+
+/// Cut a snippet of `result`'s chunk around the first query term.
+///
+/// Terms match case-insensitively at the start of a word, so `index`
+/// also marks the start of `indexing`. Without a match the snippet is the
+/// start of the chunk. The window is widened to word boundaries, and `…`
+/// marks text cut off at either end.
+pub fn extract_snippet(result: &SearchResult, query: &str, config: &SnippetConfig) -> Snippet {
+    let chars: Vec<char> = result.content.chars().collect();
+    let lower: Vec<char> = chars
+        .iter()
+        .map(|c| c.to_lowercase().next().unwrap_or(*c))
+        .collect();
+    let matches = term_matches(&chars, &lower, &query_terms(query));
+    let n = chars.len();
+    let window = config.window_chars.max(1);
+
+    // Start a quarter window before the first match, and keep the window
+    // full when the match is near the end
+    let (mut start, mut end) = match matches.first() {
+        Some(&(first, _)) => {
+            let start = first.saturating_sub(window / 4);
+            let end = (start + window).min(n);
+            (end.saturating_sub(window).min(start), end)
+        }
+        None => (0, window.min(n)),
+    };
+    let first_match = matches.first().map(|&(s, _)| s).unwrap_or(start);
+    while start > 0 && start < first_match && !chars[start - 1].is_whitespace() {
+        start += 1;
+    }
+    while end < n && end > start && !chars[end].is_whitespace() {
+        end += 1;
+    }
+
+    let mut text = String::new();
+    if start > 0 {
+        text.push('…');
+    }
+    let mut matched = false;
+    let mut pos = start;
+    for &(m_start, m_end) in matches.iter().filter(|&&(s, e)| s >= start && e <= end) {
+        matched = true;
+        push_snippet_text(&mut text, &chars[pos..m_start], config.highlight);
+        if config.highlight {
+            text.push_str("<mark>");
+        }
+        push_snippet_text(&mut text, &chars[m_start..m_end], config.highlight);
+        if config.highlight {
+            text.push_str("</mark>");
+        }
+        pos = m_end;
+    }
+    push_snippet_text(&mut text, &chars[pos..end], config.highlight);
+    if end < n {
+        text.push('…');
+    }
+
+    Snippet {
+        text: text.trim().to_string(),
+        char_start: result.char_start + start as i64,
+        char_end: result.char_start + end as i64,
+        matched,
+    }
+}
+
+/// Non-overlapping `(start, end)` char ranges of terms at word starts
+fn term_matches(chars: &[char], lower: &[char], terms: &[String]) -> Vec<(usize, usize)> {
+    let is_word = |c: char| c.is_alphanumeric() || c == '_';
+    let mut ranges = Vec::new();
+    for term in terms {
+        let term: Vec<char> = term.chars().collect();
+        for i in 0..lower.len().saturating_sub(term.len() - 1) {
+            if lower[i..i + term.len()] == term[..] && (i == 0 || !is_word(chars[i - 1])) {
+                ranges.push((i, i + term.len()));
+            }
+        }
+    }
+    ranges.sort_unstable();
+    let mut merged: Vec<(usize, usize)> = Vec::new();
+    for (start, end) in ranges {
+        match merged.last_mut() {
+            Some(last) if start <= last.1 => last.1 = last.1.max(end),
+            _ => merged.push((start, end)),
+        }
+    }
+    merged
+}
+
+fn push_snippet_text(out: &mut String, chars: &[char], escape: bool) {
+    for &c in chars {
+        match c {
+            '&' if escape => out.push_str("&amp;"),
+            '<' if escape => out.push_str("&lt;"),
+            '>' if escape => out.push_str("&gt;"),
+            c if c.is_whitespace() => {
+                if !out.ends_with(' ') {
+                    out.push(' ');
+                }
+            }
+            c => out.push(c),
+        }
+    }
+}
+
 // ============================================================================
 // Statistics
 // ============================================================================
@@ -705,12 +1155,218 @@ mod tests {
         assert!(!config.use_hybrid_search);
     }
 
+    fn result(chunk_id: &str, score: f32) -> SearchResult {
+        SearchResult {
+            document_id: "doc".to_string(),
+            chunk_id: chunk_id.to_string(),
+            chunk_index: 0,
+            content: String::new(),
+            score,
+            title: None,
+            doc_type: None,
+            tags: None,
+            matched_tags: Vec::new(),
+            heading: None,
+            char_start: 0,
+            char_end: 0,
+            metadata: SearchResultMetadata {
+                model: "test".to_string(),
+                dimension: 0,
+                semantic_match: false,
+                keyword_match: false,
+                semantic_score: None,
+                keyword_score: None,
+            },
+        }
+    }
+
+    #[test]
+    fn test_min_max_normalize() {
+        assert_eq!(min_max_normalize(&[1.0, 3.0, 2.0]), vec![0.0, 1.0, 0.5]);
+        assert_eq!(min_max_normalize(&[0.3, 0.3]), vec![1.0, 1.0]);
+        assert!(min_max_normalize(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_fuse_weighted_alpha_extremes() {
+        let semantic = || vec![result("a", 0.9), result("b", 0.1)];
+        // Keyword scores on a very different scale; normalization must cancel that out
+        let keyword = || vec![result("b", 1000.0), result("a", 10.0)];
+
+        let semantic_only = fuse_weighted(semantic(), keyword(), 1.0, 10);
+        assert_eq!(semantic_only[0].chunk_id, "a");
+        assert_eq!(semantic_only[0].score, 1.0);
+
+        let keyword_only = fuse_weighted(semantic(), keyword(), 0.0, 10);
+        assert_eq!(keyword_only[0].chunk_id, "b");
+        assert_eq!(keyword_only[0].score, 1.0);
+
+        let blended = fuse_weighted(semantic(), keyword(), 0.5, 10);
+        assert_eq!(blended.len(), 2);
+        for r in &blended {
+            assert_eq!(r.score, 0.5);
+            assert!(r.metadata.semantic_score.is_some());
+            assert!(r.metadata.keyword_score.is_some());
+        }
+    }
+
+    #[test]
+    fn test_fuse_weighted_missing_component_counts_as_zero() {
+        let fused = fuse_weighted(vec![result("a", 0.8)], vec![result("k", 3.0)], 0.7, 10);
+        let a = fused.iter().find(|r| r.chunk_id == "a").unwrap();
+        let k = fused.iter().find(|r| r.chunk_id == "k").unwrap();
+        assert!((a.score - 0.7).abs() < 1e-6);
+        assert!((k.score - 0.3).abs() < 1e-6);
+        assert_eq!(a.metadata.keyword_score, None);
+        assert_eq!(fused[0].chunk_id, "a");
+    }
+
     #[test]
     fn test_search_filters_default() {
         let filters = SearchFilters::default();
         assert!(filters.indexed_only);
         assert!(filters.doc_type.is_none());
         assert!(filters.tags.is_none());
+        assert!(filters.tag_expression.is_none());
+    }
+
+    fn tags(list: &[&str]) -> Vec<String> {
+        list.iter().map(|t| t.to_string()).collect()
+    }
+
+    fn expression(all_of: &[&str], any_of: &[&str], none_of: &[&str]) -> TagExpression {
+        TagExpression {
+            all_of: tags(all_of),
+            any_of: tags(any_of),
+            none_of: tags(none_of),
+        }
+    }
+
+    #[test]
+    fn test_tag_expression_matches() {
+        let doc = tags(&["Rust", "async", "tutorial"]);
+
+        // Empty expression matches everything
+        assert!(TagExpression::default().is_empty());
+        assert!(TagExpression::default().matches(&doc));
+        assert!(expression(&[" "], &[], &[]).is_empty());
+
+        // all_of: every tag, case-insensitively
+        assert!(expression(&["rust", "ASYNC"], &[], &[]).matches(&doc));
+        assert!(!expression(&["rust", "tokio"], &[], &[]).matches(&doc));
+
+        // any_of: at least one tag
+        assert!(expression(&[], &["python", "async"], &[]).matches(&doc));
+        assert!(!expression(&[], &["python", "go"], &[]).matches(&doc));
+
+        // none_of: excludes documents with any of the tags
+        assert!(expression(&[], &[], &["draft"]).matches(&doc));
+        assert!(!expression(&[], &[], &["draft", "tutorial"]).matches(&doc));
+        assert!(!expression(&[], &[], &["draft"]).matches(&tags(&["draft"])));
+
+        // All three combined: "rust AND (async OR sync) AND NOT draft"
+        let combined = expression(&["rust"], &["async", "sync"], &["draft"]);
+        assert!(combined.matches(&doc));
+        assert!(!combined.matches(&tags(&["rust", "async", "draft"])));
+        assert!(!combined.matches(&tags(&["rust", "tutorial"])));
+        assert!(!combined.matches(&tags(&["async", "tutorial"])));
+        assert!(!combined.matches(&[]));
+    }
+
+    #[test]
+    fn test_tag_expression_matched_tags() {
+        let combined = expression(&["rust"], &["async", "sync"], &["draft"]);
+        assert_eq!(
+            combined.matched_tags(&tags(&["tutorial", "Async", "Rust"])),
+            tags(&["Async", "Rust"])
+        );
+        assert!(expression(&[], &[], &["draft"])
+            .matched_tags(&tags(&["rust"]))
+            .is_empty());
+
+        let filters = SearchFilters {
+            tag_expression: Some(combined),
+            ..Default::default()
+        };
+        let mut tagged = result("c1", 1.0);
+        tagged.tags = Some(tags(&["rust", "sync"]));
+        let results = with_matched_tags(vec![tagged, result("c2", 0.5)], &filters);
+        assert_eq!(results[0].matched_tags, tags(&["rust", "sync"]));
+        assert!(results[1].matched_tags.is_empty());
+    }
+
+    #[test]
+    fn test_tag_expression_sql_condition() {
+        assert!(TagExpression::default().sql_condition().is_none());
+
+        let sql = expression(&["Rust"], &["async", "sync"], &["it's"])
+            .sql_condition()
+            .unwrap();
+        assert!(sql.contains("@> ARRAY['rust']::text[]"));
+        assert!(sql.contains("&& ARRAY['async', 'sync']::text[]"));
+        assert!(sql.contains("NOT (ARRAY("));
+        assert!(sql.contains("ARRAY['it''s']::text[]"));
+        assert_eq!(sql.matches(" AND ").count(), 2);
+    }
+
+    #[test]
+    fn test_extract_snippet() {
+        let mut chunk = result("c1", 1.0);
+        chunk.char_start = 1000;
+        chunk.content = format!(
+            "{} The JobQueue retries failed <jobs> with backoff. {}",
+            "intro ".repeat(40),
+            "outro ".repeat(40)
+        );
+
+        let config = SnippetConfig {
+            window_chars: 60,
+            highlight: true,
+        };
+        let snippet = extract_snippet(&chunk, "retries backoff", &config);
+        assert!(snippet.matched);
+        assert!(snippet.text.starts_with('…') && snippet.text.ends_with('…'));
+        assert!(snippet
+            .text
+            .contains("<mark>retries</mark> failed &lt;jobs&gt;"));
+        assert!(snippet.text.contains("<mark>backoff</mark>"));
+        let offset = chunk.content.find("retries").unwrap() as i64;
+        assert!(snippet.char_start > 1000 && snippet.char_start <= 1000 + offset);
+        assert!(snippet.char_end - snippet.char_start >= 60);
+
+        // Highlighting off leaves the text raw
+        let plain = extract_snippet(
+            &chunk,
+            "RETRIES",
+            &SnippetConfig {
+                highlight: false,
+                ..config.clone()
+            },
+        );
+        assert!(plain.text.contains("retries failed <jobs>"));
+        assert!(!plain.text.contains("<mark>"));
+
+        // Terms only match at word starts; no match falls back to the start
+        let none = extract_snippet(&chunk, "tries", &config);
+        assert!(!none.matched);
+        assert_eq!(none.char_start, 1000);
+        assert!(none.text.starts_with("intro"));
+
+        assert_eq!(query_terms("How do I index? a"), vec!["how", "do", "index"]);
+    }
+
+    #[test]
+    fn test_parse_tag_list() {
+        assert_eq!(
+            parse_tag_list(Some(r#"["a","b"]"#.to_string())),
+            Some(tags(&["a", "b"]))
+        );
+        assert_eq!(
+            parse_tag_list(Some("a, b,".to_string())),
+            Some(tags(&["a", "b"]))
+        );
+        assert_eq!(parse_tag_list(Some(String::new())), None);
+        assert_eq!(parse_tag_list(None), None);
     }
 
     #[test]
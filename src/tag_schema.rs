@@ -3,9 +3,10 @@
 //! Provides a robust schema for categorizing code, tracking technical debt,
 //! and building a comprehensive directory tree of codebase status.
 
+use crate::error::{AuditError, Result};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 /// Schema for audit tags with strict validation
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -430,6 +431,12 @@ pub struct TagValidation {
     pub errors: Vec<String>,
     /// Suggested corrections
     pub suggestions: Vec<String>,
+    /// File the tag was found in, when validated from a scan
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file: Option<PathBuf>,
+    /// Line of the tag within `file`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
 }
 
 impl TagValidation {
@@ -439,6 +446,8 @@ impl TagValidation {
             is_valid: true,
             errors: Vec::new(),
             suggestions: Vec::new(),
+            file: None,
+            line: None,
         }
     }
 
@@ -448,6 +457,8 @@ impl TagValidation {
             is_valid: false,
             errors: vec![error.into()],
             suggestions: Vec::new(),
+            file: None,
+            line: None,
         }
     }
 
@@ -456,6 +467,278 @@ impl TagValidation {
         self.suggestions.push(suggestion.into());
         self
     }
+
+    /// Record where the validated tag lives
+    pub fn at(mut self, file: impl Into<PathBuf>, line: usize) -> Self {
+        self.file = Some(file.into());
+        self.line = Some(line);
+        self
+    }
+}
+
+/// Allowed values for `@audit-tag` annotations.
+///
+/// Tag values take the form `status[,category][,priority]`. Each field has a
+/// list of canonical values plus a map of deprecated spellings to the value
+/// that replaces them; deprecated spellings still validate but are what
+/// [`TagRules::suggest_fix`] rewrites. The defaults mirror [`CodeStatus`],
+/// [`TagCategory`] and [`Priority`]; teams can narrow or extend them with a
+/// TOML or JSON file via [`TagRules::load`]. Matching is case-insensitive.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TagRules {
+    /// Allowed statuses (first field)
+    pub statuses: Vec<String>,
+    /// Allowed categories (second field)
+    pub categories: Vec<String>,
+    /// Allowed priorities (third field)
+    pub priorities: Vec<String>,
+    /// Deprecated status spellings and their replacements
+    pub deprecated_statuses: HashMap<String, String>,
+    /// Deprecated category spellings and their replacements
+    pub deprecated_categories: HashMap<String, String>,
+    /// Deprecated priority spellings and their replacements
+    pub deprecated_priorities: HashMap<String, String>,
+}
+
+impl Default for TagRules {
+    fn default() -> Self {
+        let list = |values: &[&str]| values.iter().map(|v| v.to_string()).collect();
+        let map = |pairs: &[(&str, &str)]| {
+            pairs
+                .iter()
+                .map(|(from, to)| (from.to_string(), to.to_string()))
+                .collect()
+        };
+        Self {
+            statuses: list(&[
+                "new",
+                "active",
+                "stable",
+                "deprecated",
+                "old",
+                "very-old",
+                "needs-review",
+                "frozen",
+                "experimental",
+            ]),
+            categories: list(&[
+                "organization",
+                "security",
+                "performance",
+                "risk",
+                "technical-debt",
+                "documentation",
+                "testing",
+                "legacy",
+                "experimental",
+                "configuration",
+            ]),
+            priorities: list(&["critical", "high", "medium", "low"]),
+            deprecated_statuses: map(&[
+                ("production", "stable"),
+                ("prod", "stable"),
+                ("dep", "deprecated"),
+                ("ancient", "very-old"),
+                ("review", "needs-review"),
+                ("freeze", "frozen"),
+                ("exp", "experimental"),
+                ("proto", "experimental"),
+            ]),
+            deprecated_categories: map(&[
+                ("org", "organization"),
+                ("sec", "security"),
+                ("perf", "performance"),
+                ("debt", "technical-debt"),
+                ("tech-debt", "technical-debt"),
+                ("docs", "documentation"),
+                ("tests", "testing"),
+                ("old", "legacy"),
+                ("new", "experimental"),
+                ("exp", "experimental"),
+                ("config", "configuration"),
+            ]),
+            deprecated_priorities: HashMap::new(),
+        }
+    }
+}
+
+/// How sure [`TagRules::suggest_fix`] is about a rewrite
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FixConfidence {
+    /// Every change comes from a deprecated-value mapping; safe to apply
+    High,
+    /// At least one change is a guess (e.g. a likely typo); review by hand
+    Ambiguous,
+}
+
+/// A suggested rewrite of one `@audit-tag` value
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TagFix {
+    /// File containing the tag
+    pub file: PathBuf,
+    /// Line of the tag within `file`
+    pub line: usize,
+    /// Tag value as written
+    pub original: String,
+    /// Tag value after the fix, with the original spacing kept
+    pub replacement: String,
+    /// Whether the fix can be applied without review
+    pub confidence: FixConfidence,
+    /// One note per changed field
+    pub notes: Vec<String>,
+}
+
+impl TagRules {
+    /// Load rules from a `.json` file, or TOML for any other extension.
+    /// Lists missing from the file keep their defaults.
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| AuditError::from(e).context(path.display().to_string()))?;
+        if path.extension().is_some_and(|e| e == "json") {
+            Ok(serde_json::from_str(&content)?)
+        } else {
+            toml::from_str(&content)
+                .map_err(|e| AuditError::config(format!("{}: {}", path.display(), e)))
+        }
+    }
+
+    /// `(name, allowed, deprecated)` for each field, in tag order
+    fn fields(&self) -> [(&'static str, &[String], &HashMap<String, String>); 3] {
+        [
+            ("status", &self.statuses, &self.deprecated_statuses),
+            ("category", &self.categories, &self.deprecated_categories),
+            ("priority", &self.priorities, &self.deprecated_priorities),
+        ]
+    }
+
+    /// Validate one `@audit-tag` value, collecting every problem found
+    pub fn validate(&self, tag_value: &str) -> TagValidation {
+        let parts: Vec<&str> = tag_value.split(',').map(|s| s.trim()).collect();
+        let fields = self.fields();
+
+        let mut validation = TagValidation::valid();
+        if parts.iter().all(|p| p.is_empty()) {
+            validation.is_valid = false;
+            validation.errors.push("Tag value is empty".to_string());
+            validation
+                .suggestions
+                .push("Use format: 'status[,category][,priority]'".to_string());
+            return validation;
+        }
+        if parts.len() > fields.len() {
+            validation.is_valid = false;
+            validation.errors.push(format!(
+                "Too many fields: expected at most {}, found {}",
+                fields.len(),
+                parts.len()
+            ));
+            validation
+                .suggestions
+                .push("Use format: 'status[,category][,priority]'".to_string());
+        }
+        for (part, (name, allowed, deprecated)) in parts.iter().zip(fields) {
+            if !is_allowed(part, allowed) && replacement_for(part, allowed, deprecated).is_none() {
+                validation.is_valid = false;
+                validation
+                    .errors
+                    .push(format!("Unknown {}: '{}'", name, part));
+                validation.suggestions.push(format!(
+                    "Valid {} values: {}",
+                    name,
+                    allowed.join(", ")
+                ));
+            }
+        }
+        validation
+    }
+
+    /// Suggest a rewrite for one `@audit-tag` value, or `None` when it
+    /// needs no change. Deprecated spellings map with high confidence;
+    /// unknown values within two edits of exactly one allowed value are
+    /// suggested as ambiguous. Unknown values with no close match are left
+    /// to [`TagRules::validate`].
+    pub fn suggest_fix(&self, tag_value: &str) -> Option<(String, FixConfidence, Vec<String>)> {
+        let mut confidence = FixConfidence::High;
+        let mut notes = Vec::new();
+        let mut parts: Vec<String> = Vec::new();
+
+        for (i, part) in tag_value.split(',').enumerate() {
+            let word = part.trim();
+            let fixed = self
+                .fields()
+                .get(i)
+                .and_then(|(name, allowed, deprecated)| {
+                    if word.is_empty() || is_allowed(word, allowed) {
+                        return None;
+                    }
+                    if let Some(to) = replacement_for(word, allowed, deprecated) {
+                        notes.push(format!("{} '{}' is deprecated, use '{}'", name, word, to));
+                        return Some(to.to_string());
+                    }
+                    let word_lower = word.to_lowercase();
+                    let close: Vec<&String> = allowed
+                        .iter()
+                        .filter(|a| edit_distance(&word_lower, &a.to_lowercase()) <= 2)
+                        .collect();
+                    match close.as_slice() {
+                        [only] => {
+                            confidence = FixConfidence::Ambiguous;
+                            notes.push(format!(
+                                "unknown {} '{}', did you mean '{}'?",
+                                name, word, only
+                            ));
+                            Some(only.to_string())
+                        }
+                        _ => None,
+                    }
+                });
+            parts.push(match fixed {
+                // Swap the word but keep whatever spacing surrounded it
+                Some(to) => part.replacen(word, &to, 1),
+                None => part.to_string(),
+            });
+        }
+
+        if notes.is_empty() {
+            None
+        } else {
+            Some((parts.join(","), confidence, notes))
+        }
+    }
+}
+
+fn is_allowed(value: &str, allowed: &[String]) -> bool {
+    allowed.iter().any(|a| a.eq_ignore_ascii_case(value))
+}
+
+/// The replacement for a deprecated `value`, if it maps to an allowed one
+fn replacement_for<'a>(
+    value: &str,
+    allowed: &[String],
+    deprecated: &'a HashMap<String, String>,
+) -> Option<&'a str> {
+    deprecated
+        .iter()
+        .find(|(from, _)| from.eq_ignore_ascii_case(value))
+        .map(|(_, to)| to.as_str())
+        .filter(|to| is_allowed(to, allowed))
+}
+
+/// Levenshtein distance between two short strings
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut cur = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let cost = usize::from(ca != *cb);
+            cur.push((prev[j] + cost).min(prev[j + 1] + 1).min(cur[j] + 1));
+        }
+        prev = cur;
+    }
+    prev[b.len()]
 }
 
 /// Validate a tag value against the schema
@@ -520,6 +803,51 @@ mod tests {
         assert!(!invalid.errors.is_empty());
     }
 
+    #[test]
+    fn test_tag_rules_flag_bad_priority() {
+        let rules = TagRules::default();
+        assert!(rules.validate("new,security,high").is_valid);
+        assert!(rules.validate("Stable, perf").is_valid);
+
+        let bad = rules.validate("new,security,urgent");
+        assert!(!bad.is_valid);
+        assert_eq!(bad.errors, vec!["Unknown priority: 'urgent'"]);
+
+        assert!(!rules.validate("").is_valid);
+        assert!(!rules.validate("new,security,high,extra").is_valid);
+    }
+
+    #[test]
+    fn test_tag_rules_suggest_fix() {
+        let rules = TagRules::default();
+        assert_eq!(rules.suggest_fix("stable,security"), None);
+
+        let (fixed, confidence, notes) = rules.suggest_fix("Prod,tech-debt").unwrap();
+        assert_eq!(fixed, "stable,technical-debt");
+        assert_eq!(confidence, FixConfidence::High);
+        assert_eq!(notes.len(), 2);
+
+        // A typo near one value is a guess, not a mapping
+        let (fixed, confidence, _) = rules.suggest_fix("new,perf,hgih").unwrap();
+        assert_eq!(fixed, "new,performance,high");
+        assert_eq!(confidence, FixConfidence::Ambiguous);
+
+        // Nothing close enough to guess from
+        assert_eq!(rules.suggest_fix("new,security,urgent"), None);
+    }
+
+    #[test]
+    fn test_tag_rules_load_keeps_missing_defaults() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("tags.toml");
+        std::fs::write(&path, "categories = [\"security\", \"compliance\"]\n").unwrap();
+
+        let rules = TagRules::load(&path).unwrap();
+        assert!(rules.validate("new,compliance,low").is_valid);
+        assert!(!rules.validate("new,performance").is_valid);
+        assert_eq!(rules.priorities, TagRules::default().priorities);
+    }
+
     #[test]
     fn test_status_technical_debt() {
         assert!(CodeStatus::Deprecated.is_technical_debt());
@@ -1,8 +1,10 @@
 //! Tag scanner for detecting audit annotations in source code
 
 use crate::error::{AuditError, Result};
+use crate::tag_schema::{FixConfidence, TagFix, TagRules, TagValidation};
 use crate::types::{AuditTag, AuditTagType};
 use regex::Regex;
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::Path;
 use walkdir::WalkDir;
@@ -82,33 +84,123 @@ impl TagScanner {
         Ok(tags)
     }
 
-    /// Scan a directory recursively for audit tags
+    /// Scan a directory recursively for audit tags. Files that can't be
+    /// read are skipped; use [`TagScanner::scan_iter`] to see those errors
+    /// or to avoid holding every tag in memory.
     pub fn scan_directory(&self, dir: &Path) -> Result<Vec<AuditTag>> {
-        let mut all_tags = Vec::new();
+        Ok(self.scan_iter(dir).filter_map(|tag| tag.ok()).collect())
+    }
 
-        for entry in WalkDir::new(dir)
+    /// Stream the audit tags under `dir`, one file at a time. Tags within a
+    /// file come in line order; a file that can't be read yields one error
+    /// and the walk continues.
+    pub fn scan_iter<'a>(&'a self, dir: &Path) -> impl Iterator<Item = Result<AuditTag>> + 'a {
+        WalkDir::new(dir)
             .follow_links(false)
+            .sort_by_file_name()
             .into_iter()
             .filter_map(|e| e.ok())
-        {
-            let path = entry.path();
+            .map(|entry| entry.into_path())
+            .filter(|path| self.is_source_file(path) && !self.should_skip(path))
+            .flat_map(move |path| {
+                let (tags, error) = match self.scan_file(&path) {
+                    Ok(tags) => (tags, None),
+                    Err(e) => (Vec::new(), Some(Err(e.context(path.display().to_string())))),
+                };
+                tags.into_iter().map(Ok).chain(error)
+            })
+    }
+
+    /// Check `@audit-tag` values against `schema`, returning one failed
+    /// [`TagValidation`] per offending tag with its file and line set.
+    /// Other tag kinds carry free text and are not checked.
+    pub fn validate_against_schema(
+        &self,
+        tags: &[AuditTag],
+        schema: &TagRules,
+    ) -> Vec<TagValidation> {
+        tags.iter()
+            .filter(|tag| tag.tag_type == AuditTagType::Tag)
+            .map(|tag| schema.validate(&tag.value).at(&tag.file, tag.line))
+            .filter(|validation| !validation.is_valid)
+            .collect()
+    }
+
+    /// Suggest rewrites for `@audit-tag` values that use deprecated or
+    /// misspelled fields. See [`TagRules::suggest_fix`] for which fixes
+    /// count as high confidence.
+    pub fn suggest_fixes(&self, tags: &[AuditTag], schema: &TagRules) -> Vec<TagFix> {
+        tags.iter()
+            .filter(|tag| tag.tag_type == AuditTagType::Tag)
+            .filter_map(|tag| {
+                let (replacement, confidence, notes) = schema.suggest_fix(&tag.value)?;
+                Some(TagFix {
+                    file: tag.file.clone(),
+                    line: tag.line,
+                    original: tag.value.clone(),
+                    replacement,
+                    confidence,
+                    notes,
+                })
+            })
+            .collect()
+    }
 
-            // Skip non-source files
-            if !self.is_source_file(path) {
-                continue;
+    /// Rewrite the tag comments for every high-confidence fix, touching only
+    /// the tag value so the rest of each line is untouched. Ambiguous fixes
+    /// are skipped. Returns a line diff of the changes; with `dry_run` the
+    /// files are left alone and only the diff is produced.
+    pub fn apply_fixes(&self, fixes: &[TagFix], dry_run: bool) -> Result<String> {
+        let mut by_file: BTreeMap<&Path, Vec<&TagFix>> = BTreeMap::new();
+        for fix in fixes {
+            if fix.confidence == FixConfidence::High {
+                by_file.entry(&fix.file).or_default().push(fix);
             }
+        }
 
-            // Skip excluded directories
-            if self.should_skip(path) {
-                continue;
+        let prefix = AuditTagType::Tag.prefix();
+        let mut diff = String::new();
+        for (file, mut fixes) in by_file {
+            fixes.sort_by_key(|fix| fix.line);
+            let content = fs::read_to_string(file)?;
+            let mut lines: Vec<String> = content.split_inclusive('\n').map(String::from).collect();
+
+            diff.push_str(&format!("--- {}\n+++ {}\n", file.display(), file.display()));
+            for fix in fixes {
+                let stale = || {
+                    AuditError::InvalidTag(format!(
+                        "{}:{}: tag no longer reads '{}'",
+                        file.display(),
+                        fix.line,
+                        fix.original
+                    ))
+                };
+                let line = fix
+                    .line
+                    .checked_sub(1)
+                    .and_then(|i| lines.get_mut(i))
+                    .ok_or_else(stale)?;
+                let value_start = line.find(prefix).ok_or_else(stale)? + prefix.len();
+                let offset = line[value_start..].find(&fix.original).ok_or_else(stale)?;
+                let start = value_start + offset;
+                let fixed = format!(
+                    "{}{}{}",
+                    &line[..start],
+                    fix.replacement,
+                    &line[start + fix.original.len()..]
+                );
+
+                diff.push_str(&format!("@@ -{} +{} @@\n", fix.line, fix.line));
+                diff.push_str(&format!("-{}\n", line.trim_end_matches(['\r', '\n'])));
+                diff.push_str(&format!("+{}\n", fixed.trim_end_matches(['\r', '\n'])));
+                *line = fixed;
             }
 
-            if let Ok(tags) = self.scan_file(path) {
-                all_tags.extend(tags);
+            if !dry_run {
+                fs::write(file, lines.concat())?;
             }
         }
-
-        Ok(all_tags)
+        Ok(diff)
     }
 
     /// Extract context around a line
@@ -286,6 +378,108 @@ def process_data(data):
         assert_eq!(tags[1].tag_type, AuditTagType::Security);
     }
 
+    #[test]
+    fn test_scan_iter_streams_in_file_order() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("src")).unwrap();
+        std::fs::write(
+            dir.path().join("src/b.rs"),
+            "// @audit-todo: second\n// @audit-security: third\n",
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("src/a.rs"), "// @audit-tag: first\n").unwrap();
+        std::fs::write(dir.path().join("src/bad.py"), [0xff, 0xfe, 0x00]).unwrap();
+
+        let scanner = TagScanner::new().unwrap();
+        let items: Vec<Result<AuditTag>> = scanner.scan_iter(dir.path()).collect();
+        assert_eq!(items.iter().filter(|i| i.is_err()).count(), 1);
+        let values: Vec<String> = items
+            .into_iter()
+            .filter_map(|i| i.ok())
+            .map(|t| t.value)
+            .collect();
+        assert_eq!(values, vec!["first", "second", "third"]);
+
+        // scan_directory is the same stream, minus unreadable files
+        let all = scanner.scan_directory(dir.path()).unwrap();
+        assert_eq!(all.len(), 3);
+        assert_eq!(all[0].file, dir.path().join("src/a.rs"));
+    }
+
+    #[test]
+    fn test_validate_against_schema_flags_bad_priority() {
+        let scanner = TagScanner::new().unwrap();
+        let tag = |line: usize, tag_type: AuditTagType, value: &str| AuditTag {
+            tag_type,
+            file: PathBuf::from("src/lib.rs"),
+            line,
+            value: value.to_string(),
+            context: None,
+        };
+        let tags = vec![
+            tag(1, AuditTagType::Tag, "stable,security,high"),
+            tag(5, AuditTagType::Tag, "new,performance,urgent"),
+            tag(9, AuditTagType::Todo, "whatever,goes,here,too"),
+        ];
+
+        let violations = scanner.validate_against_schema(&tags, &TagRules::default());
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].file.as_deref(), Some(Path::new("src/lib.rs")));
+        assert_eq!(violations[0].line, Some(5));
+        assert!(violations[0].errors[0].contains("priority: 'urgent'"));
+    }
+
+    #[test]
+    fn test_fixes_round_trip_rescans_cleanly() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("lib.rs");
+        let original = "// @audit-tag: prod, perf ,high\r\nfn a() {}\n    # @audit-tag: new,debt\n// @audit-tag: stable,secruity\n";
+        std::fs::write(&path, original).unwrap();
+
+        let scanner = TagScanner::new().unwrap();
+        let rules = TagRules::default();
+        let tags = scanner.scan_file(&path).unwrap();
+        let fixes = scanner.suggest_fixes(&tags, &rules);
+        assert_eq!(fixes.len(), 3);
+        assert_eq!(fixes[0].replacement, "stable, performance ,high");
+        assert_eq!(fixes[0].confidence, FixConfidence::High);
+        assert_eq!(fixes[1].replacement, "new,technical-debt");
+        assert_eq!(fixes[2].confidence, FixConfidence::Ambiguous);
+
+        let diff = scanner.apply_fixes(&fixes, true).unwrap();
+        assert!(diff.contains("-// @audit-tag: prod, perf ,high\n"));
+        assert!(diff.contains("+// @audit-tag: stable, performance ,high\n"));
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), original);
+
+        scanner.apply_fixes(&fixes, false).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "// @audit-tag: stable, performance ,high\r\nfn a() {}\n    # @audit-tag: new,technical-debt\n// @audit-tag: stable,secruity\n"
+        );
+
+        // Only the ambiguous typo is left, for manual review
+        let rescanned = scanner.scan_file(&path).unwrap();
+        let remaining = scanner.suggest_fixes(&rescanned, &rules);
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].line, 4);
+        let violations = scanner.validate_against_schema(&rescanned, &rules);
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].line, Some(4));
+    }
+
+    #[test]
+    fn test_apply_fixes_rejects_stale_tags() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("lib.rs");
+        std::fs::write(&path, "// @audit-tag: prod\n").unwrap();
+
+        let scanner = TagScanner::new().unwrap();
+        let tags = scanner.scan_file(&path).unwrap();
+        let fixes = scanner.suggest_fixes(&tags, &TagRules::default());
+        std::fs::write(&path, "// @audit-tag: stable\n").unwrap();
+        assert!(scanner.apply_fixes(&fixes, false).is_err());
+    }
+
     #[test]
     fn test_group_by_type() {
         let scanner = TagScanner::new().unwrap();
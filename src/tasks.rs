@@ -1,10 +1,14 @@
 //! Task generator for converting audit findings into actionable tasks
 
 use crate::error::{AuditError, Result};
+use crate::llm_audit::{FullAuditResult, Recommendation, RegularAuditResult};
+use crate::static_analysis::content_hash;
 use crate::types::{
     AuditTag, AuditTagType, Category, FileAnalysis, Issue, IssueSeverity, Task, TaskPriority,
 };
-use std::collections::HashMap;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
 
 /// Task generator
 pub struct TaskGenerator {
@@ -23,6 +27,15 @@ impl TaskGenerator {
         }
     }
 
+    /// A generator holding tasks produced elsewhere, e.g. loaded from a
+    /// store or filtered by [`TaskGenerator::dedup_against`]
+    pub fn from_tasks(tasks: Vec<Task>) -> Self {
+        Self {
+            counter: tasks.len(),
+            tasks,
+        }
+    }
+
     /// Generate tasks from audit tags
     pub fn generate_from_tags(&mut self, tags: &[AuditTag]) -> Result<Vec<Task>> {
         for tag in tags {
@@ -47,6 +60,7 @@ impl TaskGenerator {
             }
         }
 
+        self.link_dependencies();
         Ok(self.tasks.clone())
     }
 
@@ -91,9 +105,130 @@ impl TaskGenerator {
             }
         }
 
+        self.link_dependencies();
         Ok(self.tasks.clone())
     }
 
+    /// Generate tasks from a regular LLM audit: one per security concern,
+    /// tech-debt area and recommendation. Findings that repeat a task this
+    /// generator already holds (e.g. from static analysis) are skipped.
+    pub fn generate_from_llm_audit(&mut self, result: &RegularAuditResult) -> Result<Vec<Task>> {
+        for concern in &result.security_concerns {
+            let file = llm_task_file(concern.affected_areas.iter().map(String::as_str));
+            let mut description = concern.description.clone();
+            if !concern.affected_areas.is_empty() {
+                description.push_str(&format!(
+                    "\n\nAffected: {}",
+                    concern.affected_areas.join(", ")
+                ));
+            }
+            description.push_str(&format!("\n\nRecommendation: {}", concern.recommendation));
+
+            let task = Task::new(
+                format!("Security: {}", concern.description),
+                description,
+                file.clone(),
+                None,
+                priority_from_label(&concern.severity),
+                Category::from_path(&file.to_string_lossy()),
+            )
+            .with_tag("security")
+            .with_tag("from-llm-audit");
+            self.add_llm_task(task);
+        }
+
+        for area in &result.tech_debt_areas {
+            let task = Task::new(
+                format!("Tech debt: {}", area.area),
+                format!("Impact: {}\n\nEffort: {}", area.impact, area.effort),
+                PathBuf::from(PROJECT_WIDE),
+                None,
+                priority_from_label(&area.severity),
+                Category::Other,
+            )
+            .with_tag("technical-debt")
+            .with_tag("from-llm-audit");
+            self.add_llm_task(task);
+        }
+
+        for rec in &result.recommendations {
+            self.add_recommendation_task(rec);
+        }
+
+        Ok(self.tasks.clone())
+    }
+
+    /// Generate tasks from a full LLM audit: each file's security
+    /// observations plus the audit's recommendations, de-duplicated like
+    /// [`TaskGenerator::generate_from_llm_audit`]
+    pub fn generate_from_full_audit(&mut self, result: &FullAuditResult) -> Result<Vec<Task>> {
+        for analysis in &result.file_analyses {
+            for observation in &analysis.llm_analysis.security_observations {
+                let task = Task::new(
+                    format!("Security: {}", observation),
+                    format!(
+                        "Security observation for {}: {}",
+                        analysis.path.display(),
+                        observation
+                    ),
+                    analysis.path.clone(),
+                    None,
+                    priority_from_label(&analysis.llm_analysis.importance),
+                    Category::from_path(&analysis.path.to_string_lossy()),
+                )
+                .with_tag("security")
+                .with_tag("from-llm-audit");
+                self.add_llm_task(task);
+            }
+        }
+
+        for rec in &result.recommendations {
+            self.add_recommendation_task(rec);
+        }
+
+        Ok(self.tasks.clone())
+    }
+
+    /// Add a task for an LLM recommendation
+    fn add_recommendation_task(&mut self, rec: &Recommendation) {
+        let file = llm_task_file(rec.files.iter().filter_map(|f| f.to_str()));
+        let mut description = rec.recommendation.clone();
+        if !rec.benefit.is_empty() {
+            description.push_str(&format!("\n\nBenefit: {}", rec.benefit));
+        }
+        if rec.files.len() > 1 {
+            let files: Vec<String> = rec.files.iter().map(|f| f.display().to_string()).collect();
+            description.push_str(&format!("\n\nFiles: {}", files.join(", ")));
+        }
+
+        let task = Task::new(
+            format!("{}: {}", rec.category, rec.recommendation),
+            description,
+            file.clone(),
+            None,
+            priority_from_label(&rec.priority),
+            Category::from_path(&file.to_string_lossy()),
+        )
+        .with_tag(rec.category.to_lowercase().replace(' ', "-"))
+        .with_tag(format!("effort-{}", rec.effort.to_lowercase()))
+        .with_tag("from-llm-audit");
+        self.add_llm_task(task);
+    }
+
+    /// Add an LLM-derived task unless it repeats one already held
+    fn add_llm_task(&mut self, task: Task) {
+        let key = task.dedup_key();
+        if self
+            .tasks
+            .iter()
+            .any(|t| t.dedup_key() == key || same_finding(t, &task))
+        {
+            return;
+        }
+        self.tasks.push(task);
+        self.counter += 1;
+    }
+
     /// Add a TODO task
     fn add_todo_task(&mut self, tag: &AuditTag) -> Result<()> {
         let task = Task::new(
@@ -263,6 +398,177 @@ impl TaskGenerator {
         Ok(())
     }
 
+    /// Infer dependencies between tasks on the same file: frozen-code
+    /// violations come first, then security work, then everything else by
+    /// priority, with documentation last. Each task depends on the tasks in
+    /// the nearest earlier stage, so the links always form chains. Existing
+    /// dependencies are kept.
+    pub fn link_dependencies(&mut self) {
+        let mut by_file: HashMap<&std::path::Path, Vec<usize>> = HashMap::new();
+        for (i, task) in self.tasks.iter().enumerate() {
+            by_file.entry(task.file.as_path()).or_default().push(i);
+        }
+
+        let mut links: Vec<(usize, String)> = Vec::new();
+        for indices in by_file.values() {
+            let mut stages: Vec<((u8, TaskPriority), Vec<usize>)> = Vec::new();
+            for &i in indices {
+                let stage = Self::stage(&self.tasks[i]);
+                match stages.iter_mut().find(|(s, _)| *s == stage) {
+                    Some((_, members)) => members.push(i),
+                    None => stages.push((stage, vec![i])),
+                }
+            }
+            stages.sort_by_key(|(stage, _)| *stage);
+
+            for pair in stages.windows(2) {
+                for &later in &pair[1].1 {
+                    for &earlier in &pair[0].1 {
+                        links.push((later, self.tasks[earlier].id.clone()));
+                    }
+                }
+            }
+        }
+
+        for (i, id) in links {
+            if !self.tasks[i].depends_on.contains(&id) {
+                self.tasks[i].depends_on.push(id);
+            }
+        }
+    }
+
+    /// Ordering stage of a task within its file; lower runs first
+    fn stage(task: &Task) -> (u8, TaskPriority) {
+        let has = |tag: &str| task.tags.iter().any(|t| t == tag);
+        let class = if has("frozen-violation") {
+            0
+        } else if has("security") {
+            1
+        } else if has("documentation") {
+            3
+        } else {
+            2
+        };
+        (class, task.priority)
+    }
+
+    /// Tasks ordered so every task comes after its dependencies. Among
+    /// tasks that are ready at the same time, higher priority goes first.
+    /// Dependencies on unknown task IDs are ignored; a cycle is an error
+    /// naming the tasks involved.
+    pub fn topological_order(&self) -> Result<Vec<Task>> {
+        let index: HashMap<&str, usize> = self
+            .tasks
+            .iter()
+            .enumerate()
+            .map(|(i, t)| (t.id.as_str(), i))
+            .collect();
+        let deps: Vec<Vec<usize>> = self
+            .tasks
+            .iter()
+            .map(|t| {
+                let mut deps: Vec<usize> = t
+                    .depends_on
+                    .iter()
+                    .filter_map(|id| index.get(id.as_str()).copied())
+                    .collect();
+                deps.sort_unstable();
+                deps.dedup();
+                deps
+            })
+            .collect();
+
+        let mut waiting: Vec<usize> = deps.iter().map(|d| d.len()).collect();
+        let mut dependents: Vec<Vec<usize>> = vec![Vec::new(); self.tasks.len()];
+        for (i, task_deps) in deps.iter().enumerate() {
+            for &d in task_deps {
+                dependents[d].push(i);
+            }
+        }
+
+        let mut ready: Vec<usize> = (0..self.tasks.len()).filter(|&i| waiting[i] == 0).collect();
+        let mut order = Vec::with_capacity(self.tasks.len());
+        while !ready.is_empty() {
+            // Highest priority first, then generation order
+            let pos = (0..ready.len())
+                .min_by_key(|&p| (self.tasks[ready[p]].priority, ready[p]))
+                .unwrap_or(0);
+            let next = ready.remove(pos);
+            order.push(next);
+            for &dependent in &dependents[next] {
+                waiting[dependent] -= 1;
+                if waiting[dependent] == 0 {
+                    ready.push(dependent);
+                }
+            }
+        }
+
+        if order.len() < self.tasks.len() {
+            let cycle = Self::find_cycle(&deps, &waiting);
+            let ids: Vec<&str> = cycle.iter().map(|&i| self.tasks[i].id.as_str()).collect();
+            return Err(AuditError::TaskGeneration(format!(
+                "dependency cycle: {}",
+                ids.join(" -> ")
+            )));
+        }
+
+        Ok(order.into_iter().map(|i| self.tasks[i].clone()).collect())
+    }
+
+    /// Walk unfinished dependencies from a stuck task until one repeats.
+    /// Every task left waiting has an unfinished dependency, so this ends.
+    fn find_cycle(deps: &[Vec<usize>], waiting: &[usize]) -> Vec<usize> {
+        let stuck = |i: usize| waiting[i] > 0;
+        let Some(mut current) = (0..deps.len()).find(|&i| stuck(i)) else {
+            return Vec::new();
+        };
+        let mut path = Vec::new();
+        let mut seen = HashSet::new();
+        while seen.insert(current) {
+            path.push(current);
+            match deps[current].iter().copied().find(|&d| stuck(d)) {
+                Some(next) => current = next,
+                None => return path,
+            }
+        }
+        let start = path.iter().position(|&i| i == current).unwrap_or(0);
+        let mut cycle = path.split_off(start);
+        cycle.push(current);
+        cycle
+    }
+
+    /// The chain of dependent tasks with the largest total
+    /// [`Task::estimated_effort`], listed from first to last
+    pub fn critical_path(&self) -> Result<Vec<Task>> {
+        let order = self.topological_order()?;
+        let position: HashMap<&str, usize> = order
+            .iter()
+            .enumerate()
+            .map(|(i, t)| (t.id.as_str(), i))
+            .collect();
+
+        // Longest effort-weighted chain ending at each task
+        let mut best: Vec<(u32, Option<usize>)> = Vec::with_capacity(order.len());
+        for task in &order {
+            let previous = task
+                .depends_on
+                .iter()
+                .filter_map(|id| position.get(id.as_str()).copied())
+                .max_by_key(|&p| (best[p].0, std::cmp::Reverse(p)));
+            let carried = previous.map_or(0, |p| best[p].0);
+            best.push((carried + task.estimated_effort(), previous));
+        }
+
+        let mut end = (0..order.len()).max_by_key(|&i| (best[i].0, std::cmp::Reverse(i)));
+        let mut path = Vec::new();
+        while let Some(i) = end {
+            path.push(order[i].clone());
+            end = best[i].1;
+        }
+        path.reverse();
+        Ok(path)
+    }
+
     /// Get all tasks
     pub fn tasks(&self) -> &[Task] {
         &self.tasks
@@ -332,6 +638,92 @@ impl TaskGenerator {
         Ok(csv)
     }
 
+    /// Tasks from this run that match none of `existing` by
+    /// [`Task::dedup_key`]. Repeats within this run are dropped as well, so
+    /// each net-new task appears once.
+    pub fn dedup_against(&self, existing: &[Task]) -> Vec<Task> {
+        let mut seen: HashSet<String> = existing.iter().map(Task::dedup_key).collect();
+        self.tasks
+            .iter()
+            .filter(|task| seen.insert(task.dedup_key()))
+            .cloned()
+            .collect()
+    }
+
+    /// Tasks in `existing` that this run no longer produces; their issue
+    /// has presumably been fixed, so they can be closed
+    pub fn resolved_since(&self, existing: &[Task]) -> Vec<Task> {
+        let current: HashSet<String> = self.tasks.iter().map(Task::dedup_key).collect();
+        existing
+            .iter()
+            .filter(|task| !current.contains(&task.dedup_key()))
+            .cloned()
+            .collect()
+    }
+
+    /// Render tasks as a Markdown board with one column per priority, for
+    /// pasting into a README or project page. Each card is an unchecked
+    /// checkbox with a link to its file and the task's estimated effort;
+    /// cards within a column are sorted by file, then line.
+    pub fn to_markdown_board(&self) -> String {
+        let columns = [
+            (TaskPriority::Critical, "🔴 Critical"),
+            (TaskPriority::High, "🟠 High"),
+            (TaskPriority::Medium, "🟡 Medium"),
+            (TaskPriority::Low, "🟢 Low"),
+        ];
+        let total_effort: u32 = self.tasks.iter().map(|t| t.estimated_effort()).sum();
+
+        let mut board = String::from("# Task Board\n\n");
+        board.push_str(&format!(
+            "{} tasks · {} points\n",
+            self.tasks.len(),
+            total_effort
+        ));
+
+        for (priority, heading) in columns {
+            let mut cards = self.tasks_by_priority(priority);
+            if cards.is_empty() {
+                continue;
+            }
+            cards.sort_by(|a, b| a.file.cmp(&b.file).then(a.line.cmp(&b.line)));
+            let effort: u32 = cards.iter().map(|t| t.estimated_effort()).sum();
+
+            board.push_str(&format!(
+                "\n## {} ({} · {} pts)\n\n",
+                heading,
+                cards.len(),
+                effort
+            ));
+            for task in cards {
+                let file = task.file.display().to_string().replace('\\', "/");
+                let link = match task.line {
+                    Some(line) => format!("[{}:{}]({}#L{})", file, line, file, line),
+                    None => format!("[{}]({})", file, file),
+                };
+                board.push_str(&format!(
+                    "- [ ] **{}** — {} · {} pts\n",
+                    task.title.replace('\n', " "),
+                    link,
+                    task.estimated_effort()
+                ));
+            }
+        }
+        board
+    }
+
+    /// GitHub issue payloads for every task, one per distinct task. Each
+    /// body ends with a [`GitHubIssueSpec::marker`] derived from the task's
+    /// file and title, so a re-run can recognise issues it already opened.
+    pub fn to_github_issues(&self) -> Vec<GitHubIssueSpec> {
+        let mut seen = HashSet::new();
+        self.tasks
+            .iter()
+            .map(GitHubIssueSpec::from_task)
+            .filter(|spec| seen.insert(spec.marker.clone()))
+            .collect()
+    }
+
     /// Clear all tasks
     pub fn clear(&mut self) {
         self.tasks.clear();
@@ -339,6 +731,102 @@ impl TaskGenerator {
     }
 }
 
+/// File recorded for tasks that apply to the whole project
+const PROJECT_WIDE: &str = ".";
+
+/// Share of distinct words two findings on the same file must have in
+/// common to count as the same issue
+const SAME_FINDING_OVERLAP: f64 = 0.5;
+
+/// Task priority from an LLM label (Critical, High, Medium, Low)
+fn priority_from_label(label: &str) -> TaskPriority {
+    match label.trim().to_lowercase().as_str() {
+        "critical" => TaskPriority::Critical,
+        "high" => TaskPriority::High,
+        "medium" => TaskPriority::Medium,
+        _ => TaskPriority::Low,
+    }
+}
+
+/// The first entry that looks like a file path (has an extension), else
+/// the project as a whole
+fn llm_task_file<'a>(areas: impl IntoIterator<Item = &'a str>) -> PathBuf {
+    areas
+        .into_iter()
+        .map(str::trim)
+        .find(|area| !area.contains(' ') && Path::new(area).extension().is_some())
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(PROJECT_WIDE))
+}
+
+/// Whether two tasks on the same file describe the same issue: at least
+/// [`SAME_FINDING_OVERLAP`] of the smaller task's words (four letters or
+/// more, from title and description) also appear in the other's
+fn same_finding(a: &Task, b: &Task) -> bool {
+    if a.file != b.file || a.file == Path::new(PROJECT_WIDE) {
+        return false;
+    }
+    let words = |t: &Task| -> HashSet<String> {
+        format!("{} {}", t.title, t.description)
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|w| w.len() >= 4)
+            .map(str::to_lowercase)
+            .collect()
+    };
+    let (a, b) = (words(a), words(b));
+    let smaller = a.len().min(b.len());
+    smaller > 0 && a.intersection(&b).count() as f64 >= SAME_FINDING_OVERLAP * smaller as f64
+}
+
+/// A GitHub issue to open for a generated task
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct GitHubIssueSpec {
+    /// Issue title
+    pub title: String,
+    /// Markdown body, ending with the marker comment
+    pub body: String,
+    /// Labels to apply
+    pub labels: Vec<String>,
+    /// Hidden HTML comment identifying the task across runs
+    pub marker: String,
+}
+
+impl GitHubIssueSpec {
+    fn from_task(task: &Task) -> Self {
+        let hash = content_hash(&format!("{}\n{}", task.file.display(), task.title));
+        let marker = format!("<!-- rustassistant-task:{} -->", &hash[..16]);
+
+        let location = match task.line {
+            Some(line) => format!("{}:{}", task.file.display(), line),
+            None => task.file.display().to_string(),
+        };
+        let mut body = String::new();
+        if !task.description.is_empty() {
+            body.push_str(&task.description);
+            body.push_str("\n\n");
+        }
+        body.push_str(&format!("**File:** `{}`\n", location));
+        body.push_str(&format!("**Category:** {:?}\n", task.category));
+        if !task.tags.is_empty() {
+            body.push_str(&format!("**Tags:** {}\n", task.tags.join(", ")));
+        }
+        body.push_str(&format!("\n{}\n", marker));
+
+        let priority = format!("{:?}", task.priority).to_lowercase();
+        Self {
+            title: task.title.clone(),
+            body,
+            labels: vec!["audit".to_string(), format!("priority: {}", priority)],
+            marker,
+        }
+    }
+
+    /// Whether an existing issue body carries this spec's marker
+    pub fn matches(&self, existing_body: &str) -> bool {
+        existing_body.contains(&self.marker)
+    }
+}
+
 impl Default for TaskGenerator {
     fn default() -> Self {
         Self::new()
@@ -375,7 +863,6 @@ impl TaskStatistics {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::path::PathBuf;
 
     #[test]
     fn test_generate_from_todo_tag() {
@@ -605,4 +1092,292 @@ mod tests {
         assert!(frozen_task.is_some());
         assert_eq!(frozen_task.unwrap().priority, TaskPriority::Critical);
     }
+
+    fn task(title: &str, file: &str, priority: TaskPriority, tag: &str) -> Task {
+        Task::new(
+            title,
+            "",
+            PathBuf::from(file),
+            None,
+            priority,
+            Category::Other,
+        )
+        .with_tag(tag)
+    }
+
+    fn titles(tasks: &[Task]) -> Vec<&str> {
+        tasks.iter().map(|t| t.title.as_str()).collect()
+    }
+
+    #[test]
+    fn test_dependency_ordering() {
+        let mut generator = TaskGenerator::new();
+        generator.tasks = vec![
+            task("docs", "a.rs", TaskPriority::Low, "documentation"),
+            task("refactor", "a.rs", TaskPriority::Medium, "code-quality"),
+            task("other file", "b.rs", TaskPriority::High, "todo"),
+            task("fix auth", "a.rs", TaskPriority::High, "security"),
+            task("frozen", "a.rs", TaskPriority::Critical, "frozen-violation"),
+        ];
+        generator.link_dependencies();
+
+        let ids: Vec<String> = generator.tasks.iter().map(|t| t.id.clone()).collect();
+        assert_eq!(generator.tasks[1].depends_on, vec![ids[3].clone()]);
+        assert_eq!(generator.tasks[3].depends_on, vec![ids[4].clone()]);
+        assert!(generator.tasks[2].depends_on.is_empty());
+
+        // Equal priorities fall back to generation order
+        let order = generator.topological_order().unwrap();
+        assert_eq!(
+            titles(&order),
+            vec!["frozen", "other file", "fix auth", "refactor", "docs"]
+        );
+
+        // 8 + 5 + 3 + 1 along a.rs beats the lone task in b.rs
+        let path = generator.critical_path().unwrap();
+        assert_eq!(
+            titles(&path),
+            vec!["frozen", "fix auth", "refactor", "docs"]
+        );
+
+        // Linking again doesn't duplicate edges
+        generator.link_dependencies();
+        assert_eq!(generator.tasks[1].depends_on.len(), 1);
+    }
+
+    #[test]
+    fn test_dependency_cycle_is_reported() {
+        let mut generator = TaskGenerator::new();
+        let a = task("a", "a.rs", TaskPriority::High, "todo");
+        let b = task("b", "b.rs", TaskPriority::High, "todo").depends_on(&a.id);
+        let c = task("c", "c.rs", TaskPriority::High, "todo").depends_on(&b.id);
+        let a = a.depends_on(&c.id);
+        let free = task("free", "d.rs", TaskPriority::Low, "todo");
+        generator.tasks = vec![free, a, b, c];
+
+        let err = generator.topological_order().unwrap_err().to_string();
+        assert!(err.contains("dependency cycle"), "{}", err);
+        for id in generator.tasks[1..].iter().map(|t| &t.id) {
+            assert!(err.contains(id.as_str()), "{}", err);
+        }
+        assert!(generator.critical_path().is_err());
+    }
+
+    #[test]
+    fn test_github_issues_carry_stable_marker() {
+        let mut generator = TaskGenerator::new();
+        generator.tasks = vec![
+            task(
+                "Security: check input",
+                "src/auth.rs",
+                TaskPriority::Critical,
+                "security",
+            ),
+            task("TODO: tidy", "src/lib.rs", TaskPriority::Low, "todo"),
+        ];
+        generator.tasks[0].line = Some(12);
+        // Same file and title as the first task: one issue, not two
+        generator.tasks.push(generator.tasks[0].clone());
+
+        let issues = generator.to_github_issues();
+        assert_eq!(issues.len(), 2);
+        assert_eq!(issues[0].title, "Security: check input");
+        assert!(issues[0].body.contains("**File:** `src/auth.rs:12`"));
+        assert!(issues[0].body.ends_with(&format!("{}\n", issues[0].marker)));
+        assert_eq!(issues[0].labels, vec!["audit", "priority: critical"]);
+
+        // A fresh generator (new task IDs, shifted line) maps to the same marker
+        let mut rerun = TaskGenerator::new();
+        rerun.tasks = vec![task(
+            "Security: check input",
+            "src/auth.rs",
+            TaskPriority::High,
+            "security",
+        )];
+        let again = rerun.to_github_issues();
+        assert_eq!(again[0].marker, issues[0].marker);
+        assert!(again[0].matches(&issues[0].body));
+        assert!(!issues[1].matches(&issues[0].body));
+    }
+
+    #[test]
+    fn test_markdown_board_golden() {
+        let mut generator = TaskGenerator::new();
+        generator.tasks = vec![
+            task(
+                "TODO: tidy imports",
+                "src/lib.rs",
+                TaskPriority::Low,
+                "todo",
+            ),
+            task(
+                "Security: check input",
+                "src/auth.rs",
+                TaskPriority::Critical,
+                "security",
+            ),
+            task(
+                "Review: retry loop",
+                "src/net.rs",
+                TaskPriority::Medium,
+                "review",
+            ),
+            task(
+                "TODO: split parser",
+                "src/lib.rs",
+                TaskPriority::Medium,
+                "todo",
+            ),
+            task(
+                "Add documentation: src/api.rs",
+                "src/api.rs",
+                TaskPriority::Low,
+                "documentation",
+            ),
+        ];
+        generator.tasks[1].line = Some(42);
+        generator.tasks[2].line = Some(7);
+        generator.tasks[3].line = Some(3);
+
+        let expected = "\
+# Task Board
+
+5 tasks · 16 points
+
+## 🔴 Critical (1 · 8 pts)
+
+- [ ] **Security: check input** — [src/auth.rs:42](src/auth.rs#L42) · 8 pts
+
+## 🟡 Medium (2 · 6 pts)
+
+- [ ] **TODO: split parser** — [src/lib.rs:3](src/lib.rs#L3) · 3 pts
+- [ ] **Review: retry loop** — [src/net.rs:7](src/net.rs#L7) · 3 pts
+
+## 🟢 Low (2 · 2 pts)
+
+- [ ] **Add documentation: src/api.rs** — [src/api.rs](src/api.rs) · 1 pts
+- [ ] **TODO: tidy imports** — [src/lib.rs](src/lib.rs) · 1 pts
+";
+        assert_eq!(generator.to_markdown_board(), expected);
+    }
+
+    #[test]
+    fn test_second_run_only_surfaces_new_tasks() {
+        let tags = |values: &[(&str, usize)]| -> Vec<AuditTag> {
+            values
+                .iter()
+                .map(|(value, line)| AuditTag {
+                    tag_type: AuditTagType::Todo,
+                    file: PathBuf::from("src/lib.rs"),
+                    line: *line,
+                    value: value.to_string(),
+                    context: None,
+                })
+                .collect()
+        };
+
+        let mut first = TaskGenerator::new();
+        let stored = first
+            .generate_from_tags(&tags(&[("Handle errors", 10), ("Retry 3 times", 20)]))
+            .unwrap();
+
+        // Lines shift, one task is new, one is gone, one differs only in
+        // case and numbers
+        let mut second = TaskGenerator::new();
+        second
+            .generate_from_tags(&tags(&[
+                ("handle   errors", 14),
+                ("Retry 5 times", 24),
+                ("Add metrics", 30),
+                ("Add metrics", 31),
+            ]))
+            .unwrap();
+
+        let fresh = second.dedup_against(&stored);
+        assert_eq!(titles(&fresh), vec!["TODO: Add metrics"]);
+        assert!(second.resolved_since(&stored).is_empty());
+
+        let mut third = TaskGenerator::new();
+        third
+            .generate_from_tags(&tags(&[("Handle errors", 10)]))
+            .unwrap();
+        assert!(third.dedup_against(&stored).is_empty());
+        assert_eq!(
+            titles(&third.resolved_since(&stored)),
+            vec!["TODO: Retry 3 times"]
+        );
+    }
+
+    #[test]
+    fn test_llm_audit_tasks_skip_static_duplicates() {
+        use crate::llm_audit::{AuditMode, SecurityConcern, TechDebtArea};
+
+        let mut generator = TaskGenerator::new();
+        generator
+            .generate_from_tags(&[AuditTag {
+                tag_type: AuditTagType::Security,
+                file: PathBuf::from("src/auth.rs"),
+                line: 12,
+                value: "API key logged in plaintext".to_string(),
+                context: None,
+            }])
+            .unwrap();
+
+        let concern = |description: &str, areas: &[&str]| SecurityConcern {
+            severity: "High".to_string(),
+            description: description.to_string(),
+            affected_areas: areas.iter().map(|a| a.to_string()).collect(),
+            recommendation: "Redact secrets before logging".to_string(),
+        };
+        let audit = RegularAuditResult {
+            mode: AuditMode::Regular,
+            architecture_assessment: String::new(),
+            patterns: Vec::new(),
+            security_concerns: vec![
+                // Same issue as the static tag
+                concern("API key is logged in plaintext", &["src/auth.rs"]),
+                concern(
+                    "Session tokens never expire",
+                    &["session handling", "src/session.rs"],
+                ),
+            ],
+            quality_observations: Vec::new(),
+            tech_debt_areas: vec![TechDebtArea {
+                area: "Duplicated config parsing".to_string(),
+                severity: "Medium".to_string(),
+                effort: "Small".to_string(),
+                impact: "Drift between services".to_string(),
+            }],
+            recommendations: vec![Recommendation {
+                priority: "Low".to_string(),
+                category: "Testing".to_string(),
+                recommendation: "Add integration tests for the CLI".to_string(),
+                benefit: "Catch regressions".to_string(),
+                effort: "Large".to_string(),
+                files: vec![PathBuf::from("src/bin/cli.rs")],
+                priority_score: 0.0,
+            }],
+            overall_health: 70.0,
+            confidence: 80.0,
+        };
+
+        let tasks = generator.generate_from_llm_audit(&audit).unwrap();
+        assert_eq!(
+            titles(&tasks),
+            vec![
+                "Security: API key logged in plaintext",
+                "Security: Session tokens never expire",
+                "Tech debt: Duplicated config parsing",
+                "Testing: Add integration tests for the CLI",
+            ]
+        );
+        assert_eq!(tasks[1].file, PathBuf::from("src/session.rs"));
+        assert_eq!(tasks[1].priority, TaskPriority::High);
+        assert_eq!(tasks[2].file, PathBuf::from("."));
+        assert_eq!(tasks[3].priority, TaskPriority::Low);
+        assert!(tasks[3].tags.contains(&"from-llm-audit".to_string()));
+
+        // Feeding the same audit again adds nothing
+        assert_eq!(generator.generate_from_llm_audit(&audit).unwrap().len(), 4);
+    }
 }
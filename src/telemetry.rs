@@ -10,6 +10,8 @@
 //! - **OTLP Export**: Compatible with Jaeger, Tempo, Honeycomb, etc.
 //! - **Sampling**: Configurable trace sampling
 //! - **Resource Detection**: Automatic service metadata
+//! - **Request IDs**: Per-request correlation ids on every log line, with
+//!   JSON logs when `LOG_FORMAT=json`
 //!
 //! # Example
 //!
@@ -34,6 +36,12 @@
 //! ```
 
 use anyhow::{Context, Result};
+use axum::{
+    extract::Request,
+    http::{HeaderName, HeaderValue},
+    middleware::Next,
+    response::Response,
+};
 use opentelemetry::{global, KeyValue};
 use opentelemetry_otlp::WithExportConfig;
 use opentelemetry_sdk::{
@@ -41,6 +49,8 @@ use opentelemetry_sdk::{
     Resource,
 };
 use opentelemetry_semantic_conventions as semconv;
+use std::time::Instant;
+use tracing::Instrument;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, EnvFilter, Layer};
 
 // ============================================================================
@@ -201,18 +211,43 @@ pub async fn init_telemetry(config: TelemetryConfig) -> Result<()> {
 
 /// Initialize basic logging without telemetry
 fn init_basic_logging(config: &TelemetryConfig) {
+    init_logging(&config.log_level);
+}
+
+/// Whether `LOG_FORMAT=json` asks for JSON log lines
+pub fn json_logs_enabled() -> bool {
+    std::env::var("LOG_FORMAT").is_ok_and(|format| format.eq_ignore_ascii_case("json"))
+}
+
+/// Initialize stdout logging, filtered by `RUST_LOG` or `default_filter`.
+///
+/// Lines are plain text unless `LOG_FORMAT=json`, in which case each line
+/// is a JSON object carrying the fields of every enclosing span (such as
+/// the `request_id` from [`request_id_middleware`]). Does nothing if a
+/// global subscriber is already set.
+pub fn init_logging(default_filter: &str) {
     let env_filter =
-        EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(&config.log_level));
+        EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(default_filter));
+
+    let fmt_layer = if json_logs_enabled() {
+        tracing_subscriber::fmt::layer()
+            .json()
+            .with_current_span(true)
+            .with_span_list(true)
+            .boxed()
+    } else {
+        tracing_subscriber::fmt::layer()
+            .with_target(true)
+            .with_thread_ids(false)
+            .with_level(true)
+            .boxed()
+    };
 
-    tracing_subscriber::registry()
+    // A subscriber the host already installed (e.g. the CLI's) wins
+    let _ = tracing_subscriber::registry()
         .with(env_filter)
-        .with(
-            tracing_subscriber::fmt::layer()
-                .with_target(true)
-                .with_thread_ids(false)
-                .with_level(true),
-        )
-        .init();
+        .with(fmt_layer)
+        .try_init();
 }
 
 /// Build OpenTelemetry resource
@@ -242,6 +277,68 @@ pub async fn shutdown_telemetry() {
     global::shutdown_tracer_provider();
 }
 
+// ============================================================================
+// Request IDs
+// ============================================================================
+
+/// Header carrying the request correlation id, in both directions
+pub const REQUEST_ID_HEADER: HeaderName = HeaderName::from_static("x-request-id");
+
+/// Correlation id of the current request, available to handlers as an
+/// `Extension<RequestId>`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestId(pub String);
+
+/// Reuse the caller's `X-Request-Id` if it is a sane token, otherwise
+/// generate one
+fn request_id_from(value: Option<&HeaderValue>) -> String {
+    value
+        .and_then(|v| v.to_str().ok())
+        .map(str::trim)
+        .filter(|id| !id.is_empty() && id.len() <= 128 && id.chars().all(|c| c.is_ascii_graphic()))
+        .map(str::to_string)
+        .unwrap_or_else(|| uuid::Uuid::new_v4().to_string())
+}
+
+/// Middleware giving every request a correlation id.
+///
+/// Everything logged while the request is handled, including the start
+/// and finish lines with status and latency, happens inside a `request`
+/// span with the id, so JSON logs can be grouped by it. The id is echoed
+/// in the response's `X-Request-Id`.
+pub async fn request_id_middleware(mut request: Request, next: Next) -> Response {
+    let request_id = request_id_from(request.headers().get(&REQUEST_ID_HEADER));
+    request
+        .extensions_mut()
+        .insert(RequestId(request_id.clone()));
+
+    let span = tracing::info_span!(
+        "request",
+        request_id = %request_id,
+        method = %request.method(),
+        path = %request.uri().path(),
+    );
+
+    let mut response = async move {
+        let started = Instant::now();
+        tracing::info!("request started");
+        let response = next.run(request).await;
+        tracing::info!(
+            status = response.status().as_u16(),
+            latency_ms = started.elapsed().as_millis() as u64,
+            "request finished"
+        );
+        response
+    }
+    .instrument(span)
+    .await;
+
+    if let Ok(value) = HeaderValue::from_str(&request_id) {
+        response.headers_mut().insert(REQUEST_ID_HEADER, value);
+    }
+    response
+}
+
 // ============================================================================
 // Tracing Helpers
 // ============================================================================
@@ -301,6 +398,8 @@ pub mod attributes {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use axum::{body::Body, middleware, routing::get, Extension, Router};
+    use tower::ServiceExt;
 
     #[tokio::test]
     async fn test_init_telemetry_disabled() {
@@ -350,4 +449,42 @@ mod tests {
         assert_eq!(config.resource_attributes[0].0, "region");
         assert_eq!(config.resource_attributes[1].1, "prod-1");
     }
+
+    #[tokio::test]
+    async fn test_request_id_is_kept_or_generated_and_echoed() {
+        let app = Router::new()
+            .route(
+                "/",
+                get(|Extension(id): Extension<RequestId>| async move { id.0 }),
+            )
+            .layer(middleware::from_fn(request_id_middleware));
+
+        let resp = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .uri("/")
+                    .header("X-Request-Id", "abc-123")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.headers()[&REQUEST_ID_HEADER], "abc-123");
+        let body = axum::body::to_bytes(resp.into_body(), 1024).await.unwrap();
+        assert_eq!(&body[..], b"abc-123");
+
+        let resp = app
+            .oneshot(
+                Request::builder()
+                    .uri("/")
+                    .header("X-Request-Id", "has spaces")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let generated = resp.headers()[&REQUEST_ID_HEADER].to_str().unwrap();
+        assert!(uuid::Uuid::parse_str(generated).is_ok());
+    }
 }
@@ -1,9 +1,10 @@
 //! Test runner module for discovering and executing tests across different project types
 
 use crate::error::{AuditError, Result};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 use walkdir::WalkDir;
 
@@ -23,9 +24,12 @@ enum CargoTestEvent {
 struct CargoTestEventTest {
     event: String, // "started" | "ok" | "failed" | "ignored"
     name: String,
+    /// Captured output, only present on `failed` events.
     #[serde(default)]
-    #[allow(dead_code)]
     stdout: Option<String>,
+    /// Seconds, only present with `--report-time`.
+    #[serde(default)]
+    exec_time: Option<f64>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -34,6 +38,24 @@ struct CargoTestEventSuite {
     event: String, // "started" | "ok" | "failed"
 }
 
+// ── cargo --message-format=json build messages ──────────────────────────────
+
+/// A build message emitted by `cargo --message-format=json`.
+#[derive(Debug, Deserialize)]
+struct CargoBuildMessage {
+    reason: String, // "compiler-message" | "compiler-artifact" | "build-finished" | ...
+    #[serde(default)]
+    message: Option<CargoDiagnostic>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CargoDiagnostic {
+    level: String, // "error" | "warning" | "note" | ...
+    message: String,
+    #[serde(default)]
+    rendered: Option<String>,
+}
+
 // ── pytest-json-report structures ───────────────────────────────────────────
 
 /// Root of `.pytest-report.json` produced by `pytest-json-report`.
@@ -57,7 +79,7 @@ pub struct TestRunner {
 }
 
 /// Test suite results
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct TestResults {
     /// Project type
     pub project_type: ProjectType,
@@ -79,10 +101,119 @@ pub struct TestResults {
     pub results_by_file: HashMap<String, FileTestResult>,
     /// Raw output
     pub output: String,
+    /// Per-test records (only populated from structured runner output)
+    #[serde(default)]
+    pub tests: Vec<TestRecord>,
+    /// Compiler errors that stopped the tests from running at all
+    #[serde(default)]
+    pub compile_errors: Vec<String>,
+    /// Tests that failed at first but passed on a retry (included in `passed`)
+    #[serde(default)]
+    pub flaky: usize,
+}
+
+impl TestResults {
+    /// Records for the tests that failed
+    pub fn failed_tests(&self) -> impl Iterator<Item = &TestRecord> {
+        self.tests
+            .iter()
+            .filter(|t| t.outcome == TestOutcome::Failed)
+    }
+
+    /// Records for the tests that only passed after a retry
+    pub fn flaky_tests(&self) -> impl Iterator<Item = &TestRecord> {
+        self.tests.iter().filter(|t| t.flaky)
+    }
+
+    /// One-line summary, e.g. `12 passed (2 flaky), 1 failed, 0 skipped in 3.20s`
+    pub fn summary(&self) -> String {
+        if !self.compile_errors.is_empty() {
+            return format!(
+                "compilation failed with {} error(s) in {:.2}s",
+                self.compile_errors.len(),
+                self.duration
+            );
+        }
+        let flaky = if self.flaky > 0 {
+            format!(" ({} flaky)", self.flaky)
+        } else {
+            String::new()
+        };
+        format!(
+            "{} passed{}, {} failed, {} skipped in {:.2}s",
+            self.passed, flaky, self.failed, self.skipped, self.duration
+        )
+    }
+
+    /// Fold the results of re-running failed tests back into these results.
+    ///
+    /// Tests that pass in `retry` are marked flaky and counted as passed;
+    /// tests that fail again stay failed. The failure output from the first
+    /// attempt is kept, and retry durations are added to both the per-test
+    /// and the aggregate timing.
+    fn apply_retry(&mut self, retry: &TestResults) {
+        self.duration += retry.duration;
+
+        for rerun in &retry.tests {
+            let Some(record) = self
+                .tests
+                .iter_mut()
+                .find(|t| t.name == rerun.name && t.outcome == TestOutcome::Failed)
+            else {
+                continue;
+            };
+
+            if let Some(d) = rerun.duration {
+                record.duration = Some(record.duration.unwrap_or(0.0) + d);
+            }
+            if rerun.outcome != TestOutcome::Passed {
+                continue;
+            }
+
+            record.outcome = TestOutcome::Passed;
+            record.flaky = true;
+            self.passed += 1;
+            self.failed = self.failed.saturating_sub(1);
+            self.flaky += 1;
+
+            if let Some(file) = self.results_by_file.get_mut(&record.file) {
+                file.passed += 1;
+                file.failed = file.failed.saturating_sub(1);
+                file.failures.retain(|name| name != &record.name);
+            }
+        }
+    }
+}
+
+/// Result of a single test case
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct TestRecord {
+    /// Full test name, e.g. `module::tests::test_fn`
+    pub name: String,
+    /// File key the test is grouped under in `results_by_file`
+    pub file: String,
+    /// Outcome
+    pub outcome: TestOutcome,
+    /// Execution time in seconds (if the harness reported it)
+    pub duration: Option<f64>,
+    /// Captured output of a failed test; libtest merges stdout and stderr
+    pub stderr: Option<String>,
+    /// Failed at first but passed on a retry
+    #[serde(default)]
+    pub flaky: bool,
+}
+
+/// Outcome of a single test case
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum TestOutcome {
+    Passed,
+    Failed,
+    Ignored,
 }
 
 /// Test results for a single file
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct FileTestResult {
     /// File path
     pub file: String,
@@ -97,7 +228,7 @@ pub struct FileTestResult {
 }
 
 /// Project type detected
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum ProjectType {
     Rust,
@@ -237,14 +368,125 @@ impl TestRunner {
             test_files,
             coverage,
             results_by_file,
+            tests: parse_cargo_test_records(&json_output),
             output: if text_output.is_empty() {
                 json_output
             } else {
                 text_output
             },
+            compile_errors: Vec::new(),
+            flaky: 0,
         })
     }
 
+    /// Run `cargo test` in `path` (relative to the runner root) with JSON
+    /// output and collect per-test records with timings.
+    ///
+    /// Build messages and libtest events both arrive on stdout. Compiler
+    /// errors end up in `compile_errors` rather than being counted as test
+    /// failures. If no test events can be parsed (e.g. a toolchain that
+    /// rejects `--format=json`) the text summary is used for the counts.
+    pub fn run_json(&self, path: impl AsRef<Path>) -> Result<TestResults> {
+        let dir = self.root.join(path);
+        let mut results = self.run_cargo_json(&dir, &[])?;
+        results.test_files = TestRunner::new(&dir).find_rust_test_files()?;
+        Ok(results)
+    }
+
+    /// Run the tests in `path`, then re-run only the failed ones up to
+    /// `max_retries` times.
+    ///
+    /// Tests that pass on a retry are marked `flaky` and counted as passed
+    /// (and in `flaky`); whatever is still in `failed` failed on every
+    /// attempt. `duration` covers all attempts. A build failure ends the
+    /// retries immediately.
+    pub fn run_with_retries(
+        &self,
+        path: impl AsRef<Path>,
+        max_retries: u32,
+    ) -> Result<TestResults> {
+        let dir = self.root.join(path);
+        let mut results = self.run_json(&dir)?;
+
+        for attempt in 1..=max_retries {
+            if !results.compile_errors.is_empty() {
+                break;
+            }
+            let failing: Vec<String> = results.failed_tests().map(|t| t.name.clone()).collect();
+            if failing.is_empty() {
+                break;
+            }
+
+            tracing::info!(
+                "Retrying {} failed test(s) (attempt {}/{})",
+                failing.len(),
+                attempt,
+                max_retries
+            );
+            let retry = self.run_cargo_json(&dir, &failing)?;
+            results.apply_retry(&retry);
+        }
+
+        Ok(results)
+    }
+
+    /// Invoke `cargo test` with JSON output in `dir`, optionally restricted to
+    /// the exact test names in `filters`.
+    fn run_cargo_json(&self, dir: &Path, filters: &[String]) -> Result<TestResults> {
+        let start = std::time::Instant::now();
+
+        let mut cmd = Command::new("cargo");
+        cmd.arg("test")
+            .arg("--message-format=json")
+            .arg("--")
+            .arg("-Zunstable-options")
+            .arg("--format=json")
+            .arg("--report-time");
+        if !filters.is_empty() {
+            cmd.arg("--exact").args(filters);
+        }
+        let output = cmd.current_dir(dir).output().map_err(AuditError::Io)?;
+
+        let duration = start.elapsed().as_secs_f64();
+        let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+        let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+
+        let results = self.cargo_json_results(&stdout, &stderr, duration);
+        check_cargo_json_run(&results, &stderr, output.status.success())?;
+        Ok(results)
+    }
+
+    /// Build `TestResults` from the output of
+    /// `cargo test --message-format=json -- --format=json`.
+    fn cargo_json_results(&self, stdout: &str, stderr: &str, duration: f64) -> TestResults {
+        let compile_errors = parse_cargo_compile_errors(stdout);
+        let tests = parse_cargo_test_records(stdout);
+        let (results_by_file, json_total, json_passed, json_failed, json_skipped) =
+            self.parse_cargo_test_json(stdout);
+
+        let (total, passed, failed, skipped) = if json_total > 0 || !compile_errors.is_empty() {
+            (json_total, json_passed, json_failed, json_skipped)
+        } else {
+            self.parse_cargo_test_output(&format!("{}\n{}", stdout, stderr))
+        };
+
+        TestResults {
+            project_type: ProjectType::Rust,
+            total,
+            passed,
+            failed,
+            skipped,
+            duration,
+            test_files: Vec::new(),
+            coverage: None,
+            results_by_file,
+            output: stderr.to_string(),
+            tests,
+            compile_errors,
+            flaky: 0,
+        }
+    }
+
     /// Run Python tests using pytest
     fn run_python_tests(&self) -> Result<TestResults> {
         let start = std::time::Instant::now();
@@ -291,6 +533,9 @@ impl TestRunner {
             coverage,
             results_by_file,
             output: output_str,
+            tests: Vec::new(),
+            compile_errors: Vec::new(),
+            flaky: 0,
         })
     }
 
@@ -327,6 +572,9 @@ impl TestRunner {
             coverage: None,
             results_by_file: HashMap::new(),
             output: output_str,
+            tests: Vec::new(),
+            compile_errors: Vec::new(),
+            flaky: 0,
         })
     }
 
@@ -361,6 +609,9 @@ impl TestRunner {
             coverage: None,
             results_by_file: HashMap::new(),
             output: output_str,
+            tests: Vec::new(),
+            compile_errors: Vec::new(),
+            flaky: 0,
         })
     }
 
@@ -757,6 +1008,72 @@ impl TestRunner {
 
 // ── Module-level helpers ─────────────────────────────────────────────────────
 
+/// Fail a JSON run that neither compiled-and-reported nor failed to compile.
+///
+/// Stable libtest rejects `-Zunstable-options --format=json` and exits
+/// nonzero before running anything; without this the run would look like
+/// an empty, passing suite.
+fn check_cargo_json_run(results: &TestResults, stderr: &str, success: bool) -> Result<()> {
+    if success || results.total > 0 || !results.compile_errors.is_empty() {
+        return Ok(());
+    }
+    let reason = stderr
+        .lines()
+        .map(str::trim)
+        .find(|line| line.starts_with("error"))
+        .unwrap_or("no libtest JSON events");
+    Err(AuditError::other(format!(
+        "cargo test produced no test results ({}); JSON test output needs a nightly toolchain",
+        reason
+    )))
+}
+
+/// Collect per-test records from a libtest JSON event stream.
+///
+/// Lines that aren't test events (build messages, suite summaries, plain
+/// text) are skipped.
+fn parse_cargo_test_records(output: &str) -> Vec<TestRecord> {
+    output
+        .lines()
+        .map(str::trim)
+        .filter(|line| line.starts_with('{'))
+        .filter_map(|line| serde_json::from_str::<CargoTestEvent>(line).ok())
+        .filter_map(|event| {
+            let CargoTestEvent::Test(t) = event else {
+                return None;
+            };
+            let outcome = match t.event.as_str() {
+                "ok" => TestOutcome::Passed,
+                "failed" => TestOutcome::Failed,
+                "ignored" => TestOutcome::Ignored,
+                _ => return None,
+            };
+            Some(TestRecord {
+                file: derive_rust_file_key(&t.name),
+                name: t.name,
+                outcome,
+                duration: t.exec_time,
+                stderr: t.stdout.filter(|_| outcome == TestOutcome::Failed),
+                flaky: false,
+            })
+        })
+        .collect()
+}
+
+/// Collect rendered compiler errors from a `--message-format=json` stream.
+fn parse_cargo_compile_errors(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .map(str::trim)
+        .filter(|line| line.starts_with('{'))
+        .filter_map(|line| serde_json::from_str::<CargoBuildMessage>(line).ok())
+        .filter(|msg| msg.reason == "compiler-message")
+        .filter_map(|msg| msg.message)
+        .filter(|diag| diag.level.starts_with("error"))
+        .map(|diag| diag.rendered.unwrap_or(diag.message))
+        .collect()
+}
+
 /// Derive a human-readable file key from a cargo test name.
 ///
 /// Test names look like `module::submodule::test_fn` or just `test_fn`.
@@ -977,4 +1294,161 @@ mod tests {
         assert_eq!(total, 1);
         assert_eq!(passed, 1);
     }
+
+    // ── run_json parsing ─────────────────────────────────────────────────────
+
+    #[test]
+    fn cargo_json_results_capture_durations_and_failure_output() {
+        let runner = TestRunner::new(".");
+        let stdout = r#"
+{"reason":"compiler-artifact","package_id":"foo 0.1.0","target":{"name":"foo"}}
+{"reason":"build-finished","success":true}
+{"type":"suite","event":"started","test_count":3}
+{"type":"test","event":"started","name":"mod_a::tests::test_one"}
+{"type":"test","event":"ok","name":"mod_a::tests::test_one","exec_time":0.125}
+{"type":"test","event":"failed","name":"mod_a::tests::test_two","exec_time":0.5,"stdout":"thread 'mod_a::tests::test_two' panicked at src/mod_a.rs:10:5"}
+{"type":"test","event":"ignored","name":"mod_b::tests::test_skip"}
+{"type":"suite","event":"failed","passed":1,"failed":1,"ignored":1,"measured":0,"filtered_out":0,"exec_time":0.7}
+"#;
+
+        let results = runner.cargo_json_results(stdout, "", 1.0);
+
+        assert!(results.compile_errors.is_empty());
+        assert_eq!(
+            (
+                results.total,
+                results.passed,
+                results.failed,
+                results.skipped
+            ),
+            (3, 1, 1, 1)
+        );
+        assert_eq!(results.tests.len(), 3);
+
+        let one = &results.tests[0];
+        assert_eq!(one.name, "mod_a::tests::test_one");
+        assert_eq!(one.file, "src/mod_a.rs");
+        assert_eq!(one.outcome, TestOutcome::Passed);
+        assert_eq!(one.duration, Some(0.125));
+        assert!(one.stderr.is_none());
+
+        let failed: Vec<_> = results.failed_tests().collect();
+        assert_eq!(failed.len(), 1);
+        assert_eq!(failed[0].duration, Some(0.5));
+        assert!(failed[0].stderr.as_deref().unwrap().contains("panicked"));
+
+        assert_eq!(results.tests[2].outcome, TestOutcome::Ignored);
+        assert_eq!(results.tests[2].duration, None);
+    }
+
+    #[test]
+    fn cargo_json_results_report_compile_errors_separately() {
+        let runner = TestRunner::new(".");
+        let stdout = r#"
+{"reason":"compiler-message","message":{"level":"warning","message":"unused variable","rendered":"warning: unused variable: `x`"}}
+{"reason":"compiler-message","message":{"level":"error","message":"mismatched types","rendered":"error[E0308]: mismatched types\n --> src/lib.rs:3:5"}}
+{"reason":"build-finished","success":false}
+"#;
+        // The text summary must not be consulted when the build failed.
+        let stderr = "test result: ok. 5 passed; 0 failed; 0 ignored";
+
+        let results = runner.cargo_json_results(stdout, stderr, 1.0);
+
+        assert_eq!(results.compile_errors.len(), 1);
+        assert!(results.compile_errors[0].starts_with("error[E0308]"));
+        assert_eq!(results.total, 0);
+        assert_eq!(results.failed, 0);
+        assert!(results.tests.is_empty());
+    }
+
+    #[test]
+    fn cargo_json_results_fall_back_to_text_summary() {
+        let runner = TestRunner::new(".");
+        let stdout = "running 4 tests\ntest result: FAILED. 3 passed; 1 failed; 0 ignored";
+
+        let results = runner.cargo_json_results(stdout, "", 1.0);
+
+        assert!(results.tests.is_empty());
+        assert_eq!((results.total, results.passed, results.failed), (4, 3, 1));
+    }
+
+    #[test]
+    fn cargo_json_run_without_events_is_an_error() {
+        let runner = TestRunner::new(".");
+        let stdout = r#"{"reason":"build-finished","success":true}"#;
+        let stderr = "     Running unittests src/main.rs (target/debug/deps/foo-0123)\n\
+                      error: the option `Z` is only accepted on the nightly compiler\n\
+                      error: test failed, to rerun pass `--bin foo`";
+
+        let results = runner.cargo_json_results(stdout, stderr, 1.0);
+        let err = check_cargo_json_run(&results, stderr, false).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("only accepted on the nightly compiler"));
+
+        // Failing tests and compile errors are results, not run errors
+        let failing = r#"{"type":"test","event":"failed","name":"tests::t","stdout":"boom"}"#;
+        let results = runner.cargo_json_results(failing, "", 1.0);
+        assert!(check_cargo_json_run(&results, "", false).is_ok());
+        let broken = r#"{"reason":"compiler-message","message":{"level":"error","message":"oops","rendered":"error: oops"}}"#;
+        let results = runner.cargo_json_results(broken, "", 1.0);
+        assert!(check_cargo_json_run(&results, "", false).is_ok());
+    }
+
+    // ── retries ──────────────────────────────────────────────────────────────
+
+    #[test]
+    fn apply_retry_marks_flaky_and_keeps_consistent_failures() {
+        let runner = TestRunner::new(".");
+        let first = r#"
+{"type":"test","event":"ok","name":"mod_a::tests::test_one","exec_time":0.1}
+{"type":"test","event":"failed","name":"mod_a::tests::test_flaky","exec_time":0.2,"stdout":"timed out"}
+{"type":"test","event":"failed","name":"mod_b::tests::test_broken","exec_time":0.3,"stdout":"assertion failed"}
+"#;
+        let retry = r#"
+{"type":"test","event":"ok","name":"mod_a::tests::test_flaky","exec_time":0.25}
+{"type":"test","event":"failed","name":"mod_b::tests::test_broken","exec_time":0.3,"stdout":"assertion failed"}
+"#;
+
+        let mut results = runner.cargo_json_results(first, "", 1.0);
+        results.apply_retry(&runner.cargo_json_results(retry, "", 0.5));
+
+        assert_eq!((results.passed, results.failed, results.flaky), (2, 1, 1));
+        assert_eq!(results.duration, 1.5);
+
+        let flaky: Vec<_> = results.flaky_tests().collect();
+        assert_eq!(flaky.len(), 1);
+        assert_eq!(flaky[0].name, "mod_a::tests::test_flaky");
+        assert_eq!(flaky[0].outcome, TestOutcome::Passed);
+        assert_eq!(flaky[0].duration, Some(0.45));
+        // First-attempt output is kept for diagnosing the flake.
+        assert_eq!(flaky[0].stderr.as_deref(), Some("timed out"));
+
+        let failed: Vec<_> = results.failed_tests().collect();
+        assert_eq!(failed.len(), 1);
+        assert_eq!(failed[0].name, "mod_b::tests::test_broken");
+        assert!(!failed[0].flaky);
+
+        let mod_a = &results.results_by_file["src/mod_a.rs"];
+        assert_eq!((mod_a.passed, mod_a.failed), (2, 0));
+        assert!(mod_a.failures.is_empty());
+
+        assert_eq!(
+            results.summary(),
+            "2 passed (1 flaky), 1 failed, 0 skipped in 1.50s"
+        );
+    }
+
+    #[test]
+    fn apply_retry_ignores_tests_that_did_not_fail() {
+        let runner = TestRunner::new(".");
+        let first = r#"{"type":"test","event":"ok","name":"mod_a::tests::test_one"}"#;
+        let retry = r#"{"type":"test","event":"ok","name":"mod_a::tests::test_one"}"#;
+
+        let mut results = runner.cargo_json_results(first, "", 1.0);
+        results.apply_retry(&runner.cargo_json_results(retry, "", 1.0));
+
+        assert_eq!((results.passed, results.flaky), (1, 0));
+        assert_eq!(results.summary(), "1 passed, 0 failed, 0 skipped in 2.00s");
+    }
 }
@@ -36,6 +36,7 @@
 
 use chrono::{DateTime, Utc};
 use regex::Regex;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
@@ -122,7 +123,7 @@ impl Default for ScanConfig {
 // ============================================================================
 
 /// The annotation keyword found in the comment
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "UPPERCASE")]
 pub enum CommentKind {
     Todo,
@@ -182,7 +183,9 @@ impl CommentKind {
 }
 
 /// Priority level derived from kind + content heuristics
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, JsonSchema,
+)]
 #[serde(rename_all = "lowercase")]
 pub enum CommentPriority {
     Low = 0,
@@ -211,7 +214,7 @@ impl std::fmt::Display for CommentPriority {
 // ============================================================================
 
 /// A single extracted TODO comment item
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct TodoCommentItem {
     /// Stable 8-char hex ID derived from file+line
     pub id: String,
@@ -238,7 +241,7 @@ pub struct TodoCommentItem {
 }
 
 /// Summary statistics for a scan run
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
 pub struct ScanSummary {
     pub total: usize,
     pub by_kind: HashMap<String, usize>,
@@ -275,7 +278,7 @@ impl ScanSummary {
 }
 
 /// Complete output of a single scan run
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ScanOutput {
     pub repo_path: PathBuf,
     pub scanned_at: DateTime<Utc>,
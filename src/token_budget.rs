@@ -57,6 +57,14 @@ impl TokenPricing {
         }
     }
 
+    /// The offline mock provider, which is free
+    pub fn free() -> Self {
+        Self {
+            input_per_million: 0.0,
+            output_per_million: 0.0,
+        }
+    }
+
     /// Get pricing for a provider/model
     pub fn for_provider(provider: &str, model: &str) -> Self {
         let model_lower = model.to_lowercase();
@@ -68,6 +76,7 @@ impl TokenPricing {
             "openai" => Self::gpt35_turbo(), // Default OpenAI
             "anthropic" => Self::claude_sonnet(),
             "google" => Self::gemini_pro(),
+            "mock" => Self::free(),
             _ => {
                 // Check model name for provider hints
                 if model_lower.contains("grok") {
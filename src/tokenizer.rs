@@ -0,0 +1,256 @@
+//! Token counting
+//!
+//! Counts tokens the way the providers bill them instead of assuming four
+//! characters per token. With the default `bpe-tokenizer` feature every
+//! model is counted exactly with a BPE vocabulary bundled by `tiktoken-rs`:
+//! `o200k_base` for the GPT-4o/4.1/5 and o-series models, `cl100k_base`
+//! for everything else. xAI, Anthropic and Google don't publish their
+//! vocabularies, so `cl100k_base` stands in for them. Built without the
+//! feature, counts fall back to a heuristic that mirrors the `cl100k_base`
+//! pre-tokenizer and is usually within 10-20% of the real count.
+
+/// A BPE vocabulary used for counting
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Encoding {
+    Cl100kBase,
+    O200kBase,
+}
+
+impl Encoding {
+    /// The vocabulary `model` is counted with
+    pub fn for_model(model: &str) -> Self {
+        let model = model.to_lowercase();
+        let o200k = ["gpt-4o", "gpt-4.1", "gpt-4.5", "gpt-5", "o1", "o3", "o4"]
+            .iter()
+            .any(|prefix| model.starts_with(prefix));
+        if o200k {
+            Encoding::O200kBase
+        } else {
+            Encoding::Cl100kBase
+        }
+    }
+}
+
+/// Count the tokens `text` takes up for `model`
+pub fn count_tokens(text: &str, model: &str) -> usize {
+    if text.is_empty() {
+        return 0;
+    }
+
+    #[cfg(feature = "bpe-tokenizer")]
+    return bpe::count(text, Encoding::for_model(model));
+
+    #[cfg(not(feature = "bpe-tokenizer"))]
+    {
+        let _ = model;
+        heuristic_count(text)
+    }
+}
+
+/// Estimate the token count without a vocabulary.
+///
+/// Splits the text into the same pieces as the `cl100k_base` pre-tokenizer
+/// (words with one leading space or symbol, up to three digits, symbol
+/// runs with their trailing newlines, whitespace runs) and estimates how
+/// many BPE tokens each piece becomes.
+pub fn heuristic_count(text: &str) -> usize {
+    let chars: Vec<char> = text.chars().collect();
+    let mut tokens = 0;
+    let mut i = 0;
+    while i < chars.len() {
+        let (end, piece_tokens) = next_piece(&chars, i);
+        tokens += piece_tokens;
+        i = end;
+    }
+    tokens
+}
+
+fn is_newline(c: char) -> bool {
+    c == '\r' || c == '\n'
+}
+
+fn is_symbol(c: char) -> bool {
+    !c.is_whitespace() && !c.is_alphabetic() && !c.is_numeric()
+}
+
+/// End of the piece starting at `start`, and its estimated token count
+fn next_piece(chars: &[char], start: usize) -> (usize, usize) {
+    let n = chars.len();
+    let run_end = |from: usize, pred: fn(char) -> bool| {
+        let mut end = from;
+        while end < n && pred(chars[end]) {
+            end += 1;
+        }
+        end
+    };
+    let c = chars[start];
+
+    // A word, optionally prefixed by one space or symbol
+    let word_start = if c.is_alphabetic() {
+        Some(start)
+    } else if !c.is_numeric()
+        && !is_newline(c)
+        && chars.get(start + 1).is_some_and(|c| c.is_alphabetic())
+    {
+        Some(start + 1)
+    } else {
+        None
+    };
+    if let Some(word_start) = word_start {
+        let end = run_end(word_start, char::is_alphabetic);
+        return (end, word_tokens(&chars[word_start..end]));
+    }
+
+    // Numbers are split into groups of up to three digits
+    if c.is_numeric() {
+        return (run_end(start, char::is_numeric).min(start + 3), 1);
+    }
+
+    // Symbols, optionally prefixed by a space, with trailing newlines
+    let symbol_start = if c == ' ' && chars.get(start + 1).is_some_and(|&c| is_symbol(c)) {
+        start + 1
+    } else {
+        start
+    };
+    if is_symbol(chars[symbol_start]) {
+        let symbols_end = run_end(symbol_start, is_symbol);
+        let end = run_end(symbols_end, is_newline);
+        return (end, (symbols_end - symbol_start).div_ceil(3));
+    }
+
+    // Whitespace up to the last newline, or all but the space that
+    // prefixes the next word
+    let end = run_end(start, char::is_whitespace);
+    if let Some(last_newline) = (start..end).rev().find(|&i| is_newline(chars[i])) {
+        return (last_newline + 1, 1);
+    }
+    if end < n && end - start > 1 {
+        return (end - 1, 1);
+    }
+    (end, 1)
+}
+
+/// Tokens for a run of letters: ASCII words are split at camel-case
+/// boundaries, with common-length subwords as one token; other scripts
+/// count one token per character
+fn word_tokens(word: &[char]) -> usize {
+    let mut tokens = 0;
+    let mut subword_len = 0;
+    for (i, &c) in word.iter().enumerate() {
+        if !c.is_ascii() {
+            tokens += 1;
+            continue;
+        }
+        let camel_boundary = i > 0 && c.is_ascii_uppercase() && word[i - 1].is_ascii_lowercase();
+        if camel_boundary && subword_len > 0 {
+            tokens += subword_tokens(subword_len);
+            subword_len = 0;
+        }
+        subword_len += 1;
+    }
+    tokens + subword_tokens(subword_len)
+}
+
+fn subword_tokens(len: usize) -> usize {
+    if len == 0 {
+        0
+    } else {
+        1 + (len - 1) / 8
+    }
+}
+
+#[cfg(feature = "bpe-tokenizer")]
+mod bpe {
+    use super::Encoding;
+    use tiktoken_rs::{cl100k_base_singleton, o200k_base_singleton};
+
+    /// Exact count; special-token markers in `text` count as plain text,
+    /// as they do when sent to the provider
+    pub(super) fn count(text: &str, encoding: Encoding) -> usize {
+        let bpe = match encoding {
+            Encoding::Cl100kBase => cl100k_base_singleton(),
+            Encoding::O200kBase => o200k_base_singleton(),
+        };
+        bpe.encode_ordinary(text).len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Texts with their `cl100k_base` token counts
+    const CL100K_FIXTURES: &[(&str, usize)] = &[
+        ("hello world", 2),
+        ("Hello, world!", 4),
+        ("The quick brown fox jumps over the lazy dog.", 10),
+        ("tiktoken is great!", 6),
+        ("fn main() {\n    println!(\"Hello, world!\");\n}", 12),
+        ("pub fn count_tokens(text: &str) -> usize", 11),
+        (
+            "    let total: usize = items.iter().map(|i| i.len()).sum();\n",
+            18,
+        ),
+        ("GrokReasoningClient", 5),
+        ("日本語", 4),
+    ];
+
+    #[cfg(feature = "bpe-tokenizer")]
+    #[test]
+    fn test_cl100k_counts_are_exact() {
+        assert_eq!(count_tokens("", "grok-4"), 0);
+        for &(text, expected) in CL100K_FIXTURES {
+            assert_eq!(count_tokens(text, "grok-4"), expected, "{:?}", text);
+            assert_eq!(count_tokens(text, "gpt-4"), expected, "{:?}", text);
+        }
+        // Special-token markers are ordinary text
+        assert_eq!(count_tokens("<|endoftext|> hi", "gpt-4"), 8);
+    }
+
+    #[cfg(feature = "bpe-tokenizer")]
+    #[test]
+    fn test_o200k_models_use_o200k() {
+        assert_eq!(count_tokens("日本語", "gpt-4o"), 2);
+        assert_eq!(count_tokens("日本語", "o3-mini"), 2);
+        assert_eq!(
+            count_tokens("The quick brown fox jumps over the lazy dog.", "gpt-5"),
+            10
+        );
+    }
+
+    #[test]
+    fn test_heuristic_matches_prose_fixtures() {
+        // Common English words are single cl100k tokens, so plain prose
+        // comes out exact; rare words and code are where it drifts
+        for &(text, expected) in &CL100K_FIXTURES[..3] {
+            assert_eq!(heuristic_count(text), expected, "{:?}", text);
+        }
+    }
+
+    #[test]
+    fn test_heuristic_pieces() {
+        // Camel case and long words split into several tokens
+        assert_eq!(heuristic_count("GrokReasoningClient"), 4);
+        // Numbers in groups of three digits
+        assert_eq!(heuristic_count("1234567"), 3);
+        // Indentation is one piece; its last space prefixes the word
+        assert_eq!(heuristic_count("\n        let"), 3);
+        // Non-Latin scripts count per character
+        assert_eq!(heuristic_count("日本語"), 3);
+    }
+
+    #[test]
+    fn test_model_families() {
+        assert_eq!(
+            Encoding::for_model("grok-4-1-fast-reasoning"),
+            Encoding::Cl100kBase
+        );
+        assert_eq!(Encoding::for_model("gpt-4-turbo"), Encoding::Cl100kBase);
+        assert_eq!(Encoding::for_model("GPT-4o-mini"), Encoding::O200kBase);
+        assert_eq!(Encoding::for_model("o1-preview"), Encoding::O200kBase);
+        assert_eq!(
+            Encoding::for_model("claude-opus-4-20250514"),
+            Encoding::Cl100kBase
+        );
+    }
+}
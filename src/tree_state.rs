@@ -370,6 +370,18 @@ impl TreeStateManager {
         }
     }
 
+    /// Keep state in `cache_dir` instead of `<root>/.audit-cache`
+    pub fn with_cache_dir(mut self, cache_dir: impl Into<PathBuf>) -> Self {
+        self.cache_dir = cache_dir.into();
+        self
+    }
+
+    /// Track only files with these extensions (without the dot)
+    pub fn with_extensions(mut self, extensions: &[&str]) -> Self {
+        self.include_extensions = extensions.iter().map(|e| e.to_string()).collect();
+        self
+    }
+
     /// Ensure cache directory exists
     fn ensure_cache_dir(&self) -> Result<()> {
         if !self.cache_dir.exists() {
@@ -545,7 +557,11 @@ impl TreeStateManager {
 
     /// Check if path should be excluded
     fn should_exclude(&self, path: &Path) -> bool {
-        let path_str = path.to_string_lossy();
+        // Match below the root only, so a root under e.g. /srv/build still works
+        let path_str = path
+            .strip_prefix(&self.root)
+            .unwrap_or(path)
+            .to_string_lossy();
         self.exclude_patterns
             .iter()
             .any(|pattern| path_str.contains(pattern))
@@ -1,9 +1,10 @@
 //! Core types for the audit service
 
 use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use std::path::PathBuf;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
 use uuid::Uuid;
 
 // Re-export types from other modules
@@ -11,7 +12,9 @@ pub use crate::context::GlobalContextBundle;
 pub use crate::tests_runner::TestResults;
 
 /// File category based on location and purpose
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, JsonSchema,
+)]
 #[serde(rename_all = "lowercase")]
 pub enum Category {
     /// Janus - Core trading system, neuromorphic components, and decision-making
@@ -175,7 +178,9 @@ impl MainCategory {
 }
 
 /// File priority for audit
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, JsonSchema,
+)]
 #[serde(rename_all = "lowercase")]
 pub enum FilePriority {
     Critical = 4,
@@ -223,7 +228,7 @@ impl FilePriority {
 }
 
 /// Security rating for code
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "UPPERCASE")]
 pub enum SecurityRating {
     A,
@@ -251,7 +256,7 @@ impl SecurityRating {
 }
 
 /// Audit tag found in code
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 pub struct AuditTag {
     /// Tag type
     pub tag_type: AuditTagType,
@@ -265,8 +270,35 @@ pub struct AuditTag {
     pub context: Option<String>,
 }
 
+impl AuditTag {
+    /// Header row matching [`to_csv_row`](Self::to_csv_row)
+    pub const CSV_HEADER: &'static str = "type,file,line,value";
+
+    /// One CSV row (`type,file,line,value`, no newline), with the file
+    /// relative to `root` when it lies under it
+    pub fn to_csv_row(&self, root: &Path) -> String {
+        let file = self.file.strip_prefix(root).unwrap_or(&self.file);
+        format!(
+            "{},{},{},{}",
+            self.tag_type.prefix().trim_end_matches(':'),
+            csv_field(&file.display().to_string()),
+            self.line,
+            csv_field(&self.value)
+        )
+    }
+}
+
+/// Quote a CSV field if it contains a separator, quote or newline
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
 /// Type of audit tag
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "kebab-case")]
 pub enum AuditTagType {
     /// @audit-tag: [new | old | experimental | deprecated]
@@ -295,7 +327,7 @@ impl AuditTagType {
 }
 
 /// Generated task from audit
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Task {
     /// Unique task ID
     pub id: String,
@@ -315,6 +347,9 @@ pub struct Task {
     pub created_at: DateTime<Utc>,
     /// Tags
     pub tags: Vec<String>,
+    /// IDs of tasks that should be done before this one
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub depends_on: Vec<String>,
 }
 
 impl Task {
@@ -337,6 +372,7 @@ impl Task {
             category,
             created_at: Utc::now(),
             tags: Vec::new(),
+            depends_on: Vec::new(),
         }
     }
 
@@ -345,10 +381,55 @@ impl Task {
         self.tags.push(tag.into());
         self
     }
+
+    /// Add a dependency on another task
+    pub fn depends_on(mut self, task_id: impl Into<String>) -> Self {
+        self.depends_on.push(task_id.into());
+        self
+    }
+
+    /// Identity of the task across generation runs: file, category and
+    /// title, with the title lowercased, numbers blanked out and whitespace
+    /// collapsed so counts and line numbers in it don't make a new task
+    pub fn dedup_key(&self) -> String {
+        let mut title = String::with_capacity(self.title.len());
+        let mut last = ' ';
+        for c in self.title.trim().chars().flat_map(char::to_lowercase) {
+            let c = if c.is_ascii_digit() {
+                '#'
+            } else if c.is_whitespace() {
+                ' '
+            } else {
+                c
+            };
+            if !(c == last && (c == '#' || c == ' ')) {
+                title.push(c);
+            }
+            last = c;
+        }
+        format!(
+            "{}|{:?}|{}",
+            self.file.display(),
+            self.category,
+            title.trim_end_matches(['.', ':', ' '])
+        )
+    }
+
+    /// Rough effort in story points, from the task's priority
+    pub fn estimated_effort(&self) -> u32 {
+        match self.priority {
+            TaskPriority::Critical => 8,
+            TaskPriority::High => 5,
+            TaskPriority::Medium => 3,
+            TaskPriority::Low => 1,
+        }
+    }
 }
 
 /// Task priority
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, JsonSchema,
+)]
 #[serde(rename_all = "lowercase")]
 pub enum TaskPriority {
     Critical,
@@ -358,7 +439,7 @@ pub enum TaskPriority {
 }
 
 /// File analysis result
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct FileAnalysis {
     /// File path
     pub path: PathBuf,
@@ -381,7 +462,7 @@ pub struct FileAnalysis {
 }
 
 /// Code issue
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Issue {
     /// Issue severity
     pub severity: IssueSeverity,
@@ -398,7 +479,9 @@ pub struct Issue {
 }
 
 /// Issue severity
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, JsonSchema,
+)]
 #[serde(rename_all = "lowercase")]
 pub enum IssueSeverity {
     Critical,
@@ -409,7 +492,7 @@ pub enum IssueSeverity {
 }
 
 /// Issue category
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "kebab-case")]
 pub enum IssueCategory {
     Security,
@@ -423,14 +506,14 @@ pub enum IssueCategory {
 }
 
 /// System architecture map
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct SystemMap {
     /// Total files analyzed
     pub total_files: usize,
     /// Files by category
-    pub files_by_category: HashMap<Category, usize>,
+    pub files_by_category: BTreeMap<Category, usize>,
     /// Lines by category
-    pub lines_by_category: HashMap<Category, usize>,
+    pub lines_by_category: BTreeMap<Category, usize>,
     /// Service dependencies
     pub dependencies: Vec<ServiceDependency>,
     /// Mermaid diagram
@@ -438,7 +521,7 @@ pub struct SystemMap {
 }
 
 /// Service dependency
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ServiceDependency {
     /// Source service
     pub from: String,
@@ -449,7 +532,7 @@ pub struct ServiceDependency {
 }
 
 /// Dependency type
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum DependencyType {
     Grpc,
@@ -458,6 +541,9 @@ pub enum DependencyType {
 }
 
 /// Audit request
+///
+/// Build one with [`AuditRequest::builder`], which validates the fields; the
+/// struct stays public so requests can be deserialized from API bodies.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AuditRequest {
     /// Repository URL or local path
@@ -472,9 +558,143 @@ pub struct AuditRequest {
     pub include_tests: bool,
 }
 
+impl AuditRequest {
+    /// Start building a request
+    pub fn builder() -> AuditRequestBuilder {
+        AuditRequestBuilder::default()
+    }
+}
+
+/// Builder for [`AuditRequest`]
+///
+/// ```rust
+/// use rustassistant::types::AuditRequest;
+///
+/// let request = AuditRequest::builder()
+///     .repository(".")
+///     .focus(["security", "api"])
+///     .build()
+///     .unwrap();
+/// assert!(!request.enable_llm);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct AuditRequestBuilder {
+    repository: String,
+    branch: Option<String>,
+    enable_llm: bool,
+    focus: Vec<String>,
+    include_tests: bool,
+    extra_focus_keywords: Vec<String>,
+}
+
+impl AuditRequestBuilder {
+    /// Repository URL or local path (required)
+    pub fn repository(mut self, repository: impl Into<String>) -> Self {
+        self.repository = repository.into();
+        self
+    }
+
+    /// Branch to audit (default: main)
+    pub fn branch(mut self, branch: impl Into<String>) -> Self {
+        self.branch = Some(branch.into());
+        self
+    }
+
+    pub fn enable_llm(mut self, enable: bool) -> Self {
+        self.enable_llm = enable;
+        self
+    }
+
+    /// Add focus keywords; see [`crate::llm_config::default_focus_heuristics`]
+    pub fn focus<I, S>(mut self, keywords: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.focus.extend(keywords.into_iter().map(Into::into));
+        self
+    }
+
+    pub fn include_tests(mut self, include: bool) -> Self {
+        self.include_tests = include;
+        self
+    }
+
+    /// Also accept these focus keywords, e.g. the ones configured in
+    /// [`crate::llm_config::LlmConfig::focus_heuristics`]
+    pub fn allow_focus_keywords<I, S>(mut self, keywords: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.extra_focus_keywords
+            .extend(keywords.into_iter().map(|k| k.into().to_lowercase()));
+        self
+    }
+
+    /// Validate and build the request. Fails if the repository is empty or
+    /// a focus keyword is unknown. Focus keywords are lowercased and
+    /// deduplicated.
+    pub fn build(self) -> crate::error::Result<AuditRequest> {
+        use crate::error::AuditError;
+
+        let repository = self.repository.trim().to_string();
+        if repository.is_empty() {
+            return Err(AuditError::InvalidRequest(
+                "repository must not be empty".into(),
+            ));
+        }
+        if self.branch.as_deref().is_some_and(|b| b.trim().is_empty()) {
+            return Err(AuditError::InvalidRequest(
+                "branch must not be empty".into(),
+            ));
+        }
+
+        let known: Vec<String> = crate::llm_config::default_focus_heuristics()
+            .into_iter()
+            .map(|h| h.keyword)
+            .chain(self.extra_focus_keywords)
+            .collect();
+        let mut focus: Vec<String> = Vec::new();
+        for keyword in self.focus {
+            let keyword = keyword.trim().to_lowercase();
+            if !known.contains(&keyword) {
+                return Err(AuditError::InvalidRequest(format!(
+                    "unknown focus '{}' (expected one of: {})",
+                    keyword,
+                    known.join(", ")
+                )));
+            }
+            if !focus.contains(&keyword) {
+                focus.push(keyword);
+            }
+        }
+
+        Ok(AuditRequest {
+            repository,
+            branch: self.branch,
+            enable_llm: self.enable_llm,
+            focus,
+            include_tests: self.include_tests,
+        })
+    }
+}
+
+/// Current [`AuditReport::schema_version`]
+pub const AUDIT_REPORT_SCHEMA_VERSION: u32 = 1;
+
 /// Audit report
-#[derive(Debug, Clone, Serialize, Deserialize)]
+///
+/// Saved reports are loaded with [`AuditReport::from_json`] or
+/// [`AuditReport::load`], which upgrade older shapes first. Schema versions:
+///
+/// - **1**: current shape. Reports saved before `schema_version` existed
+///   have the same fields and are read as version 1.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct AuditReport {
+    /// Shape of this report; missing in reports saved before versioning
+    #[serde(default = "schema_version_1")]
+    pub schema_version: u32,
     /// Report ID
     pub id: String,
     /// Repository audited
@@ -485,12 +705,12 @@ pub struct AuditReport {
     pub created_at: DateTime<Utc>,
     /// System map
     pub system_map: SystemMap,
-    /// File analyses
+    /// File analyses, sorted by path
     pub files: Vec<FileAnalysis>,
     /// Generated tasks
     pub tasks: Vec<Task>,
     /// Total issues by severity
-    pub issues_by_severity: HashMap<IssueSeverity, usize>,
+    pub issues_by_severity: BTreeMap<IssueSeverity, usize>,
     /// Summary
     pub summary: AuditSummary,
     /// Test results (if tests were run)
@@ -498,11 +718,67 @@ pub struct AuditReport {
     pub test_results: Option<Vec<TestResults>>,
     /// Global context bundle (if deep analysis was performed)
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(with = "Option<serde_json::Value>")]
     pub context_bundle: Option<GlobalContextBundle>,
 }
 
+fn schema_version_1() -> u32 {
+    1
+}
+
+impl AuditReport {
+    /// Upgrade a saved report's JSON to the current schema version.
+    /// Fails for reports written by a newer version.
+    ///
+    /// There is only one version so far, so this validates the version and
+    /// stamps it; a future version 2 adds its `version < 2` step here.
+    pub fn migrate(mut value: serde_json::Value) -> crate::error::Result<serde_json::Value> {
+        use crate::error::AuditError;
+        use serde_json::Value;
+
+        let report = value
+            .as_object_mut()
+            .ok_or_else(|| AuditError::other("Not an audit report: expected a JSON object"))?;
+        let version = report
+            .get("schema_version")
+            .map(|v| {
+                v.as_u64()
+                    .ok_or_else(|| AuditError::other("Invalid audit report schema_version"))
+            })
+            .transpose()?
+            .unwrap_or(1);
+        if version > u64::from(AUDIT_REPORT_SCHEMA_VERSION) {
+            return Err(AuditError::other(format!(
+                "Audit report uses schema version {}, this build reads up to version {}",
+                version, AUDIT_REPORT_SCHEMA_VERSION
+            )));
+        }
+
+        report.insert(
+            "schema_version".into(),
+            Value::from(AUDIT_REPORT_SCHEMA_VERSION),
+        );
+        Ok(value)
+    }
+
+    /// Parse a saved report of any supported schema version
+    pub fn from_json(json: &str) -> crate::error::Result<Self> {
+        let value = Self::migrate(serde_json::from_str(json)?)?;
+        Ok(serde_json::from_value(value)?)
+    }
+
+    /// Read and parse a saved report file
+    pub fn load(path: impl AsRef<std::path::Path>) -> crate::error::Result<Self> {
+        let path = path.as_ref();
+        let json = std::fs::read_to_string(path).map_err(|e| {
+            crate::error::AuditError::from(e).context(format!("reading {}", path.display()))
+        })?;
+        Self::from_json(&json)
+    }
+}
+
 /// Audit summary
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, JsonSchema)]
 pub struct AuditSummary {
     /// Total files analyzed
     pub total_files: usize,
@@ -523,3 +799,143 @@ pub struct AuditSummary {
     /// Code coverage percentage
     pub code_coverage: Option<f64>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::AuditError;
+
+    /// A report as saved before schema versioning: no `schema_version`,
+    /// tasks without `depends_on`, and map keys in `HashMap` order
+    const UNVERSIONED_REPORT: &str = r#"{
+        "id": "3f1c",
+        "repository": "/src/app",
+        "branch": "main",
+        "created_at": "2025-06-01T12:00:00Z",
+        "system_map": {
+            "total_files": 1,
+            "files_by_category": { "other": 1 },
+            "lines_by_category": { "other": 40 },
+            "dependencies": [],
+            "mermaid_diagram": null
+        },
+        "files": [{
+            "path": "src/main.rs",
+            "category": "other",
+            "priority": "high",
+            "lines": 40,
+            "doc_blocks": 0,
+            "security_rating": "C",
+            "issues": [{
+                "severity": "high",
+                "category": "security",
+                "file": "src/main.rs",
+                "line": 12,
+                "message": "Unsafe code at line 12",
+                "suggestion": null
+            }],
+            "llm_analysis": null,
+            "tags": [{
+                "tag_type": "security",
+                "file": "src/main.rs",
+                "line": 11,
+                "value": "raw pointer",
+                "context": null
+            }]
+        }],
+        "tasks": [{
+            "id": "TASK-1A2B3C4D",
+            "title": "Review unsafe block",
+            "description": "Unsafe code at line 12",
+            "file": "src/main.rs",
+            "line": 12,
+            "priority": "high",
+            "category": "other",
+            "created_at": "2025-06-01T12:00:00Z",
+            "tags": ["security"]
+        }],
+        "issues_by_severity": { "low": 0, "high": 1 },
+        "summary": {
+            "total_files": 1,
+            "total_lines": 40,
+            "total_issues": 1,
+            "total_tasks": 1,
+            "critical_files": 0,
+            "avg_security_rating": 70.0,
+            "total_tests": null,
+            "test_pass_rate": null
+        }
+    }"#;
+
+    #[test]
+    fn test_unversioned_report_loads() {
+        let report = AuditReport::from_json(UNVERSIONED_REPORT).unwrap();
+        assert_eq!(report.schema_version, AUDIT_REPORT_SCHEMA_VERSION);
+        assert_eq!(report.created_at.to_rfc3339(), "2025-06-01T12:00:00+00:00");
+        assert_eq!(
+            report.issues_by_severity.get(&IssueSeverity::High),
+            Some(&1)
+        );
+        assert!(report.tasks[0].depends_on.is_empty());
+        assert_eq!(report.files[0].issues[0].file, PathBuf::from("src/main.rs"));
+
+        // Saving and loading again keeps the current version
+        let json = serde_json::to_string(&report).unwrap();
+        let reloaded = AuditReport::from_json(&json).unwrap();
+        assert_eq!(reloaded.created_at, report.created_at);
+
+        let mut newer: serde_json::Value = serde_json::from_str(&json).unwrap();
+        newer["schema_version"] = serde_json::Value::from(AUDIT_REPORT_SCHEMA_VERSION + 1);
+        assert!(AuditReport::from_json(&newer.to_string()).is_err());
+    }
+
+    #[test]
+    fn test_audit_request_builder() {
+        let request = AuditRequest::builder()
+            .repository(" ./repo ")
+            .branch("dev")
+            .enable_llm(true)
+            .focus(["Security", "api", "security"])
+            .build()
+            .unwrap();
+        assert_eq!(request.repository, "./repo");
+        assert_eq!(request.branch.as_deref(), Some("dev"));
+        assert!(request.enable_llm && !request.include_tests);
+        assert_eq!(request.focus, vec!["security", "api"]);
+    }
+
+    #[test]
+    fn test_audit_request_builder_validation() {
+        let err = AuditRequest::builder().build().unwrap_err();
+        assert!(matches!(err, AuditError::InvalidRequest(ref m) if m.contains("repository")));
+
+        let err = AuditRequest::builder()
+            .repository("  ")
+            .build()
+            .unwrap_err();
+        assert!(matches!(err, AuditError::InvalidRequest(_)));
+
+        let err = AuditRequest::builder()
+            .repository(".")
+            .branch("")
+            .build()
+            .unwrap_err();
+        assert!(matches!(err, AuditError::InvalidRequest(ref m) if m.contains("branch")));
+
+        let err = AuditRequest::builder()
+            .repository(".")
+            .focus(["security", "trading"])
+            .build()
+            .unwrap_err();
+        assert!(matches!(err, AuditError::InvalidRequest(ref m) if m.contains("'trading'")));
+
+        // Configured keywords are accepted
+        let request = AuditRequest::builder()
+            .repository(".")
+            .focus(["trading"])
+            .allow_focus_keywords(["Trading"])
+            .build()
+            .unwrap();
+        assert_eq!(request.focus, vec!["trading"]);
+    }
+}
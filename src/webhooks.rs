@@ -135,7 +135,14 @@ pub enum WebhookEvent {
     },
 
     /// Indexing job failed
-    JobFailed { job_id: String, error: String },
+    JobFailed {
+        job_id: String,
+        error: String,
+        document_count: usize,
+        success_count: usize,
+        failed_count: usize,
+        duration_ms: u64,
+    },
 
     /// Document was deleted
     DocumentDeleted { document_id: i64 },
@@ -318,6 +325,17 @@ impl WebhookManager {
         }
     }
 
+    /// Create a manager with endpoints already registered
+    pub fn with_endpoints(config: WebhookConfig, endpoints: Vec<WebhookEndpoint>) -> Self {
+        let manager = Self::new(config);
+        let endpoints = endpoints.into_iter().map(|e| (e.id.clone(), e)).collect();
+        *manager
+            .endpoints
+            .try_write()
+            .expect("new manager has no other lock holders") = endpoints;
+        manager
+    }
+
     /// Register a new webhook endpoint
     pub async fn register(
         &self,
@@ -525,6 +543,8 @@ impl WebhookManager {
 
     /// Mark delivery as failed
     async fn mark_delivery_failed(&self, id: &str, error: String) {
+        tracing::warn!(delivery_id = %id, error = %error, "Webhook delivery failed after retries");
+
         let mut deliveries = self.deliveries.write().await;
         if let Some(delivery) = deliveries.iter_mut().find(|d| d.id == id) {
             delivery.status = DeliveryStatus::Failed;
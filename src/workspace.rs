@@ -0,0 +1,246 @@
+//! Cargo workspace members
+//!
+//! Reads `[workspace] members` (and `exclude`) from a root `Cargo.toml` so a
+//! multi-crate repository can be analyzed one member at a time: resolve the
+//! member with [`find_member`] and point [`Scanner`](crate::scanner::Scanner),
+//! [`RepoAnalyzer`](crate::repo_analysis::RepoAnalyzer) or
+//! [`EnhancedScanner`](crate::enhanced_scanner::EnhancedScanner) at its path.
+//!
+//! Member entries may use glob wildcards (`*`, `?`, `[a-z]`) within a path
+//! component, as in `crates/*`; only matching directories that contain a
+//! `Cargo.toml` are members.
+
+use crate::error::{AuditError, Result};
+use globset::Glob;
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// A crate in a Cargo workspace
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct WorkspaceMember {
+    /// Package name, or the directory name if the manifest has none
+    pub name: String,
+    /// Crate directory
+    pub path: PathBuf,
+}
+
+/// Members of the workspace rooted at `root`, sorted by path. A root
+/// manifest with its own `[package]` counts as a member too.
+pub fn workspace_members(root: &Path) -> Result<Vec<WorkspaceMember>> {
+    let manifest_path = root.join("Cargo.toml");
+    let manifest = read_manifest(&manifest_path)?;
+    let workspace = manifest.get("workspace").ok_or_else(|| {
+        AuditError::other(format!(
+            "{} is not a Cargo workspace (no [workspace] table)",
+            manifest_path.display()
+        ))
+    })?;
+
+    let patterns = |key: &str| -> Vec<String> {
+        workspace
+            .get(key)
+            .and_then(|v| v.as_array())
+            .map(|entries| {
+                entries
+                    .iter()
+                    .filter_map(|e| e.as_str())
+                    .map(normalize)
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+    let excluded: Vec<PathBuf> = patterns("exclude").iter().map(|p| root.join(p)).collect();
+
+    let mut dirs = Vec::new();
+    if manifest.get("package").is_some() {
+        dirs.push(root.to_path_buf());
+    }
+    for pattern in patterns("members") {
+        expand(root, &pattern, &mut dirs)?;
+    }
+    dirs.retain(|dir| dir.join("Cargo.toml").is_file() && !excluded.contains(dir));
+    dirs.sort();
+    dirs.dedup();
+
+    dirs.into_iter()
+        .map(|path| {
+            let manifest = read_manifest(&path.join("Cargo.toml"))?;
+            let name = manifest
+                .get("package")
+                .and_then(|p| p.get("name"))
+                .and_then(|n| n.as_str())
+                .map(String::from)
+                .unwrap_or_else(|| dir_name(&path));
+            Ok(WorkspaceMember { name, path })
+        })
+        .collect()
+}
+
+/// The member of the workspace at `root` named `name`, matched by package
+/// name or directory name
+pub fn find_member(root: &Path, name: &str) -> Result<WorkspaceMember> {
+    let members = workspace_members(root)?;
+    if let Some(member) = members
+        .iter()
+        .find(|m| m.name == name || dir_name(&m.path) == name)
+    {
+        return Ok(member.clone());
+    }
+
+    let names: Vec<&str> = members.iter().map(|m| m.name.as_str()).collect();
+    Err(AuditError::other(format!(
+        "'{}' is not a member of the workspace at {} (members: {})",
+        name,
+        root.display(),
+        if names.is_empty() {
+            "none".to_string()
+        } else {
+            names.join(", ")
+        }
+    )))
+}
+
+fn read_manifest(path: &Path) -> Result<toml::Value> {
+    if !path.is_file() {
+        return Err(AuditError::FileNotFound(path.to_path_buf()));
+    }
+    let content = fs::read_to_string(path)?;
+    toml::from_str(&content).map_err(|e| AuditError::Parse {
+        file: path.to_path_buf(),
+        message: e.to_string(),
+    })
+}
+
+/// `./crates/*/` → `crates/*`
+fn normalize(pattern: &str) -> String {
+    pattern
+        .trim_start_matches("./")
+        .trim_end_matches('/')
+        .to_string()
+}
+
+fn dir_name(path: &Path) -> String {
+    path.file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+/// Push every directory under `base` matching `pattern`, one path component
+/// at a time
+fn expand(base: &Path, pattern: &str, out: &mut Vec<PathBuf>) -> Result<()> {
+    let (component, rest) = match pattern.split_once('/') {
+        Some((component, rest)) => (component, Some(rest)),
+        None => (pattern, None),
+    };
+
+    let matches: Vec<PathBuf> = if component.contains(['*', '?', '[']) {
+        let glob = Glob::new(component)
+            .map_err(|e| {
+                AuditError::other(format!(
+                    "invalid workspace member pattern '{}': {}",
+                    pattern, e
+                ))
+            })?
+            .compile_matcher();
+        let Ok(entries) = fs::read_dir(base) else {
+            return Ok(());
+        };
+        entries
+            .flatten()
+            .map(|e| e.path())
+            .filter(|p| p.is_dir() && glob.is_match(dir_name(p)))
+            .collect()
+    } else {
+        vec![base.join(component)]
+    };
+
+    for path in matches {
+        match rest {
+            Some(rest) => expand(&path, rest, out)?,
+            None => out.push(path),
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write_crate(dir: &Path, name: &str) {
+        fs::create_dir_all(dir).unwrap();
+        fs::write(
+            dir.join("Cargo.toml"),
+            format!("[package]\nname = \"{}\"\nversion = \"0.1.0\"\n", name),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_members_with_globs_and_excludes() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        fs::write(
+            root.join("Cargo.toml"),
+            "[workspace]\nmembers = [\"app\", \"./crates/*/\"]\nexclude = [\"crates/legacy\"]\n",
+        )
+        .unwrap();
+        write_crate(&root.join("app"), "my-app");
+        write_crate(&root.join("crates/core"), "my-core");
+        write_crate(&root.join("crates/util"), "my-util");
+        write_crate(&root.join("crates/legacy"), "my-legacy");
+        fs::create_dir_all(root.join("crates/docs")).unwrap();
+
+        let members = workspace_members(root).unwrap();
+        let names: Vec<&str> = members.iter().map(|m| m.name.as_str()).collect();
+        assert_eq!(names, vec!["my-app", "my-core", "my-util"]);
+
+        // By package name or by directory name
+        assert_eq!(
+            find_member(root, "my-core").unwrap().path,
+            root.join("crates/core")
+        );
+        assert_eq!(find_member(root, "util").unwrap().name, "my-util");
+
+        let err = find_member(root, "my-legacy").unwrap_err().to_string();
+        assert!(err.contains("'my-legacy' is not a member"));
+        assert!(err.contains("my-app, my-core, my-util"));
+    }
+
+    #[test]
+    fn test_member_pattern_classes() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        fs::write(
+            root.join("Cargo.toml"),
+            "[workspace]\nmembers = [\"crates/core-[ab]\", \"tools/?x\"]\n",
+        )
+        .unwrap();
+        write_crate(&root.join("crates/core-a"), "core-a");
+        write_crate(&root.join("crates/core-c"), "core-c");
+        write_crate(&root.join("tools/fx"), "fx");
+        write_crate(&root.join("tools/fmt"), "fmt");
+
+        let members = workspace_members(root).unwrap();
+        let names: Vec<&str> = members.iter().map(|m| m.name.as_str()).collect();
+        assert_eq!(names, vec!["core-a", "fx"]);
+
+        fs::write(
+            root.join("Cargo.toml"),
+            "[workspace]\nmembers = [\"crates/core-[a\"]\n",
+        )
+        .unwrap();
+        let err = workspace_members(root).unwrap_err().to_string();
+        assert!(err.contains("invalid workspace member pattern"), "{}", err);
+    }
+
+    #[test]
+    fn test_not_a_workspace() {
+        let temp = TempDir::new().unwrap();
+        write_crate(temp.path(), "single");
+        let err = workspace_members(temp.path()).unwrap_err().to_string();
+        assert!(err.contains("not a Cargo workspace"));
+    }
+}
@@ -350,6 +350,9 @@ async fn test_search_documents() {
         query: "rust programming".to_string(),
         limit: 10,
         search_type: SearchType::Hybrid,
+        alpha: None,
+        rerank: false,
+        highlight: true,
         filters: Default::default(),
     };
 
@@ -424,7 +427,7 @@ async fn test_auth_invalid_key() {
         .await
         .expect("Failed to send");
 
-    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
 }
 
 #[tokio::test]
@@ -0,0 +1,275 @@
+//! Golden tests for report formatting
+//!
+//! Renders fixed reports as Markdown, CSV and JSON and compares them with
+//! the files in `tests/golden/`, so any change to a report's output shows
+//! up as a diff in review. After an intentional formatting change, rewrite
+//! the goldens and check the diff before committing:
+//!
+//! ```text
+//! UPDATE_GOLDEN=1 cargo test --test report_golden
+//! ```
+
+use chrono::{DateTime, TimeZone, Utc};
+use rustassistant::code_review::{
+    CodeReview, FileReview, IssueSeverity as ReviewSeverity, ReviewIssue, ReviewStats,
+};
+use rustassistant::tasks::TaskGenerator;
+use rustassistant::types::{
+    AuditReport, AuditSummary, AuditTag, AuditTagType, Category, FileAnalysis, FilePriority, Issue,
+    IssueCategory, IssueSeverity, SecurityRating, SystemMap, Task, TaskPriority,
+};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+const UPDATE_HINT: &str = "UPDATE_GOLDEN=1 cargo test --test report_golden";
+
+/// Compare `actual` with `tests/golden/<name>`, or overwrite the golden
+/// when `UPDATE_GOLDEN` is set
+fn assert_golden(name: &str, actual: &str) {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/golden")
+        .join(name);
+    if std::env::var_os("UPDATE_GOLDEN").is_some() {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, actual).unwrap();
+        return;
+    }
+
+    let expected = std::fs::read_to_string(&path).unwrap_or_else(|_| {
+        panic!(
+            "missing golden file {}; create it with `{}`",
+            path.display(),
+            UPDATE_HINT
+        )
+    });
+    assert!(
+        actual == expected,
+        "{} no longer matches its golden file.\n\
+         If the change is intended, run `{}` and review the diff.\n\n\
+         --- expected\n{}\n--- actual\n{}",
+        name,
+        UPDATE_HINT,
+        expected,
+        actual
+    );
+}
+
+fn fixed_time() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2025, 6, 1, 12, 0, 0).unwrap()
+}
+
+fn task(id: &str, title: &str, file: &str, line: Option<usize>, priority: TaskPriority) -> Task {
+    let mut task = Task::new(
+        title,
+        format!("Details for {}", title),
+        PathBuf::from(file),
+        line,
+        priority,
+        Category::Audit,
+    )
+    .with_tag("security");
+    task.id = id.to_string();
+    task.created_at = fixed_time();
+    task
+}
+
+fn tags() -> Vec<AuditTag> {
+    let tag = |tag_type, file: &str, line, value: &str| AuditTag {
+        tag_type,
+        file: PathBuf::from("/repo").join(file),
+        line,
+        value: value.to_string(),
+        context: None,
+    };
+    vec![
+        tag(AuditTagType::Todo, "src/lib.rs", 3, "Handle errors"),
+        tag(
+            AuditTagType::Security,
+            "src/auth.rs",
+            42,
+            "Check \"token\" expiry, then rotate",
+        ),
+        tag(AuditTagType::Freeze, "src/wire.rs", 1, ""),
+    ]
+}
+
+fn audit_report() -> AuditReport {
+    let issue = |severity, line, category, message: &str| Issue {
+        severity,
+        category,
+        file: PathBuf::from("src/auth.rs"),
+        line,
+        message: message.to_string(),
+        suggestion: Some("Validate the input first".to_string()),
+    };
+    let tags = tags();
+
+    AuditReport {
+        schema_version: rustassistant::types::AUDIT_REPORT_SCHEMA_VERSION,
+        id: "report-1".to_string(),
+        repository: "/repo".to_string(),
+        branch: "main".to_string(),
+        created_at: fixed_time(),
+        system_map: SystemMap {
+            total_files: 2,
+            files_by_category: BTreeMap::from([(Category::Config, 1), (Category::Audit, 1)]),
+            lines_by_category: BTreeMap::from([(Category::Config, 30), (Category::Audit, 120)]),
+            dependencies: Vec::new(),
+            mermaid_diagram: None,
+        },
+        files: vec![
+            FileAnalysis {
+                path: PathBuf::from("Cargo.toml"),
+                category: Category::Config,
+                priority: FilePriority::Medium,
+                lines: 30,
+                doc_blocks: 0,
+                security_rating: None,
+                issues: Vec::new(),
+                llm_analysis: None,
+                tags: Vec::new(),
+            },
+            FileAnalysis {
+                path: PathBuf::from("src/auth.rs"),
+                category: Category::Audit,
+                priority: FilePriority::High,
+                lines: 120,
+                doc_blocks: 4,
+                security_rating: Some(SecurityRating::B),
+                issues: vec![
+                    issue(
+                        IssueSeverity::High,
+                        10,
+                        IssueCategory::Security,
+                        "Token compared with ==",
+                    ),
+                    issue(
+                        IssueSeverity::Medium,
+                        57,
+                        IssueCategory::CodeQuality,
+                        "unwrap() on user input",
+                    ),
+                ],
+                llm_analysis: None,
+                tags: vec![tags[1].clone()],
+            },
+        ],
+        tasks: vec![task(
+            "TASK-0001",
+            "Security: check token expiry",
+            "src/auth.rs",
+            Some(42),
+            TaskPriority::High,
+        )],
+        issues_by_severity: BTreeMap::from([(IssueSeverity::Medium, 1), (IssueSeverity::High, 1)]),
+        summary: AuditSummary {
+            total_files: 2,
+            total_lines: 150,
+            total_issues: 2,
+            total_tasks: 1,
+            critical_files: 0,
+            avg_security_rating: Some(0.75),
+            ..Default::default()
+        },
+        test_results: None,
+        context_bundle: None,
+    }
+}
+
+fn code_review() -> CodeReview {
+    let issue = |severity, line, description: &str| ReviewIssue {
+        severity,
+        description: description.to_string(),
+        line: Some(line),
+        end_line: None,
+        suggestion: None,
+    };
+    CodeReview {
+        repo_path: "/repo".to_string(),
+        base_branch: Some("main".to_string()),
+        files: vec![
+            FileReview {
+                path: "src/auth.rs".to_string(),
+                score: 62.5,
+                security_score: 48.0,
+                issues: vec![
+                    issue(ReviewSeverity::Critical, 10, "Token compared with =="),
+                    issue(ReviewSeverity::Medium, 57, "unwrap() on user input"),
+                ],
+                suggestions: vec!["Use a constant-time comparison".to_string()],
+                lines_changed: 24,
+            },
+            FileReview {
+                path: "src/lib.rs".to_string(),
+                score: 91.0,
+                security_score: 100.0,
+                issues: Vec::new(),
+                suggestions: Vec::new(),
+                lines_changed: 3,
+            },
+        ],
+        stats: ReviewStats {
+            total_files: 2,
+            files_with_issues: 1,
+            total_issues: 2,
+            critical_issues: 1,
+            high_issues: 0,
+            medium_issues: 1,
+            low_issues: 0,
+            avg_quality: 76.75,
+            avg_security: 74.0,
+            total_lines_changed: 27,
+        },
+        summary: "One file needs attention before merging.\n".to_string(),
+        timestamp: fixed_time(),
+    }
+}
+
+#[test]
+fn audit_report_json() {
+    let json = serde_json::to_string_pretty(&audit_report()).unwrap();
+    assert_golden("audit_report.json", &format!("{}\n", json));
+}
+
+#[test]
+fn code_review_markdown() {
+    assert_golden("code_review.md", &code_review().format_markdown());
+}
+
+#[test]
+fn code_review_json() {
+    let json = serde_json::to_string_pretty(&code_review()).unwrap();
+    assert_golden("code_review.json", &format!("{}\n", json));
+}
+
+#[test]
+fn tags_csv() {
+    let mut csv = format!("{}\n", AuditTag::CSV_HEADER);
+    for tag in tags() {
+        csv.push_str(&tag.to_csv_row(Path::new("/repo")));
+        csv.push('\n');
+    }
+    assert_golden("tags.csv", &csv);
+}
+
+#[test]
+fn tasks_csv_and_markdown_board() {
+    let generator = TaskGenerator::from_tasks(vec![
+        task(
+            "TASK-0001",
+            "Security: check token expiry",
+            "src/auth.rs",
+            Some(42),
+            TaskPriority::High,
+        ),
+        task(
+            "TASK-0002",
+            "TODO: handle errors, then retry",
+            "src/lib.rs",
+            None,
+            TaskPriority::Low,
+        ),
+    ]);
+    assert_golden("tasks.csv", &generator.to_csv().unwrap());
+    assert_golden("tasks.md", &generator.to_markdown_board());
+}